@@ -0,0 +1,70 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+use std::sync::atomic::{AtomicBool, Ordering}; // Flag pembatalan yang aman antar thread
+use std::sync::Arc; // Berbagi flag antara pemanggil dan pipeline
+use std::time::{Duration, Instant}; // Untuk batas waktu (timeout)
+
+use crate::error::Error; // Error pembatalan yang bisa dikenali pemanggil library
+
+/// Token pembatalan untuk operasi yang berjalan lama
+///
+/// Token bisa di-clone dan dibagikan ke thread lain (misalnya handler Ctrl-C,
+/// server, atau GUI). Pipeline memeriksa token di titik-titik aman dan berhenti
+/// dengan error tanpa meninggalkan file output yang setengah jadi.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>, // Diset true saat cancel() dipanggil
+    deadline: Option<Instant>,  // Batas waktu opsional
+}
+
+impl CancellationToken {
+    /// Buat token baru tanpa batas waktu
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Buat token yang otomatis dianggap batal setelah durasi tertentu
+    pub fn with_timeout(timeout: Duration) -> CancellationToken {
+        CancellationToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
+    /// Token turunan dengan batas waktu sendiri (tidak melewati batas waktu
+    /// token ini) yang ikut batal jika token ini dibatalkan; dipakai untuk
+    /// batas waktu per dokumen di `sign-batch`
+    pub fn child(&self, timeout: Duration) -> CancellationToken {
+        let deadline = Instant::now() + timeout;
+        CancellationToken {
+            cancelled: Arc::clone(&self.cancelled),
+            deadline: Some(self.deadline.map_or(deadline, |parent| parent.min(deadline))),
+        }
+    }
+
+    /// Batalkan operasi yang memakai token ini (atau clone-nya)
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Sisa waktu sampai batas waktu (None jika tanpa batas waktu)
+    /// Dipakai untuk membatasi operasi yang tidak bisa diperiksa di tengah jalan,
+    /// misalnya request HTTP ke TSA
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Periksa token: error jika sudah dibatalkan atau melewati batas waktu
+    /// (Error::Cancelled / Error::TimedOut di dalam anyhow::Error)
+    pub fn check(&self) -> Result<()> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(Error::Cancelled.into());
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(Error::TimedOut.into());
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,34 @@
 // Import macro-macro dari clap untuk parsing command-line arguments
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf; // Path file sign dan opsi signature
+
+use crate::paths::{self, PathArg}; // Ekspansi ~ dan pemeriksaan ekstensi
+
+use pdfsign::crypto::cert::KeyUsageFlag; // Flag key usage sertifikat
+use pdfsign::crypto::cms::EmbedChain; // Sertifikat yang disisipkan di signature
+use pdfsign::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use pdfsign::crypto::ecc::Curve; // Kurva kunci generate-key
+use pdfsign::crypto::keyfile::KeyFormat; // Format file kunci
+use pdfsign::crypto::policy::{WeakAction, WeakAlgorithm}; // Deny-list algoritma lemah
+use pdfsign::crypto::provider::Backend; // Backend kriptografi (--crypto-backend)
+use pdfsign::crypto::rsa::RsaPadding; // Padding signature RSA
+use pdfsign::pdf::appearance::{AppearanceLanguage, TextColor}; // Bahasa dan warna teks tampilan signature
+use pdfsign::pdf::budget::Growth; // Batas pertumbuhan file --warn-growth
+#[cfg(feature = "verify")]
+use pdfsign::crypto::revocation::RevocationMode; // Pemeriksaan OCSP/CRL verify --check-revocation
+#[cfg(feature = "verify")]
+use pdfsign::pdf::archive::ReportFormat; // Format laporan verify --recursive
+#[cfg(feature = "verify")]
+use pdfsign::pdf::inspect::CertFormat; // Format file sertifikat inspect --dump-certs
+#[cfg(feature = "verify")]
+use pdfsign::pdf::checks::SignerPattern; // Penandatangan yang diharapkan verify --expected-signer
+use pdfsign::pdf::form::NeedAppearancesPolicy; // Kebijakan penanganan /NeedAppearances
+use pdfsign::pdf::options::{AnnotationFlags, Position, Rect}; // Posisi signature yang terlihat
+use pdfsign::pdf::mdp::{CertifyLevel, FieldLock}; // Certification (DocMDP) dan penguncian field (FieldMDP)
+use pdfsign::pdf::optimize::IfSigned; // Perlakuan dokumen bertanda tangan di optimize
+use pdfsign::pdf::pades::{PadesLevel, RevocationPreference}; // Level PAdES baseline dan sumber OCSP/CRL
+use pdfsign::pdf::placement::PageSize; // Ukuran kertas gen-testdoc
+use pdfsign::scratch::TempStrategy; // Strategi file sementara
 
 /// Struktur utama untuk parsing command-line arguments
 /// Parser trait akan men-generate kode parsing otomatis
@@ -8,44 +37,1441 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)] // Sub-command untuk menjalankan perintah berbeda
     pub command: Commands,
+
+    /// Format hasil sign, verify, inspect, dan pages info: text, atau json (satu object
+    /// JSON di stdout, termasuk untuk error; lihat "Exit codes" di README)
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
+    /// Tampilkan detail setiap tahap di stderr: -v untuk offset placeholder,
+    /// ByteRange, ukuran CMS, dan round-trip TSA; -vv juga request HTTP
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Hanya tampilkan error: tanpa pesan status, peringatan, dan progress bar
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Backend kriptografi untuk digest dan signing dengan file kunci: rustcrypto
+    /// (bawaan) atau ring (perlu fitur ring)
+    #[arg(long, value_enum, global = true, env = "PDFSIGN_CRYPTO_BACKEND", default_value_t = Backend::Rustcrypto)]
+    pub crypto_backend: Backend,
+
+    /// Mode deterministik untuk snapshot test dan laporan bug: nama field baru,
+    /// waktu signing default, dan /ID gen-testdoc diturunkan dari SEED sehingga
+    /// output sama persis di setiap run. Kunci, enkripsi, dan serial sertifikat
+    /// tetap memakai generator acak OS. Hanya lewat flag ini (tanpa environment
+    /// variable); ditolak untuk serve dan watch
+    #[arg(long, global = true, hide = true, value_name = "SEED")]
+    pub deterministic_seed: Option<String>,
+}
+
+/// Format hasil perintah (--output-format)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Teks untuk dibaca user
+    Text,
+    /// Satu object JSON di stdout (untuk pipeline CI)
+    Json,
 }
 
 /// Enum untuk menyimpan berbagai command yang tersedia
+/// Enum ini hanya dibuat sekali saat parsing, jadi perbedaan ukuran variant tidak masalah
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Command 1: generate-key
-    /// Fungsi: Membuat pasangan kunci ECC P-256 (publik & privat)
-    GenerateKey,
+    /// Fungsi: Membuat pasangan kunci ECC (P-256/P-384/P-521/Ed25519, publik & privat)
+    GenerateKey {
+        /// Format file kunci: raw (format lama), pem atau der (PKCS#8 / SubjectPublicKeyInfo)
+        #[arg(long, value_enum, default_value_t = KeyFormat::Raw)]
+        format: KeyFormat,
+
+        /// Passphrase untuk mengenkripsi kunci privat PKCS#8 (hanya pem/der)
+        #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+
+        /// Kurva kunci: p256, p384, p521, atau ed25519 (ed25519 hanya pem/der)
+        #[arg(long, value_enum, default_value_t = Curve::P256)]
+        curve: Curve,
+    },
+
+    /// Command 2: generate-cert
+    /// Fungsi: Membuat sertifikat X.509 self-signed dari kunci privat (ECDSA, Ed25519, atau RSA)
+    GenerateCert {
+        /// Path file kunci privat (private.key)
+        #[arg(long, default_value = "private.key")]
+        key: String,
+
+        /// Path file sertifikat DER; file PEM ditulis di sebelahnya
+        /// (default: certificate.der di direktori yang sama dengan kunci,
+        /// tempat `sign` mencarinya)
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Common name (CN) subject, biasanya nama penandatangan
+        #[arg(long)]
+        cn: String,
+
+        /// Organisasi (O) subject
+        #[arg(long)]
+        org: Option<String>,
+
+        /// Kode negara 2 huruf (C) subject, misalnya ID
+        #[arg(long)]
+        country: Option<String>,
+
+        /// Masa berlaku sertifikat dalam hari
+        #[arg(long, default_value_t = 365)]
+        days: u32,
+
+        /// Flag key usage, dipisah koma
+        #[arg(long, value_enum, value_delimiter = ',', default_values_t = [KeyUsageFlag::DigitalSignature, KeyUsageFlag::NonRepudiation])]
+        key_usage: Vec<KeyUsageFlag>,
+
+        /// Passphrase kunci privat terenkripsi (jika tidak ada, ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+    },
+
+    /// Command 3: generate-csr
+    /// Fungsi: Membuat certificate signing request (PKCS#10, PEM) dari kunci privat
+    /// untuk diajukan ke CA
+    GenerateCsr {
+        /// Path file kunci privat (private.key)
+        #[arg(long, default_value = "private.key")]
+        key: String,
+
+        /// Subject dalam format RFC 4514, misalnya "CN=Jane Doe,O=Acme,C=ID"
+        #[arg(long)]
+        subject: String,
+
+        /// Path file CSR (PEM) (default: certificate.csr di direktori yang sama dengan kunci)
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Passphrase kunci privat terenkripsi (jika tidak ada, ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+    },
     
-    /// Command 2: sign
+    /// Command 4: sign
     /// Fungsi: Menandatangani file PDF dengan ECDSA
     Sign {
+        /// Path file PDF yang akan ditandatangani ("-" untuk stdin)
+        #[arg(long, value_parser = PathArg::any())]
+        input: PathBuf,
+
+        /// Path file PDF output hasil penandatanganan ("-" untuk stdout;
+        /// pesan status ditulis ke stderr)
+        #[arg(long, value_parser = PathArg::any(), required_unless_present_any = ["dry_run", "detached"])]
+        output: Option<PathBuf>,
+
+        /// Tulis CMS SignedData atas seluruh file input ke file .p7s terpisah;
+        /// tanpa --output, PDF tidak diubah sama sekali
+        #[arg(long, value_name = "P7S", value_parser = PathArg::any(), conflicts_with = "dry_run")]
+        detached: Option<PathBuf>,
+
+        /// Jalankan semua pemeriksaan (dokumen, kunci dan sertifikat, halaman,
+        /// ukuran placeholder) dan tampilkan apa yang akan ditulis, tanpa
+        /// menandatangani dan tanpa menulis output
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Kunci, tampilan, dan kebijakan signature
+        #[command(flatten)]
+        args: SignArgs,
+    },
+    /// Command 5: verify
+    /// Fungsi: Memverifikasi semua signature di dalam file PDF
+    Verify {
+        /// Path file PDF yang akan diverifikasi
+        #[arg(long, required_unless_present = "recursive")]
+        input: Option<String>,
+
+        /// Verifikasi semua PDF di direktori ini (termasuk subdirektori) dan
+        /// tulis laporan gabungan, sebagai pengganti --input
+        #[arg(long, conflicts_with = "input")]
+        recursive: Option<String>,
+
+        /// Verifikasi signature detached (.p7s dari `sign --detached`) atas
+        /// seluruh isi --input, sebagai pengganti signature di dalam PDF
+        #[arg(long, value_name = "P7S", requires = "input")]
+        detached: Option<String>,
+
+        /// File tujuan laporan --recursive; jika tidak diisi, laporan ditulis ke stdout
+        #[arg(long, requires = "recursive")]
+        report: Option<String>,
+
+        /// Format laporan --recursive: csv atau json
+        /// (default: json jika --report berakhiran .json, selain itu csv)
+        #[cfg(feature = "verify")]
+        #[arg(long, value_enum, requires = "recursive")]
+        format: Option<ReportFormat>,
+
+        /// Timestamp yang sertifikat TSA-nya habis dalam jumlah hari ini
+        /// dihitung sebagai akan habis di laporan --recursive
+        #[arg(long, default_value_t = 90, requires = "recursive")]
+        expiring_within: u32,
+
+        /// Path file kunci publik (public.key); jika tidak diisi,
+        /// dipakai sertifikat yang tertanam di signature
+        #[arg(long)]
+        public_key: Option<String>,
+
+        /// Bahasa pesan temuan: en (default) atau id
+        #[arg(long, value_enum, default_value_t = AppearanceLanguage::En)]
+        lang: AppearanceLanguage,
+
+        /// Algoritma lemah yang tidak dianggap pelanggaran, dipisah koma:
+        /// md5, sha1, small-rsa (RSA < 2048 bit), p192
+        #[arg(long, value_enum, value_delimiter = ',')]
+        allow_weak: Vec<WeakAlgorithm>,
+
+        /// Hasil jika signature memakai algoritma lemah yang ditolak:
+        /// fail (default, signature tidak valid) atau warn (hanya peringatan)
+        #[arg(long, value_enum, default_value_t = WeakAction::Fail)]
+        on_weak: WeakAction,
+        /// Sumber trust anchor, dipisah koma atau diulang: system (root CA
+        /// sistem operasi), file:<path> (bundle PEM/DER), store:<dir>
+        /// (direktori berisi .pem, .crt, .cer, .der), atau nama daftar (bundle
+        /// <nama>.pem di PDFSIGN_TRUST_DIR, default ~/.pdfsign/trust, misalnya
+        /// aatl atau eutl); laporan menyebut sumber mana yang menjadi ujung
+        /// rantai setiap signature. Tanpa --trust rantai tidak diperiksa dan
+        /// setiap signature mendapat peringatan
+        #[arg(long, value_delimiter = ',', value_name = "SOURCE")]
+        trust: Vec<String>,
+
+        /// Lama: direktori daftar trust bernama (sekarang PDFSIGN_TRUST_DIR)
+        #[arg(long, env = "PDFSIGN_TRUST_DIR", hide = true)]
+        trust_dir: Option<String>,
+
+        /// Lama: sama dengan --trust store:<DIR>
+        #[arg(long, value_name = "DIR", hide = true)]
+        trust_store: Vec<String>,
+
+        /// Anggap signature yang rantainya tidak diperiksa karena tidak ada
+        /// --trust sebagai untrusted (exit code 5), bukan hanya peringatan
+        #[arg(long)]
+        require_trust: bool,
+
+        /// Periksa status revocation (OCSP/CRL) setiap sertifikat di rantai:
+        /// online (default; tanya responder OCSP dan unduh CRL dari URL di
+        /// sertifikat) atau offline (hanya respons yang tertanam di /DSS)
+        #[cfg(feature = "verify")]
+        #[arg(long, value_enum, value_name = "MODE", num_args = 0..=1, default_missing_value = "online", conflicts_with = "recursive")]
+        check_revocation: Option<RevocationMode>,
+
+        /// Penandatangan yang diharapkan; signature dari orang lain tidak valid.
+        /// Subject DN (`CN=PT Pemasok*,O=PT Pemasok`, `*` boleh di nilai),
+        /// email subjectAltName, atau fingerprint SHA-256/SHA-1 sertifikat;
+        /// boleh diulang (cocok dengan salah satu = diterima)
+        #[cfg(feature = "verify")]
+        #[arg(long, value_name = "PATTERN", conflicts_with_all = ["recursive", "detached"])]
+        expected_signer: Vec<SignerPattern>,
+
+        /// Verifikasi dokumen seperti pada revisi ini (1 = file asli, lalu setiap
+        /// incremental update): signature yang ditambahkan sesudahnya diabaikan
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with_all = ["recursive", "detached"])]
+        revision: Option<u32>,
+
+        /// Jumlah signature satu dokumen yang diverifikasi bersamaan (rantai,
+        /// revocation, timestamp); default: jumlah CPU, 1 = satu per satu
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Tolak dokumen yang melanggar spesifikasi PDF (nomor object ganda,
+        /// /Length stream yang salah) alih-alih memperbaikinya dengan peringatan
+        #[arg(long)]
+        strict_parse: bool,
+
+        /// Cetak hasil sebagai satu baris dengan format tetap untuk log CI dan
+        /// Git hook, misalnya `VALID 2/2 signatures, LTV: yes, certified:
+        /// form-filling`, sebagai pengganti laporan lengkap (juga dengan -q)
+        #[arg(long, conflicts_with = "recursive")]
+        summary_line: bool,
+
+        /// Tambahkan hasil verifikasi (waktu, pemeriksa, versi pdfsign, hash
+        /// dan status tiap dokumen) sebagai baris JSON ke file audit log ini
+        #[arg(long, value_name = "FILE")]
+        audit_log: Option<String>,
+
+        /// Nama pemeriksa di audit log (default: user sistem operasi)
+        #[arg(long, env = "PDFSIGN_AUDITOR")]
+        auditor: Option<String>,
+    },
+    /// Command 6: add-ltv
+    /// Fungsi: Menambahkan respons OCSP dan CRL untuk semua signature ke /DSS
+    /// (Long-Term Validation) lewat incremental update
+    AddLtv {
+        /// Path file PDF yang sudah ditandatangani
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// Sumber data revocation: ocsp (default, lalu CRL jika gagal), crl
+        /// (lalu OCSP), atau both (keduanya jika tersedia)
+        #[arg(long, value_enum)]
+        ltv_revocation: Option<RevocationPreference>,
+
+        /// Sertifikat yang data OCSP/CRL-nya tidak bisa diambil hanya diberi
+        /// peringatan (sertifikat yang dicabut tetap ditolak)
+        #[arg(long)]
+        ltv_best_effort: bool,
+
+        /// Path file konfigurasi (pdfsign.toml); dipakai bersama --profile
+        #[arg(long, value_parser = PathArg::any())]
+        config: Option<PathBuf>,
+
+        /// Nama profil di file konfigurasi yang menyediakan `ltv_revocation`,
+        /// `ltv_best_effort`, dan kebijakan pengulangan request
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Batas waktu seluruh request OCSP/CRL dalam detik
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Jumlah pengulangan request TSA/OCSP/CRL/KMS yang gagal sementara
+        /// (koneksi gagal, timeout, HTTP 429/5xx); default: 0
+        #[arg(long)]
+        retries: Option<u32>,
+
+        /// Jeda sebelum pengulangan pertama dalam milidetik; berlipat dua
+        /// setiap pengulangan, maksimal 30 detik (default: 500)
+        #[arg(long, value_name = "MS")]
+        retry_backoff: Option<u64>,
+
+        /// Persentase jeda yang diacak (0-100) agar klien tidak mengulang
+        /// serempak (default: 50)
+        #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+        retry_jitter: Option<u8>,
+    },
+    /// Command 7: session
+    /// Fungsi: Membuka kunci untuk sementara waktu (kiosk / komputer bersama)
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+    /// Command 8: prepare
+    /// Fungsi: Tahap 1 signing eksternal (HSM / API jarak jauh): siapkan signature
+    /// field dengan /Contents kosong dan tulis digest yang harus ditandatangani
+    Prepare {
         /// Path file PDF yang akan ditandatangani
         #[arg(long)]
         input: String,
 
-        /// Path file PDF output hasil penandatanganan
+        /// Path file PDF yang sudah disiapkan (input untuk `embed`)
         #[arg(long)]
         output: String,
 
-        /// Path file kunci privat (private.key)
+        /// File tujuan digest SHA-256 (32 bytes) yang harus ditandatangani
         #[arg(long)]
-        key: String,
+        digest_out: String,
+
+        /// File tujuan bytes persis yang ditandatangani (opsional)
+        #[arg(long)]
+        data_out: Option<String>,
+
+        /// Sertifikat penandatangan (DER, boleh diikuti rantainya). Jika diisi,
+        /// digest-nya adalah signed attributes dan cukup ditandatangani raw;
+        /// jika tidak, layanan eksternal harus membuat CMS sendiri
+        #[arg(long)]
+        certificate: Option<String>,
+
+        /// Ruang yang dicadangkan untuk signature di /Contents, dalam bytes
+        #[arg(long, default_value_t = pdfsign::pdf::external::EXTERNAL_SIGNATURE_RESERVE)]
+        reserve: usize,
+
+        /// Izinkan algoritma lemah dari deny-list pada sertifikat, dipisah koma:
+        /// md5, sha1, small-rsa (RSA < 2048 bit), p192
+        #[arg(long, value_enum, value_delimiter = ',')]
+        allow_weak: Vec<WeakAlgorithm>,
 
         /// Nama penandatangan (default: "pdfsign-cli")
-        #[arg(long, default_value = "pdfsign-cli")]
-        name: String,
+        #[arg(long)]
+        name: Option<String>,
 
         /// Alasan penandatanganan (default: "Digitally signed")
-        #[arg(long, default_value = "Digitally signed")]
-        reason: String,
+        #[arg(long)]
+        reason: Option<String>,
 
         /// Lokasi penandatanganan (default: kosong)
-        #[arg(long, default_value = "")]
-        location: String,
+        #[arg(long)]
+        location: Option<String>,
 
         /// Informasi kontak penandatangan (default: kosong)
-        #[arg(long, default_value = "")]
-        contact_info: String,
+        #[arg(long)]
+        contact_info: Option<String>,
+
+        /// Penanganan flag /NeedAppearances pada AcroForm (default: warn)
+        #[arg(long, value_enum)]
+        need_appearances: Option<NeedAppearancesPolicy>,
+
+        /// Tolak dokumen yang form-nya punya perubahan tertunda
+        #[arg(long)]
+        strict: bool,
+
+        /// Hapus data XFA dari form hybrid (XFA + AcroForm)
+        #[arg(long)]
+        drop_xfa: bool,
+
+        /// Tolak dokumen yang melanggar spesifikasi PDF alih-alih memperbaikinya
+        #[arg(long)]
+        strict_parse: bool,
+
+        /// Waktu penandatanganan RFC 3339 untuk /M (default: sekarang)
+        #[arg(long, value_name = "RFC3339", value_parser = chrono::DateTime::parse_from_rfc3339)]
+        signing_time: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+        /// Password dokumen PDF terenkripsi (user atau owner)
+        #[arg(long, env = "PDFSIGN_PDF_PASSWORD", hide_env_values = true)]
+        pdf_password: Option<String>,
+
+        /// Tulis dokumen terenkripsi tanpa enkripsi (butuh password owner;
+        /// signature yang sudah ada tidak lagi valid); tanpa ini hasilnya
+        /// tetap terenkripsi
+        #[arg(long)]
+        decrypt_output: bool,
+
+        /// Izinkan --certificate yang ditandai sebagai kredensial uji oleh
+        /// `generate-cert`
+        #[arg(long)]
+        allow_test_credentials: bool,
+    },
+    /// Command 9: embed
+    /// Fungsi: Tahap 2 signing eksternal: sisipkan signature (CMS atau raw)
+    /// ke /Contents PDF hasil `prepare`
+    Embed {
+        /// Path PDF hasil `prepare`
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (default: menimpa --input)
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Signature dari layanan eksternal: CMS SignedData (DER) atau
+        /// signature raw (ECDSA DER atau r||s, RSA)
+        #[arg(long)]
+        signature: String,
+
+        /// Sertifikat yang sama dengan saat `prepare` (wajib untuk signature raw)
+        #[arg(long)]
+        certificate: Option<String>,
+
+        /// Padding signature raw jika kuncinya RSA (default: pkcs1v15)
+        #[arg(long, value_enum, default_value_t = RsaPadding::Pkcs1v15)]
+        rsa_padding: RsaPadding,
+
+        /// Izinkan algoritma lemah dari deny-list, dipisah koma:
+        /// md5, sha1, small-rsa (RSA < 2048 bit), p192
+        #[arg(long, value_enum, value_delimiter = ',')]
+        allow_weak: Vec<WeakAlgorithm>,
+    },
+    /// Command 10: ltv-audit
+    /// Fungsi: Cari dokumen yang timestamp atau rantai sertifikatnya habis dalam
+    /// beberapa bulan, sebagai daftar kerja untuk pembaruan data LTV
+    LtvAudit {
+        /// File PDF atau direktori arsip (termasuk subdirektori)
+        #[arg(long)]
+        input: String,
+
+        /// Jangka waktu audit dalam bulan
+        #[arg(long, default_value_t = 6)]
+        months: u32,
+
+        /// File tujuan daftar kerja (satu path per baris)
+        #[arg(long)]
+        worklist: Option<String>,
+    },
+    /// Command 11: inspect
+    /// Fungsi: Tampilkan metadata dokumen dan isi setiap signature field
+    /// (penandatangan, alasan, waktu, ByteRange, sertifikat) tanpa verifikasi
+    Inspect {
+        /// Path file PDF
+        #[arg(long)]
+        input: String,
+
+        /// Tulis hasil sebagai JSON ke stdout (untuk skrip; sama dengan --output-format json)
+        #[arg(long)]
+        json: bool,
+
+        /// Tulis sertifikat penandatangan dan rantainya dari setiap signature
+        /// ke direktori ini (`<field>-0` = penandatangan, lalu issuer-nya)
+        #[arg(long, value_name = "DIR")]
+        dump_certs: Option<String>,
+
+        /// Format file --dump-certs, dipisah koma: pem (default; juga
+        /// `<field>-chain.pem`) dan/atau der
+        #[cfg(feature = "verify")]
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "pem", requires = "dump_certs")]
+        cert_format: Vec<CertFormat>,
+    },
+    /// Command 12: sign-batch
+    /// Fungsi: Menandatangani banyak file PDF sekaligus dengan satu kunci,
+    /// paralel di beberapa worker thread
+    SignBatch {
+        /// Direktori berisi PDF yang akan ditandatangani (termasuk subdirektori)
+        #[arg(long, required_unless_present = "input")]
+        input_dir: Option<String>,
+
+        /// File PDF yang akan ditandatangani; boleh diulang atau diisi banyak
+        /// file sekaligus (misalnya hasil glob shell: --input in/*.pdf)
+        #[arg(long, num_args = 1..)]
+        input: Vec<String>,
+
+        /// Direktori tujuan; subdirektori --input-dir dipertahankan
+        #[arg(long)]
+        output_dir: String,
+
+        /// Jumlah worker thread (default: jumlah CPU)
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Lewati self-test kunci, TSA, dan OCSP/CRL sebelum file pertama
+        #[arg(long)]
+        no_self_test: bool,
+
+        /// Ping kunci (satu signing kecil) setiap SECONDS detik selama batch,
+        /// agar sesi HSM atau koneksi KMS tidak habis
+        #[arg(long, value_name = "SECONDS")]
+        keep_alive: Option<u64>,
+
+        /// Kunci, tampilan, dan kebijakan signature (sama seperti `sign`);
+        /// --timeout berlaku per file
+        #[command(flatten)]
+        args: SignArgs,
+    },
+    /// Command 13: verify-legacy
+    /// Fungsi: Memeriksa signature format lama (sebelum CMS) terhadap public.key;
+    /// versi lama menandatangani seluruh file asli sebelum disimpan ulang, jadi
+    /// file asli tersebut juga diperlukan
+    VerifyLegacy {
+        /// Path file PDF yang ditandatangani pdfsign versi lama
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF asli yang ditandatangani (sebelum signing)
+        #[arg(long)]
+        original: String,
+
+        /// Path file kunci publik (public.key, titik P-256 mentah 65 bytes)
+        #[arg(long, default_value = "public.key")]
+        public_key: String,
+    },
+    /// Command 14: remove-signature
+    /// Fungsi: Menghapus signature (dan opsional field-nya) agar dokumen bisa
+    /// diperbaiki lalu ditandatangani ulang; dokumen ditulis ulang seluruhnya
+    RemoveSignature {
+        /// Path file PDF yang sudah ditandatangani
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// Nama lengkap signature field yang signature-nya dihapus
+        #[arg(long, required_unless_present = "all")]
+        field: Option<String>,
+
+        /// Hapus semua signature di dokumen
+        #[arg(long, conflicts_with = "field")]
+        all: bool,
+
+        /// Hapus juga signature field dan widget-nya (default: field tetap ada
+        /// sebagai signature field kosong)
+        #[arg(long)]
+        remove_fields: bool,
+    },
+    /// Command 15: refresh-appearance
+    /// Fungsi: Membuat ulang tampilan signature pdfsign yang sudah ada (misalnya
+    /// setelah pergantian logo) sebagai incremental update; bytes yang sudah
+    /// ditandatangani tidak berubah dan hasilnya diverifikasi sebelum disimpan
+    RefreshAppearance {
+        /// Path file PDF yang sudah ditandatangani
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// Hanya signature field ini (nama lengkap); default: semua signature
+        /// dengan tampilan buatan pdfsign
+        #[arg(long)]
+        field: Option<String>,
+
+        /// Bahasa teks tampilan, satu atau dua dipisah koma (default: en)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        appearance_lang: Option<Vec<AppearanceLanguage>>,
+
+        /// Template teks tampilan (variabel seperti pada `sign`); teks diisi
+        /// dari signature dictionary yang sudah ada
+        #[arg(long, conflicts_with = "appearance_lang")]
+        appearance_template: Option<String>,
+
+        /// File berisi template teks tampilan
+        #[arg(long, conflicts_with_all = ["appearance_lang", "appearance_template"])]
+        appearance_template_file: Option<String>,
+
+        /// Ukuran font teks tampilan dalam point (default: menyesuaikan kotak, maksimal 10)
+        #[arg(long)]
+        appearance_font_size: Option<f32>,
+
+        /// Warna teks tampilan sebagai #RRGGBB (default: #000000)
+        #[arg(long)]
+        appearance_color: Option<TextColor>,
+
+        /// Logo PNG atau JPEG baru di sebelah kiri teks signature
+        #[arg(long)]
+        appearance_image: Option<String>,
+
+        /// Font TrueType (.ttf) yang disematkan untuk teks tampilan
+        #[arg(long)]
+        appearance_font: Option<String>,
+
+        /// Masukkan object baru ke object stream dan kompres stream baru
+        #[arg(long)]
+        object_streams: bool,
+
+        /// Wajibkan dokumen PDF/A dan tolak jika tampilan baru akan merusak
+        /// kesesuaiannya
+        #[arg(long)]
+        require_pdfa: bool,
+    },
+    /// Command 16: sign-fanout
+    /// Fungsi: Menandatangani satu dokumen dengan beberapa identitas (profil),
+    /// satu file output per identitas, misalnya stempel beberapa departemen
+    SignFanout {
+        /// Path file PDF yang akan ditandatangani
+        #[arg(long)]
+        input: String,
+
+        /// Direktori tujuan; output bernama <nama input>-<profil>.pdf
+        #[arg(long)]
+        output_dir: String,
+
+        /// Profil di file konfigurasi, satu per identitas, dipisah koma
+        /// Contoh: --profiles finance,legal
+        #[arg(long, value_delimiter = ',', required = true)]
+        profiles: Vec<String>,
+
+        /// Opsi signature (sama seperti `sign`) yang menimpa nilai setiap
+        /// profil; --timeout berlaku per identitas
+        #[command(flatten)]
+        args: SignArgs,
+    },
+    /// Command 17: pages
+    /// Fungsi: Menampilkan koordinat halaman untuk menentukan --rect
+    Pages {
+        #[command(subcommand)]
+        action: PagesAction,
+    },
+    /// Command 18: watch
+    /// Fungsi: Memantau folder dan menandatangani setiap PDF yang masuk; file
+    /// yang gagal pemeriksaan atau signing dipindahkan ke folder karantina
+    Watch {
+        /// Folder yang dipantau (tanpa subdirektori); file yang berhasil
+        /// ditandatangani dihapus dari folder ini
+        #[arg(long)]
+        input_dir: String,
+
+        /// Folder tujuan PDF hasil (nama file sama dengan input)
+        #[arg(long)]
+        output_dir: String,
+
+        /// Folder karantina untuk file yang ditolak, masing-masing dengan
+        /// file <nama>.reason.txt
+        #[arg(long)]
+        quarantine_dir: String,
+
+        /// Ukuran file maksimal dalam MB; file yang lebih besar dikarantina
+        #[arg(long, default_value_t = 100)]
+        max_file_size: u64,
+
+        /// Jumlah file yang diproses bersamaan (default: jumlah CPU); file
+        /// lain menunggu di folder input
+        #[arg(long)]
+        max_in_flight: Option<usize>,
+
+        /// Jeda antar pemindaian folder dalam detik
+        #[arg(long, default_value_t = 2)]
+        poll_interval: u64,
+
+        /// Lewati self-test kunci, TSA, dan OCSP/CRL sebelum file pertama
+        #[arg(long)]
+        no_self_test: bool,
+
+        /// Ping kunci (satu signing kecil) setiap SECONDS detik selama pemantauan,
+        /// agar sesi HSM atau koneksi KMS tidak habis
+        #[arg(long, value_name = "SECONDS")]
+        keep_alive: Option<u64>,
+
+        /// Kunci, tampilan, dan kebijakan signature (sama seperti `sign`);
+        /// --timeout berlaku per file
+        #[command(flatten)]
+        args: SignArgs,
+    },
+    /// Command 19: timestamp
+    /// Fungsi: Menambahkan document timestamp (/DocTimeStamp, RFC 3161) tanpa
+    /// signature penandatangan, lewat incremental update
+    Timestamp {
+        /// Path file PDF
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// URL Time Stamping Authority (RFC 3161); wajib diisi, kecuali sudah
+        /// ditentukan di profil config (`tsa_url`)
+        #[arg(long)]
+        tsa_url: Option<String>,
+
+        /// Hash message imprint yang dikirim ke TSA: sha256 (default),
+        /// sha384, atau sha512
+        #[arg(long, value_enum)]
+        tsa_hash: Option<DigestAlgorithm>,
+
+        /// Username HTTP Basic untuk TSA yang butuh login
+        #[arg(long)]
+        tsa_username: Option<String>,
+
+        /// Password HTTP Basic untuk TSA (dengan --tsa-username)
+        #[arg(long, env = "PDFSIGN_TSA_PASSWORD", hide_env_values = true)]
+        tsa_password: Option<String>,
+
+        /// Bearer token untuk TSA (menggantikan --tsa-username/--tsa-password)
+        #[arg(long, env = "PDFSIGN_TSA_TOKEN", hide_env_values = true, conflicts_with = "tsa_username")]
+        tsa_token: Option<String>,
+
+        /// Path file konfigurasi (pdfsign.toml); dipakai bersama --profile
+        #[arg(long, value_parser = PathArg::any())]
+        config: Option<PathBuf>,
+
+        /// Nama profil di file konfigurasi yang menyediakan `tsa_url`,
+        /// kredensial TSA, `object_streams`, dan kebijakan pengulangan request
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Masukkan object baru ke object stream (sama seperti `sign --object-streams`)
+        #[arg(long)]
+        object_streams: bool,
+
+        /// Stamp "Timestamped on ..." atau widget Hidden (default: widget
+        /// berukuran nol tanpa tampilan)
+        #[command(flatten)]
+        stamp: StampArgs,
+
+        /// Batas waktu request TSA dalam detik
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Jumlah pengulangan request TSA yang gagal sementara
+        /// (koneksi gagal, timeout, HTTP 429/5xx); default: 0
+        #[arg(long)]
+        retries: Option<u32>,
+
+        /// Jeda sebelum pengulangan pertama dalam milidetik; berlipat dua
+        /// setiap pengulangan, maksimal 30 detik (default: 500)
+        #[arg(long, value_name = "MS")]
+        retry_backoff: Option<u64>,
+
+        /// Persentase jeda yang diacak (0-100) agar klien tidak mengulang
+        /// serempak (default: 50)
+        #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+        retry_jitter: Option<u8>,
+    },
+
+    /// Command 20: key-info
+    /// Fungsi: Menampilkan detail kunci privat, sertifikat, atau bundle PKCS#12
+    /// dan memeriksa apakah kunci dan sertifikat penandatangan berpasangan
+    #[command(group(clap::ArgGroup::new("source").required(true).multiple(true).args(["key", "cert", "p12"])))]
+    KeyInfo {
+        /// Path file kunci privat; tanpa --cert, certificate.der di sebelahnya
+        /// ikut diperiksa (sama seperti `sign`)
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Path file sertifikat (DER atau PEM, boleh berisi rantainya); dengan
+        /// --key atau --p12, sertifikat pertama dicocokkan dengan kuncinya
+        #[arg(long)]
+        cert: Option<String>,
+
+        /// Bundle PKCS#12 (.p12/.pfx) berisi kunci privat dan rantai sertifikat
+        #[arg(long, conflicts_with = "key")]
+        p12: Option<String>,
+
+        /// Password bundle PKCS#12 (jika tidak ada: coba kosong, lalu ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_P12_PASSWORD", hide_env_values = true)]
+        p12_password: Option<String>,
+
+        /// Passphrase kunci privat terenkripsi (jika tidak ada, ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+    },
+
+    /// Command 21: gen-testdoc
+    /// Fungsi: Membuat PDF sintetis dengan ciri tertentu (banyak halaman, xref
+    /// stream, form, enkripsi) untuk pengujian dan reproduksi bug tanpa dokumen asli
+    GenTestdoc {
+        /// Path file PDF hasil ("-" untuk stdout)
+        #[arg(long)]
+        output: String,
+
+        /// Jumlah halaman
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        pages: u32,
+
+        /// Ukuran kertas
+        #[arg(long, value_enum, default_value_t = PageSize::A4)]
+        page_size: PageSize,
+
+        /// Halaman mendatar
+        #[arg(long)]
+        landscape: bool,
+
+        /// Teks tambahan di halaman pertama (misalnya teks jangkar aturan placement)
+        #[arg(long)]
+        text: Option<String>,
+
+        /// Jumlah field teks yang sudah terisi (AcroForm)
+        #[arg(long, default_value_t = 0)]
+        form_fields: u32,
+
+        /// Tambahkan field signature kosong di halaman terakhir
+        #[arg(long)]
+        signature_field: bool,
+
+        /// Tulis cross-reference stream alih-alih tabel xref
+        #[arg(long)]
+        xref_stream: bool,
+
+        /// Simpan object di object stream (termasuk cross-reference stream)
+        #[arg(long, conflicts_with = "encrypt")]
+        object_streams: bool,
+
+        /// Jumlah incremental update setelah revisi pertama
+        #[arg(long, default_value_t = 0)]
+        updates: u32,
+
+        /// Enkripsi dokumen (AES-256, security handler standar)
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Password untuk membuka dokumen terenkripsi (default: kosong, terbuka tanpa password)
+        #[arg(long, requires = "encrypt", default_value = "")]
+        user_password: String,
+
+        /// Password pemilik dokumen terenkripsi
+        #[arg(long, requires = "encrypt", default_value = "owner")]
+        owner_password: String,
+    },
+
+    /// Command 22: prepare-field
+    /// Fungsi: Menambahkan signature field kosong untuk penandatangan berikutnya,
+    /// opsional dengan seed value dictionary (/SV) yang membatasi signature-nya
+    PrepareField {
+        /// Path file PDF
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// Nama field baru (default: Signature1, Signature2, ...)
+        #[arg(long)]
+        field_name: Option<String>,
+
+        /// Halaman tempat field ditampilkan, mulai dari 1 (default: 1)
+        #[arg(long)]
+        page: Option<u32>,
+
+        /// Posisi dan ukuran field dalam point: x,y,w,h dari pojok kiri bawah
+        /// halaman (default: 100,650,200,50, di bawah signature yang sudah ada)
+        #[arg(long, allow_hyphen_values = true, conflicts_with = "position")]
+        rect: Option<Rect>,
+
+        /// Posisi siap pakai relatif terhadap tepi halaman (seperti pada `sign`)
+        #[arg(long, value_enum)]
+        position: Option<Position>,
+
+        /// Buat field tanpa tampilan (rect berukuran nol di halaman 1)
+        #[arg(long, conflicts_with_all = ["page", "rect", "position"])]
+        invisible: bool,
+
+        /// File JSON (atau .toml) berisi batasan untuk penandatangan: reasons,
+        /// digest_methods, sub_filters, timestamp_url, certificate, dan required
+        #[arg(long)]
+        seed_value: Option<String>,
+
+        /// Masukkan object baru ke object stream (sama seperti `sign --object-streams`)
+        #[arg(long)]
+        object_streams: bool,
+    },
+
+    /// Command 23: optimize
+    /// Fungsi: Memperkecil dokumen yang belum ditandatangani (object yang tidak
+    /// terpakai dibuang, stream dikompres ulang); dokumen bertanda tangan
+    /// ditolak atau disalin apa adanya
+    Optimize {
+        /// Path file PDF
+        #[arg(long)]
+        input: String,
+
+        /// Path file PDF hasil (boleh sama dengan --input)
+        #[arg(long)]
+        output: String,
+
+        /// Jangan kompres ulang stream; hanya buang object yang tidak terpakai
+        #[arg(long)]
+        no_recompress: bool,
+
+        /// Simpan object yang bukan stream di object stream (PDF 1.5, tidak untuk PDF/A-1)
+        #[arg(long)]
+        object_streams: bool,
+
+        /// Dokumen yang sudah ditandatangani: refuse (error) atau copy (disalin
+        /// apa adanya, untuk pipeline yang berisi dokumen campuran)
+        #[arg(long, value_enum, default_value_t = IfSigned::Refuse)]
+        if_signed: IfSigned,
+    },
+
+    /// Command 24: serve
+    /// Fungsi: Layanan HTTP signing dengan satu kunci di server:
+    /// POST /sign (multipart: PDF dan opsi per request), POST /verify, GET /health
+    Serve {
+        /// Alamat dan port yang didengarkan
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+
+        /// Jumlah request yang diproses bersamaan (default: jumlah CPU)
+        #[arg(long)]
+        workers: Option<usize>,
+
+        /// Ukuran upload maksimal dalam MB; request yang lebih besar ditolak (413)
+        #[arg(long, default_value_t = 100)]
+        max_file_size: u64,
+
+        /// Token yang wajib dikirim di header `Authorization: Bearer <token>`
+        /// untuk /sign dan /verify (default: tanpa autentikasi)
+        #[arg(long, env = "PDFSIGN_SERVE_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        /// Sumber trust anchor untuk /verify, dipisah koma atau diulang
+        /// (seperti `verify --trust`: system, file:<path>, store:<dir>, atau nama daftar)
+        #[arg(long, value_delimiter = ',', value_name = "SOURCE")]
+        trust: Vec<String>,
+
+        /// Lama: direktori daftar trust bernama (sekarang PDFSIGN_TRUST_DIR)
+        #[arg(long, env = "PDFSIGN_TRUST_DIR", hide = true)]
+        trust_dir: Option<String>,
+
+        /// Lama: sama dengan --trust store:<DIR>
+        #[arg(long, value_name = "DIR", hide = true)]
+        trust_store: Vec<String>,
+
+        /// Kunci dan opsi signature default (sama seperti `sign`); kunci dimuat
+        /// sekali saat start, --timeout berlaku per request
+        #[command(flatten)]
+        args: SignArgs,
+    },
+
+    /// Command 25: interop-test
+    /// Fungsi: Menandatangani dokumen contoh dengan setiap profil lalu
+    /// memeriksanya dengan validator eksternal (misalnya demo webapp DSS)
+    InteropTest {
+        /// Endpoint REST validasi DSS, misalnya
+        /// https://ec.europa.eu/digital-building-blocks/DSS/webapp-demo/services/rest/validation/validateSignature
+        #[arg(long, env = "PDFSIGN_VALIDATOR_URL", required_unless_present = "validator_command", conflicts_with = "validator_command")]
+        validator_url: Option<String>,
+
+        /// Command validator yang dijalankan lewat shell; `{}` diganti path PDF
+        /// (tanpa `{}`, path ditambahkan di akhir). Exit code 0 = lolos
+        #[arg(long)]
+        validator_command: Option<String>,
+
+        /// Profil yang diuji, dipisah koma (default: semua profil di file konfigurasi)
+        #[arg(long, value_delimiter = ',')]
+        profiles: Vec<String>,
+
+        /// Direktori untuk dokumen contoh dan hasil per profil (<contoh>-<profil>.pdf)
+        #[arg(long)]
+        output_dir: String,
+
+        /// Dokumen contoh sendiri (default: dokumen uji satu halaman)
+        #[arg(long)]
+        sample: Option<String>,
+
+        /// Hitung INDETERMINATE sebagai lolos, untuk identitas uji yang rantainya
+        /// tidak dipercaya validator
+        #[arg(long)]
+        allow_indeterminate: bool,
+
+        /// Batas waktu satu validasi dalam detik
+        #[arg(long, default_value_t = 120)]
+        validator_timeout: u64,
+
+        /// Opsi signature (sama seperti `sign`) yang menimpa nilai setiap profil
+        #[command(flatten)]
+        args: SignArgs,
+    },
+}
+
+/// Opsi penandatanganan yang dipakai bersama oleh `sign`, `sign-batch`, `sign-fanout`, `watch`, `serve`, dan `interop-test`
+#[derive(Args, Clone)]
+pub struct SignArgs {
+    /// Path file kunci privat (private.key)
+    /// Wajib diisi, kecuali sudah ditentukan di profil config atau memakai --p12 / --pkcs11-module
+    #[arg(long, value_parser = PathArg::any())]
+    pub key: Option<PathBuf>,
+
+    /// Bundle PKCS#12 (.p12/.pfx) berisi kunci privat dan rantai sertifikat,
+    /// sebagai pengganti --key dan certificate.der
+    #[arg(long, value_parser = PathArg::with_extensions(paths::PKCS12), conflicts_with = "key")]
+    pub p12: Option<PathBuf>,
+
+    /// Password bundle PKCS#12 (jika tidak ada: coba kosong, lalu ditanyakan di terminal)
+    #[arg(long, env = "PDFSIGN_P12_PASSWORD", hide_env_values = true)]
+    pub p12_password: Option<String>,
+
+    /// Modul PKCS#11 smart card / USB token / HSM (misalnya /usr/lib/opensc-pkcs11.so);
+    /// signing dilakukan di token, kunci privat tidak pernah keluar
+    #[arg(long, value_parser = PathArg::any(), conflicts_with_all = ["key", "p12"])]
+    pub pkcs11_module: Option<PathBuf>,
+
+    /// ID slot token PKCS#11 (default: slot pertama yang berisi token)
+    #[arg(long)]
+    pub slot: Option<u64>,
+
+    /// PIN token PKCS#11 (jika tidak ada: PIN pad reader, atau ditanyakan di terminal)
+    #[arg(long, env = "PDFSIGN_PKCS11_PIN", hide_env_values = true)]
+    pub pin: Option<String>,
+
+    /// Label kunci di token jika token berisi beberapa kunci signing
+    #[arg(long)]
+    pub pkcs11_key: Option<String>,
+
+    /// Kunci di KMS cloud; signing dilakukan oleh layanannya, kunci privat
+    /// tidak pernah keluar. Bentuk ID menentukan layanannya:
+    /// key ID / ARN / alias/<nama> (AWS KMS),
+    /// projects/.../cryptoKeyVersions/<n> (Google Cloud KMS),
+    /// https://<vault>.vault.azure.net/keys/<nama> (Azure Key Vault)
+    #[arg(long, conflicts_with_all = ["key", "p12", "pkcs11_module"])]
+    pub kms_key_id: Option<String>,
+
+    /// Region AWS KMS (default: dari ARN, AWS_REGION, atau AWS_DEFAULT_REGION)
+    #[arg(long)]
+    pub kms_region: Option<String>,
+
+    /// Sertifikat penandatangan untuk kunci KMS (DER, boleh diikuti rantainya)
+    #[arg(long, value_parser = PathArg::with_extensions(paths::CERTIFICATE))]
+    pub kms_certificate: Option<PathBuf>,
+
+    /// Sertifikat di Windows certificate store atau macOS Keychain, dipilih
+    /// dengan thumbprint SHA-1 atau common name; signing dilakukan oleh OS dan
+    /// rantai sertifikat diambil dari store (butuh fitur Cargo `keystore`)
+    #[arg(long, value_name = "THUMBPRINT|CN", conflicts_with_all = ["key", "p12", "pkcs11_module", "kms_key_id"])]
+    pub keystore_cert: Option<String>,
+
+    /// Izinkan sertifikat yang ditandai sebagai kredensial uji oleh
+    /// `generate-cert`; tanpa ini signing dengan sertifikat tersebut ditolak
+    /// (--dry-run hanya memberi peringatan)
+    #[arg(long)]
+    pub allow_test_credentials: bool,
+
+    /// Sertifikat yang disisipkan di signature: full (default), leaf-only, atau
+    /// no-root (tanpa root self-signed)
+    #[arg(long, value_enum)]
+    pub embed_chain: Option<EmbedChain>,
+
+    /// Izinkan algoritma lemah dari deny-list, dipisah koma: md5, sha1,
+    /// small-rsa (RSA < 2048 bit), p192 (default: semuanya ditolak)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub allow_weak: Option<Vec<WeakAlgorithm>>,
+
+    /// Nama penandatangan (default: "pdfsign-cli")
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Alasan penandatanganan (default: "Digitally signed")
+    #[arg(long)]
+    pub reason: Option<String>,
+
+    /// Lokasi penandatanganan (default: kosong)
+    #[arg(long)]
+    pub location: Option<String>,
+
+    /// Informasi kontak penandatangan (default: kosong)
+    #[arg(long)]
+    pub contact_info: Option<String>,
+
+    /// File JSON (atau .toml) berisi name, reason, location, contact_info,
+    /// field_name, page, dan rect; flag di command line menimpa isi file,
+    /// isi file menimpa profil
+    #[arg(long, value_parser = PathArg::any())]
+    pub metadata_file: Option<PathBuf>,
+
+    /// Bahasa teks tampilan signature, satu atau dua dipisah koma (default: en)
+    /// Contoh: --appearance-lang id,en untuk dokumen dwibahasa
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub appearance_lang: Option<Vec<AppearanceLanguage>>,
+
+    /// Template teks tampilan, menggantikan --appearance-lang; baris dipisah \n,
+    /// variabel: {name}, {reason}, {location}, {contact}, {date}
+    /// Contoh: --appearance-template "Signed by {name}\n{date}\nReason: {reason}"
+    #[arg(long, conflicts_with_all = ["appearance_lang", "invisible"])]
+    pub appearance_template: Option<String>,
+
+    /// File berisi template teks tampilan (satu baris template per baris file)
+    #[arg(long, value_parser = PathArg::any(), conflicts_with_all = ["appearance_lang", "appearance_template", "invisible"])]
+    pub appearance_template_file: Option<PathBuf>,
+
+    /// Ukuran font teks tampilan dalam point (default: menyesuaikan kotak, maksimal 10)
+    #[arg(long)]
+    pub appearance_font_size: Option<f32>,
+
+    /// Warna teks tampilan sebagai #RRGGBB (default: #000000)
+    #[arg(long)]
+    pub appearance_color: Option<TextColor>,
+
+    /// Logo PNG atau JPEG yang ditampilkan di sebelah kiri teks signature;
+    /// transparansi PNG dan profil ICC dipertahankan
+    #[arg(long, value_parser = PathArg::with_extensions(paths::IMAGE), conflicts_with = "invisible")]
+    pub appearance_image: Option<PathBuf>,
+
+    /// Font TrueType (.ttf) yang disematkan untuk teks tampilan (default:
+    /// Helvetica standar; dokumen PDF/A memakai Arial/DejaVu Sans/Liberation
+    /// Sans dari sistem jika ada)
+    #[arg(long, value_parser = PathArg::with_extensions(paths::FONT), conflicts_with = "invisible")]
+    pub appearance_font: Option<PathBuf>,
+
+    /// Penanganan flag /NeedAppearances pada AcroForm (default: warn)
+    /// warn = beri peringatan, clear = hapus flag, generate = buat appearance lalu hapus flag
+    #[arg(long, value_enum)]
+    pub need_appearances: Option<NeedAppearancesPolicy>,
+
+    /// Halaman tempat signature ditampilkan, mulai dari 1 (default: 1)
+    #[arg(long)]
+    pub page: Option<u32>,
+
+    /// Posisi dan ukuran signature dalam point: x,y,w,h dari pojok kiri bawah
+    /// halaman (default: 100,650,200,50, di bawah signature yang sudah ada)
+    #[arg(long, allow_hyphen_values = true, conflicts_with = "position")]
+    pub rect: Option<Rect>,
+
+    /// Posisi siap pakai relatif terhadap tepi halaman: top-left, top-center,
+    /// top-right, center, bottom-left, bottom-center, atau bottom-right
+    #[arg(long, value_enum)]
+    pub position: Option<Position>,
+
+    /// Buat signature tanpa tampilan (appearance kosong, rect berukuran nol)
+    #[arg(long, conflicts_with_all = ["page", "rect", "position"])]
+    pub invisible: bool,
+
+    /// Tampilkan signature sebagai annotation stamp di posisi --page/--rect/
+    /// --position; signature field-nya dibuat tanpa tampilan (rect nol)
+    #[arg(long, conflicts_with_all = ["invisible", "field_name"])]
+    pub stamp_only: bool,
+
+    /// Flag annotation widget signature, dipisah koma: print, no-view,
+    /// locked, locked-contents, atau none (default: print); print,no-view =
+    /// hanya di hasil cetak, tanpa print = hanya di layar
+    #[arg(long, value_name = "FLAGS")]
+    pub widget_flags: Option<AnnotationFlags>,
+
+    /// Flag annotation stamp --stamp-only, format sama dengan --widget-flags
+    /// (default: print,locked)
+    #[arg(long, value_name = "FLAGS")]
+    pub stamp_flags: Option<AnnotationFlags>,
+
+    /// Isi signature field kosong yang sudah ada di dokumen (nama lengkap
+    /// field); halaman dan posisinya diambil dari widget field tersebut
+    #[arg(long, conflicts_with_all = ["page", "rect", "position"])]
+    pub field_name: Option<String>,
+
+    /// Tolak dokumen yang form-nya punya perubahan tertunda (XFA, nilai field
+    /// yang tidak sesuai appearance) alih-alih hanya memberi peringatan
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Hapus data XFA dari form hybrid (XFA + AcroForm) sehingga yang
+    /// ditandatangani hanya field AcroForm; dynamic XFA tetap ditolak
+    #[arg(long)]
+    pub drop_xfa: bool,
+
+    /// Tolak dokumen yang melanggar spesifikasi PDF (nomor object ganda dalam
+    /// satu revisi, /Length stream yang salah); tanpa flag ini pelanggaran
+    /// diperbaiki dan ditampilkan sebagai peringatan
+    #[arg(long)]
+    pub strict_parse: bool,
+
+    /// Jika dokumen tidak bisa dibaca (cross-reference rusak atau terpotong),
+    /// bangun ulang dari header object-nya dan tulis ulang seluruhnya sebelum
+    /// ditandatangani; signature yang sudah ada tidak lagi valid
+    #[arg(long, conflicts_with = "strict_parse")]
+    pub repair: bool,
+
+    /// Command yang memeriksa dokumen sebelum ditandatangani (dijalankan lewat
+    /// shell, deskripsi dokumen dalam JSON di stdin): exit code selain 0
+    /// menolak dokumen, baris `reason=`, `location=`, atau `contact-info=` di
+    /// stdout mengganti nilai tersebut
+    #[arg(long, value_name = "COMMAND")]
+    pub classify_hook: Option<String>,
+
+    /// Wajibkan dokumen PDF/A (dari metadata XMP) dan tolak jika signature
+    /// akan merusak kesesuaiannya; tanpa flag ini hanya diberi peringatan
+    #[arg(long)]
+    pub require_pdfa: bool,
+
+    /// Skema padding jika kunci privat RSA (default: pkcs1v15; diabaikan untuk ECDSA)
+    #[arg(long, value_enum)]
+    pub rsa_padding: Option<RsaPadding>,
+
+    /// Algoritma digest signature: sha256 (default), sha384, atau sha512
+    #[arg(long, value_enum)]
+    pub digest: Option<DigestAlgorithm>,
+
+    /// URL Time Stamping Authority (RFC 3161); signature diberi timestamp
+    /// terpercaya, misalnya http://timestamp.digicert.com
+    #[arg(long)]
+    pub tsa_url: Option<String>,
+
+    /// Hash message imprint yang dikirim ke TSA: sha256 (default), sha384,
+    /// atau sha512; tidak bergantung pada --digest
+    #[arg(long, value_enum)]
+    pub tsa_hash: Option<DigestAlgorithm>,
+
+    /// Username HTTP Basic untuk TSA yang butuh login; kredensial TSA terpisah
+    /// dari identitas penandatangan
+    #[arg(long)]
+    pub tsa_username: Option<String>,
+
+    /// Password HTTP Basic untuk TSA (dengan --tsa-username)
+    #[arg(long, env = "PDFSIGN_TSA_PASSWORD", hide_env_values = true)]
+    pub tsa_password: Option<String>,
+
+    /// Bearer token untuk TSA (menggantikan --tsa-username/--tsa-password)
+    #[arg(long, env = "PDFSIGN_TSA_TOKEN", hide_env_values = true, conflicts_with = "tsa_username")]
+    pub tsa_token: Option<String>,
+
+    /// Level PAdES baseline (ETSI EN 319 142): b-b, b-t, b-lt, atau b-lta;
+    /// b-t ke atas butuh --tsa-url; b-lt ke atas mengambil OCSP/CRL dari internet
+    #[arg(long, value_enum)]
+    pub pades_level: Option<PadesLevel>,
+
+    /// Sumber data revocation untuk b-lt dan b-lta: ocsp (default, lalu CRL
+    /// jika gagal), crl (lalu OCSP), atau both (keduanya jika tersedia)
+    #[arg(long, value_enum)]
+    pub ltv_revocation: Option<RevocationPreference>,
+
+    /// Sertifikat yang data OCSP/CRL-nya tidak bisa diambil hanya diberi
+    /// peringatan, tidak membatalkan signing (sertifikat yang dicabut tetap ditolak)
+    #[arg(long)]
+    pub ltv_best_effort: bool,
+
+    /// Buat certification signature (DocMDP) yang membatasi perubahan
+    /// berikutnya: no-changes, form-filling, atau annotations;
+    /// hanya untuk signature pertama di dokumen
+    #[arg(long, value_enum)]
+    pub certify: Option<CertifyLevel>,
+
+    /// Kunci form field setelah ditandatangani (FieldMDP): all,
+    /// include:<f1,f2>, atau exclude:<f3>; field yang dikunci menjadi read-only
+    #[arg(long)]
+    pub lock_fields: Option<FieldLock>,
+
+    /// Masukkan object baru (widget, AcroForm, font) ke object stream dan
+    /// kompres stream baru (appearance, logo) dengan Flate agar output lebih kecil;
+    /// dokumen di bawah PDF 1.5 dinaikkan ke PDF 1.5
+    #[arg(long)]
+    pub object_streams: bool,
+
+    /// Tandatangani juga setiap file lampiran dengan CMS detached dan simpan
+    /// hasilnya sebagai lampiran "<nama>.p7s" di samping file aslinya, sehingga
+    /// lampiran yang diekstrak tetap bisa diverifikasi di luar PDF
+    #[arg(long)]
+    pub sign_attachments: bool,
+
+    /// Ruang /Contents untuk signature dalam bytes (default: diperkirakan dari
+    /// rantai sertifikat, kunci, dan TSA); proses gagal jika signature tidak muat
+    #[arg(long)]
+    pub placeholder_size: Option<usize>,
+
+    /// Peringatkan jika signing menambah file lebih dari SIZE (misalnya 500KB
+    /// atau 2MB) atau PERCENT dari ukuran aslinya (misalnya 50%), beserta
+    /// object terbesar yang ditambahkan
+    #[arg(long, value_name = "SIZE|PERCENT")]
+    pub warn_growth: Option<Growth>,
+
+    /// Peringatkan jika signing menambah atau menulis ulang lebih dari N object
+    #[arg(long, value_name = "N")]
+    pub warn_objects: Option<usize>,
+
+    /// Waktu penandatanganan RFC 3339 (misalnya 2026-01-20T10:53:37+07:00)
+    /// untuk /M, tampilan, dan atribut signingTime (default: sekarang); untuk
+    /// fixture yang reproducible
+    #[arg(long, value_name = "RFC3339", value_parser = chrono::DateTime::parse_from_rfc3339)]
+    pub signing_time: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+    /// Passphrase kunci privat terenkripsi (jika tidak ada, ditanyakan di terminal)
+    #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+    pub passphrase: Option<String>,
+
+    /// Password dokumen PDF terenkripsi (user atau owner); tanpa ini, password
+    /// user kosong dicoba. Update ditulis dengan enkripsi dokumen asli
+    #[arg(long, env = "PDFSIGN_PDF_PASSWORD", hide_env_values = true)]
+    pub pdf_password: Option<String>,
+
+    /// Tulis dokumen terenkripsi sebagai salinan tanpa enkripsi: seluruh
+    /// dokumen ditulis ulang (butuh password owner; signature yang sudah ada
+    /// tidak lagi valid). Tanpa ini hasilnya selalu tetap terenkripsi
+    #[arg(long)]
+    pub decrypt_output: bool,
+
+    /// Path file konfigurasi (pdfsign.toml) yang berisi profil signing
+    /// (default: ./pdfsign.toml, lalu ~/.pdfsign/pdfsign.toml); dipakai bersama
+    /// --profile (atau --profiles di sign-fanout)
+    #[arg(long, value_parser = PathArg::any())]
+    pub config: Option<PathBuf>,
+
+    /// Nama profil di file konfigurasi yang dipakai sebagai nilai default
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Batas waktu proses signing dalam detik; jika terlewati, proses
+    /// dibatalkan tanpa menulis file output
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Jumlah pengulangan request TSA/OCSP/CRL/KMS yang gagal sementara
+    /// (koneksi gagal, timeout, HTTP 429/5xx); default: 0
+    #[arg(long)]
+    pub retries: Option<u32>,
+
+    /// Jeda sebelum pengulangan pertama dalam milidetik; berlipat dua
+    /// setiap pengulangan, maksimal 30 detik (default: 500)
+    #[arg(long, value_name = "MS")]
+    pub retry_backoff: Option<u64>,
+
+    /// Persentase jeda yang diacak (0-100) agar klien tidak mengulang
+    /// serempak (default: 50)
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub retry_jitter: Option<u8>,
+
+    /// Tempat menyimpan hasil serialisasi sementara (default: memory)
+    /// memory = di RAM, dir = file sementara bernama, anonymous = file tanpa nama (O_TMPFILE)
+    #[arg(long, value_enum)]
+    pub temp_strategy: Option<TempStrategy>,
+
+    /// Direktori untuk file sementara (default: direktori temp sistem)
+    #[arg(long, value_parser = PathArg::any())]
+    pub temp_dir: Option<PathBuf>,
+}
+
+/// Tampilan document timestamp (`timestamp`)
+///
+/// Opsi posisi atau tampilan mana pun (--page, --rect, --position,
+/// --appearance-*) menampilkan stamp kecil, disusun dengan engine template
+/// yang sama seperti `sign`
+#[derive(Args, Clone)]
+pub struct StampArgs {
+    /// Halaman tempat stamp timestamp ditampilkan, mulai dari 1
+    #[arg(long)]
+    pub page: Option<u32>,
+
+    /// Posisi dan ukuran stamp dalam point: x,y,w,h dari pojok kiri bawah
+    /// halaman (default dengan --page: 100,650,200,50, di bawah signature yang sudah ada)
+    #[arg(long, allow_hyphen_values = true, conflicts_with = "position")]
+    pub rect: Option<Rect>,
+
+    /// Posisi stamp siap pakai relatif terhadap tepi halaman: top-left,
+    /// top-center, top-right, center, bottom-left, bottom-center, atau bottom-right
+    #[arg(long, value_enum)]
+    pub position: Option<Position>,
+
+    /// Benar-benar tak terlihat: widget berukuran nol ditandai Hidden, tidak
+    /// pernah digambar atau dicetak (tidak untuk dokumen PDF/A)
+    #[arg(long, conflicts_with_all = ["page", "rect", "position"])]
+    pub invisible: bool,
+
+    /// Bahasa teks stamp, satu atau dua dipisah koma (default: en);
+    /// en = "Timestamped on {date}", id = "Dibubuhi cap waktu pada {date}"
+    #[arg(long, value_enum, value_delimiter = ',', conflicts_with = "invisible")]
+    pub appearance_lang: Option<Vec<AppearanceLanguage>>,
+
+    /// Template teks stamp, menggantikan --appearance-lang; variabel: {date}
+    /// (waktu saat timestamp diminta) dan {name} (host TSA)
+    #[arg(long, conflicts_with_all = ["appearance_lang", "invisible"])]
+    pub appearance_template: Option<String>,
+
+    /// File berisi template teks stamp (satu baris template per baris file)
+    #[arg(long, conflicts_with_all = ["appearance_lang", "appearance_template", "invisible"])]
+    pub appearance_template_file: Option<String>,
+
+    /// Ukuran font teks stamp dalam point (default: menyesuaikan kotak, maksimal 10)
+    #[arg(long, conflicts_with = "invisible")]
+    pub appearance_font_size: Option<f32>,
+
+    /// Warna teks stamp sebagai #RRGGBB (default: #000000)
+    #[arg(long, conflicts_with = "invisible")]
+    pub appearance_color: Option<TextColor>,
+
+    /// Font TrueType (.ttf) yang disematkan untuk teks stamp
+    #[arg(long, conflicts_with = "invisible")]
+    pub appearance_font: Option<String>,
+}
+
+/// Sub-command untuk `session`
+#[derive(Subcommand)]
+pub enum SessionAction {
+    /// Buka kunci atau bundle PKCS#12 untuk sementara; `sign` dengan kunci yang
+    /// sama tidak meminta passphrase sampai sesi habis
+    Start {
+        /// Path file kunci privat
+        #[arg(long, required_unless_present = "p12")]
+        key: Option<String>,
+
+        /// Bundle PKCS#12 (.p12/.pfx) sebagai pengganti --key
+        #[arg(long, conflicts_with = "key")]
+        p12: Option<String>,
+
+        /// Password bundle PKCS#12 (jika tidak ada: coba kosong, lalu ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_P12_PASSWORD", hide_env_values = true)]
+        p12_password: Option<String>,
+
+        /// Passphrase kunci privat terenkripsi (jika tidak ada, ditanyakan di terminal)
+        #[arg(long, env = "PDFSIGN_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+
+        /// Lama sesi dalam detik; setelahnya kunci harus dibuka lagi
+        #[arg(long, default_value_t = 900)]
+        duration: u64,
+    },
+    /// Tampilkan sesi yang sedang aktif
+    Status,
+    /// Akhiri sesi sekarang
+    Stop,
+    /// Agent sesi (dijalankan oleh `session start`)
+    #[command(hide = true)]
+    Serve,
+}
+
+/// Sub-command untuk `pdfsign pages`
+#[derive(Subcommand)]
+pub enum PagesAction {
+    /// Tampilkan MediaBox/CropBox, rotasi, dan kotak annotation setiap
+    /// halaman (--output-format json untuk skrip)
+    Info {
+        /// Path file PDF
+        #[arg(long)]
+        input: String,
     },
 }
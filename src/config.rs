@@ -0,0 +1,583 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use serde::Deserialize; // Untuk membaca file TOML ke struct
+use std::collections::BTreeMap; // Map profil berdasarkan nama
+use std::fs; // Untuk membaca file config
+use std::path::{Path, PathBuf}; // Lokasi file config yang dicari otomatis, file metadata
+
+use pdfsign::crypto::cert::read_certificates; // Sertifikat di file seed value
+use pdfsign::crypto::cms::EmbedChain; // Sertifikat yang disisipkan di signature
+use pdfsign::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use pdfsign::crypto::policy::WeakAlgorithm; // Deny-list algoritma lemah
+use pdfsign::crypto::rsa::RsaPadding; // Padding signature RSA
+use pdfsign::pdf::appearance::AppearanceLanguage; // Bahasa teks tampilan
+use pdfsign::pdf::form::NeedAppearancesPolicy; // Kebijakan /NeedAppearances
+use pdfsign::pdf::options::{Placement, Position, Rect}; // Posisi signature siap pakai dan rect x,y,w,h
+use pdfsign::pdf::mdp::CertifyLevel; // Izin certification signature (DocMDP)
+use pdfsign::pdf::pades::{PadesLevel, RevocationPreference}; // Level PAdES baseline dan sumber OCSP/CRL
+use pdfsign::pdf::placement::{Orientation, PageSize, PlacementRule}; // Posisi berdasarkan ciri dokumen
+use pdfsign::pdf::seed::{CertificateSeed, SeedConstraint, SeedValue}; // Seed value dictionary (prepare-field)
+use pdfsign::scratch::TempStrategy; // Strategi file sementara
+
+/// Struktur file konfigurasi (pdfsign.toml)
+///
+/// Contoh:
+/// ```toml
+/// [profile.base]
+/// key = "keys/company.key"
+/// location = "Jakarta"
+///
+/// [profile.finance]
+/// inherits = "base"
+/// reason = "Invoice approval"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Semua profil yang didefinisikan dengan tabel [profile.<nama>]
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+}
+
+/// Satu profil signing. Semua field opsional: nilai yang kosong
+/// akan diambil dari profil induk (inherits) atau dari default CLI
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub inherits: Option<String>,     // Nama profil induk
+    pub key: Option<String>,          // Path file kunci privat
+    pub p12: Option<String>,          // Path bundle PKCS#12 (menggantikan key)
+    pub pkcs11_module: Option<String>, // Modul PKCS#11 untuk kunci di token (menggantikan key)
+    pub pkcs11_slot: Option<u64>,     // ID slot token PKCS#11
+    pub pkcs11_key: Option<String>,   // Label kunci di token PKCS#11
+    pub kms_key_id: Option<String>,   // Kunci di KMS cloud (menggantikan key)
+    pub kms_region: Option<String>,   // Region AWS KMS
+    pub kms_certificate: Option<String>, // Sertifikat untuk kunci KMS
+    pub keystore_cert: Option<String>, // Sertifikat di keystore OS (menggantikan key)
+    pub name: Option<String>,         // Nama penandatangan
+    pub reason: Option<String>,       // Alasan penandatanganan
+    pub location: Option<String>,     // Lokasi penandatanganan
+    pub contact_info: Option<String>, // Informasi kontak
+    pub page: Option<u32>,            // Halaman tempat signature ditampilkan
+    pub rect: Option<String>,         // Posisi signature "x,y,w,h" (point)
+    pub position: Option<Position>,   // Posisi siap pakai (menggantikan rect)
+    pub invisible: Option<bool>,      // Signature tanpa tampilan
+    pub stamp_only: Option<bool>,     // Tampilan sebagai annotation stamp
+    pub widget_flags: Option<String>, // Flag annotation widget (print,no-view,locked,locked-contents)
+    pub stamp_flags: Option<String>,  // Flag annotation stamp --stamp-only
+    pub field_name: Option<String>,   // Signature field kosong yang diisi (menggantikan page/rect/position)
+    pub placement_rules: Option<Vec<PlacementRuleConfig>>, // Posisi per jenis dokumen ([[profile.<nama>.placement_rules]])
+    pub appearance_languages: Option<Vec<AppearanceLanguage>>, // Bahasa teks tampilan
+    pub appearance_template: Option<String>,      // Template teks tampilan (menggantikan appearance_languages)
+    pub appearance_template_file: Option<String>, // File template teks tampilan
+    pub appearance_font_size: Option<f32>,        // Ukuran font teks tampilan
+    pub appearance_color: Option<String>,         // Warna teks tampilan (#RRGGBB)
+    pub appearance_image: Option<String>,                       // Logo PNG/JPEG di tampilan
+    pub appearance_font: Option<String>,                        // Font TrueType yang disematkan di tampilan
+    pub need_appearances: Option<NeedAppearancesPolicy>, // Kebijakan /NeedAppearances
+    pub strict: Option<bool>,                            // Tolak form dengan perubahan tertunda
+    pub drop_xfa: Option<bool>,                          // Hapus data XFA dari form hybrid
+    pub strict_parse: Option<bool>,                      // Tolak dokumen yang melanggar spesifikasi PDF
+    pub repair: Option<bool>,                            // Bangun ulang dokumen yang cross-reference-nya rusak
+    pub classify_hook: Option<String>,                   // Command yang memeriksa dokumen sebelum signing
+    pub require_pdfa: Option<bool>,                      // Tolak jika kesesuaian PDF/A tidak bisa dipertahankan
+    pub rsa_padding: Option<RsaPadding>,                 // Padding signature RSA
+    pub digest: Option<DigestAlgorithm>,                 // Algoritma digest signature
+    pub embed_chain: Option<EmbedChain>,                 // Sertifikat yang disisipkan di signature
+    pub allow_weak: Option<Vec<WeakAlgorithm>>,          // Algoritma lemah yang diizinkan
+    pub tsa_url: Option<String>,                         // URL Time Stamping Authority
+    pub tsa_hash: Option<DigestAlgorithm>,               // Hash message imprint untuk TSA
+    pub tsa_username: Option<String>,                    // Username HTTP Basic untuk TSA
+    pub tsa_password_env: Option<String>,                // Environment variable berisi password TSA
+    pub tsa_token_env: Option<String>,                   // Environment variable berisi bearer token TSA
+    pub pades_level: Option<PadesLevel>,                 // Level PAdES baseline
+    pub ltv_revocation: Option<RevocationPreference>,    // Sumber OCSP/CRL untuk B-LT ke atas
+    pub ltv_best_effort: Option<bool>,                   // Sertifikat tanpa data revocation hanya diberi peringatan
+    pub certify: Option<CertifyLevel>,                   // Certification signature (DocMDP)
+    pub lock_fields: Option<String>,                     // Field yang dikunci (all, include:..., exclude:...)
+    pub object_streams: Option<bool>,                    // Object baru di object stream terkompresi
+    pub sign_attachments: Option<bool>,                  // Signature .p7s terpisah untuk tiap file lampiran
+    pub placeholder_size: Option<usize>,                 // Ruang /Contents signature (bytes)
+    pub warn_growth: Option<String>,                     // Batas pertumbuhan file ("2MB" atau "50%"), hanya peringatan
+    pub warn_objects: Option<usize>,                     // Batas jumlah object yang ditulis, hanya peringatan
+    pub retries: Option<u32>,                            // Pengulangan request TSA/OCSP/CRL/KMS
+    pub retry_backoff: Option<u64>,                      // Jeda pengulangan pertama (milidetik)
+    pub retry_jitter: Option<u8>,                        // Persentase jeda yang diacak
+    pub temp_strategy: Option<TempStrategy>, // Strategi file sementara
+    pub temp_dir: Option<String>,            // Direktori file sementara
+}
+
+/// Satu aturan posisi di profil: dokumen yang cocok dengan semua ciri yang
+/// diisi (page_size, orientation, anchor_text) ditandatangani di page dan
+/// rect/position aturan ini
+///
+/// Contoh:
+/// ```toml
+/// [[profile.office.placement_rules]]
+/// name = "invoice"
+/// anchor_text = "INVOICE"
+/// position = "bottom-right"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlacementRuleConfig {
+    pub name: String,                     // Nama aturan (pesan dan --dry-run)
+    pub page_size: Option<PageSize>,      // Ukuran kertas halaman pertama (a3, a4, a5, letter, legal)
+    pub orientation: Option<Orientation>, // Orientasi halaman pertama (portrait, landscape)
+    pub anchor_text: Option<String>,      // Teks yang harus ada di dokumen
+    pub page: Option<u32>,                // Halaman signature (default: 1)
+    pub rect: Option<String>,             // Posisi signature "x,y,w,h" (point)
+    pub position: Option<Position>,       // Posisi siap pakai (menggantikan rect)
+}
+
+impl PlacementRuleConfig {
+    /// Ubah aturan dari profil menjadi PlacementRule untuk SignatureOptions
+    pub fn to_rule(&self) -> Result<PlacementRule> {
+        if self.page_size.is_none() && self.orientation.is_none() && self.anchor_text.is_none() {
+            bail!("placement rule {} needs at least one of page_size, orientation or anchor_text", self.name);
+        }
+        let page = self.page.unwrap_or(1);
+        let placement = match (&self.rect, self.position) {
+            (Some(_), Some(_)) => bail!("placement rule {} has both rect and position; choose one", self.name),
+            (Some(rect), None) => {
+                let rect = rect.parse::<Rect>().map_err(|e| anyhow!("invalid `rect` in placement rule {}: {}", self.name, e))?;
+                Placement::Page(page, rect)
+            }
+            (None, Some(position)) => Placement::Anchored(page, position),
+            (None, None) => bail!("placement rule {} needs rect or position", self.name),
+        };
+        Ok(PlacementRule {
+            name: self.name.clone(),
+            page_size: self.page_size,
+            orientation: self.orientation,
+            anchor_text: self.anchor_text.clone(),
+            placement,
+        })
+    }
+}
+
+/// Nama file konfigurasi yang dicari otomatis
+pub const FILE_NAME: &str = "pdfsign.toml";
+
+impl Config {
+    /// Cari file konfigurasi jika --config tidak diberikan: pdfsign.toml di
+    /// direktori kerja, lalu ~/.pdfsign/pdfsign.toml
+    ///
+    /// Return: path file pertama yang ada, atau None
+    pub fn discover() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let candidates = [Some(PathBuf::from(FILE_NAME)), home.map(|home| home.join(".pdfsign").join(FILE_NAME))];
+        candidates.into_iter().flatten().find(|path| path.is_file())
+    }
+
+    /// Baca dan parse file konfigurasi dari path yang diberikan
+    pub fn load(path: &Path) -> Result<Config> {
+        let text = fs::read_to_string(path).with_context(|| format!("cannot read config file {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid config file {}", path.display()))
+    }
+
+    /// Resolve profil berdasarkan nama, termasuk seluruh rantai inherits
+    ///
+    /// Profil anak selalu menimpa nilai dari profil induk, sehingga
+    /// organisasi cukup menulis satu profil dasar dan perbedaan kecil per departemen.
+    pub fn resolve(&self, name: &str) -> Result<Profile> {
+        // Kumpulkan rantai profil dari anak ke induk paling atas
+        let mut chain: Vec<&Profile> = Vec::new();
+        let mut visited: Vec<&str> = Vec::new();
+        let mut current = name;
+        loop {
+            // Deteksi inheritance melingkar (a -> b -> a)
+            if visited.contains(&current) {
+                visited.push(current);
+                bail!("profile inheritance cycle: {}", visited.join(" -> "));
+            }
+            visited.push(current);
+
+            let profile = self
+                .profile
+                .get(current)
+                .ok_or_else(|| anyhow!("profile '{}' not found in config", current))?;
+            chain.push(profile);
+
+            match &profile.inherits {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+
+        // Gabungkan mulai dari induk paling atas, lalu timpa dengan anak-anaknya
+        let mut resolved = Profile::default();
+        for profile in chain.iter().rev() {
+            resolved.merge_from(profile);
+        }
+        resolved.inherits = None;
+        for rule in resolved.placement_rules.iter().flatten() {
+            rule.to_rule()?;
+        }
+        Ok(resolved)
+    }
+}
+
+/// Metadata signature dari --metadata-file (JSON, atau TOML untuk file
+/// berakhiran .toml), untuk skrip yang tidak ingin meng-quote banyak flag
+///
+/// Contoh:
+/// ```json
+/// {"name": "Budi Santoso", "reason": "Persetujuan", "page": 2, "rect": "100,100,200,50"}
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataFile {
+    pub name: Option<String>,         // Nama penandatangan
+    pub reason: Option<String>,       // Alasan penandatanganan
+    pub location: Option<String>,     // Lokasi penandatanganan
+    pub contact_info: Option<String>, // Informasi kontak
+    pub field_name: Option<String>,   // Signature field kosong yang diisi (menggantikan page/rect)
+    pub page: Option<u32>,            // Halaman tempat signature ditampilkan
+    pub rect: Option<String>,         // Posisi signature "x,y,w,h" (point)
+}
+
+impl MetadataFile {
+    /// Baca file metadata; formatnya dipilih dari ekstensi (.toml atau JSON)
+    pub fn load(path: &Path) -> Result<MetadataFile> {
+        let path = &*path.to_string_lossy();
+        let text = fs::read_to_string(path).with_context(|| format!("cannot read metadata file {}", path))?;
+        let metadata: MetadataFile = if path.to_ascii_lowercase().ends_with(".toml") {
+            toml::from_str(&text).with_context(|| format!("invalid metadata file {}", path))?
+        } else {
+            serde_json::from_str(&text).with_context(|| format!("invalid metadata file {}", path))?
+        };
+        if let Some(rect) = &metadata.rect {
+            rect.parse::<Rect>().map_err(|e| anyhow!("invalid `rect` in metadata file {}: {}", path, e))?;
+        }
+        Ok(metadata)
+    }
+
+    /// Timpa nilai profil dengan isi file metadata; page, rect, dan
+    /// field_name saling menggantikan seperti pada profil turunan, dan
+    /// menggantikan placement_rules profil
+    pub fn apply_to(self, profile: &mut Profile) {
+        if self.page.is_some() || self.rect.is_some() || self.field_name.is_some() {
+            profile.placement_rules = None;
+        }
+        profile.merge_from(&Profile {
+            name: self.name,
+            reason: self.reason,
+            location: self.location,
+            contact_info: self.contact_info,
+            field_name: self.field_name,
+            page: self.page,
+            rect: self.rect,
+            ..Profile::default()
+        });
+    }
+}
+
+/// Seed value dictionary dari --seed-value (JSON, atau TOML untuk file
+/// berakhiran .toml) untuk `prepare-field`
+///
+/// Contoh:
+/// ```json
+/// {"reasons": ["Approved"], "digest_methods": ["sha256", "sha384"],
+///  "certificate": {"subject_dn": [{"O": "PT Contoh"}], "issuers": ["ca.pem"]},
+///  "required": ["reasons", "subject_dn"]}
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedValueFile {
+    #[serde(default)]
+    pub reasons: Vec<String>,                 // Alasan yang boleh dipilih
+    #[serde(default)]
+    pub digest_methods: Vec<DigestAlgorithm>, // Digest signature yang diterima
+    #[serde(default)]
+    pub sub_filters: Vec<String>,             // SubFilter yang diterima
+    pub timestamp_url: Option<String>,        // TSA untuk timestamp signature
+    #[serde(default)]
+    pub certificate: CertificateSeedFile,     // Batasan sertifikat penandatangan
+    #[serde(default)]
+    pub required: Vec<SeedConstraint>,        // Batasan yang wajib
+}
+
+/// Bagian `certificate` dari file seed value
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CertificateSeedFile {
+    #[serde(default)]
+    pub subjects: Vec<String>,                     // File sertifikat yang boleh menandatangani (PEM/DER)
+    #[serde(default)]
+    pub issuers: Vec<String>,                      // File sertifikat penerbit yang diterima (PEM/DER)
+    #[serde(default)]
+    pub subject_dn: Vec<BTreeMap<String, String>>, // Atribut subject, misalnya {"CN": "...", "O": "..."}
+    #[serde(default)]
+    pub key_usage: Vec<String>,                    // Pola key usage, misalnya "1X0XXXXXX"
+    #[serde(default)]
+    pub oids: Vec<String>,                         // OID certificate policy
+}
+
+impl SeedValueFile {
+    /// Baca file seed value; sertifikat dibaca dari path yang relatif
+    /// terhadap direktori kerja
+    pub fn load(path: &str) -> Result<SeedValue> {
+        let text = fs::read_to_string(path).with_context(|| format!("cannot read seed value file {}", path))?;
+        let file: SeedValueFile = if path.to_ascii_lowercase().ends_with(".toml") {
+            toml::from_str(&text).with_context(|| format!("invalid seed value file {}", path))?
+        } else {
+            serde_json::from_str(&text).with_context(|| format!("invalid seed value file {}", path))?
+        };
+        let read_all = |paths: &[String]| -> Result<Vec<Vec<u8>>> {
+            // Hanya sertifikat pertama tiap file (tanpa rantainya) yang dipakai
+            paths.iter().map(|path| Ok(read_certificates(path)?.swap_remove(0))).collect()
+        };
+        let seed_value = SeedValue {
+            reasons: file.reasons,
+            digest_methods: file.digest_methods,
+            sub_filters: file.sub_filters,
+            timestamp_url: file.timestamp_url,
+            certificate: CertificateSeed {
+                subjects: read_all(&file.certificate.subjects)?,
+                issuers: read_all(&file.certificate.issuers)?,
+                subject_dn: file.certificate.subject_dn.into_iter().map(|name| name.into_iter().collect()).collect(),
+                key_usage: file.certificate.key_usage,
+                oids: file.certificate.oids,
+            },
+            required: file.required,
+        };
+        seed_value.validate().with_context(|| format!("invalid seed value file {}", path))?;
+        Ok(seed_value)
+    }
+}
+
+impl Profile {
+    /// Timpa field pada profil ini dengan field yang terisi di `other`
+    fn merge_from(&mut self, other: &Profile) {
+        // key, p12, pkcs11_module, kms_key_id, dan keystore_cert saling menggantikan:
+        // profil turunan yang memilih salah satu tidak mewarisi yang lain dari induknya
+        if other.key.is_some() {
+            self.key = other.key.clone();
+            self.p12 = None;
+            self.pkcs11_module = None;
+            self.kms_key_id = None;
+            self.keystore_cert = None;
+        }
+        if other.p12.is_some() {
+            self.p12 = other.p12.clone();
+            self.key = None;
+            self.pkcs11_module = None;
+            self.kms_key_id = None;
+            self.keystore_cert = None;
+        }
+        if other.pkcs11_module.is_some() {
+            self.pkcs11_module = other.pkcs11_module.clone();
+            self.key = None;
+            self.p12 = None;
+            self.kms_key_id = None;
+            self.keystore_cert = None;
+        }
+        if other.kms_key_id.is_some() {
+            self.kms_key_id = other.kms_key_id.clone();
+            self.key = None;
+            self.p12 = None;
+            self.pkcs11_module = None;
+            self.keystore_cert = None;
+        }
+        if other.keystore_cert.is_some() {
+            self.keystore_cert = other.keystore_cert.clone();
+            self.key = None;
+            self.p12 = None;
+            self.pkcs11_module = None;
+            self.kms_key_id = None;
+        }
+        if other.pkcs11_slot.is_some() {
+            self.pkcs11_slot = other.pkcs11_slot;
+        }
+        if other.pkcs11_key.is_some() {
+            self.pkcs11_key = other.pkcs11_key.clone();
+        }
+        if other.kms_region.is_some() {
+            self.kms_region = other.kms_region.clone();
+        }
+        if other.kms_certificate.is_some() {
+            self.kms_certificate = other.kms_certificate.clone();
+        }
+        if other.name.is_some() {
+            self.name = other.name.clone();
+        }
+        if other.reason.is_some() {
+            self.reason = other.reason.clone();
+        }
+        if other.location.is_some() {
+            self.location = other.location.clone();
+        }
+        if other.contact_info.is_some() {
+            self.contact_info = other.contact_info.clone();
+        }
+        if other.page.is_some() {
+            self.page = other.page;
+            self.field_name = None;
+        }
+        // rect, position, dan invisible juga saling menggantikan
+        if other.rect.is_some() {
+            self.rect = other.rect.clone();
+            self.position = None;
+            self.invisible = None;
+            self.field_name = None;
+        }
+        if other.position.is_some() {
+            self.position = other.position;
+            self.rect = None;
+            self.invisible = None;
+            self.field_name = None;
+        }
+        // field_name menggantikan page, rect, dan position
+        if other.field_name.is_some() {
+            self.field_name = other.field_name.clone();
+            self.page = None;
+            self.rect = None;
+            self.position = None;
+        }
+        if other.invisible == Some(true) {
+            self.invisible = Some(true);
+            self.rect = None;
+            self.position = None;
+        } else if other.invisible.is_some() {
+            self.invisible = other.invisible;
+        }
+        if other.stamp_only.is_some() {
+            self.stamp_only = other.stamp_only;
+        }
+        if other.widget_flags.is_some() {
+            self.widget_flags = other.widget_flags.clone();
+        }
+        if other.stamp_flags.is_some() {
+            self.stamp_flags = other.stamp_flags.clone();
+        }
+        // Daftar aturan posisi diganti seluruhnya, tidak digabung
+        if other.placement_rules.is_some() {
+            self.placement_rules = other.placement_rules.clone();
+        }
+        // appearance_languages, appearance_template, dan appearance_template_file
+        // saling menggantikan
+        if other.appearance_languages.is_some() {
+            self.appearance_languages = other.appearance_languages.clone();
+            self.appearance_template = None;
+            self.appearance_template_file = None;
+        }
+        if other.appearance_template.is_some() {
+            self.appearance_template = other.appearance_template.clone();
+            self.appearance_languages = None;
+            self.appearance_template_file = None;
+        }
+        if other.appearance_template_file.is_some() {
+            self.appearance_template_file = other.appearance_template_file.clone();
+            self.appearance_languages = None;
+            self.appearance_template = None;
+        }
+        if other.appearance_font_size.is_some() {
+            self.appearance_font_size = other.appearance_font_size;
+        }
+        if other.appearance_color.is_some() {
+            self.appearance_color = other.appearance_color.clone();
+        }
+        if other.appearance_image.is_some() {
+            self.appearance_image = other.appearance_image.clone();
+        }
+        if other.appearance_font.is_some() {
+            self.appearance_font = other.appearance_font.clone();
+        }
+        if other.need_appearances.is_some() {
+            self.need_appearances = other.need_appearances;
+        }
+        if other.strict.is_some() {
+            self.strict = other.strict;
+        }
+        if other.drop_xfa.is_some() {
+            self.drop_xfa = other.drop_xfa;
+        }
+        if other.strict_parse.is_some() {
+            self.strict_parse = other.strict_parse;
+        }
+        if other.repair.is_some() {
+            self.repair = other.repair;
+        }
+        if other.classify_hook.is_some() {
+            self.classify_hook = other.classify_hook.clone();
+        }
+        if other.require_pdfa.is_some() {
+            self.require_pdfa = other.require_pdfa;
+        }
+        if other.rsa_padding.is_some() {
+            self.rsa_padding = other.rsa_padding;
+        }
+        if other.digest.is_some() {
+            self.digest = other.digest;
+        }
+        if other.embed_chain.is_some() {
+            self.embed_chain = other.embed_chain;
+        }
+        if other.allow_weak.is_some() {
+            self.allow_weak = other.allow_weak.clone();
+        }
+        if other.tsa_url.is_some() {
+            self.tsa_url = other.tsa_url.clone();
+        }
+        if other.tsa_hash.is_some() {
+            self.tsa_hash = other.tsa_hash;
+        }
+        // Login Basic dan bearer token TSA saling menggantikan
+        if other.tsa_username.is_some() || other.tsa_password_env.is_some() {
+            self.tsa_username = other.tsa_username.clone().or(self.tsa_username.take());
+            self.tsa_password_env = other.tsa_password_env.clone().or(self.tsa_password_env.take());
+            self.tsa_token_env = None;
+        }
+        if other.tsa_token_env.is_some() {
+            self.tsa_token_env = other.tsa_token_env.clone();
+            self.tsa_username = None;
+            self.tsa_password_env = None;
+        }
+        if other.pades_level.is_some() {
+            self.pades_level = other.pades_level;
+        }
+        if other.ltv_revocation.is_some() {
+            self.ltv_revocation = other.ltv_revocation;
+        }
+        if other.ltv_best_effort.is_some() {
+            self.ltv_best_effort = other.ltv_best_effort;
+        }
+        if other.certify.is_some() {
+            self.certify = other.certify;
+        }
+        if other.lock_fields.is_some() {
+            self.lock_fields = other.lock_fields.clone();
+        }
+        if other.object_streams.is_some() {
+            self.object_streams = other.object_streams;
+        }
+        if other.sign_attachments.is_some() {
+            self.sign_attachments = other.sign_attachments;
+        }
+        if other.placeholder_size.is_some() {
+            self.placeholder_size = other.placeholder_size;
+        }
+        if other.warn_growth.is_some() {
+            self.warn_growth = other.warn_growth.clone();
+        }
+        if other.warn_objects.is_some() {
+            self.warn_objects = other.warn_objects;
+        }
+        if other.retries.is_some() {
+            self.retries = other.retries;
+        }
+        if other.retry_backoff.is_some() {
+            self.retry_backoff = other.retry_backoff;
+        }
+        if other.retry_jitter.is_some() {
+            self.retry_jitter = other.retry_jitter;
+        }
+        if other.temp_strategy.is_some() {
+            self.temp_strategy = other.temp_strategy;
+        }
+        if other.temp_dir.is_some() {
+            self.temp_dir = other.temp_dir.clone();
+        }
+    }
+}
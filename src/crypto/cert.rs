@@ -0,0 +1,369 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use sha2::{Digest, Sha256}; // SHA-256 hashing
+use std::fs; // Untuk membaca dan menulis file sertifikat
+use std::str::FromStr; // Parsing subject RFC 4514
+use std::time::{Duration, SystemTime}; // Masa berlaku sertifikat
+use x509_cert::der::asn1::{Any, BitString, Ia5StringRef, ObjectIdentifier, OctetString, PrintableStringRef, SetOfVec, UtcTime, Utf8StringRef}; // Tipe ASN.1
+use x509_cert::der::flagset::FlagSet; // Kumpulan flag key usage
+use x509_cert::der::oid::AssociatedOid; // OID dari tipe extension
+use x509_cert::der::{Decode, EncodePem, Encode, pem::LineEnding}; // Encoding DER/PEM
+use x509_cert::ext::pkix::{BasicConstraints, KeyUsage, KeyUsages, SubjectKeyIdentifier}; // Extension X.509
+use x509_cert::ext::Extension; // Extension generik
+use x509_cert::name::{Name, RdnSequence, RelativeDistinguishedName}; // Subject/issuer
+use x509_cert::request::{CertReq, CertReqInfo}; // Certificate signing request PKCS#10
+use x509_cert::attr::AttributeTypeAndValue; // Satu komponen nama (CN, O, C)
+use x509_cert::serial_number::SerialNumber; // Serial number sertifikat
+use x509_cert::time::{Time, Validity}; // Masa berlaku
+use x509_cert::{Certificate, TbsCertificate, Version}; // Struktur sertifikat
+
+use crate::crypto::cms::split_certificates; // Rantai sertifikat di certificate.der
+use crate::crypto::digest::DigestAlgorithm; // Digest signature sertifikat
+use crate::crypto::policy::AlgorithmPolicy; // Tolak kunci lemah
+use crate::crypto::provider; // Serial number acak
+use crate::crypto::rsa::RsaPadding; // Padding default untuk kunci RSA
+use crate::crypto::signer::{load_signer, Signer}; // Backend penandatanganan (ECDSA / RSA)
+use crate::pdf::naming; // Awal masa berlaku (mode deterministik)
+use crate::pdf::text::{self, json_string}; // Escape string JSON, waktu RFC 3339
+
+// OID yang dipakai di sertifikat
+const ID_AT_COMMON_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.3");
+const ID_AT_ORGANIZATION: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.10");
+const ID_AT_COUNTRY: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.6");
+// Netscape Comment: teks bebas yang ditampilkan `openssl x509 -text`
+const NETSCAPE_COMMENT: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.113730.1.13");
+
+/// Isi extension Netscape Comment yang menandai sertifikat `generate-cert`
+/// sebagai kredensial uji
+pub const TEST_CREDENTIAL_MARKER: &str = "pdfsign test credential - not for production use";
+
+/// Flag key usage yang bisa dipilih untuk sertifikat
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum KeyUsageFlag {
+    /// Verifikasi signature digital
+    DigitalSignature,
+    /// Non-repudiation / content commitment (umum untuk tanda tangan dokumen)
+    NonRepudiation,
+    /// Enkripsi kunci
+    KeyEncipherment,
+    /// Key agreement (ECDH)
+    KeyAgreement,
+    /// Menandatangani sertifikat lain (CA)
+    KeyCertSign,
+    /// Menandatangani CRL (CA)
+    CrlSign,
+}
+
+/// Ringkasan sertifikat penandatangan (untuk inspect dan output JSON)
+pub struct CertificateInfo {
+    pub subject: String,        // Subject DN
+    pub issuer: String,         // Issuer DN
+    pub serial: String,         // Serial number (hex)
+    pub not_before: SystemTime, // Awal masa berlaku
+    pub not_after: SystemTime,  // Akhir masa berlaku
+}
+
+impl CertificateInfo {
+    /// Ringkasan dari sertifikat DER; None jika tidak bisa diurai
+    pub fn from_der(der: &[u8]) -> Option<CertificateInfo> {
+        let tbs = Certificate::from_der(der).ok()?.tbs_certificate;
+        Some(CertificateInfo {
+            subject: tbs.subject.to_string(),
+            issuer: tbs.issuer.to_string(),
+            serial: tbs.serial_number.as_bytes().iter().map(|b| format!("{:02X}", b)).collect(),
+            not_before: tbs.validity.not_before.to_system_time(),
+            not_after: tbs.validity.not_after.to_system_time(),
+        })
+    }
+
+    /// Objek JSON satu baris (inspect, output JSON sign/verify)
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"subject\": {}, \"issuer\": {}, \"serial\": {}, \"not_before\": {}, \"not_after\": {}}}",
+            json_string(&self.subject),
+            json_string(&self.issuer),
+            json_string(&self.serial),
+            json_string(&text::machine_time(self.not_before)),
+            json_string(&text::machine_time(self.not_after)),
+        )
+    }
+
+    /// Masa berlaku untuk output teks: "<awal> to <akhir>" (UTC)
+    pub fn validity(&self) -> String {
+        format!("{} to {}", text::human_time(self.not_before), text::human_time(self.not_after))
+    }
+}
+
+/// Baca sertifikat dari file: PEM (satu atau beberapa blok) atau DER yang digabung
+pub fn read_certificates(path: &str) -> Result<Vec<Vec<u8>>> {
+    let bytes = fs::read(path).with_context(|| format!("cannot read certificate {}", path))?;
+    let certificates = if bytes.windows(11).any(|window| window == b"-----BEGIN ") {
+        Certificate::load_pem_chain(&bytes)
+            .map_err(|e| anyhow!("invalid PEM certificate in {}: {}", path, e))?
+            .iter()
+            .map(|cert| cert.to_der().map_err(|e| anyhow!("{}", e)))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        split_certificates(&bytes).with_context(|| format!("invalid certificate {}", path))?
+    };
+    if certificates.is_empty() {
+        bail!("no certificate found in {}", path);
+    }
+    Ok(certificates)
+}
+
+/// Parameter untuk sertifikat self-signed
+pub struct CertificateParams {
+    pub common_name: String,          // CN
+    pub organization: Option<String>, // O (opsional)
+    pub country: Option<String>,      // C, kode negara 2 huruf (opsional)
+    pub validity_days: u32,           // Masa berlaku dalam hari
+    pub key_usage: Vec<KeyUsageFlag>, // Flag key usage
+}
+
+/// Fungsi untuk membuat sertifikat X.509 self-signed dari kunci privat (ECDSA, Ed25519, atau RSA)
+/// Output: file DER (path `der_path`) dan PEM (path yang sama dengan ekstensi .pem)
+///
+/// Parameter:
+///   - key_path: path file kunci privat (private.key)
+///   - passphrase: passphrase jika kunci privat terenkripsi
+///   - der_path: path file sertifikat DER yang akan ditulis
+///   - params: subject, masa berlaku, dan key usage
+pub fn generate_certificate(key_path: &str, passphrase: Option<&str>, der_path: &str, params: &CertificateParams) -> Result<()> {
+    let signer = load_signer(key_path, RsaPadding::default(), passphrase)?;
+    // Sertifikat untuk kunci lemah tidak akan bisa dipakai `sign` dengan kebijakan default
+    if let Some(violation) = AlgorithmPolicy::default().check_key(&signer.public_key_info()?, "key") {
+        bail!("refusing to certify a weak key: {}", violation);
+    }
+    let cert = self_signed_certificate(signer.as_ref(), params)?;
+
+    // Simpan dalam format DER (dipakai saat signing) dan PEM (untuk tool lain)
+    let pem_path = match der_path.strip_suffix(".der") {
+        Some(stem) => format!("{}.pem", stem),
+        None => format!("{}.pem", der_path),
+    };
+    fs::write(der_path, cert.to_der().map_err(|e| anyhow!("certificate encoding failed: {}", e))?)?;
+    fs::write(&pem_path, cert.to_pem(LineEnding::LF).map_err(|e| anyhow!("certificate encoding failed: {}", e))?)?;
+
+    // Tampilkan pesan sukses ke user
+    crate::info!("Certificate generated: {} & {} (self-signed, {} days)", der_path, pem_path, params.validity_days);
+    crate::info!("Marked as a test credential: signing with it needs --allow-test-credentials");
+    Ok(())
+}
+
+/// Fungsi untuk membuat certificate signing request (PKCS#10, PEM) dari kunci
+/// privat, untuk diajukan ke CA agar diterbitkan sertifikat yang dipercaya
+///
+/// Parameter:
+///   - key_path: path file kunci privat (private.key)
+///   - passphrase: passphrase jika kunci privat terenkripsi
+///   - csr_path: path file CSR (PEM) yang akan ditulis
+///   - subject: subject dalam format RFC 4514, misalnya "CN=Jane Doe,O=Acme,C=ID"
+pub fn generate_csr(key_path: &str, passphrase: Option<&str>, csr_path: &str, subject: &str) -> Result<()> {
+    let signer = load_signer(key_path, RsaPadding::default(), passphrase)?;
+    // CA menolak kunci lemah, dan `sign` juga tidak akan memakainya
+    if let Some(violation) = AlgorithmPolicy::default().check_key(&signer.public_key_info()?, "key") {
+        bail!("refusing to request a certificate for a weak key: {}", violation);
+    }
+    let subject = parse_subject(subject)?;
+    let request = certificate_request(signer.as_ref(), &subject)?;
+    fs::write(csr_path, request.to_pem(LineEnding::LF).map_err(|e| anyhow!("certificate request encoding failed: {}", e))?)?;
+
+    // Tampilkan pesan sukses ke user
+    crate::info!("Certificate signing request generated: {} (subject: {})", csr_path, subject);
+    Ok(())
+}
+
+/// Baca certificate.der di direktori yang sama dengan kunci privat (opsional,
+/// bisa dibuat dengan `pdfsign generate-cert`)
+///
+/// File boleh berisi beberapa sertifikat DER berurutan: penandatangan dulu, lalu rantainya
+/// Return: daftar sertifikat DER, kosong jika file tidak ada
+pub fn certificates_for_key(key_path: impl AsRef<std::path::Path>) -> Result<Vec<Vec<u8>>> {
+    let cert_path = key_path.as_ref().with_file_name("certificate.der");
+    match fs::read(&cert_path) {
+        Ok(bytes) => split_certificates(&bytes),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Subject sertifikat pertama di `certificates` yang ditandai sebagai
+/// kredensial uji (`TEST_CREDENTIAL_MARKER`); None jika tidak ada
+pub fn test_credential(certificates: &[Vec<u8>]) -> Option<String> {
+    certificates.iter().filter_map(|der| Certificate::from_der(der).ok()).find_map(|cert| {
+        let marked = cert.tbs_certificate.extensions.iter().flatten().any(|ext| {
+            ext.extn_id == NETSCAPE_COMMENT
+                && Ia5StringRef::from_der(ext.extn_value.as_bytes()).is_ok_and(|comment| comment.as_str() == TEST_CREDENTIAL_MARKER)
+        });
+        marked.then(|| cert.tbs_certificate.subject.to_string())
+    })
+}
+
+/// Tolak signing dengan kredensial uji (sertifikat penandatangan atau
+/// rantainya dibuat `generate-cert`) kecuali diizinkan
+/// (--allow-test-credentials)
+pub fn check_test_credentials(certificates: &[Vec<u8>], allowed: bool) -> Result<()> {
+    match test_credential(certificates) {
+        Some(subject) if !allowed => bail!(
+            "the certificate {} is a test credential made by `pdfsign generate-cert`; pass --allow-test-credentials to sign with it anyway",
+            subject
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Bangun sertifikat self-signed (issuer = subject) untuk kunci milik `signer`
+pub fn self_signed_certificate(signer: &dyn Signer, params: &CertificateParams) -> Result<Certificate> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("certificate encoding failed: {}", e);
+    if params.common_name.trim().is_empty() {
+        bail!("certificate common name (CN) must not be empty");
+    }
+    if params.validity_days == 0 {
+        bail!("certificate validity must be at least one day");
+    }
+    if params.key_usage.is_empty() {
+        bail!("at least one key usage flag is required");
+    }
+
+    // Subject = issuer: CN, O, C
+    let mut rdns = Vec::new();
+    if let Some(country) = &params.country {
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            bail!("country must be a two-letter code (e.g. ID)");
+        }
+        let value = Any::encode_from(&PrintableStringRef::new(country).map_err(der_err)?).map_err(der_err)?;
+        rdns.push(rdn(ID_AT_COUNTRY, value)?);
+    }
+    if let Some(organization) = &params.organization {
+        let value = Any::encode_from(&Utf8StringRef::new(organization).map_err(der_err)?).map_err(der_err)?;
+        rdns.push(rdn(ID_AT_ORGANIZATION, value)?);
+    }
+    let value = Any::encode_from(&Utf8StringRef::new(&params.common_name).map_err(der_err)?).map_err(der_err)?;
+    rdns.push(rdn(ID_AT_COMMON_NAME, value)?);
+    let name: Name = RdnSequence(rdns);
+
+    // Kunci publik pasangan kunci privat
+    let spki = signer.public_key_info()?;
+
+    // Serial number acak 16 bytes, bit teratas dinolkan agar selalu positif
+    let mut serial = [0u8; 16];
+    provider::fill_random(&mut serial);
+    serial[0] &= 0x7f;
+    serial[0] |= 0x01;
+
+    // Masa berlaku mulai sekarang (mode deterministik: waktu signing tetap)
+    let now = SystemTime::from(naming::now());
+    let validity = Validity {
+        not_before: x509_time(now)?,
+        not_after: x509_time(now + Duration::from_secs(u64::from(params.validity_days) * 24 * 60 * 60))?,
+    };
+
+    // Extensions: basicConstraints (bukan CA), keyUsage, subjectKeyIdentifier,
+    // dan penanda kredensial uji
+    let is_ca = params.key_usage.contains(&KeyUsageFlag::KeyCertSign);
+    let key_usage = KeyUsage(params.key_usage.iter().fold(FlagSet::<KeyUsages>::default(), |flags, flag| {
+        flags
+            | match flag {
+                KeyUsageFlag::DigitalSignature => KeyUsages::DigitalSignature,
+                KeyUsageFlag::NonRepudiation => KeyUsages::NonRepudiation,
+                KeyUsageFlag::KeyEncipherment => KeyUsages::KeyEncipherment,
+                KeyUsageFlag::KeyAgreement => KeyUsages::KeyAgreement,
+                KeyUsageFlag::KeyCertSign => KeyUsages::KeyCertSign,
+                KeyUsageFlag::CrlSign => KeyUsages::CRLSign,
+            }
+    }));
+    let key_id = SubjectKeyIdentifier(
+        OctetString::new(&Sha256::digest(spki.subject_public_key.raw_bytes())[..20]).map_err(der_err)?,
+    );
+    let extensions = vec![
+        extension(BasicConstraints::OID, true, &BasicConstraints { ca: is_ca, path_len_constraint: None })?,
+        extension(KeyUsage::OID, true, &key_usage)?,
+        extension(SubjectKeyIdentifier::OID, false, &key_id)?,
+        extension(NETSCAPE_COMMENT, false, &Ia5StringRef::new(TEST_CREDENTIAL_MARKER).map_err(der_err)?)?,
+    ];
+
+    // Sertifikat ditandatangani dengan SHA-256, kecuali signer hanya mendukung
+    // digest tertentu (Ed25519, ECDSA P-384 di backend ring)
+    let digest = signer.digest_for(DigestAlgorithm::Sha256);
+    let signature_algorithm = signer.signature_algorithm(digest)?;
+    let tbs_certificate = TbsCertificate {
+        version: Version::V3,
+        serial_number: SerialNumber::new(&serial).map_err(der_err)?,
+        signature: signature_algorithm.clone(),
+        issuer: name.clone(),
+        validity,
+        subject: name,
+        subject_public_key_info: spki,
+        issuer_unique_id: None,
+        subject_unique_id: None,
+        extensions: Some(extensions),
+    };
+
+    // Tandatangani TBSCertificate dengan kunci privat itu sendiri (self-signed)
+    let signature = signer.sign_message(&tbs_certificate.to_der().map_err(der_err)?, digest)?;
+
+    Ok(Certificate {
+        tbs_certificate,
+        signature_algorithm,
+        signature: BitString::from_bytes(&signature).map_err(der_err)?,
+    })
+}
+
+/// Bangun certificate signing request (PKCS#10) untuk kunci milik `signer`,
+/// ditandatangani dengan kunci itu sendiri sebagai bukti kepemilikan
+pub fn certificate_request(signer: &dyn Signer, subject: &Name) -> Result<CertReq> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("certificate request encoding failed: {}", e);
+    let info = CertReqInfo {
+        version: x509_cert::request::Version::V1,
+        subject: subject.clone(),
+        public_key: signer.public_key_info()?,
+        attributes: SetOfVec::new(),
+    };
+    // CSR ditandatangani dengan digest yang sama seperti sertifikat self-signed
+    let digest = signer.digest_for(DigestAlgorithm::Sha256);
+    let algorithm = signer.signature_algorithm(digest)?;
+    let signature = signer.sign_message(&info.to_der().map_err(der_err)?, digest)?;
+    Ok(CertReq {
+        info,
+        algorithm,
+        signature: BitString::from_bytes(&signature).map_err(der_err)?,
+    })
+}
+
+/// Parse subject RFC 4514 ("CN=Jane Doe,O=Acme"); harus berisi CN
+fn parse_subject(subject: &str) -> Result<Name> {
+    let name = RdnSequence::from_str(subject)
+        .map_err(|e| anyhow!("invalid subject '{}' ({}); expected e.g. \"CN=Jane Doe,O=Acme,C=ID\"", subject, e))?;
+    let has_common_name = name.0.iter().flat_map(|rdn| rdn.0.iter()).any(|atv| atv.oid == ID_AT_COMMON_NAME && !atv.value.value().is_empty());
+    if !has_common_name {
+        bail!("the subject must contain a non-empty common name (CN=...)");
+    }
+    Ok(name)
+}
+
+/// Waktu X.509: UTCTime sampai tahun 2049, GeneralizedTime setelahnya (RFC 5280)
+fn x509_time(time: SystemTime) -> Result<Time> {
+    let result = match UtcTime::from_system_time(time) {
+        Ok(utc) => Ok(Time::UtcTime(utc)),
+        Err(_) => Time::try_from(time),
+    };
+    result.map_err(|e| anyhow!("invalid certificate validity: {}", e))
+}
+
+/// Buat satu RDN berisi satu atribut
+fn rdn(oid: ObjectIdentifier, value: Any) -> Result<RelativeDistinguishedName> {
+    let set = SetOfVec::try_from(vec![AttributeTypeAndValue { oid, value }])
+        .map_err(|e| anyhow!("certificate encoding failed: {}", e))?;
+    Ok(RelativeDistinguishedName(set))
+}
+
+/// Buat Extension dari nilai yang di-encode DER
+fn extension(oid: ObjectIdentifier, critical: bool, value: &impl Encode) -> Result<Extension> {
+    let der = value.to_der().map_err(|e| anyhow!("certificate encoding failed: {}", e))?;
+    Ok(Extension {
+        extn_id: oid,
+        critical,
+        extn_value: OctetString::new(der).map_err(|e| anyhow!("certificate encoding failed: {}", e))?,
+    })
+}
@@ -0,0 +1,502 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber}; // Identitas penandatangan dan sertifikat
+use cms::content_info::{CmsVersion, ContentInfo}; // Wrapper ContentInfo
+use der::Sequence; // Derive struktur ASN.1 ESS
+use cms::signed_data::{
+    CertificateSet, EncapsulatedContentInfo, SignedData, SignerIdentifier, SignerInfo, SignerInfos,
+}; // Struktur SignedData (RFC 5652)
+use sha2::{Digest, Sha256}; // SHA-256 hashing
+use x509_cert::attr::Attribute; // Signed attributes
+use x509_cert::der::asn1::{Any, ObjectIdentifier, OctetString, SetOfVec, UtcTime}; // Tipe ASN.1
+use x509_cert::der::{DateTime, Decode, Encode, Reader, SliceReader}; // Encoding/decoding DER
+use x509_cert::ext::pkix::name::GeneralName; // Nama issuer di IssuerSerial
+use x509_cert::ext::pkix::SubjectKeyIdentifier; // Identitas penandatangan tanpa sertifikat
+use x509_cert::serial_number::SerialNumber; // Serial sertifikat di IssuerSerial
+use x509_cert::spki::AlgorithmIdentifierOwned; // Identifier algoritma
+use x509_cert::time::Time; // Waktu penandatanganan
+use x509_cert::Certificate; // Sertifikat X.509
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::signer::Signer; // Backend penandatanganan (ECDSA / RSA)
+
+// OID yang dipakai di SignedData
+const ID_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.1");
+const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+const ID_CONTENT_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.3");
+const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const ID_SIGNING_TIME: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.5");
+const ID_SIGNATURE_TIME_STAMP_TOKEN: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.2.14");
+const ID_SIGNING_CERTIFICATE_V2: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.2.47");
+
+/// Kumpulan signed attributes yang ditulis di SignerInfo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignedAttributes {
+    /// contentType, signingTime, messageDigest (adbe.pkcs7.detached)
+    Pkcs7,
+    /// CAdES untuk PAdES (EN 319 142-1): contentType, messageDigest, signing-certificate-v2;
+    /// tanpa signingTime karena waktu klaim penandatanganan ada di /M
+    Cades,
+}
+
+/// Sertifikat mana saja yang disisipkan di SignedData
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum EmbedChain {
+    /// Seluruh rantai seperti di certificate.der / bundle PKCS#12
+    #[default]
+    Full,
+    /// Hanya sertifikat penandatangan
+    LeafOnly,
+    /// Seluruh rantai kecuali root (sertifikat self-signed)
+    NoRoot,
+}
+
+impl EmbedChain {
+    /// Pilih sertifikat yang disisipkan dari rantai (penandatangan selalu yang pertama)
+    ///
+    /// Sertifikat penandatangan selalu dipertahankan, juga jika ia self-signed
+    pub fn select(self, certificates: &[Vec<u8>]) -> Result<Vec<Vec<u8>>> {
+        let Some((leaf, chain)) = certificates.split_first() else {
+            return Ok(Vec::new());
+        };
+        let mut selected = vec![leaf.clone()];
+        match self {
+            EmbedChain::Full => selected.extend(chain.iter().cloned()),
+            EmbedChain::LeafOnly => {}
+            EmbedChain::NoRoot => {
+                for der in chain {
+                    let cert = Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+                    if cert.tbs_certificate.subject != cert.tbs_certificate.issuer {
+                        selected.push(der.clone());
+                    }
+                }
+            }
+        }
+        Ok(selected)
+    }
+}
+
+/// ESS SigningCertificateV2 (RFC 5035): mengikat sertifikat penandatangan ke signature
+#[derive(Sequence)]
+struct SigningCertificateV2 {
+    certs: Vec<EssCertIdV2>,
+}
+
+/// ESSCertIDv2 dengan hashAlgorithm default (SHA-256), sehingga tidak ditulis
+#[derive(Sequence)]
+struct EssCertIdV2 {
+    cert_hash: OctetString,
+    issuer_serial: IssuerSerial,
+}
+
+/// Issuer dan serial sertifikat penandatangan
+#[derive(Sequence)]
+struct IssuerSerial {
+    issuer: Vec<GeneralName>,
+    serial_number: SerialNumber,
+}
+
+/// Hasil parsing SignedData yang dibutuhkan untuk verifikasi
+pub struct ParsedSignedData {
+    pub signature: Vec<u8>,              // Nilai signature
+    pub signature_algorithm: AlgorithmIdentifierOwned, // Algoritma signature (ECDSA / RSA)
+    pub digest_algorithm: AlgorithmIdentifierOwned,    // digestAlgorithm SignerInfo
+    pub signed_digest: Vec<u8>,          // Digest yang benar-benar ditandatangani (hanya bermakna untuk SHA-2)
+    pub signed_attributes: Option<Vec<u8>>, // DER SET signed attributes (pesan yang ditandatangani Ed25519)
+    pub message_digest: Option<Vec<u8>>, // Atribut messageDigest (digest dokumen)
+    pub signer_certificate: Option<Vec<u8>>, // Sertifikat penandatangan (DER), jika tertanam
+    pub certificates: Vec<Vec<u8>>,          // Semua sertifikat yang tertanam (DER)
+    pub timestamp_token: Option<Vec<u8>>,    // TimeStampToken RFC 3161 (unsigned attribute), jika ada
+}
+
+impl ParsedSignedData {
+    /// digestAlgorithm SignerInfo; None jika bukan SHA-256/384/512 (tidak bisa diverifikasi)
+    pub fn digest(&self) -> Option<DigestAlgorithm> {
+        DigestAlgorithm::from_oid(&self.digest_algorithm.oid)
+    }
+
+    /// Apakah digestAlgorithm-nya bisa diverifikasi
+    pub fn digest_supported(&self) -> bool {
+        self.digest().is_some()
+    }
+}
+
+/// Bangun CMS SignedData (detached) untuk digest dokumen
+///
+/// Parameter:
+///   - digest: digest dari bagian dokumen yang ditunjuk ByteRange
+///   - digest_algorithm: algoritma `digest`, juga untuk signed attributes
+///   - signer: backend penandatanganan (ECDSA, Ed25519, atau RSA)
+///   - certificates: sertifikat DER; yang pertama adalah sertifikat penandatangan,
+///     sisanya rantai (intermediate) yang ikut disisipkan
+///   - signing_time: waktu penandatanganan (atribut signingTime)
+///   - attributes: signed attributes gaya PKCS#7 atau CAdES (PAdES, butuh sertifikat)
+///
+/// Return: ContentInfo berisi SignedData dalam format DER
+pub fn build_signed_data(
+    digest: &[u8],
+    digest_algorithm: DigestAlgorithm,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    signing_time: chrono::DateTime<chrono::Utc>,
+    attributes: SignedAttributes,
+) -> Result<Vec<u8>> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("CMS encoding failed: {}", e);
+    let digest_alg = AlgorithmIdentifierOwned { oid: digest_algorithm.oid(), parameters: None };
+
+    // Identitas penandatangan: issuer + serial dari sertifikat, atau
+    // subjectKeyIdentifier (160 bit pertama SHA-256 kunci publik, RFC 7093) jika tanpa sertifikat
+    let parsed_certs = certificates
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e)))
+        .collect::<Result<Vec<Certificate>>>()?;
+    let (version, sid) = match parsed_certs.first() {
+        Some(cert) => (
+            CmsVersion::V1,
+            SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+                issuer: cert.tbs_certificate.issuer.clone(),
+                serial_number: cert.tbs_certificate.serial_number.clone(),
+            }),
+        ),
+        None => {
+            let public_key = signer.public_key_info()?;
+            let key_id = OctetString::new(&Sha256::digest(public_key.subject_public_key.raw_bytes())[..20]).map_err(der_err)?;
+            (CmsVersion::V3, SignerIdentifier::SubjectKeyIdentifier(SubjectKeyIdentifier(key_id)))
+        }
+    };
+
+    let signed_attrs = signed_attributes(digest, certificates, &parsed_certs, signing_time, attributes)?;
+
+    // Yang ditandatangani adalah DER dari SET signed attributes (RFC 5652 5.4);
+    // ECDSA/RSA menandatangani digest-nya, Ed25519 pesannya langsung (RFC 8419)
+    let signature = signer.sign_message(&signed_attrs.to_der().map_err(der_err)?, digest_algorithm)?;
+
+    let signer_info = SignerInfo {
+        version,
+        sid,
+        digest_alg: digest_alg.clone(),
+        signed_attrs: Some(signed_attrs),
+        signature_algorithm: signer.signature_algorithm(digest_algorithm)?,
+        signature: OctetString::new(signature).map_err(der_err)?,
+        unsigned_attrs: None,
+    };
+
+    let certificate_set = if parsed_certs.is_empty() {
+        None
+    } else {
+        Some(CertificateSet(
+            SetOfVec::try_from(parsed_certs.into_iter().map(CertificateChoices::Certificate).collect::<Vec<_>>())
+                .map_err(der_err)?,
+        ))
+    };
+
+    let signed_data = SignedData {
+        // Versi 3 jika signer memakai subjectKeyIdentifier, selain itu 1
+        version,
+        digest_algorithms: SetOfVec::try_from(vec![digest_alg]).map_err(der_err)?,
+        // Detached: tidak ada eContent, isi dokumen ada di PDF itu sendiri
+        encap_content_info: EncapsulatedContentInfo { econtent_type: ID_DATA, econtent: None },
+        certificates: certificate_set,
+        crls: None,
+        signer_infos: SignerInfos(SetOfVec::try_from(vec![signer_info]).map_err(der_err)?),
+    };
+
+    ContentInfo {
+        content_type: ID_SIGNED_DATA,
+        content: Any::encode_from(&signed_data).map_err(der_err)?,
+    }
+    .to_der()
+    .map_err(der_err)
+}
+
+/// DER dari SET signed attributes, yaitu bytes yang ditandatangani signer
+/// (RFC 5652 5.4); untuk signing eksternal dengan signature raw
+///
+/// Parameter: sama seperti `build_signed_data`
+pub fn signed_attributes_der(
+    digest: &[u8],
+    certificates: &[Vec<u8>],
+    signing_time: chrono::DateTime<chrono::Utc>,
+    attributes: SignedAttributes,
+) -> Result<Vec<u8>> {
+    let parsed_certs = certificates
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e)))
+        .collect::<Result<Vec<Certificate>>>()?;
+    signed_attributes(digest, certificates, &parsed_certs, signing_time, attributes)?
+        .to_der()
+        .map_err(|e| anyhow!("CMS encoding failed: {}", e))
+}
+
+/// Signed attributes: contentType, messageDigest, lalu signingTime (PKCS#7)
+/// atau signing-certificate-v2 (CAdES)
+fn signed_attributes(
+    digest: &[u8],
+    certificates: &[Vec<u8>],
+    parsed_certs: &[Certificate],
+    signing_time: chrono::DateTime<chrono::Utc>,
+    attributes: SignedAttributes,
+) -> Result<SetOfVec<Attribute>> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("CMS encoding failed: {}", e);
+    let mut attrs = vec![
+        attribute(ID_CONTENT_TYPE, Any::encode_from(&ID_DATA).map_err(der_err)?)?,
+        attribute(ID_MESSAGE_DIGEST, Any::encode_from(&OctetString::new(digest).map_err(der_err)?).map_err(der_err)?)?,
+    ];
+    match attributes {
+        SignedAttributes::Pkcs7 => {
+            let time = UtcTime::from_date_time(
+                DateTime::from_unix_duration(std::time::Duration::from_secs(signing_time.timestamp().max(0) as u64))
+                    .map_err(der_err)?,
+            )
+            .map_err(der_err)?;
+            attrs.push(attribute(ID_SIGNING_TIME, Any::encode_from(&Time::UtcTime(time)).map_err(der_err)?)?);
+        }
+        SignedAttributes::Cades => {
+            let (Some(cert_der), Some(cert)) = (certificates.first(), parsed_certs.first()) else {
+                bail!("PAdES signatures need the signer certificate (certificate.der or --p12)");
+            };
+            let signing_certificate = SigningCertificateV2 {
+                certs: vec![EssCertIdV2 {
+                    cert_hash: OctetString::new(Sha256::digest(cert_der).to_vec()).map_err(der_err)?,
+                    issuer_serial: IssuerSerial {
+                        issuer: vec![GeneralName::DirectoryName(cert.tbs_certificate.issuer.clone())],
+                        serial_number: cert.tbs_certificate.serial_number.clone(),
+                    },
+                }],
+            };
+            attrs.push(attribute(ID_SIGNING_CERTIFICATE_V2, Any::encode_from(&signing_certificate).map_err(der_err)?)?);
+        }
+    }
+    SetOfVec::try_from(attrs).map_err(der_err)
+}
+
+/// Parse ContentInfo/SignedData dan ambil data yang diperlukan untuk verifikasi
+///
+/// Parameter:
+///   - cms_der: ContentInfo DER (tanpa padding nol di belakang)
+pub fn parse_signed_data(cms_der: &[u8]) -> Result<ParsedSignedData> {
+    let content_info = ContentInfo::from_der(cms_der).map_err(|e| anyhow!("invalid CMS structure: {}", e))?;
+    if content_info.content_type != ID_SIGNED_DATA {
+        bail!("CMS content is not SignedData");
+    }
+    let signed_data: SignedData = content_info
+        .content
+        .decode_as()
+        .map_err(|e| anyhow!("invalid SignedData: {}", e))?;
+    let signer = signed_data
+        .signer_infos
+        .0
+        .iter()
+        .next()
+        .ok_or_else(|| anyhow!("SignedData has no SignerInfo"))?;
+    // Cari sertifikat penandatangan berdasarkan issuer + serial
+    let signer_certificate = match &signer.sid {
+        SignerIdentifier::IssuerAndSerialNumber(id) => signed_data.certificates.as_ref().and_then(|set| {
+            set.0.iter().find_map(|choice| match choice {
+                CertificateChoices::Certificate(cert)
+                    if cert.tbs_certificate.issuer == id.issuer
+                        && cert.tbs_certificate.serial_number == id.serial_number =>
+                {
+                    cert.to_der().ok()
+                }
+                _ => None,
+            })
+        }),
+        SignerIdentifier::SubjectKeyIdentifier(_) => None,
+    };
+
+    // Seluruh sertifikat yang tertanam (rantai penandatangan), misalnya untuk data LTV
+    let certificates = signed_data
+        .certificates
+        .as_ref()
+        .map(|set| {
+            set.0
+                .iter()
+                .filter_map(|choice| match choice {
+                    CertificateChoices::Certificate(cert) => cert.to_der().ok(),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Dengan signed attributes, yang ditandatangani adalah DER dari SET atribut;
+    // tanpa atribut, signature langsung atas digest dokumen
+    let (signed_digest, message_digest, signed_attributes) = match &signer.signed_attrs {
+        Some(attrs) => {
+            let message_digest = attrs
+                .iter()
+                .find(|attr| attr.oid == ID_MESSAGE_DIGEST)
+                .and_then(|attr| attr.values.iter().next())
+                .and_then(|value| value.decode_as::<OctetString>().ok())
+                .map(|digest| digest.as_bytes().to_vec())
+                .ok_or_else(|| anyhow!("signed attributes have no messageDigest"))?;
+            // Digest signed attributes memakai digestAlgorithm SignerInfo
+            let encoded = attrs.to_der().map_err(|e| anyhow!("invalid signed attributes: {}", e))?;
+            let algorithm = DigestAlgorithm::from_oid(&signer.digest_alg.oid).unwrap_or_default();
+            (algorithm.digest(&encoded), Some(message_digest), Some(encoded))
+        }
+        None => (Vec::new(), None, None),
+    };
+
+    // Timestamp signature (RFC 3161) disimpan sebagai unsigned attribute
+    let timestamp_token = signer
+        .unsigned_attrs
+        .as_ref()
+        .and_then(|attrs| attrs.iter().find(|attr| attr.oid == ID_SIGNATURE_TIME_STAMP_TOKEN))
+        .and_then(|attr| attr.values.iter().next())
+        .and_then(|value| value.to_der().ok());
+
+    Ok(ParsedSignedData {
+        signature: signer.signature.as_bytes().to_vec(),
+        signature_algorithm: signer.signature_algorithm.clone(),
+        digest_algorithm: signer.digest_alg.clone(),
+        signed_digest,
+        signed_attributes,
+        message_digest,
+        signer_certificate,
+        certificates,
+        timestamp_token,
+    })
+}
+
+/// Tambahkan timestamp signature (RFC 3161) ke SignerInfo sebagai unsigned attribute
+/// id-aa-signatureTimeStampToken
+///
+/// Parameter:
+///   - cms_der: ContentInfo/SignedData dari `build_signed_data`
+///   - timestamp: fungsi yang menerima nilai signature dan mengembalikan
+///     TimeStampToken (DER) atas nilai tersebut, misalnya request ke TSA
+///
+/// Return: ContentInfo baru dalam format DER
+pub fn add_signature_timestamp(cms_der: &[u8], timestamp: impl FnOnce(&[u8]) -> Result<Vec<u8>>) -> Result<Vec<u8>> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("CMS encoding failed: {}", e);
+    let content_info = ContentInfo::from_der(cms_der).map_err(|e| anyhow!("invalid CMS structure: {}", e))?;
+    let mut signed_data: SignedData = content_info.content.decode_as().map_err(|e| anyhow!("invalid SignedData: {}", e))?;
+
+    let mut signer_infos = signed_data.signer_infos.0.into_vec();
+    let signer = signer_infos.first_mut().ok_or_else(|| anyhow!("SignedData has no SignerInfo"))?;
+    let token = timestamp(signer.signature.as_bytes())?;
+    let token = Any::from_der(&token).map_err(|e| anyhow!("invalid TimeStampToken: {}", e))?;
+
+    let mut attrs = signer.unsigned_attrs.take().map(|attrs| attrs.into_vec()).unwrap_or_default();
+    attrs.retain(|attr| attr.oid != ID_SIGNATURE_TIME_STAMP_TOKEN);
+    attrs.push(attribute(ID_SIGNATURE_TIME_STAMP_TOKEN, token)?);
+    signer.unsigned_attrs = Some(SetOfVec::try_from(attrs).map_err(der_err)?);
+    signed_data.signer_infos = SignerInfos(SetOfVec::try_from(signer_infos).map_err(der_err)?);
+
+    ContentInfo {
+        content_type: ID_SIGNED_DATA,
+        content: Any::encode_from(&signed_data).map_err(der_err)?,
+    }
+    .to_der()
+    .map_err(der_err)
+}
+
+/// Pisahkan file sertifikat yang berisi satu atau beberapa sertifikat DER
+/// yang digabung (sertifikat penandatangan dulu, lalu rantainya)
+pub fn split_certificates(bytes: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let mut reader = SliceReader::new(bytes).map_err(|e| anyhow!("invalid certificate file: {}", e))?;
+    let mut certificates = Vec::new();
+    while !reader.is_finished() {
+        let cert = Certificate::decode(&mut reader).map_err(|e| anyhow!("invalid certificate file: {}", e))?;
+        certificates.push(cert.to_der().map_err(|e| anyhow!("invalid certificate file: {}", e))?);
+    }
+    Ok(certificates)
+}
+
+/// Buat satu Attribute dengan satu nilai
+fn attribute(oid: ObjectIdentifier, value: Any) -> Result<Attribute> {
+    Ok(Attribute {
+        oid,
+        values: SetOfVec::try_from(vec![value]).map_err(|e| anyhow!("CMS encoding failed: {}", e))?,
+    })
+}
+
+/// Hitung panjang total satu elemen DER (tag + length + isi), misalnya CMS
+/// SignedData di awal /Contents yang diikuti padding nol
+pub fn der_element_len(bytes: &[u8]) -> Option<usize> {
+    let first = *bytes.get(1)? as usize;
+    let (len, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        let mut len = 0usize;
+        for i in 0..count {
+            len = (len << 8) | *bytes.get(2 + i)? as usize;
+        }
+        (len, 2 + count)
+    };
+    (header + len <= bytes.len()).then_some(header + len)
+}
+
+#[cfg(all(test, feature = "sign"))]
+mod tests {
+    use super::*;
+    use crate::crypto::rsa::RsaPadding;
+    use crate::crypto::signer::signer_from_bytes;
+    use chrono::TimeZone;
+    use p256::ecdsa::signature::hazmat::PrehashVerifier;
+    use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+
+    const KEY: [u8; 32] = [0x11; 32];
+
+    /// CMS tanpa sertifikat untuk digest "document" pada 2000-01-01T00:00:00Z
+    fn build() -> (Vec<u8>, Vec<u8>) {
+        let digest = Sha256::digest(b"document").to_vec();
+        let time = chrono::Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let signer = signer_from_bytes(&KEY, RsaPadding::default()).unwrap();
+        let cms = build_signed_data(&digest, DigestAlgorithm::Sha256, signer.as_ref(), &[], time, SignedAttributes::Pkcs7).unwrap();
+        (cms, digest)
+    }
+
+    fn signer_info(cms: &[u8]) -> SignerInfo {
+        let signed_data: SignedData = ContentInfo::from_der(cms).unwrap().content.decode_as().unwrap();
+        signed_data.signer_infos.0.iter().next().unwrap().clone()
+    }
+
+    /// Nilai tunggal atribut `oid` dalam DER
+    fn value(attrs: &SetOfVec<Attribute>, oid: ObjectIdentifier) -> Vec<u8> {
+        let attr = attrs.iter().find(|attr| attr.oid == oid).unwrap();
+        assert_eq!(attr.values.len(), 1);
+        attr.values.iter().next().unwrap().to_der().unwrap()
+    }
+
+    #[test]
+    fn pkcs7_signed_attributes() {
+        let (cms, digest) = build();
+        let attrs = signer_info(&cms).signed_attrs.unwrap();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(value(&attrs, ID_CONTENT_TYPE), ID_DATA.to_der().unwrap());
+        assert_eq!(value(&attrs, ID_MESSAGE_DIGEST), OctetString::new(digest).unwrap().to_der().unwrap());
+        // UTCTime 000101000000Z
+        assert_eq!(value(&attrs, ID_SIGNING_TIME), b"\x17\x0d000101000000Z");
+    }
+
+    #[test]
+    fn signature_covers_the_der_set_of_attributes() {
+        let (cms, digest) = build();
+        let parsed = parse_signed_data(&cms).unwrap();
+        assert_eq!(parsed.message_digest.as_deref(), Some(digest.as_slice()));
+        // Yang ditandatangani adalah SET OF (tag 0x31), bukan [0] IMPLICIT di SignerInfo
+        let encoded = signer_info(&cms).signed_attrs.unwrap().to_der().unwrap();
+        assert_eq!(encoded[0], 0x31);
+        assert_eq!(parsed.signed_digest, Sha256::digest(&encoded).to_vec());
+        let key = VerifyingKey::from(&SigningKey::from_slice(&KEY).unwrap());
+        key.verify_prehash(&parsed.signed_digest, &Signature::from_der(&parsed.signature).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn signer_without_certificate_uses_key_identifier() {
+        let (cms, _) = build();
+        let info = signer_info(&cms);
+        assert_eq!(info.version, CmsVersion::V3);
+        let SignerIdentifier::SubjectKeyIdentifier(id) = info.sid else {
+            panic!("expected subjectKeyIdentifier");
+        };
+        let public_key = VerifyingKey::from(&SigningKey::from_slice(&KEY).unwrap()).to_encoded_point(false);
+        assert_eq!(id.0.as_bytes(), &Sha256::digest(public_key.as_bytes())[..20]);
+        assert!(parse_signed_data(&cms).unwrap().signer_certificate.is_none());
+    }
+}
@@ -0,0 +1,164 @@
+// Import library yang diperlukan
+use sha2::{Digest, Sha256, Sha384, Sha512}; // Fungsi hash SHA-2
+use x509_cert::der::asn1::ObjectIdentifier; // OID algoritma
+
+use crate::crypto::provider::{self, DigestContext}; // Digest dari provider kriptografi yang terpasang
+
+// OID digest SHA-2
+const ID_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const ID_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const ID_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
+// OID ECDSA dengan SHA-2
+const ID_ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ID_ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ID_ECDSA_WITH_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+// OID RSASSA-PKCS1-v1_5 dengan SHA-2
+const ID_SHA256_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const ID_SHA384_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const ID_SHA512_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+
+/// Algoritma digest untuk signature dokumen (--digest)
+///
+/// Dipakai untuk digest ByteRange, atribut messageDigest, dan signed attributes;
+/// digestAlgorithm di CMS dan hash di algoritma signature selalu mengikuti pilihan ini.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum DigestAlgorithm {
+    /// SHA-256 (default, didukung semua validator)
+    #[default]
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+/// Hash inkremental untuk data besar (misalnya segmen ByteRange)
+pub enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    /// Hash dari provider kriptografi yang terpasang (`provider::set_provider`)
+    Provider(Box<dyn DigestContext>),
+}
+
+impl DigestAlgorithm {
+    /// Semua algoritma yang didukung
+    pub const ALL: [DigestAlgorithm; 3] = [DigestAlgorithm::Sha256, DigestAlgorithm::Sha384, DigestAlgorithm::Sha512];
+
+    /// Nama seperti di command line, misalnya "sha384"
+    pub fn name(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha384 => "sha384",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Kebalikan dari `name`
+    pub fn from_name(name: &str) -> Option<DigestAlgorithm> {
+        DigestAlgorithm::ALL.into_iter().find(|algorithm| algorithm.name() == name)
+    }
+
+    /// Nama untuk ditampilkan ke user, misalnya "SHA-384"
+    pub fn label(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha384 => "SHA-384",
+            DigestAlgorithm::Sha512 => "SHA-512",
+        }
+    }
+
+    /// OID digest (digestAlgorithm di CMS)
+    pub fn oid(self) -> ObjectIdentifier {
+        match self {
+            DigestAlgorithm::Sha256 => ID_SHA256,
+            DigestAlgorithm::Sha384 => ID_SHA384,
+            DigestAlgorithm::Sha512 => ID_SHA512,
+        }
+    }
+
+    /// Algoritma dari OID digest; None untuk digest yang tidak didukung
+    pub fn from_oid(oid: &ObjectIdentifier) -> Option<DigestAlgorithm> {
+        DigestAlgorithm::ALL.into_iter().find(|algorithm| algorithm.oid() == *oid)
+    }
+
+    /// OID ECDSA dengan digest ini
+    pub fn ecdsa_oid(self) -> ObjectIdentifier {
+        match self {
+            DigestAlgorithm::Sha256 => ID_ECDSA_WITH_SHA256,
+            DigestAlgorithm::Sha384 => ID_ECDSA_WITH_SHA384,
+            DigestAlgorithm::Sha512 => ID_ECDSA_WITH_SHA512,
+        }
+    }
+
+    /// OID RSASSA-PKCS1-v1_5 dengan digest ini
+    pub fn rsa_oid(self) -> ObjectIdentifier {
+        match self {
+            DigestAlgorithm::Sha256 => ID_SHA256_WITH_RSA,
+            DigestAlgorithm::Sha384 => ID_SHA384_WITH_RSA,
+            DigestAlgorithm::Sha512 => ID_SHA512_WITH_RSA,
+        }
+    }
+
+    /// Digest yang dipakai algoritma signature ECDSA atau RSASSA-PKCS1-v1_5
+    /// (misalnya untuk signature sertifikat); None untuk algoritma lain
+    pub fn from_signature_oid(oid: &ObjectIdentifier) -> Option<DigestAlgorithm> {
+        DigestAlgorithm::ALL.into_iter().find(|algorithm| algorithm.ecdsa_oid() == *oid || algorithm.rsa_oid() == *oid)
+    }
+
+    /// Panjang digest dalam bit (256, 384, atau 512)
+    pub fn bits(self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 => 256,
+            DigestAlgorithm::Sha384 => 384,
+            DigestAlgorithm::Sha512 => 512,
+        }
+    }
+
+    /// Panjang digest dalam bytes
+    pub fn output_len(self) -> usize {
+        self.bits() / 8
+    }
+
+    /// Digest dari `data`
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Hash inkremental baru, dari provider kriptografi jika ada yang terpasang
+    pub fn hasher(self) -> Hasher {
+        if let Some(context) = provider::provider().and_then(|provider| provider.hasher(self)) {
+            return Hasher::Provider(context);
+        }
+        match self {
+            DigestAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            DigestAlgorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            DigestAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+        }
+    }
+}
+
+impl Hasher {
+    /// Tambahkan data ke hash
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+            Hasher::Provider(context) => context.update(data),
+        }
+    }
+
+    /// Selesaikan hash dan kembalikan digest-nya
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Provider(context) => context.finalize(),
+        }
+    }
+}
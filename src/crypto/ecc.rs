@@ -1,48 +1,361 @@
 // Import library yang diperlukan
-use anyhow::Result;  // Untuk error handling yang fleksibel
-use p256::ecdsa::{SigningKey, Signature, signature::Signer}; // ECDSA P-256 signing
-use sha2::{Sha256, Digest}; // SHA-256 hashing
+use anyhow::{anyhow, bail, Result};  // Untuk error handling yang fleksibel
+#[cfg(feature = "sign")]
+use p256::ecdsa::signature::hazmat::PrehashSigner; // Sign digest langsung (ECDSA)
+use p256::ecdsa::signature::hazmat::PrehashVerifier; // Verifikasi digest langsung (ECDSA)
+#[cfg(feature = "sign")]
+use p256::ecdsa::signature::Signer as _; // Sign pesan utuh (Ed25519)
+use p256::ecdsa::signature::Verifier as _; // Verifikasi pesan utuh (Ed25519)
+use p256::pkcs8::DecodePrivateKey; // Membaca PKCS#8
+#[cfg(feature = "sign")]
+use p256::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding}; // Format PKCS#8 / SPKI
+#[cfg(feature = "sign")]
 use std::fs;  // Untuk file operations
+use x509_cert::der::asn1::{Any, ObjectIdentifier}; // OID kurva dan parameter algoritma
+use x509_cert::spki::AlgorithmIdentifierOwned; // Algoritma kunci publik / signature
 
-/// Fungsi untuk membuat pasangan kunci ECDSA P-256
+#[cfg(feature = "sign")]
+use crate::crypto::keyfile::{self, KeyFormat}; // Format file kunci
+#[cfg(feature = "sign")]
+use crate::crypto::provider; // Sumber acak untuk kunci baru
+
+// OID kunci publik EC (parameter: OID kurva) dan Ed25519 (tanpa parameter, RFC 8410)
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const ID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+// OID kurva NIST
+const ID_PRIME256V1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const ID_SECP384R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+const ID_SECP521R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.35");
+
+/// Kurva kunci ECC (--curve)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum Curve {
+    /// ECDSA NIST P-256 (default)
+    #[default]
+    P256,
+    /// ECDSA NIST P-384
+    P384,
+    /// ECDSA NIST P-521
+    P521,
+    /// EdDSA Ed25519 (menandatangani pesan utuh, bukan digest)
+    Ed25519,
+}
+
+/// Jalankan `$body` dengan `$ec` = crate kurva ECDSA (p256, p384, p521);
+/// Ed25519 bukan ECDSA sehingga ditolak
+macro_rules! with_ecdsa_curve {
+    ($curve:expr, $ec:ident => $body:expr) => {
+        match $curve {
+            Curve::P256 => {
+                use p256 as $ec;
+                $body
+            }
+            Curve::P384 => {
+                use p384 as $ec;
+                $body
+            }
+            Curve::P521 => {
+                use p521 as $ec;
+                $body
+            }
+            Curve::Ed25519 => bail!("Ed25519 is not an ECDSA curve"),
+        }
+    };
+}
+
+impl Curve {
+    /// Semua kurva yang didukung
+    pub const ALL: [Curve; 4] = [Curve::P256, Curve::P384, Curve::P521, Curve::Ed25519];
+
+    /// Nama untuk ditampilkan ke user, misalnya "ECDSA P-384"
+    pub fn label(self) -> &'static str {
+        match self {
+            Curve::P256 => "ECDSA P-256",
+            Curve::P384 => "ECDSA P-384",
+            Curve::P521 => "ECDSA P-521",
+            Curve::Ed25519 => "Ed25519",
+        }
+    }
+
+    /// OID kurva (parameter id-ecPublicKey); None untuk Ed25519
+    pub fn curve_oid(self) -> Option<ObjectIdentifier> {
+        match self {
+            Curve::P256 => Some(ID_PRIME256V1),
+            Curve::P384 => Some(ID_SECP384R1),
+            Curve::P521 => Some(ID_SECP521R1),
+            Curve::Ed25519 => None,
+        }
+    }
+
+    /// Panjang skalar privat (ECDSA) atau seed (Ed25519) dalam bytes
+    pub fn key_len(self) -> usize {
+        match self {
+            Curve::P256 | Curve::Ed25519 => 32,
+            Curve::P384 => 48,
+            Curve::P521 => 66,
+        }
+    }
+
+    /// Kurva ECDSA dari panjang kunci privat raw (format raw generate-key)
+    ///
+    /// 32 bytes selalu dianggap P-256 (format lama); kunci Ed25519 hanya
+    /// disimpan sebagai PKCS#8
+    pub fn from_raw_private_len(len: usize) -> Option<Curve> {
+        [Curve::P256, Curve::P384, Curve::P521].into_iter().find(|curve| curve.key_len() == len)
+    }
+
+    /// Kurva dari panjang kunci publik raw: titik SEC1 (compressed atau
+    /// uncompressed) atau kunci Ed25519 32 bytes
+    pub fn from_public_len(len: usize) -> Option<Curve> {
+        if len == 32 {
+            return Some(Curve::Ed25519);
+        }
+        [Curve::P256, Curve::P384, Curve::P521]
+            .into_iter()
+            .find(|curve| len == 1 + curve.key_len() || len == 1 + 2 * curve.key_len())
+    }
+
+    /// AlgorithmIdentifier SubjectPublicKeyInfo untuk kurva ini
+    pub fn spki_algorithm(self) -> Result<AlgorithmIdentifierOwned> {
+        Ok(match self.curve_oid() {
+            Some(curve) => AlgorithmIdentifierOwned {
+                oid: ID_EC_PUBLIC_KEY,
+                parameters: Some(Any::encode_from(&curve).map_err(|e| anyhow!("{}", e))?),
+            },
+            None => AlgorithmIdentifierOwned { oid: ID_ED25519, parameters: None },
+        })
+    }
+
+    /// Kurva dari AlgorithmIdentifier SubjectPublicKeyInfo
+    ///
+    /// Return: None jika bukan kunci EC/Ed25519; error untuk kurva EC yang tidak didukung
+    pub fn from_spki_algorithm(algorithm: &AlgorithmIdentifierOwned) -> Result<Option<Curve>> {
+        if algorithm.oid == ID_ED25519 {
+            return Ok(Some(Curve::Ed25519));
+        }
+        if algorithm.oid != ID_EC_PUBLIC_KEY {
+            return Ok(None);
+        }
+        let oid = algorithm.parameters.as_ref().and_then(|any| any.decode_as::<ObjectIdentifier>().ok());
+        match Curve::ALL.into_iter().find(|curve| curve.curve_oid().is_some() && curve.curve_oid() == oid) {
+            Some(curve) => Ok(Some(curve)),
+            None => bail!("unsupported EC curve (only P-256, P-384 and P-521 are supported)"),
+        }
+    }
+}
+
+/// AlgorithmIdentifier signature Ed25519 (id-Ed25519 tanpa parameter, RFC 8419)
+pub fn ed25519_algorithm() -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned { oid: ID_ED25519, parameters: None }
+}
+
+/// Apakah OID ini algoritma signature Ed25519
+pub fn is_ed25519(oid: &ObjectIdentifier) -> bool {
+    *oid == ID_ED25519
+}
+
+/// Fungsi untuk membuat pasangan kunci ECC
+/// Parameter:
+///   - curve: kurva kunci (P-256, P-384, P-521, atau Ed25519)
+///   - format: format file kunci (raw, PKCS#8 PEM, atau PKCS#8 DER)
+///   - passphrase: jika ada, kunci privat PKCS#8 dienkripsi (hanya PEM/DER)
+///
 /// Output: File "private.key" dan "public.key"
-pub fn generate_keypair() -> Result<()> {
-    // Buat kunci privat secara random menggunakan OS random number generator
-    let signing_key = SigningKey::random(&mut rand_core::OsRng);
-    
-    // Dari kunci privat, turunkan kunci publik
-    let verify_key = signing_key.verifying_key();
-
-    // Simpan kunci privat ke file "private.key" dalam format bytes
-    fs::write("private.key", signing_key.to_bytes())?;
-    
-    // Simpan kunci publik ke file "public.key" dalam format encoded point
-    // Parameter false = format uncompressed (76 bytes)
-    fs::write("public.key", verify_key.to_encoded_point(false).as_bytes())?;
+#[cfg(feature = "sign")]
+pub fn generate_keypair(curve: Curve, format: KeyFormat, passphrase: Option<&str>) -> Result<()> {
+    if format == KeyFormat::Raw && passphrase.is_some() {
+        bail!("raw keys cannot be encrypted; use --format pem or --format der");
+    }
+    // Seed Ed25519 raw (32 bytes) tidak bisa dibedakan dari kunci P-256 raw
+    if format == KeyFormat::Raw && curve == Curve::Ed25519 {
+        bail!("Ed25519 keys cannot be stored raw; use --format pem or --format der");
+    }
+
+    // Buat kunci privat secara random menggunakan OS random number generator,
+    // lalu encode kunci privat (PKCS#8 DER) dan kunci publik (SPKI DER / SEC1)
+    let (raw_private, raw_public, pkcs8_der, spki_der) = match curve {
+        Curve::Ed25519 => {
+            let signing_key = ed25519_dalek::SigningKey::generate(&mut provider::rng());
+            let pkcs8_der = signing_key.to_pkcs8_der().map_err(|e| anyhow!("cannot encode private key: {}", e))?;
+            let spki_der = signing_key.verifying_key().to_public_key_der().map_err(|e| anyhow!("cannot encode public key: {}", e))?;
+            (Vec::new(), Vec::new(), pkcs8_der.as_bytes().to_vec(), spki_der.into_vec())
+        }
+        _ => with_ecdsa_curve!(curve, ec => {
+            let secret_key = ec::SecretKey::random(&mut provider::rng());
+            let public_key = secret_key.public_key();
+            let pkcs8_der = secret_key.to_pkcs8_der().map_err(|e| anyhow!("cannot encode private key: {}", e))?;
+            let spki_der = public_key.to_public_key_der().map_err(|e| anyhow!("cannot encode public key: {}", e))?;
+            (
+                secret_key.to_bytes().to_vec(),
+                public_key.to_sec1_bytes().to_vec(),
+                pkcs8_der.as_bytes().to_vec(),
+                spki_der.into_vec(),
+            )
+        }),
+    };
+    let (private_bytes, public_bytes): (Vec<u8>, Vec<u8>) = match format {
+        // Format lama: skalar privat dan encoded point uncompressed
+        KeyFormat::Raw => (raw_private, raw_public),
+        // PKCS#8 PEM (terenkripsi jika ada passphrase) dan SubjectPublicKeyInfo PEM
+        KeyFormat::Pem => {
+            let public_pem = pem_encode("PUBLIC KEY", &spki_der)?;
+            (keyfile::encode_private_key(&pkcs8_der, format, passphrase)?, public_pem.into_bytes())
+        }
+        // PKCS#8 DER (terenkripsi jika ada passphrase) dan SubjectPublicKeyInfo DER
+        KeyFormat::Der => (keyfile::encode_private_key(&pkcs8_der, format, passphrase)?, spki_der),
+    };
+
+    // Simpan kunci privat ke file "private.key" dan kunci publik ke "public.key"
+    fs::write("private.key", private_bytes)?;
+    fs::write("public.key", public_bytes)?;
 
     // Tampilkan pesan sukses ke user
-    println!("Keys generated: private.key & public.key (ECDSA P-256)");
+    let description = match (format, passphrase.is_some()) {
+        (KeyFormat::Raw, _) => "raw",
+        (KeyFormat::Pem, false) => "PKCS#8 PEM",
+        (KeyFormat::Pem, true) => "encrypted PKCS#8 PEM",
+        (KeyFormat::Der, false) => "PKCS#8 DER",
+        (KeyFormat::Der, true) => "encrypted PKCS#8 DER",
+    };
+    crate::info!("Keys generated: private.key & public.key ({}, {})", curve.label(), description);
     Ok(())
 }
 
-/// Fungsi untuk menandatangani data dengan ECDSA P-256
+/// Encode DER sebagai blok PEM dengan label tertentu
+#[cfg(feature = "sign")]
+fn pem_encode(label: &str, der: &[u8]) -> Result<String> {
+    x509_cert::der::pem::encode_string(label, LineEnding::LF, der).map_err(|e| anyhow!("cannot encode {}: {}", label.to_lowercase(), e))
+}
+
+/// Baca kunci privat ECC dari PKCS#8 atau SEC1 ("EC PRIVATE KEY"), DER atau PEM
+/// Return: kurva dan skalar privat (ECDSA) atau seed 32 bytes (Ed25519),
+/// atau None jika bytes bukan kunci ECC yang didukung
+pub fn private_key_from_encoded(bytes: &[u8]) -> Option<(Curve, Vec<u8>)> {
+    let text = std::str::from_utf8(bytes).ok().filter(|text| text.contains("-----BEGIN"));
+    macro_rules! try_curve {
+        ($ec:ident, $curve:expr) => {
+            let key = match text {
+                Some(text) => $ec::SecretKey::from_pkcs8_pem(text).ok().or_else(|| $ec::SecretKey::from_sec1_pem(text).ok()),
+                None => $ec::SecretKey::from_pkcs8_der(bytes).ok().or_else(|| $ec::SecretKey::from_sec1_der(bytes).ok()),
+            };
+            if let Some(key) = key {
+                return Some(($curve, key.to_bytes().to_vec()));
+            }
+        };
+    }
+    try_curve!(p256, Curve::P256);
+    try_curve!(p384, Curve::P384);
+    try_curve!(p521, Curve::P521);
+    let key = match text {
+        Some(text) => ed25519_dalek::SigningKey::from_pkcs8_pem(text).ok(),
+        None => ed25519_dalek::SigningKey::from_pkcs8_der(bytes).ok(),
+    }?;
+    Some((Curve::Ed25519, key.to_bytes().to_vec()))
+}
+
+/// Samakan panjang digest dengan ukuran field kurva: digest yang lebih pendek
+/// (misalnya SHA-256 dengan P-521) dipadding nol di depan, sehingga nilainya
+/// sebagai bilangan tidak berubah (FIPS 186-4 6.4)
+fn prehash<'a>(digest: &'a [u8], curve: Curve, padded: &'a mut Vec<u8>) -> &'a [u8] {
+    if digest.len() >= curve.key_len() {
+        return digest;
+    }
+    *padded = vec![0; curve.key_len() - digest.len()];
+    padded.extend_from_slice(digest);
+    padded
+}
+
+/// Fungsi untuk menandatangani digest dengan ECDSA
 /// Parameter:
-///   - data: data yang akan ditandatangani (PDF bytes)
-///   - private_key: kunci privat dalam format bytes
+///   - curve: kurva kunci (bukan Ed25519)
+///   - digest: digest data yang ditandatangani
+///   - private_key: skalar privat dalam format bytes
+///
 /// Return: signature dalam format DER encoding
-pub fn sign(data: &[u8], private_key: &[u8]) -> Vec<u8> {
-    // Buat signing key langsung dari bytes privat key
-    // SigningKey::from_bytes() menerima slice dengan ukuran fixed 32 bytes (256 bit)
-    let key = SigningKey::from_bytes(private_key.into()).unwrap();
-    
-    // Hash data menggunakan SHA-256
-    // Ini menghasilkan 32 bytes digest
-    let hash = Sha256::digest(data);
-    
-    // Tanda tangani hash dengan signing key menggunakan ECDSA
-    let sig: Signature = key.sign(&hash);
-    
-    // Konversi signature ke format DER dan kembalikan sebagai Vec<u8>
-    // DER adalah format standar untuk encoding digital signature
-    sig.to_der().as_bytes().to_vec()
-}
\ No newline at end of file
+#[cfg(feature = "sign")]
+pub fn sign_digest(curve: Curve, digest: &[u8], private_key: &[u8]) -> Result<Vec<u8>> {
+    let mut padded = Vec::new();
+    let digest = prehash(digest, curve, &mut padded);
+    with_ecdsa_curve!(curve, ec => {
+        // Buat signing key langsung dari bytes privat key
+        let key = ec::ecdsa::SigningKey::from_slice(private_key)
+            .map_err(|_| anyhow!("invalid {} private key", curve.label()))?;
+        // Tanda tangani digest secara langsung (tanpa hashing ulang)
+        let sig: ec::ecdsa::Signature = key.sign_prehash(digest).map_err(|e| anyhow!("ECDSA signing failed: {}", e))?;
+        // Konversi signature ke format DER, format standar CMS untuk ECDSA
+        Ok(sig.to_der().as_bytes().to_vec())
+    })
+}
+
+/// Fungsi untuk memverifikasi signature ECDSA atas digest
+/// Parameter:
+///   - curve: kurva kunci (bukan Ed25519)
+///   - digest: digest data yang ditandatangani
+///   - signature_der: signature dalam format DER
+///   - public_key: kunci publik dalam format SEC1 (encoded point, seperti public.key)
+///
+/// Return: true jika signature valid
+pub fn verify_digest(curve: Curve, digest: &[u8], signature_der: &[u8], public_key: &[u8]) -> Result<bool> {
+    let mut padded = Vec::new();
+    let digest = prehash(digest, curve, &mut padded);
+    with_ecdsa_curve!(curve, ec => {
+        // Parse kunci publik dari encoded point (compressed atau uncompressed)
+        let key = ec::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|_| anyhow!("invalid {} public key", curve.label()))?;
+        // Parse signature DER; signature yang rusak dianggap tidak valid
+        let Ok(sig) = ec::ecdsa::Signature::from_der(signature_der) else {
+            return Ok(false);
+        };
+        Ok(key.verify_prehash(digest, &sig).is_ok())
+    })
+}
+
+/// Tandatangani pesan utuh dengan Ed25519 (PureEdDSA)
+/// Return: signature 64 bytes
+#[cfg(feature = "sign")]
+pub fn ed25519_sign(message: &[u8], seed: &[u8]) -> Result<Vec<u8>> {
+    let seed: [u8; 32] = seed.try_into().map_err(|_| anyhow!("invalid Ed25519 private key (expected a 32-byte seed)"))?;
+    let key = ed25519_dalek::SigningKey::from_bytes(&seed);
+    Ok(key.sign(message).to_bytes().to_vec())
+}
+
+/// Verifikasi signature Ed25519 atas pesan utuh
+/// Return: true jika signature valid
+pub fn ed25519_verify(message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool> {
+    let public_key: [u8; 32] = public_key.try_into().map_err(|_| anyhow!("invalid Ed25519 public key"))?;
+    let key = ed25519_dalek::VerifyingKey::from_bytes(&public_key).map_err(|_| anyhow!("invalid Ed25519 public key"))?;
+    let Ok(sig) = ed25519_dalek::Signature::from_slice(signature) else {
+        return Ok(false);
+    };
+    Ok(key.verify(message, &sig).is_ok())
+}
+
+/// Normalisasi signature ECDSA ke DER: HSM dan API signing jarak jauh
+/// sering mengembalikan format raw r || s (2 x ukuran field)
+/// Return: signature DER; bytes yang bukan raw r || s dikembalikan apa adanya
+pub fn signature_to_der(curve: Curve, signature: &[u8]) -> Vec<u8> {
+    if curve == Curve::Ed25519 || signature.len() != 2 * curve.key_len() {
+        return signature.to_vec();
+    }
+    let der = || -> Result<Vec<u8>> {
+        with_ecdsa_curve!(curve, ec => {
+            let sig = ec::ecdsa::Signature::from_slice(signature).map_err(|e| anyhow!("{}", e))?;
+            Ok(sig.to_der().as_bytes().to_vec())
+        })
+    };
+    der().unwrap_or_else(|_| signature.to_vec())
+}
+
+/// Turunkan kunci publik dari kunci privat: SEC1 uncompressed (sama seperti
+/// public.key) untuk ECDSA, 32 bytes untuk Ed25519
+pub fn public_key_from_private(curve: Curve, private_key: &[u8]) -> Result<Vec<u8>> {
+    if curve == Curve::Ed25519 {
+        let seed: [u8; 32] = private_key.try_into().map_err(|_| anyhow!("invalid Ed25519 private key (expected a 32-byte seed)"))?;
+        return Ok(ed25519_dalek::SigningKey::from_bytes(&seed).verifying_key().to_bytes().to_vec());
+    }
+    with_ecdsa_curve!(curve, ec => {
+        let key = ec::SecretKey::from_slice(private_key).map_err(|_| anyhow!("invalid {} private key", curve.label()))?;
+        Ok(key.public_key().to_sec1_bytes().to_vec())
+    })
+}
@@ -0,0 +1,252 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use std::io::Read; // Membaca body response
+use std::sync::{OnceLock, RwLock}; // Agent dan kebijakan pengulangan bersama untuk seluruh proses
+use std::time::Duration; // Timeout HTTP dan jeda pengulangan
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+
+/// Batas waktu default untuk satu request HTTP
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Ukuran maksimum body response yang diterima (bytes); CRL bisa berukuran beberapa MB
+const MAX_RESPONSE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Ukuran maksimum dokumen yang diunduh lewat `download` (bytes)
+const MAX_DOCUMENT_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Jumlah koneksi idle (keep-alive) yang disimpan per host
+const IDLE_CONNECTIONS_PER_HOST: usize = 8;
+
+/// Kebijakan pengulangan yang berlaku untuk semua request dalam proses ini
+static RETRY_POLICY: RwLock<RetryPolicy> = RwLock::new(RetryPolicy::NONE);
+
+/// Kebijakan pengulangan request ke layanan trust (TSA, OCSP, CRL, KMS) yang
+/// gagal sementara: koneksi gagal, timeout, HTTP 429/5xx
+///
+/// Jeda sebelum pengulangan ke-n adalah `backoff * 2^(n-1)`, dibatasi
+/// `max_backoff`, lalu dikurangi secara acak sampai `jitter` bagian agar banyak
+/// klien tidak mengulang serempak. Header Retry-After dari server dihormati.
+/// Pengulangan tidak pernah melewati batas waktu token pembatalan.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub retries: u32,          // Jumlah pengulangan setelah percobaan pertama; 0 = tidak diulang
+    pub backoff: Duration,     // Jeda sebelum pengulangan pertama
+    pub max_backoff: Duration, // Batas atas jeda (termasuk dari Retry-After)
+    pub jitter: f64,           // Bagian jeda yang diacak, 0.0 (tetap) sampai 1.0 (penuh)
+}
+
+impl RetryPolicy {
+    /// Tanpa pengulangan (default)
+    pub const NONE: RetryPolicy = RetryPolicy {
+        retries: 0,
+        backoff: Duration::from_millis(500),
+        max_backoff: Duration::from_secs(30),
+        jitter: 0.5,
+    };
+
+    /// Jeda sebelum pengulangan ke-`attempt` (mulai dari 1), sudah termasuk jitter
+    fn delay(&self, attempt: u32) -> Duration {
+        let exponential = self.backoff.saturating_mul(1u32 << (attempt - 1).min(16)).min(self.max_backoff);
+        let mut bytes = [0u8; 4];
+        crate::crypto::provider::fill_random(&mut bytes);
+        let random = u32::from_le_bytes(bytes) as f64 / u32::MAX as f64;
+        exponential.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * random)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::NONE
+    }
+}
+
+/// Pasang kebijakan pengulangan untuk semua request HTTP berikutnya dalam proses ini
+pub fn set_retry_policy(policy: RetryPolicy) {
+    *RETRY_POLICY.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+}
+
+/// Kebijakan pengulangan yang sedang berlaku
+pub fn retry_policy() -> RetryPolicy {
+    *RETRY_POLICY.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Kirim request POST (TSA, OCSP) dan kembalikan body response
+///
+/// Parameter:
+///   - url: URL tujuan (http:// atau https://)
+///   - content_type: MIME type body request
+///   - accept: MIME type response yang diharapkan
+///   - body: isi request
+///   - cancel: token pembatalan; batas waktunya membatasi request
+pub fn post(url: &str, content_type: &str, accept: &str, body: &[u8], cancel: &CancellationToken) -> Result<Vec<u8>> {
+    post_with_headers(url, content_type, accept, &[], body, cancel)
+}
+
+/// Seperti `post`, dengan header tambahan (misalnya Authorization untuk TSA
+/// yang butuh login)
+pub fn post_with_headers(
+    url: &str,
+    content_type: &str,
+    accept: &str,
+    extra_headers: &[(&str, &str)],
+    body: &[u8],
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    // Query TSA/OCSP tidak mengubah apa pun di server: aman diulang (request
+    // TSA yang diulang memakai nonce yang sama)
+    let mut headers = vec![("Content-Type", content_type), ("Accept", accept)];
+    headers.extend_from_slice(extra_headers);
+    let response = send("POST", url, &headers, Some(body), true, cancel)?;
+    read_response(url, response, MAX_RESPONSE_SIZE, cancel)
+}
+
+/// Kirim request GET (misalnya mengunduh CRL) dan kembalikan body response
+pub fn get(url: &str, cancel: &CancellationToken) -> Result<Vec<u8>> {
+    read_response(url, send("GET", url, &[], None, true, cancel)?, MAX_RESPONSE_SIZE, cancel)
+}
+
+/// Unduh dokumen dengan GET (storage::HttpSource); batas ukurannya jauh lebih
+/// besar daripada response TSA/OCSP/CRL
+pub fn download(url: &str, headers: &[(&str, &str)], cancel: &CancellationToken) -> Result<Vec<u8>> {
+    read_response(url, send("GET", url, headers, None, true, cancel)?, MAX_DOCUMENT_SIZE, cancel)
+}
+
+/// Kirim request ke REST API layanan cloud (KMS) dan kembalikan body response
+///
+/// Berbeda dengan `post`/`get`, pesan error dari server ikut ditampilkan,
+/// karena API cloud menjelaskan penyebab penolakan (izin, key tidak ada) di body.
+/// Request POST hanya diulang jika server pasti belum memprosesnya (koneksi
+/// gagal dibuka, HTTP 429/503), karena POST ke API tidak dijamin idempoten.
+///
+/// Parameter:
+///   - method: "GET", "POST", atau "PUT"
+///   - url: URL endpoint API
+///   - headers: header tambahan (otorisasi, content type, ...)
+///   - body: isi request untuk POST/PUT
+///   - cancel: token pembatalan; batas waktunya membatasi request
+pub fn call_api(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&[u8]>, cancel: &CancellationToken) -> Result<Vec<u8>> {
+    let response = send(method, url, headers, body, method != "POST", cancel)?;
+    if let Err(ureq::Error::Status(code, response)) = response {
+        let mut message = String::new();
+        let _ = response.into_reader().take(4096).read_to_string(&mut message);
+        bail!("{} returned HTTP {}: {}", url, code, message.trim());
+    }
+    read_response(url, response, MAX_RESPONSE_SIZE, cancel)
+}
+
+/// Kirim request, diulang sesuai kebijakan pengulangan jika gagal sementara
+///
+/// Parameter:
+///   - idempotent: request boleh dikirim ulang walaupun server mungkin sudah
+///     menerimanya (timeout, HTTP 500/502/504); jika false, hanya diulang saat
+///     server pasti belum memprosesnya
+///
+/// Return: response atau error ureq dari percobaan terakhir; error anyhow hanya
+/// untuk pembatalan atau batas waktu
+fn send(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&[u8]>,
+    idempotent: bool,
+    cancel: &CancellationToken,
+) -> Result<std::result::Result<ureq::Response, ureq::Error>> {
+    let policy = retry_policy();
+    let mut attempt = 0;
+    loop {
+        let mut request = build_request(method, url, cancel)?;
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        crate::debug!("http", "{} {} (attempt {}, {} bytes)", method, url, attempt + 1, body.map_or(0, <[u8]>::len));
+        let response = match body {
+            Some(body) => request.send_bytes(body),
+            None => request.call(),
+        };
+        attempt += 1;
+        match &response {
+            Ok(response) => crate::debug!("http", "{} {} from {}", response.status(), response.status_text(), url),
+            Err(error) => crate::debug!("http", "{} failed: {}", url, error),
+        }
+        let delay = match &response {
+            Err(error) if attempt <= policy.retries => retry_delay(error, idempotent, &policy, attempt),
+            _ => None,
+        };
+        // Jangan menunggu jika jedanya melewati batas waktu: kembalikan error terakhir
+        match delay {
+            Some(delay) if cancel.remaining().is_none_or(|remaining| remaining > delay) => wait(delay, cancel)?,
+            _ => return Ok(response),
+        }
+    }
+}
+
+/// Jeda sebelum mengulang request yang gagal dengan `error`, atau None jika
+/// kegagalannya tidak sementara atau request tidak aman diulang
+fn retry_delay(error: &ureq::Error, idempotent: bool, policy: &RetryPolicy, attempt: u32) -> Option<Duration> {
+    use ureq::ErrorKind::{ConnectionFailed, Dns, Io};
+    match error {
+        // Server menolak tanpa memproses request; ikuti Retry-After (dalam detik) jika ada
+        ureq::Error::Status(429 | 503, response) => Some(
+            response
+                .header("Retry-After")
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map_or_else(|| policy.delay(attempt), |seconds| Duration::from_secs(seconds).min(policy.max_backoff)),
+        ),
+        // Server atau gateway mungkin sudah memproses request
+        ureq::Error::Status(408 | 500 | 502 | 504, _) if idempotent => Some(policy.delay(attempt)),
+        // Koneksi belum terbuka: request belum terkirim
+        ureq::Error::Transport(transport) if matches!(transport.kind(), Dns | ConnectionFailed) => Some(policy.delay(attempt)),
+        // Koneksi terputus atau timeout di tengah request
+        ureq::Error::Transport(transport) if idempotent && transport.kind() == Io => Some(policy.delay(attempt)),
+        _ => None,
+    }
+}
+
+/// Tunggu `delay`, tetapi berhenti segera jika token dibatalkan (Ctrl-C)
+fn wait(delay: Duration, cancel: &CancellationToken) -> Result<()> {
+    const STEP: Duration = Duration::from_millis(100);
+    let mut waited = Duration::ZERO;
+    while waited < delay {
+        let step = STEP.min(delay - waited);
+        std::thread::sleep(step);
+        waited += step;
+        cancel.check()?;
+    }
+    Ok(())
+}
+
+/// Request lewat agent bersama, dengan timeout mengikuti sisa waktu token pembatalan
+///
+/// Semua request dalam satu proses memakai agent yang sama, sehingga koneksi
+/// TCP/TLS ke TSA, OCSP, CRL, dan KMS tetap terbuka dan dipakai ulang untuk
+/// dokumen berikutnya (batch, server, atau agent sesi) tanpa handshake ulang.
+fn build_request(method: &str, url: &str, cancel: &CancellationToken) -> Result<ureq::Request> {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+    cancel.check()?;
+    let timeout = cancel.remaining().map_or(HTTP_TIMEOUT, |remaining| remaining.min(HTTP_TIMEOUT));
+    let agent = AGENT.get_or_init(|| ureq::AgentBuilder::new().max_idle_connections_per_host(IDLE_CONNECTIONS_PER_HOST).build());
+    Ok(agent.request(method, url).timeout(timeout))
+}
+
+/// Baca body response (dibatasi `limit` bytes) dan ubah error HTTP menjadi pesan yang jelas
+fn read_response(
+    url: &str,
+    response: std::result::Result<ureq::Response, ureq::Error>,
+    limit: u64,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, _)) => bail!("{} returned HTTP {}", url, code),
+        Err(e) => bail!("cannot reach {}: {}", url, e),
+    };
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(limit)
+        .read_to_end(&mut body)
+        .map_err(|e| anyhow!("cannot read response from {}: {}", url, e))?;
+    cancel.check()?;
+    Ok(body)
+}
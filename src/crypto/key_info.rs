@@ -0,0 +1,260 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use sha2::{Digest, Sha256}; // Fingerprint kunci publik dan sertifikat
+use std::time::SystemTime; // Status masa berlaku sertifikat
+use x509_cert::der::asn1::ObjectIdentifier; // OID extendedKeyUsage
+use x509_cert::der::{Decode, Encode}; // Encoding DER
+use x509_cert::ext::pkix::{BasicConstraints, ExtendedKeyUsage, KeyUsage}; // Extension yang ditampilkan
+use x509_cert::spki::SubjectPublicKeyInfoOwned; // Kunci publik
+use x509_cert::Certificate; // Sertifikat X.509
+
+use crate::crypto::cert::{certificates_for_key, read_certificates, test_credential, CertificateInfo}; // certificate.der di sebelah kunci, file sertifikat, ringkasan sertifikat, kredensial uji
+use crate::crypto::p12::load_pkcs12; // Bundle PKCS#12
+use crate::crypto::rsa::RsaPadding; // Padding tidak berpengaruh pada kunci publik
+use crate::crypto::signer::{load_signer, PublicKey}; // Kunci privat dan jenis kunci publik
+
+/// Hasil `pdfsign key-info`
+pub struct KeyReport {
+    pub key: Option<KeyDetails>,                // Kunci privat (--key atau kunci di bundle --p12)
+    pub certificate_source: Option<String>,     // File asal sertifikat
+    pub certificates: Vec<CertificateDetails>,  // Sertifikat: penandatangan dulu, lalu rantainya
+    pub matches: Option<bool>,                  // Kunci cocok dengan sertifikat pertama (None jika salah satunya tidak ada)
+    pub warnings: Vec<String>,                  // Masalah yang membuat signature ditolak atau tidak dipercaya
+}
+
+/// Kunci privat yang diperiksa
+pub struct KeyDetails {
+    pub source: String,      // Path --key atau --p12
+    pub algorithm: String,   // Misalnya "ECDSA P-256" atau "RSA 3072-bit"
+    pub fingerprint: String, // SHA-256 SubjectPublicKeyInfo (hex, dipisah titik dua)
+}
+
+/// Satu sertifikat yang diperiksa
+pub struct CertificateDetails {
+    pub info: CertificateInfo,              // Subject, issuer, serial, masa berlaku
+    pub algorithm: String,                  // Jenis kunci publik di sertifikat
+    pub fingerprint: String,                // SHA-256 SubjectPublicKeyInfo (sama dengan fingerprint kunci jika cocok)
+    pub sha256: String,                     // SHA-256 seluruh sertifikat DER
+    pub key_usage: Vec<&'static str>,       // Flag keyUsage (kosong jika extension tidak ada)
+    pub extended_key_usage: Vec<String>,    // extendedKeyUsage (nama atau OID)
+    pub ca: bool,                           // basicConstraints cA
+    pub status: &'static str,               // "valid", "expired", atau "not yet valid"
+}
+
+// extendedKeyUsage yang dikenali; selain ini ditampilkan sebagai OID
+const EXTENDED_KEY_USAGES: [(ObjectIdentifier, &str); 10] = [
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.36"), "documentSigning"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.4"), "emailProtection"),
+    (ObjectIdentifier::new_unwrap("1.2.840.113583.1.1.5"), "adobeAuthenticDocuments"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.10.3.12"), "msDocumentSigning"),
+    (ObjectIdentifier::new_unwrap("2.5.29.37.0"), "anyExtendedKeyUsage"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.8"), "timeStamping"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.1"), "serverAuth"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.2"), "clientAuth"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.3"), "codeSigning"),
+    (ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.9"), "OCSPSigning"),
+];
+
+// extendedKeyUsage yang diterima untuk tanda tangan dokumen (sama seperti verify)
+const DOCUMENT_SIGNING_USAGES: [&str; 5] = ["documentSigning", "emailProtection", "adobeAuthenticDocuments", "msDocumentSigning", "anyExtendedKeyUsage"];
+
+/// Periksa kunci privat, sertifikat, atau bundle PKCS#12, dan apakah kunci
+/// dan sertifikat penandatangan berpasangan
+///
+/// Tanpa `cert`, sertifikat diambil dari tempat yang sama dengan `sign`:
+/// bundle `p12`, atau certificate.der di sebelah `key`.
+///
+/// Parameter:
+///   - key: path file kunci privat
+///   - passphrase: passphrase kunci privat terenkripsi
+///   - cert: path file sertifikat (DER atau PEM, boleh berisi rantainya)
+///   - p12: path bundle PKCS#12 (menggantikan `key`)
+///   - p12_password: password bundle PKCS#12
+///
+/// Return: detail kunci dan sertifikat, hasil pencocokan, dan peringatan
+pub fn key_info(key: Option<&str>, passphrase: Option<&str>, cert: Option<&str>, p12: Option<&str>, p12_password: Option<&str>) -> Result<KeyReport> {
+    let (public_key, source, bundled) = match (key, p12) {
+        (Some(_), Some(_)) => bail!("--key and --p12 cannot be used together"),
+        (Some(path), None) => {
+            let signer = load_signer(path, RsaPadding::default(), passphrase)?;
+            (Some(signer.public_key_info()?), Some(path), certificates_for_key(path)?)
+        }
+        (None, Some(path)) => {
+            let bundle = load_pkcs12(path, p12_password, RsaPadding::default())?;
+            (Some(bundle.signer.public_key_info()?), Some(path), bundle.certificates)
+        }
+        (None, None) if cert.is_none() => bail!("nothing to inspect: pass --key, --cert or --p12"),
+        (None, None) => (None, None, Vec::new()),
+    };
+
+    let (certificate_source, certificates) = match (cert, key, p12) {
+        (Some(path), _, _) => (Some(path.to_string()), read_certificates(path)?),
+        (None, Some(path), _) if !bundled.is_empty() => {
+            (Some(std::path::Path::new(path).with_file_name("certificate.der").to_string_lossy().into_owned()), bundled)
+        }
+        (None, _, Some(path)) if !bundled.is_empty() => (Some(path.to_string()), bundled),
+        _ => (None, Vec::new()),
+    };
+    let test_credential = test_credential(&certificates);
+    let certificates = certificates
+        .iter()
+        .map(|der| certificate_details(der))
+        .collect::<Result<Vec<_>>>()?;
+
+    let key = match (&public_key, source) {
+        (Some(spki), Some(source)) => Some(KeyDetails {
+            source: source.to_string(),
+            algorithm: algorithm_name(spki),
+            fingerprint: spki_fingerprint(spki)?,
+        }),
+        _ => None,
+    };
+    let matches = match (&key, certificates.first()) {
+        (Some(key), Some(signer)) => Some(key.fingerprint == signer.fingerprint),
+        _ => None,
+    };
+
+    let mut warnings = Vec::new();
+    if key.is_some() && certificates.is_empty() {
+        warnings.push("no certificate found next to the key; signatures will carry no certificate and verifiers need the public key to check them".to_string());
+    }
+    if let Some(signer) = certificates.first() {
+        if !signer.key_usage.is_empty() && !signer.key_usage.iter().any(|usage| ["digitalSignature", "nonRepudiation"].contains(usage)) {
+            warnings.push("the certificate's key usage allows neither digitalSignature nor nonRepudiation; verifiers reject its signatures".to_string());
+        }
+        if !signer.extended_key_usage.is_empty() && !signer.extended_key_usage.iter().any(|usage| DOCUMENT_SIGNING_USAGES.contains(&usage.as_str())) {
+            warnings.push("the certificate's extended key usage does not cover document signing".to_string());
+        }
+    }
+    if let Some(subject) = test_credential {
+        warnings.push(format!("certificate {} is a test credential made by `pdfsign generate-cert`; signing with it needs --allow-test-credentials", subject));
+    }
+    for cert in &certificates {
+        if cert.status != "valid" {
+            warnings.push(format!("certificate {} is {} (valid {})", cert.info.subject, cert.status, cert.info.validity()));
+        }
+    }
+
+    Ok(KeyReport { key, certificate_source, certificates, matches, warnings })
+}
+
+/// Hasil `key-info` sebagai teks untuk user (ditampilkan oleh CLI)
+pub fn format_key_info(report: &KeyReport) -> String {
+    let mut out = String::new();
+    if let Some(key) = &report.key {
+        out.push_str(&format!("Key:          {}\n", key.source));
+        out.push_str(&format!("  Algorithm:    {}\n", key.algorithm));
+        out.push_str(&format!("  Fingerprint:  {}\n", key.fingerprint));
+    }
+    if let Some(source) = &report.certificate_source {
+        out.push_str(&format!("Certificates: {}\n", source));
+    }
+    for (index, cert) in report.certificates.iter().enumerate() {
+        out.push_str(&format!("Certificate #{}{}\n", index + 1, if index == 0 { " (signer)" } else { "" }));
+        out.push_str(&format!("  Subject:      {}\n", cert.info.subject));
+        out.push_str(&format!("  Issuer:       {}\n", cert.info.issuer));
+        out.push_str(&format!("  Serial:       {}\n", cert.info.serial));
+        out.push_str(&format!("  Valid:        {} ({})\n", cert.info.validity(), cert.status));
+        out.push_str(&format!("  Algorithm:    {}\n", cert.algorithm));
+        out.push_str(&format!("  Fingerprint:  {} (public key)\n", cert.fingerprint));
+        out.push_str(&format!("  SHA-256:      {}\n", cert.sha256));
+        out.push_str(&format!("  Key usage:    {}\n", list_or_dash(&cert.key_usage)));
+        out.push_str(&format!("  Ext. usage:   {}\n", list_or_dash(&cert.extended_key_usage)));
+        if cert.ca {
+            out.push_str("  CA:           yes\n");
+        }
+    }
+    match report.matches {
+        Some(true) => out.push_str("Key matches the signer certificate\n"),
+        Some(false) => out.push_str("Key does NOT match the signer certificate\n"),
+        None => {}
+    }
+    out
+}
+
+/// Daftar dipisah koma, atau "-" jika kosong
+fn list_or_dash<T: AsRef<str>>(items: &[T]) -> String {
+    if items.is_empty() {
+        return "-".to_string();
+    }
+    items.iter().map(|item| item.as_ref()).collect::<Vec<_>>().join(", ")
+}
+
+/// Detail satu sertifikat DER
+fn certificate_details(der: &[u8]) -> Result<CertificateDetails> {
+    let cert = Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+    let info = CertificateInfo::from_der(der).ok_or_else(|| anyhow!("invalid certificate"))?;
+    let tbs = &cert.tbs_certificate;
+
+    let key_usage = match tbs.get::<KeyUsage>() {
+        Ok(Some((_, usage))) => [
+            (usage.digital_signature(), "digitalSignature"),
+            (usage.non_repudiation(), "nonRepudiation"),
+            (usage.key_encipherment(), "keyEncipherment"),
+            (usage.data_encipherment(), "dataEncipherment"),
+            (usage.key_agreement(), "keyAgreement"),
+            (usage.key_cert_sign(), "keyCertSign"),
+            (usage.crl_sign(), "cRLSign"),
+            (usage.encipher_only(), "encipherOnly"),
+            (usage.decipher_only(), "decipherOnly"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect(),
+        _ => Vec::new(),
+    };
+    let extended_key_usage = match tbs.get::<ExtendedKeyUsage>() {
+        Ok(Some((_, usage))) => usage
+            .0
+            .iter()
+            .map(|oid| match EXTENDED_KEY_USAGES.iter().find(|(known, _)| known == oid) {
+                Some((_, name)) => name.to_string(),
+                None => oid.to_string(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let ca = matches!(tbs.get::<BasicConstraints>(), Ok(Some((_, constraints))) if constraints.ca);
+
+    let now = SystemTime::now();
+    let status = if now > tbs.validity.not_after.to_system_time() {
+        "expired"
+    } else if now < tbs.validity.not_before.to_system_time() {
+        "not yet valid"
+    } else {
+        "valid"
+    };
+
+    Ok(CertificateDetails {
+        info,
+        algorithm: algorithm_name(&tbs.subject_public_key_info),
+        fingerprint: spki_fingerprint(&tbs.subject_public_key_info)?,
+        sha256: colon_hex(&Sha256::digest(der)),
+        key_usage,
+        extended_key_usage,
+        ca,
+        status,
+    })
+}
+
+/// Nama jenis kunci publik, misalnya "ECDSA P-256" atau "RSA 3072-bit"
+fn algorithm_name(spki: &SubjectPublicKeyInfoOwned) -> String {
+    match PublicKey::from_spki(spki) {
+        Ok(PublicKey::Ecdsa(curve, _)) => curve.label().to_string(),
+        Ok(PublicKey::Ed25519(_)) => "Ed25519".to_string(),
+        Ok(PublicKey::Rsa(key)) => format!("RSA {}-bit", key.bits()),
+        Err(_) => format!("unsupported ({})", spki.algorithm.oid),
+    }
+}
+
+/// SHA-256 SubjectPublicKeyInfo DER; kunci dan sertifikat yang berpasangan
+/// punya fingerprint yang sama
+fn spki_fingerprint(spki: &SubjectPublicKeyInfoOwned) -> Result<String> {
+    let der = spki.to_der().map_err(|e| anyhow!("cannot encode public key: {}", e))?;
+    Ok(colon_hex(&Sha256::digest(der)))
+}
+
+/// Bytes sebagai hex huruf besar dipisah titik dua (format fingerprint openssl)
+fn colon_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
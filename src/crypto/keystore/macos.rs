@@ -0,0 +1,246 @@
+// macOS Keychain: identity (sertifikat + kunci privat) di keychain user,
+// signing lewat Security framework (SecKeyCreateSignature), termasuk kunci
+// di smart card yang dipasang lewat CryptoTokenKit (macOS menanyakan PIN-nya)
+
+// Import library yang diperlukan
+use anyhow::{anyhow, Result}; // Untuk error handling yang fleksibel
+use core_foundation_sys::array::{CFArrayGetCount, CFArrayGetValueAtIndex, CFArrayRef}; // Hasil pencarian identity
+use core_foundation_sys::base::{kCFAllocatorDefault, CFIndex, CFRelease, CFRetain, CFTypeRef, OSStatus}; // Object Core Foundation
+use core_foundation_sys::data::{CFDataCreate, CFDataGetBytePtr, CFDataGetLength, CFDataRef}; // Digest, sertifikat, dan signature
+use core_foundation_sys::dictionary::{
+    kCFTypeDictionaryKeyCallBacks, kCFTypeDictionaryValueCallBacks, CFDictionaryCreate, CFDictionaryRef,
+}; // Query keychain
+use core_foundation_sys::error::{CFErrorCopyDescription, CFErrorRef}; // Error Security framework
+use core_foundation_sys::number::kCFBooleanTrue; // Nilai kSecReturnRef
+use core_foundation_sys::string::{
+    kCFStringEncodingUTF8, CFStringGetCString, CFStringGetLength, CFStringGetMaximumSizeForEncoding, CFStringRef,
+}; // Pesan error
+use std::ffi::{c_void, CStr}; // Pointer FFI dan string C
+use std::ptr; // Pointer null untuk API Security
+
+use super::KeyKind; // Jenis kunci di keystore
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+
+/// Nama keystore untuk pesan ke user
+pub const STORE_NAME: &str = "macOS Keychain";
+
+/// errSecItemNotFound: keychain tidak berisi identity sama sekali
+const ERR_SEC_ITEM_NOT_FOUND: OSStatus = -25300;
+
+// Security framework (SecIdentityRef, SecCertificateRef, SecKeyRef, SecPolicyRef,
+// dan SecTrustRef semuanya object Core Foundation)
+#[link(name = "Security", kind = "framework")]
+extern "C" {
+    static kSecClass: CFStringRef;
+    static kSecClassIdentity: CFStringRef;
+    static kSecReturnRef: CFStringRef;
+    static kSecMatchLimit: CFStringRef;
+    static kSecMatchLimitAll: CFStringRef;
+    static kSecKeyAlgorithmECDSASignatureDigestX962SHA256: CFStringRef;
+    static kSecKeyAlgorithmECDSASignatureDigestX962SHA384: CFStringRef;
+    static kSecKeyAlgorithmECDSASignatureDigestX962SHA512: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA256: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA384: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA512: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPSSSHA256: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPSSSHA384: CFStringRef;
+    static kSecKeyAlgorithmRSASignatureDigestPSSSHA512: CFStringRef;
+
+    fn SecItemCopyMatching(query: CFDictionaryRef, result: *mut CFTypeRef) -> OSStatus;
+    fn SecIdentityCopyCertificate(identity: CFTypeRef, certificate: *mut CFTypeRef) -> OSStatus;
+    fn SecIdentityCopyPrivateKey(identity: CFTypeRef, key: *mut CFTypeRef) -> OSStatus;
+    fn SecCertificateCopyData(certificate: CFTypeRef) -> CFDataRef;
+    fn SecKeyCreateSignature(key: CFTypeRef, algorithm: CFStringRef, data: CFDataRef, error: *mut CFErrorRef) -> CFDataRef;
+    fn SecPolicyCreateBasicX509() -> CFTypeRef;
+    fn SecTrustCreateWithCertificates(certificates: CFTypeRef, policies: CFTypeRef, trust: *mut CFTypeRef) -> OSStatus;
+    fn SecTrustEvaluateWithError(trust: CFTypeRef, error: *mut CFErrorRef) -> bool;
+    fn SecTrustGetCertificateCount(trust: CFTypeRef) -> CFIndex;
+    fn SecTrustGetCertificateAtIndex(trust: CFTypeRef, index: CFIndex) -> CFTypeRef;
+    fn SecCopyErrorMessageString(status: OSStatus, reserved: *mut c_void) -> CFStringRef;
+}
+
+/// Object Core Foundation milik kita; dilepas dengan CFRelease di Drop
+struct Owned(CFTypeRef);
+
+/// Identity (sertifikat + kunci privat) di keychain
+pub struct Identity {
+    pub certificate: Vec<u8>, // Sertifikat DER
+    identity: Owned,          // SecIdentityRef
+}
+
+/// Kunci privat di keychain
+pub struct Key {
+    key: Owned, // SecKeyRef
+}
+
+// SecKeyRef boleh dipakai dari thread mana pun (object Security framework thread-safe)
+unsafe impl Send for Key {}
+unsafe impl Sync for Key {}
+
+/// Daftar identity di keychain yang bisa dicari (login, System, smart card)
+pub fn identities() -> Result<Vec<Identity>> {
+    let mut result: CFTypeRef = ptr::null();
+    let status = unsafe {
+        let keys = [kSecClass as CFTypeRef, kSecReturnRef as CFTypeRef, kSecMatchLimit as CFTypeRef];
+        let values = [kSecClassIdentity as CFTypeRef, kCFBooleanTrue as CFTypeRef, kSecMatchLimitAll as CFTypeRef];
+        let query = Owned(CFDictionaryCreate(
+            kCFAllocatorDefault,
+            keys.as_ptr(),
+            values.as_ptr(),
+            keys.len() as CFIndex,
+            &kCFTypeDictionaryKeyCallBacks,
+            &kCFTypeDictionaryValueCallBacks,
+        ) as CFTypeRef);
+        SecItemCopyMatching(query.0 as CFDictionaryRef, &mut result)
+    };
+    if status == ERR_SEC_ITEM_NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    check(status, "SecItemCopyMatching")?;
+    let array = Owned(result);
+
+    let mut identities = Vec::new();
+    let count = unsafe { CFArrayGetCount(array.0 as CFArrayRef) };
+    for index in 0..count {
+        let identity = unsafe { CFArrayGetValueAtIndex(array.0 as CFArrayRef, index) } as CFTypeRef;
+        let Ok(certificate) = identity_certificate(identity) else {
+            continue;
+        };
+        let identity = Owned(unsafe { CFRetain(identity) });
+        identities.push(Identity { certificate: certificate_data(certificate.0), identity });
+    }
+    Ok(identities)
+}
+
+impl Identity {
+    /// Kunci privat pasangan sertifikat
+    pub fn private_key(&self) -> Result<Key> {
+        let mut key: CFTypeRef = ptr::null();
+        check(unsafe { SecIdentityCopyPrivateKey(self.identity.0, &mut key) }, "SecIdentityCopyPrivateKey")?;
+        Ok(Key { key: Owned(key) })
+    }
+
+    /// Rantai sertifikat yang disusun SecTrust dari keychain (intermediate dan
+    /// root sistem), penandatangan dulu
+    pub fn chain(&self) -> Result<Vec<Vec<u8>>> {
+        let certificate = identity_certificate(self.identity.0)?;
+        let policy = Owned(unsafe { SecPolicyCreateBasicX509() });
+        let mut trust: CFTypeRef = ptr::null();
+        check(
+            unsafe { SecTrustCreateWithCertificates(certificate.0, policy.0, &mut trust) },
+            "SecTrustCreateWithCertificates",
+        )?;
+        let trust = Owned(trust);
+
+        // Hasil evaluasi diabaikan: rantai tetap disusun walaupun root tidak dipercaya
+        let mut error: CFErrorRef = ptr::null_mut();
+        unsafe {
+            SecTrustEvaluateWithError(trust.0, &mut error);
+            if !error.is_null() {
+                CFRelease(error as CFTypeRef);
+            }
+        }
+
+        let count = unsafe { SecTrustGetCertificateCount(trust.0) };
+        let mut certificates = Vec::new();
+        for index in 0..count {
+            // Sertifikat dari SecTrust tidak dimiliki pemanggil
+            let element = unsafe { SecTrustGetCertificateAtIndex(trust.0, index) };
+            if !element.is_null() {
+                certificates.push(certificate_data(element));
+            }
+        }
+        if certificates.is_empty() {
+            certificates.push(certificate_data(certificate.0));
+        }
+        Ok(certificates)
+    }
+}
+
+impl Key {
+    /// Tandatangani digest dengan SecKeyCreateSignature; ECDSA X9.62 sudah DER
+    pub fn sign(&self, kind: KeyKind, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        let algorithm = unsafe {
+            match (kind, algorithm) {
+                (KeyKind::Ecdsa(_), DigestAlgorithm::Sha256) => kSecKeyAlgorithmECDSASignatureDigestX962SHA256,
+                (KeyKind::Ecdsa(_), DigestAlgorithm::Sha384) => kSecKeyAlgorithmECDSASignatureDigestX962SHA384,
+                (KeyKind::Ecdsa(_), DigestAlgorithm::Sha512) => kSecKeyAlgorithmECDSASignatureDigestX962SHA512,
+                (KeyKind::Rsa(RsaPadding::Pkcs1v15), DigestAlgorithm::Sha256) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA256,
+                (KeyKind::Rsa(RsaPadding::Pkcs1v15), DigestAlgorithm::Sha384) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA384,
+                (KeyKind::Rsa(RsaPadding::Pkcs1v15), DigestAlgorithm::Sha512) => kSecKeyAlgorithmRSASignatureDigestPKCS1v15SHA512,
+                (KeyKind::Rsa(RsaPadding::Pss), DigestAlgorithm::Sha256) => kSecKeyAlgorithmRSASignatureDigestPSSSHA256,
+                (KeyKind::Rsa(RsaPadding::Pss), DigestAlgorithm::Sha384) => kSecKeyAlgorithmRSASignatureDigestPSSSHA384,
+                (KeyKind::Rsa(RsaPadding::Pss), DigestAlgorithm::Sha512) => kSecKeyAlgorithmRSASignatureDigestPSSSHA512,
+            }
+        };
+        let data = Owned(unsafe { CFDataCreate(kCFAllocatorDefault, digest.as_ptr(), digest.len() as CFIndex) } as CFTypeRef);
+        let mut error: CFErrorRef = ptr::null_mut();
+        let signature = unsafe { SecKeyCreateSignature(self.key.0, algorithm, data.0 as CFDataRef, &mut error) };
+        if signature.is_null() {
+            let message = if error.is_null() {
+                "unknown error".to_string()
+            } else {
+                let error = Owned(error as CFTypeRef);
+                let description = Owned(unsafe { CFErrorCopyDescription(error.0 as CFErrorRef) } as CFTypeRef);
+                string_value(description.0 as CFStringRef)
+            };
+            return Err(anyhow!("SecKeyCreateSignature failed: {}", message));
+        }
+        let signature = Owned(signature as CFTypeRef);
+        Ok(data_bytes(signature.0 as CFDataRef))
+    }
+}
+
+impl Drop for Owned {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { CFRelease(self.0) };
+        }
+    }
+}
+
+/// SecCertificateRef milik identity
+fn identity_certificate(identity: CFTypeRef) -> Result<Owned> {
+    let mut certificate: CFTypeRef = ptr::null();
+    check(unsafe { SecIdentityCopyCertificate(identity, &mut certificate) }, "SecIdentityCopyCertificate")?;
+    Ok(Owned(certificate))
+}
+
+/// DER dari SecCertificateRef
+fn certificate_data(certificate: CFTypeRef) -> Vec<u8> {
+    let data = Owned(unsafe { SecCertificateCopyData(certificate) } as CFTypeRef);
+    data_bytes(data.0 as CFDataRef)
+}
+
+/// Isi CFData
+fn data_bytes(data: CFDataRef) -> Vec<u8> {
+    if data.is_null() {
+        return Vec::new();
+    }
+    unsafe { std::slice::from_raw_parts(CFDataGetBytePtr(data), CFDataGetLength(data) as usize).to_vec() }
+}
+
+/// Isi CFString sebagai String UTF-8
+fn string_value(string: CFStringRef) -> String {
+    if string.is_null() {
+        return String::new();
+    }
+    unsafe {
+        let size = CFStringGetMaximumSizeForEncoding(CFStringGetLength(string), kCFStringEncodingUTF8) + 1;
+        let mut buffer = vec![0u8; size as usize];
+        if CFStringGetCString(string, buffer.as_mut_ptr().cast(), size, kCFStringEncodingUTF8) == 0 {
+            return String::new();
+        }
+        CStr::from_ptr(buffer.as_ptr().cast()).to_string_lossy().into_owned()
+    }
+}
+
+/// Ubah OSStatus Security framework menjadi error
+fn check(status: OSStatus, function: &str) -> Result<()> {
+    if status == 0 {
+        return Ok(());
+    }
+    let message = Owned(unsafe { SecCopyErrorMessageString(status, ptr::null_mut()) } as CFTypeRef);
+    Err(anyhow!("{} failed: {} ({})", function, string_value(message.0 as CFStringRef), status))
+}
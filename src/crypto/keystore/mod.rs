@@ -0,0 +1,233 @@
+// Backend keystore OS: kunci privat disimpan di Windows certificate store (CNG)
+// atau macOS Keychain, dan signature dihitung oleh API sistem operasi.
+// Dikompilasi dengan fitur Cargo opsional `keystore`.
+#![cfg_attr(not(feature = "keystore"), allow(dead_code, unused_imports))]
+
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+#[cfg(feature = "keystore")]
+use sha1::{Digest, Sha1}; // Thumbprint sertifikat (SHA-1, seperti di certmgr dan Keychain Access)
+use std::time::SystemTime; // Sertifikat yang masih berlaku
+use x509_cert::der::asn1::ObjectIdentifier; // OID atribut nama
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+use x509_cert::Certificate; // Sertifikat di keystore
+
+use crate::crypto::cert::CertificateInfo; // Masa berlaku untuk pesan error
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::ecc::Curve; // Kurva ECDSA
+use crate::crypto::rsa::{RsaPadding, RsaPublic}; // Padding dan kunci publik RSA
+use crate::crypto::signer::{ecdsa_with, Credentials, Signer}; // Trait backend penandatanganan
+
+// platform = API keystore sistem operasi: certificate store + NCrypt (Windows),
+// Keychain + Security framework (macOS), atau error di platform lain
+#[cfg(all(feature = "keystore", target_os = "windows"))]
+#[path = "windows.rs"]
+mod platform;
+#[cfg(all(feature = "keystore", target_os = "macos"))]
+#[path = "macos.rs"]
+mod platform;
+#[cfg(all(feature = "keystore", not(any(target_os = "windows", target_os = "macos"))))]
+#[path = "unsupported.rs"]
+mod platform;
+
+// OID common name (CN) di subject sertifikat
+const ID_AT_COMMON_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.3");
+
+/// Jenis kunci di keystore, dari kunci publik sertifikatnya
+#[derive(Clone, Copy)]
+enum KeyKind {
+    /// ECDSA P-256/P-384/P-521 (kurva dipakai untuk signature r || s dari CNG)
+    Ecdsa(#[cfg_attr(not(target_os = "windows"), allow(dead_code))] Curve),
+    /// RSA dengan padding PKCS#1 v1.5 atau PSS
+    Rsa(RsaPadding),
+}
+
+/// Signer yang menandatangani lewat API keystore OS; kunci privat tidak pernah
+/// keluar dari keystore (dan bisa berada di smart card yang terdaftar di sana)
+#[cfg(feature = "keystore")]
+struct KeystoreSigner {
+    key: platform::Key,              // Handle kunci privat di keystore
+    kind: KeyKind,                   // Jenis kunci dan padding
+    spki: SubjectPublicKeyInfoOwned, // Kunci publik dari sertifikat
+    description: String,             // Untuk ditampilkan ke user
+}
+
+/// Cari sertifikat di keystore OS dan siapkan kunci privat pasangannya
+///
+/// Sertifikat dipilih dengan thumbprint SHA-1 (40 digit hex, spasi dan titik
+/// dua diabaikan) atau dengan common name persis (tanpa membedakan huruf besar).
+/// Jika beberapa sertifikat memiliki CN yang sama, dipakai satu-satunya yang
+/// masih berlaku; jika masih lebih dari satu, user harus memakai thumbprint.
+/// Rantai sertifikat disusun oleh API OS dari store (intermediate dan root).
+///
+/// Parameter:
+///   - selector: thumbprint atau common name sertifikat penandatangan
+///   - rsa_padding: skema padding jika kuncinya RSA
+///
+/// Return: signer yang memanggil API OS dan sertifikat DER (penandatangan dulu, lalu rantainya)
+#[cfg(feature = "keystore")]
+pub fn load_keystore(selector: &str, rsa_padding: RsaPadding) -> Result<Credentials> {
+    let identities = platform::identities()?;
+    let index = select_identity(selector, identities.iter().map(|identity| identity.certificate.as_slice()))?;
+    let identity = &identities[index];
+
+    let certificate = Certificate::from_der(&identity.certificate)
+        .map_err(|e| anyhow!("invalid certificate in the {}: {}", platform::STORE_NAME, e))?;
+    let label = common_name(&certificate).unwrap_or_else(|| certificate.tbs_certificate.subject.to_string());
+    let spki = certificate.tbs_certificate.subject_public_key_info.clone();
+    let (kind, algorithm) = key_kind(&spki, rsa_padding).with_context(|| format!("cannot use certificate '{}'", label))?;
+
+    let key = identity
+        .private_key()
+        .with_context(|| format!("cannot use the private key of certificate '{}' in the {}", label, platform::STORE_NAME))?;
+    let certificates = identity
+        .chain()
+        .with_context(|| format!("cannot build the certificate chain of '{}'", label))?;
+
+    let signer = KeystoreSigner {
+        key,
+        kind,
+        spki,
+        description: format!("{} in the {} ({})", algorithm, platform::STORE_NAME, label),
+    };
+    Ok((Box::new(signer), certificates))
+}
+
+/// Tanpa fitur keystore keystore OS tidak bisa dipakai
+#[cfg(not(feature = "keystore"))]
+pub fn load_keystore(_selector: &str, _rsa_padding: RsaPadding) -> Result<Credentials> {
+    Err(crate::error::missing_feature("OS keystore", "keystore"))
+}
+
+#[cfg(feature = "keystore")]
+impl Signer for KeystoreSigner {
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        self.key.sign(self.kind, digest, algorithm)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        match self.kind {
+            KeyKind::Ecdsa(_) => Ok(ecdsa_with(digest)),
+            KeyKind::Rsa(padding) => padding.signature_algorithm(digest),
+        }
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.spki.clone())
+    }
+
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+}
+
+/// Pilih sertifikat yang cocok dengan `selector` dari sertifikat di keystore
+///
+/// Return: indeks sertifikat yang dipilih
+#[cfg(feature = "keystore")]
+fn select_identity<'a>(selector: &str, certificates: impl Iterator<Item = &'a [u8]>) -> Result<usize> {
+    let wanted = parse_thumbprint(selector);
+    let mut matches = Vec::new();
+    for (index, der) in certificates.enumerate() {
+        // Sertifikat yang tidak bisa diurai tidak mungkin dipakai untuk signing
+        let Ok(certificate) = Certificate::from_der(der) else {
+            continue;
+        };
+        let found = match &wanted {
+            Some(thumbprint) => Sha1::digest(der)[..] == thumbprint[..],
+            None => common_name(&certificate).is_some_and(|name| name.eq_ignore_ascii_case(selector.trim())),
+        };
+        if found {
+            matches.push((index, certificate, der));
+        }
+    }
+
+    if matches.len() > 1 {
+        // Sertifikat lama yang sudah diperpanjang biasanya masih ada di store
+        let now = SystemTime::now();
+        let valid: Vec<usize> = matches
+            .iter()
+            .filter(|(_, certificate, _)| {
+                let validity = &certificate.tbs_certificate.validity;
+                validity.not_before.to_system_time() <= now && now <= validity.not_after.to_system_time()
+            })
+            .map(|(index, _, _)| *index)
+            .collect();
+        if let [index] = valid.as_slice() {
+            return Ok(*index);
+        }
+    }
+    match matches.as_slice() {
+        [(index, _, _)] => Ok(*index),
+        [] => bail!(
+            "no certificate with a private key matching '{}' in the {}; pass the SHA-1 thumbprint or the exact common name",
+            selector,
+            platform::STORE_NAME
+        ),
+        _ => {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|(_, _, der)| match CertificateInfo::from_der(der) {
+                    Some(info) => format!("{} (valid until {})", thumbprint(der), crate::pdf::text::human_time(info.not_after)),
+                    None => thumbprint(der),
+                })
+                .collect();
+            bail!(
+                "several certificates match '{}'; choose one by thumbprint: {}",
+                selector,
+                candidates.join(", ")
+            )
+        }
+    }
+}
+
+/// Thumbprint dari selector: 40 digit hex, boleh dengan spasi atau titik dua
+/// (format salinan certmgr.msc dan Keychain Access)
+fn parse_thumbprint(selector: &str) -> Option<Vec<u8>> {
+    let digits: String = selector.chars().filter(|c| !c.is_whitespace() && *c != ':').collect();
+    if digits.len() != 40 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Thumbprint SHA-1 sertifikat dalam hex huruf besar
+#[cfg(feature = "keystore")]
+fn thumbprint(der: &[u8]) -> String {
+    Sha1::digest(der).iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Common name (CN) pertama di subject sertifikat
+fn common_name(certificate: &Certificate) -> Option<String> {
+    certificate
+        .tbs_certificate
+        .subject
+        .0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .find(|atv| atv.oid == ID_AT_COMMON_NAME)
+        .and_then(|atv| std::str::from_utf8(atv.value.value()).ok())
+        .map(|name| name.to_string())
+}
+
+/// Jenis kunci dari kunci publik sertifikat
+///
+/// Return: jenis kunci dan deskripsinya, misalnya "RSA PSS"
+fn key_kind(spki: &SubjectPublicKeyInfoOwned, rsa_padding: RsaPadding) -> Result<(KeyKind, String)> {
+    match Curve::from_spki_algorithm(&spki.algorithm)? {
+        Some(Curve::Ed25519) => bail!("Ed25519 keys in an OS keystore are not supported; only P-256, P-384, P-521 and RSA are supported"),
+        Some(curve) => Ok((KeyKind::Ecdsa(curve), curve.label().to_string())),
+        None => {
+            RsaPublic::from_spki(spki)?;
+            let padding = match rsa_padding {
+                RsaPadding::Pkcs1v15 => "PKCS#1 v1.5",
+                RsaPadding::Pss => "PSS",
+            };
+            Ok((KeyKind::Rsa(rsa_padding), format!("RSA {}", padding)))
+        }
+    }
+}
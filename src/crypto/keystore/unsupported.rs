@@ -0,0 +1,43 @@
+// Platform tanpa keystore OS yang didukung: --keystore-cert selalu gagal
+// dengan pesan yang jelas (gunakan PKCS#11 untuk smart card di Linux)
+
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+
+use super::KeyKind; // Jenis kunci di keystore
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+
+/// Nama keystore untuk pesan ke user
+pub const STORE_NAME: &str = "OS keystore";
+
+/// Sertifikat beserta kunci privatnya di keystore (tidak pernah dibuat di sini)
+pub struct Identity {
+    pub certificate: Vec<u8>, // Sertifikat DER
+}
+
+/// Kunci privat di keystore (tidak ada nilainya di platform ini)
+pub enum Key {}
+
+/// Daftar sertifikat yang memiliki kunci privat
+pub fn identities() -> Result<Vec<Identity>> {
+    bail!("--keystore-cert needs the Windows certificate store or the macOS Keychain; on this system use --pkcs11-module for keys on a token")
+}
+
+impl Identity {
+    /// Kunci privat pasangan sertifikat
+    pub fn private_key(&self) -> Result<Key> {
+        bail!("no OS keystore on this system")
+    }
+
+    /// Rantai sertifikat dari store, penandatangan dulu
+    pub fn chain(&self) -> Result<Vec<Vec<u8>>> {
+        bail!("no OS keystore on this system")
+    }
+}
+
+impl Key {
+    /// Tandatangani digest; signature ECDSA dalam DER
+    pub fn sign(&self, _kind: KeyKind, _digest: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        match *self {}
+    }
+}
@@ -0,0 +1,219 @@
+// Windows certificate store: sertifikat di store "My" milik user dan mesin
+// lokal, signing lewat CNG (NCrypt) dengan kunci yang terdaftar di store,
+// termasuk kunci di smart card (Windows menampilkan dialog PIN jika perlu)
+
+// Import library yang diperlukan
+use anyhow::{anyhow, Result}; // Untuk error handling yang fleksibel
+use std::ffi::c_void; // Pointer parameter padding NCrypt
+use std::ptr; // Pointer null untuk API Win32
+use windows_sys::Win32::Foundation::BOOL; // Hasil API crypt32
+use windows_sys::Win32::Security::Cryptography::{
+    CertCloseStore, CertDuplicateCertificateContext, CertEnumCertificatesInStore, CertFreeCertificateChain,
+    CertFreeCertificateContext, CertGetCertificateChain, CertGetCertificateContextProperty, CertOpenStore,
+    CryptAcquireCertificatePrivateKey, NCryptFreeObject, NCryptSignHash, BCRYPT_PKCS1_PADDING_INFO,
+    BCRYPT_PSS_PADDING_INFO, BCRYPT_SHA256_ALGORITHM, BCRYPT_SHA384_ALGORITHM, BCRYPT_SHA512_ALGORITHM,
+    CERT_CHAIN_CONTEXT, CERT_CHAIN_PARA, CERT_CONTEXT, CERT_KEY_PROV_INFO_PROP_ID, CERT_STORE_OPEN_EXISTING_FLAG,
+    CERT_STORE_PROV_SYSTEM_W, CERT_STORE_READONLY_FLAG, CERT_SYSTEM_STORE_CURRENT_USER_ID,
+    CERT_SYSTEM_STORE_LOCAL_MACHINE_ID, CERT_SYSTEM_STORE_LOCATION_SHIFT, CRYPT_ACQUIRE_COMPARE_KEY_FLAG,
+    CRYPT_ACQUIRE_ONLY_NCRYPT_KEY_FLAG, NCRYPT_KEY_HANDLE, NCRYPT_PAD_PKCS1_FLAG, NCRYPT_PAD_PSS_FLAG,
+}; // Certificate store dan CNG
+
+use super::KeyKind; // Jenis kunci di keystore
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::ecc; // Konversi signature ECDSA ke DER
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+
+/// Nama keystore untuk pesan ke user
+pub const STORE_NAME: &str = "Windows certificate store";
+
+/// Lokasi store "My" yang diperiksa: user saat ini, lalu mesin lokal
+const STORE_LOCATIONS: [u32; 2] = [
+    CERT_SYSTEM_STORE_CURRENT_USER_ID << CERT_SYSTEM_STORE_LOCATION_SHIFT,
+    CERT_SYSTEM_STORE_LOCAL_MACHINE_ID << CERT_SYSTEM_STORE_LOCATION_SHIFT,
+];
+
+/// Sertifikat di store yang memiliki kunci privat
+pub struct Identity {
+    pub certificate: Vec<u8>,     // Sertifikat DER
+    context: *const CERT_CONTEXT, // Context sertifikat (duplikat, dilepas di Drop)
+}
+
+/// Kunci privat CNG pasangan sertifikat
+pub struct Key {
+    handle: NCRYPT_KEY_HANDLE, // Handle NCrypt
+    owned: bool,               // Handle harus dilepas oleh pemanggil
+}
+
+// Handle NCrypt boleh dipakai dari thread lain; KSP menyerialkan operasinya sendiri
+unsafe impl Send for Key {}
+unsafe impl Sync for Key {}
+
+/// Daftar sertifikat yang memiliki kunci privat di store "My" user dan mesin lokal
+pub fn identities() -> Result<Vec<Identity>> {
+    let store_name: Vec<u16> = "MY".encode_utf16().chain(Some(0)).collect();
+    let mut identities: Vec<Identity> = Vec::new();
+    for location in STORE_LOCATIONS {
+        let store = unsafe {
+            CertOpenStore(
+                CERT_STORE_PROV_SYSTEM_W,
+                0,
+                0,
+                location | CERT_STORE_READONLY_FLAG | CERT_STORE_OPEN_EXISTING_FLAG,
+                store_name.as_ptr().cast(),
+            )
+        };
+        // Store mesin lokal bisa tidak ada atau tidak boleh dibaca user ini
+        if store.is_null() {
+            continue;
+        }
+        let mut context: *const CERT_CONTEXT = ptr::null();
+        loop {
+            // Context sebelumnya dilepas oleh CertEnumCertificatesInStore
+            context = unsafe { CertEnumCertificatesInStore(store, context) };
+            if context.is_null() {
+                break;
+            }
+            if !has_private_key(context) {
+                continue;
+            }
+            let certificate = unsafe { encoded(context) };
+            // Sertifikat yang sama bisa terdaftar di store user dan mesin
+            if identities.iter().any(|identity| identity.certificate == certificate) {
+                continue;
+            }
+            let context = unsafe { CertDuplicateCertificateContext(context) };
+            identities.push(Identity { certificate, context });
+        }
+        unsafe { CertCloseStore(store, 0) };
+    }
+    Ok(identities)
+}
+
+impl Identity {
+    /// Kunci privat CNG pasangan sertifikat
+    pub fn private_key(&self) -> Result<Key> {
+        let mut handle = 0;
+        let mut key_spec = 0;
+        let mut owned: BOOL = 0;
+        let acquired = unsafe {
+            CryptAcquireCertificatePrivateKey(
+                self.context,
+                CRYPT_ACQUIRE_ONLY_NCRYPT_KEY_FLAG | CRYPT_ACQUIRE_COMPARE_KEY_FLAG,
+                ptr::null(),
+                &mut handle,
+                &mut key_spec,
+                &mut owned,
+            )
+        };
+        if acquired == 0 {
+            return Err(anyhow!(
+                "CryptAcquireCertificatePrivateKey failed: {} (keys in a legacy CryptoAPI CSP are not supported; import the certificate with a CNG key storage provider)",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(Key { handle, owned: owned != 0 })
+    }
+
+    /// Rantai sertifikat yang disusun oleh chain engine Windows dari store
+    /// user (CA, Root, My), penandatangan dulu
+    pub fn chain(&self) -> Result<Vec<Vec<u8>>> {
+        let mut parameters: CERT_CHAIN_PARA = unsafe { std::mem::zeroed() };
+        parameters.cbSize = std::mem::size_of::<CERT_CHAIN_PARA>() as u32;
+        let mut chain: *mut CERT_CHAIN_CONTEXT = ptr::null_mut();
+        let built = unsafe {
+            CertGetCertificateChain(0, self.context, ptr::null(), ptr::null_mut(), &parameters, 0, ptr::null(), &mut chain)
+        };
+        if built == 0 || chain.is_null() {
+            return Err(anyhow!("CertGetCertificateChain failed: {}", std::io::Error::last_os_error()));
+        }
+
+        let mut certificates = Vec::new();
+        unsafe {
+            // Rantai sederhana pertama: dari penandatangan sampai root (atau sejauh yang ditemukan)
+            if (*chain).cChain > 0 {
+                let simple = &**(*chain).rgpChain;
+                for index in 0..simple.cElement as usize {
+                    let element = &**simple.rgpElement.add(index);
+                    certificates.push(encoded(element.pCertContext));
+                }
+            }
+            CertFreeCertificateChain(chain);
+        }
+        if certificates.is_empty() {
+            certificates.push(self.certificate.clone());
+        }
+        Ok(certificates)
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        unsafe { CertFreeCertificateContext(self.context) };
+    }
+}
+
+impl Key {
+    /// Tandatangani digest dengan NCryptSignHash; signature ECDSA dalam DER
+    pub fn sign(&self, kind: KeyKind, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        let algorithm_id = match algorithm {
+            DigestAlgorithm::Sha256 => BCRYPT_SHA256_ALGORITHM,
+            DigestAlgorithm::Sha384 => BCRYPT_SHA384_ALGORITHM,
+            DigestAlgorithm::Sha512 => BCRYPT_SHA512_ALGORITHM,
+        };
+        let pkcs1 = BCRYPT_PKCS1_PADDING_INFO { pszAlgId: algorithm_id };
+        let pss = BCRYPT_PSS_PADDING_INFO { pszAlgId: algorithm_id, cbSalt: algorithm.output_len() as u32 };
+        let (padding, flags): (*const c_void, u32) = match kind {
+            KeyKind::Ecdsa(_) => (ptr::null(), 0),
+            KeyKind::Rsa(RsaPadding::Pkcs1v15) => ((&pkcs1 as *const BCRYPT_PKCS1_PADDING_INFO).cast(), NCRYPT_PAD_PKCS1_FLAG),
+            KeyKind::Rsa(RsaPadding::Pss) => ((&pss as *const BCRYPT_PSS_PADDING_INFO).cast(), NCRYPT_PAD_PSS_FLAG),
+        };
+
+        // Panggilan pertama menanyakan ukuran signature
+        let mut size = 0u32;
+        check(unsafe { NCryptSignHash(self.handle, padding, digest.as_ptr(), digest.len() as u32, ptr::null_mut(), 0, &mut size, flags) })?;
+        let mut signature = vec![0u8; size as usize];
+        check(unsafe {
+            NCryptSignHash(self.handle, padding, digest.as_ptr(), digest.len() as u32, signature.as_mut_ptr(), size, &mut size, flags)
+        })?;
+        signature.truncate(size as usize);
+
+        // CNG mengembalikan ECDSA sebagai r || s
+        Ok(match kind {
+            KeyKind::Ecdsa(curve) => ecc::signature_to_der(curve, &signature),
+            KeyKind::Rsa(_) => signature,
+        })
+    }
+}
+
+impl Drop for Key {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { NCryptFreeObject(self.handle) };
+        }
+    }
+}
+
+/// Apakah sertifikat terhubung dengan kunci privat di store
+fn has_private_key(context: *const CERT_CONTEXT) -> bool {
+    let mut size = 0u32;
+    unsafe { CertGetCertificateContextProperty(context, CERT_KEY_PROV_INFO_PROP_ID, ptr::null_mut(), &mut size) != 0 }
+}
+
+/// DER sertifikat dari context-nya
+///
+/// Safety: `context` harus menunjuk ke CERT_CONTEXT yang masih hidup
+unsafe fn encoded(context: *const CERT_CONTEXT) -> Vec<u8> {
+    std::slice::from_raw_parts((*context).pbCertEncoded, (*context).cbCertEncoded as usize).to_vec()
+}
+
+/// Ubah HRESULT NCrypt menjadi error (misalnya PIN smart card dibatalkan)
+fn check(status: i32) -> Result<()> {
+    if status == 0 {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "NCryptSignHash failed: {} (0x{:08X})",
+        std::io::Error::from_raw_os_error(status),
+        status as u32
+    ))
+}
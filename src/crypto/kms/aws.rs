@@ -0,0 +1,246 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use base64::engine::general_purpose::STANDARD as BASE64; // Base64 di body JSON
+use base64::Engine; // encode / decode
+use chrono::Utc; // Tanggal request untuk AWS Signature Version 4
+use hmac::{Hmac, Mac}; // HMAC-SHA256 untuk AWS Signature Version 4
+use serde_json::{json, Value}; // Body request dan response
+use sha2::{Digest, Sha256}; // Hash payload dan canonical request
+use std::env; // Kredensial dan region dari environment
+use std::fs; // File kredensial ~/.aws/credentials
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+use super::{json_str, KmsAlgorithm, KmsKey}; // Bagian bersama backend KMS
+use crate::cancel::CancellationToken; // Batas waktu request
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::http; // Request HTTP ke API KMS
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::signer::Signer; // Trait backend penandatanganan
+
+/// Access key AWS untuk menandatangani request API
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>, // Untuk kredensial sementara (STS / SSO)
+}
+
+/// Klien API AWS KMS untuk satu region
+struct AwsClient {
+    region: String,              // Region AWS, misalnya ap-southeast-3
+    endpoint: String,            // https://kms.<region>.amazonaws.com
+    credentials: AwsCredentials, // Access key untuk SigV4
+}
+
+/// Signer yang memanggil Sign API AWS KMS; kunci privat tidak pernah keluar dari KMS
+pub struct AwsKmsSigner {
+    client: AwsClient,               // Klien API
+    key_id: String,                  // Key ID, ARN, atau alias
+    algorithm: KmsAlgorithm,         // Algoritma sesuai KeySpec
+    spki: SubjectPublicKeyInfoOwned, // Kunci publik dari GetPublicKey
+}
+
+impl AwsKmsSigner {
+    /// Baca kredensial, lalu ambil kunci publik dan jenis kunci dari KMS
+    pub fn connect(key: &KmsKey, rsa_padding: RsaPadding) -> Result<AwsKmsSigner> {
+        let region = key
+            .region
+            .clone()
+            .or_else(|| region_from_arn(&key.key_id))
+            .or_else(|| env::var("AWS_REGION").ok())
+            .or_else(|| env::var("AWS_DEFAULT_REGION").ok())
+            .ok_or_else(|| anyhow!("unknown AWS region; pass --kms-region, use a key ARN, or set AWS_REGION"))?;
+        // Endpoint bisa diganti (VPC endpoint, LocalStack) seperti di AWS CLI
+        let endpoint = env::var("AWS_ENDPOINT_URL_KMS")
+            .or_else(|_| env::var("AWS_ENDPOINT_URL"))
+            .unwrap_or_else(|_| format!("https://kms.{}.amazonaws.com", region));
+        let client = AwsClient {
+            region,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            credentials: AwsCredentials::load()?,
+        };
+
+        let response = client.call("GetPublicKey", &json!({ "KeyId": key.key_id }))?;
+        if response["KeyUsage"].as_str() != Some("SIGN_VERIFY") {
+            bail!("key usage is {}, not SIGN_VERIFY", response["KeyUsage"]);
+        }
+        let algorithm = match json_str(&response, "KeySpec")? {
+            "ECC_NIST_P256" => KmsAlgorithm::EcdsaP256,
+            spec if spec.starts_with("RSA_") => KmsAlgorithm::Rsa(rsa_padding),
+            spec => bail!("unsupported key spec {}; only ECC_NIST_P256 and RSA keys are supported", spec),
+        };
+        let der = BASE64.decode(json_str(&response, "PublicKey")?)?;
+        let spki = SubjectPublicKeyInfoOwned::from_der(&der).map_err(|e| anyhow!("invalid public key from AWS KMS: {}", e))?;
+        Ok(AwsKmsSigner { client, key_id: key.key_id.clone(), algorithm, spki })
+    }
+}
+
+impl AwsClient {
+    /// Panggil satu action API KMS (JSON 1.1, ditandatangani dengan SigV4)
+    fn call(&self, action: &str, body: &Value) -> Result<Value> {
+        let body = body.to_string();
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = self
+            .endpoint
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .ok_or_else(|| anyhow!("invalid AWS KMS endpoint {}", self.endpoint))?;
+        let target = format!("TrentService.{}", action);
+
+        // Header yang ikut ditandatangani, urut berdasarkan nama
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1"),
+            ("host", host),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.as_str()));
+        }
+        headers.push(("x-amz-target", target.as_str()));
+        let canonical_headers: String = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value)).collect();
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(body.as_bytes()))
+        );
+        let scope = format!("{}/{}/kms/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [self.region.as_str(), "kms", "aws4_request"].iter().fold(
+            hmac_sha256(format!("AWS4{}", self.credentials.secret_access_key).as_bytes(), date.as_bytes()),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.credentials.access_key_id,
+            scope,
+            signed_headers,
+            hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()))
+        );
+
+        // Host dikirim oleh klien HTTP dari URL
+        let mut request_headers: Vec<(&str, &str)> = headers.into_iter().filter(|(name, _)| *name != "host").collect();
+        request_headers.push(("authorization", authorization.as_str()));
+        let response = http::call_api(
+            "POST",
+            &format!("{}/", self.endpoint),
+            &request_headers,
+            Some(body.as_bytes()),
+            &CancellationToken::new(),
+        )?;
+        serde_json::from_slice(&response).context("invalid response from AWS KMS")
+    }
+}
+
+impl Signer for AwsKmsSigner {
+    fn sign_digest(&self, digest: &[u8], hash: DigestAlgorithm) -> Result<Vec<u8>> {
+        // Kunci P-256 hanya bisa dengan SHA-256
+        self.algorithm.signature_algorithm(hash)?;
+        // Nama SigningAlgorithm, misalnya RSASSA_PSS_SHA_384
+        let algorithm = match self.algorithm {
+            KmsAlgorithm::EcdsaP256 => "ECDSA_SHA_256".to_string(),
+            KmsAlgorithm::Rsa(RsaPadding::Pkcs1v15) => format!("RSASSA_PKCS1_V1_5_SHA_{}", hash.bits()),
+            KmsAlgorithm::Rsa(RsaPadding::Pss) => format!("RSASSA_PSS_SHA_{}", hash.bits()),
+        };
+        let response = self.client.call(
+            "Sign",
+            &json!({
+                "KeyId": self.key_id,
+                "Message": BASE64.encode(digest),
+                "MessageType": "DIGEST",
+                "SigningAlgorithm": algorithm,
+            }),
+        )?;
+        // ECDSA sudah dalam format DER, RSA berupa signature raw
+        Ok(BASE64.decode(json_str(&response, "Signature")?)?)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        self.algorithm.signature_algorithm(digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.spki.clone())
+    }
+
+    fn description(&self) -> String {
+        format!("{} in AWS KMS", self.algorithm.label())
+    }
+}
+
+impl AwsCredentials {
+    /// Kredensial dari environment (AWS_ACCESS_KEY_ID, ...) atau dari profil
+    /// AWS_PROFILE (default: "default") di ~/.aws/credentials
+    fn load() -> Result<AwsCredentials> {
+        if let (Ok(access_key_id), Ok(secret_access_key)) = (env::var("AWS_ACCESS_KEY_ID"), env::var("AWS_SECRET_ACCESS_KEY")) {
+            return Ok(AwsCredentials {
+                access_key_id,
+                secret_access_key,
+                session_token: env::var("AWS_SESSION_TOKEN").ok(),
+            });
+        }
+
+        let path = match env::var("AWS_SHARED_CREDENTIALS_FILE") {
+            Ok(path) => path,
+            Err(_) => format!("{}/.aws/credentials", env::var("HOME").unwrap_or_default()),
+        };
+        let profile = env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
+        let missing = || anyhow!("no AWS credentials: set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY, or add profile [{}] to {}", profile, path);
+        let content = fs::read_to_string(&path).map_err(|_| missing())?;
+
+        // Format INI sederhana: [profil] lalu baris key = value
+        let mut section = String::new();
+        let (mut access_key_id, mut secret_access_key, mut session_token) = (None, None, None);
+        for line in content.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                section = name.trim().to_string();
+            } else if section == profile {
+                if let Some((name, value)) = line.split_once('=') {
+                    let value = Some(value.trim().to_string());
+                    match name.trim() {
+                        "aws_access_key_id" => access_key_id = value,
+                        "aws_secret_access_key" => secret_access_key = value,
+                        "aws_session_token" => session_token = value,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(AwsCredentials {
+            access_key_id: access_key_id.ok_or_else(missing)?,
+            secret_access_key: secret_access_key.ok_or_else(missing)?,
+            session_token,
+        })
+    }
+}
+
+/// Region dari ARN kunci (arn:aws:kms:<region>:<akun>:key/<id>)
+fn region_from_arn(key_id: &str) -> Option<String> {
+    let mut parts = key_id.split(':');
+    if parts.next() != Some("arn") {
+        return None;
+    }
+    parts.nth(2).filter(|region| !region.is_empty()).map(str::to_string)
+}
+
+/// HMAC-SHA256
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Hex huruf kecil
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
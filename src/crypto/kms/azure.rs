@@ -0,0 +1,128 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use ::rsa::pkcs8::EncodePublicKey; // Encoding SPKI kunci publik RSA
+use ::rsa::{BigUint, RsaPublicKey}; // Kunci publik RSA dari modulus dan eksponen
+use base64::alphabet::URL_SAFE; // Base64url di JSON Web Key
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig}; // Base64url dengan atau tanpa padding
+use base64::Engine; // encode / decode
+use serde_json::{json, Value}; // Body request dan response
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+use super::{access_token, json_str, KmsAlgorithm, KmsKey}; // Bagian bersama backend KMS
+use crate::cancel::CancellationToken; // Batas waktu request
+use crate::crypto::ecc::{self, Curve}; // Konversi signature ECDSA P-256 ke DER
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::http; // Request HTTP ke API Key Vault
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::signer::Signer; // Trait backend penandatanganan
+
+/// Versi REST API Key Vault
+const API_VERSION: &str = "7.4";
+
+/// Base64url: Key Vault mengirim tanpa padding, tetapi padding tetap diterima
+const BASE64URL: GeneralPurpose = GeneralPurpose::new(
+    &URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Signer yang memanggil operasi sign Azure Key Vault; kunci privat tidak pernah keluar dari vault
+pub struct AzureKeyVaultSigner {
+    kid: String,                     // URL kunci beserta versinya
+    token: String,                   // Access token OAuth untuk vault.azure.net
+    algorithm: KmsAlgorithm,         // Algoritma sesuai jenis kunci
+    spki: SubjectPublicKeyInfoOwned, // Kunci publik dari JSON Web Key
+}
+
+impl AzureKeyVaultSigner {
+    /// Ambil access token, lalu kunci publik (JSON Web Key) dari vault
+    pub fn connect(key: &KmsKey, rsa_padding: RsaPadding) -> Result<AzureKeyVaultSigner> {
+        let url = key.key_id.trim_end_matches('/');
+        if !url.contains("/keys/") {
+            bail!("Azure Key Vault keys must be given as https://<vault>.vault.azure.net/keys/<name>[/<version>]");
+        }
+        let token = access_token(
+            "az",
+            &["account", "get-access-token", "--resource", "https://vault.azure.net", "--query", "accessToken", "-o", "tsv"],
+        )?;
+
+        let response = call(&format!("{}?api-version={}", url, API_VERSION), &token, None)?;
+        let jwk = &response["key"];
+        let field = |name: &str| -> Result<Vec<u8>> { Ok(BASE64URL.decode(json_str(jwk, name)?)?) };
+        let der_err = |e: x509_cert::der::Error| anyhow!("invalid public key from Azure Key Vault: {}", e);
+        let (algorithm, der) = match json_str(jwk, "kty")? {
+            "EC" | "EC-HSM" => {
+                if jwk["crv"].as_str() != Some("P-256") {
+                    bail!("unsupported curve {}; only P-256 is supported", jwk["crv"]);
+                }
+                let point = [vec![0x04], field("x")?, field("y")?].concat();
+                let public_key = p256::PublicKey::from_sec1_bytes(&point).map_err(|_| anyhow!("invalid P-256 public key from Azure Key Vault"))?;
+                let der = p256::pkcs8::EncodePublicKey::to_public_key_der(&public_key).map_err(|e| anyhow!("{}", e))?;
+                (KmsAlgorithm::EcdsaP256, der.as_bytes().to_vec())
+            }
+            "RSA" | "RSA-HSM" => {
+                let public_key = RsaPublicKey::new(BigUint::from_bytes_be(&field("n")?), BigUint::from_bytes_be(&field("e")?))
+                    .map_err(|e| anyhow!("invalid RSA public key from Azure Key Vault: {}", e))?;
+                let der = public_key.to_public_key_der().map_err(|e| anyhow!("{}", e))?;
+                (KmsAlgorithm::Rsa(rsa_padding), der.as_bytes().to_vec())
+            }
+            kty => bail!("unsupported key type {}; only EC P-256 and RSA keys are supported", kty),
+        };
+
+        Ok(AzureKeyVaultSigner {
+            // kid dari response selalu berisi versi kunci, sehingga semua signature
+            // memakai versi yang sama walaupun kuncinya dirotasi di tengah jalan
+            kid: json_str(jwk, "kid")?.to_string(),
+            token,
+            algorithm,
+            spki: SubjectPublicKeyInfoOwned::from_der(&der).map_err(der_err)?,
+        })
+    }
+}
+
+impl Signer for AzureKeyVaultSigner {
+    fn sign_digest(&self, digest: &[u8], hash: DigestAlgorithm) -> Result<Vec<u8>> {
+        // Kunci P-256 hanya bisa dengan SHA-256
+        self.algorithm.signature_algorithm(hash)?;
+        // Nama algoritma JWA, misalnya PS384
+        let algorithm = match self.algorithm {
+            KmsAlgorithm::EcdsaP256 => "ES256".to_string(),
+            KmsAlgorithm::Rsa(RsaPadding::Pkcs1v15) => format!("RS{}", hash.bits()),
+            KmsAlgorithm::Rsa(RsaPadding::Pss) => format!("PS{}", hash.bits()),
+        };
+        let url = format!("{}/sign?api-version={}", self.kid, API_VERSION);
+        let body = json!({ "alg": algorithm, "value": BASE64URL.encode(digest) });
+        let signature = BASE64URL.decode(json_str(&call(&url, &self.token, Some(&body))?, "value")?)?;
+        // ES256 mengembalikan r || s, CMS membutuhkan DER
+        Ok(match self.algorithm {
+            KmsAlgorithm::EcdsaP256 => ecc::signature_to_der(Curve::P256, &signature),
+            KmsAlgorithm::Rsa(_) => signature,
+        })
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        self.algorithm.signature_algorithm(digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.spki.clone())
+    }
+
+    fn description(&self) -> String {
+        format!("{} in Azure Key Vault", self.algorithm.label())
+    }
+}
+
+/// Panggil REST API Key Vault (GET tanpa body, POST dengan body JSON)
+fn call(url: &str, token: &str, body: Option<&Value>) -> Result<Value> {
+    let authorization = format!("Bearer {}", token);
+    let body = body.map(|body| body.to_string());
+    let (method, headers) = match body {
+        Some(_) => ("POST", vec![("Authorization", authorization.as_str()), ("Content-Type", "application/json")]),
+        None => ("GET", vec![("Authorization", authorization.as_str())]),
+    };
+    let response = http::call_api(method, url, &headers, body.as_deref().map(str::as_bytes), &CancellationToken::new())?;
+    serde_json::from_slice(&response).context("invalid response from Azure Key Vault")
+}
@@ -0,0 +1,273 @@
+// Backend KMS cloud: kunci privat disimpan di AWS KMS, Google Cloud KMS, atau
+// Azure Key Vault, dan hanya digest yang dikirim ke Sign API layanan tersebut.
+// Setiap layanan adalah fitur Cargo opsional (aws-kms, gcp-kms, azure-kms).
+#![cfg_attr(not(any(feature = "aws-kms", feature = "gcp-kms", feature = "azure-kms")), allow(dead_code))]
+
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs; // Membaca file sertifikat
+use std::sync::{Arc, Mutex}; // Koneksi KMS yang dipakai bersama antar dokumen
+use std::time::{Duration, Instant}; // Umur koneksi KMS
+use x509_cert::spki::SubjectPublicKeyInfoOwned; // Kunci publik dari KMS
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::AlgorithmIdentifierOwned; // Identifier algoritma signature
+use x509_cert::Certificate; // Sertifikat penandatangan
+
+use crate::crypto::cms::split_certificates; // Sertifikat + rantainya dari satu file
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::signer::{ecdsa_with, Credentials, Signer}; // Trait backend penandatanganan
+
+// aws = AWS KMS (Sign API dengan AWS Signature Version 4)
+#[cfg(feature = "aws-kms")]
+mod aws;
+// azure = Azure Key Vault (REST API keys/sign)
+#[cfg(feature = "azure-kms")]
+mod azure;
+// gcp = Google Cloud KMS (REST API asymmetricSign)
+#[cfg(feature = "gcp-kms")]
+mod gcp;
+
+/// Environment variable berisi access token OAuth untuk Google Cloud KMS dan
+/// Azure Key Vault; jika kosong, token diambil dari CLI `gcloud` / `az`
+pub const ACCESS_TOKEN_ENV: &str = "PDFSIGN_KMS_ACCESS_TOKEN";
+
+/// Lama koneksi KMS (access token, kredensial, kunci publik) dipakai ulang;
+/// jauh di bawah umur access token gcloud/az dan kredensial STS (1 jam)
+const CONNECTION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Koneksi KMS yang masih bisa dipakai, per kunci dan padding
+static CONNECTIONS: Mutex<Vec<Connection>> = Mutex::new(Vec::new());
+
+/// Signer KMS yang sudah terhubung
+struct Connection {
+    cache_key: String,       // Key ID, region, dan padding RSA
+    signer: Arc<dyn Signer>, // Signer beserta token dan kunci publiknya
+    connected: Instant,      // Waktu terhubung
+}
+
+/// Signer dari cache koneksi; koneksi dibuang jika Sign API gagal (misalnya
+/// token sudah dicabut), sehingga dokumen berikutnya terhubung ulang
+struct SharedSigner {
+    cache_key: String,       // Kunci entri di CONNECTIONS
+    signer: Arc<dyn Signer>, // Signer bersama
+}
+
+/// Kunci penandatangan di layanan KMS cloud
+#[derive(Clone, Debug)]
+pub struct KmsKey {
+    pub key_id: String,              // Key ID/ARN/alias AWS, versi kunci GCP, atau URL kunci Azure
+    pub region: Option<String>,      // Region AWS; None = dari ARN atau AWS_REGION
+    pub certificate: Option<String>, // Sertifikat penandatangan (DER, boleh diikuti rantainya)
+}
+
+/// Layanan KMS, dikenali dari bentuk key ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KmsProvider {
+    /// AWS KMS: key ID, ARN, atau alias/<nama>
+    Aws,
+    /// Google Cloud KMS: projects/<p>/locations/<l>/keyRings/<r>/cryptoKeys/<k>/cryptoKeyVersions/<v>
+    Gcp,
+    /// Azure Key Vault: https://<vault>.vault.azure.net/keys/<nama>[/<versi>]
+    Azure,
+}
+
+impl KmsKey {
+    /// Layanan yang menyimpan kunci ini
+    pub fn provider(&self) -> KmsProvider {
+        if self.key_id.starts_with("projects/") {
+            KmsProvider::Gcp
+        } else if self.key_id.starts_with("https://") || self.key_id.starts_with("http://") {
+            KmsProvider::Azure
+        } else {
+            KmsProvider::Aws
+        }
+    }
+}
+
+impl KmsProvider {
+    /// Nama layanan untuk ditampilkan ke user
+    pub fn label(self) -> &'static str {
+        match self {
+            KmsProvider::Aws => "AWS KMS",
+            KmsProvider::Gcp => "Google Cloud KMS",
+            KmsProvider::Azure => "Azure Key Vault",
+        }
+    }
+
+    /// Fitur Cargo yang mengaktifkan backend ini
+    fn feature(self) -> &'static str {
+        match self {
+            KmsProvider::Aws => "aws-kms",
+            KmsProvider::Gcp => "gcp-kms",
+            KmsProvider::Azure => "azure-kms",
+        }
+    }
+}
+
+/// Algoritma signature kunci di KMS
+#[derive(Clone, Copy)]
+enum KmsAlgorithm {
+    /// ECDSA P-256 (hanya dengan SHA-256)
+    EcdsaP256,
+    /// RSA dengan padding PKCS#1 v1.5 atau PSS
+    Rsa(RsaPadding),
+}
+
+impl KmsAlgorithm {
+    /// AlgorithmIdentifier signature untuk CMS
+    fn signature_algorithm(self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        match self {
+            KmsAlgorithm::EcdsaP256 if digest != DigestAlgorithm::Sha256 => {
+                bail!("P-256 keys in a KMS only sign SHA-256 digests; use --digest sha256")
+            }
+            KmsAlgorithm::EcdsaP256 => Ok(ecdsa_with(digest)),
+            KmsAlgorithm::Rsa(padding) => padding.signature_algorithm(digest),
+        }
+    }
+
+    /// Deskripsi algoritma, misalnya "RSA PSS"
+    fn label(self) -> &'static str {
+        match self {
+            KmsAlgorithm::EcdsaP256 => "ECDSA P-256",
+            KmsAlgorithm::Rsa(RsaPadding::Pkcs1v15) => "RSA PKCS#1 v1.5",
+            KmsAlgorithm::Rsa(RsaPadding::Pss) => "RSA PSS",
+        }
+    }
+}
+
+/// Hubungkan ke kunci di KMS dan muat sertifikatnya
+///
+/// Parameter:
+///   - key: kunci KMS dan file sertifikatnya
+///   - rsa_padding: padding untuk kunci RSA (Google Cloud KMS memakai padding
+///     yang ditetapkan pada versi kunci)
+///
+/// Return: signer yang memanggil Sign API dan sertifikat DER (penandatangan dulu, lalu rantainya)
+pub fn load_kms(key: &KmsKey, rsa_padding: RsaPadding) -> Result<Credentials> {
+    let provider = key.provider();
+    let signer = shared_connection(key, rsa_padding).with_context(|| format!("cannot use {} key {}", provider.label(), key.key_id))?;
+
+    let certificates = match &key.certificate {
+        Some(path) => {
+            let bytes = fs::read(path).with_context(|| format!("cannot read certificate {}", path))?;
+            let certificates = split_certificates(&bytes).with_context(|| format!("cannot load certificate {}", path))?;
+            // Sertifikat penandatangan harus berisi kunci publik dari KMS
+            let leaf = certificates.first().ok_or_else(|| anyhow!("certificate file {} is empty", path))?;
+            let leaf = Certificate::from_der(leaf).map_err(|e| anyhow!("invalid certificate {}: {}", path, e))?;
+            if leaf.tbs_certificate.subject_public_key_info != signer.public_key_info()? {
+                bail!("certificate {} does not belong to {} key {}", path, provider.label(), key.key_id);
+            }
+            certificates
+        }
+        None => Vec::new(),
+    };
+    Ok((signer, certificates))
+}
+
+/// Signer untuk kunci KMS, dipakai ulang dari koneksi sebelumnya jika masih baru
+///
+/// Dalam batch atau server, setiap dokumen memanggil `load_kms`; tanpa cache,
+/// setiap dokumen mengambil access token (menjalankan gcloud/az) dan kunci
+/// publik lagi sebelum Sign API.
+fn shared_connection(key: &KmsKey, rsa_padding: RsaPadding) -> Result<Box<dyn Signer>> {
+    let cache_key = format!("{}|{:?}|{:?}", key.key_id, key.region, rsa_padding);
+    let mut connections = CONNECTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    connections.retain(|connection| connection.connected.elapsed() < CONNECTION_TTL);
+    let signer = match connections.iter().find(|connection| connection.cache_key == cache_key) {
+        Some(connection) => connection.signer.clone(),
+        None => {
+            let signer = connect(key, rsa_padding)?;
+            connections.push(Connection { cache_key: cache_key.clone(), signer: signer.clone(), connected: Instant::now() });
+            signer
+        }
+    };
+    Ok(Box::new(SharedSigner { cache_key, signer }))
+}
+
+impl SharedSigner {
+    /// Buang koneksi dari cache jika `result` gagal
+    fn forget_on_error<T>(&self, result: Result<T>) -> Result<T> {
+        if result.is_err() {
+            let mut connections = CONNECTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            connections.retain(|connection| connection.cache_key != self.cache_key);
+        }
+        result
+    }
+}
+
+impl Signer for SharedSigner {
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        self.forget_on_error(self.signer.sign_digest(digest, algorithm))
+    }
+
+    fn sign_message(&self, message: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        self.forget_on_error(self.signer.sign_message(message, algorithm))
+    }
+
+    fn digest_for(&self, requested: DigestAlgorithm) -> DigestAlgorithm {
+        self.signer.digest_for(requested)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        self.signer.signature_algorithm(digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        self.signer.public_key_info()
+    }
+
+    fn description(&self) -> String {
+        self.signer.description()
+    }
+}
+
+/// Buat signer untuk layanan yang menyimpan kunci (jika fiturnya dikompilasi)
+#[cfg_attr(not(any(feature = "aws-kms", feature = "azure-kms")), allow(unused_variables))]
+fn connect(key: &KmsKey, rsa_padding: RsaPadding) -> Result<Arc<dyn Signer>> {
+    match key.provider() {
+        #[cfg(feature = "aws-kms")]
+        KmsProvider::Aws => Ok(Arc::new(aws::AwsKmsSigner::connect(key, rsa_padding)?)),
+        #[cfg(feature = "gcp-kms")]
+        KmsProvider::Gcp => Ok(Arc::new(gcp::GcpKmsSigner::connect(key)?)),
+        #[cfg(feature = "azure-kms")]
+        KmsProvider::Azure => Ok(Arc::new(azure::AzureKeyVaultSigner::connect(key, rsa_padding)?)),
+        #[allow(unreachable_patterns)]
+        provider => Err(crate::error::missing_feature(provider.label(), provider.feature())),
+    }
+}
+
+/// Access token OAuth dari PDFSIGN_KMS_ACCESS_TOKEN, atau dari CLI layanan cloud
+///
+/// Parameter:
+///   - command: CLI yang mencetak token (gcloud / az)
+///   - args: argumen CLI tersebut
+#[cfg(any(feature = "gcp-kms", feature = "azure-kms"))]
+fn access_token(command: &str, args: &[&str]) -> Result<String> {
+    if let Ok(token) = std::env::var(ACCESS_TOKEN_ENV) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+    let output = std::process::Command::new(command)
+        .args(args)
+        .output()
+        .with_context(|| format!("cannot get an access token: set {} or install the `{}` CLI", ACCESS_TOKEN_ENV, command))?;
+    if !output.status.success() {
+        bail!(
+            "`{} {}` failed: {}",
+            command,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Ambil field string dari response JSON API
+#[cfg(any(feature = "aws-kms", feature = "gcp-kms", feature = "azure-kms"))]
+fn json_str<'a>(value: &'a serde_json::Value, field: &str) -> Result<&'a str> {
+    value[field]
+        .as_str()
+        .ok_or_else(|| anyhow!("unexpected API response: missing `{}`", field))
+}
@@ -1,3 +1,54 @@
 // Module untuk cryptography functions
-// ecc = Elliptic Curve Cryptography (menggunakan P-256)
-pub mod ecc;
\ No newline at end of file
+// ecc = Elliptic Curve Cryptography (ECDSA P-256/P-384/P-521 dan Ed25519)
+pub mod ecc;
+// keyfile = format file kunci (PKCS#8 PEM/DER, terenkripsi dengan passphrase)
+#[cfg(feature = "sign")]
+pub mod keyfile;
+// p12 = bundle PKCS#12 (.p12/.pfx) berisi kunci privat dan rantai sertifikat
+#[cfg(feature = "sign")]
+pub mod p12;
+// pkcs11 = kunci di smart card / USB token / HSM lewat modul PKCS#11
+// (token dan opsi selalu ada; modul PKCS#11 hanya dimuat dengan fitur pkcs11)
+#[cfg(feature = "sign")]
+pub mod pkcs11;
+// kms = kunci di AWS KMS, Google Cloud KMS, atau Azure Key Vault (fitur opsional)
+#[cfg(feature = "sign")]
+pub mod kms;
+// keystore = kunci di Windows certificate store (CNG) atau macOS Keychain (fitur opsional)
+#[cfg(feature = "sign")]
+pub mod keystore;
+// provider = provider kriptografi pengganti (misalnya modul FIPS) dan sumber bilangan acak
+pub mod provider;
+// ring = provider kriptografi berbasis ring (fitur opsional)
+#[cfg(feature = "ring")]
+pub mod ring;
+// digest = algoritma digest SHA-256/384/512 untuk signature (--digest)
+pub mod digest;
+// rsa = RSA PKCS#1 v1.5 / PSS
+pub mod rsa;
+// signer = trait Signer yang dipakai bersama oleh ECDSA dan RSA
+pub mod signer;
+// cms = struktur CMS/PKCS#7 SignedData untuk signature PDF
+pub mod cms;
+// http = request HTTP (TSA, OCSP, CRL) dengan batas waktu
+#[cfg(feature = "network")]
+pub mod http;
+// tsa = timestamp RFC 3161 dari Time Stamping Authority
+pub mod tsa;
+// revocation = data revocation (OCSP/CRL) untuk validasi jangka panjang dan status revocation saat verify
+#[cfg(any(feature = "network", feature = "verify"))]
+pub mod revocation;
+// policy = deny-list algoritma lemah (SHA-1, RSA < 2048, P-192) untuk sign dan verify
+pub mod policy;
+// trust = sumber trust anchor (AATL, EUTL, file root, --trust store:<dir>) dan validasi rantai sertifikat untuk verify
+#[cfg(feature = "verify")]
+pub mod trust;
+// session = sesi signing berbatas waktu (agent yang menyimpan kunci yang sudah dibuka)
+#[cfg(all(unix, feature = "server"))]
+pub mod session;
+// cert = sertifikat X.509 self-signed (generate-cert)
+#[cfg(feature = "sign")]
+pub mod cert;
+// key_info = detail kunci, sertifikat, dan bundle PKCS#12, serta pencocokan kunci dengan sertifikat (key-info)
+#[cfg(feature = "sign")]
+pub mod key_info;
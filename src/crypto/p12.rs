@@ -0,0 +1,76 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use p12_keystore::KeyStore; // Parsing container PKCS#12 (.p12/.pfx)
+use std::fs; // Untuk membaca file bundle
+
+use crate::crypto::keyfile; // Prompt password di terminal
+use crate::crypto::rsa::RsaPadding; // Padding jika kuncinya RSA
+use crate::crypto::signer::{signer_from_bytes, Signer}; // Backend penandatanganan
+
+/// Nama environment variable untuk password PKCS#12
+pub const P12_PASSWORD_ENV: &str = "PDFSIGN_P12_PASSWORD";
+
+/// Isi bundle PKCS#12: kunci privat dan rantai sertifikatnya
+pub struct Pkcs12Bundle {
+    pub signer: Box<dyn Signer>,     // Kunci privat (ECDSA, Ed25519, atau RSA)
+    pub certificates: Vec<Vec<u8>>,  // Sertifikat DER: penandatangan dulu, lalu rantainya sampai root
+}
+
+/// Baca kunci privat dan rantai sertifikat dari file PKCS#12 (.p12/.pfx)
+///
+/// Parameter:
+///   - path: path file PKCS#12
+///   - password: password bundle; jika None, dicoba password kosong lalu
+///     user diminta mengetik password di terminal
+///   - rsa_padding: skema padding jika kuncinya RSA
+pub fn load_pkcs12(path: &str, password: Option<&str>, rsa_padding: RsaPadding) -> Result<Pkcs12Bundle> {
+    let (key, certificates) = load_pkcs12_key(path, password)?;
+    let signer = signer_from_bytes(&key, rsa_padding).with_context(|| format!("cannot load private key from {}", path))?;
+    Ok(Pkcs12Bundle { signer, certificates })
+}
+
+/// Seperti `load_pkcs12`, tetapi mengembalikan kunci privat apa adanya
+/// (PKCS#8 DER, tidak terenkripsi) alih-alih signer, misalnya untuk sesi signing
+///
+/// Return: (kunci privat PKCS#8 DER, sertifikat DER: penandatangan dulu, lalu rantainya)
+pub fn load_pkcs12_key(path: &str, password: Option<&str>) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+    let data = fs::read(path).with_context(|| format!("cannot read PKCS#12 file {}", path))?;
+
+    let keystore = match password {
+        Some(password) => open_keystore(&data, password),
+        // Banyak bundle hasil ekspor tidak memakai password
+        None => match open_keystore(&data, "") {
+            Err(PasswordError::Wrong) => match keyfile::prompt_secret(&format!("Password for {}: ", path))? {
+                Some(password) => open_keystore(&data, &password),
+                None => bail!("PKCS#12 file {} is password protected; pass --p12-password or set {}", path, P12_PASSWORD_ENV),
+            },
+            other => other,
+        },
+    }
+    .map_err(|e| match e {
+        PasswordError::Wrong => anyhow!("cannot open PKCS#12 file {} (wrong password?)", path),
+        PasswordError::Invalid(e) => anyhow!("invalid PKCS#12 file {}: {}", path, e),
+    })?;
+
+    // Ambil pasangan kunci + sertifikat pertama; urutan rantai: leaf dulu, root terakhir
+    let Some((_, chain)) = keystore.private_key_chain() else {
+        bail!("PKCS#12 file {} has no private key with a matching certificate", path);
+    };
+    let certificates = chain.chain().iter().map(|cert| cert.as_der().to_vec()).collect();
+
+    Ok((chain.key().to_vec(), certificates))
+}
+
+/// Penyebab gagal membuka bundle
+enum PasswordError {
+    Wrong,           // MAC tidak cocok atau dekripsi gagal
+    Invalid(String), // Struktur PKCS#12 rusak atau tidak didukung
+}
+
+/// Parse bundle dengan password tertentu
+fn open_keystore(data: &[u8], password: &str) -> std::result::Result<KeyStore, PasswordError> {
+    KeyStore::from_pkcs12(data, password).map_err(|e| match e {
+        p12_keystore::error::Error::MacError(_) | p12_keystore::error::Error::UnpadError => PasswordError::Wrong,
+        other => PasswordError::Invalid(other.to_string()),
+    })
+}
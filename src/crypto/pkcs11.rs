@@ -0,0 +1,372 @@
+// Tanpa fitur pkcs11 hanya Pkcs11Token yang tersisa (untuk opsi signature);
+// memuat token mengembalikan error
+#![cfg_attr(not(feature = "pkcs11"), allow(unused_imports))]
+
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use ::rsa::pkcs8::EncodePublicKey; // Encoding SPKI kunci publik RSA
+use ::rsa::{BigUint, RsaPublicKey}; // Kunci publik RSA dari modulus dan eksponen
+#[cfg(feature = "pkcs11")]
+use cryptoki::context::{CInitializeArgs, CInitializeFlags, Pkcs11}; // Modul PKCS#11 (.so / .dll)
+#[cfg(feature = "pkcs11")]
+use cryptoki::mechanism::rsa::{PkcsMgfType, PkcsPssParams}; // Parameter RSASSA-PSS
+#[cfg(feature = "pkcs11")]
+use cryptoki::mechanism::{Mechanism, MechanismType}; // Mekanisme signing di token
+#[cfg(feature = "pkcs11")]
+use cryptoki::object::{Attribute, AttributeType, CertificateType, KeyType, ObjectClass, ObjectHandle}; // Object di token
+#[cfg(feature = "pkcs11")]
+use cryptoki::session::{Session, UserType}; // Sesi dan login ke token
+#[cfg(feature = "pkcs11")]
+use cryptoki::slot::Slot; // Slot reader
+#[cfg(feature = "pkcs11")]
+use cryptoki::types::AuthPin; // PIN token
+use std::sync::Mutex; // Sesi token dipakai bergantian oleh thread batch
+use x509_cert::der::asn1::{Any, ObjectIdentifier, OctetString}; // Tipe ASN.1
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+use x509_cert::Certificate; // Sertifikat di token
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::ecc::{self, Curve}; // Kurva ECDSA dan konversi signature ke DER
+use crate::crypto::keyfile::prompt_secret; // PIN dari terminal
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::signer::{ecdsa_with, Credentials, Signer}; // Trait backend penandatanganan
+
+// OID kunci publik EC
+#[cfg(feature = "pkcs11")]
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+/// Awalan DigestInfo SHA-256/384/512 (RFC 8017 9.2) untuk CKM_RSA_PKCS, yang
+/// hanya menambahkan padding tanpa membungkus digest
+#[cfg(feature = "pkcs11")]
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+];
+#[cfg(feature = "pkcs11")]
+const SHA384_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30,
+];
+#[cfg(feature = "pkcs11")]
+const SHA512_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40,
+];
+
+/// Environment variable untuk PIN token (dipakai CLI)
+pub const PIN_ENV: &str = "PDFSIGN_PKCS11_PIN";
+
+/// Token PKCS#11 (smart card, USB token, HSM) yang menyimpan kunci penandatangan
+#[derive(Clone, Debug)]
+pub struct Pkcs11Token {
+    pub module: String,            // Path modul PKCS#11, misalnya /usr/lib/opensc-pkcs11.so
+    pub slot: Option<u64>,         // ID slot; None = slot pertama yang berisi token
+    pub pin: Option<String>,       // PIN user; None = PIN pad token atau tanyakan di terminal
+    pub key_label: Option<String>, // Label kunci (CKA_LABEL) jika token berisi beberapa kunci
+}
+
+/// Jenis kunci di token
+#[cfg(feature = "pkcs11")]
+enum TokenKey {
+    /// ECDSA P-256/P-384/P-521; token mengembalikan signature r || s
+    Ecdsa(Curve),
+    /// RSA dengan padding PKCS#1 v1.5 atau PSS
+    Rsa(RsaPadding),
+}
+
+/// Signer yang menandatangani di dalam token; kunci privat tidak pernah keluar
+#[cfg(feature = "pkcs11")]
+pub struct Pkcs11Signer {
+    session: Mutex<Session>,         // Sesi yang sudah login (menyimpan context modul); satu operasi sekaligus
+    key: ObjectHandle,               // Handle kunci privat di token
+    kind: TokenKey,                  // Jenis kunci dan padding
+    spki: SubjectPublicKeyInfoOwned, // Kunci publik pasangannya
+    description: String,             // Untuk ditampilkan ke user
+}
+
+/// Buka token, login, lalu cari kunci penandatangan beserta sertifikatnya
+///
+/// Sertifikat penandatangan adalah sertifikat X.509 dengan CKA_ID yang sama
+/// dengan kunci; sertifikat lain di token yang menerbitkannya ikut menjadi rantai.
+///
+/// Parameter:
+///   - token: modul, slot, PIN, dan label kunci
+///   - rsa_padding: skema padding jika kuncinya RSA
+///   - interactive: boleh menanyakan PIN di terminal jika token.pin kosong
+///
+/// Return: signer dan sertifikat DER (penandatangan dulu, lalu rantainya)
+#[cfg(feature = "pkcs11")]
+pub fn load_pkcs11(token: &Pkcs11Token, rsa_padding: RsaPadding, interactive: bool) -> Result<Credentials> {
+    let context = Pkcs11::new(&token.module).with_context(|| format!("cannot load PKCS#11 module {}", token.module))?;
+    context
+        .initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK))
+        .with_context(|| format!("cannot initialize PKCS#11 module {}", token.module))?;
+
+    // Slot yang diminta, atau slot pertama yang berisi token
+    let slots = context.get_slots_with_token()?;
+    let slot = match token.slot {
+        Some(id) => *slots
+            .iter()
+            .find(|slot| slot.id() == id)
+            .ok_or_else(|| anyhow!("no token in PKCS#11 slot {} (slots with a token: {})", id, slot_list(&slots)))?,
+        None => *slots.first().ok_or_else(|| anyhow!("no token found by PKCS#11 module {}", token.module))?,
+    };
+    let token_info = context.get_token_info(slot)?;
+    let token_label = token_info.label().trim().to_string();
+
+    // Login: PIN dari opsi, PIN pad di reader, atau ditanyakan di terminal
+    let session = context.open_ro_session(slot)?;
+    let pin = match &token.pin {
+        Some(pin) => Some(pin.clone()),
+        None if token_info.protected_authentication_path() => None,
+        None if interactive => Some(
+            prompt_secret(&format!("PIN for token {}: ", token_label))?
+                .ok_or_else(|| anyhow!("token {} needs a PIN; pass --pin or set {}", token_label, PIN_ENV))?,
+        ),
+        None => bail!("token {} needs a PIN", token_label),
+    };
+    session
+        .login(UserType::User, pin.map(AuthPin::from).as_ref())
+        .with_context(|| format!("cannot log in to token {} (wrong PIN?)", token_label))?;
+
+    // Kunci privat untuk signing, dipilih dengan label jika ada beberapa
+    let mut template = vec![Attribute::Class(ObjectClass::PRIVATE_KEY), Attribute::Sign(true)];
+    if let Some(label) = &token.key_label {
+        template.push(Attribute::Label(label.as_bytes().to_vec()));
+    }
+    let keys = session.find_objects(&template)?;
+    let key = match keys.as_slice() {
+        [key] => *key,
+        [] => match &token.key_label {
+            Some(label) => bail!("no signing key labelled '{}' on token {}", label, token_label),
+            None => bail!("no signing key on token {}", token_label),
+        },
+        _ => {
+            let labels: Vec<String> = keys.iter().map(|key| object_label(&session, *key)).collect();
+            bail!(
+                "token {} has several signing keys; choose one with --pkcs11-key: {}",
+                token_label,
+                labels.join(", ")
+            );
+        }
+    };
+    let key_id = attribute_bytes(&session, key, AttributeType::Id)?;
+    let key_label = object_label(&session, key);
+
+    // Sertifikat penandatangan (CKA_ID sama) dan rantainya dari token
+    let mut token_certificates = Vec::new();
+    for handle in session.find_objects(&[
+        Attribute::Class(ObjectClass::CERTIFICATE),
+        Attribute::CertificateType(CertificateType::X_509),
+    ])? {
+        let der = attribute_bytes(&session, handle, AttributeType::Value)?;
+        let id = attribute_bytes(&session, handle, AttributeType::Id)?;
+        token_certificates.push((id, der));
+    }
+    let certificates = match token_certificates.iter().position(|(id, _)| !key_id.is_empty() && *id == key_id) {
+        Some(index) => {
+            let leaf = token_certificates.swap_remove(index).1;
+            let others: Vec<Vec<u8>> = token_certificates.into_iter().map(|(_, der)| der).collect();
+            build_chain(leaf, &others)?
+        }
+        None => Vec::new(),
+    };
+
+    // Kunci publik dari sertifikat, atau dari object kunci publik pasangannya
+    let spki = match certificates.first() {
+        Some(der) => Certificate::from_der(der)
+            .map_err(|e| anyhow!("invalid certificate on token: {}", e))?
+            .tbs_certificate
+            .subject_public_key_info,
+        None => public_key_info(&session, &key_id)
+            .with_context(|| format!("cannot find the certificate or public key for '{}' on token {}", key_label, token_label))?,
+    };
+
+    let key_type = session
+        .get_attributes(key, &[AttributeType::KeyType])?
+        .into_iter()
+        .find_map(|attribute| match attribute {
+            Attribute::KeyType(key_type) => Some(key_type),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("cannot read the type of key '{}'", key_label))?;
+    let (kind, algorithm) = if key_type == KeyType::EC {
+        let curve = match Curve::from_spki_algorithm(&spki.algorithm) {
+            Ok(Some(curve)) if curve != Curve::Ed25519 => curve,
+            _ => bail!("key '{}' on token {} is not on a supported curve; only P-256, P-384, P-521 and RSA are supported", key_label, token_label),
+        };
+        (TokenKey::Ecdsa(curve), curve.label().to_string())
+    } else if key_type == KeyType::RSA {
+        let padding = match rsa_padding {
+            RsaPadding::Pkcs1v15 => "PKCS#1 v1.5",
+            RsaPadding::Pss => "PSS",
+        };
+        (TokenKey::Rsa(rsa_padding), format!("RSA {}", padding))
+    } else {
+        bail!("key '{}' on token {} has an unsupported type ({})", key_label, token_label, key_type);
+    };
+
+    let signer = Pkcs11Signer {
+        session: Mutex::new(session),
+        key,
+        kind,
+        spki,
+        description: format!("{} on PKCS#11 token {}", algorithm, token_label),
+    };
+    Ok((Box::new(signer), certificates))
+}
+
+/// Tanpa fitur pkcs11 modul PKCS#11 tidak bisa dimuat
+#[cfg(not(feature = "pkcs11"))]
+pub fn load_pkcs11(_token: &Pkcs11Token, _rsa_padding: RsaPadding, _interactive: bool) -> Result<Credentials> {
+    Err(crate::error::missing_feature("PKCS#11", "pkcs11"))
+}
+
+#[cfg(feature = "pkcs11")]
+impl Signer for Pkcs11Signer {
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        // Sesi PKCS#11 tidak boleh dipakai dua thread sekaligus
+        let session = self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let signature = match self.kind {
+            TokenKey::Ecdsa(curve) => ecc::signature_to_der(curve, &session.sign(&Mechanism::Ecdsa, self.key, digest)?),
+            TokenKey::Rsa(RsaPadding::Pkcs1v15) => {
+                let prefix = match algorithm {
+                    DigestAlgorithm::Sha256 => SHA256_DIGEST_INFO,
+                    DigestAlgorithm::Sha384 => SHA384_DIGEST_INFO,
+                    DigestAlgorithm::Sha512 => SHA512_DIGEST_INFO,
+                };
+                let digest_info = [prefix.as_slice(), digest].concat();
+                session.sign(&Mechanism::RsaPkcs, self.key, &digest_info)?
+            }
+            TokenKey::Rsa(RsaPadding::Pss) => {
+                let (hash_alg, mgf) = match algorithm {
+                    DigestAlgorithm::Sha256 => (MechanismType::SHA256, PkcsMgfType::MGF1_SHA256),
+                    DigestAlgorithm::Sha384 => (MechanismType::SHA384, PkcsMgfType::MGF1_SHA384),
+                    DigestAlgorithm::Sha512 => (MechanismType::SHA512, PkcsMgfType::MGF1_SHA512),
+                };
+                let params = PkcsPssParams {
+                    hash_alg,
+                    mgf,
+                    s_len: (algorithm.output_len() as u64).into(),
+                };
+                session.sign(&Mechanism::RsaPkcsPss(params), self.key, digest)?
+            }
+        };
+        Ok(signature)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        match self.kind {
+            TokenKey::Ecdsa(_) => Ok(ecdsa_with(digest)),
+            TokenKey::Rsa(padding) => padding.signature_algorithm(digest),
+        }
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.spki.clone())
+    }
+
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+}
+
+/// Susun rantai: sertifikat penandatangan, lalu penerbitnya dari sertifikat lain di token
+#[cfg(feature = "pkcs11")]
+fn build_chain(leaf: Vec<u8>, others: &[Vec<u8>]) -> Result<Vec<Vec<u8>>> {
+    let parsed = others
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate on token: {}", e)))
+        .collect::<Result<Vec<Certificate>>>()?;
+    let mut current = Certificate::from_der(&leaf).map_err(|e| anyhow!("invalid certificate on token: {}", e))?;
+    let mut chain = vec![leaf];
+    while current.tbs_certificate.subject != current.tbs_certificate.issuer && chain.len() <= others.len() {
+        let Some(index) = parsed
+            .iter()
+            .position(|cert| cert.tbs_certificate.subject == current.tbs_certificate.issuer)
+        else {
+            break;
+        };
+        chain.push(others[index].clone());
+        current = parsed[index].clone();
+    }
+    Ok(chain)
+}
+
+/// SubjectPublicKeyInfo dari object kunci publik dengan CKA_ID yang sama
+#[cfg(feature = "pkcs11")]
+fn public_key_info(session: &Session, id: &[u8]) -> Result<SubjectPublicKeyInfoOwned> {
+    let handle = *session
+        .find_objects(&[Attribute::Class(ObjectClass::PUBLIC_KEY), Attribute::Id(id.to_vec())])?
+        .first()
+        .ok_or_else(|| anyhow!("no public key object"))?;
+    let der_err = |e: x509_cert::der::Error| anyhow!("invalid public key on token: {}", e);
+    let attributes = session.get_attributes(
+        handle,
+        &[AttributeType::KeyType, AttributeType::EcParams, AttributeType::EcPoint, AttributeType::Modulus, AttributeType::PublicExponent],
+    )?;
+    let mut key_type = None;
+    let (mut ec_params, mut ec_point, mut modulus, mut exponent) = (None, None, None, None);
+    for attribute in attributes {
+        match attribute {
+            Attribute::KeyType(value) => key_type = Some(value),
+            Attribute::EcParams(value) => ec_params = Some(value),
+            Attribute::EcPoint(value) => ec_point = Some(value),
+            Attribute::Modulus(value) => modulus = Some(value),
+            Attribute::PublicExponent(value) => exponent = Some(value),
+            _ => {}
+        }
+    }
+    match (key_type, ec_params, ec_point, modulus, exponent) {
+        (Some(KeyType::EC), Some(params), Some(point), _, _) => {
+            // CKA_EC_POINT berisi OCTET STRING DER; beberapa token memberi titik mentah
+            let point = match OctetString::from_der(&point) {
+                Ok(octets) => octets.as_bytes().to_vec(),
+                Err(_) => point,
+            };
+            Ok(SubjectPublicKeyInfoOwned {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid: ID_EC_PUBLIC_KEY,
+                    parameters: Some(Any::from_der(&params).map_err(der_err)?),
+                },
+                subject_public_key: x509_cert::der::asn1::BitString::from_bytes(&point).map_err(der_err)?,
+            })
+        }
+        (Some(KeyType::RSA), _, _, Some(modulus), Some(exponent)) => {
+            let key = RsaPublicKey::new(BigUint::from_bytes_be(&modulus), BigUint::from_bytes_be(&exponent))
+                .map_err(|e| anyhow!("invalid RSA public key on token: {}", e))?;
+            let der = key.to_public_key_der().map_err(|e| anyhow!("{}", e))?;
+            SubjectPublicKeyInfoOwned::from_der(der.as_bytes()).map_err(der_err)
+        }
+        _ => bail!("unsupported public key object"),
+    }
+}
+
+/// Nilai atribut bytes (CKA_ID, CKA_VALUE); kosong jika token tidak menyimpannya
+#[cfg(feature = "pkcs11")]
+fn attribute_bytes(session: &Session, object: ObjectHandle, attribute: AttributeType) -> Result<Vec<u8>> {
+    Ok(session
+        .get_attributes(object, &[attribute])?
+        .into_iter()
+        .find_map(|value| match value {
+            Attribute::Id(bytes) | Attribute::Value(bytes) | Attribute::Label(bytes) => Some(bytes),
+            _ => None,
+        })
+        .unwrap_or_default())
+}
+
+/// Label object untuk pesan ke user
+#[cfg(feature = "pkcs11")]
+fn object_label(session: &Session, object: ObjectHandle) -> String {
+    match attribute_bytes(session, object, AttributeType::Label) {
+        Ok(label) if !label.is_empty() => String::from_utf8_lossy(&label).into_owned(),
+        _ => format!("object {}", object),
+    }
+}
+
+/// Daftar ID slot untuk pesan error
+#[cfg(feature = "pkcs11")]
+fn slot_list(slots: &[Slot]) -> String {
+    if slots.is_empty() {
+        return "none".to_string();
+    }
+    slots.iter().map(|slot| slot.id().to_string()).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,225 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use ::rsa::pkcs1::RsaPssParams; // Parameter RSASSA-PSS (berisi algoritma hash)
+use std::fmt; // Menampilkan pelanggaran ke user
+use x509_cert::der::asn1::ObjectIdentifier; // OID algoritma
+use x509_cert::der::{Decode, Encode}; // Parsing DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+use x509_cert::Certificate; // Sertifikat X.509
+
+use crate::crypto::rsa::RsaPublic; // Ukuran kunci RSA
+
+// OID digest
+const ID_MD5: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.2.5");
+const ID_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+// OID algoritma signature yang memakai digest lemah
+const ID_MD5_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.4");
+const ID_SHA1_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.5");
+const ID_ECDSA_WITH_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.1");
+const ID_DSA_WITH_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10040.4.3");
+const ID_RSASSA_PSS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
+// OID kunci publik
+const ID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const ID_PRIME192V1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.1");
+
+/// Algoritma lemah yang bisa dimasukkan ke deny-list
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum WeakAlgorithm {
+    /// Digest MD5 (di signature, sertifikat, atau timestamp)
+    Md5,
+    /// Digest SHA-1 (di signature, sertifikat, atau timestamp)
+    Sha1,
+    /// Kunci RSA di bawah ukuran minimum (default 2048 bit)
+    SmallRsa,
+    /// Kunci ECDSA pada kurva P-192
+    P192,
+}
+
+impl WeakAlgorithm {
+    /// Nama algoritma untuk ditampilkan ke user
+    pub fn label(self) -> &'static str {
+        match self {
+            WeakAlgorithm::Md5 => "MD5",
+            WeakAlgorithm::Sha1 => "SHA-1",
+            WeakAlgorithm::SmallRsa => "small RSA key",
+            WeakAlgorithm::P192 => "P-192",
+        }
+    }
+
+    /// Nama seperti di CLI dan config (untuk petunjuk --allow-weak)
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            WeakAlgorithm::Md5 => "md5",
+            WeakAlgorithm::Sha1 => "sha1",
+            WeakAlgorithm::SmallRsa => "small-rsa",
+            WeakAlgorithm::P192 => "p192",
+        }
+    }
+}
+
+/// Apa yang dilakukan verify jika menemukan algoritma dari deny-list
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum WeakAction {
+    /// Signature dianggap tidak valid
+    #[default]
+    Fail,
+    /// Signature tetap valid, tetapi diberi peringatan
+    Warn,
+}
+
+/// Satu pemakaian algoritma lemah yang ditolak kebijakan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub algorithm: WeakAlgorithm, // Entry deny-list yang dilanggar
+    pub description: String,      // Apa yang lemah dan di mana, misalnya "SHA-1 signature on certificate CN=..."
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+/// Kebijakan algoritma lemah untuk sign dan verify
+///
+/// Default: MD5, SHA-1, RSA di bawah 2048 bit, dan P-192 ditolak; signing
+/// dengan algoritma tersebut gagal, dan verify menganggap signature-nya tidak valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlgorithmPolicy {
+    pub deny: Vec<WeakAlgorithm>, // Deny-list
+    pub min_rsa_bits: usize,      // Ukuran kunci RSA minimum untuk SmallRsa
+    pub verify_action: WeakAction, // Hasil verify jika ada pelanggaran
+}
+
+impl Default for AlgorithmPolicy {
+    fn default() -> AlgorithmPolicy {
+        AlgorithmPolicy {
+            deny: vec![WeakAlgorithm::Md5, WeakAlgorithm::Sha1, WeakAlgorithm::SmallRsa, WeakAlgorithm::P192],
+            min_rsa_bits: 2048,
+            verify_action: WeakAction::Fail,
+        }
+    }
+}
+
+impl AlgorithmPolicy {
+    /// Hapus algoritma dari deny-list
+    pub fn allow(mut self, algorithms: &[WeakAlgorithm]) -> AlgorithmPolicy {
+        self.deny.retain(|algorithm| !algorithms.contains(algorithm));
+        self
+    }
+
+    /// Periksa algoritma digest (digestAlgorithm CMS, hash timestamp)
+    pub fn check_digest(&self, oid: &ObjectIdentifier, usage: &str) -> Option<Violation> {
+        let algorithm = weak_digest(oid)?;
+        self.violation(algorithm, format!("{} {}", algorithm.label(), usage))
+    }
+
+    /// Periksa algoritma signature (CMS SignerInfo atau sertifikat)
+    pub fn check_signature_algorithm(&self, algorithm: &AlgorithmIdentifierOwned, usage: &str) -> Option<Violation> {
+        let weak = if algorithm.oid == ID_MD5_WITH_RSA {
+            WeakAlgorithm::Md5
+        } else if [ID_SHA1_WITH_RSA, ID_ECDSA_WITH_SHA1, ID_DSA_WITH_SHA1].contains(&algorithm.oid) {
+            WeakAlgorithm::Sha1
+        } else if algorithm.oid == ID_RSASSA_PSS {
+            // Tanpa parameter, hash PSS default-nya SHA-1
+            let params = algorithm.parameters.as_ref().and_then(|any| any.to_der().ok());
+            match params.as_deref().map(RsaPssParams::try_from) {
+                Some(Ok(params)) => weak_digest(&params.hash.oid)?,
+                _ => WeakAlgorithm::Sha1,
+            }
+        } else {
+            return None;
+        };
+        self.violation(weak, format!("{} {}", weak.label(), usage))
+    }
+
+    /// Periksa kunci publik (ukuran RSA dan kurva EC)
+    pub fn check_key(&self, spki: &SubjectPublicKeyInfoOwned, usage: &str) -> Option<Violation> {
+        if spki.algorithm.oid == ID_EC_PUBLIC_KEY {
+            let curve = spki.algorithm.parameters.as_ref().and_then(|any| any.decode_as::<ObjectIdentifier>().ok());
+            if curve == Some(ID_PRIME192V1) {
+                return self.violation(WeakAlgorithm::P192, format!("P-192 {}", usage));
+            }
+        } else if spki.algorithm.oid == ID_RSA_ENCRYPTION || spki.algorithm.oid == ID_RSASSA_PSS {
+            let bits = RsaPublic::from_spki(spki).ok()?.bits();
+            return self.check_rsa_bits(bits, usage);
+        }
+        None
+    }
+
+    /// Periksa ukuran kunci RSA (misalnya dari file --public-key)
+    pub fn check_rsa_bits(&self, bits: usize, usage: &str) -> Option<Violation> {
+        if bits >= self.min_rsa_bits {
+            return None;
+        }
+        self.violation(
+            WeakAlgorithm::SmallRsa,
+            format!("RSA-{} {} (minimum {} bits)", bits, usage, self.min_rsa_bits),
+        )
+    }
+
+    /// Periksa kunci dan algoritma signature setiap sertifikat dalam rantai
+    ///
+    /// Signature sertifikat self-signed (root) tidak diperiksa: kepercayaan
+    /// pada root tidak bergantung pada signature atas dirinya sendiri
+    pub fn check_certificates(&self, certificates: &[Vec<u8>]) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+        for der in certificates {
+            let cert = Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+            let subject = cert.tbs_certificate.subject.to_string();
+            let key_usage = format!("key in certificate {}", subject);
+            violations.extend(self.check_key(&cert.tbs_certificate.subject_public_key_info, &key_usage));
+            if cert.tbs_certificate.subject != cert.tbs_certificate.issuer {
+                let signature_usage = format!("signature on certificate {}", subject);
+                violations.extend(self.check_signature_algorithm(&cert.signature_algorithm, &signature_usage));
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Tolak penandatanganan jika kunci penandatangan atau rantai sertifikatnya
+    /// memakai algoritma dari deny-list
+    ///
+    /// Parameter:
+    ///   - key: SubjectPublicKeyInfo kunci penandatangan
+    ///   - certificates: sertifikat DER (penandatangan dulu, lalu rantainya)
+    pub fn check_signing(&self, key: &SubjectPublicKeyInfoOwned, certificates: &[Vec<u8>]) -> Result<()> {
+        // Sertifikat penandatangan berisi kunci yang sama; kunci diperiksa langsung
+        // hanya jika tidak ada sertifikat
+        let violations = if certificates.is_empty() {
+            self.check_key(key, "signing key").into_iter().collect()
+        } else {
+            self.check_certificates(certificates)?
+        };
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut names: Vec<&str> = violations.iter().map(|v| v.algorithm.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        bail!(
+            "refusing to sign with weak algorithms: {} (allow with --allow-weak {})",
+            violations.iter().map(Violation::to_string).collect::<Vec<_>>().join("; "),
+            names.join(",")
+        )
+    }
+
+    /// Pelanggaran jika algoritma ada di deny-list
+    fn violation(&self, algorithm: WeakAlgorithm, description: String) -> Option<Violation> {
+        self.deny.contains(&algorithm).then_some(Violation { algorithm, description })
+    }
+}
+
+/// Entry deny-list untuk OID digest, jika digest tersebut lemah
+fn weak_digest(oid: &ObjectIdentifier) -> Option<WeakAlgorithm> {
+    if *oid == ID_MD5 {
+        Some(WeakAlgorithm::Md5)
+    } else if *oid == ID_SHA1 {
+        Some(WeakAlgorithm::Sha1)
+    } else {
+        None
+    }
+}
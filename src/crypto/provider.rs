@@ -0,0 +1,193 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+#[cfg(feature = "sign")]
+use rand_core::{CryptoRng, RngCore}; // Adapter sumber acak untuk crate RustCrypto
+#[cfg(feature = "sign")]
+use sha2::{Digest, Sha256}; // Ekspansi seed SeededRandom
+#[cfg(feature = "sign")]
+use std::sync::Mutex; // Counter SeededRandom
+use std::sync::{Arc, RwLock}; // Provider dan sumber acak bersama untuk seluruh proses
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest
+use crate::crypto::rsa::RsaPadding; // Padding signature RSA
+use crate::crypto::signer::Signer; // Trait backend penandatanganan
+
+/// Provider kriptografi yang sedang terpasang; None = implementasi bawaan (RustCrypto)
+static PROVIDER: RwLock<Option<Arc<dyn CryptoProvider>>> = RwLock::new(None);
+
+/// Backend kriptografi yang bisa dipilih dari command line (--crypto-backend)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Backend {
+    /// Crate RustCrypto bawaan (sha2, p256/p384/p521, ed25519-dalek, rsa)
+    #[default]
+    Rustcrypto,
+    /// ring: digest SHA-2 dan signing ECDSA P-256/P-384, Ed25519, RSA (fitur ring)
+    Ring,
+}
+
+/// Hash inkremental dari provider (lihat `CryptoProvider::hasher`)
+pub trait DigestContext: Send {
+    /// Tambahkan data ke hash
+    fn update(&mut self, data: &[u8]);
+
+    /// Selesaikan hash dan kembalikan digest-nya
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+/// Pengganti primitive kriptografi untuk seluruh proses, misalnya modul yang
+/// tervalidasi FIPS 140-3 (aws-lc-rs dalam mode FIPS) atau `RingProvider`
+///
+/// Setiap method boleh mengembalikan None agar implementasi bawaan yang dipakai;
+/// provider yang wajib menangani semuanya (FIPS) sebaiknya mengembalikan error
+/// untuk algoritma atau kunci yang tidak didukungnya. Yang diganti: digest
+/// dokumen dan CMS (`DigestAlgorithm::digest`/`hasher`) dan signer dari file
+/// kunci (`signer_from_bytes`: --key, PKCS#12, sesi). Signer PKCS#11, KMS, dan
+/// keystore sudah memakai modul kriptografi token/layanannya sendiri;
+/// verifikasi signature tetap memakai implementasi bawaan. Bilangan acak
+/// diatur terpisah dengan `set_random_source`.
+pub trait CryptoProvider: Send + Sync {
+    /// Nama provider untuk ditampilkan ke user, misalnya "ring"
+    fn name(&self) -> &str;
+
+    /// Hash inkremental baru untuk `algorithm`; None = SHA-2 bawaan
+    fn hasher(&self, _algorithm: DigestAlgorithm) -> Option<Box<dyn DigestContext>> {
+        None
+    }
+
+    /// Signer dari kunci privat yang tidak terenkripsi (raw, PKCS#8, SEC1,
+    /// PKCS#1; DER atau PEM); None = kunci dibaca oleh implementasi bawaan
+    fn signer_from_bytes(&self, _bytes: &[u8], _rsa_padding: RsaPadding) -> Option<Result<Box<dyn Signer>>> {
+        None
+    }
+}
+
+/// Pasang provider kriptografi untuk semua operasi berikutnya dalam proses ini
+pub fn set_provider(provider: Arc<dyn CryptoProvider>) {
+    *PROVIDER.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(provider);
+}
+
+/// Kembali ke implementasi bawaan
+pub fn reset_provider() {
+    *PROVIDER.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Provider yang sedang terpasang; None jika memakai implementasi bawaan
+pub fn provider() -> Option<Arc<dyn CryptoProvider>> {
+    PROVIDER.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Sumber acak yang sedang terpasang; None = OS (getrandom)
+#[cfg(feature = "sign")]
+static RANDOM: RwLock<Option<Arc<dyn RandomSource>>> = RwLock::new(None);
+
+/// Sumber bilangan acak untuk kunci baru, salt/IV, nonce TSA, serial
+/// sertifikat, padding PSS, dan /ID dokumen
+///
+/// Seperti `RngCore::fill_bytes`, sumber yang gagal harus panic: melanjutkan
+/// dengan bytes yang tidak acak tidak pernah aman.
+#[cfg(feature = "sign")]
+pub trait RandomSource: Send + Sync {
+    /// Isi `dest` dengan bytes acak
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+/// Generator acak OS (getrandom), default
+#[cfg(feature = "sign")]
+pub struct SystemRandom;
+
+#[cfg(feature = "sign")]
+impl RandomSource for SystemRandom {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        rand_core::OsRng.fill_bytes(dest);
+    }
+}
+
+/// Sumber acak deterministik dari seed: SHA-256(seed || counter)
+///
+/// Hanya untuk test (output yang bisa diulang); jangan dipakai untuk kunci atau
+/// dokumen sungguhan.
+#[cfg(feature = "sign")]
+pub struct SeededRandom {
+    seed: Vec<u8>,       // Seed dari test
+    counter: Mutex<u64>, // Blok berikutnya
+}
+
+#[cfg(feature = "sign")]
+impl SeededRandom {
+    /// Sumber acak baru yang selalu menghasilkan urutan yang sama untuk `seed`
+    pub fn new(seed: &[u8]) -> SeededRandom {
+        SeededRandom { seed: seed.to_vec(), counter: Mutex::new(0) }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl RandomSource for SeededRandom {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        let mut counter = self.counter.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for chunk in dest.chunks_mut(32) {
+            let block = Sha256::new().chain_update(&self.seed).chain_update(counter.to_be_bytes()).finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            *counter += 1;
+        }
+    }
+}
+
+/// Pasang sumber acak untuk semua operasi berikutnya dalam proses ini
+#[cfg(feature = "sign")]
+pub fn set_random_source(source: Arc<dyn RandomSource>) {
+    *RANDOM.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(source);
+}
+
+/// Kembali ke generator acak OS
+#[cfg(feature = "sign")]
+pub fn reset_random_source() {
+    *RANDOM.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Isi `dest` dengan bytes dari sumber acak yang sedang terpasang
+#[cfg(feature = "sign")]
+pub fn fill_random(dest: &mut [u8]) {
+    match RANDOM.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref() {
+        Some(source) => source.fill_bytes(dest),
+        None => SystemRandom.fill_bytes(dest),
+    }
+}
+
+/// Sumber acak yang sedang terpasang sebagai `RngCore` untuk crate RustCrypto
+/// (pembuatan kunci, padding PSS)
+#[cfg(feature = "sign")]
+pub fn rng() -> SourceRng {
+    SourceRng
+}
+
+/// Adapter `RngCore + CryptoRng` di atas `fill_random`
+#[cfg(feature = "sign")]
+pub struct SourceRng;
+
+#[cfg(feature = "sign")]
+impl RngCore for SourceRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        fill_random(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        fill_random(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_random(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        fill_random(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sign")]
+impl CryptoRng for SourceRng {}
@@ -0,0 +1,508 @@
+// Pengambilan data revocation lewat HTTP hanya ada dengan fitur network;
+// pemeriksaan status dari respons yang tertanam di /DSS juga untuk verify
+
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use sha1::{Digest, Sha1}; // SHA-1 untuk CertID OCSP (yang didukung semua responder)
+#[cfg(feature = "verify")]
+use std::time::SystemTime; // Waktu pencabutan sertifikat
+use x509_cert::crl::CertificateList; // CRL (RFC 5280)
+use x509_cert::der::asn1::ObjectIdentifier; // OID algoritma dan tipe respons
+#[cfg(feature = "network")]
+use x509_cert::der::asn1::{Any, OctetString}; // Tipe ASN.1 untuk request OCSP
+#[cfg(feature = "network")]
+use x509_cert::der::oid::AssociatedOid; // OID extension
+#[cfg(feature = "network")]
+use x509_cert::der::DecodeOwned; // Decoding extension
+use x509_cert::der::{Decode, Encode}; // Encoding/decoding DER
+#[cfg(feature = "network")]
+use x509_cert::ext::pkix::name::{DistributionPointName, GeneralName}; // Lokasi OCSP/CRL
+#[cfg(feature = "verify")]
+use x509_cert::ext::pkix::ExtendedKeyUsage; // EKU responder OCSP yang didelegasikan
+#[cfg(feature = "network")]
+use x509_cert::ext::pkix::{AuthorityInfoAccessSyntax, CrlDistributionPoints}; // Extension lokasi revocation
+#[cfg(feature = "network")]
+use x509_cert::spki::AlgorithmIdentifierOwned; // Identifier algoritma hash
+use x509_cert::Certificate; // Sertifikat X.509
+use x509_ocsp::{BasicOcspResponse, CertStatus, OcspResponse, OcspResponseStatus, SingleResponse}; // Respons OCSP (RFC 6960)
+#[cfg(feature = "network")]
+use x509_ocsp::{CertId, OcspRequest, Request, TbsRequest}; // Request OCSP
+
+#[cfg(feature = "network")]
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::digest::DigestAlgorithm; // Hash CertID selain SHA-1
+#[cfg(feature = "network")]
+use crate::crypto::http; // Request HTTP ke responder OCSP / server CRL
+#[cfg(feature = "network")]
+use crate::pdf::pades::{LtvPolicy, RevocationPreference}; // Sumber dan toleransi data revocation
+#[cfg(feature = "verify")]
+use crate::crypto::trust; // Verifikasi signature respons OCSP dan CRL
+
+// OID yang dipakai untuk OCSP
+const ID_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+#[cfg(feature = "network")]
+const ID_AD_OCSP: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.48.1");
+const ID_PKIX_OCSP_BASIC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.48.1.1");
+#[cfg(feature = "verify")]
+const ID_KP_OCSP_SIGNING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.9");
+
+/// Data revocation untuk sekumpulan sertifikat (disimpan di DSS untuk PAdES B-LT)
+#[derive(Clone, Default)]
+pub struct RevocationData {
+    pub ocsp_responses: Vec<Vec<u8>>, // OCSPResponse dalam DER
+    pub crls: Vec<Vec<u8>>,           // CertificateList dalam DER
+}
+
+/// Ambil data revocation untuk setiap sertifikat di rantai
+///
+/// Sertifikat self-signed (root) dilewati. Untuk sertifikat lain OCSP dicoba lebih
+/// dulu (URL dari Authority Information Access, issuer harus ada di `certificates`),
+/// lalu CRL dari CRL Distribution Points. Sertifikat yang sudah dicabut ditolak.
+/// Signature respons OCSP/CRL tidak diperiksa di sini; itu tugas validator.
+///
+/// Parameter:
+///   - certificates: sertifikat DER (urutan bebas; issuer dicari berdasarkan nama)
+///   - cancel: token pembatalan; batas waktunya membatasi request HTTP
+#[cfg(feature = "network")]
+pub fn fetch_revocation_data(certificates: &[Vec<u8>], cancel: &CancellationToken) -> Result<RevocationData> {
+    fetch_revocation_data_with(certificates, &LtvPolicy::default(), cancel)
+}
+
+/// Seperti `fetch_revocation_data`, dengan urutan sumber dan toleransi
+/// kegagalan dari `policy`
+///
+/// Dengan `RevocationPreference::Both` OCSP dan CRL sama-sama disimpan jika
+/// tersedia. Dengan `best_effort`, sertifikat yang data revocation-nya tidak
+/// bisa diambil hanya diberi peringatan; sertifikat yang sudah dicabut tetap
+/// ditolak.
+#[cfg(feature = "network")]
+pub fn fetch_revocation_data_with(certificates: &[Vec<u8>], policy: &LtvPolicy, cancel: &CancellationToken) -> Result<RevocationData> {
+    let parsed = certificates
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| anyhow!("invalid certificate: {}", e)))
+        .collect::<Result<Vec<Certificate>>>()?;
+
+    let sources: &[Source] = match policy.revocation {
+        RevocationPreference::Ocsp | RevocationPreference::Both => &[Source::Ocsp, Source::Crl],
+        RevocationPreference::Crl => &[Source::Crl, Source::Ocsp],
+    };
+    let mut data = RevocationData::default();
+    for cert in &parsed {
+        let tbs = &cert.tbs_certificate;
+        if tbs.subject == tbs.issuer {
+            continue;
+        }
+        let subject = tbs.subject.to_string();
+        let issuer = parsed.iter().find(|candidate| candidate.tbs_certificate.subject == tbs.issuer);
+
+        let mut failure = None;
+        let mut found = false;
+        for source in sources {
+            if found && policy.revocation != RevocationPreference::Both {
+                break;
+            }
+            let fetched = match source {
+                Source::Ocsp => fetch_ocsp_for(cert, issuer, &subject, &mut data, cancel)?,
+                Source::Crl => fetch_crl_for(cert, &subject, &mut data, cancel)?,
+            };
+            match fetched {
+                Ok(added) => found |= added,
+                Err(e) => failure = Some(e),
+            }
+        }
+        if found {
+            continue;
+        }
+
+        let error = match failure {
+            Some(e) => e.context(format!("cannot get revocation data for {}", subject)),
+            None => anyhow!("certificate {} has no OCSP responder or CRL distribution point, so no revocation data can be embedded", subject),
+        };
+        if !policy.best_effort {
+            return Err(error);
+        }
+        crate::warning!("{:#}; continuing without it (best effort)", error);
+    }
+
+    Ok(data)
+}
+
+/// Sumber data revocation
+#[cfg(feature = "network")]
+enum Source {
+    Ocsp,
+    Crl,
+}
+
+/// Respons OCSP untuk satu sertifikat, ditambahkan ke `data`
+///
+/// Return: error untuk sertifikat yang sudah dicabut; di dalamnya true jika
+/// respons ditambahkan, false jika tidak ada responder (atau issuer tidak
+/// diketahui), atau error jika responder gagal
+#[cfg(feature = "network")]
+fn fetch_ocsp_for(
+    cert: &Certificate,
+    issuer: Option<&Certificate>,
+    subject: &str,
+    data: &mut RevocationData,
+    cancel: &CancellationToken,
+) -> Result<Result<bool>> {
+    let (Some(issuer), Some(url)) = (issuer, ocsp_url(cert)?) else {
+        return Ok(Ok(false));
+    };
+    match fetch_ocsp(cert, issuer, &url, cancel) {
+        Ok((response, CertStatus::Good(_))) => {
+            data.ocsp_responses.push(response);
+            Ok(Ok(true))
+        }
+        Ok((_, CertStatus::Revoked(_))) => bail!("certificate {} has been revoked (OCSP)", subject),
+        Ok((_, CertStatus::Unknown(_))) => Ok(Err(anyhow!("OCSP responder {} does not know certificate {}", url, subject))),
+        Err(e) => Ok(Err(e)),
+    }
+}
+
+/// CRL dari distribution point pertama yang berhasil, ditambahkan ke `data`
+///
+/// Return: seperti `fetch_ocsp_for`
+#[cfg(feature = "network")]
+fn fetch_crl_for(cert: &Certificate, subject: &str, data: &mut RevocationData, cancel: &CancellationToken) -> Result<Result<bool>> {
+    let mut failure = None;
+    for url in crl_urls(cert)? {
+        match fetch_crl(&url, cancel) {
+            Ok((der, crl)) => {
+                let revoked = crl
+                    .tbs_cert_list
+                    .revoked_certificates
+                    .iter()
+                    .flatten()
+                    .any(|entry| entry.serial_number == cert.tbs_certificate.serial_number);
+                if revoked {
+                    bail!("certificate {} has been revoked (CRL {})", subject, url);
+                }
+                data.crls.push(der);
+                return Ok(Ok(true));
+            }
+            Err(e) => failure = Some(e),
+        }
+    }
+    Ok(failure.map_or(Ok(false), Err))
+}
+
+/// Minta status sertifikat ke responder OCSP
+/// Return: OCSPResponse (DER) dan status sertifikat di dalamnya
+#[cfg(feature = "network")]
+fn fetch_ocsp(cert: &Certificate, issuer: &Certificate, url: &str, cancel: &CancellationToken) -> Result<(Vec<u8>, CertStatus)> {
+    let body = request_ocsp(cert, issuer, url, cancel)?;
+    let basic = basic_response(&body).map_err(|e| e.context(format!("OCSP responder {}", url)))?;
+    let single = single_response(&basic, cert, issuer)
+        .ok_or_else(|| anyhow!("OCSP response from {} does not cover the requested certificate", url))?;
+    let status = single.cert_status;
+    Ok((body, status))
+}
+
+/// Kirim request OCSP untuk satu sertifikat
+/// Return: OCSPResponse (DER) apa adanya
+#[cfg(feature = "network")]
+fn request_ocsp(cert: &Certificate, issuer: &Certificate, url: &str, cancel: &CancellationToken) -> Result<Vec<u8>> {
+    let der_err = |e: x509_cert::der::Error| anyhow!("OCSP encoding failed: {}", e);
+
+    // CertID: hash nama dan kunci publik issuer, plus serial sertifikat
+    let issuer_name = issuer.tbs_certificate.subject.to_der().map_err(der_err)?;
+    let issuer_key = issuer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+    let cert_id = CertId {
+        hash_algorithm: AlgorithmIdentifierOwned { oid: ID_SHA1, parameters: Some(Any::null()) },
+        issuer_name_hash: OctetString::new(Sha1::digest(&issuer_name).to_vec()).map_err(der_err)?,
+        issuer_key_hash: OctetString::new(Sha1::digest(issuer_key).to_vec()).map_err(der_err)?,
+        serial_number: cert.tbs_certificate.serial_number.clone(),
+    };
+    let request = OcspRequest {
+        tbs_request: TbsRequest {
+            version: Default::default(),
+            requestor_name: None,
+            request_list: vec![Request { req_cert: cert_id, single_request_extensions: None }],
+            request_extensions: None,
+        },
+        optional_signature: None,
+    }
+    .to_der()
+    .map_err(der_err)?;
+
+    http::post(url, "application/ocsp-request", "application/ocsp-response", &request, cancel)
+}
+
+/// Periksa status respons OCSP dan ambil BasicOCSPResponse-nya
+fn basic_response(der: &[u8]) -> Result<BasicOcspResponse> {
+    let response = OcspResponse::from_der(der).map_err(|e| anyhow!("invalid OCSP response: {}", e))?;
+    if response.response_status != OcspResponseStatus::Successful {
+        bail!("the OCSP responder answered {:?}", response.response_status);
+    }
+    let bytes = response.response_bytes.ok_or_else(|| anyhow!("the OCSP response is empty"))?;
+    if bytes.response_type != ID_PKIX_OCSP_BASIC {
+        bail!("the OCSP response is not a basic response");
+    }
+    BasicOcspResponse::from_der(bytes.response.as_bytes()).map_err(|e| anyhow!("invalid OCSP response: {}", e))
+}
+
+/// Jawaban di respons OCSP untuk `cert` (CertID dengan hash SHA-1 atau SHA-2)
+fn single_response<'a>(basic: &'a BasicOcspResponse, cert: &Certificate, issuer: &Certificate) -> Option<&'a SingleResponse> {
+    let issuer_name = issuer.tbs_certificate.subject.to_der().ok()?;
+    let issuer_key = issuer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+    basic.tbs_response_data.responses.iter().find(|single| {
+        let id = &single.cert_id;
+        let hash = |data: &[u8]| match id.hash_algorithm.oid {
+            ID_SHA1 => Some(Sha1::digest(data).to_vec()),
+            oid => DigestAlgorithm::from_oid(&oid).map(|digest| digest.digest(data)),
+        };
+        id.serial_number == cert.tbs_certificate.serial_number
+            && hash(&issuer_name).is_some_and(|digest| digest == id.issuer_name_hash.as_bytes())
+            && hash(issuer_key).is_some_and(|digest| digest == id.issuer_key_hash.as_bytes())
+    })
+}
+
+/// Unduh CRL (DER) dari distribution point
+#[cfg(feature = "network")]
+fn fetch_crl(url: &str, cancel: &CancellationToken) -> Result<(Vec<u8>, CertificateList)> {
+    let der = http::get(url, cancel)?;
+    let crl = CertificateList::from_der(&der).map_err(|e| anyhow!("invalid CRL from {}: {}", url, e))?;
+    Ok((der, crl))
+}
+
+/// URL responder OCSP dari extension Authority Information Access (hanya http/https)
+#[cfg(feature = "network")]
+fn ocsp_url(cert: &Certificate) -> Result<Option<String>> {
+    let Some(aia) = extension::<AuthorityInfoAccessSyntax>(cert)? else {
+        return Ok(None);
+    };
+    Ok(aia
+        .0
+        .iter()
+        .filter(|access| access.access_method == ID_AD_OCSP)
+        .filter_map(|access| http_uri(&access.access_location))
+        .next())
+}
+
+/// URL CRL dari extension CRL Distribution Points (hanya http/https)
+#[cfg(feature = "network")]
+fn crl_urls(cert: &Certificate) -> Result<Vec<String>> {
+    let Some(points) = extension::<CrlDistributionPoints>(cert)? else {
+        return Ok(Vec::new());
+    };
+    Ok(points
+        .0
+        .iter()
+        .filter_map(|point| match &point.distribution_point {
+            Some(DistributionPointName::FullName(names)) => Some(names),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(http_uri)
+        .collect())
+}
+
+/// Ambil URI http/https dari GeneralName (URL LDAP dan lainnya dilewati)
+#[cfg(feature = "network")]
+fn http_uri(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::UniformResourceIdentifier(uri)
+            if uri.as_str().starts_with("http://") || uri.as_str().starts_with("https://") =>
+        {
+            Some(uri.as_str().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Decode extension sertifikat berdasarkan OID-nya (None jika tidak ada)
+#[cfg(feature = "network")]
+fn extension<T: AssociatedOid + DecodeOwned>(cert: &Certificate) -> Result<Option<T>> {
+    let Some(extensions) = &cert.tbs_certificate.extensions else {
+        return Ok(None);
+    };
+    match extensions.iter().find(|ext| ext.extn_id == T::OID) {
+        Some(ext) => T::from_der(ext.extn_value.as_bytes())
+            .map(Some)
+            .map_err(|e| anyhow!("invalid certificate extension {}: {}", T::OID, e)),
+        None => Ok(None),
+    }
+}
+
+/// Cara memeriksa status revocation saat verify (--check-revocation)
+#[cfg(feature = "verify")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum RevocationMode {
+    /// Hanya respons OCSP dan CRL yang tertanam di /DSS dokumen (tanpa jaringan)
+    Offline,
+    /// Tanya responder OCSP dan unduh CRL dari URL di sertifikat (AIA/CDP);
+    /// /DSS dipakai jika keduanya tidak memberi jawaban
+    Online,
+}
+
+/// Asal jawaban status revocation
+#[cfg(feature = "verify")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevocationSource {
+    /// Respons OCSP: dari URL responder, atau None jika tertanam di /DSS
+    Ocsp(Option<String>),
+    /// CRL: dari URL distribution point, atau None jika tertanam di /DSS
+    Crl(Option<String>),
+}
+
+#[cfg(feature = "verify")]
+impl RevocationSource {
+    /// Label sumber di laporan, misalnya "OCSP, DSS" atau "CRL http://..."
+    pub fn label(&self) -> String {
+        match self {
+            RevocationSource::Ocsp(None) => "OCSP, DSS".to_string(),
+            RevocationSource::Ocsp(Some(url)) => format!("OCSP {}", url),
+            RevocationSource::Crl(None) => "CRL, DSS".to_string(),
+            RevocationSource::Crl(Some(url)) => format!("CRL {}", url),
+        }
+    }
+}
+
+/// Status revocation satu sertifikat
+#[cfg(feature = "verify")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevocationStatus {
+    /// Sertifikat tidak dicabut menurut `source`
+    Good(RevocationSource),
+    /// Sertifikat dicabut sejak `revoked_at`
+    Revoked { source: RevocationSource, revoked_at: SystemTime },
+    /// Tidak ada respons OCSP atau CRL yang bisa dipakai (detail kegagalan terakhir, jika ada)
+    Unknown(Option<String>),
+}
+
+/// Periksa status revocation satu sertifikat
+///
+/// Dengan `RevocationMode::Online` responder OCSP ditanya lebih dulu, lalu CRL
+/// dari setiap distribution point; jika tidak ada yang menjawab, dipakai respons
+/// dan CRL yang tertanam di /DSS seperti mode offline. Signature setiap respons
+/// OCSP (oleh issuer atau responder yang didelegasikan issuer) dan CRL (oleh
+/// issuer) diperiksa; respons yang tidak valid diabaikan.
+///
+/// Parameter:
+///   - cert: sertifikat yang diperiksa
+///   - issuer: issuer `cert` (None = status tidak bisa diperiksa)
+///   - embedded: respons OCSP dan CRL dari /DSS
+///   - mode: offline (hanya /DSS) atau online
+#[cfg(feature = "verify")]
+pub fn check_status(cert: &Certificate, issuer: Option<&Certificate>, embedded: &RevocationData, mode: RevocationMode) -> RevocationStatus {
+    let Some(issuer) = issuer else {
+        return RevocationStatus::Unknown(Some("the issuer certificate is not available".to_string()));
+    };
+
+    let mut failure = None;
+    if mode == RevocationMode::Online {
+        #[cfg(feature = "network")]
+        match online_status(cert, issuer, &CancellationToken::new()) {
+            Ok(status) => return status,
+            Err(e) => failure = Some(format!("{:#}", e)),
+        }
+        #[cfg(not(feature = "network"))]
+        {
+            failure = Some(crate::error::missing_feature("online revocation checking", "network").to_string());
+        }
+    }
+
+    // Data dari /DSS: respons yang tidak mencakup sertifikat ini dilewati
+    let answers = embedded
+        .ocsp_responses
+        .iter()
+        .map(|der| ocsp_status(der, cert, issuer, RevocationSource::Ocsp(None)))
+        .chain(embedded.crls.iter().map(|der| crl_status(der, cert, issuer, RevocationSource::Crl(None))));
+    for answer in answers {
+        match answer {
+            Ok(Some(status)) => return status,
+            Ok(None) => {}
+            Err(e) => failure = Some(format!("{:#}", e)),
+        }
+    }
+    RevocationStatus::Unknown(failure)
+}
+
+/// Tanya status sertifikat lewat OCSP, lalu CRL (URL dari sertifikat)
+#[cfg(all(feature = "verify", feature = "network"))]
+fn online_status(cert: &Certificate, issuer: &Certificate, cancel: &CancellationToken) -> Result<RevocationStatus> {
+    let mut failure = None;
+    if let Some(url) = ocsp_url(cert)? {
+        let status = request_ocsp(cert, issuer, &url, cancel)
+            .and_then(|der| ocsp_status(&der, cert, issuer, RevocationSource::Ocsp(Some(url.clone()))));
+        match status {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) => failure = Some(anyhow!("OCSP response from {} does not cover the certificate", url)),
+            Err(e) => failure = Some(e.context(format!("OCSP responder {}", url))),
+        }
+    }
+    for url in crl_urls(cert)? {
+        let status = fetch_crl(&url, cancel).and_then(|(der, _)| crl_status(&der, cert, issuer, RevocationSource::Crl(Some(url.clone()))));
+        match status {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) => failure = Some(anyhow!("CRL {} is not issued by the certificate's issuer", url)),
+            Err(e) => failure = Some(e.context(format!("CRL {}", url))),
+        }
+    }
+    Err(failure.unwrap_or_else(|| anyhow!("the certificate has no OCSP responder or CRL distribution point")))
+}
+
+/// Status `cert` menurut respons OCSP (DER)
+/// Return: None jika respons tidak mencakup `cert`
+#[cfg(feature = "verify")]
+fn ocsp_status(der: &[u8], cert: &Certificate, issuer: &Certificate, source: RevocationSource) -> Result<Option<RevocationStatus>> {
+    let basic = basic_response(der)?;
+    let Some(single) = single_response(&basic, cert, issuer) else {
+        return Ok(None);
+    };
+
+    // Penanda tangan respons: issuer sendiri, atau responder dengan EKU
+    // OCSPSigning yang sertifikatnya diterbitkan issuer (RFC 6960 4.2.2.2)
+    let tbs = basic.tbs_response_data.to_der().map_err(|e| anyhow!("invalid OCSP response: {}", e))?;
+    let signature = basic.signature.as_bytes().ok_or_else(|| anyhow!("OCSP response signature is not byte-aligned"))?;
+    let delegated = basic.certs.iter().flatten().filter(|responder| {
+        let authorized = responder
+            .tbs_certificate
+            .get::<ExtendedKeyUsage>()
+            .ok()
+            .flatten()
+            .is_some_and(|(_, usage)| usage.0.contains(&ID_KP_OCSP_SIGNING));
+        authorized && trust::issued_by(responder, issuer)
+    });
+    let signed = std::iter::once(issuer)
+        .chain(delegated)
+        .any(|responder| trust::signed_with(responder, &basic.signature_algorithm, &tbs, signature));
+    if !signed {
+        bail!("the OCSP response is not signed by the issuer of {} or a responder it authorized", cert.tbs_certificate.subject);
+    }
+
+    match single.cert_status {
+        CertStatus::Good(_) => Ok(Some(RevocationStatus::Good(source))),
+        CertStatus::Revoked(info) => Ok(Some(RevocationStatus::Revoked { source, revoked_at: info.revocation_time.0.to_system_time() })),
+        CertStatus::Unknown(_) => bail!("the OCSP responder does not know certificate {}", cert.tbs_certificate.subject),
+    }
+}
+
+/// Status `cert` menurut CRL (DER)
+/// Return: None jika CRL tidak diterbitkan oleh issuer `cert`
+#[cfg(feature = "verify")]
+fn crl_status(der: &[u8], cert: &Certificate, issuer: &Certificate, source: RevocationSource) -> Result<Option<RevocationStatus>> {
+    let crl = CertificateList::from_der(der).map_err(|e| anyhow!("invalid CRL: {}", e))?;
+    if crl.tbs_cert_list.issuer != issuer.tbs_certificate.subject {
+        return Ok(None);
+    }
+    let tbs = crl.tbs_cert_list.to_der().map_err(|e| anyhow!("invalid CRL: {}", e))?;
+    let signature = crl.signature.as_bytes().ok_or_else(|| anyhow!("CRL signature is not byte-aligned"))?;
+    if !trust::signed_with(issuer, &crl.signature_algorithm, &tbs, signature) {
+        bail!("the CRL from {} is not signed by that issuer", issuer.tbs_certificate.subject);
+    }
+
+    let entry = crl
+        .tbs_cert_list
+        .revoked_certificates
+        .iter()
+        .flatten()
+        .find(|entry| entry.serial_number == cert.tbs_certificate.serial_number);
+    Ok(Some(match entry {
+        Some(entry) => RevocationStatus::Revoked { source, revoked_at: entry.revocation_date.to_system_time() },
+        None => RevocationStatus::Good(source),
+    }))
+}
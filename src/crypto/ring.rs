@@ -0,0 +1,188 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use ::ring::rand::SystemRandom; // Sumber acak ring untuk signing ECDSA dan RSA-PSS
+use ::ring::signature::{self as ring_signature, EcdsaKeyPair, Ed25519KeyPair, RsaKeyPair}; // Kunci privat ring
+use p256::pkcs8::EncodePrivateKey; // PKCS#8 untuk kunci ECDSA dan RSA
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest
+use crate::crypto::ecc::{self, Curve}; // Deteksi kunci ECC
+use crate::crypto::provider::{CryptoProvider, DigestContext}; // Trait provider
+use crate::crypto::rsa::{RsaPadding, RsaSigner}; // Deteksi kunci RSA
+use crate::crypto::signer::{EcdsaSigner, Ed25519Signer, Signer}; // Algoritma dan kunci publik untuk CMS
+
+/// Provider kriptografi berbasis ring (--crypto-backend ring)
+///
+/// Digest SHA-2 dan signing dengan kunci ECDSA P-256/P-384, Ed25519, dan RSA
+/// (minimal 2048 bit) dilakukan oleh ring; kunci P-521 tetap memakai
+/// implementasi bawaan. ring memakai generator acak OS-nya sendiri, bukan
+/// `provider::set_random_source`.
+pub struct RingProvider {
+    random: SystemRandom, // Generator acak untuk membuat dan memakai kunci
+}
+
+impl RingProvider {
+    /// Provider baru
+    pub fn new() -> RingProvider {
+        RingProvider { random: SystemRandom::new() }
+    }
+
+    /// Signer ECDSA; None untuk kurva yang tidak didukung ring (P-521)
+    fn ecdsa(&self, curve: Curve, private_key: &[u8]) -> Option<Result<Box<dyn Signer>>> {
+        let (algorithm, digest, pkcs8) = match curve {
+            Curve::P256 => (
+                &ring_signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+                DigestAlgorithm::Sha256,
+                p256::SecretKey::from_slice(private_key).map(|key| key.to_pkcs8_der()),
+            ),
+            Curve::P384 => (
+                &ring_signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+                DigestAlgorithm::Sha384,
+                p384::SecretKey::from_slice(private_key).map(|key| key.to_pkcs8_der()),
+            ),
+            _ => return None,
+        };
+        Some((|| {
+            let inner = EcdsaSigner::new(curve, private_key)?;
+            let pkcs8 = pkcs8
+                .map_err(|_| anyhow!("invalid {} private key", curve.label()))?
+                .map_err(|e| anyhow!("cannot encode private key: {}", e))?;
+            let key = EcdsaKeyPair::from_pkcs8(algorithm, pkcs8.as_bytes(), &self.random)
+                .map_err(|e| anyhow!("ring rejected the {} key: {}", curve.label(), e))?;
+            Ok(Box::new(RingSigner { key: RingKey::Ecdsa(key, digest), inner: Box::new(inner), random: self.random.clone() }) as Box<dyn Signer>)
+        })())
+    }
+
+    /// Signer Ed25519 dari seed 32 bytes
+    fn ed25519(&self, seed: &[u8]) -> Result<Box<dyn Signer>> {
+        let inner = Ed25519Signer::new(seed)?;
+        let key = Ed25519KeyPair::from_seed_unchecked(seed).map_err(|e| anyhow!("ring rejected the Ed25519 key: {}", e))?;
+        Ok(Box::new(RingSigner { key: RingKey::Ed25519(key), inner: Box::new(inner), random: self.random.clone() }))
+    }
+
+    /// Signer RSA dari kunci yang sudah dibaca implementasi bawaan
+    fn rsa(&self, signer: RsaSigner, padding: RsaPadding) -> Result<Box<dyn Signer>> {
+        let pkcs8 = signer.to_pkcs8_der()?;
+        let key = RsaKeyPair::from_pkcs8(&pkcs8).map_err(|e| anyhow!("ring rejected the RSA key (at least 2048 bits are required): {}", e))?;
+        Ok(Box::new(RingSigner { key: RingKey::Rsa(key, padding), inner: Box::new(signer), random: self.random.clone() }))
+    }
+}
+
+impl Default for RingProvider {
+    fn default() -> Self {
+        RingProvider::new()
+    }
+}
+
+impl CryptoProvider for RingProvider {
+    fn name(&self) -> &str {
+        "ring"
+    }
+
+    fn hasher(&self, algorithm: DigestAlgorithm) -> Option<Box<dyn DigestContext>> {
+        let algorithm = match algorithm {
+            DigestAlgorithm::Sha256 => &::ring::digest::SHA256,
+            DigestAlgorithm::Sha384 => &::ring::digest::SHA384,
+            DigestAlgorithm::Sha512 => &::ring::digest::SHA512,
+        };
+        Some(Box::new(RingDigest(::ring::digest::Context::new(algorithm))))
+    }
+
+    fn signer_from_bytes(&self, bytes: &[u8], rsa_padding: RsaPadding) -> Option<Result<Box<dyn Signer>>> {
+        // Urutan deteksi sama seperti signer::signer_from_bytes
+        if let Some(curve) = Curve::from_raw_private_len(bytes.len()) {
+            return self.ecdsa(curve, bytes);
+        }
+        match ecc::private_key_from_encoded(bytes) {
+            Some((Curve::Ed25519, seed)) => return Some(self.ed25519(&seed)),
+            Some((curve, private_key)) => return self.ecdsa(curve, &private_key),
+            None => {}
+        }
+        match RsaSigner::from_bytes(bytes, rsa_padding) {
+            Ok(Some(signer)) => Some(self.rsa(signer, rsa_padding)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Hash inkremental ring
+struct RingDigest(::ring::digest::Context);
+
+impl DigestContext for RingDigest {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.0.finish().as_ref().to_vec()
+    }
+}
+
+/// Kunci privat ring
+enum RingKey {
+    /// ECDSA dengan digest yang ditetapkan kurvanya (P-256/SHA-256, P-384/SHA-384)
+    Ecdsa(EcdsaKeyPair, DigestAlgorithm),
+    Ed25519(Ed25519KeyPair),
+    Rsa(RsaKeyPair, RsaPadding),
+}
+
+/// Signer yang menandatangani dengan ring; algoritma, kunci publik, dan
+/// deskripsi diambil dari signer bawaan untuk kunci yang sama
+struct RingSigner {
+    key: RingKey,           // Kunci yang dipakai untuk signing
+    inner: Box<dyn Signer>, // Signer bawaan (tidak dipakai untuk signing)
+    random: SystemRandom,   // Nonce ECDSA dan salt PSS
+}
+
+impl Signer for RingSigner {
+    fn sign_digest(&self, _digest: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        bail!("the ring crypto backend signs the message itself and cannot sign a precomputed digest; use --crypto-backend rustcrypto")
+    }
+
+    fn sign_message(&self, message: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        match &self.key {
+            RingKey::Ecdsa(key, digest) => {
+                if algorithm != *digest {
+                    bail!("the ring crypto backend signs {} keys with {} only, not {}", self.inner.description(), digest.label(), algorithm.label());
+                }
+                let signature = key.sign(&self.random, message).map_err(|_| anyhow!("ECDSA signing failed"))?;
+                Ok(signature.as_ref().to_vec())
+            }
+            RingKey::Ed25519(key) => Ok(key.sign(message).as_ref().to_vec()),
+            RingKey::Rsa(key, padding) => {
+                let encoding: &'static dyn ring_signature::RsaEncoding = match (padding, algorithm) {
+                    (RsaPadding::Pkcs1v15, DigestAlgorithm::Sha256) => &ring_signature::RSA_PKCS1_SHA256,
+                    (RsaPadding::Pkcs1v15, DigestAlgorithm::Sha384) => &ring_signature::RSA_PKCS1_SHA384,
+                    (RsaPadding::Pkcs1v15, DigestAlgorithm::Sha512) => &ring_signature::RSA_PKCS1_SHA512,
+                    (RsaPadding::Pss, DigestAlgorithm::Sha256) => &ring_signature::RSA_PSS_SHA256,
+                    (RsaPadding::Pss, DigestAlgorithm::Sha384) => &ring_signature::RSA_PSS_SHA384,
+                    (RsaPadding::Pss, DigestAlgorithm::Sha512) => &ring_signature::RSA_PSS_SHA512,
+                };
+                let mut signature = vec![0u8; key.public().modulus_len()];
+                key.sign(encoding, &self.random, message, &mut signature).map_err(|_| anyhow!("RSA signing failed"))?;
+                Ok(signature)
+            }
+        }
+    }
+
+    fn digest_for(&self, requested: DigestAlgorithm) -> DigestAlgorithm {
+        match &self.key {
+            RingKey::Ecdsa(_, digest) => *digest,
+            RingKey::Ed25519(_) => DigestAlgorithm::Sha512,
+            RingKey::Rsa(..) => requested,
+        }
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        self.inner.signature_algorithm(digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        self.inner.public_key_info()
+    }
+
+    fn description(&self) -> String {
+        format!("{} (ring)", self.inner.description())
+    }
+}
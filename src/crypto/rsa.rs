@@ -0,0 +1,197 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use ::rsa::pkcs1::{DecodeRsaPublicKey, RsaPssParams}; // Format PKCS#1 dan parameter PSS
+use ::rsa::pkcs8::DecodePublicKey; // Format SPKI
+use ::rsa::traits::PublicKeyParts; // Ukuran modulus
+use ::rsa::{Pkcs1v15Sign, Pss, RsaPublicKey}; // Kunci publik dan skema signature RSA
+#[cfg(feature = "sign")]
+use ::rsa::{pkcs1::DecodeRsaPrivateKey, pkcs8::{DecodePrivateKey, EncodePublicKey}, RsaPrivateKey}; // Kunci privat RSA
+#[cfg(feature = "ring")]
+use ::rsa::pkcs8::EncodePrivateKey; // PKCS#8 untuk backend ring
+use sha2::{Sha256, Sha384, Sha512}; // Digest untuk PKCS#1 v1.5 dan PSS
+use x509_cert::der::asn1::{Any, ObjectIdentifier}; // Tipe ASN.1
+use x509_cert::der::Encode; // Encoding DER
+#[cfg(feature = "sign")]
+use x509_cert::der::Decode; // Decoding DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+#[cfg(feature = "sign")]
+use crate::crypto::signer::Signer; // Trait backend penandatanganan
+#[cfg(feature = "sign")]
+use crate::crypto::provider; // Sumber acak padding PSS
+
+// OID untuk RSA
+const ID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const ID_RSASSA_PSS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
+
+/// Skema padding untuk signature RSA
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum RsaPadding {
+    /// RSASSA-PKCS1-v1_5 (misalnya sha256WithRSAEncryption), paling kompatibel
+    #[default]
+    Pkcs1v15,
+    /// RSASSA-PSS dengan MGF1 memakai digest yang sama, salt sepanjang digest
+    Pss,
+}
+
+impl RsaPadding {
+    /// AlgorithmIdentifier signature RSA dengan `digest` untuk padding ini
+    pub fn signature_algorithm(self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        let pss_params = |params: RsaPssParams| Any::encode_from(&params).map_err(|e| anyhow!("{}", e));
+        Ok(match self {
+            RsaPadding::Pkcs1v15 => AlgorithmIdentifierOwned {
+                oid: digest.rsa_oid(),
+                parameters: Some(Any::null()),
+            },
+            RsaPadding::Pss => AlgorithmIdentifierOwned {
+                oid: ID_RSASSA_PSS,
+                parameters: Some(match digest {
+                    DigestAlgorithm::Sha256 => pss_params(RsaPssParams::new::<Sha256>(32))?,
+                    DigestAlgorithm::Sha384 => pss_params(RsaPssParams::new::<Sha384>(48))?,
+                    DigestAlgorithm::Sha512 => pss_params(RsaPssParams::new::<Sha512>(64))?,
+                }),
+            },
+        })
+    }
+}
+
+/// Skema RSASSA-PKCS1-v1_5 untuk digest ini
+fn pkcs1v15(digest: DigestAlgorithm) -> Pkcs1v15Sign {
+    match digest {
+        DigestAlgorithm::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+        DigestAlgorithm::Sha384 => Pkcs1v15Sign::new::<Sha384>(),
+        DigestAlgorithm::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+    }
+}
+
+/// Skema RSASSA-PSS untuk digest ini dengan panjang salt tertentu
+fn pss(digest: DigestAlgorithm, salt_len: usize) -> Pss {
+    match digest {
+        DigestAlgorithm::Sha256 => Pss::new_with_salt::<Sha256>(salt_len),
+        DigestAlgorithm::Sha384 => Pss::new_with_salt::<Sha384>(salt_len),
+        DigestAlgorithm::Sha512 => Pss::new_with_salt::<Sha512>(salt_len),
+    }
+}
+
+/// Signer RSA (umumnya 2048/3072/4096 bit) dengan padding PKCS#1 v1.5 atau PSS
+#[cfg(feature = "sign")]
+pub struct RsaSigner {
+    key: RsaPrivateKey,   // Kunci privat RSA
+    padding: RsaPadding,  // Skema padding
+}
+
+#[cfg(feature = "sign")]
+impl RsaSigner {
+    /// Coba baca kunci privat RSA dari PKCS#1 atau PKCS#8 (DER atau PEM)
+    /// Return: None jika bytes bukan kunci RSA
+    pub fn from_bytes(bytes: &[u8], padding: RsaPadding) -> Result<Option<RsaSigner>> {
+        let key = match std::str::from_utf8(bytes) {
+            // PEM: "BEGIN RSA PRIVATE KEY" (PKCS#1) atau "BEGIN PRIVATE KEY" (PKCS#8)
+            Ok(text) if text.contains("-----BEGIN") => RsaPrivateKey::from_pkcs1_pem(text)
+                .ok()
+                .or_else(|| RsaPrivateKey::from_pkcs8_pem(text).ok()),
+            _ => RsaPrivateKey::from_pkcs1_der(bytes)
+                .ok()
+                .or_else(|| RsaPrivateKey::from_pkcs8_der(bytes).ok()),
+        };
+        // Ukuran minimum kunci diperiksa oleh AlgorithmPolicy saat signing
+        Ok(key.map(|key| RsaSigner { key, padding }))
+    }
+
+    /// Kunci privat dalam PKCS#8 DER (untuk backend ring)
+    #[cfg(feature = "ring")]
+    pub(crate) fn to_pkcs8_der(&self) -> Result<Vec<u8>> {
+        let der = self.key.to_pkcs8_der().map_err(|e| anyhow!("cannot encode private key: {}", e))?;
+        Ok(der.as_bytes().to_vec())
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Signer for RsaSigner {
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        let signature = match self.padding {
+            RsaPadding::Pkcs1v15 => self.key.sign(pkcs1v15(algorithm), digest),
+            RsaPadding::Pss => self.key.sign_with_rng(&mut provider::rng(), pss(algorithm, algorithm.output_len()), digest),
+        };
+        signature.map_err(|e| anyhow!("RSA signing failed: {}", e))
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        self.padding.signature_algorithm(digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        let der = self.key.to_public_key().to_public_key_der().map_err(|e| anyhow!("{}", e))?;
+        SubjectPublicKeyInfoOwned::from_der(der.as_bytes()).map_err(|e| anyhow!("{}", e))
+    }
+
+    fn description(&self) -> String {
+        let padding = match self.padding {
+            RsaPadding::Pkcs1v15 => "PKCS#1 v1.5",
+            RsaPadding::Pss => "PSS",
+        };
+        format!("RSA-{} {}", self.key.size() * 8, padding)
+    }
+}
+
+/// Kunci publik RSA untuk verifikasi
+pub struct RsaPublic(RsaPublicKey);
+
+impl RsaPublic {
+    /// Baca kunci publik RSA dari PKCS#1 atau SPKI (DER atau PEM)
+    pub fn from_bytes(bytes: &[u8]) -> Option<RsaPublic> {
+        let key = match std::str::from_utf8(bytes) {
+            Ok(text) if text.contains("-----BEGIN") => RsaPublicKey::from_pkcs1_pem(text)
+                .ok()
+                .or_else(|| RsaPublicKey::from_public_key_pem(text).ok()),
+            _ => RsaPublicKey::from_pkcs1_der(bytes)
+                .ok()
+                .or_else(|| RsaPublicKey::from_public_key_der(bytes).ok()),
+        };
+        key.map(RsaPublic)
+    }
+
+    /// Ukuran modulus dalam bit
+    pub fn bits(&self) -> usize {
+        self.0.size() * 8
+    }
+
+    /// Ambil kunci publik RSA dari SubjectPublicKeyInfo
+    pub fn from_spki(spki: &SubjectPublicKeyInfoOwned) -> Result<RsaPublic> {
+        if spki.algorithm.oid != ID_RSA_ENCRYPTION && spki.algorithm.oid != ID_RSASSA_PSS {
+            bail!("unsupported public key algorithm {}", spki.algorithm.oid);
+        }
+        let der = spki.to_der().map_err(|e| anyhow!("{}", e))?;
+        RsaPublicKey::from_public_key_der(&der)
+            .map(RsaPublic)
+            .map_err(|e| anyhow!("invalid RSA public key: {}", e))
+    }
+
+    /// Verifikasi signature RSA atas digest SHA-256, SHA-384, atau SHA-512
+    pub fn verify(&self, algorithm: &AlgorithmIdentifierOwned, digest: &[u8], signature: &[u8]) -> Result<bool> {
+        let result = if let Some(hash) = DigestAlgorithm::ALL.into_iter().find(|hash| hash.rsa_oid() == algorithm.oid) {
+            self.0.verify(pkcs1v15(hash), digest, signature)
+        } else if algorithm.oid == ID_RSA_ENCRYPTION {
+            // rsaEncryption tidak menyebut digest; ditebak dari panjangnya
+            let hash = DigestAlgorithm::ALL.into_iter().find(|hash| hash.output_len() == digest.len()).unwrap_or_default();
+            self.0.verify(pkcs1v15(hash), digest, signature)
+        } else if algorithm.oid == ID_RSASSA_PSS {
+            // Parameter PSS: hash SHA-2 dan panjang salt diambil dari parameter
+            let params_der = match &algorithm.parameters {
+                Some(any) => any.to_der().map_err(|e| anyhow!("{}", e))?,
+                None => bail!("RSASSA-PSS signature has no parameters"),
+            };
+            let params = RsaPssParams::try_from(params_der.as_slice())
+                .map_err(|e| anyhow!("invalid RSASSA-PSS parameters: {}", e))?;
+            let Some(hash) = DigestAlgorithm::from_oid(&params.hash.oid) else {
+                bail!("unsupported RSASSA-PSS hash algorithm {}", params.hash.oid);
+            };
+            self.0.verify(pss(hash, params.salt_len as usize), digest, signature)
+        } else {
+            bail!("signature algorithm {} does not match the RSA key", algorithm.oid);
+        };
+        Ok(result.is_ok())
+    }
+}
@@ -0,0 +1,433 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs; // Direktori socket dan file kunci
+use std::io::{BufRead, BufReader, Read, Write}; // Protokol agent lewat pipe dan socket
+use std::os::unix::fs::DirBuilderExt; // Direktori socket dengan mode 0700
+use std::os::unix::net::{UnixListener, UnixStream}; // Socket lokal ke agent
+use std::os::unix::process::CommandExt; // Agent di process group sendiri
+use std::path::{Path, PathBuf}; // Path socket dan identitas
+use std::process::{Command, Stdio}; // Menjalankan agent di background
+use std::time::{Duration, SystemTime, UNIX_EPOCH}; // Masa berlaku sesi
+use x509_cert::der::{Decode, Encode}; // Encoding kunci publik
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+use crate::crypto::cert::certificates_for_key; // certificate.der di sebelah kunci
+use crate::crypto::cms::split_certificates; // Rantai sertifikat dari agent
+use crate::crypto::digest::DigestAlgorithm; // Digest yang ditandatangani agent
+use crate::crypto::keyfile; // Dekripsi kunci PKCS#8
+use crate::crypto::p12::load_pkcs12_key; // Bundle PKCS#12
+use crate::crypto::rsa::RsaPadding; // Padding jika kuncinya RSA
+use crate::crypto::signer::{signature_algorithm_for, signer_from_bytes, Credentials, Signer}; // Backend penandatanganan
+use crate::lock::FileLock; // Satu start/stop pada satu waktu
+
+/// Batas ukuran satu pesan protokol (kunci, sertifikat, signature)
+const MAX_MESSAGE: usize = 1 << 20;
+
+/// Identitas yang dibuka untuk sesi signing
+pub enum Identity {
+    /// File kunci privat; passphrase ditanyakan di terminal jika None dan kuncinya terenkripsi
+    Key { path: String, passphrase: Option<String> },
+    /// Bundle PKCS#12; password ditanyakan di terminal jika None dan bundle-nya terproteksi
+    Pkcs12 { path: String, password: Option<String> },
+}
+
+/// Keadaan sesi yang sedang aktif
+pub struct SessionStatus {
+    pub identity: String,     // Path absolut file kunci atau bundle PKCS#12
+    pub expires: SystemTime,  // Setelah waktu ini agent berhenti dan kunci harus dibuka lagi
+}
+
+/// Buka identitas dan jalankan agent yang menyimpannya selama `duration`
+///
+/// Passphrase/password hanya diminta sekali di sini. Kunci yang sudah
+/// didekripsi disimpan di memori proses agent (tidak pernah ditulis ke disk);
+/// `sign` dengan kunci atau bundle yang sama memakai agent tersebut sampai
+/// sesi habis atau dihentikan dengan `stop`. Sesi lain yang masih aktif dihentikan.
+///
+/// Parameter:
+///   - identity: file kunci atau bundle PKCS#12 beserta passphrase-nya (opsional)
+///   - duration: lama sesi
+pub fn start(identity: Identity, duration: Duration) -> Result<SessionStatus> {
+    if duration.is_zero() {
+        bail!("session duration must be at least one second");
+    }
+
+    // Buka kunci di foreground (boleh bertanya di terminal)
+    let (path, key, certificates) = match identity {
+        Identity::Key { path, passphrase } => {
+            let bytes = fs::read(&path).with_context(|| format!("cannot read private key {}", path))?;
+            let key = if keyfile::is_encrypted(&bytes) {
+                keyfile::decrypt_private_key(&bytes, passphrase.as_deref(), &path)
+                    .with_context(|| format!("cannot load private key {}", path))?
+            } else {
+                bytes
+            };
+            let certificates = certificates_for_key(&path)?;
+            (path, key, certificates)
+        }
+        Identity::Pkcs12 { path, password } => {
+            let (key, certificates) = load_pkcs12_key(&path, password.as_deref())?;
+            (path, key, certificates)
+        }
+    };
+    signer_from_bytes(&key, RsaPadding::default()).with_context(|| format!("cannot load private key {}", path))?;
+    let identity = canonical(&path)?;
+    let expires = SystemTime::now() + duration;
+
+    // Satu sesi pada satu waktu: start lain menunggu sampai agent ini siap,
+    // agar tidak ada agent yatim yang socket-nya sudah ditimpa
+    let _lock = session_lock()?;
+    stop_agent()?;
+
+    // Agent = program ini sendiri dengan command tersembunyi `session serve`;
+    // kunci dikirim lewat stdin, bukan argumen atau environment
+    let mut child = Command::new(std::env::current_exe().context("cannot locate the pdfsign executable")?)
+        .args(["session", "serve"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .context("cannot start the session agent")?;
+    {
+        let mut stdin = child.stdin.take().ok_or_else(|| anyhow!("cannot start the session agent"))?;
+        write_message(&mut stdin, identity.as_bytes())?;
+        write_message(&mut stdin, unix_seconds(expires).to_string().as_bytes())?;
+        write_message(&mut stdin, &key)?;
+        write_message(&mut stdin, &certificates.concat())?;
+    }
+
+    // Tunggu sampai agent siap menerima koneksi
+    let mut line = String::new();
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("cannot start the session agent"))?;
+    BufReader::new(stdout).read_line(&mut line)?;
+    if line.trim() != "ready" {
+        bail!("session agent failed to start");
+    }
+    Ok(SessionStatus { identity, expires })
+}
+
+/// Jalankan agent (command tersembunyi `session serve`, dipanggil oleh `start`)
+///
+/// Membaca identitas, waktu habis, kunci, dan sertifikat dari `input` (pipe
+/// dari `start`), menulis "ready" ke `output` setelah socket siap, lalu
+/// melayani request di socket sampai sesi habis atau dihentikan.
+///
+/// Parameter:
+///   - input: pipe dari proses `start` (CLI: stdin)
+///   - output: pipe ke proses `start` (CLI: stdout)
+pub fn serve(input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+    let identity = String::from_utf8(read_message(input)?)?;
+    let expires: u64 = String::from_utf8(read_message(input)?)?.parse()?;
+    let key = read_message(input)?;
+    let certificates = read_message(input)?;
+
+    // Socket di direktori milik user (0700), sehingga user lain tidak bisa terhubung
+    let socket = socket_path()?;
+    if let Some(dir) = socket.parent() {
+        fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    }
+    let _ = fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket).with_context(|| format!("cannot listen on {}", socket.display()))?;
+    writeln!(output, "ready")?;
+    output.flush()?;
+
+    // Setelah waktu habis, socket dihapus dan proses (beserta kuncinya) berakhir
+    let expiry_socket = socket.clone();
+    std::thread::spawn(move || {
+        let remaining = (UNIX_EPOCH + Duration::from_secs(expires)).duration_since(SystemTime::now()).unwrap_or_default();
+        std::thread::sleep(remaining);
+        let _ = fs::remove_file(&expiry_socket);
+        std::process::exit(0);
+    });
+
+    let agent = Agent { identity, expires, key, certificates };
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else { continue };
+        let result = agent.handle(&mut stream);
+        if let Err(err) = &result {
+            let _ = write_message(&mut stream, b"err");
+            let _ = write_message(&mut stream, err.to_string().as_bytes());
+        }
+        if matches!(result, Ok(Request::Stop)) {
+            let _ = fs::remove_file(&socket);
+            std::process::exit(0);
+        }
+    }
+    Ok(())
+}
+
+/// Status sesi yang sedang aktif, None jika tidak ada
+pub fn status() -> Result<Option<SessionStatus>> {
+    let Some(mut stream) = connect()? else {
+        return Ok(None);
+    };
+    write_message(&mut stream, b"status")?;
+    let reply = read_reply(&mut stream)?;
+    let identity = String::from_utf8(reply_field(&reply, 0)?.to_vec())?;
+    let expires: u64 = std::str::from_utf8(reply_field(&reply, 1)?)?.parse()?;
+    Ok(Some(SessionStatus { identity, expires: UNIX_EPOCH + Duration::from_secs(expires) }))
+}
+
+/// Hentikan sesi yang sedang aktif
+/// Return: true jika ada sesi yang dihentikan
+pub fn stop() -> Result<bool> {
+    let _lock = session_lock()?;
+    stop_agent()
+}
+
+/// Minta agent berhenti (pemanggil sudah memegang lock sesi)
+fn stop_agent() -> Result<bool> {
+    let Some(mut stream) = connect()? else {
+        return Ok(false);
+    };
+    write_message(&mut stream, b"stop")?;
+    read_reply(&mut stream)?;
+    Ok(true)
+}
+
+/// Kunci dan sertifikat dari sesi aktif, jika sesi tersebut membuka `path`
+///
+/// Parameter:
+///   - path: file kunci atau bundle PKCS#12 yang dipakai `sign`
+///   - rsa_padding: skema padding jika kuncinya RSA
+///
+/// Return: None jika tidak ada sesi untuk identitas ini (sign membuka kunci seperti biasa)
+pub fn credentials_for(path: impl AsRef<Path>, rsa_padding: RsaPadding) -> Result<Option<Credentials>> {
+    let Ok(identity) = canonical(&path.as_ref().to_string_lossy()) else {
+        return Ok(None);
+    };
+    match status()? {
+        Some(status) if status.identity == identity && status.expires > SystemTime::now() => {}
+        _ => return Ok(None),
+    }
+    let Some(mut stream) = connect()? else {
+        return Ok(None);
+    };
+    write_message(&mut stream, b"info")?;
+    write_message(&mut stream, padding_name(rsa_padding).as_bytes())?;
+    let reply = read_reply(&mut stream)?;
+    let signer = SessionSigner {
+        rsa_padding,
+        public_key_info: SubjectPublicKeyInfoOwned::from_der(reply_field(&reply, 0)?).map_err(|e| anyhow!("{}", e))?,
+        description: String::from_utf8(reply_field(&reply, 1)?.to_vec())?,
+    };
+    let certificates = match reply_field(&reply, 2)? {
+        [] => Vec::new(),
+        chain => split_certificates(chain)?,
+    };
+    Ok(Some((Box::new(signer), certificates)))
+}
+
+/// Signer yang meminta agent sesi menandatangani digest; kunci privat tidak
+/// pernah keluar dari proses agent
+struct SessionSigner {
+    rsa_padding: RsaPadding,                         // Padding yang diminta sign
+    public_key_info: SubjectPublicKeyInfoOwned,      // Kunci publik dari agent
+    description: String,                             // Deskripsi kunci dari agent
+}
+
+impl SessionSigner {
+    /// Kirim request `sign` (digest) atau `sign-message` (pesan utuh) ke agent
+    fn request_signature(&self, request: &[u8], data: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        let mut stream = connect()?.ok_or_else(|| anyhow!("the signing session has ended; start a new one"))?;
+        write_message(&mut stream, request)?;
+        write_message(&mut stream, padding_name(self.rsa_padding).as_bytes())?;
+        write_message(&mut stream, algorithm.name().as_bytes())?;
+        write_message(&mut stream, data)?;
+        let reply = read_reply(&mut stream)?;
+        Ok(reply_field(&reply, 0)?.to_vec())
+    }
+}
+
+impl Signer for SessionSigner {
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        self.request_signature(b"sign", digest, algorithm)
+    }
+
+    fn sign_message(&self, message: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        // Agent yang menghitung digest (atau menandatangani pesannya langsung untuk Ed25519)
+        self.request_signature(b"sign-message", message, algorithm)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        signature_algorithm_for(&self.public_key_info, self.rsa_padding, digest)
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.public_key_info.clone())
+    }
+
+    fn description(&self) -> String {
+        format!("{}, signing session", self.description)
+    }
+}
+
+/// Jenis request yang ditangani agent
+enum Request {
+    Status,
+    Info,
+    Sign,
+    SignMessage,
+    Stop,
+}
+
+/// Data yang disimpan agent selama sesi
+struct Agent {
+    identity: String,   // Path absolut identitas yang dibuka
+    expires: u64,       // Waktu habis (detik sejak epoch)
+    key: Vec<u8>,       // Kunci privat yang sudah didekripsi
+    certificates: Vec<u8>, // Sertifikat DER berurutan
+}
+
+impl Agent {
+    /// Baca satu request dari `stream` dan kirim balasannya
+    fn handle(&self, stream: &mut UnixStream) -> Result<Request> {
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        let request = match read_message(stream)?.as_slice() {
+            b"status" => Request::Status,
+            b"info" => Request::Info,
+            b"sign" => Request::Sign,
+            b"sign-message" => Request::SignMessage,
+            b"stop" => Request::Stop,
+            _ => bail!("unknown session request"),
+        };
+        match request {
+            Request::Status => {
+                write_message(stream, b"ok")?;
+                write_message(stream, self.identity.as_bytes())?;
+                write_message(stream, self.expires.to_string().as_bytes())?;
+            }
+            Request::Info => {
+                let signer = signer_from_bytes(&self.key, parse_padding(&read_message(stream)?)?)?;
+                let der_err = |e: x509_cert::der::Error| anyhow!("{}", e);
+                write_message(stream, b"ok")?;
+                write_message(stream, &signer.public_key_info()?.to_der().map_err(der_err)?)?;
+                write_message(stream, signer.description().as_bytes())?;
+                write_message(stream, &self.certificates)?;
+            }
+            Request::Sign | Request::SignMessage => {
+                let signer = signer_from_bytes(&self.key, parse_padding(&read_message(stream)?)?)?;
+                let algorithm = std::str::from_utf8(&read_message(stream)?)
+                    .ok()
+                    .and_then(DigestAlgorithm::from_name)
+                    .ok_or_else(|| anyhow!("unknown digest algorithm"))?;
+                let data = read_message(stream)?;
+                let signature = match request {
+                    Request::Sign => signer.sign_digest(&data, algorithm)?,
+                    _ => signer.sign_message(&data, algorithm)?,
+                };
+                write_message(stream, b"ok")?;
+                write_message(stream, &signature)?;
+            }
+            Request::Stop => write_message(stream, b"ok")?,
+        }
+        Ok(request)
+    }
+}
+
+/// Path socket agent: $XDG_RUNTIME_DIR/pdfsign/session.sock, atau
+/// ~/.pdfsign/session.sock jika XDG_RUNTIME_DIR tidak ada
+fn socket_path() -> Result<PathBuf> {
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("pdfsign"),
+        None => PathBuf::from(std::env::var_os("HOME").ok_or_else(|| anyhow!("cannot locate the session socket: HOME is not set"))?)
+            .join(".pdfsign"),
+    };
+    Ok(dir.join("session.sock"))
+}
+
+/// Lock untuk start/stop sesi, di direktori socket (dibuat dengan mode 0700)
+fn session_lock() -> Result<FileLock> {
+    let socket = socket_path()?;
+    if let Some(dir) = socket.parent() {
+        fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    }
+    FileLock::exclusive(socket)
+}
+
+/// Hubungkan ke agent; None jika tidak ada sesi (socket tidak ada atau basi)
+fn connect() -> Result<Option<UnixStream>> {
+    let socket = socket_path()?;
+    match UnixStream::connect(&socket) {
+        Ok(stream) => {
+            stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+            Ok(Some(stream))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Baca balasan agent: "ok" diikuti field-field hasil, atau "err" diikuti pesannya
+fn read_reply(stream: &mut UnixStream) -> Result<Vec<Vec<u8>>> {
+    let status = read_message(stream)?;
+    let mut fields = Vec::new();
+    while let Ok(field) = read_message(stream) {
+        fields.push(field);
+    }
+    match status.as_slice() {
+        b"ok" => Ok(fields),
+        _ => bail!(
+            "signing session: {}",
+            fields.first().map(|message| String::from_utf8_lossy(message).into_owned()).unwrap_or_default()
+        ),
+    }
+}
+
+/// Ambil field ke-`index` dari balasan agent
+fn reply_field(reply: &[Vec<u8>], index: usize) -> Result<&[u8]> {
+    reply
+        .get(index)
+        .map(Vec::as_slice)
+        .ok_or_else(|| anyhow!("incomplete reply from the signing session"))
+}
+
+/// Tulis satu pesan: panjang (4 byte big-endian) diikuti isinya
+fn write_message(writer: &mut impl Write, data: &[u8]) -> Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(data)?;
+    Ok(())
+}
+
+/// Baca satu pesan yang ditulis `write_message`
+fn read_message(reader: &mut impl Read) -> Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_MESSAGE {
+        bail!("session message too large");
+    }
+    let mut data = vec![0u8; len];
+    reader.read_exact(&mut data)?;
+    Ok(data)
+}
+
+/// Nama padding RSA di protokol agent
+fn padding_name(padding: RsaPadding) -> &'static str {
+    match padding {
+        RsaPadding::Pkcs1v15 => "pkcs1v15",
+        RsaPadding::Pss => "pss",
+    }
+}
+
+/// Kebalikan dari `padding_name`
+fn parse_padding(name: &[u8]) -> Result<RsaPadding> {
+    match name {
+        b"pkcs1v15" => Ok(RsaPadding::Pkcs1v15),
+        b"pss" => Ok(RsaPadding::Pss),
+        _ => bail!("unknown RSA padding"),
+    }
+}
+
+/// Path absolut untuk membandingkan identitas sesi dengan path di `sign`
+fn canonical(path: &str) -> Result<String> {
+    Ok(fs::canonicalize(Path::new(path))
+        .with_context(|| format!("cannot resolve {}", path))?
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Detik sejak epoch
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
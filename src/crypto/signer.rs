@@ -0,0 +1,307 @@
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+#[cfg(feature = "sign")]
+use anyhow::{anyhow, Context}; // Error saat membaca file kunci
+#[cfg(feature = "sign")]
+use std::fs; // Untuk membaca file kunci
+#[cfg(feature = "sign")]
+use std::path::Path; // Path file kunci
+use x509_cert::der::asn1::ObjectIdentifier; // OID algoritma
+#[cfg(feature = "sign")]
+use x509_cert::der::asn1::BitString; // Kunci publik di SubjectPublicKeyInfo
+use x509_cert::der::{Decode, DecodePem}; // Parsing DER/PEM
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+
+// OID kunci publik EC (juga dipakai sebagai algoritma signature oleh blob lama)
+const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::ecc::{self, Curve}; // ECDSA P-256/P-384/P-521 dan Ed25519
+#[cfg(feature = "sign")]
+use crate::crypto::keyfile; // PKCS#8 terenkripsi
+#[cfg(feature = "sign")]
+use crate::crypto::provider; // Provider kriptografi yang terpasang
+use crate::crypto::rsa::RsaPublic; // Kunci publik RSA
+use crate::crypto::rsa::RsaPadding; // Padding signature RSA
+#[cfg(feature = "sign")]
+use crate::crypto::rsa::RsaSigner; // Signer RSA
+
+/// Backend penandatanganan: menandatangani digest dan menjelaskan dirinya
+/// (algoritma dan kunci publik) untuk CMS dan sertifikat
+///
+/// Implementasi saat ini: `EcdsaSigner`, `Ed25519Signer`, dan `RsaSigner`.
+/// Signer dipakai bersama oleh thread `sign-batch`, sehingga harus `Send + Sync`.
+pub trait Signer: Send + Sync {
+    /// Tandatangani digest (hasil `algorithm`) dan kembalikan nilai signature
+    /// dalam format yang diharapkan CMS (DER untuk ECDSA, raw untuk RSA)
+    fn sign_digest(&self, digest: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>>;
+
+    /// Tandatangani pesan utuh (signed attributes CMS, TBSCertificate)
+    ///
+    /// Default: digest pesan dengan `algorithm` lalu `sign_digest`; Ed25519
+    /// menandatangani pesannya langsung.
+    fn sign_message(&self, message: &[u8], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        self.sign_digest(&algorithm.digest(message), algorithm)
+    }
+
+    /// Digest yang benar-benar dipakai jika user memilih `requested`: kunci
+    /// Ed25519 selalu memakai SHA-512 (RFC 8419), kunci lain mengikuti pilihan user
+    fn digest_for(&self, requested: DigestAlgorithm) -> DigestAlgorithm {
+        match self.public_key_info() {
+            Ok(spki) if ecc::is_ed25519(&spki.algorithm.oid) => DigestAlgorithm::Sha512,
+            _ => requested,
+        }
+    }
+
+    /// AlgorithmIdentifier signature dengan digest `digest` (signatureAlgorithm
+    /// di CMS dan sertifikat)
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned>;
+
+    /// SubjectPublicKeyInfo dari kunci publik pasangannya
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned>;
+
+    /// Deskripsi singkat untuk ditampilkan ke user, misalnya "ECDSA P-256"
+    fn description(&self) -> String;
+}
+
+/// Kunci privat beserta sertifikat DER-nya (penandatangan dulu, lalu rantainya)
+#[cfg(feature = "sign")]
+pub type Credentials = (Box<dyn Signer>, Vec<Vec<u8>>);
+
+/// Signer ECDSA (P-256, P-384, P-521) dari skalar privat raw (format private.key)
+#[cfg(feature = "sign")]
+pub struct EcdsaSigner {
+    curve: Curve,         // Kurva kunci
+    private_key: Vec<u8>, // Skalar privat (32, 48, atau 66 bytes)
+}
+
+#[cfg(feature = "sign")]
+impl EcdsaSigner {
+    /// Buat signer dari skalar privat raw
+    pub fn new(curve: Curve, private_key: &[u8]) -> Result<EcdsaSigner> {
+        if curve == Curve::Ed25519 {
+            bail!("Ed25519 keys need Ed25519Signer");
+        }
+        // Validasi kunci sekarang agar error muncul saat loading, bukan saat signing
+        ecc::public_key_from_private(curve, private_key)?;
+        Ok(EcdsaSigner { curve, private_key: private_key.to_vec() })
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Signer for EcdsaSigner {
+    fn sign_digest(&self, digest: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        ecc::sign_digest(self.curve, digest, &self.private_key)
+    }
+
+    fn signature_algorithm(&self, digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        Ok(ecdsa_with(digest))
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        let public_key = ecc::public_key_from_private(self.curve, &self.private_key)?;
+        Ok(SubjectPublicKeyInfoOwned {
+            algorithm: self.curve.spki_algorithm()?,
+            subject_public_key: BitString::from_bytes(&public_key).map_err(|e| anyhow!("{}", e))?,
+        })
+    }
+
+    fn description(&self) -> String {
+        self.curve.label().to_string()
+    }
+}
+
+/// Signer Ed25519 dari seed 32 bytes; menandatangani pesan utuh (PureEdDSA)
+#[cfg(feature = "sign")]
+pub struct Ed25519Signer {
+    seed: Vec<u8>, // Seed kunci privat 32 bytes
+}
+
+#[cfg(feature = "sign")]
+impl Ed25519Signer {
+    /// Buat signer dari seed 32 bytes
+    pub fn new(seed: &[u8]) -> Result<Ed25519Signer> {
+        ecc::public_key_from_private(Curve::Ed25519, seed)?;
+        Ok(Ed25519Signer { seed: seed.to_vec() })
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Signer for Ed25519Signer {
+    fn sign_digest(&self, _digest: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        bail!("Ed25519 keys sign the message itself and cannot sign a precomputed digest")
+    }
+
+    fn sign_message(&self, message: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        ecc::ed25519_sign(message, &self.seed)
+    }
+
+    fn signature_algorithm(&self, _digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        Ok(ecc::ed25519_algorithm())
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        let public_key = ecc::public_key_from_private(Curve::Ed25519, &self.seed)?;
+        Ok(SubjectPublicKeyInfoOwned {
+            algorithm: Curve::Ed25519.spki_algorithm()?,
+            subject_public_key: BitString::from_bytes(&public_key).map_err(|e| anyhow!("{}", e))?,
+        })
+    }
+
+    fn description(&self) -> String {
+        Curve::Ed25519.label().to_string()
+    }
+}
+
+/// Baca file kunci privat dan deteksi jenisnya secara otomatis
+///
+/// Parameter:
+///   - path: path file kunci privat
+///   - rsa_padding: skema padding jika kuncinya ternyata RSA
+///   - passphrase: passphrase untuk kunci PKCS#8 terenkripsi; jika None dan
+///     kuncinya terenkripsi, user diminta mengetiknya di terminal
+#[cfg(feature = "sign")]
+pub fn load_signer(path: impl AsRef<Path>, rsa_padding: RsaPadding, passphrase: Option<&str>) -> Result<Box<dyn Signer>> {
+    let path = path.as_ref().display().to_string();
+    let bytes = fs::read(&path).with_context(|| format!("cannot read private key {}", path))?;
+    let bytes = if keyfile::is_encrypted(&bytes) {
+        keyfile::decrypt_private_key(&bytes, passphrase, &path).with_context(|| format!("cannot load private key {}", path))?
+    } else {
+        bytes
+    };
+    signer_from_bytes(&bytes, rsa_padding).with_context(|| format!("cannot load private key {}", path))
+}
+
+/// Deteksi jenis kunci (yang tidak terenkripsi) dari isinya:
+/// - 32/48/66 bytes raw = ECDSA P-256/P-384/P-521 (format raw generate-key)
+/// - PKCS#8 atau SEC1 dengan kurva P-256/P-384/P-521, DER atau PEM = ECDSA
+/// - PKCS#8 Ed25519, DER atau PEM = Ed25519
+/// - PKCS#1 / PKCS#8, DER atau PEM = RSA
+///
+/// Provider kriptografi yang terpasang (`provider::set_provider`) mendapat
+/// kesempatan pertama untuk membaca kuncinya.
+#[cfg(feature = "sign")]
+pub fn signer_from_bytes(bytes: &[u8], rsa_padding: RsaPadding) -> Result<Box<dyn Signer>> {
+    if let Some(signer) = provider::provider().and_then(|provider| provider.signer_from_bytes(bytes, rsa_padding)) {
+        return signer;
+    }
+    if let Some(curve) = Curve::from_raw_private_len(bytes.len()) {
+        return Ok(Box::new(EcdsaSigner::new(curve, bytes)?));
+    }
+    match ecc::private_key_from_encoded(bytes) {
+        Some((Curve::Ed25519, seed)) => return Ok(Box::new(Ed25519Signer::new(&seed)?)),
+        Some((curve, private_key)) => return Ok(Box::new(EcdsaSigner::new(curve, &private_key)?)),
+        None => {}
+    }
+    if let Some(signer) = RsaSigner::from_bytes(bytes, rsa_padding)? {
+        return Ok(Box::new(signer));
+    }
+    bail!("unsupported private key format (expected a raw ECDSA key, a P-256/P-384/P-521 or Ed25519 key in PKCS#8/SEC1, or an RSA key in PKCS#1/PKCS#8, DER or PEM)")
+}
+
+/// Kunci publik untuk verifikasi signature
+pub enum PublicKey {
+    /// Titik ECDSA dalam format SEC1 (seperti public.key)
+    Ecdsa(Curve, Vec<u8>),
+    /// Kunci publik Ed25519 32 bytes
+    Ed25519(Vec<u8>),
+    /// Kunci publik RSA
+    Rsa(RsaPublic),
+}
+
+impl PublicKey {
+    /// Baca kunci publik dari file: SubjectPublicKeyInfo (DER atau PEM),
+    /// titik SEC1 raw (public.key format raw) atau RSA PKCS#1
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey> {
+        let spki = match std::str::from_utf8(bytes) {
+            Ok(text) if text.contains("-----BEGIN PUBLIC KEY-----") => {
+                SubjectPublicKeyInfoOwned::from_pem(text.as_bytes()).ok()
+            }
+            _ => SubjectPublicKeyInfoOwned::from_der(bytes).ok(),
+        };
+        if let Some(spki) = spki {
+            return PublicKey::from_spki(&spki);
+        }
+        if let Some(rsa) = RsaPublic::from_bytes(bytes) {
+            return Ok(PublicKey::Rsa(rsa));
+        }
+        Ok(match Curve::from_public_len(bytes.len()) {
+            Some(Curve::Ed25519) => PublicKey::Ed25519(bytes.to_vec()),
+            curve => PublicKey::Ecdsa(curve.unwrap_or_default(), bytes.to_vec()),
+        })
+    }
+
+    /// Ambil kunci publik dari SubjectPublicKeyInfo (misalnya dari sertifikat)
+    pub fn from_spki(spki: &SubjectPublicKeyInfoOwned) -> Result<PublicKey> {
+        let point = spki.subject_public_key.raw_bytes().to_vec();
+        match Curve::from_spki_algorithm(&spki.algorithm)? {
+            Some(Curve::Ed25519) => Ok(PublicKey::Ed25519(point)),
+            Some(curve) => Ok(PublicKey::Ecdsa(curve, point)),
+            None => Ok(PublicKey::Rsa(RsaPublic::from_spki(spki)?)),
+        }
+    }
+
+    /// Verifikasi signature atas digest sesuai algoritma signature
+    /// Return: true jika signature valid
+    pub fn verify(&self, algorithm: &AlgorithmIdentifierOwned, digest: &[u8], signature: &[u8]) -> Result<bool> {
+        match self {
+            PublicKey::Ecdsa(curve, point) => {
+                let ecdsa = DigestAlgorithm::ALL.into_iter().any(|digest| digest.ecdsa_oid() == algorithm.oid);
+                if !ecdsa && algorithm.oid != ID_EC_PUBLIC_KEY {
+                    bail!("signature algorithm {} does not match the ECDSA key", algorithm.oid);
+                }
+                ecc::verify_digest(*curve, digest, signature, point)
+            }
+            PublicKey::Ed25519(_) => bail!("Ed25519 signatures cover the message itself, not a digest"),
+            PublicKey::Rsa(key) => key.verify(algorithm, digest, signature),
+        }
+    }
+
+    /// Verifikasi signature atas pesan utuh (signed attributes, TBSCertificate)
+    ///
+    /// Ed25519 memverifikasi pesannya langsung; algoritma lain memverifikasi
+    /// digest pesan dengan `digest`.
+    pub fn verify_message(
+        &self,
+        algorithm: &AlgorithmIdentifierOwned,
+        digest: DigestAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<bool> {
+        match self {
+            PublicKey::Ed25519(key) => {
+                if !ecc::is_ed25519(&algorithm.oid) {
+                    bail!("signature algorithm {} does not match the Ed25519 key", algorithm.oid);
+                }
+                ecc::ed25519_verify(message, signature, key)
+            }
+            _ => self.verify(algorithm, &digest.digest(message), signature),
+        }
+    }
+}
+
+/// AlgorithmIdentifier ECDSA P-256 dengan SHA-256 (untuk signature lama tanpa CMS)
+pub fn ecdsa_with_sha256() -> AlgorithmIdentifierOwned {
+    ecdsa_with(DigestAlgorithm::Sha256)
+}
+
+/// AlgorithmIdentifier ECDSA dengan digest tertentu (tanpa parameter, RFC 5758)
+pub fn ecdsa_with(digest: DigestAlgorithm) -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned { oid: digest.ecdsa_oid(), parameters: None }
+}
+
+/// AlgorithmIdentifier signature untuk kunci publik `spki` dengan digest tertentu
+/// (ECDSA, Ed25519, atau RSA dengan `rsa_padding`); untuk signer yang kuncinya ada di tempat lain
+pub fn signature_algorithm_for(
+    spki: &SubjectPublicKeyInfoOwned,
+    rsa_padding: RsaPadding,
+    digest: DigestAlgorithm,
+) -> Result<AlgorithmIdentifierOwned> {
+    if ecc::is_ed25519(&spki.algorithm.oid) {
+        return Ok(ecc::ed25519_algorithm());
+    }
+    if spki.algorithm.oid == ID_EC_PUBLIC_KEY {
+        return Ok(ecdsa_with(digest));
+    }
+    rsa_padding.signature_algorithm(digest)
+}
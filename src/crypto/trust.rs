@@ -0,0 +1,427 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs; // Membaca bundle sertifikat
+use std::path::{Path, PathBuf}; // Lokasi daftar trust bernama
+use std::time::SystemTime; // Waktu penandatanganan untuk masa berlaku sertifikat
+use x509_cert::der::asn1::ObjectIdentifier; // OID extended key usage
+use x509_cert::der::{Decode, DecodePem, Encode}; // Parsing dan encoding DER/PEM
+use x509_cert::ext::pkix::{BasicConstraints, ExtendedKeyUsage, KeyUsage}; // Extension yang diperiksa di rantai
+use x509_cert::spki::AlgorithmIdentifierOwned; // Algoritma signature sertifikat, respons OCSP, dan CRL
+use x509_cert::Certificate; // Sertifikat X.509
+
+use crate::crypto::cms; // Bundle sertifikat DER
+use crate::crypto::digest::DigestAlgorithm; // Digest TBSCertificate untuk cek signature sertifikat
+use crate::crypto::signer::PublicKey; // Verifikasi signature sertifikat
+
+// Extended key usage yang mengizinkan tanda tangan dokumen
+const EKU_DOCUMENT_SIGNING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.36"); // id-kp-documentSigning (RFC 9336)
+const EKU_EMAIL_PROTECTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.4"); // S/MIME, umum di sertifikat eID
+const EKU_ADOBE_AUTHENTIC_DOCUMENTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113583.1.1.5"); // Adobe Authentic Documents Trust
+const EKU_MS_DOCUMENT_SIGNING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.10.3.12"); // Microsoft Document Signing
+const EKU_ANY: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37.0"); // anyExtendedKeyUsage
+// Extended key usage sertifikat TSA (RFC 3161: wajib ada)
+const EKU_TIME_STAMPING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.8");
+
+/// Lokasi bundle root sistem yang umum (Debian/Ubuntu, RHEL/Fedora, SUSE,
+/// Alpine/macOS/BSD); SSL_CERT_FILE diperiksa lebih dulu
+const SYSTEM_BUNDLES: [&str; 4] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+];
+
+/// Satu sumber trust anchor (misalnya AATL, EUTL, atau file root perusahaan)
+///
+/// Daftar resmi (AATL dari Adobe, EUTL dari Uni Eropa, daftar GOST, ...) harus
+/// diekspor dulu menjadi bundle PEM/DER; pdfsign tidak mengunduhnya sendiri.
+pub struct TrustSource {
+    pub label: String,            // Nama sumber di laporan: "aatl", "file:corp-roots.pem"
+    anchors: Vec<Certificate>,    // Sertifikat trust anchor
+}
+
+/// Trust anchor yang menjadi ujung rantai sebuah signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustAnchor {
+    pub source: String,  // Label sumber yang memuat anchor ini
+    pub subject: String, // Subject sertifikat anchor
+}
+
+impl TrustSource {
+    /// Baca daftar sumber dari spesifikasi dipisah koma, misalnya
+    /// "aatl,eutl,file:corp-roots.pem,store:/etc/pdfsign/roots"
+    ///
+    /// Parameter:
+    ///   - specs: `system`, `file:<path>`, `store:<dir>`, atau nama daftar
+    ///     (bundle `<nama>.pem` di `trust_dir`)
+    ///   - trust_dir: direktori daftar bernama
+    pub fn load_all(specs: &[String], trust_dir: &Path) -> Result<Vec<TrustSource>> {
+        let mut sources: Vec<TrustSource> = Vec::new();
+        for spec in specs {
+            let source = TrustSource::load(spec.trim(), trust_dir)?;
+            if sources.iter().any(|existing| existing.label == source.label) {
+                bail!("trust source {} is listed twice", source.label);
+            }
+            sources.push(source);
+        }
+        Ok(sources)
+    }
+
+    /// Baca satu sumber: `file:<path>`, `store:<dir>` (lihat `load_store`),
+    /// `system` (root CA sistem operasi), atau nama daftar yang dicari sebagai
+    /// `<trust_dir>/<nama>.pem`
+    pub fn load(spec: &str, trust_dir: &Path) -> Result<TrustSource> {
+        if spec.eq_ignore_ascii_case("system") {
+            return TrustSource::system();
+        }
+        match spec.strip_prefix("store:") {
+            Some(dir) if !dir.is_empty() => return TrustSource::load_store(Path::new(dir)),
+            Some(_) => bail!("trust source `store:` needs a directory"),
+            None => {}
+        }
+        let (label, path) = match spec.strip_prefix("file:") {
+            Some(path) if !path.is_empty() => (spec.to_string(), PathBuf::from(path)),
+            Some(_) => bail!("trust source `file:` needs a path"),
+            None if !spec.is_empty() && spec.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => {
+                (spec.to_ascii_lowercase(), trust_dir.join(format!("{}.pem", spec.to_ascii_lowercase())))
+            }
+            None => bail!("unknown trust source `{}` (use system, file:<path>, store:<dir>, or a list name such as aatl or eutl)", spec),
+        };
+        let bytes = fs::read(&path).with_context(|| format!("cannot read trust source {} ({})", label, path.display()))?;
+        let anchors = parse_bundle(&bytes).with_context(|| format!("invalid trust source {} ({})", label, path.display()))?;
+        if anchors.is_empty() {
+            bail!("trust source {} ({}) contains no certificates", label, path.display());
+        }
+        Ok(TrustSource { label, anchors })
+    }
+
+    /// Baca semua sertifikat di satu direktori (--trust store:<dir>): setiap file
+    /// .pem, .crt, .cer, atau .der berisi satu atau beberapa sertifikat
+    pub fn load_store(dir: &Path) -> Result<TrustSource> {
+        let label = format!("store:{}", dir.display());
+        let entries = fs::read_dir(dir).with_context(|| format!("cannot read trust store {}", dir.display()))?;
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_ascii_lowercase();
+                path.is_file() && matches!(extension.as_str(), "pem" | "crt" | "cer" | "der")
+            })
+            .collect();
+        paths.sort();
+        let mut anchors = Vec::new();
+        for path in &paths {
+            let bytes = fs::read(path).with_context(|| format!("cannot read {}", path.display()))?;
+            anchors.extend(parse_bundle(&bytes).with_context(|| format!("invalid certificate in trust store {}", path.display()))?);
+        }
+        if anchors.is_empty() {
+            bail!("trust store {} contains no certificates (.pem, .crt, .cer or .der files)", dir.display());
+        }
+        Ok(TrustSource { label, anchors })
+    }
+
+    /// Root CA sistem operasi: bundle dari SSL_CERT_FILE atau lokasi yang umum
+    ///
+    /// Sertifikat di bundle yang tidak bisa dibaca dilewati: bundle sistem
+    /// berisi ratusan root dan satu yang tidak dikenali tidak boleh membuat
+    /// seluruh sumber gagal.
+    pub fn system() -> Result<TrustSource> {
+        let from_env = std::env::var_os("SSL_CERT_FILE").map(PathBuf::from);
+        let path = from_env
+            .into_iter()
+            .chain(SYSTEM_BUNDLES.iter().map(PathBuf::from))
+            .find(|path| path.is_file())
+            .ok_or_else(|| anyhow!("no system root certificates found (set SSL_CERT_FILE or use --trust store:<dir>)"))?;
+        let bytes = fs::read(&path).with_context(|| format!("cannot read system roots {}", path.display()))?;
+        let anchors = parse_pem_lenient(&bytes);
+        if anchors.is_empty() {
+            bail!("system roots {} contain no certificates", path.display());
+        }
+        Ok(TrustSource { label: "system".to_string(), anchors })
+    }
+}
+
+/// Tujuan sertifikat penandatangan, untuk pemeriksaan extended key usage
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Purpose {
+    /// Tanda tangan dokumen (approval / certification signature)
+    DocumentSigning,
+    /// Timestamp RFC 3161 (document timestamp)
+    Timestamping,
+}
+
+/// Satu masalah pada rantai sertifikat penandatangan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainIssue {
+    /// Sertifikat issuer tidak tertanam di signature maupun ada di sumber trust
+    MissingIssuer { subject: String, issuer: String },
+    /// Sertifikat dengan nama issuer yang cocok ada, tetapi tidak menandatangani `subject`
+    BadSignature { subject: String },
+    /// Sertifikat belum berlaku pada waktu penandatanganan
+    NotYetValid { subject: String, not_before: SystemTime },
+    /// Sertifikat sudah habis masa berlakunya pada waktu penandatanganan
+    Expired { subject: String, not_after: SystemTime },
+    /// keyUsage penandatangan tidak berisi digitalSignature maupun nonRepudiation
+    KeyUsage { subject: String },
+    /// extendedKeyUsage penandatangan tidak mencakup tujuan signature
+    ExtendedKeyUsage { subject: String, purpose: Purpose },
+    /// Sertifikat yang menerbitkan sertifikat lain bukan CA (basicConstraints, keyCertSign)
+    NotCa { subject: String },
+}
+
+/// Validasi rantai sertifikat penandatangan
+///
+/// Rantai dibangun dari sertifikat penandatangan ke atas memakai sertifikat
+/// yang tertanam di signature dan anchor dari `sources`, sampai root
+/// self-signed atau trust anchor. Untuk setiap sertifikat dari signature
+/// diperiksa: signature issuer-nya, masa berlaku pada `at`, dan peran CA untuk
+/// sertifikat yang menerbitkan sertifikat lain; untuk penandatangan juga
+/// keyUsage dan extendedKeyUsage sesuai `purpose`. Anchor dari sumber trust
+/// tidak diperiksa (sumber itu sendiri yang dipercaya).
+///
+/// Parameter:
+///   - sources: sumber trust (boleh kosong)
+///   - signer: sertifikat penandatangan (DER)
+///   - certificates: sertifikat lain yang tertanam di signature (DER)
+///   - at: waktu penandatanganan (timestamp, atau /M jika tidak ada); None =
+///     masa berlaku tidak diperiksa
+///   - purpose: tujuan signature
+///
+/// Return: masalah yang ditemukan, dari penandatangan ke atas
+pub fn validate_chain(sources: &[TrustSource], signer: &[u8], certificates: &[Vec<u8>], at: Option<SystemTime>, purpose: Purpose) -> Vec<ChainIssue> {
+    let mut issues = Vec::new();
+    let Ok(leaf) = Certificate::from_der(signer) else {
+        return issues;
+    };
+    let embedded: Vec<Certificate> = certificates.iter().filter_map(|der| Certificate::from_der(der).ok()).collect();
+    let anchors: Vec<&Certificate> = sources.iter().flat_map(|source| &source.anchors).collect();
+
+    check_signer_usage(&leaf, purpose, &mut issues);
+    let mut current = leaf;
+    // Batasi panjang rantai untuk menghindari loop pada sertifikat yang saling menandatangani
+    for _ in 0..16 {
+        if anchors.contains(&&current) {
+            break;
+        }
+        if let Some(at) = at {
+            check_validity(&current, at, &mut issues);
+        }
+        let subject = current.tbs_certificate.subject.to_string();
+        // Root self-signed: ujung rantai
+        if current.tbs_certificate.subject == current.tbs_certificate.issuer {
+            break;
+        }
+        let named: Vec<&Certificate> = anchors
+            .iter()
+            .copied()
+            .chain(embedded.iter())
+            .filter(|issuer| issuer.tbs_certificate.subject == current.tbs_certificate.issuer)
+            .collect();
+        let Some(issuer) = named.iter().copied().find(|issuer| is_signed_by(&current, issuer)) else {
+            issues.push(match named.is_empty() {
+                true => ChainIssue::MissingIssuer { subject, issuer: current.tbs_certificate.issuer.to_string() },
+                false => ChainIssue::BadSignature { subject },
+            });
+            break;
+        };
+        if anchors.contains(&issuer) {
+            break;
+        }
+        if !is_ca(issuer) {
+            issues.push(ChainIssue::NotCa { subject: issuer.tbs_certificate.subject.to_string() });
+        }
+        current = issuer.clone();
+    }
+    issues
+}
+
+/// Direktori default daftar trust bernama: ~/.pdfsign/trust
+pub fn default_trust_dir() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    home.join(".pdfsign").join("trust")
+}
+
+/// Cari sumber trust yang menjadi ujung rantai sertifikat penandatangan
+///
+/// Rantai dibangun dari sertifikat penandatangan ke atas memakai sertifikat
+/// yang tertanam di signature (issuer = subject, signature sertifikat valid).
+/// Sebuah sumber menjadi anchor jika salah satu sertifikat di rantai ada di
+/// sumber tersebut, atau ditandatangani langsung oleh sertifikat di sumber
+/// tersebut. Satu rantai bisa di-anchor oleh beberapa sumber sekaligus.
+///
+/// Parameter:
+///   - sources: sumber trust yang dipilih
+///   - signer: sertifikat penandatangan (DER)
+///   - certificates: sertifikat lain yang tertanam di signature (DER)
+///
+/// Return: anchor per sumber (tanpa duplikat), urut seperti `sources`
+pub fn anchors_for(sources: &[TrustSource], signer: &[u8], certificates: &[Vec<u8>]) -> Vec<TrustAnchor> {
+    let mut found: Vec<TrustAnchor> = Vec::new();
+    let Ok(mut current) = Certificate::from_der(signer) else {
+        return found;
+    };
+    let embedded: Vec<Certificate> = certificates.iter().filter_map(|der| Certificate::from_der(der).ok()).collect();
+
+    // Batasi panjang rantai untuk menghindari loop pada sertifikat yang saling menandatangani
+    for _ in 0..16 {
+        for source in sources {
+            for anchor in &source.anchors {
+                let anchored = *anchor == current
+                    || (anchor.tbs_certificate.subject == current.tbs_certificate.issuer && is_signed_by(&current, anchor));
+                let entry = TrustAnchor { source: source.label.clone(), subject: anchor.tbs_certificate.subject.to_string() };
+                if anchored && !found.iter().any(|existing| existing.source == entry.source) {
+                    found.push(entry);
+                }
+            }
+        }
+        // Naik ke issuer dari sertifikat yang tertanam (berhenti di root self-signed)
+        if current.tbs_certificate.subject == current.tbs_certificate.issuer {
+            break;
+        }
+        match embedded
+            .iter()
+            .find(|issuer| issuer.tbs_certificate.subject == current.tbs_certificate.issuer && is_signed_by(&current, issuer))
+        {
+            Some(issuer) => current = issuer.clone(),
+            None => break,
+        }
+    }
+    found.sort_by_key(|anchor| sources.iter().position(|source| source.label == anchor.source));
+    found
+}
+
+/// Sertifikat di rantai penandatangan beserta issuer-nya, untuk pemeriksaan revocation
+///
+/// Rantai dibangun seperti `validate_chain` dan berhenti di root self-signed
+/// atau trust anchor; keduanya tidak ikut dikembalikan karena status
+/// revocation-nya tidak diperiksa. Issuer None jika tidak ditemukan.
+///
+/// Parameter:
+///   - sources: sumber trust (boleh kosong)
+///   - signer: sertifikat penandatangan (DER)
+///   - certificates: sertifikat lain yang tertanam di signature atau /DSS (DER)
+///
+/// Return: pasangan (sertifikat, issuer), dari penandatangan ke atas
+pub fn issuer_chain(sources: &[TrustSource], signer: &[u8], certificates: &[Vec<u8>]) -> Vec<(Certificate, Option<Certificate>)> {
+    let mut chain = Vec::new();
+    let Ok(mut current) = Certificate::from_der(signer) else {
+        return chain;
+    };
+    let embedded: Vec<Certificate> = certificates.iter().filter_map(|der| Certificate::from_der(der).ok()).collect();
+    let anchors: Vec<&Certificate> = sources.iter().flat_map(|source| &source.anchors).collect();
+
+    // Batasi panjang rantai untuk menghindari loop pada sertifikat yang saling menandatangani
+    for _ in 0..16 {
+        if anchors.contains(&&current) || current.tbs_certificate.subject == current.tbs_certificate.issuer {
+            break;
+        }
+        let issuer = anchors
+            .iter()
+            .copied()
+            .chain(embedded.iter())
+            .find(|issuer| issuer.tbs_certificate.subject == current.tbs_certificate.issuer && is_signed_by(&current, issuer))
+            .cloned();
+        chain.push((current, issuer.clone()));
+        match issuer {
+            Some(issuer) => current = issuer,
+            None => break,
+        }
+    }
+    chain
+}
+
+/// Apakah `cert` ditandatangani dengan kunci `issuer`
+/// (ECDSA/RSA berbasis SHA-2, atau Ed25519)
+fn is_signed_by(cert: &Certificate, issuer: &Certificate) -> bool {
+    match (cert.tbs_certificate.to_der(), cert.signature.as_bytes()) {
+        (Ok(tbs), Some(signature)) => signed_with(issuer, &cert.signature_algorithm, &tbs, signature),
+        _ => false,
+    }
+}
+
+/// Apakah `cert` diterbitkan `issuer` (nama issuer cocok dan signature valid)
+pub(crate) fn issued_by(cert: &Certificate, issuer: &Certificate) -> bool {
+    cert.tbs_certificate.issuer == issuer.tbs_certificate.subject && is_signed_by(cert, issuer)
+}
+
+/// Apakah `signature` atas `message` dibuat dengan kunci sertifikat `signer`
+/// (dipakai juga untuk respons OCSP dan CRL)
+pub(crate) fn signed_with(signer: &Certificate, algorithm: &AlgorithmIdentifierOwned, message: &[u8], signature: &[u8]) -> bool {
+    let check = || -> Result<bool> {
+        let key = PublicKey::from_spki(&signer.tbs_certificate.subject_public_key_info)?;
+        let digest = DigestAlgorithm::from_signature_oid(&algorithm.oid).unwrap_or_default();
+        key.verify_message(algorithm, digest, message, signature)
+    };
+    check().unwrap_or(false)
+}
+
+/// Periksa masa berlaku sertifikat pada waktu `at`
+fn check_validity(cert: &Certificate, at: SystemTime, issues: &mut Vec<ChainIssue>) {
+    let validity = &cert.tbs_certificate.validity;
+    let subject = cert.tbs_certificate.subject.to_string();
+    if at < validity.not_before.to_system_time() {
+        issues.push(ChainIssue::NotYetValid { subject, not_before: validity.not_before.to_system_time() });
+    } else if at > validity.not_after.to_system_time() {
+        issues.push(ChainIssue::Expired { subject, not_after: validity.not_after.to_system_time() });
+    }
+}
+
+/// Periksa keyUsage dan extendedKeyUsage sertifikat penandatangan
+///
+/// keyUsage yang ada harus berisi digitalSignature atau nonRepudiation.
+/// extendedKeyUsage yang ada harus mencakup tanda tangan dokumen (documentSigning,
+/// emailProtection, Adobe Authentic Documents, Microsoft Document Signing, atau
+/// anyExtendedKeyUsage); sertifikat TSA wajib punya timeStamping (RFC 3161).
+fn check_signer_usage(cert: &Certificate, purpose: Purpose, issues: &mut Vec<ChainIssue>) {
+    let subject = cert.tbs_certificate.subject.to_string();
+    if let Ok(Some((_, usage))) = cert.tbs_certificate.get::<KeyUsage>() {
+        if !usage.digital_signature() && !usage.non_repudiation() {
+            issues.push(ChainIssue::KeyUsage { subject: subject.clone() });
+        }
+    }
+    let extended = cert.tbs_certificate.get::<ExtendedKeyUsage>().ok().flatten().map(|(_, usage)| usage.0);
+    let allowed = match (purpose, extended) {
+        (Purpose::DocumentSigning, None) => true,
+        (Purpose::DocumentSigning, Some(usages)) => usages.iter().any(|oid| {
+            [EKU_DOCUMENT_SIGNING, EKU_EMAIL_PROTECTION, EKU_ADOBE_AUTHENTIC_DOCUMENTS, EKU_MS_DOCUMENT_SIGNING, EKU_ANY].contains(oid)
+        }),
+        (Purpose::Timestamping, usages) => usages.is_some_and(|usages| usages.contains(&EKU_TIME_STAMPING)),
+    };
+    if !allowed {
+        issues.push(ChainIssue::ExtendedKeyUsage { subject, purpose });
+    }
+}
+
+/// Apakah sertifikat boleh menerbitkan sertifikat lain: basicConstraints cA,
+/// dan keyCertSign jika keyUsage ada
+fn is_ca(cert: &Certificate) -> bool {
+    let ca = matches!(cert.tbs_certificate.get::<BasicConstraints>(), Ok(Some((_, constraints))) if constraints.ca);
+    let cert_sign = match cert.tbs_certificate.get::<KeyUsage>() {
+        Ok(Some((_, usage))) => usage.key_cert_sign(),
+        _ => true,
+    };
+    ca && cert_sign
+}
+
+/// Sertifikat dari setiap blok CERTIFICATE di teks PEM (blok yang rusak dilewati)
+fn parse_pem_lenient(bytes: &[u8]) -> Vec<Certificate> {
+    let text = String::from_utf8_lossy(bytes);
+    text.split("-----BEGIN CERTIFICATE-----")
+        .skip(1)
+        .filter_map(|block| {
+            let block = block.split("-----END CERTIFICATE-----").next()?;
+            let pem = format!("-----BEGIN CERTIFICATE-----{}-----END CERTIFICATE-----\n", block);
+            Certificate::from_pem(pem.as_bytes()).ok()
+        })
+        .collect()
+}
+
+/// Baca bundle sertifikat: PEM (satu atau beberapa blok) atau DER yang digabung
+fn parse_bundle(bytes: &[u8]) -> Result<Vec<Certificate>> {
+    if bytes.windows(11).any(|window| window == b"-----BEGIN ") {
+        return Certificate::load_pem_chain(bytes).map_err(|e| anyhow!("{}", e));
+    }
+    cms::split_certificates(bytes)?
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|e| anyhow!("{}", e)))
+        .collect()
+}
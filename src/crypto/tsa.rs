@@ -0,0 +1,308 @@
+// Tanpa fitur network hanya pembacaan TimeStampToken yang tersisa (untuk
+// verifikasi); struktur Tsa tetap ada untuk opsi signature
+#![cfg_attr(not(feature = "network"), allow(unused_imports))]
+
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use cms::content_info::ContentInfo; // TimeStampToken ::= ContentInfo
+use cms::content_info::CmsVersion; // Versi SignedData
+use cms::signed_data::EncapsulatedContentInfo; // TSTInfo di dalam TimeStampToken
+use x509_cert::der::asn1::{Any, Int, ObjectIdentifier, OctetString}; // Tipe ASN.1
+use x509_cert::der::{Decode, Encode, Reader, SliceReader, Tag, TagNumber, Tagged}; // Encoding/decoding DER
+use x509_cert::spki::AlgorithmIdentifier; // Identifier algoritma hash
+use x509_tsp::{MessageImprint, TimeStampReq, TimeStampResp, TspVersion, TstInfo}; // Struktur RFC 3161
+
+#[cfg(feature = "network")]
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::digest::DigestAlgorithm; // Hash message imprint
+#[cfg(feature = "network")]
+use crate::crypto::http; // Request HTTP ke TSA
+#[cfg(feature = "network")]
+use crate::crypto::policy::AlgorithmPolicy; // Deny-list untuk digest token TSA
+#[cfg(feature = "network")]
+use crate::crypto::provider; // Nonce acak
+
+// OID yang dipakai di RFC 3161
+#[cfg(feature = "network")]
+const ID_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+#[cfg(feature = "network")]
+const ID_MD5: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.2.5");
+const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+
+/// Time Stamping Authority (RFC 3161) yang dipakai untuk timestamp signature
+#[cfg(feature = "sign")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tsa {
+    pub url: String,                         // URL endpoint TSA (http:// atau https://)
+    pub hash: DigestAlgorithm,               // Hash message imprint (default SHA-256), terpisah dari digest signature
+    pub credentials: Option<TsaCredentials>, // Login ke TSA; terpisah dari identitas penandatangan
+}
+
+/// Kredensial TSA yang dikirim di header Authorization setiap request
+///
+/// TSA komersial dan internal perusahaan biasanya meminta login sendiri,
+/// tidak terkait dengan kunci penandatangan (token PKCS#11, KMS).
+#[cfg(feature = "sign")]
+#[derive(Clone, PartialEq, Eq)]
+pub enum TsaCredentials {
+    /// HTTP Basic (RFC 7617)
+    Basic { username: String, password: String },
+    /// Bearer token (RFC 6750)
+    Bearer(String),
+}
+
+#[cfg(feature = "sign")]
+impl TsaCredentials {
+    /// Nilai header Authorization
+    #[cfg(feature = "network")]
+    fn authorization(&self) -> String {
+        use base64::Engine as _;
+        match self {
+            TsaCredentials::Basic { username, password } => {
+                format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password)))
+            }
+            TsaCredentials::Bearer(token) => format!("Bearer {}", token),
+        }
+    }
+}
+
+// Password dan token tidak boleh ikut tercetak di log atau pesan error
+#[cfg(feature = "sign")]
+impl std::fmt::Debug for TsaCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TsaCredentials::Basic { username, .. } => f.debug_struct("Basic").field("username", username).field("password", &"***").finish(),
+            TsaCredentials::Bearer(_) => f.debug_tuple("Bearer").field(&"***").finish(),
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Tsa {
+    /// TSA yang diakses lewat HTTP(S) POST ke URL tertentu, dengan imprint SHA-256
+    pub fn url(url: impl Into<String>) -> Tsa {
+        Tsa { url: url.into(), hash: DigestAlgorithm::Sha256, credentials: None }
+    }
+
+    /// Hash message imprint yang diminta ke TSA (--tsa-hash)
+    pub fn hash(mut self, hash: DigestAlgorithm) -> Tsa {
+        self.hash = hash;
+        self
+    }
+
+    /// Login ke TSA (--tsa-username/--tsa-password atau --tsa-token)
+    pub fn credentials(mut self, credentials: TsaCredentials) -> Tsa {
+        self.credentials = Some(credentials);
+        self
+    }
+}
+
+/// Minta TimeStampToken dari TSA
+///
+/// Imprint dihitung dengan `tsa.hash`. Jika TSA menolak request dengan hash
+/// selain SHA-256, request diulang sekali dengan SHA-256, asalkan sisa batas
+/// waktu token pembatalan masih cukup untuk satu round-trip lagi. Token yang
+/// imprint-nya memakai algoritma lain dari yang diminta (misalnya SHA-1) atau
+/// yang ditandatangani TSA dengan digest dari deny-list ditolak.
+///
+/// Parameter:
+///   - tsa: TSA yang dihubungi
+///   - imprint: menghitung hash data yang di-timestamp (nilai signature, atau
+///     bagian dokumen yang ditunjuk ByteRange untuk document timestamp) dengan
+///     algoritma yang diminta
+///   - policy: deny-list untuk digest yang dipakai TSA menandatangani token
+///   - cancel: token pembatalan; batas waktunya membatasi request HTTP
+///
+/// Return: TimeStampToken (ContentInfo berisi SignedData) dalam DER
+#[cfg(feature = "network")]
+pub fn request_timestamp(
+    tsa: &Tsa,
+    imprint: &mut dyn FnMut(DigestAlgorithm) -> Result<Vec<u8>>,
+    policy: &AlgorithmPolicy,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    let started = std::time::Instant::now();
+    let rejection = match exchange(tsa, tsa.hash, &imprint(tsa.hash)?, policy, cancel)? {
+        Ok(token) => return Ok(token),
+        Err(rejection) => rejection,
+    };
+    if tsa.hash == DigestAlgorithm::Sha256 {
+        bail!("{}", rejection);
+    }
+
+    // Banyak TSA hanya menerima SHA-256; ulangi sekali jika waktunya masih cukup
+    let round_trip = started.elapsed();
+    if cancel.remaining().is_some_and(|remaining| remaining < round_trip) {
+        bail!("{} (not enough time left before the deadline to retry with SHA-256)", rejection);
+    }
+    crate::warning!("{} for a {} message imprint; retrying with SHA-256", rejection, tsa.hash.label());
+    match exchange(tsa, DigestAlgorithm::Sha256, &imprint(DigestAlgorithm::Sha256)?, policy, cancel)? {
+        Ok(token) => Ok(token),
+        Err(rejection) => bail!("{}", rejection),
+    }
+}
+
+/// Satu request TimeStampReq ke TSA
+///
+/// Return: TimeStampToken, atau pesan penolakan jika TSA menolak request
+/// (status rejection ke atas) agar pemanggil bisa mengulang dengan hash lain
+#[cfg(feature = "network")]
+fn exchange(
+    tsa: &Tsa,
+    hash: DigestAlgorithm,
+    digest: &[u8],
+    policy: &AlgorithmPolicy,
+    cancel: &CancellationToken,
+) -> Result<std::result::Result<Vec<u8>, String>> {
+
+    // Nonce acak 64 bit (positif) agar response tidak bisa diputar ulang
+    let mut nonce = [0u8; 8];
+    provider::fill_random(&mut nonce);
+    nonce[0] &= 0x7F;
+    let nonce = Int::new(&nonce).map_err(|e| anyhow!("{}", e))?;
+
+    let request = TimeStampReq {
+        version: TspVersion::V1,
+        message_imprint: MessageImprint {
+            hash_algorithm: AlgorithmIdentifier { oid: hash.oid(), parameters: None },
+            hashed_message: OctetString::new(digest).map_err(|e| anyhow!("{}", e))?,
+        },
+        req_policy: None,
+        nonce: Some(nonce.clone()),
+        // Minta sertifikat TSA disertakan agar token bisa diverifikasi mandiri
+        cert_req: true,
+        extensions: None,
+    }
+    .to_der()
+    .map_err(|e| anyhow!("cannot encode TimeStampReq: {}", e))?;
+
+    // Kirim request; timeout mengikuti sisa waktu token pembatalan
+    crate::verbose!("tsa", "request to {} ({} bytes, {} imprint)", tsa.url, request.len(), hash.label());
+    let started = std::time::Instant::now();
+    let authorization = tsa.credentials.as_ref().map(TsaCredentials::authorization);
+    let headers: Vec<(&str, &str)> = authorization.iter().map(|value| ("Authorization", value.as_str())).collect();
+    let body = http::post_with_headers(&tsa.url, "application/timestamp-query", "application/timestamp-reply", &headers, &request, cancel)
+        .context("timestamp request failed")?;
+    crate::verbose!("tsa", "response after {} ms ({} bytes)", started.elapsed().as_millis(), body.len());
+
+    // Parse TimeStampResp dan periksa statusnya (0 = granted, 1 = grantedWithMods)
+    let response = TimeStampResp::from_der(&body).map_err(|e| anyhow!("invalid TSA response: {}", e))?;
+    let status = response.status.status as u8;
+    if status > 1 {
+        let text = response
+            .status
+            .status_string
+            .map(|texts| texts.iter().map(|t| t.as_str().to_string()).collect::<Vec<_>>().join("; "))
+            .unwrap_or_default();
+        let text = if text.is_empty() { String::new() } else { format!(": {}", text) };
+        return Ok(Err(format!("TSA rejected the request (status {}){}", status, text)));
+    }
+    let token = response
+        .time_stamp_token
+        .ok_or_else(|| anyhow!("TSA response has no TimeStampToken"))?
+        .to_der()
+        .map_err(|e| anyhow!("cannot encode TimeStampToken: {}", e))?;
+
+    // Pastikan token memang untuk data, algoritma, dan nonce yang diminta
+    let info = tst_info(&token)?;
+    let returned = &info.message_imprint.hash_algorithm.oid;
+    if [ID_SHA1, ID_MD5].contains(returned) {
+        let weak = if *returned == ID_SHA1 { "SHA-1" } else { "MD5" };
+        bail!("TSA downgraded the message imprint from {} to {}; refusing to embed a weak timestamp (use a TSA that supports {})", hash.label(), weak, hash.label());
+    }
+    if *returned != hash.oid() {
+        let name = DigestAlgorithm::from_oid(returned).map(|a| a.label().to_string()).unwrap_or_else(|| returned.to_string());
+        bail!("TSA returned a {} message imprint, but {} was requested", name, hash.label());
+    }
+    if info.message_imprint.hashed_message.as_bytes() != digest {
+        bail!("TSA returned a timestamp for different data");
+    }
+    if info.nonce.as_ref() != Some(&nonce) {
+        bail!("TSA response nonce does not match the request");
+    }
+    // Digest yang dipakai TSA untuk menandatangani token itu sendiri
+    for oid in read_token(&token)?.digest_algorithms {
+        if let Some(violation) = policy.check_digest(&oid, "digest in the TSA's token signature") {
+            bail!("TSA signed the timestamp token with a weak algorithm: {} (allow with --allow-weak {})", violation, violation.algorithm.name());
+        }
+    }
+    crate::verbose!("tsa", "TimeStampToken {} bytes, time {}", token.len(), crate::pdf::text::human_time(info.gen_time.to_system_time()));
+
+    Ok(Ok(token))
+}
+
+/// Ambil TSTInfo dari TimeStampToken (DER)
+pub fn tst_info(token_der: &[u8]) -> Result<TstInfo> {
+    let encap_content_info = read_token(token_der)?.encap_content_info;
+    if encap_content_info.econtent_type != ID_CT_TST_INFO {
+        bail!("TimeStampToken does not contain TSTInfo");
+    }
+    let content = encap_content_info
+        .econtent
+        .ok_or_else(|| anyhow!("TimeStampToken has no TSTInfo"))?;
+    let content = content
+        .decode_as::<OctetString>()
+        .map_err(|e| anyhow!("invalid TSTInfo: {}", e))?;
+    TstInfo::from_der(content.as_bytes()).map_err(|e| anyhow!("invalid TSTInfo: {}", e))
+}
+
+/// Ambil sertifikat TSA (DER, tanpa duplikat) yang disertakan di TimeStampToken
+pub fn token_certificates(token_der: &[u8]) -> Result<Vec<Vec<u8>>> {
+    Ok(read_token(token_der)?.certificates)
+}
+
+/// Bagian TimeStampToken yang dibutuhkan pdfsign
+struct TokenContents {
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    digest_algorithms: Vec<ObjectIdentifier>,    // SET digestAlgorithms (digest signature TSA)
+    encap_content_info: EncapsulatedContentInfo, // Berisi TSTInfo
+    certificates: Vec<Vec<u8>>,                  // Sertifikat DER dari SET certificates
+}
+
+/// Baca SignedData di TimeStampToken secara longgar
+///
+/// Hanya version, digestAlgorithms, encapContentInfo, dan certificates yang dibaca:
+/// beberapa TSA (termasuk OpenSSL) menulis sertifikat yang sama dua kali di SET
+/// certificates, yang ditolak decoder DER yang ketat
+fn read_token(token_der: &[u8]) -> Result<TokenContents> {
+    let token = ContentInfo::from_der(token_der).map_err(|e| anyhow!("invalid TimeStampToken: {}", e))?;
+    if token.content_type != ID_SIGNED_DATA {
+        bail!("TimeStampToken is not SignedData");
+    }
+    let invalid = |e: x509_cert::der::Error| anyhow!("invalid TimeStampToken: {}", e);
+    let mut reader = SliceReader::new(token.content.value()).map_err(invalid)?;
+    CmsVersion::decode(&mut reader).map_err(invalid)?;
+
+    // digestAlgorithms SET OF AlgorithmIdentifier; hanya OID-nya yang dibaca
+    let mut digest_algorithms = Vec::new();
+    let set = Any::decode(&mut reader).map_err(invalid)?;
+    let mut set = SliceReader::new(set.value()).map_err(invalid)?;
+    while !set.is_finished() {
+        let algorithm = Any::decode(&mut set).map_err(invalid)?;
+        let mut fields = SliceReader::new(algorithm.value()).map_err(invalid)?;
+        digest_algorithms.push(ObjectIdentifier::decode(&mut fields).map_err(invalid)?);
+    }
+    let encap_content_info = EncapsulatedContentInfo::decode(&mut reader).map_err(invalid)?;
+
+    // certificates [0] IMPLICIT CertificateSet (opsional)
+    let mut certificates = Vec::new();
+    if !reader.is_finished() {
+        let next = Any::decode(&mut reader).map_err(invalid)?;
+        if next.tag() == (Tag::ContextSpecific { constructed: true, number: TagNumber::N0 }) {
+            let mut set = SliceReader::new(next.value()).map_err(invalid)?;
+            while !set.is_finished() {
+                let cert = Any::decode(&mut set).map_err(invalid)?;
+                // Hanya sertifikat X.509 biasa (SEQUENCE); jenis lain dilewati
+                if cert.tag() == Tag::Sequence {
+                    let der = cert.to_der().map_err(invalid)?;
+                    if !certificates.contains(&der) {
+                        certificates.push(der);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(TokenContents { digest_algorithms, encap_content_info, certificates })
+}
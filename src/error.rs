@@ -0,0 +1,113 @@
+// Import library yang diperlukan
+use std::fmt; // Menampilkan pesan error
+use std::io; // Error baca/tulis
+
+/// Error yang dikembalikan API library (`PdfSigner`)
+///
+/// Pipeline internal memakai anyhow; di batas API error tersebut dipetakan ke
+/// variant yang bisa dicocokkan oleh pemanggil (lihat `Error::from_anyhow`).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Gagal membaca input atau menulis output
+    Io(io::Error),
+    /// PDF tidak bisa dibaca atau ditulis
+    Pdf(lopdf::Error),
+    /// Kunci privat tidak ada, tidak didukung, atau tidak bisa didekripsi
+    Key(String),
+    /// Sertifikat atau bundle PKCS#12 tidak valid
+    Certificate(String),
+    /// Kombinasi opsi signature tidak valid
+    InvalidOptions(String),
+    /// Operasi dibatalkan lewat CancellationToken
+    Cancelled,
+    /// Operasi melewati batas waktu CancellationToken
+    TimedOut,
+    /// Penandatanganan gagal karena sebab lain (form ditolak, TSA/OCSP, hook, ...)
+    Signing(String),
+    /// Dokumen tidak berisi signature (verify, add-ltv, remove-signature)
+    NoSignatures(String),
+}
+
+impl Error {
+    /// Petakan error anyhow dari pipeline internal ke variant yang sesuai
+    ///
+    /// Error I/O, lopdf, dan pembatalan dikenali dari isinya; sisanya menjadi
+    /// `fallback` dengan pesan lengkap (termasuk context)
+    #[cfg(feature = "sign")]
+    pub(crate) fn from_anyhow(err: anyhow::Error, fallback: fn(String) -> Error) -> Error {
+        let err = match err.downcast::<Error>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<io::Error>() {
+            Ok(err) => return Error::Io(err),
+            Err(err) => err,
+        };
+        match err.downcast::<lopdf::Error>() {
+            Ok(err) => Error::Pdf(err),
+            Err(err) => fallback(format!("{:#}", err)),
+        }
+    }
+}
+
+/// Error untuk fitur yang tidak ikut dikompilasi (lihat `[features]` di Cargo.toml)
+///
+/// Parameter:
+///   - what: nama fitur untuk user, misalnya "PKCS#11"
+///   - feature: nama fitur Cargo yang harus diaktifkan
+pub fn missing_feature(what: &str, feature: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "this pdfsign was built without {} support; rebuild with `cargo build --release --features {}`",
+        what,
+        feature
+    )
+}
+
+/// Apakah error penulisan berarti tempat penyimpanan habis: disk atau kuota
+/// penuh (ENOSPC, EDQUOT), file melewati batas ukuran (EFBIG), atau data yang
+/// tidak tertulis seluruhnya (short write, lihat `storage::FileSink`)
+pub fn is_disk_full(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded | io::ErrorKind::FileTooLarge | io::ErrorKind::WriteZero
+    )
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Pdf(err) => write!(f, "PDF error: {}", err),
+            Error::Key(message) => write!(f, "private key error: {}", message),
+            Error::Certificate(message) => write!(f, "certificate error: {}", message),
+            Error::InvalidOptions(message) => write!(f, "invalid signature options: {}", message),
+            Error::Cancelled => write!(f, "operation cancelled"),
+            Error::TimedOut => write!(f, "operation timed out"),
+            Error::Signing(message) => write!(f, "{}", message),
+            Error::NoSignatures(path) => write!(f, "no signatures found in {}", path),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Pdf(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<lopdf::Error> for Error {
+    fn from(err: lopdf::Error) -> Error {
+        Error::Pdf(err)
+    }
+}
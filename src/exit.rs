@@ -0,0 +1,170 @@
+// Exit code CLI: setiap jenis kegagalan punya kode sendiri (untuk pipeline CI)
+// Tanpa fitur verify sebagian kode (hasil verifikasi) tidak pernah dipakai
+#![cfg_attr(not(feature = "verify"), allow(dead_code))]
+use std::fmt; // Menampilkan pesan kegagalan
+
+use pdfsign::error::Error; // Error API library
+
+/// Exit code pdfsign (didokumentasikan di README, "Exit codes")
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitStatus {
+    Success,          // 0: berhasil
+    Failure,          // 1: error lain (opsi, kunci, sertifikat, TSA, ...)
+    InvalidSignature, // 3: ada signature yang tidak valid
+    Unverified,       // 4: ada signature yang tidak bisa diperiksa
+    UntrustedChain,   // 5: rantai sertifikat tidak berujung di --trust (atau tidak ada --trust dengan --require-trust)
+    Attachment,       // 6: lampiran gagal pemeriksaan integritas
+    NoSignatures,     // 7: dokumen tidak berisi signature
+    Io,               // 8: file tidak bisa dibaca atau ditulis
+    MalformedPdf,     // 9: PDF tidak bisa diurai
+    Cancelled,        // 10: dibatalkan (Ctrl-C) atau melewati --timeout
+    DiskFull,         // 11: output tidak tertulis utuh (disk/kuota penuh, short write)
+}
+
+impl ExitStatus {
+    /// Exit code proses (2 dipakai clap untuk argumen yang salah)
+    pub fn code(self) -> u8 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Failure => 1,
+            ExitStatus::InvalidSignature => 3,
+            ExitStatus::Unverified => 4,
+            ExitStatus::UntrustedChain => 5,
+            ExitStatus::Attachment => 6,
+            ExitStatus::NoSignatures => 7,
+            ExitStatus::Io => 8,
+            ExitStatus::MalformedPdf => 9,
+            ExitStatus::Cancelled => 10,
+            ExitStatus::DiskFull => 11,
+        }
+    }
+
+    /// Label untuk field "result" output JSON
+    pub fn label(self) -> &'static str {
+        match self {
+            ExitStatus::Success => "ok",
+            ExitStatus::Failure => "error",
+            ExitStatus::InvalidSignature => "invalid_signature",
+            ExitStatus::Unverified => "unverified_signature",
+            ExitStatus::UntrustedChain => "untrusted_chain",
+            ExitStatus::Attachment => "attachment_mismatch",
+            ExitStatus::NoSignatures => "no_signatures",
+            ExitStatus::Io => "io_error",
+            ExitStatus::MalformedPdf => "malformed_pdf",
+            ExitStatus::Cancelled => "cancelled",
+            ExitStatus::DiskFull => "disk_full",
+        }
+    }
+}
+
+/// Kegagalan dengan exit code tertentu (hasil verify yang tidak lolos, dsb.)
+#[derive(Debug)]
+pub struct Failure {
+    pub status: ExitStatus, // Exit code
+    pub message: String,    // Pesan untuk user
+    pub reported: bool,     // Hasilnya sudah ditulis sebagai JSON ke stdout
+}
+
+impl Failure {
+    /// Parameter:
+    ///   - status: exit code
+    ///   - message: pesan untuk user
+    pub fn new(status: ExitStatus, message: String) -> Failure {
+        Failure { status, message, reported: false }
+    }
+
+    /// Tandai bahwa hasil perintah sudah ditulis sebagai JSON
+    pub fn reported(mut self) -> Failure {
+        self.reported = true;
+        self
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// Exit code untuk error dari perintah
+///
+/// Rantai error (termasuk context) ditelusuri dari luar ke dalam; penyebab
+/// yang pertama dikenali menentukan kodenya, sisanya menjadi `Failure`
+pub fn classify(err: &anyhow::Error) -> ExitStatus {
+    for cause in err.chain() {
+        if let Some(failure) = cause.downcast_ref::<Failure>() {
+            return failure.status;
+        }
+        if let Some(err) = cause.downcast_ref::<Error>() {
+            match err {
+                Error::Io(err) => return io_status(err),
+                Error::Pdf(lopdf::Error::IO(err)) => return io_status(err),
+                Error::Pdf(_) => return ExitStatus::MalformedPdf,
+                Error::Cancelled | Error::TimedOut => return ExitStatus::Cancelled,
+                Error::NoSignatures(_) => return ExitStatus::NoSignatures,
+                _ => {}
+            }
+        }
+        if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+            return io_status(err);
+        }
+        match cause.downcast_ref::<lopdf::Error>() {
+            Some(lopdf::Error::IO(err)) => return io_status(err),
+            Some(_) => return ExitStatus::MalformedPdf,
+            None => {}
+        }
+    }
+    ExitStatus::Failure
+}
+
+/// Exit code untuk error I/O: disk penuh dan short write punya kode sendiri
+/// agar pipeline bisa membedakannya dari file yang tidak bisa dibaca
+fn io_status(err: &std::io::Error) -> ExitStatus {
+    if pdfsign::error::is_disk_full(err) {
+        ExitStatus::DiskFull
+    } else {
+        ExitStatus::Io
+    }
+}
+
+/// Apakah hasil perintah sudah ditulis sebagai JSON ke stdout
+pub fn reported(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.downcast_ref::<Failure>().is_some_and(|failure| failure.reported))
+}
+
+/// Exit code untuk hasil `verify --input`
+///
+/// Jika ada beberapa masalah, urutannya: signature tidak valid, signature
+/// yang tidak bisa diperiksa, lampiran rusak, lalu rantai yang tidak
+/// dipercaya
+///
+/// Parameter:
+///   - verification: hasil verifikasi dokumen
+///   - require_trust: rantai yang tidak diperiksa karena tidak ada trust
+///     anchor juga dihitung untrusted (--trust atau --require-trust);
+///     jika false, temuan itu tetap peringatan saja
+#[cfg(feature = "verify")]
+pub fn verification_status(verification: &pdfsign::pdf::verify::Verification, require_trust: bool) -> ExitStatus {
+    use pdfsign::crypto::trust::ChainIssue;
+    use pdfsign::pdf::verify::{Finding, SignatureStatus};
+
+    let any = |status: SignatureStatus| verification.signatures.iter().any(|report| report.status == status);
+    let untrusted = verification.signatures.iter().flat_map(|report| &report.findings).any(|finding| match finding {
+        Finding::Untrusted(_) | Finding::Chain(ChainIssue::MissingIssuer { .. }) => true,
+        Finding::NoTrustAnchor => require_trust,
+        _ => false,
+    });
+    if any(SignatureStatus::Invalid) {
+        ExitStatus::InvalidSignature
+    } else if any(SignatureStatus::Unverifiable) {
+        ExitStatus::Unverified
+    } else if verification.attachments.iter().any(|report| report.failed()) {
+        ExitStatus::Attachment
+    } else if untrusted {
+        ExitStatus::UntrustedChain
+    } else {
+        ExitStatus::Success
+    }
+}
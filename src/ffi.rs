@@ -0,0 +1,69 @@
+// C ABI untuk build verify-only (fitur ffi): aplikasi mobile (lewat JNI / Swift)
+// dan host WASM memanggil fungsi-fungsi ini untuk memeriksa dokumen tanpa
+// membawa kode penandatanganan.
+
+// Import library yang diperlukan
+use std::slice; // Buffer dari pemanggil
+
+use crate::crypto::policy::AlgorithmPolicy; // Kebijakan algoritma default
+use crate::pdf::verify::{self, SignatureStatus}; // Verifikasi signature
+
+/// Verifikasi semua signature dalam PDF di memori dengan kebijakan algoritma default
+///
+/// Parameter:
+///   - pdf, len: isi file PDF apa adanya
+///   - signatures: diisi jumlah signature di dokumen (boleh null)
+///   - invalid: diisi jumlah signature yang tidak valid atau tidak bisa diperiksa (boleh null)
+///
+/// Return: 0 jika semua signature valid, 1 jika ada yang tidak valid atau
+/// dokumen tidak punya signature, -1 jika PDF tidak bisa dibaca
+///
+/// # Safety
+/// `pdf` harus menunjuk ke `len` bytes yang bisa dibaca; `signatures` dan
+/// `invalid` harus null atau menunjuk ke `u32` yang bisa ditulis.
+#[no_mangle]
+pub unsafe extern "C" fn pdfsign_verify(pdf: *const u8, len: usize, signatures: *mut u32, invalid: *mut u32) -> i32 {
+    if pdf.is_null() {
+        return -1;
+    }
+    let bytes = slice::from_raw_parts(pdf, len);
+    let reports = match verify::verify_bytes(bytes, None, &AlgorithmPolicy::default()) {
+        Ok(reports) => reports,
+        Err(_) => return -1,
+    };
+    let failed = reports.iter().filter(|report| report.status != SignatureStatus::Valid).count();
+
+    if !signatures.is_null() {
+        *signatures = reports.len() as u32;
+    }
+    if !invalid.is_null() {
+        *invalid = failed as u32;
+    }
+    if reports.is_empty() || failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Alokasikan buffer untuk PDF (host WASM menulis dokumen ke memori modul)
+///
+/// Return: pointer ke `len` bytes; bebaskan dengan `pdfsign_free`
+#[no_mangle]
+pub extern "C" fn pdfsign_alloc(len: usize) -> *mut u8 {
+    let mut buffer = vec![0u8; len].into_boxed_slice();
+    let pointer = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    pointer
+}
+
+/// Bebaskan buffer dari `pdfsign_alloc`
+///
+/// # Safety
+/// `pointer` dan `len` harus sama persis dengan hasil dan argumen `pdfsign_alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn pdfsign_free(pointer: *mut u8, len: usize) {
+    if !pointer.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(pointer, len)));
+    }
+}
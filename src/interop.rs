@@ -0,0 +1,249 @@
+// Uji interoperabilitas (pdfsign interop-test): dokumen contoh ditandatangani
+// dengan setiap profil lalu diperiksa oleh validator eksternal
+use anyhow::{anyhow, Context, Result}; // Untuk error handling yang fleksibel
+use base64::engine::general_purpose::STANDARD as BASE64; // PDF di body JSON DSS
+use base64::Engine as _; // encode()
+use pdfsign::cancel::CancellationToken; // Pembatalan dan batas waktu per validasi
+use pdfsign::crypto::http; // Request ke endpoint validator
+use pdfsign::pdf::fanout::{self, FanoutItem}; // Signing per profil
+use pdfsign::pdf::pades::PadesLevel; // Format yang diharapkan dari validator
+use pdfsign::pdf::testdoc::{self, TestDocOptions}; // Dokumen contoh bawaan
+use serde_json::{json, Value}; // Request dan laporan DSS
+use std::path::{Path, PathBuf}; // Dokumen contoh dan hasil per profil
+use std::process::Command; // Validator berupa command
+use std::time::{Duration, Instant}; // Batas waktu dan lama per profil
+
+/// Nama file dokumen contoh yang dibuat jika --sample tidak diberikan
+const SAMPLE_NAME: &str = "interop-sample.pdf";
+
+/// Validator eksternal
+pub enum Validator {
+    /// Endpoint REST validasi DSS, misalnya demo webapp DSS:
+    /// `.../services/rest/validation/validateSignature`
+    Dss(String),
+    /// Command shell; path PDF menggantikan `{}` (atau ditambahkan di akhir),
+    /// exit code 0 = lolos
+    Command(String),
+}
+
+impl Validator {
+    /// Deskripsi untuk laporan
+    pub fn describe(&self) -> String {
+        match self {
+            Validator::Dss(url) => format!("DSS {}", url),
+            Validator::Command(command) => format!("command `{}`", command),
+        }
+    }
+}
+
+/// Konfigurasi `pdfsign interop-test`
+pub struct InteropConfig {
+    pub validator: Validator,      // Validator yang memeriksa setiap hasil
+    pub output_dir: PathBuf,       // Tempat dokumen contoh dan hasil per profil
+    pub sample: Option<PathBuf>,   // Dokumen contoh sendiri; None = dibuat otomatis
+    pub allow_indeterminate: bool, // INDETERMINATE dihitung lolos (identitas uji tanpa rantai terpercaya)
+    pub timeout: Duration,         // Batas waktu satu validasi
+}
+
+/// Hasil uji satu profil
+pub struct ProfileResult {
+    pub profile: String,                  // Nama profil
+    pub output: PathBuf,                  // PDF yang ditandatangani profil ini
+    pub passed: bool,                     // Lolos validasi
+    pub indication: Option<String>,       // Indication ETSI dari DSS: TOTAL_PASSED, INDETERMINATE, TOTAL_FAILED
+    pub sub_indication: Option<String>,   // SubIndication, misalnya NO_CERTIFICATE_CHAIN_FOUND
+    pub signature_format: Option<String>, // Format menurut validator, misalnya PAdES-BASELINE-T
+    pub expected_format: Option<String>,  // Format yang seharusnya menurut profil
+    pub detail: Option<String>,           // Alasan gagal atau pesan validator
+    pub elapsed: Duration,                // Lama signing dan validasi
+}
+
+impl ProfileResult {
+    /// Ringkasan satu baris: indication/sub-indication, format, dan alasannya
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(indication) = &self.indication {
+            parts.push(match &self.sub_indication {
+                Some(sub_indication) => format!("{}/{}", indication, sub_indication),
+                None => indication.clone(),
+            });
+        }
+        parts.extend(self.signature_format.clone());
+        parts.extend(self.detail.clone());
+        parts.join(", ")
+    }
+}
+
+/// Hasil pemeriksaan validator untuk satu file
+#[derive(Default)]
+struct Check {
+    passed: bool,
+    indication: Option<String>,
+    sub_indication: Option<String>,
+    signature_format: Option<String>,
+    detail: Option<String>,
+}
+
+/// Format signature yang dilaporkan DSS untuk level PAdES profil; None untuk
+/// adbe.pkcs7.detached biasa (formatnya tidak diperiksa)
+pub fn expected_format(level: Option<PadesLevel>) -> Option<String> {
+    level.map(|level| format!("PAdES-BASELINE-{}", level.label().trim_start_matches("B-")))
+}
+
+/// Dokumen contoh: --sample, atau dokumen uji satu halaman di `output_dir`
+pub fn sample_document(config: &InteropConfig) -> Result<PathBuf> {
+    if let Some(sample) = &config.sample {
+        return Ok(sample.clone());
+    }
+    std::fs::create_dir_all(&config.output_dir).with_context(|| format!("cannot create directory {}", config.output_dir.display()))?;
+    let path = config.output_dir.join(SAMPLE_NAME);
+    let options = TestDocOptions { text: Some("pdfsign interoperability test".to_string()), ..TestDocOptions::default() };
+    testdoc::generate_test_document(&path.to_string_lossy(), &options)?;
+    Ok(path)
+}
+
+/// Tandatangani dokumen contoh dengan setiap profil, lalu periksa setiap hasil
+/// dengan validator
+///
+/// Profil yang gagal ditandatangani atau ditolak validator tidak menghentikan
+/// profil lain. Validasi dijalankan berurutan agar validator bersama (demo
+/// webapp) tidak dibanjiri request.
+///
+/// Parameter:
+///   - config: validator dan direktori output
+///   - sample: dokumen contoh (`sample_document`)
+///   - items: satu identitas per profil; output-nya di `config.output_dir`
+///   - cancel: token pembatalan seluruh uji
+///   - on_result: dipanggil setiap satu profil selesai diperiksa
+///
+/// Return: hasil per profil, urut seperti `items`
+pub fn run(
+    config: &InteropConfig,
+    sample: &Path,
+    items: Vec<FanoutItem>,
+    cancel: &CancellationToken,
+    on_result: &dyn Fn(&ProfileResult),
+) -> Result<Vec<ProfileResult>> {
+    let expected: Vec<Option<String>> = items.iter().map(|item| expected_format(item.options.pades)).collect();
+    let outcomes = fanout::sign_fanout(&sample.to_string_lossy(), items, cancel, &|_| {})?;
+
+    let mut results = Vec::with_capacity(outcomes.len());
+    for (outcome, expected_format) in outcomes.into_iter().zip(expected) {
+        cancel.check()?;
+        let started = Instant::now();
+        let check = match outcome.result {
+            Ok(()) => validate(config, &outcome.output, expected_format.as_deref(), &cancel.child(config.timeout))
+                .unwrap_or_else(|e| Check { detail: Some(format!("validator error: {:#}", e)), ..Check::default() }),
+            Err(e) => Check { detail: Some(format!("signing failed: {:#}", e)), ..Check::default() },
+        };
+        let result = ProfileResult {
+            profile: outcome.identity,
+            output: outcome.output,
+            passed: check.passed,
+            indication: check.indication,
+            sub_indication: check.sub_indication,
+            signature_format: check.signature_format,
+            expected_format,
+            detail: check.detail,
+            elapsed: outcome.elapsed + started.elapsed(),
+        };
+        on_result(&result);
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Periksa satu PDF yang sudah ditandatangani dengan validator
+fn validate(config: &InteropConfig, path: &Path, expected_format: Option<&str>, cancel: &CancellationToken) -> Result<Check> {
+    match &config.validator {
+        Validator::Dss(url) => validate_dss(url, path, expected_format, config.allow_indeterminate, cancel),
+        Validator::Command(command) => validate_command(command, path),
+    }
+}
+
+/// Kirim PDF ke endpoint REST DSS dan baca simple report-nya
+fn validate_dss(url: &str, path: &Path, expected_format: Option<&str>, allow_indeterminate: bool, cancel: &CancellationToken) -> Result<Check> {
+    let bytes = std::fs::read(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let name = path.file_name().map_or_else(|| "document.pdf".into(), |name| name.to_string_lossy());
+    let request = json!({
+        "signedDocument": { "bytes": BASE64.encode(&bytes), "name": name },
+        "originalDocuments": [],
+        "policy": null,
+        "signatureId": null,
+    });
+    let headers = [("Content-Type", "application/json"), ("Accept", "application/json")];
+    let response = http::call_api("POST", url, &headers, Some(request.to_string().as_bytes()), cancel)?;
+    let report: Value = serde_json::from_slice(&response).map_err(|e| anyhow!("{} did not return a DSS report: {}", url, e))?;
+    let simple = field(&report, "simpleReport").ok_or_else(|| anyhow!("{} returned no simpleReport", url))?;
+    let signature = first_signature(simple).ok_or_else(|| anyhow!("the validator found no signature in {}", path.display()))?;
+
+    let text = |name: &str| field(signature, name).and_then(Value::as_str).map(str::to_string);
+    let mut check = Check {
+        indication: text("Indication"),
+        sub_indication: text("SubIndication"),
+        signature_format: text("SignatureFormat"),
+        ..Check::default()
+    };
+    check.passed = match check.indication.as_deref() {
+        Some("TOTAL_PASSED") => true,
+        Some("INDETERMINATE") => allow_indeterminate,
+        _ => false,
+    };
+    // Format yang tidak sesuai profil berarti SubFilter atau atribut CMS berubah
+    if let (Some(expected), Some(actual)) = (expected_format, check.signature_format.as_deref()) {
+        if expected != actual {
+            check.passed = false;
+            check.detail = Some(format!("the validator sees {}, the profile signs {}", actual, expected));
+        }
+    }
+    Ok(check)
+}
+
+/// Signature pertama di simple report DSS (bukan timestamp); nama field
+/// berbeda antar versi DSS
+fn first_signature(simple: &Value) -> Option<&Value> {
+    ["signatureOrTimestampOrEvidenceRecord", "signatureOrTimestamp"]
+        .iter()
+        .filter_map(|name| field(simple, name).and_then(Value::as_array))
+        .flatten()
+        .find_map(|entry| field(entry, "Signature"))
+        .or_else(|| field(simple, "Signature").and_then(Value::as_array).and_then(|signatures| signatures.first()))
+}
+
+/// Field object JSON tanpa membedakan huruf besar/kecil (serialisasi DSS
+/// tergantung versi dan konfigurasi Jackson)
+fn field<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    value.as_object()?.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+}
+
+/// Jalankan command validator lewat shell untuk satu PDF
+fn validate_command(command: &str, path: &Path) -> Result<Check> {
+    let path = path.to_string_lossy();
+    #[cfg(windows)]
+    let (mut process, quoted) = {
+        let mut process = Command::new("cmd");
+        process.arg("/C");
+        (process, format!("\"{}\"", path))
+    };
+    #[cfg(not(windows))]
+    let (mut process, quoted) = {
+        let mut process = Command::new("sh");
+        process.arg("-c");
+        (process, format!("'{}'", path.replace('\'', "'\\''")))
+    };
+    let line = if command.contains("{}") { command.replace("{}", &quoted) } else { format!("{} {}", command, quoted) };
+    let output = process.arg(&line).output().with_context(|| format!("cannot run the validator `{}`", command))?;
+
+    // Baris terakhir output validator sebagai ringkasan
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = |text: &str| text.lines().map(str::trim).rfind(|line| !line.is_empty()).map(str::to_string);
+    let passed = output.status.success();
+    let detail = if passed { last_line(&stdout) } else { last_line(&stderr).or_else(|| last_line(&stdout)) };
+    let status = output.status.code().map_or_else(|| "terminated by a signal".to_string(), |code| format!("exit code {}", code));
+    Ok(Check {
+        passed,
+        detail: Some(detail.map_or_else(|| status.clone(), |detail| format!("{} ({})", detail, status))),
+        ..Check::default()
+    })
+}
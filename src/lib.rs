@@ -0,0 +1,69 @@
+//! pdfsign: tanda tangan digital PDF (ECDSA / Ed25519 / RSA, CMS, PAdES)
+//!
+//! Library ini dipakai oleh CLI `pdfsign` dan bisa dipakai langsung dari
+//! service Rust lewat builder `PdfSigner`:
+//!
+//! ```ignore
+//! use pdfsign::PdfSigner;
+//!
+//! PdfSigner::new()
+//!     .key_file("private.key")
+//!     .certificate(std::fs::read("certificate.der")?)
+//!     .reason("Contract approval")
+//!     .sign(File::open("contract.pdf")?, File::create("contract_signed.pdf")?)?;
+//! ```
+//!
+//! ## Fitur Cargo
+//!
+//! Default: semua fitur kecuali `ffi`, `serve`, `interop-test`, dan backend KMS. API yang tersedia per fitur:
+//!
+//! - tanpa fitur: `cancel`, `error`, `log` (beserta macro `warning!`, `info!`,
+//!   `verbose!`, `debug!`), `crypto::{ecc, digest, rsa, signer, cms, tsa,
+//!   policy, provider}` (parsing dan verifikasi signature mentah), `pdf::{form, naming, pages, syntax, text}`
+//! - `sign`: `PdfSigner`, `pdf::{sign, options, signer, external, hooks, mdp, pades,
+//!   update, budget, batch, fanout, watch, health, placement, pdfa, detached, testdoc, remove, optimize, attachment_signatures, encryption}`, `crypto::{keyfile, p12, cert, key_info}`,
+//!   sumber acak `crypto::provider::{set_random_source, SeededRandom}`, `pdf::naming::{now, file_id}`, `lock`, `scratch`, `storage`
+//!   (file dan memori); `crypto::pkcs11::Pkcs11Token`, `crypto::kms::KmsKey`, dan
+//!   `crypto::keystore::load_keystore` selalu ada, memuatnya butuh `pkcs11` / fitur KMS / `keystore`
+//! - `network`: `crypto::{http, revocation}`, `tsa::request_timestamp`
+//!   (login `TsaCredentials` lewat header Authorization), `pdf::ltv` (`add_ltv_with`
+//!   memakai `pades::LtvPolicy`; struct ini selalu ada), `pdf::timestamp` (stamp `TimestampAppearance::Stamp` juga butuh `appearance`),
+//!   `pades::{add_dss, add_document_timestamp, add_document_timestamp_with}`, `storage::{HttpSource, HttpSink}`
+//! - `pkcs11`: `crypto::pkcs11::load_pkcs11` dan `Pkcs11Signer`
+//! - `server`: `crypto::session` (Unix)
+//! - `appearance`: `pdf::{image, font}`, `pdf::appearance::{CompiledAppearance, AppearanceCache}`
+//!   (tanpa fitur ini signature selalu tak terlihat); bersama `verify` juga `pdf::refresh`
+//! - `verify`: `pdf::{verify, checks, attachments}`, `crypto::trust`, `crypto::revocation::check_status`
+//!   (pemeriksaan online butuh `network`); bersama `sign` juga `pdf::{archive, audit, inspect}`
+//! - `ffi`: `ffi` (C ABI `pdfsign_verify`)
+//! - `aws-kms`, `gcp-kms`, `azure-kms`: backend `crypto::kms`
+//! - `keystore`: backend `crypto::keystore` (Windows certificate store / macOS Keychain)
+//! - `serve`: perintah `pdfsign serve` di binary (tidak menambah API library)
+//! - `interop-test`: perintah `pdfsign interop-test` di binary (tidak menambah API library)
+//! - `ring`: `crypto::ring::RingProvider` (--crypto-backend ring)
+//!
+//! Opsi yang butuh fitur yang tidak dikompilasi ditolak dengan
+//! `error::missing_feature` (misalnya TSA tanpa `network`).
+
+// Deklarasi modul-modul library
+pub mod cancel;   // Pembatalan dan timeout untuk operasi yang lama
+pub mod crypto;   // Cryptography module (ECDSA, RSA, CMS, TSA, OCSP/CRL)
+pub mod error;    // Error API library
+#[cfg(feature = "ffi")]
+pub mod ffi;      // C ABI untuk verifikasi (aplikasi mobile, host WASM)
+#[cfg(feature = "sign")]
+pub mod lock;     // Lock antar proses untuk file bersama (output, sesi)
+pub mod log;      // Tingkat log (-q/-v/-vv), peringatan, dan progress bar
+pub mod pdf;      // PDF manipulation module
+#[cfg(feature = "sign")]
+pub mod scratch;  // Penyimpanan data sementara (memori / temp dir / file anonim)
+#[cfg(feature = "sign")]
+pub mod storage;  // Sumber dan tujuan dokumen (file, memori, HTTP/S3, storage sendiri)
+
+// Tipe yang paling sering dipakai, langsung dari root crate
+pub use cancel::CancellationToken;
+pub use error::Error;
+#[cfg(feature = "sign")]
+pub use pdf::options::{AnnotationFlags, Placement, Position, Rect};
+#[cfg(feature = "sign")]
+pub use pdf::signer::PdfSigner;
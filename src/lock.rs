@@ -0,0 +1,80 @@
+// Import library yang diperlukan
+use anyhow::{Context, Result}; // Untuk error handling yang fleksibel
+use std::fs::{self, File, OpenOptions, TryLockError}; // File lock dan lock advisory
+use std::path::{Path, PathBuf}; // Path file lock
+
+/// Lock advisory eksklusif antar proses pdfsign pada satu file bersama
+///
+/// Lock disimpan di file terpisah `<path>.lock`, karena file yang dilindungi
+/// sendiri diganti lewat rename (inode-nya berubah). Proses lain yang meminta
+/// lock yang sama menunggu sampai lock dilepas; lock dilepas saat di-drop,
+/// juga jika proses berhenti di tengah jalan.
+pub struct FileLock {
+    file: File,    // File lock yang sedang dikunci
+    path: PathBuf, // Path file lock (dihapus saat dilepas)
+}
+
+impl FileLock {
+    /// Ambil lock eksklusif untuk `target`, tunggu jika sedang dipegang proses lain
+    ///
+    /// Parameter:
+    ///   - target: file yang dilindungi (output PDF, socket sesi, ...)
+    pub fn exclusive(target: impl AsRef<Path>) -> Result<FileLock> {
+        let mut path = target.as_ref().as_os_str().to_owned();
+        path.push(".lock");
+        let path = PathBuf::from(path);
+
+        let mut waiting = false;
+        loop {
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)
+                .with_context(|| format!("cannot open lock file {}", path.display()))?;
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(TryLockError::WouldBlock) => {
+                    if !waiting {
+                        crate::log::status(&format!("Waiting for another pdfsign process to release {}", path.display()), true);
+                        waiting = true;
+                    }
+                    file.lock().with_context(|| format!("cannot lock {}", path.display()))?;
+                }
+                Err(TryLockError::Error(err)) => {
+                    return Err(err).with_context(|| format!("cannot lock {}", path.display()));
+                }
+            }
+            // Pemegang lock sebelumnya menghapus file lock saat melepasnya; jika
+            // file yang kita kunci sudah bukan file di path tersebut, ulangi
+            if same_file(&file, &path) {
+                return Ok(FileLock { file, path });
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Hapus selagi masih terkunci, agar tidak ada proses yang mengunci file basi
+        let _ = fs::remove_file(&self.path);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Apakah file yang terbuka masih file yang sama dengan yang ada di `path`
+#[cfg(unix)]
+fn same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt; // Device dan inode
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(open), Ok(current)) => open.dev() == current.dev() && open.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+/// Di Windows file yang sedang terbuka tidak bisa dihapus, jadi path selalu
+/// menunjuk ke file yang sama
+#[cfg(not(unix))]
+fn same_file(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
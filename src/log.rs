@@ -0,0 +1,224 @@
+// Import library yang diperlukan
+use std::fmt; // Pesan yang sudah diformat oleh macro
+use std::io::{IsTerminal, Write}; // Progress bar hanya di terminal
+use std::sync::atomic::{AtomicU8, Ordering}; // Tingkat log global, aman antar thread
+use std::sync::{Mutex, OnceLock}; // Status progress bar dan waktu mulai
+use std::time::{Duration, Instant}; // Waktu sejak mulai (detail -v) dan jeda gambar ulang
+
+/// Seberapa banyak yang ditulis pdfsign ke terminal (-q, default, -v, -vv)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// -q: hanya error
+    Quiet,
+    /// Pesan status dan peringatan
+    #[default]
+    Normal,
+    /// -v: ditambah detail setiap tahap (offset placeholder, ByteRange, ukuran CMS, TSA)
+    Verbose,
+    /// -vv: ditambah detail tingkat rendah (request HTTP, object yang ditulis)
+    Debug,
+}
+
+/// Tingkat log yang berlaku untuk seluruh proses
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Waktu mulai, untuk cap waktu di baris detail
+static START: OnceLock<Instant> = OnceLock::new();
+
+/// Atur tingkat log (CLI: -q / -v / -vv); library memakai Normal jika tidak diatur
+pub fn set_verbosity(verbosity: Verbosity) {
+    START.get_or_init(Instant::now);
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Tingkat log yang berlaku
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Debug,
+    }
+}
+
+/// Apakah pesan pada tingkat ini ditampilkan
+pub fn enabled(level: Verbosity) -> bool {
+    verbosity() >= level
+}
+
+/// Tulis pesan status ke stdout, atau ke stderr jika stdout berisi PDF hasil
+/// (disembunyikan oleh -q)
+pub fn status(line: &str, stderr: bool) {
+    if !enabled(Verbosity::Normal) {
+        return;
+    }
+    clear_progress();
+    if stderr {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Tulis kegagalan per dokumen ke stdout (sign-batch, sign-fanout, watch);
+/// tetap ditampilkan dengan -q, seperti error
+pub fn failure(line: &str) {
+    clear_progress();
+    println!("{}", line);
+}
+
+/// Dipakai oleh macro `warning!`: "Warning: ..." di stderr, baris
+/// berikutnya diindentasi sejajar dengan teks pertama
+#[doc(hidden)]
+pub fn write_warning(args: fmt::Arguments) {
+    if !enabled(Verbosity::Normal) {
+        return;
+    }
+    clear_progress();
+    eprintln!("Warning: {}", args.to_string().replace('\n', "\n         "));
+}
+
+/// Dipakai oleh macro `info!`: pesan status di stdout
+#[doc(hidden)]
+pub fn write_info(args: fmt::Arguments) {
+    status(&args.to_string(), false);
+}
+
+/// Dipakai oleh macro `verbose!` dan `debug!`: "[waktu tahap] pesan" di stderr
+#[doc(hidden)]
+pub fn write_detail(level: Verbosity, step: &str, args: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    clear_progress();
+    let elapsed = START.get_or_init(Instant::now).elapsed();
+    eprintln!("[{:>8.3}s {}] {}", elapsed.as_secs_f64(), step, args);
+}
+
+/// Peringatan ke stderr dengan awalan "Warning: " (disembunyikan oleh -q)
+///
+/// Contoh: `warning!("--lock-fields matched no form fields")`
+#[macro_export]
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        $crate::log::write_warning(format_args!($($arg)*))
+    };
+}
+
+/// Pesan status ke stdout (disembunyikan oleh -q)
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::write_info(format_args!($($arg)*))
+    };
+}
+
+/// Detail satu tahap untuk -v, dengan nama tahapnya
+///
+/// Contoh: `verbose!("sign", "ByteRange [0 {} {} {}]", a, b, c)`
+#[macro_export]
+macro_rules! verbose {
+    ($step:expr, $($arg:tt)*) => {
+        $crate::log::write_detail($crate::log::Verbosity::Verbose, $step, format_args!($($arg)*))
+    };
+}
+
+/// Detail tingkat rendah untuk -vv, dengan nama tahapnya
+#[macro_export]
+macro_rules! debug {
+    ($step:expr, $($arg:tt)*) => {
+        $crate::log::write_detail($crate::log::Verbosity::Debug, $step, format_args!($($arg)*))
+    };
+}
+
+/// Progress bar yang sedang tampil (paling banyak satu), agar pesan lain
+/// bisa menghapusnya dulu sebelum ditulis
+static PROGRESS_VISIBLE: Mutex<bool> = Mutex::new(false);
+
+/// Hapus baris progress bar sebelum pesan lain ditulis ke terminal
+fn clear_progress() {
+    let mut visible = PROGRESS_VISIBLE.lock().unwrap_or_else(|e| e.into_inner());
+    if *visible {
+        eprint!("\r\x1b[2K");
+        *visible = false;
+    }
+}
+
+/// Progress bar di stderr untuk pekerjaan panjang (sign-batch, hashing file besar)
+///
+/// Hanya digambar jika stderr adalah terminal dan tingkat log bukan -q; di
+/// pipe atau file log tidak ada yang ditulis. Aman dipakai dari banyak thread.
+pub struct Progress {
+    label: String,              // Nama pekerjaan, mis. "Hashing"
+    total: u64,                 // Jumlah total (item atau byte)
+    bytes: bool,                // Tampilkan sebagai ukuran (MB) alih-alih jumlah item
+    shown: bool,                // Digambar di terminal
+    state: Mutex<(u64, Option<Instant>)>, // Posisi sekarang dan waktu terakhir digambar
+}
+
+impl Progress {
+    /// Jeda minimum antar gambar ulang, agar terminal tidak dibanjiri
+    const REDRAW: Duration = Duration::from_millis(100);
+
+    /// Progress bar untuk sejumlah item (dokumen)
+    pub fn items(label: impl Into<String>, total: u64) -> Progress {
+        Progress::new(label.into(), total, false)
+    }
+
+    /// Progress bar untuk sejumlah byte (hashing)
+    pub fn bytes(label: impl Into<String>, total: u64) -> Progress {
+        Progress::new(label.into(), total, true)
+    }
+
+    fn new(label: String, total: u64, bytes: bool) -> Progress {
+        let shown = enabled(Verbosity::Normal) && std::io::stderr().is_terminal();
+        Progress { label, total, bytes, shown, state: Mutex::new((0, None)) }
+    }
+
+    /// Tambah posisi sebanyak `amount` dan gambar ulang jika perlu
+    pub fn advance(&self, amount: u64) {
+        if !self.shown {
+            return;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.0 = (state.0 + amount).min(self.total);
+        let due = state.1.is_none_or(|last| last.elapsed() >= Self::REDRAW);
+        if due || state.0 == self.total {
+            state.1 = Some(Instant::now());
+            self.draw(state.0);
+        }
+    }
+
+    /// Gambar bar: "Label [=====>     ]  45% (9/20)"
+    fn draw(&self, current: u64) {
+        const WIDTH: u64 = 30;
+        let filled = (current * WIDTH).checked_div(self.total).unwrap_or(WIDTH);
+        let percent = (current * 100).checked_div(self.total).unwrap_or(100);
+        let bar: String = (0..WIDTH)
+            .map(|i| match i.cmp(&filled) {
+                std::cmp::Ordering::Less => '=',
+                std::cmp::Ordering::Equal => '>',
+                std::cmp::Ordering::Greater => ' ',
+            })
+            .collect();
+        let count = if self.bytes {
+            format!("{:.1}/{:.1} MB", current as f64 / 1e6, self.total as f64 / 1e6)
+        } else {
+            format!("{}/{}", current, self.total)
+        };
+        let mut visible = PROGRESS_VISIBLE.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{} [{}] {:>3}% ({})", self.label, bar, percent, count);
+        let _ = stderr.flush();
+        *visible = true;
+    }
+}
+
+/// Bar dihapus saat pekerjaan selesai (atau gagal)
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.shown {
+            clear_progress();
+        }
+    }
+}
@@ -1,41 +1,1366 @@
-// Deklarasi modul-modul yang digunakan dalam project
+// CLI tipis di atas library pdfsign (lihat lib.rs)
 mod cli;      // Command-line interface (parsing arguments)
-mod crypto;   // Cryptography module (ECC signing)
-mod pdf;      // PDF manipulation module
+mod config;   // Config file (pdfsign.toml) dan profil signing
+mod exit;     // Exit code per jenis kegagalan
+mod output;   // Output JSON (--output-format json)
+mod paths;    // Path di command line: ekspansi ~, ekstensi, dan saran nama file
+#[cfg(feature = "serve")]
+mod serve;    // Layanan HTTP signing (pdfsign serve)
+#[cfg(feature = "interop-test")]
+mod interop;  // Uji interoperabilitas dengan validator eksternal (pdfsign interop-test)
 
 use clap::Parser;      // Parser untuk command-line arguments
-use anyhow::Result;    // Result type untuk error handling yang fleksibel
-use cli::{Cli, Commands}; // Import struktur CLI dan enum Commands
+use pdfsign::{cancel, crypto, pdf, scratch}; // Modul library yang dipakai CLI
+use anyhow::{anyhow, Context, Result}; // Result type untuk error handling yang fleksibel
+use cli::{Cli, Commands, OutputFormat, PagesAction, SessionAction}; // Import struktur CLI dan enum Commands
+use exit::{ExitStatus, Failure}; // Exit code hasil verify dan key-info
 
 /// Fungsi utama program
 /// Menangani logika dasarnya:
 /// 1. Parse command-line arguments dari user
-/// 2. Jalankan perintah yang sesuai (generate-key atau sign)
-fn main() -> Result<()> {
+/// 2. Jalankan perintah yang sesuai (generate-key, generate-cert, sign, atau verify)
+/// 3. Jika gagal, tampilkan error dan keluar dengan exit code sesuai jenis kegagalannya
+fn main() -> std::process::ExitCode {
     // Parse command-line arguments yang diberikan user
     let cli = Cli::parse();
+    let json = cli.output_format == OutputFormat::Json;
+    pdfsign::log::set_verbosity(match (cli.quiet, cli.verbose) {
+        (true, _) => pdfsign::log::Verbosity::Quiet,
+        (false, 0) => pdfsign::log::Verbosity::Normal,
+        (false, 1) => pdfsign::log::Verbosity::Verbose,
+        (false, _) => pdfsign::log::Verbosity::Debug,
+    });
+    let file = command_input(&cli.command);
 
+    let started = deterministic_mode(cli.deterministic_seed.as_deref(), &cli.command).and_then(|()| install_crypto_backend(cli.crypto_backend));
+    match started.and_then(|()| run(cli.command, json)) {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(err) => {
+            let status = exit::classify(&err);
+            eprintln!("Error: {:?}", err);
+            // Mode JSON: error juga berupa object JSON di stdout, kecuali hasilnya sudah ditulis
+            if json && !exit::reported(&err) {
+                println!("{}", output::error(file.as_deref(), status, &format!("{:#}", err)));
+            }
+            std::process::ExitCode::from(status.code())
+        }
+    }
+}
+
+/// Dokumen yang diproses perintah (field "file" di output JSON untuk error)
+fn command_input(command: &Commands) -> Option<String> {
+    match command {
+        Commands::Sign { input, .. } => Some(input.to_string_lossy().into_owned()),
+        Commands::Inspect { input, .. } | Commands::RefreshAppearance { input, .. } | Commands::SignFanout { input, .. } => Some(input.clone()),
+        Commands::Verify { input, recursive, .. } => input.clone().or_else(|| recursive.clone()),
+        Commands::Pages { action: PagesAction::Info { input } } => Some(input.clone()),
+        _ => None,
+    }
+}
+
+/// Aktifkan mode deterministik (--deterministic-seed)
+///
+/// Service yang berjalan terus (serve, watch) menandatangani dokumen
+/// sungguhan dan ditolak: waktu signing dan nama field yang bisa ditebak
+/// tidak boleh masuk ke dokumen seperti itu.
+fn deterministic_mode(seed: Option<&str>, command: &Commands) -> Result<()> {
+    let Some(seed) = seed else {
+        return Ok(());
+    };
+    if matches!(command, Commands::Serve { .. } | Commands::Watch { .. }) {
+        return Err(anyhow!("--deterministic-seed is for tests and cannot be used with serve or watch"));
+    }
+    pdfsign::pdf::naming::set_deterministic(seed.as_bytes());
+    pdfsign::warning!("deterministic mode (--deterministic-seed): field names and the signing time are fixed; use only for tests");
+    Ok(())
+}
+
+/// Pasang backend kriptografi pilihan user (--crypto-backend)
+fn install_crypto_backend(backend: crypto::provider::Backend) -> Result<()> {
+    match backend {
+        crypto::provider::Backend::Rustcrypto => Ok(()),
+        #[cfg(feature = "ring")]
+        crypto::provider::Backend::Ring => {
+            crypto::provider::set_provider(std::sync::Arc::new(crypto::ring::RingProvider::new()));
+            Ok(())
+        }
+        #[cfg(not(feature = "ring"))]
+        crypto::provider::Backend::Ring => Err(pdfsign::error::missing_feature("ring crypto backend", "ring")),
+    }
+}
+
+/// Jalankan perintah yang dipilih user
+///
+/// Parameter:
+///   - command: perintah beserta opsinya
+///   - json: --output-format json (hasil sign, verify, inspect, dan pages info sebagai JSON)
+fn run(command: Commands, json: bool) -> Result<()> {
     // Cocokkan command yang dipilih user
-    match cli.command {
+    match command {
         // Perintah: generate-key
-        // Membuat pasangan kunci publik-privat ECDSA P-256
-        Commands::GenerateKey => crypto::ecc::generate_keypair()?,
+        // Membuat pasangan kunci publik-privat ECC (--curve)
+        Commands::GenerateKey { format, passphrase, curve } => crypto::ecc::generate_keypair(curve, format, passphrase.as_deref())?,
+
+        // Perintah: generate-cert
+        // Membuat sertifikat X.509 self-signed untuk kunci privat
+        Commands::GenerateCert { key, output, cn, org, country, days, key_usage, passphrase } => {
+            // Default: certificate.der di sebelah kunci, sama seperti yang dicari oleh sign
+            let output = output.unwrap_or_else(|| {
+                std::path::Path::new(&key)
+                    .with_file_name("certificate.der")
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            let params = crypto::cert::CertificateParams {
+                common_name: cn,
+                organization: org,
+                country,
+                validity_days: days,
+                key_usage,
+            };
+            crypto::cert::generate_certificate(&key, passphrase.as_deref(), &output, &params)?
+        }
+
+        // Perintah: generate-csr
+        // Membuat certificate signing request untuk diajukan ke CA
+        Commands::GenerateCsr { key, subject, output, passphrase } => {
+            let output = output.unwrap_or_else(|| {
+                std::path::Path::new(&key)
+                    .with_file_name("certificate.csr")
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            crypto::cert::generate_csr(&key, passphrase.as_deref(), &output, &subject)?
+        }
         
         // Perintah: sign
         // Menandatangani file PDF dengan kunci privat
-        Commands::Sign { input, output, key, name, reason, location, contact_info } => {
-            // Buat struktur metadata untuk signature
-            let metadata = pdf::sign::SignatureMetadata {
-                name,           // Nama penandatangan
-                reason,         // Alasan penandatanganan
-                location,       // Lokasi penandatanganan
-                contact_info,   // Informasi kontak penandatangan
+        Commands::Sign { input, output, detached, dry_run, args } => {
+            // Dokumen yang tidak ada dilaporkan sebelum kunci dimuat (PIN, passphrase)
+            paths::check(&input, "input document", paths::ANY)?;
+            let (key, options, timeout) = sign_options(args)?;
+
+            // Siapkan token pembatalan: batas waktu opsional dan Ctrl-C
+            let cancel = match timeout {
+                Some(seconds) => cancel::CancellationToken::with_timeout(std::time::Duration::from_secs(seconds)),
+                None => cancel::CancellationToken::new(),
             };
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            // --dry-run: laporan tanpa output (--output opsional, hanya ditampilkan)
+            if dry_run {
+                let plan = pdf::sign::sign_dry_run(&input, &key, &options, &cancel)?;
+                if json {
+                    println!("{}", output::sign_dry_run(&input, output.as_deref(), &plan));
+                } else {
+                    print!("{}", pdf::sign::format_dry_run(&plan, output.as_deref()));
+                }
+                return Ok(());
+            }
+            // --detached: CMS atas file input di file .p7s, dengan atau tanpa PDF hasil
+            if let Some(detached) = detached {
+                let summary = pdf::detached::sign_detached(&input, &detached, output.as_deref(), &key, &options, &cancel)?;
+                let stdout_pdf = output.as_deref().is_some_and(pdf::sign::is_stdio);
+                if json {
+                    let result = output::sign(&input, output.as_deref(), Some(&detached), &summary);
+                    if stdout_pdf { eprintln!("{}", result) } else { println!("{}", result) }
+                } else {
+                    let report = |line: String| pdfsign::log::status(&line, stdout_pdf);
+                    match &output {
+                        Some(_) if stdout_pdf => report("PDF signed: <stdout>".to_string()),
+                        Some(output) => report(format!("PDF signed: {}", output.display())),
+                        None if pdf::sign::is_stdio(&input) => report("Input unchanged: <stdin>".to_string()),
+                        None => report(format!("Input unchanged: {}", input.display())),
+                    }
+                    report(format!("Detached signature: {}", detached.display()));
+                    pdf::sign::print_summary(&summary, stdout_pdf);
+                }
+                return Ok(());
+            }
+            // clap mewajibkan --output tanpa --dry-run dan --detached
+            let output = output.unwrap_or_default();
+
             // Panggil fungsi untuk menandatangani PDF
-            pdf::sign::sign_pdf(&input, &output, &key, metadata)?
+            if json {
+                // PDF hasil di stdout: JSON ditulis ke stderr, seperti pesan teks
+                let summary = pdf::sign::sign_pdf_summary(&input, &output, &key, &options, &mut pdf::hooks::NoHooks, &cancel)?;
+                let result = output::sign(&input, Some(&output), None, &summary);
+                if pdf::sign::is_stdio(&output) { eprintln!("{}", result) } else { println!("{}", result) }
+            } else {
+                pdf::sign::sign_pdf_with_hooks(&input, &output, &key, options, &mut pdf::hooks::NoHooks, &cancel)?
+            }
+        }
+
+        // Perintah: verify
+        // Memverifikasi semua signature di dalam PDF
+        #[cfg(feature = "verify")]
+        Commands::Verify { input, recursive, detached, report, format, expiring_within, public_key, lang, allow_weak, on_weak, trust, trust_dir, trust_store, require_trust, check_revocation, expected_signer, revision, jobs, strict_parse, summary_line, audit_log, auditor } => {
+            #[cfg(not(feature = "network"))]
+            if check_revocation == Some(crypto::revocation::RevocationMode::Online) {
+                return Err(pdfsign::error::missing_feature("online revocation checking", "network"));
+            }
+            let policy = crypto::policy::AlgorithmPolicy { verify_action: on_weak, ..Default::default() }.allow(&allow_weak);
+            let trust = trust_sources(trust, trust_dir, trust_store)?;
+            if let Some(jobs) = jobs {
+                pdf::verify::set_parallelism(jobs);
+            }
+            let parse = if strict_parse { pdf::syntax::ParseMode::Strict } else { pdf::syntax::ParseMode::Lenient };
+            let audit = audit_log.map(|path| pdf::audit::AuditLog::new(path, auditor));
+            if let Some(root) = recursive {
+                // Mode JSON: laporan JSON kecuali --format / ekstensi --report memilih lain
+                let default_format = if json { pdf::archive::ReportFormat::Json } else { pdf::archive::ReportFormat::Csv };
+                let format = format.unwrap_or_else(|| report.as_deref().map_or(default_format, pdf::archive::ReportFormat::from_path));
+                return verify_archive(&root, report.as_deref(), format, expiring_within, public_key.as_deref(), &policy, &trust, parse, audit.as_ref(), json);
+            }
+            let input = input.unwrap_or_default();
+            let verified = match &detached {
+                Some(detached) => pdf::verify::verify_detached(&input, detached, public_key.as_deref(), &policy, &trust, check_revocation),
+                None => {
+                    let expected = (!expected_signer.is_empty()).then(|| pdf::checks::ExpectedSigner::new(expected_signer));
+                    let checks: Vec<&dyn pdf::checks::VerificationCheck> = expected.iter().map(|check| check as _).collect();
+                    match revision {
+                        Some(revision) => pdf::verify::verify_revision_with_checks(
+                            &input,
+                            revision as usize,
+                            public_key.as_deref(),
+                            &policy,
+                            &trust,
+                            parse,
+                            check_revocation,
+                            &checks,
+                        ),
+                        None => pdf::verify::verify_pdf_with_checks(&input, public_key.as_deref(), &policy, &trust, parse, check_revocation, &checks),
+                    }
+                }
+            };
+            let verification = match verified {
+                Ok(verification) => verification,
+                Err(err) => {
+                    // Dokumen yang tidak bisa diverifikasi juga dicatat di audit log
+                    if let Some(audit) = &audit {
+                        audit.append(&[pdf::audit::AuditEntry::error(&input, format!("{:#}", err))])?;
+                    }
+                    return Err(err);
+                }
+            };
+            let status = exit::verification_status(&verification, require_trust || !trust.is_empty());
+            let broken = verification.attachments.iter().filter(|report| report.failed()).count();
+            if summary_line {
+                // Satu baris untuk CI, dicetak juga dengan -q
+                println!("{}", verify_summary_line(&input, detached.is_some(), &verification, status)?);
+            } else if json {
+                println!("{}", output::verify(&input, &verification, status, lang));
+            } else if pdfsign::log::enabled(pdfsign::log::Verbosity::Normal) {
+                // -q: hasil verifikasi hanya lewat exit code
+                match &verification.revision {
+                    Some(revision) if revision.number < revision.count => println!(
+                        "Revision {} of {} (first {} bytes); {} later revision(s) not checked\n",
+                        revision.number,
+                        revision.count,
+                        revision.end,
+                        revision.count - revision.number
+                    ),
+                    Some(revision) => println!("Revision {} of {} (the latest, {} bytes)\n", revision.number, revision.count, revision.end),
+                    None => {}
+                }
+                print!("{}", pdf::verify::format_reports(&verification.signatures, lang));
+                print!("{}", pdf::attachments::format_attachments(&verification.attachments, lang));
+            }
+            if let Some(audit) = &audit {
+                audit.append(&[pdf::audit::AuditEntry::from_reports(&input, &verification.signatures, broken)])?;
+            }
+            // Exit code non-zero jika ada signature yang tidak valid / tidak bisa diperiksa,
+            // lampiran yang rusak, atau rantai yang tidak berujung di --trust (atau tidak ada
+            // --trust sama sekali dengan --require-trust)
+            let count = |status: pdf::verify::SignatureStatus| verification.signatures.iter().filter(|report| report.status == status).count();
+            let message = match status {
+                ExitStatus::Success => return Ok(()),
+                ExitStatus::InvalidSignature => {
+                    format!("{} of {} signature(s) failed verification", count(pdf::verify::SignatureStatus::Invalid), verification.signatures.len())
+                }
+                ExitStatus::Unverified => {
+                    format!("{} of {} signature(s) could not be verified", count(pdf::verify::SignatureStatus::Unverifiable), verification.signatures.len())
+                }
+                ExitStatus::Attachment => format!("{} of {} attachment(s) failed the integrity check", broken, verification.attachments.len()),
+                _ if trust.is_empty() => "no trust anchor configured and --require-trust given; pass --trust (for example --trust system) to check the certificate chain".to_string(),
+                _ => "the certificate chain does not end at a trusted anchor".to_string(),
+            };
+            let failure = Failure::new(status, message);
+            return Err(if json { failure.reported() } else { failure }.into());
+        }
+
+        // Perintah: session
+        // Membuka kunci untuk sementara agar sign tidak meminta passphrase berulang kali
+        Commands::Session { action } => run_session(action)?,
+
+        // Perintah: add-ltv
+        // Menyimpan data revocation (OCSP/CRL) untuk signature yang sudah ada
+        #[cfg(feature = "network")]
+        Commands::AddLtv { input, output, ltv_revocation, ltv_best_effort, config, profile, timeout, retries, retry_backoff, retry_jitter } => {
+            let defaults = load_profile(config, profile)?;
+            set_retry_policy(retries.or(defaults.retries), retry_backoff.or(defaults.retry_backoff), retry_jitter.or(defaults.retry_jitter));
+            let cancel = match timeout {
+                Some(seconds) => cancel::CancellationToken::with_timeout(std::time::Duration::from_secs(seconds)),
+                None => cancel::CancellationToken::new(),
+            };
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            let policy = pdf::pades::LtvPolicy {
+                revocation: ltv_revocation.or(defaults.ltv_revocation).unwrap_or_default(),
+                best_effort: ltv_best_effort || defaults.ltv_best_effort.unwrap_or(false),
+            };
+            let count = pdf::ltv::add_ltv_with(&input, &output, &policy, &cancel)?;
+            pdfsign::info!("LTV data added for {} signature(s): {}", count, output);
+        }
+
+        // Perintah: prepare
+        // Tahap 1 signing eksternal: placeholder signature dan digest yang harus ditandatangani
+        Commands::Prepare { input, output, digest_out, data_out, certificate, reserve, allow_weak, name, reason, location, contact_info, need_appearances, strict, drop_xfa, strict_parse, signing_time, pdf_password, decrypt_output, allow_test_credentials } => {
+            let certificates = match &certificate {
+                Some(path) => crypto::cms::split_certificates(&std::fs::read(path)?)?,
+                None => Vec::new(),
+            };
+            let mut builder = pdf::options::SignatureOptions::builder()
+                .strict(strict)
+                .drop_xfa(drop_xfa)
+                .strict_parse(strict_parse)
+                .algorithm_policy(crypto::policy::AlgorithmPolicy::default().allow(&allow_weak));
+            if let Some(name) = name {
+                builder = builder.name(name);
+            }
+            if let Some(reason) = reason {
+                builder = builder.reason(reason);
+            }
+            if let Some(location) = location {
+                builder = builder.location(location);
+            }
+            if let Some(contact_info) = contact_info {
+                builder = builder.contact_info(contact_info);
+            }
+            if let Some(policy) = need_appearances {
+                builder = builder.need_appearances(policy);
+            }
+            if let Some(time) = signing_time {
+                builder = builder.signing_time(time);
+            }
+            if let Some(password) = pdf_password {
+                builder = builder.pdf_password(password);
+            }
+            builder = builder.decrypt_output(decrypt_output).allow_test_credentials(allow_test_credentials);
+            let options = builder.build()?;
+
+            let prepared = pdf::external::prepare_pdf(&input, &output, &certificates, &options, reserve)?;
+            pdfsign::storage::write_file(&digest_out, &prepared.digest)?;
+            if let Some(data_out) = &data_out {
+                pdfsign::storage::write_file(data_out, &prepared.data)?;
+            }
+            pdfsign::info!("PDF prepared: {}", output);
+            pdfsign::info!("Digest (SHA-256): {}", prepared.digest.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+            if prepared.raw {
+                pdfsign::info!("Sign the digest with the certificate's key and pass the raw signature to `pdfsign embed --certificate`");
+            } else {
+                pdfsign::info!("Have the signing service build a detached CMS signature over the digest, then run `pdfsign embed`");
+            }
+        }
+
+        // Perintah: embed
+        // Tahap 2 signing eksternal: sisipkan signature ke /Contents
+        Commands::Embed { input, output, signature, certificate, rsa_padding, allow_weak } => {
+            let output = output.unwrap_or_else(|| input.clone());
+            let certificates = match &certificate {
+                Some(path) => crypto::cms::split_certificates(&std::fs::read(path)?)?,
+                None => Vec::new(),
+            };
+            let policy = crypto::policy::AlgorithmPolicy::default().allow(&allow_weak);
+            let kind = pdf::external::embed_signature(&input, &output, &std::fs::read(&signature)?, &certificates, rsa_padding, &policy)?;
+            pdfsign::info!("PDF signed: {}", output);
+            match kind {
+                pdf::external::EmbeddedSignature::Cms => pdfsign::info!("Signature: CMS SignedData (external)"),
+                pdf::external::EmbeddedSignature::Raw => pdfsign::info!("Signature: raw signature wrapped in CMS SignedData"),
+            }
+        }
+
+        // Perintah: ltv-audit
+        // Daftar dokumen yang bukti validasinya habis dalam beberapa bulan
+        #[cfg(feature = "verify")]
+        Commands::LtvAudit { input, months, worklist } => {
+            let deadline = chrono::Utc::now()
+                .checked_add_months(chrono::Months::new(months))
+                .ok_or_else(|| anyhow!("--months {} is out of range", months))?;
+            let alerts = pdf::archive::expiry_worklist(std::path::Path::new(&input), deadline.into())?;
+            for alert in &alerts {
+                println!("{}  {}  ({})", pdf::archive::format_date(alert.expires), alert.path.display(), alert.reason);
+            }
+            if let Some(worklist) = &worklist {
+                let lines: String = alerts.iter().map(|alert| format!("{}\n", alert.path.display())).collect();
+                pdfsign::storage::write_file(worklist, lines.as_bytes()).with_context(|| format!("cannot write worklist {}", worklist))?;
+                pdfsign::info!("Worklist written: {} ({} document(s))", worklist, alerts.len());
+            }
+            // Exit code non-zero jika ada dokumen yang perlu diperbarui (untuk alert terjadwal)
+            if !alerts.is_empty() {
+                return Err(anyhow!("{} document(s) become unverifiable within {} month(s)", alerts.len(), months));
+            }
+            pdfsign::info!("No documents become unverifiable within {} month(s)", months);
+        }
+
+        // Perintah: sign-batch
+        // Menandatangani banyak PDF secara paralel dengan satu kunci
+        Commands::SignBatch { input_dir, input, output_dir, jobs, no_self_test, keep_alive, args } => {
+            let inputs: Vec<std::path::PathBuf> = input.iter().map(std::path::PathBuf::from).collect();
+            let items = pdf::batch::batch_items(input_dir.as_deref().map(std::path::Path::new), &inputs, std::path::Path::new(&output_dir))?;
+            let (key, options, timeout) = sign_options(args)?;
+            let workers = jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from));
+            let health = pdf::health::HealthPolicy { self_test: !no_self_test, keep_alive: keep_alive.map(std::time::Duration::from_secs) };
+
+            // Ctrl-C menghentikan seluruh batch; --timeout berlaku per file
+            let cancel = cancel::CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            let total = items.len();
+            let started = std::time::Instant::now();
+            let progress = pdfsign::log::Progress::items("Signing", total as u64);
+            let outcomes = pdf::batch::sign_batch_with(items, &key, &options, workers, timeout.map(std::time::Duration::from_secs), &health, &cancel, &|outcome| {
+                match &outcome.result {
+                    Ok(()) => pdfsign::info!("Signed  {} -> {} ({:.1} s)", outcome.item.input.display(), outcome.item.output.display(), outcome.elapsed.as_secs_f64()),
+                    Err(e) => pdfsign::log::failure(&format!("FAILED  {}: {:#}", outcome.item.input.display(), e)),
+                }
+                progress.advance(1);
+            })?;
+            let failed = outcomes.iter().filter(|outcome| outcome.result.is_err()).count();
+            pdfsign::info!(
+                "Batch finished: {} signed, {} failed ({} file(s), {} worker(s), {:.1} s)",
+                total - failed,
+                failed,
+                total,
+                workers.min(total),
+                started.elapsed().as_secs_f64()
+            );
+            // Exit code non-zero jika ada file yang gagal
+            if failed > 0 {
+                return Err(anyhow!("{} of {} file(s) failed to sign", failed, total));
+            }
         }
+
+        // Perintah: sign-fanout
+        // Satu dokumen, beberapa identitas, satu output per identitas
+        Commands::SignFanout { input, output_dir, profiles, args } => {
+            if args.profile.is_some() {
+                return Err(anyhow!("sign-fanout selects identities with --profiles, not --profile"));
+            }
+            let mut items = Vec::new();
+            for profile in &profiles {
+                let (key, options, timeout) = sign_options(cli::SignArgs { profile: Some(profile.clone()), ..args.clone() })
+                    .with_context(|| format!("profile {}", profile))?;
+                items.push(pdf::fanout::FanoutItem {
+                    identity: profile.clone(),
+                    key_path: key,
+                    options,
+                    timeout: timeout.map(std::time::Duration::from_secs),
+                    output: pdf::fanout::fanout_output(std::path::Path::new(&input), std::path::Path::new(&output_dir), profile),
+                });
+            }
+
+            let cancel = cancel::CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            let outcomes = pdf::fanout::sign_fanout(&input, items, &cancel, &|outcome| match &outcome.result {
+                Ok(()) => pdfsign::info!("Signed  {} -> {} ({:.1} s)", outcome.identity, outcome.output.display(), outcome.elapsed.as_secs_f64()),
+                Err(e) => pdfsign::log::failure(&format!("FAILED  {}: {:#}", outcome.identity, e)),
+            })?;
+            let failed = outcomes.iter().filter(|outcome| outcome.result.is_err()).count();
+            pdfsign::info!("Fan-out finished: {} signed, {} failed", outcomes.len() - failed, failed);
+            if failed > 0 {
+                return Err(anyhow!("{} of {} identities failed to sign", failed, outcomes.len()));
+            }
+        }
+
+        // Perintah: watch
+        // Memantau folder sampai Ctrl-C; file yang ditolak masuk karantina
+        Commands::Watch { input_dir, output_dir, quarantine_dir, max_file_size, max_in_flight, poll_interval, no_self_test, keep_alive, args } => {
+            let (key, options, timeout) = sign_options(args)?;
+            let config = pdf::watch::WatchConfig {
+                input_dir: input_dir.into(),
+                output_dir: output_dir.into(),
+                quarantine_dir: quarantine_dir.into(),
+                max_file_size: max_file_size.saturating_mul(1024 * 1024),
+                max_in_flight: max_in_flight.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from)),
+                poll_interval: std::time::Duration::from_secs(poll_interval.max(1)),
+                timeout: timeout.map(std::time::Duration::from_secs),
+                health: pdf::health::HealthPolicy { self_test: !no_self_test, keep_alive: keep_alive.map(std::time::Duration::from_secs) },
+            };
+
+            // Ctrl-C menghentikan pemantauan; file yang sedang diproses diselesaikan dulu
+            let cancel = cancel::CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            pdfsign::info!("Watching {} (Ctrl-C to stop)", config.input_dir.display());
+            pdf::watch::watch(&config, &key, &options, &cancel, &|event| match event {
+                pdf::watch::WatchEvent::Signed { input, output, elapsed } => {
+                    pdfsign::info!("Signed       {} -> {} ({:.1} s)", input.display(), output.display(), elapsed.as_secs_f64())
+                }
+                pdf::watch::WatchEvent::Quarantined { input, quarantined, reason } => {
+                    pdfsign::log::failure(&format!("QUARANTINED  {} -> {}: {}", input.display(), quarantined.display(), reason))
+                }
+            })?;
+            pdfsign::info!("Watch stopped");
+        }
+
+        // Perintah: serve
+        // Layanan HTTP signing sampai Ctrl-C; kunci dimuat sekali saat start
+        #[cfg(feature = "serve")]
+        Commands::Serve { listen, workers, max_file_size, token, trust, trust_dir, trust_store, args } => {
+            let trust = trust_sources(trust, trust_dir, trust_store)?;
+            let config = serve::ServeConfig {
+                listen,
+                workers: workers.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from)).max(1),
+                max_body: max_file_size.saturating_mul(1024 * 1024),
+                token: token.filter(|token| !token.is_empty()),
+                trust,
+                args,
+            };
+
+            // Ctrl-C menghentikan layanan; request yang sedang diproses diselesaikan dulu
+            let shutdown = cancel::CancellationToken::new();
+            let ctrl_c = shutdown.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+            serve::run(config, &shutdown)?;
+        }
+
+        // Perintah: interop-test
+        // Dokumen contoh ditandatangani per profil, lalu diperiksa validator eksternal
+        #[cfg(feature = "interop-test")]
+        Commands::InteropTest { validator_url, validator_command, profiles, output_dir, sample, allow_indeterminate, validator_timeout, args } => {
+            if args.profile.is_some() {
+                return Err(anyhow!("interop-test selects profiles with --profiles, not --profile"));
+            }
+            let profiles = if profiles.is_empty() { config_profiles(args.config.clone())? } else { profiles };
+            let config = interop::InteropConfig {
+                validator: validator_url.map_or_else(|| interop::Validator::Command(validator_command.unwrap_or_default()), interop::Validator::Dss),
+                output_dir: output_dir.into(),
+                sample: sample.map(std::path::PathBuf::from),
+                allow_indeterminate,
+                timeout: std::time::Duration::from_secs(validator_timeout.max(1)),
+            };
+            let sample = interop::sample_document(&config)?;
+            let mut items = Vec::new();
+            for profile in &profiles {
+                let (key, options, timeout) = sign_options(cli::SignArgs { profile: Some(profile.clone()), ..args.clone() })
+                    .with_context(|| format!("profile {}", profile))?;
+                items.push(pdf::fanout::FanoutItem {
+                    identity: profile.clone(),
+                    key_path: key,
+                    options,
+                    timeout: timeout.map(std::time::Duration::from_secs),
+                    output: pdf::fanout::fanout_output(&sample, &config.output_dir, profile),
+                });
+            }
+
+            let cancel = cancel::CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            pdfsign::log::status(&format!("Testing {} profile(s) against {}", items.len(), config.validator.describe()), json);
+            let results = interop::run(&config, &sample, items, &cancel, &|result| {
+                let line = format!("{:<6}{}: {} ({:.1} s)", if result.passed { "PASS" } else { "FAIL" }, result.profile, result.summary(), result.elapsed.as_secs_f64());
+                pdfsign::log::status(&line, json);
+            })?;
+            let failed = results.iter().filter(|result| !result.passed).count();
+            let status = if failed > 0 { ExitStatus::Failure } else { ExitStatus::Success };
+            if json {
+                println!("{}", output::interop_test(&config.validator.describe(), &results, status));
+            }
+            pdfsign::log::status(&format!("Interop test finished: {} passed, {} failed", results.len() - failed, failed), json);
+            if failed > 0 {
+                let failure = Failure::new(status, format!("{} of {} profile(s) failed validation", failed, results.len()));
+                return Err(if json { failure.reported() } else { failure }.into());
+            }
+        }
+
+        // Perintah: timestamp
+        // Document timestamp (RFC 3161) tanpa signature penandatangan
+        #[cfg(feature = "network")]
+        Commands::Timestamp { input, output, tsa_url, tsa_hash, tsa_username, tsa_password, tsa_token, config, profile, object_streams, stamp, timeout, retries, retry_backoff, retry_jitter } => {
+            let defaults = load_profile(config, profile)?;
+            let url = tsa_url
+                .or(defaults.tsa_url.clone())
+                .ok_or_else(|| anyhow!("--tsa-url is required (or set `tsa_url` in the profile chosen with --profile)"))?;
+            set_retry_policy(retries.or(defaults.retries), retry_backoff.or(defaults.retry_backoff), retry_jitter.or(defaults.retry_jitter));
+            let cancel = match timeout {
+                Some(seconds) => cancel::CancellationToken::with_timeout(std::time::Duration::from_secs(seconds)),
+                None => cancel::CancellationToken::new(),
+            };
+            let ctrl_c = cancel.clone();
+            ctrlc::set_handler(move || ctrl_c.cancel())?;
+
+            let mut tsa = crypto::tsa::Tsa::url(url).hash(tsa_hash.or(defaults.tsa_hash).unwrap_or_default());
+            if let Some(credentials) = tsa_credentials(tsa_username, tsa_password, tsa_token, &defaults)? {
+                tsa = tsa.credentials(credentials);
+            }
+            let object_streams = object_streams || defaults.object_streams.unwrap_or(false);
+            let appearance = timestamp_appearance(stamp)?;
+            let stamp = pdf::timestamp::timestamp_document(&input, &output, &tsa, &appearance, object_streams, &cancel)?;
+            if json {
+                println!("{}", output::timestamp(&input, &output, &tsa.url, &stamp));
+            } else {
+                pdfsign::info!("Document timestamp added: {}", output);
+                pdfsign::info!("  Field: {}", stamp.field_name);
+                pdfsign::info!("  Time:  {} (TSA: {})", pdf::text::human_time(stamp.time), tsa.url);
+            }
+        }
+
+        // Perintah: key-info
+        // Detail kunci/sertifikat/PKCS#12 dan apakah kunci cocok dengan sertifikatnya
+        Commands::KeyInfo { key, cert, p12, p12_password, passphrase } => {
+            let report = crypto::key_info::key_info(key.as_deref(), passphrase.as_deref(), cert.as_deref(), p12.as_deref(), p12_password.as_deref())?;
+            for warning in &report.warnings {
+                pdfsign::warning!("{}", warning);
+            }
+            if json {
+                let status = if report.matches == Some(false) { ExitStatus::Failure } else { ExitStatus::Success };
+                println!("{}", output::key_info(&report, status));
+            } else {
+                print!("{}", crypto::key_info::format_key_info(&report));
+            }
+            if report.matches == Some(false) {
+                let failure = Failure::new(ExitStatus::Failure, "the private key does not belong to the signer certificate; signatures made with this pair fail verification".to_string());
+                return Err(if json { failure.reported() } else { failure }.into());
+            }
+        }
+
+        // Perintah: gen-testdoc
+        // Membuat PDF sintetis untuk pengujian dan reproduksi bug
+        Commands::GenTestdoc { output, pages, page_size, landscape, text, form_fields, signature_field, xref_stream, object_streams, updates, encrypt, user_password, owner_password } => {
+            let options = pdf::testdoc::TestDocOptions {
+                pages,
+                page_size,
+                landscape,
+                text,
+                form_fields,
+                signature_field,
+                xref_stream,
+                object_streams,
+                updates,
+                encryption: encrypt.then_some(pdf::testdoc::TestEncryption { user_password, owner_password }),
+            };
+            let summary = pdf::testdoc::generate_test_document(&output, &options)?;
+            // Dengan --output - pesan status ke stderr agar tidak bercampur dengan PDF
+            let report = |line: String| pdfsign::log::status(&line, output == pdf::sign::STDIO_PATH);
+            report(format!("Test document written: {}", if output == pdf::sign::STDIO_PATH { "<stdout>" } else { &output }));
+            report(format!("  Pages: {}, revisions: {}, size: {} bytes", summary.pages, summary.revisions, summary.size));
+        }
+
+        // Perintah: prepare-field
+        // Signature field kosong (opsional dengan /SV) untuk penandatangan berikutnya
+        Commands::PrepareField { input, output, field_name, page, rect, position, invisible, seed_value, object_streams } => {
+            let page = page.unwrap_or(1);
+            let placement = match (invisible, rect, position) {
+                (true, _, _) => None,
+                (false, Some(rect), _) => Some(pdf::options::Placement::Page(page, rect)),
+                (false, None, Some(position)) => Some(pdf::options::Placement::Anchored(page, position)),
+                (false, None, None) => Some(pdf::options::Placement::Default(page)),
+            };
+            let seed_value = seed_value.map(|path| config::SeedValueFile::load(&path)).transpose()?;
+            let field = pdf::field::NewSignatureField { name: field_name, placement, seed_value };
+            let prepared = pdf::field::prepare_field(&input, &output, &field, object_streams)?;
+            pdfsign::info!("Signature field added: {}", output);
+            pdfsign::info!("  Field: {} (page {})", prepared.name, prepared.page);
+            if field.seed_value.is_some() {
+                pdfsign::info!("  Seed value: constraints for the signer stored in /SV");
+            }
+        }
+
+        // Perintah: optimize
+        // Memperkecil dokumen yang belum ditandatangani; signature tidak pernah dirusak
+        Commands::Optimize { input, output, no_recompress, object_streams, if_signed } => {
+            let options = pdf::optimize::OptimizeOptions { recompress: !no_recompress, object_streams, if_signed };
+            let summary = pdf::optimize::optimize(&input, &output, &options)?;
+            let size = |bytes: u64| pdf::text::human_size(bytes);
+            match summary.outcome {
+                pdf::optimize::Outcome::Optimized => {
+                    let saved = summary.before - summary.after;
+                    pdfsign::info!("Optimized: {}", output);
+                    pdfsign::info!("  Size: {} -> {} (-{}, -{:.0}%)", size(summary.before), size(summary.after), size(saved), saved as f64 * 100.0 / summary.before as f64);
+                    pdfsign::info!("  Removed {} unused object(s), recompressed {} stream(s)", summary.removed, summary.recompressed);
+                }
+                pdf::optimize::Outcome::NotSmaller => pdfsign::info!("Already optimal, copied unchanged ({}): {}", size(summary.before), output),
+                pdf::optimize::Outcome::Signed(signatures) => pdfsign::info!("Signed ({}), copied unchanged: {}", signatures.join(", "), output),
+            }
+        }
+
+        // Perintah: inspect
+        // Menampilkan metadata dan signature field tanpa verifikasi
+        #[cfg(feature = "verify")]
+        Commands::Inspect { input, json: inspect_json, dump_certs, cert_format } => {
+            let info = pdf::inspect::inspect_pdf(&input)?;
+            let json = json || inspect_json;
+            if json {
+                info.write_json(&mut std::io::stdout().lock())?;
+            } else {
+                print!("{}", pdf::inspect::format_inspection(&info));
+            }
+            // Sertifikat untuk tool lain (openssl verify, pencarian CT); pesan ke stderr jika stdout berisi JSON
+            if let Some(dir) = dump_certs {
+                let written = info.dump_certificates(std::path::Path::new(&dir), &cert_format)?;
+                pdfsign::log::status(&format!("Wrote {} certificate file(s) to {}", written.len(), dir), json);
+            }
+        }
+
+        // Perintah: verify-legacy
+        // Memeriksa signature format lama (sebelum CMS) terhadap file asli dan public.key
+        #[cfg(feature = "verify")]
+        Commands::VerifyLegacy { input, original, public_key } => {
+            let reports = pdf::verify::verify_legacy(&input, &original, &public_key)?;
+            print!("{}", pdf::verify::format_legacy_reports(&reports));
+            let invalid = reports.iter().filter(|report| report.status != pdf::verify::SignatureStatus::Valid).count();
+            if invalid > 0 {
+                return Err(anyhow!("{} of {} legacy signature(s) failed verification", invalid, reports.len()));
+            }
+        }
+
+        // Perintah: remove-signature
+        // Menghapus signature agar dokumen bisa diperbaiki dan ditandatangani ulang
+        Commands::RemoveSignature { input, output, field, all, remove_fields } => {
+            let selection = match field {
+                Some(name) if !all => pdf::remove::Selection::Field(name),
+                _ => pdf::remove::Selection::All,
+            };
+            let removed = pdf::remove::remove_signatures(&input, &output, &selection, remove_fields)?;
+            pdfsign::info!("Removed {} signature(s) ({}): {}", removed.len(), removed.join(", "), output);
+        }
+
+        // Perintah: refresh-appearance
+        // Membuat ulang tampilan signature tanpa mengubah bytes yang ditandatangani
+        #[cfg(all(feature = "appearance", feature = "verify"))]
+        Commands::RefreshAppearance { input, output, field, appearance_lang, appearance_template, appearance_template_file, appearance_font_size, appearance_color, appearance_image, appearance_font, object_streams, require_pdfa } => {
+            let template = match (appearance_template, appearance_template_file) {
+                (Some(template), _) => Some(template),
+                (None, Some(path)) => Some(std::fs::read_to_string(&path).with_context(|| format!("cannot read appearance template {}", path))?),
+                (None, None) => None,
+            };
+            let mut builder = pdf::options::SignatureOptions::builder().object_streams(object_streams).require_pdfa(require_pdfa);
+            match (appearance_lang, template) {
+                (Some(languages), _) => builder = builder.appearance_languages(languages), // Bahasa teks tampilan
+                (None, Some(template)) => builder = builder.appearance_template(template), // Template teks tampilan
+                (None, None) => {}
+            }
+            if let Some(size) = appearance_font_size {
+                builder = builder.appearance_font_size(size); // Ukuran font teks tampilan
+            }
+            if let Some(color) = appearance_color {
+                builder = builder.appearance_color(color); // Warna teks tampilan
+            }
+            if let Some(image) = appearance_image {
+                builder = builder.appearance_image(image); // Logo baru
+            }
+            if let Some(font) = appearance_font {
+                builder = builder.appearance_font(font); // Font yang disematkan
+            }
+            let refreshed = pdf::refresh::refresh_appearances(&input, &output, field.as_deref(), &builder.build()?)?;
+            pdfsign::info!("Refreshed {} signature appearance(s) ({}): {}", refreshed.len(), refreshed.join(", "), output);
+        }
+
+        // Perintah: pages info
+        // Menampilkan kotak halaman dan annotation untuk menentukan --rect
+        Commands::Pages { action: PagesAction::Info { input } } => {
+            let pages = pdf::pages::pages_info(&input)?;
+            if json {
+                pdf::pages::write_json(&input, &pages, &mut std::io::stdout().lock())?;
+            } else {
+                print!("{}", pdf::pages::format_pages(&pages));
+            }
+        }
+
+        // Perintah yang fiturnya tidak ikut dikompilasi
+        #[cfg(not(feature = "verify"))]
+        Commands::Verify { .. } | Commands::LtvAudit { .. } | Commands::Inspect { .. } | Commands::VerifyLegacy { .. } => {
+            return Err(pdfsign::error::missing_feature("signature verification", "verify"));
+        }
+        #[cfg(not(feature = "network"))]
+        Commands::AddLtv { .. } => return Err(pdfsign::error::missing_feature("OCSP/CRL", "network")),
+        #[cfg(not(feature = "network"))]
+        Commands::Timestamp { .. } => return Err(pdfsign::error::missing_feature("document timestamps", "network")),
+        #[cfg(not(feature = "serve"))]
+        Commands::Serve { .. } => return Err(pdfsign::error::missing_feature("HTTP signing service", "serve")),
+        #[cfg(not(feature = "interop-test"))]
+        Commands::InteropTest { .. } => return Err(pdfsign::error::missing_feature("interoperability tests", "interop-test")),
+        #[cfg(not(all(feature = "appearance", feature = "verify")))]
+        Commands::RefreshAppearance { .. } => return Err(pdfsign::error::missing_feature("signature appearance refresh", "appearance,verify")),
     }
 
     // Kembalikan Ok jika tidak ada error
     Ok(())
-}
\ No newline at end of file
+}
+/// Tampilan document timestamp dari opsi `timestamp`: stamp jika ada opsi
+/// posisi atau tampilan, widget Hidden dengan --invisible
+#[cfg(feature = "network")]
+fn timestamp_appearance(args: cli::StampArgs) -> Result<pdf::timestamp::TimestampAppearance> {
+    use pdf::timestamp::TimestampAppearance;
+
+    let cli::StampArgs { page, rect, position, invisible, appearance_lang, appearance_template, appearance_template_file, appearance_font_size, appearance_color, appearance_font } = args;
+    if invisible {
+        return Ok(TimestampAppearance::Hidden);
+    }
+    let stamp = page.is_some()
+        || rect.is_some()
+        || position.is_some()
+        || appearance_lang.is_some()
+        || appearance_template.is_some()
+        || appearance_template_file.is_some()
+        || appearance_font_size.is_some()
+        || appearance_color.is_some()
+        || appearance_font.is_some();
+    if !stamp {
+        return Ok(TimestampAppearance::Invisible);
+    }
+    #[cfg(not(feature = "appearance"))]
+    return Err(pdfsign::error::missing_feature("visible timestamp", "appearance"));
+
+    #[cfg(feature = "appearance")]
+    {
+        let page = page.unwrap_or(1);
+        let placement = match (rect, position) {
+            (Some(rect), _) => pdf::options::Placement::Page(page, rect),
+            (None, Some(position)) => pdf::options::Placement::Anchored(page, position),
+            (None, None) => pdf::options::Placement::Default(page),
+        };
+        let mut builder = pdf::options::SignatureOptions::builder().visible(placement);
+        let template = match (appearance_template, appearance_template_file) {
+            (Some(template), _) => Some(template),
+            (None, Some(path)) => Some(std::fs::read_to_string(&path).with_context(|| format!("cannot read appearance template {}", path))?),
+            (None, None) => None,
+        };
+        if let Some(languages) = appearance_lang {
+            builder = builder.appearance_languages(languages);
+        }
+        if let Some(template) = template {
+            builder = builder.appearance_template(template);
+        }
+        if let Some(size) = appearance_font_size {
+            builder = builder.appearance_font_size(size);
+        }
+        if let Some(color) = appearance_color {
+            builder = builder.appearance_color(color);
+        }
+        if let Some(font) = appearance_font {
+            builder = builder.appearance_font(font);
+        }
+        Ok(TimestampAppearance::Stamp(Box::new(builder.build()?)))
+    }
+}
+
+/// Susun kunci dan opsi signature dari opsi `sign`/`sign-batch` dan profil
+/// config, lalu pasang kebijakan pengulangan request HTTP
+///
+/// Return: path file kunci (kosong jika memakai PKCS#12/PKCS#11/KMS), opsi
+/// signature, dan --timeout dalam detik
+fn sign_options(args: cli::SignArgs) -> Result<(std::path::PathBuf, pdf::options::SignatureOptions, Option<u64>)> {
+    let cli::SignArgs { key, p12, p12_password, pkcs11_module, slot, pin, pkcs11_key, kms_key_id, kms_region, kms_certificate, keystore_cert, allow_test_credentials, embed_chain, allow_weak, name, reason, location, contact_info, metadata_file, page, rect, position, invisible, stamp_only, widget_flags, stamp_flags, field_name, appearance_lang, appearance_template, appearance_template_file, appearance_font_size, appearance_color, appearance_image, appearance_font, need_appearances, strict, drop_xfa, strict_parse, repair, classify_hook, require_pdfa, rsa_padding, digest, tsa_url, tsa_hash, tsa_username, tsa_password, tsa_token, pades_level, ltv_revocation, ltv_best_effort, certify, lock_fields, object_streams, sign_attachments, placeholder_size, warn_growth, warn_objects, signing_time, passphrase, pdf_password, decrypt_output, config, profile, timeout, retries, retry_backoff, retry_jitter, temp_strategy, temp_dir } = args;
+    // Ambil nilai default dari profil config (jika dipilih)
+    // Urutan prioritas: flag CLI > profil (beserta induknya) > default bawaan
+    // Tanpa --config, file config dicari otomatis (lihat Config::discover)
+    let mut defaults = load_profile(config, profile)?;
+    // Path di profil boleh diawali ~ seperti di command line
+    let profile_path = |value: Option<String>| value.map(|value| paths::expand_home(value.as_ref()));
+    // Isi --metadata-file menimpa profil, flag di command line menimpa keduanya
+    if let Some(path) = metadata_file {
+        paths::check(&path, "metadata file", paths::ANY)?;
+        config::MetadataFile::load(&path)?.apply_to(&mut defaults);
+    }
+    // Login TSA dibaca sebelum field profil dipindahkan ke builder
+    let tsa_login = if tsa_url.is_some() || defaults.tsa_url.is_some() {
+        tsa_credentials(tsa_username, tsa_password, tsa_token, &defaults)?
+    } else {
+        None
+    };
+
+    // Kunci privat wajib ada, baik dari CLI maupun dari profil: file kunci,
+    // bundle PKCS#12, token PKCS#11, KMS cloud, atau keystore OS; pilihan di command line
+    // menang atas profil (profil hanya berisi salah satu, lihat Profile::merge_from)
+    let (key, p12, pkcs11_module, kms_key_id, keystore_cert) = match (key, p12, pkcs11_module, kms_key_id, keystore_cert) {
+        (None, None, None, None, None) => match (profile_path(defaults.key), profile_path(defaults.p12), profile_path(defaults.pkcs11_module), defaults.kms_key_id, defaults.keystore_cert) {
+            (None, None, None, None, None) => {
+                return Err(anyhow!(
+                    "missing --key, --p12, --pkcs11-module, --kms-key-id or --keystore-cert (or `key`/`p12`/`pkcs11_module`/`kms_key_id`/`keystore_cert` in the selected profile)"
+                ))
+            }
+            sources => sources,
+        },
+        sources => sources,
+    };
+    let key = key.unwrap_or_default();
+    // File yang akan dibaca diperiksa sebelum dokumen disentuh, dengan saran
+    // nama file yang mirip jika tidak ditemukan
+    if !key.as_os_str().is_empty() {
+        paths::check(&key, "private key", paths::ANY)?;
+    }
+    if let Some(p12) = &p12 {
+        paths::check(p12, "PKCS#12 bundle", paths::PKCS12)?;
+    }
+
+    // Susun opsi signature melalui builder agar tervalidasi
+    let mut builder = pdf::options::SignatureOptions::builder();
+    if let Some(name) = name.or(defaults.name) {
+        builder = builder.name(name); // Nama penandatangan
+    }
+    if let Some(reason) = reason.or(defaults.reason) {
+        builder = builder.reason(reason); // Alasan penandatanganan
+    }
+    if let Some(location) = location.or(defaults.location) {
+        builder = builder.location(location); // Lokasi penandatanganan
+    }
+    if let Some(contact_info) = contact_info.or(defaults.contact_info) {
+        builder = builder.contact_info(contact_info); // Informasi kontak penandatangan
+    }
+    // Posisi signature: flag CLI menggantikan seluruh posisi dari profil
+    let no_placement_flags = (page, &rect, position) == (None, &None, None);
+    // Aturan posisi profil hanya berlaku jika command line tidak memilih posisi
+    let rules_apply = no_placement_flags && !invisible && field_name.is_none();
+    // Tanpa fitur appearance, signature tanpa flag posisi dibuat tak terlihat
+    let default_invisible = defaults.invisible == Some(true) || cfg!(not(feature = "appearance"));
+    let invisible = invisible || (no_placement_flags && field_name.is_none() && default_invisible);
+    // Field yang sudah ada: halaman dan rect diambil dari widget-nya
+    let field_name = field_name.or(defaults.field_name.filter(|_| no_placement_flags));
+    if let Some(field_name) = &field_name {
+        builder = builder.field_name(field_name);
+    }
+    let stamp_only = stamp_only || defaults.stamp_only.unwrap_or(false);
+    builder = builder.stamp_only(stamp_only);
+    let profile_widget_flags = match defaults.widget_flags {
+        Some(flags) => Some(flags.parse().map_err(|e| anyhow!("invalid `widget_flags` in profile: {}", e))?),
+        None => None,
+    };
+    if let Some(flags) = widget_flags.or(profile_widget_flags) {
+        builder = builder.widget_flags(flags); // Flag annotation widget
+    }
+    let profile_stamp_flags = match defaults.stamp_flags.filter(|_| stamp_only) {
+        Some(flags) => Some(flags.parse().map_err(|e| anyhow!("invalid `stamp_flags` in profile: {}", e))?),
+        None => None,
+    };
+    if let Some(flags) = stamp_flags.or(profile_stamp_flags) {
+        builder = builder.stamp_flags(flags); // Flag annotation stamp
+    }
+    if let Some(rules) = defaults.placement_rules.filter(|_| rules_apply && field_name.is_none()) {
+        let rules = rules.iter().map(config::PlacementRuleConfig::to_rule).collect::<Result<Vec<_>>>()?;
+        builder = builder.placement_rules(rules);
+    }
+    if invisible {
+        builder = builder.invisible(); // Tanpa tampilan, rect berukuran nol
+    } else if field_name.is_none() {
+        let page = page.or(defaults.page).unwrap_or(1);
+        let rect = match (rect, defaults.rect) {
+            (Some(rect), _) => Some(rect),
+            (None, Some(text)) if position.is_none() => {
+                Some(text.parse().map_err(|e| anyhow!("invalid `rect` in profile: {}", e))?)
+            }
+            _ => None,
+        };
+        match (rect, position.or(defaults.position)) {
+            (Some(rect), _) => builder = builder.visible(pdf::options::Placement::Page(page, rect)),
+            (None, Some(position)) => builder = builder.visible(pdf::options::Placement::Anchored(page, position)),
+            // Hanya --page: ukuran dan posisi default di halaman tersebut
+            (None, None) => builder = builder.visible(pdf::options::Placement::Default(page)),
+        }
+    }
+    // Teks tampilan: template (teks atau file) atau blok per bahasa; pilihan
+    // di command line menggantikan pilihan profil
+    let template_file = appearance_template_file.or(profile_path(defaults.appearance_template_file));
+    let template = match (appearance_template, template_file) {
+        (Some(template), _) => Some(template),
+        (None, Some(path)) => {
+            paths::check(&path, "appearance template", paths::ANY)?;
+            Some(std::fs::read_to_string(&path).with_context(|| format!("cannot read appearance template {}", path.display()))?)
+        }
+        (None, None) => defaults.appearance_template,
+    };
+    match (appearance_lang, template) {
+        (Some(languages), _) => builder = builder.appearance_languages(languages), // Bahasa teks tampilan
+        (None, Some(template)) => builder = builder.appearance_template(template), // Template teks tampilan
+        (None, None) => {
+            if let Some(languages) = defaults.appearance_languages {
+                builder = builder.appearance_languages(languages);
+            }
+        }
+    }
+    if let Some(size) = appearance_font_size.or(defaults.appearance_font_size) {
+        builder = builder.appearance_font_size(size); // Ukuran font teks tampilan
+    }
+    let profile_color = match defaults.appearance_color {
+        Some(color) => Some(color.parse().map_err(|e| anyhow!("invalid `appearance_color` in profile: {}", e))?),
+        None => None,
+    };
+    if let Some(color) = appearance_color.or(profile_color) {
+        builder = builder.appearance_color(color); // Warna teks tampilan
+    }
+    // Logo dari profil diabaikan untuk signature tanpa tampilan
+    if let Some(image) = appearance_image.or(profile_path(defaults.appearance_image.filter(|_| !invisible))) {
+        paths::check(&image, "appearance image", paths::IMAGE)?;
+        builder = builder.appearance_image(paths::utf8(&image, "appearance image")?); // Logo di tampilan signature
+    }
+    if let Some(font) = appearance_font.or(profile_path(defaults.appearance_font.filter(|_| !invisible))) {
+        paths::check(&font, "appearance font", paths::FONT)?;
+        builder = builder.appearance_font(paths::utf8(&font, "appearance font")?); // Font TrueType yang disematkan
+    }
+    if let Some(policy) = need_appearances.or(defaults.need_appearances) {
+        builder = builder.need_appearances(policy); // Kebijakan /NeedAppearances
+    }
+    // --strict di command line selalu menang; jika tidak ada, pakai nilai profil
+    builder = builder.strict(strict || defaults.strict.unwrap_or(false));
+    builder = builder.drop_xfa(drop_xfa || defaults.drop_xfa.unwrap_or(false));
+    builder = builder.strict_parse(strict_parse || defaults.strict_parse.unwrap_or(false));
+    builder = builder.repair(repair || defaults.repair.unwrap_or(false));
+    if let Some(command) = classify_hook.or(defaults.classify_hook) {
+        builder = builder.classify_hook(command); // Pemeriksaan dokumen sebelum signing
+    }
+    builder = builder.require_pdfa(require_pdfa || defaults.require_pdfa.unwrap_or(false));
+    builder = builder.object_streams(object_streams || defaults.object_streams.unwrap_or(false));
+    builder = builder.sign_attachments(sign_attachments || defaults.sign_attachments.unwrap_or(false));
+    if let Some(size) = placeholder_size.or(defaults.placeholder_size) {
+        builder = builder.placeholder_size(size); // Ruang /Contents signature
+    }
+    let profile_growth = match defaults.warn_growth {
+        Some(growth) => Some(growth.parse().map_err(|e| anyhow!("invalid `warn_growth` in profile: {}", e))?),
+        None => None,
+    };
+    builder = builder.budget(pdf::budget::UpdateBudget {
+        max_growth: warn_growth.or(profile_growth),
+        max_objects: warn_objects.or(defaults.warn_objects),
+    });
+    if let Some(time) = signing_time {
+        builder = builder.signing_time(time); // Waktu tetap untuk fixture
+    }
+    if let Some(padding) = rsa_padding.or(defaults.rsa_padding) {
+        builder = builder.rsa_padding(padding); // Padding jika kunci RSA
+    }
+    if let Some(digest) = digest.or(defaults.digest) {
+        builder = builder.digest(digest); // Digest signature
+    }
+    if let Some(url) = tsa_url.or(defaults.tsa_url) {
+        let hash = tsa_hash.or(defaults.tsa_hash).unwrap_or_default(); // Imprint TSA, terpisah dari --digest
+        let mut tsa = crypto::tsa::Tsa::url(url).hash(hash);
+        if let Some(credentials) = tsa_login {
+            tsa = tsa.credentials(credentials); // Login TSA, terpisah dari kunci penandatangan
+        }
+        builder = builder.timestamp(tsa); // Timestamp terpercaya dari TSA
+    }
+    if let Some(level) = pades_level.or(defaults.pades_level) {
+        builder = builder.pades(level); // Level PAdES baseline
+    }
+    builder = builder.ltv_policy(pdf::pades::LtvPolicy {
+        revocation: ltv_revocation.or(defaults.ltv_revocation).unwrap_or_default(), // Urutan OCSP/CRL
+        best_effort: ltv_best_effort || defaults.ltv_best_effort.unwrap_or(false),
+    });
+    if let Some(level) = certify.or(defaults.certify) {
+        builder = builder.certify(level); // Certification signature (DocMDP)
+    }
+    let profile_lock = match defaults.lock_fields {
+        Some(lock) => Some(lock.parse().map_err(|e| anyhow!("invalid `lock_fields` in profile: {}", e))?),
+        None => None,
+    };
+    if let Some(lock) = lock_fields.or(profile_lock) {
+        builder = builder.lock_fields(lock); // Field yang dikunci (FieldMDP)
+    }
+    if let Some(p12) = p12 {
+        builder = builder.pkcs12(paths::utf8(&p12, "PKCS#12 bundle")?, p12_password); // Kunci + rantai sertifikat dari PKCS#12
+    }
+    if let Some(module) = pkcs11_module {
+        // Kunci + rantai sertifikat di smart card / token PKCS#11
+        builder = builder.pkcs11(crypto::pkcs11::Pkcs11Token {
+            module: paths::utf8(&module, "PKCS#11 module")?.to_string(),
+            slot: slot.or(defaults.pkcs11_slot),
+            pin,
+            key_label: pkcs11_key.or(defaults.pkcs11_key),
+        });
+    }
+    if let Some(key_id) = kms_key_id {
+        // Kunci di KMS cloud; sertifikatnya dari file terpisah
+        let certificate = match kms_certificate.or(profile_path(defaults.kms_certificate)) {
+            Some(path) => {
+                paths::check(&path, "KMS certificate", paths::CERTIFICATE)?;
+                Some(paths::utf8(&path, "KMS certificate")?.to_string())
+            }
+            None => None,
+        };
+        builder = builder.kms(crypto::kms::KmsKey { key_id, region: kms_region.or(defaults.kms_region), certificate });
+    }
+    if let Some(selector) = keystore_cert {
+        // Kunci di keystore OS; rantai sertifikat dari store
+        builder = builder.keystore_cert(selector);
+    }
+    if let Some(embed_chain) = embed_chain.or(defaults.embed_chain) {
+        builder = builder.embed_chain(embed_chain); // Sertifikat yang disisipkan di signature
+    }
+    if let Some(allow_weak) = allow_weak.or(defaults.allow_weak) {
+        // Algoritma lemah yang diizinkan dihapus dari deny-list default
+        builder = builder.algorithm_policy(crypto::policy::AlgorithmPolicy::default().allow(&allow_weak));
+    }
+    if let Some(passphrase) = passphrase {
+        builder = builder.passphrase(passphrase); // Untuk kunci terenkripsi
+    }
+    if let Some(password) = pdf_password {
+        builder = builder.pdf_password(password); // Untuk dokumen terenkripsi
+    }
+    builder = builder.decrypt_output(decrypt_output); // Salinan tanpa enkripsi (eksplisit)
+    builder = builder.allow_test_credentials(allow_test_credentials); // Sertifikat dari generate-cert
+    // Tempat serialisasi sementara
+    builder = builder.scratch(scratch::ScratchSpace {
+        strategy: temp_strategy.or(defaults.temp_strategy).unwrap_or_default(),
+        dir: temp_dir.or(profile_path(defaults.temp_dir)),
+    });
+    let options = builder.build()?;
+
+    // Pengulangan request TSA/OCSP/CRL/KMS yang gagal sementara
+    set_retry_policy(retries.or(defaults.retries), retry_backoff.or(defaults.retry_backoff), retry_jitter.or(defaults.retry_jitter));
+    Ok((key, options, timeout))
+}
+
+/// Kredensial TSA dari command line atau profil; command line menang
+///
+/// Password dan token tidak ditulis di file config: profil hanya menyebut nama
+/// environment variable-nya (`tsa_password_env`, `tsa_token_env`), sehingga
+/// setiap profil bisa memakai login TSA sendiri.
+fn tsa_credentials(
+    username: Option<String>,
+    password: Option<String>,
+    token: Option<String>,
+    defaults: &config::Profile,
+) -> Result<Option<crypto::tsa::TsaCredentials>> {
+    let from_env = |name: &str, key: &str| {
+        std::env::var(name).map_err(|_| anyhow!("environment variable {} (`{}` in the profile) is not set", name, key))
+    };
+    let token = match (token, &defaults.tsa_token_env) {
+        (Some(token), _) => Some(token),
+        (None, Some(name)) if username.is_none() => Some(from_env(name, "tsa_token_env")?),
+        (None, _) => None,
+    };
+    if let Some(token) = token {
+        return Ok(Some(crypto::tsa::TsaCredentials::Bearer(token)));
+    }
+    let Some(username) = username.or_else(|| defaults.tsa_username.clone()) else {
+        return Ok(None);
+    };
+    let password = match (password, &defaults.tsa_password_env) {
+        (Some(password), _) => password,
+        (None, Some(name)) => from_env(name, "tsa_password_env")?,
+        (None, None) => return Err(anyhow!("TSA user {} needs a password: pass --tsa-password or set PDFSIGN_TSA_PASSWORD", username)),
+    };
+    Ok(Some(crypto::tsa::TsaCredentials::Basic { username, password }))
+}
+
+/// Muat profil dari --config/--profile (tanpa --config, file config dicari
+/// otomatis); tanpa --profile hasilnya profil kosong
+fn load_profile(config: Option<std::path::PathBuf>, profile: Option<String>) -> Result<config::Profile> {
+    match profile {
+        Some(profile) => {
+            let config = match config {
+                Some(config) => {
+                    paths::check(&config, "config file", paths::ANY)?;
+                    config
+                }
+                None => config::Config::discover()
+                    .ok_or_else(|| anyhow!("--profile {} needs a config file: pass --config, or create ./{} or ~/.pdfsign/{}", profile, config::FILE_NAME, config::FILE_NAME))?,
+            };
+            config::Config::load(&config)?.resolve(&profile).with_context(|| format!("in config file {}", config.display()))
+        }
+        None if config.is_some() => Err(anyhow!("--config needs --profile to choose a profile")),
+        None => Ok(config::Profile::default()),
+    }
+}
+
+/// Nama semua profil di file konfigurasi (--config, atau file yang dicari otomatis)
+#[cfg(feature = "interop-test")]
+fn config_profiles(config: Option<std::path::PathBuf>) -> Result<Vec<String>> {
+    let config = match config {
+        Some(config) => config,
+        None => config::Config::discover()
+            .ok_or_else(|| anyhow!("interop-test needs profiles: pass --profiles or --config, or create ./{} or ~/.pdfsign/{}", config::FILE_NAME, config::FILE_NAME))?,
+    };
+    let profiles: Vec<String> = config::Config::load(&config)?.profile.into_keys().collect();
+    if profiles.is_empty() {
+        return Err(anyhow!("config file {} defines no profiles", config.display()));
+    }
+    Ok(profiles)
+}
+
+/// Pasang kebijakan pengulangan request HTTP dari opsi --retries,
+/// --retry-backoff, dan --retry-jitter (nilai yang tidak diisi memakai default)
+#[cfg(feature = "network")]
+fn set_retry_policy(retries: Option<u32>, backoff_ms: Option<u64>, jitter_percent: Option<u8>) {
+    let default = crypto::http::RetryPolicy::default();
+    crypto::http::set_retry_policy(crypto::http::RetryPolicy {
+        retries: retries.unwrap_or(default.retries),
+        backoff: backoff_ms.map_or(default.backoff, std::time::Duration::from_millis),
+        jitter: jitter_percent.map_or(default.jitter, |percent| f64::from(percent) / 100.0),
+        ..default
+    });
+}
+
+/// Tanpa fitur network tidak ada request HTTP yang perlu diulang
+#[cfg(not(feature = "network"))]
+fn set_retry_policy(_retries: Option<u32>, _backoff_ms: Option<u64>, _jitter_percent: Option<u8>) {}
+
+/// Jalankan sub-command `session` (start, status, stop)
+#[cfg(all(unix, feature = "server"))]
+fn run_session(action: SessionAction) -> Result<()> {
+    use crypto::session;
+
+    // Waktu habis sesi dalam waktu lokal
+    let local_time = |time: std::time::SystemTime| chrono::DateTime::<chrono::Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string();
+    match action {
+        SessionAction::Start { key, p12, p12_password, passphrase, duration } => {
+            let identity = match (key, p12) {
+                (_, Some(path)) => session::Identity::Pkcs12 { path, password: p12_password },
+                (Some(path), None) => session::Identity::Key { path, passphrase },
+                (None, None) => return Err(anyhow!("missing --key or --p12")),
+            };
+            let status = session::start(identity, std::time::Duration::from_secs(duration))?;
+            pdfsign::info!("Signing session started for {}", status.identity);
+            pdfsign::info!("Expires at {} ({} s); run `pdfsign session stop` when done", local_time(status.expires), duration);
+        }
+        SessionAction::Status => match session::status()? {
+            Some(status) => {
+                let remaining = status.expires.duration_since(std::time::SystemTime::now()).unwrap_or_default();
+                pdfsign::info!("Active session: {}", status.identity);
+                pdfsign::info!("Expires at {} ({} s left)", local_time(status.expires), remaining.as_secs());
+            }
+            None => pdfsign::info!("No active signing session"),
+        },
+        SessionAction::Stop => match session::stop()? {
+            true => pdfsign::info!("Signing session stopped"),
+            false => pdfsign::info!("No active signing session"),
+        },
+        SessionAction::Serve => session::serve(&mut std::io::stdin(), &mut std::io::stdout())?,
+    }
+    Ok(())
+}
+
+/// Sesi signing butuh Unix domain socket dan fitur server
+#[cfg(not(all(unix, feature = "server")))]
+fn run_session(_action: SessionAction) -> Result<()> {
+    if cfg!(unix) {
+        return Err(pdfsign::error::missing_feature("signing session", "server"));
+    }
+    Err(anyhow!("signing sessions are only supported on Unix systems"))
+}
+
+/// Sumber trust anchor dari --trust; --trust-store lama diubah menjadi
+/// `store:<dir>` dengan peringatan
+#[cfg(feature = "verify")]
+fn trust_sources(mut specs: Vec<String>, trust_dir: Option<String>, trust_store: Vec<String>) -> Result<Vec<crypto::trust::TrustSource>> {
+    for dir in trust_store {
+        pdfsign::warning!("--trust-store {} is deprecated; use --trust store:{}", dir, dir);
+        specs.push(format!("store:{}", dir));
+    }
+    let trust_dir = trust_dir.map_or_else(crypto::trust::default_trust_dir, std::path::PathBuf::from);
+    crypto::trust::TrustSource::load_all(&specs, &trust_dir)
+}
+
+/// Baris `verify --summary-line`, misalnya
+/// `VALID 2/2 signatures, LTV: yes, certified: form-filling`
+///
+/// Formatnya tetap agar bisa dicocokkan di CI: status VALID, INVALID,
+/// UNVERIFIED, UNTRUSTED, atau ATTACHMENT-MISMATCH (sesuai exit code); LTV yes
+/// (semua signature punya entry /VRI di /DSS), partial, atau no; certified
+/// berisi level DocMDP atau no. Dengan --revision keduanya dibaca dari revisi
+/// itu; untuk --detached keduanya no.
+#[cfg(feature = "verify")]
+fn verify_summary_line(input: &str, detached: bool, verification: &pdf::verify::Verification, status: ExitStatus) -> Result<String> {
+    let word = match status {
+        ExitStatus::Success => "VALID",
+        ExitStatus::InvalidSignature => "INVALID",
+        ExitStatus::Unverified => "UNVERIFIED",
+        ExitStatus::UntrustedChain => "UNTRUSTED",
+        _ => "ATTACHMENT-MISMATCH",
+    };
+    let valid = verification.signatures.iter().filter(|report| report.status == pdf::verify::SignatureStatus::Valid).count();
+    let (ltv, certified) = if detached {
+        ("no", None)
+    } else {
+        let bytes = std::fs::read(input).with_context(|| format!("cannot read {}", input))?;
+        let end = verification.revision.as_ref().map_or(bytes.len(), |revision| revision.end.min(bytes.len()));
+        let doc = lopdf::Document::load_mem(&bytes[..end]).with_context(|| format!("cannot parse {}", input))?;
+        let ltv = match pdf::archive::ltv_coverage(&doc) {
+            (0, _) => "no",
+            (covered, total) if covered == total => "yes",
+            _ => "partial",
+        };
+        (ltv, pdf::inspect::certification_level(&doc))
+    };
+    Ok(format!(
+        "{} {}/{} signatures, LTV: {}, certified: {}",
+        word,
+        valid,
+        verification.signatures.len(),
+        ltv,
+        certified.unwrap_or("no")
+    ))
+}
+
+/// Jalankan `verify --recursive`: verifikasi seluruh arsip dan tulis laporannya
+///
+/// Ringkasan ditulis ke stdout jika laporan ke file, atau ke stderr jika
+/// laporan ke stdout (agar output CSV/JSON tetap bersih); dengan
+/// --output-format json ringkasan selalu ke stderr dan laporannya menjadi hasil
+#[cfg(feature = "verify")]
+#[allow(clippy::too_many_arguments)]
+fn verify_archive(
+    root: &str,
+    report: Option<&str>,
+    format: pdf::archive::ReportFormat,
+    expiring_within: u32,
+    public_key: Option<&str>,
+    policy: &crypto::policy::AlgorithmPolicy,
+    trust: &[crypto::trust::TrustSource],
+    parse: pdf::syntax::ParseMode,
+    audit: Option<&pdf::audit::AuditLog>,
+    json: bool,
+) -> Result<()> {
+    let public_key = match public_key {
+        Some(path) => Some(std::fs::read(path).with_context(|| format!("cannot read public key {}", path))?),
+        None => None,
+    };
+    let within = std::time::Duration::from_secs(u64::from(expiring_within) * 86_400);
+    let archive = pdf::archive::verify_directory(std::path::Path::new(root), public_key.as_deref(), policy, trust, within, parse)?;
+    if let Some(audit) = audit {
+        let entries: Vec<_> = archive.documents.iter().map(pdf::audit::AuditEntry::from_summary).collect();
+        audit.append(&entries)?;
+    }
+
+    let summary = match report {
+        Some(path) => {
+            // Laporan disusun di memori lalu ditulis atomik: laporan yang
+            // terpotong karena disk penuh tidak pernah tertinggal
+            let mut contents = Vec::new();
+            archive.write(format, &mut contents)?;
+            pdfsign::storage::write_file(path, &contents).with_context(|| format!("cannot write report {}", path))?;
+            let summary: fn(String) = if json { |line| pdfsign::log::status(&line, true) } else { |line| pdfsign::log::status(&line, false) };
+            summary(format!("Report written: {}", path));
+            summary
+        }
+        None => {
+            archive.write(format, &mut std::io::stdout().lock())?;
+            |line: String| pdfsign::log::status(&line, true)
+        }
+    };
+    let totals = archive.totals();
+    summary(format!(
+        "Documents:  {} ({} valid, {} invalid, {} unverified, {} unsigned, {} unreadable)",
+        totals.documents, totals.valid, totals.invalid, totals.unverified, totals.unsigned, totals.errors
+    ));
+    summary(format!("Signatures: {}", totals.signatures));
+    summary(format!("Timestamps expiring within {} days: {}", expiring_within, totals.expiring_timestamps));
+    summary(format!("Signatures without LTV data: {}", totals.missing_ltv));
+
+    // Exit code non-zero jika ada dokumen yang tidak valid atau tidak terbaca
+    let failed = totals.invalid + totals.errors;
+    if failed > 0 {
+        let status = if totals.invalid > 0 { ExitStatus::InvalidSignature } else { ExitStatus::MalformedPdf };
+        let failure = Failure::new(status, format!("{} of {} document(s) failed verification", failed, totals.documents));
+        return Err(if json { failure.reported() } else { failure }.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,323 @@
+// Output JSON untuk --output-format json (satu object per perintah di stdout)
+use pdfsign::pdf; // Hasil sign dan verify
+use pdfsign::pdf::text::json_string; // Escape string JSON
+use std::path::Path; // File input dan output sign
+
+use crate::exit::ExitStatus; // Exit code dan labelnya
+
+/// Nilai JSON untuk string opsional
+fn optional(value: Option<&str>) -> String {
+    value.map_or("null".to_string(), json_string)
+}
+
+/// Nilai JSON untuk path (opsional)
+fn path(value: Option<&Path>) -> String {
+    value.map_or("null".to_string(), |path| json_string(&path.to_string_lossy()))
+}
+
+/// Hasil `sign`
+///
+/// Parameter:
+///   - input: dokumen asli
+///   - output: dokumen hasil ("-" untuk stdout; None jika hanya --detached)
+///   - detached: file .p7s (--detached)
+///   - summary: ringkasan signature yang dibuat
+pub fn sign(input: &Path, output: Option<&Path>, detached: Option<&Path>, summary: &pdf::sign::SignSummary) -> String {
+    format!(
+        "{{\"file\": {}, \"output\": {}, \"detached\": {}, \"status\": \"signed\", \"result\": \"{}\", \"exit_code\": 0, \"signature\": {}, \"signer\": {}, \
+         \"certificate\": {}, \"timestamp\": {}, \"pades\": {}, \"certification\": {}, \"locked_fields\": {}}}",
+        path(Some(input)),
+        path(output),
+        path(detached),
+        ExitStatus::Success.label(),
+        json_string(&summary.signature),
+        json_string(&summary.signer),
+        summary.certificate.as_ref().map_or("null".to_string(), |cert| cert.to_json()),
+        optional(summary.timestamp.as_deref()),
+        optional(summary.pades),
+        optional(summary.certification.as_deref()),
+        optional(summary.locked_fields.as_deref()),
+    )
+}
+
+/// Hasil `timestamp`
+///
+/// Parameter:
+///   - input: dokumen asli
+///   - output: dokumen hasil
+///   - tsa_url: TSA yang memberi timestamp
+///   - stamp: field dan waktu document timestamp
+#[cfg(feature = "network")]
+pub fn timestamp(input: &str, output: &str, tsa_url: &str, stamp: &pdf::timestamp::DocumentTimestamp) -> String {
+    format!(
+        "{{\"file\": {}, \"output\": {}, \"status\": \"timestamped\", \"result\": \"{}\", \"exit_code\": 0, \"field\": {}, \"time\": {}, \"tsa\": {}, \"token_size\": {}}}",
+        json_string(input),
+        json_string(output),
+        ExitStatus::Success.label(),
+        json_string(&stamp.field_name),
+        json_string(&pdf::text::machine_time(stamp.time)),
+        json_string(tsa_url),
+        stamp.token_size,
+    )
+}
+
+/// Hasil `key-info`
+///
+/// Parameter:
+///   - report: detail kunci dan sertifikat
+///   - status: Failure jika kunci tidak cocok dengan sertifikat
+pub fn key_info(report: &pdfsign::crypto::key_info::KeyReport, status: ExitStatus) -> String {
+    let list = |items: Vec<&str>| format!("[{}]", items.into_iter().map(json_string).collect::<Vec<_>>().join(", "));
+    let key = report.key.as_ref().map_or("null".to_string(), |key| {
+        format!(
+            "{{\"source\": {}, \"algorithm\": {}, \"fingerprint\": {}}}",
+            json_string(&key.source),
+            json_string(&key.algorithm),
+            json_string(&key.fingerprint)
+        )
+    });
+    let certificates = report
+        .certificates
+        .iter()
+        .map(|cert| {
+            format!(
+                "{{\"certificate\": {}, \"algorithm\": {}, \"fingerprint\": {}, \"sha256\": {}, \"key_usage\": {}, \"extended_key_usage\": {}, \"ca\": {}, \"status\": {}}}",
+                cert.info.to_json(),
+                json_string(&cert.algorithm),
+                json_string(&cert.fingerprint),
+                json_string(&cert.sha256),
+                list(cert.key_usage.clone()),
+                list(cert.extended_key_usage.iter().map(String::as_str).collect()),
+                cert.ca,
+                json_string(cert.status)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{{\"status\": \"{}\", \"result\": \"{}\", \"exit_code\": {}, \"key\": {}, \"certificate_source\": {}, \"certificates\": [{}], \"matches\": {}, \"warnings\": {}}}",
+        match report.matches {
+            Some(true) => "match",
+            Some(false) => "mismatch",
+            None => "inspected",
+        },
+        status.label(),
+        status.code(),
+        key,
+        optional(report.certificate_source.as_deref()),
+        certificates.join(", "),
+        report.matches.map_or("null".to_string(), |matches| matches.to_string()),
+        list(report.warnings.iter().map(String::as_str).collect())
+    )
+}
+
+/// Hasil `sign --dry-run`
+///
+/// Parameter:
+///   - input: dokumen yang diperiksa
+///   - output: --output (jika diberikan; tidak ditulis)
+///   - plan: field, halaman, ukuran, dan ringkasan signature yang akan dibuat
+pub fn sign_dry_run(input: &Path, output: Option<&Path>, plan: &pdf::sign::DryRun) -> String {
+    let summary = &plan.summary;
+    let (field, page, rect, rule) = match &plan.placement {
+        Some(placement) => (
+            json_string(&placement.field_name),
+            placement.page.to_string(),
+            format!("[{}, {}, {}, {}]", placement.rect.left, placement.rect.bottom, placement.rect.right, placement.rect.top),
+            optional(placement.rule.as_deref()),
+        ),
+        None => ("null".to_string(), "null".to_string(), "null".to_string(), "null".to_string()),
+    };
+    format!(
+        "{{\"file\": {}, \"output\": {}, \"status\": \"dry-run\", \"result\": \"{}\", \"exit_code\": 0, \"field\": {}, \"page\": {}, \"rect\": {}, \"placement_rule\": {}, \
+         \"reuses_placeholder\": {}, \"estimated_cms_size\": {}, \"contents_size\": {}, \"signature\": {}, \"signer\": {}, \"certificate\": {}, \
+         \"timestamp\": {}, \"pades\": {}, \"certification\": {}, \"locked_fields\": {}}}",
+        path(Some(input)),
+        path(output),
+        ExitStatus::Success.label(),
+        field,
+        page,
+        rect,
+        rule,
+        plan.placement.is_none(),
+        plan.estimated_cms_size,
+        plan.contents_size,
+        json_string(&summary.signature),
+        json_string(&summary.signer),
+        summary.certificate.as_ref().map_or("null".to_string(), |cert| cert.to_json()),
+        optional(summary.timestamp.as_deref()),
+        optional(summary.pades),
+        optional(summary.certification.as_deref()),
+        optional(summary.locked_fields.as_deref()),
+    )
+}
+
+/// Hasil `verify --input`
+///
+/// Detail sertifikat penandatangan diambil dari `inspect` (dicocokkan lewat
+/// nama field), sehingga signature yang rusak pun tetap menampilkannya.
+///
+/// Parameter:
+///   - input: dokumen yang diverifikasi
+///   - verification: laporan per signature dan per lampiran
+///   - status: exit code hasil verifikasi
+///   - lang: bahasa pesan temuan
+#[cfg(feature = "verify")]
+pub fn verify(input: &str, verification: &pdf::verify::Verification, status: ExitStatus, lang: pdf::appearance::AppearanceLanguage) -> String {
+    use pdf::appearance::AppearanceLanguage;
+    use pdf::archive::DocumentStatus;
+    use pdf::verify::{Severity, SignatureStatus};
+
+    let severity = |severity: Severity| json_string(&severity.label(AppearanceLanguage::En).to_lowercase());
+    let inspection = pdf::inspect::inspect_pdf(input).ok();
+    let certificate = |field: &str| {
+        inspection
+            .as_ref()
+            .and_then(|info| info.signatures.iter().find(|sig| sig.field == field))
+            .and_then(|sig| sig.certificate.as_ref())
+            .map_or("null".to_string(), |cert| cert.to_json())
+    };
+
+    let signatures: Vec<String> = verification
+        .signatures
+        .iter()
+        .map(|report| {
+            let signature_status = match report.status {
+                SignatureStatus::Valid => "valid",
+                SignatureStatus::Invalid => "invalid",
+                SignatureStatus::Unverifiable => "unverified",
+            };
+            let byte_range: Vec<String> = report.byte_range.iter().map(i64::to_string).collect();
+            let trusted_by: Vec<String> = report
+                .trust
+                .iter()
+                .map(|anchor| format!("{{\"source\": {}, \"subject\": {}}}", json_string(&anchor.source), json_string(&anchor.subject)))
+                .collect();
+            let findings: Vec<String> = report
+                .findings
+                .iter()
+                .map(|finding| format!("{{\"severity\": {}, \"message\": {}}}", severity(finding.severity()), json_string(&finding.message(lang))))
+                .collect();
+            format!(
+                "{{\"field\": {}, \"signer\": {}, \"signing_time\": {}, \"sub_filter\": {}, \"byte_range\": [{}], \"covers_whole_document\": {}, \
+                 \"timestamp\": {}, \"status\": \"{}\", \"trusted_by\": [{}], \"certificate\": {}, \"findings\": [{}]}}",
+                json_string(&report.field),
+                optional(report.signer.as_deref()),
+                optional(report.signing_time.as_deref().and_then(pdf::text::machine_pdf_date).as_deref()),
+                optional(report.sub_filter.as_deref()),
+                byte_range.join(", "),
+                report.covers_whole_document,
+                optional(report.timestamp.map(pdf::text::machine_time).as_deref()),
+                signature_status,
+                trusted_by.join(", "),
+                certificate(&report.field),
+                findings.join(", "),
+            )
+        })
+        .collect();
+
+    let attachments: Vec<String> = verification
+        .attachments
+        .iter()
+        .map(|report| {
+            let integrity = match report.status {
+                pdf::attachments::AttachmentStatus::Valid => "ok",
+                pdf::attachments::AttachmentStatus::ChecksumMismatch | pdf::attachments::AttachmentStatus::SizeMismatch { .. } => "mismatch",
+                pdf::attachments::AttachmentStatus::NoChecksum => "not_checked",
+                pdf::attachments::AttachmentStatus::Unreadable(_) => "unreadable",
+            };
+            let signed_by: Vec<String> = report.signed_by.iter().map(|field| json_string(field)).collect();
+            let findings: Vec<String> = report
+                .findings(lang)
+                .into_iter()
+                .map(|(level, message)| format!("{{\"severity\": {}, \"message\": {}}}", severity(level), json_string(&message)))
+                .collect();
+            format!(
+                "{{\"name\": {}, \"relationship\": {}, \"size\": {}, \"integrity\": \"{}\", \"signed_by\": [{}], \"findings\": [{}]}}",
+                json_string(&report.name),
+                optional(report.relationship.as_deref()),
+                report.size.map_or("null".to_string(), |size| size.to_string()),
+                integrity,
+                signed_by.join(", "),
+                findings.join(", "),
+            )
+        })
+        .collect();
+
+    // Status dokumen sama seperti di laporan verify --recursive
+    let any = |status: SignatureStatus| verification.signatures.iter().any(|report| report.status == status);
+    let document = if any(SignatureStatus::Invalid) || status == ExitStatus::Attachment {
+        DocumentStatus::Invalid
+    } else if any(SignatureStatus::Unverifiable) {
+        DocumentStatus::Unverified
+    } else {
+        DocumentStatus::Valid
+    };
+    let revision = verification.revision.map_or("null".to_string(), |revision| {
+        format!("{{\"number\": {}, \"count\": {}, \"end\": {}}}", revision.number, revision.count, revision.end)
+    });
+    format!(
+        "{{\"file\": {}, \"status\": \"{}\", \"result\": \"{}\", \"exit_code\": {}, \"revision\": {}, \"signatures\": [{}], \"attachments\": [{}]}}",
+        json_string(input),
+        document.label(),
+        status.label(),
+        status.code(),
+        revision,
+        signatures.join(", "),
+        attachments.join(", "),
+    )
+}
+
+/// Hasil `interop-test`
+///
+/// Parameter:
+///   - validator: deskripsi validator
+///   - results: hasil per profil
+///   - status: Failure jika ada profil yang tidak lolos
+#[cfg(feature = "interop-test")]
+pub fn interop_test(validator: &str, results: &[crate::interop::ProfileResult], status: ExitStatus) -> String {
+    let profiles: Vec<String> = results
+        .iter()
+        .map(|result| {
+            format!(
+                "{{\"profile\": {}, \"output\": {}, \"status\": \"{}\", \"indication\": {}, \"sub_indication\": {}, \"signature_format\": {}, \
+                 \"expected_format\": {}, \"detail\": {}, \"elapsed_ms\": {}}}",
+                json_string(&result.profile),
+                json_string(&result.output.display().to_string()),
+                if result.passed { "passed" } else { "failed" },
+                optional(result.indication.as_deref()),
+                optional(result.sub_indication.as_deref()),
+                optional(result.signature_format.as_deref()),
+                optional(result.expected_format.as_deref()),
+                optional(result.detail.as_deref()),
+                result.elapsed.as_millis(),
+            )
+        })
+        .collect();
+    let passed = results.iter().filter(|result| result.passed).count();
+    format!(
+        "{{\"validator\": {}, \"status\": \"{}\", \"result\": \"{}\", \"exit_code\": {}, \"passed\": {}, \"failed\": {}, \"profiles\": [{}]}}",
+        json_string(validator),
+        if passed == results.len() { "passed" } else { "failed" },
+        status.label(),
+        status.code(),
+        passed,
+        results.len() - passed,
+        profiles.join(", "),
+    )
+}
+
+/// Perintah gagal sebelum menghasilkan hasil
+///
+/// Parameter:
+///   - file: dokumen yang diproses (jika perintahnya punya --input)
+///   - status: exit code
+///   - message: pesan error lengkap (termasuk context)
+pub fn error(file: Option<&str>, status: ExitStatus, message: &str) -> String {
+    format!(
+        "{{\"file\": {}, \"status\": \"error\", \"result\": \"{}\", \"exit_code\": {}, \"error\": {}}}",
+        optional(file),
+        status.label(),
+        status.code(),
+        json_string(message),
+    )
+}
@@ -0,0 +1,175 @@
+// Path file di command line dan profil: ekspansi `~`, pemeriksaan ekstensi,
+// dan saran nama file yang mirip jika file yang dibaca tidak ditemukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use clap::builder::TypedValueParser; // Parser nilai argumen clap
+use clap::error::ErrorKind; // Jenis error argumen
+use std::ffi::OsStr; // Nilai argumen apa adanya (boleh bukan UTF-8)
+use std::path::{Path, PathBuf}; // Path hasil parsing
+
+use crate::exit::{ExitStatus, Failure}; // File yang tidak ada = exit code io_error
+use pdfsign::pdf::sign::is_stdio; // "-" untuk stdin/stdout
+
+/// Ekstensi file sertifikat (DER atau PEM)
+pub const CERTIFICATE: &[&str] = &["der", "cer", "crt", "cert", "pem"];
+/// Ekstensi bundle PKCS#12
+pub const PKCS12: &[&str] = &["p12", "pfx"];
+/// Ekstensi logo tampilan signature
+pub const IMAGE: &[&str] = &["png", "jpg", "jpeg"];
+/// Ekstensi font tampilan signature (hanya TrueType)
+pub const FONT: &[&str] = &["ttf"];
+/// Tanpa pemeriksaan ekstensi (kunci privat, PDF, template, metadata, ...)
+pub const ANY: &[&str] = &[];
+
+/// Jarak edit maksimal nama file yang disarankan ("did you mean ...?")
+const MAX_DISTANCE: usize = 2;
+
+/// Parser clap untuk argumen path: `~` diekspansi, dan ekstensinya diperiksa
+/// jika argumen hanya menerima jenis file tertentu
+///
+/// Keberadaan file tidak diperiksa di sini: file yang tidak ada dilaporkan
+/// `check` sebagai io_error (exit code 8), sama seperti sebelumnya.
+#[derive(Clone, Copy)]
+pub struct PathArg {
+    extensions: &'static [&'static str], // Ekstensi yang diterima; kosong = semua
+}
+
+impl PathArg {
+    /// Path apa saja
+    pub fn any() -> PathArg {
+        PathArg { extensions: ANY }
+    }
+
+    /// Path dengan salah satu ekstensi ini (tanpa membedakan huruf besar/kecil)
+    pub fn with_extensions(extensions: &'static [&'static str]) -> PathArg {
+        PathArg { extensions }
+    }
+}
+
+impl TypedValueParser for PathArg {
+    type Value = PathBuf;
+
+    fn parse_ref(&self, cmd: &clap::Command, arg: Option<&clap::Arg>, value: &OsStr) -> std::result::Result<PathBuf, clap::Error> {
+        let name = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+        let invalid = |message: String| {
+            clap::Error::raw(ErrorKind::InvalidValue, format!("invalid value '{}' for '{}': {}\n", value.to_string_lossy(), name, message)).with_cmd(cmd)
+        };
+        if value.is_empty() {
+            return Err(invalid("expected a path".to_string()));
+        }
+        let path = expand_home(value);
+        extension_error(&path, self.extensions).map_or(Ok(path), |message| Err(invalid(message)))
+    }
+}
+
+/// Ganti `~` atau `~/` di awal path dengan direktori home (HOME, atau
+/// USERPROFILE di Windows); `~user` dan path lain tidak diubah
+pub fn expand_home(value: &OsStr) -> PathBuf {
+    let Some(text) = value.to_str() else {
+        return PathBuf::from(value);
+    };
+    let rest = match text.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with('/') || (cfg!(windows) && rest.starts_with('\\')) => &rest[1..],
+        _ => return PathBuf::from(value),
+    };
+    match home() {
+        Some(home) if rest.is_empty() => home,
+        Some(home) => home.join(rest),
+        None => PathBuf::from(value),
+    }
+}
+
+/// Direktori home user
+fn home() -> Option<PathBuf> {
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+/// Periksa file yang akan dibaca sebelum perintah mulai bekerja: ekstensi
+/// (untuk nilai dari profil, yang tidak melewati `PathArg`) dan keberadaannya
+///
+/// Parameter:
+///   - path: path file; "-" (stdin) selalu diterima
+///   - what: nama file untuk pesan, misalnya "private key"
+///   - extensions: ekstensi yang diterima; kosong = semua
+pub fn check(path: &Path, what: &str, extensions: &[&str]) -> Result<()> {
+    if is_stdio(path) {
+        return Ok(());
+    }
+    if let Some(message) = extension_error(path, extensions) {
+        bail!("{} {}: {}", what, path.display(), message);
+    }
+    if path.exists() {
+        return Ok(());
+    }
+    let hint = suggestion(path, extensions).map_or_else(String::new, |candidate| format!(" (did you mean {}?)", candidate.display()));
+    Err(Failure::new(ExitStatus::Io, format!("{} {} not found{}", what, path.display(), hint)).into())
+}
+
+/// Path sebagai teks untuk opsi library yang masih berupa String
+pub fn utf8<'a>(path: &'a Path, what: &str) -> Result<&'a str> {
+    path.to_str().ok_or_else(|| anyhow!("{} {} is not valid UTF-8", what, path.display()))
+}
+
+/// Pesan jika ekstensi path tidak termasuk `extensions`
+fn extension_error(path: &Path, extensions: &[&str]) -> Option<String> {
+    if extensions.is_empty() || is_stdio(path) {
+        return None;
+    }
+    let extension = path.extension().and_then(OsStr::to_str).unwrap_or_default();
+    if extensions.iter().any(|expected| expected.eq_ignore_ascii_case(extension)) {
+        return None;
+    }
+    let names: Vec<String> = extensions.iter().map(|extension| format!(".{}", extension)).collect();
+    let expected = match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    };
+    let hint = suggestion(path, extensions).map_or_else(String::new, |candidate| format!(" (did you mean {}?)", candidate.display()));
+    Some(format!("expected a {} file{}", expected, hint))
+}
+
+/// File di direktori yang sama yang kemungkinan dimaksud: nama sama dengan
+/// ekstensi yang diterima (cert.der -> cert.pem), atau nama yang berbeda
+/// paling banyak `MAX_DISTANCE` huruf (certifcate.der -> certificate.der)
+fn suggestion(path: &Path, extensions: &[&str]) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    let stem = path.file_stem()?.to_str()?.to_lowercase();
+    let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+    let entries = std::fs::read_dir(parent.unwrap_or(Path::new("."))).ok()?;
+    let accepted = |candidate: &Path| {
+        let extension = candidate.extension().and_then(OsStr::to_str).unwrap_or_default();
+        extensions.is_empty() || extensions.iter().any(|expected| expected.eq_ignore_ascii_case(extension))
+    };
+
+    let mut best: Option<(usize, String)> = None;
+    for entry in entries.flatten() {
+        let Ok(candidate) = entry.file_name().into_string() else {
+            continue;
+        };
+        let candidate_path = Path::new(&candidate);
+        if candidate.to_lowercase() == name || !accepted(candidate_path) || !entry.path().is_file() {
+            continue;
+        }
+        let same_stem = candidate_path.file_stem().and_then(OsStr::to_str).is_some_and(|other| other.to_lowercase() == stem);
+        let distance = if same_stem { 0 } else { distance(&name, &candidate.to_lowercase()) };
+        if distance <= MAX_DISTANCE && best.as_ref().is_none_or(|(best, best_name)| (distance, &candidate) < (*best, best_name)) {
+            best = Some((distance, candidate));
+        }
+    }
+    best.map(|(_, candidate)| path.with_file_name(candidate))
+}
+
+/// Jarak edit (Levenshtein) antara dua nama file
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b) in b.iter().enumerate() {
+            current.push((previous[j] + usize::from(a != b)).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,535 @@
+// Import library yang diperlukan
+#[cfg(feature = "appearance")]
+use anyhow::{anyhow, Result}; // Untuk error handling yang fleksibel
+#[cfg(feature = "appearance")]
+use std::sync::{Arc, Mutex}; // Cache tampilan yang dipakai bersama
+
+#[cfg(feature = "appearance")]
+use crate::pdf::font::EmbeddedFont; // Font TrueType yang disematkan
+#[cfg(feature = "appearance")]
+use crate::pdf::image::AppearanceImage; // Logo di tampilan signature
+#[cfg(feature = "appearance")]
+use crate::pdf::options::{Rect, SignatureOptions}; // Opsi dan ukuran signature
+#[cfg(feature = "appearance")]
+use crate::pdf::text::win_ansi_byte; // Karakter yang ada di font standar
+
+/// Bahasa untuk blok teks pada tampilan signature
+///
+/// Dokumen resmi dwibahasa bisa memakai dua blok sekaligus,
+/// misalnya `--appearance-lang id,en` (Indonesia di atas, Inggris di bawah).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum AppearanceLanguage {
+    /// Bahasa Indonesia
+    Id,
+    /// English
+    En,
+}
+
+/// Format waktu penandatanganan di tampilan, misalnya "2026-01-20 10:53:37 +07:00"
+#[cfg(feature = "appearance")]
+pub const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+/// Contoh tanggal selebar `DATE_FORMAT`, untuk menghitung layout sebelum
+/// waktu penandatanganan diketahui
+#[cfg(feature = "appearance")]
+const DATE_SAMPLE: &str = "0000-00-00 00:00:00 +00:00";
+
+/// Warna teks tampilan (RGB, masing-masing 0.0 - 1.0)
+///
+/// Dibaca dari teks "#RRGGBB", misalnya `--appearance-color "#1a4d8f"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextColor {
+    pub red: f32,   // Komponen merah
+    pub green: f32, // Komponen hijau
+    pub blue: f32,  // Komponen biru
+}
+
+impl std::str::FromStr for TextColor {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<TextColor, String> {
+        let hex = text.trim().trim_start_matches('#');
+        let channel = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .map(|value| f32::from(value) / 255.0)
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(red), Some(green), Some(blue)) => Ok(TextColor { red, green, blue }),
+            _ => Err(format!("expected a color as #RRGGBB, got `{}`", text)),
+        }
+    }
+}
+
+/// Ruang warna device untuk teks tampilan
+///
+/// Dokumen PDF/A hanya boleh memakai warna device yang sesuai dengan
+/// OutputIntent-nya, jadi warna teks dikonversi ke ruang warna itu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// DeviceGray (operator g)
+    Gray,
+    /// DeviceRGB (operator rg)
+    #[default]
+    Rgb,
+    /// DeviceCMYK (operator k)
+    Cmyk,
+}
+
+#[cfg(feature = "appearance")]
+impl TextColor {
+    /// Operator warna isi untuk content stream dalam ruang warna `space`
+    fn fill_operator(self, space: ColorSpace) -> String {
+        let TextColor { red, green, blue } = self;
+        match space {
+            ColorSpace::Gray => format!("{:.3} g", 0.299 * red + 0.587 * green + 0.114 * blue),
+            ColorSpace::Rgb => format!("{:.3} {:.3} {:.3} rg", red, green, blue),
+            ColorSpace::Cmyk => {
+                let black = 1.0 - red.max(green).max(blue);
+                let ink = |channel: f32| if black < 1.0 { (1.0 - channel - black) / (1.0 - black) } else { 0.0 };
+                format!("{:.3} {:.3} {:.3} {:.3} k", ink(red), ink(green), ink(blue), black)
+            }
+        }
+    }
+}
+
+/// Font dan ruang warna tampilan yang bergantung pada dokumen tujuan
+///
+/// Untuk dokumen biasa font diambil dari `--appearance-font` (atau Helvetica
+/// standar) dan warna dalam RGB; untuk dokumen PDF/A lihat `pdfa::PdfaPlan`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppearanceTarget {
+    pub font: Option<String>,    // File TrueType yang disematkan; None = Helvetica standar
+    pub color_space: ColorSpace, // Ruang warna teks
+}
+
+/// Bagian dari satu baris template: teks tetap atau variabel
+#[derive(Clone, Debug, PartialEq)]
+enum TemplatePart {
+    Text(String),
+    Variable(Variable),
+}
+
+/// Variabel yang bisa dipakai di template tampilan
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Variable {
+    Name,     // {name}
+    Reason,   // {reason}
+    Location, // {location}
+    Contact,  // {contact}
+    Date,     // {date}, waktu penandatanganan
+}
+
+/// Nama variabel template beserta nilainya
+const VARIABLES: [(&str, Variable); 5] = [
+    ("name", Variable::Name),
+    ("reason", Variable::Reason),
+    ("location", Variable::Location),
+    ("contact", Variable::Contact),
+    ("date", Variable::Date),
+];
+
+/// Parse template tampilan menjadi baris-baris
+///
+/// Baris dipisah newline atau `\n`; `{name}`, `{reason}`, `{location}`,
+/// `{contact}`, dan `{date}` diganti nilainya; `{{` dan `}}` menghasilkan
+/// kurung kurawal biasa.
+///
+/// Return: error jika ada variabel yang tidak dikenal atau kurung yang tidak ditutup
+fn parse_template(template: &str) -> std::result::Result<Vec<Vec<TemplatePart>>, String> {
+    let template = template.replace("\\n", "\n");
+    let mut lines = Vec::new();
+    for line in template.trim_end_matches(['\r', '\n']).lines() {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = line.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest.find('}').ok_or_else(|| format!("unclosed `{{` in appearance template line `{}`", line))?;
+                    let name = &rest[..end];
+                    let variable = VARIABLES
+                        .iter()
+                        .find(|(known, _)| *known == name)
+                        .map(|(_, variable)| *variable)
+                        .ok_or_else(|| format!("unknown appearance template variable `{{{}}}` (use name, reason, location, contact, date)", name))?;
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Variable(variable));
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Err(format!("unmatched `}}` in appearance template line `{}`", line)),
+                _ => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        lines.push(parts);
+    }
+    if lines.is_empty() {
+        return Err("appearance template is empty".to_string());
+    }
+    Ok(lines)
+}
+
+/// Periksa template tampilan tanpa menyusunnya (dipakai saat validasi opsi)
+pub fn validate_template(template: &str) -> std::result::Result<(), String> {
+    parse_template(template).map(|_| ())
+}
+
+#[cfg(feature = "appearance")]
+impl AppearanceLanguage {
+    /// Template bawaan tampilan signature dalam bahasa ini
+    /// (baris alasan dan lokasi hanya jika nilainya ada)
+    fn template(self, options: &SignatureOptions) -> String {
+        let (signed_by, date, reason, location) = match self {
+            AppearanceLanguage::Id => ("Ditandatangani secara digital oleh", "Tanggal", "Alasan", "Lokasi"),
+            AppearanceLanguage::En => ("Digitally signed by", "Date", "Reason", "Location"),
+        };
+        let mut template = format!("{} {{name}}\n{}: {{date}}", signed_by, date);
+        if !options.reason.is_empty() {
+            template.push_str(&format!("\n{}: {{reason}}", reason));
+        }
+        if !options.location.is_empty() {
+            template.push_str(&format!("\n{}: {{location}}", location));
+        }
+        template
+    }
+
+    /// Template bawaan stamp document timestamp dalam bahasa ini
+    /// (`{name}` berisi host TSA, lihat `timestamp::TimestampAppearance`)
+    pub fn timestamp_template(self) -> &'static str {
+        match self {
+            AppearanceLanguage::Id => "Dibubuhi cap waktu pada {date}",
+            AppearanceLanguage::En => "Timestamped on {date}",
+        }
+    }
+}
+
+/// Tampilan signature yang sudah dikompilasi: layout, teks tetap, dan logo
+/// (sudah dikompres). Per dokumen hanya tanggal yang disisipkan.
+///
+/// Teks diambil dari `options.appearance_template`, atau satu blok bawaan per
+/// bahasa di `options.appearance_languages` (nama, tanggal, alasan, lokasi),
+/// berurutan dari atas ke bawah. Logo (jika
+/// ada) ditaruh di kiri dengan rasio aslinya, maksimal 40% lebar kotak, dan
+/// teks mengisi sisanya. Ukuran font dikecilkan agar semua baris muat.
+/// Font yang dipakai adalah /F1 dan logo adalah /Im1 (lihat `resources`).
+#[cfg(feature = "appearance")]
+#[derive(Debug)]
+pub struct CompiledAppearance {
+    key: AppearanceKey,           // Input yang menghasilkan tampilan ini
+    image: Option<AppearanceImage>, // Logo, siap disalin ke dokumen
+    font: Option<EmbeddedFont>,   // Font yang disematkan; None = Helvetica standar
+    head: Vec<u8>,                // Gambar logo dan pengaturan font sebelum baris teks
+    lines: Vec<Vec<Segment>>,     // Isi setiap baris teks
+}
+
+/// Potongan baris teks yang sudah dikompilasi
+#[cfg(feature = "appearance")]
+#[derive(Debug)]
+enum Segment {
+    Text(Vec<u8>), // Teks tetap, sudah di-encode untuk literal string PDF
+    Date,          // Waktu penandatanganan, diisi per dokumen
+}
+
+/// Semua input yang menentukan isi tampilan (untuk mendeteksi cache yang usang)
+#[cfg(feature = "appearance")]
+#[derive(Clone, Debug, PartialEq)]
+struct AppearanceKey {
+    name: String,
+    reason: String,
+    location: String,
+    contact_info: String,
+    languages: Vec<AppearanceLanguage>,
+    template: Option<String>,
+    font_size: Option<f32>,
+    color: Option<TextColor>,
+    image: Option<String>,
+    target: AppearanceTarget,
+    size: (f32, f32),
+}
+
+#[cfg(feature = "appearance")]
+impl AppearanceKey {
+    fn new(options: &SignatureOptions, rect: &Rect, target: &AppearanceTarget) -> AppearanceKey {
+        AppearanceKey {
+            name: options.name.clone(),
+            reason: options.reason.clone(),
+            location: options.location.clone(),
+            contact_info: options.contact_info.clone(),
+            languages: options.appearance_languages.clone(),
+            template: options.appearance_template.clone(),
+            font_size: options.appearance_font_size,
+            color: options.appearance_color,
+            image: options.appearance_image.clone(),
+            target: target.clone(),
+            size: (rect.width(), rect.height()),
+        }
+    }
+}
+
+#[cfg(feature = "appearance")]
+impl CompiledAppearance {
+    /// Susun layout tampilan untuk kotak berukuran `rect`, baca logonya, dan
+    /// font yang disematkan (`target.font`)
+    pub fn compile(options: &SignatureOptions, rect: &Rect, target: &AppearanceTarget) -> Result<CompiledAppearance> {
+        let image = options.appearance_image.as_deref().map(AppearanceImage::load).transpose()?;
+        let font = target.font.as_deref().map(EmbeddedFont::load).transpose()?;
+
+        // Template dari opsi, atau satu blok bawaan per bahasa
+        let template = match &options.appearance_template {
+            Some(template) => template.clone(),
+            None => options
+                .appearance_languages
+                .iter()
+                .map(|lang| lang.template(options))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        // Ganti variabel dengan nilainya; {date} baru diisi per dokumen
+        let lines: Vec<Vec<TemplatePart>> = parse_template(&template)
+            .map_err(|e| anyhow!(e))?
+            .into_iter()
+            .map(|line| {
+                line.into_iter()
+                    .map(|part| match part {
+                        TemplatePart::Variable(Variable::Name) => TemplatePart::Text(options.name.clone()),
+                        TemplatePart::Variable(Variable::Reason) => TemplatePart::Text(options.reason.clone()),
+                        TemplatePart::Variable(Variable::Location) => TemplatePart::Text(options.location.clone()),
+                        TemplatePart::Variable(Variable::Contact) => TemplatePart::Text(options.contact_info.clone()),
+                        part => part,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Helvetica standar hanya punya karakter WinAnsi, font yang disematkan
+        // hanya glyph yang ada di file-nya; sisanya digambar sebagai '?'
+        // (signature dictionary tetap menyimpan teks lengkap)
+        let drawable = |ch: char| font.as_ref().map_or(win_ansi_byte(ch).is_some(), |font| font.has_glyph(ch));
+        let mut missing: Vec<char> = lines
+            .iter()
+            .flatten()
+            .filter_map(|part| match part {
+                TemplatePart::Text(text) => Some(text.chars()),
+                TemplatePart::Variable(_) => None,
+            })
+            .flatten()
+            .filter(|&ch| !drawable(ch))
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if !missing.is_empty() {
+            crate::warning!(
+                "the appearance font has no glyphs for \"{}\"; they are drawn as '?' (the signature metadata keeps the full text)",
+                missing.iter().collect::<String>()
+            );
+        }
+
+        let padding = 2.0;
+        let mut head = Vec::new();
+
+        // Logo: diskalakan agar muat tinggi kotak dan 40% lebarnya, di tengah secara vertikal
+        let mut text_left = padding;
+        if let Some(image) = image.as_ref().filter(|image| image.width > 0 && image.height > 0) {
+            let aspect = image.width as f32 / image.height as f32;
+            let box_height = (rect.height() - 2.0 * padding).max(0.0);
+            let box_width = ((rect.width() - 2.0 * padding) * 0.4).max(0.0);
+            let (draw_width, draw_height) = if box_height * aspect <= box_width {
+                (box_height * aspect, box_height)
+            } else {
+                (box_width, box_width / aspect)
+            };
+            let bottom = (rect.height() - draw_height) / 2.0;
+            head.extend_from_slice(
+                format!("q\n{:.2} 0 0 {:.2} {:.2} {:.2} cm\n/Im1 Do\nQ\n", draw_width, draw_height, padding, bottom).as_bytes(),
+            );
+            text_left += draw_width + padding;
+        }
+
+        // Ukuran font: dari opsi, atau maksimal 10pt dan dikecilkan agar muat tinggi
+        // dan lebar sisa kotak (lebar rata-rata karakter Helvetica kira-kira
+        // setengah ukuran font)
+        let longest = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|part| match part {
+                        TemplatePart::Text(text) => text.chars().count(),
+                        TemplatePart::Variable(_) => DATE_SAMPLE.len(),
+                    })
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(1)
+            .max(1) as f32;
+        let font_size = options.appearance_font_size.unwrap_or_else(|| {
+            10.0_f32
+                .min((rect.height() - 2.0 * padding) / (lines.len() as f32 * 1.2))
+                .min((rect.width() - text_left - padding) / (longest * 0.5))
+                .max(1.0)
+        });
+        let color = options.appearance_color.unwrap_or_default();
+
+        head.extend_from_slice(
+            format!(
+                "q\nBT\n/F1 {:.2} Tf\n{}\n{:.2} TL\n{:.2} {:.2} Td\n",
+                font_size,
+                color.fill_operator(target.color_space),
+                font_size * 1.2,
+                text_left,
+                rect.height() - padding - font_size
+            )
+            .as_bytes(),
+        );
+
+        let lines = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|part| match part {
+                        TemplatePart::Text(text) => Segment::Text(encode_text(text, font.as_ref())),
+                        TemplatePart::Variable(_) => Segment::Date,
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(CompiledAppearance { key: AppearanceKey::new(options, rect, target), image, font, head, lines })
+    }
+
+    /// Content stream tampilan dengan waktu penandatanganan `signed_at`
+    pub fn content(&self, signed_at: &str) -> Vec<u8> {
+        let mut content = self.head.clone();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                content.extend_from_slice(b"T*\n");
+            }
+            content.push(b'(');
+            for segment in line {
+                match segment {
+                    Segment::Text(text) => content.extend_from_slice(text),
+                    Segment::Date => content.extend_from_slice(&encode_text(signed_at, self.font.as_ref())),
+                }
+            }
+            content.extend_from_slice(b") Tj\n");
+        }
+        content.extend_from_slice(b"ET\nQ");
+        content
+    }
+
+    /// Logo yang ditampilkan (jika ada)
+    pub fn image(&self) -> Option<&AppearanceImage> {
+        self.image.as_ref()
+    }
+
+    /// Font /F1 untuk `resources`: referensi ke font yang disematkan (ditambahkan
+    /// ke `doc`), atau dictionary Helvetica standar
+    pub fn add_font(&self, doc: &mut lopdf::Document) -> lopdf::Object {
+        match &self.font {
+            Some(font) => lopdf::Object::Reference(font.add_to(doc)),
+            None => lopdf::Object::Dictionary(standard_font()),
+        }
+    }
+}
+
+/// Cache tampilan yang dikompilasi, dipakai bersama oleh semua salinan
+/// `SignatureOptions` (clone), sehingga batch dokumen dengan opsi yang sama
+/// hanya membaca, mendekode, dan mengompres logo serta menyusun layout sekali
+///
+/// Jika nama, alasan, lokasi, kontak, bahasa, template, ukuran font, warna,
+/// logo, font dan ruang warna dokumen tujuan, atau ukuran kotak berubah,
+/// tampilan dikompilasi ulang.
+#[cfg(feature = "appearance")]
+#[derive(Clone, Default)]
+pub struct AppearanceCache(Arc<Mutex<Option<Arc<CompiledAppearance>>>>);
+
+/// Tanpa fitur appearance tidak ada yang dikompilasi; tipe ini hanya ada agar
+/// `SignatureOptions` dan `PdfSigner` sama di semua kombinasi fitur
+#[cfg(all(feature = "sign", not(feature = "appearance")))]
+#[derive(Clone, Debug, Default)]
+pub struct AppearanceCache;
+
+#[cfg(feature = "appearance")]
+impl AppearanceCache {
+    /// Ambil tampilan dari cache, atau kompilasi jika belum ada / sudah usang
+    pub fn get_or_compile(&self, options: &SignatureOptions, rect: &Rect, target: &AppearanceTarget) -> Result<Arc<CompiledAppearance>> {
+        let key = AppearanceKey::new(options, rect, target);
+        let mut cached = self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(appearance) = cached.as_ref().filter(|appearance| appearance.key == key) {
+            return Ok(Arc::clone(appearance));
+        }
+        let appearance = Arc::new(CompiledAppearance::compile(options, rect, target)?);
+        *cached = Some(Arc::clone(&appearance));
+        Ok(appearance)
+    }
+}
+
+#[cfg(feature = "appearance")]
+impl std::fmt::Debug for AppearanceCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let compiled = self.0.lock().map(|cached| cached.is_some()).unwrap_or(false);
+        f.debug_struct("AppearanceCache").field("compiled", &compiled).finish()
+    }
+}
+
+/// Font standar Helvetica (WinAnsiEncoding) untuk /F1; tidak disematkan, jadi
+/// tidak boleh dipakai di dokumen PDF/A
+#[cfg(feature = "sign")]
+pub fn standard_font() -> lopdf::Dictionary {
+    let mut font = lopdf::Dictionary::new();
+    font.set("Type", lopdf::Object::Name(b"Font".to_vec()));
+    font.set("Subtype", lopdf::Object::Name(b"Type1".to_vec()));
+    font.set("BaseFont", lopdf::Object::Name(b"Helvetica".to_vec()));
+    font.set("Encoding", lopdf::Object::Name(b"WinAnsiEncoding".to_vec()));
+    font
+}
+
+/// Resource dictionary untuk appearance stream: /F1 = `font` (lihat
+/// `CompiledAppearance::add_font`) dan /Im1 = logo jika `image` berisi object
+/// ID image XObject. Tampilan kosong tidak mereferensikan font apa pun.
+#[cfg(feature = "sign")]
+pub fn resources(font: Option<lopdf::Object>, image: Option<lopdf::ObjectId>) -> lopdf::Dictionary {
+    let mut resources = lopdf::Dictionary::new();
+    if let Some(font) = font {
+        let mut fonts = lopdf::Dictionary::new();
+        fonts.set("F1", font);
+        resources.set("Font", lopdf::Object::Dictionary(fonts));
+    }
+    if let Some(image_id) = image {
+        let mut images = lopdf::Dictionary::new();
+        images.set("Im1", lopdf::Object::Reference(image_id));
+        resources.set("XObject", lopdf::Object::Dictionary(images));
+    }
+    resources
+}
+
+/// Encode teks untuk literal string PDF dengan WinAnsiEncoding: escape \ ( ),
+/// karakter yang tidak ada di WinAnsi (atau tidak punya glyph di `font`) diganti '?'
+#[cfg(feature = "appearance")]
+fn encode_text(text: &str, font: Option<&EmbeddedFont>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for ch in text.chars() {
+        match ch {
+            '\\' | '(' | ')' => {
+                bytes.push(b'\\');
+                bytes.push(ch as u8);
+            }
+            _ if font.is_some_and(|font| !font.has_glyph(ch)) => bytes.push(b'?'),
+            _ => bytes.push(win_ansi_byte(ch).unwrap_or(b'?')),
+        }
+    }
+    bytes
+}
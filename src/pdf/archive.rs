@@ -0,0 +1,490 @@
+// Import library yang diperlukan
+use anyhow::{Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object}; // Struktur PDF
+use sha1::{Digest, Sha1}; // Kunci VRI = SHA-1 dari signature
+use std::fs; // Menelusuri direktori dan membaca file
+use std::io::Write; // Menulis laporan
+use std::path::{Path, PathBuf}; // Path dokumen di arsip
+use std::time::{Duration, SystemTime}; // Batas waktu kedaluwarsa
+use x509_cert::der::Decode; // Parsing sertifikat TSA
+
+use crate::crypto::cms; // Timestamp di dalam CMS SignedData
+use crate::crypto::policy::AlgorithmPolicy; // Deny-list algoritma lemah
+use crate::crypto::trust::TrustSource; // Sumber trust anchor (--trust)
+use crate::crypto::tsa; // Sertifikat dari TimeStampToken
+use crate::pdf::{form, verify}; // Signature field dan verifikasi
+use crate::pdf::syntax::{self, ParseMode}; // Pemeriksaan struktur file (--strict-parse)
+use crate::pdf::text::{self, json_string}; // Escape string JSON, waktu RFC 3339
+use crate::pdf::verify::SignatureStatus; // Status per signature
+use crate::storage::collect_pdfs; // Semua file .pdf di bawah direktori
+
+/// Format laporan arsip
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ReportFormat {
+    /// Satu baris per dokumen (untuk spreadsheet)
+    Csv,
+    /// Ringkasan dan daftar dokumen dalam satu object JSON
+    Json,
+}
+
+impl ReportFormat {
+    /// Tebak format dari ekstensi file laporan (.json = JSON, selain itu CSV)
+    pub fn from_path(path: &str) -> ReportFormat {
+        if path.to_ascii_lowercase().ends_with(".json") {
+            ReportFormat::Json
+        } else {
+            ReportFormat::Csv
+        }
+    }
+}
+
+/// Status keseluruhan satu dokumen di arsip
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentStatus {
+    /// Semua signature valid
+    Valid,
+    /// Ada signature yang tidak valid
+    Invalid,
+    /// Tidak ada yang tidak valid, tetapi ada signature yang tidak bisa diperiksa
+    Unverified,
+    /// Dokumen tidak punya signature
+    Unsigned,
+    /// Dokumen tidak bisa dibaca
+    Error,
+}
+
+impl DocumentStatus {
+    /// Nama status di laporan
+    pub fn label(self) -> &'static str {
+        match self {
+            DocumentStatus::Valid => "valid",
+            DocumentStatus::Invalid => "invalid",
+            DocumentStatus::Unverified => "unverified",
+            DocumentStatus::Unsigned => "unsigned",
+            DocumentStatus::Error => "error",
+        }
+    }
+}
+
+/// Hasil pemeriksaan satu dokumen di arsip
+pub struct DocumentSummary {
+    pub path: PathBuf,                       // Path dokumen
+    pub status: DocumentStatus,              // Status keseluruhan
+    pub signatures: usize,                   // Jumlah signature (termasuk document timestamp)
+    pub valid: usize,                        // Signature valid
+    pub invalid: usize,                      // Signature tidak valid
+    pub unverified: usize,                   // Signature yang tidak bisa diperiksa
+    pub timestamps: usize,                   // Timestamp RFC 3161 (di signature atau document timestamp)
+    pub expiring_timestamps: usize,          // Timestamp yang sertifikat TSA-nya habis dalam jangka waktu audit
+    pub earliest_expiry: Option<SystemTime>, // Sertifikat TSA yang paling cepat habis
+    pub missing_ltv: usize,                  // Signature tanpa entry /VRI di /DSS
+    pub error: Option<String>,               // Alasan dokumen tidak bisa dibaca
+}
+
+/// Laporan verifikasi seluruh arsip
+pub struct ArchiveReport {
+    pub root: PathBuf,                     // Direktori yang ditelusuri
+    pub expiring_within: Duration,         // Jangka waktu untuk timestamp yang akan habis
+    pub documents: Vec<DocumentSummary>,   // Per dokumen, urut berdasarkan path
+}
+
+/// Jumlah dokumen per status dan total temuan di arsip
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArchiveTotals {
+    pub documents: usize,           // Dokumen PDF yang ditemukan
+    pub valid: usize,               // Dokumen yang semua signature-nya valid
+    pub invalid: usize,             // Dokumen dengan signature tidak valid
+    pub unverified: usize,          // Dokumen dengan signature yang tidak bisa diperiksa
+    pub unsigned: usize,            // Dokumen tanpa signature
+    pub errors: usize,              // Dokumen yang tidak bisa dibaca
+    pub signatures: usize,          // Total signature
+    pub expiring_timestamps: usize, // Total timestamp yang akan habis
+    pub missing_ltv: usize,         // Total signature tanpa data LTV
+}
+
+/// Data validasi jangka panjang satu signature (di luar hasil verifikasi)
+struct LongTermInfo {
+    timestamp_expires: Option<SystemTime>, // Sertifikat TSA yang paling cepat habis
+    chain_expires: Option<SystemTime>,     // Sertifikat penandatangan (rantai di CMS) yang paling cepat habis
+    signed_until: i64,                     // Akhir ByteRange (urutan signature di file)
+    ltv: bool,                             // Ada entry /VRI untuk signature ini
+    document_timestamp: bool,              // Signature adalah document timestamp
+}
+
+/// Verifikasi semua PDF di sebuah direktori (termasuk subdirektori)
+///
+/// File dikenali dari ekstensi .pdf (huruf besar/kecil). Symlink tidak diikuti
+/// agar arsip dengan link melingkar tetap selesai. Dokumen yang tidak bisa
+/// dibaca dicatat dengan status error; penelusuran tetap berlanjut.
+///
+/// Parameter:
+///   - root: direktori arsip
+///   - public_key: isi file kunci publik; jika None, dipakai sertifikat yang
+///     tertanam di signature
+///   - policy: deny-list algoritma lemah
+///   - trust: sumber trust anchor (boleh kosong)
+///   - expiring_within: timestamp yang sertifikat TSA-nya habis sebelum
+///     sekarang + jangka waktu ini dihitung sebagai akan habis
+///   - parse: Strict mencatat dokumen yang melanggar spesifikasi PDF sebagai error
+///
+/// Return: laporan per dokumen
+pub fn verify_directory(
+    root: &Path,
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    expiring_within: Duration,
+    parse: ParseMode,
+) -> Result<ArchiveReport> {
+    let mut paths = Vec::new();
+    collect_pdfs(root, &mut paths).with_context(|| format!("cannot read directory {}", root.display()))?;
+    paths.sort();
+
+    let deadline = SystemTime::now() + expiring_within;
+    let documents = paths
+        .into_iter()
+        .map(|path| match summarize(&path, public_key, policy, trust, deadline, parse) {
+            Ok(summary) => summary,
+            Err(err) => DocumentSummary::error(path, format!("{:#}", err)),
+        })
+        .collect();
+    Ok(ArchiveReport { root: root.to_path_buf(), expiring_within, documents })
+}
+
+/// Dokumen yang akan tidak bisa diverifikasi lagi (hasil `ltv-audit`)
+pub struct ExpiryAlert {
+    pub path: PathBuf,       // Path dokumen
+    pub expires: SystemTime, // Kapan bukti di dokumen tidak bisa diverifikasi lagi
+    pub reason: String,      // Bagian yang paling cepat habis
+}
+
+/// Cari dokumen yang timestamp atau rantai sertifikatnya habis sebelum `deadline`
+///
+/// Bukti di satu dokumen berlaku sampai:
+///   - sertifikat TSA document timestamp terakhir habis, jika ada document
+///     timestamp (timestamp itu melindungi seluruh isi dokumen sebelumnya);
+///   - jika tidak ada, yang paling cepat dari setiap signature: sertifikat TSA
+///     timestamp-nya, atau rantai sertifikat penandatangan jika signature
+///     tidak punya timestamp.
+///
+/// Signature tanpa sertifikat yang tertanam (format lama) diabaikan. Dokumen
+/// yang tidak bisa dibaca dilaporkan ke stderr dan dilewati.
+///
+/// Parameter:
+///   - root: file PDF atau direktori arsip (ditelusuri seperti `verify_directory`)
+///   - deadline: batas waktu audit
+///
+/// Return: dokumen yang perlu diperbarui, urut dari yang paling cepat habis
+pub fn expiry_worklist(root: &Path, deadline: SystemTime) -> Result<Vec<ExpiryAlert>> {
+    let mut paths = Vec::new();
+    if root.is_dir() {
+        collect_pdfs(root, &mut paths).with_context(|| format!("cannot read directory {}", root.display()))?;
+        paths.sort();
+    } else {
+        paths.push(root.to_path_buf());
+    }
+
+    let mut alerts = Vec::new();
+    for path in paths {
+        let doc = match Document::load(&path) {
+            Ok(doc) => doc,
+            Err(err) => {
+                crate::warning!("skipping {}: {}", path.display(), err);
+                continue;
+            }
+        };
+        if let Some((expires, reason)) = protected_until(&doc) {
+            if expires < deadline {
+                alerts.push(ExpiryAlert { path, expires, reason });
+            }
+        }
+    }
+    alerts.sort_by_key(|alert| alert.expires);
+    Ok(alerts)
+}
+
+/// Sampai kapan bukti di dokumen bisa diverifikasi, dan bagian yang membatasinya
+fn protected_until(doc: &Document) -> Option<(SystemTime, String)> {
+    let signatures: Vec<_> = form::signature_fields(doc)
+        .into_iter()
+        .map(|field| (long_term_info(&field.value, &[]), field.name))
+        .collect();
+
+    // Document timestamp terakhir melindungi semua yang ada sebelumnya
+    let last_timestamp = signatures
+        .iter()
+        .filter(|(info, _)| info.document_timestamp)
+        .max_by_key(|(info, _)| info.signed_until);
+    if let Some((info, name)) = last_timestamp {
+        if let Some(expires) = info.timestamp_expires {
+            return Some((expires, format!("document timestamp {}", name)));
+        }
+    }
+
+    signatures
+        .iter()
+        .filter(|(info, _)| !info.document_timestamp)
+        .filter_map(|(info, name)| match (info.timestamp_expires, info.chain_expires) {
+            (Some(expires), _) => Some((expires, format!("signature timestamp of {}", name))),
+            (None, Some(expires)) => Some((expires, format!("signer certificate of {}", name))),
+            (None, None) => None,
+        })
+        .min_by_key(|(expires, _)| *expires)
+}
+
+/// Cakupan LTV dokumen: signature dengan entry /VRI di /DSS, dari signature
+/// yang membutuhkannya (document timestamp tidak dihitung)
+///
+/// Return: (signature dengan data LTV, signature yang dihitung)
+pub fn ltv_coverage(doc: &Document) -> (usize, usize) {
+    let vri = vri_keys(doc);
+    form::signature_fields(doc)
+        .iter()
+        .map(|field| long_term_info(&field.value, &vri))
+        .filter(|info| !info.document_timestamp)
+        .fold((0, 0), |(covered, total), info| (covered + usize::from(info.ltv), total + 1))
+}
+
+/// Verifikasi satu dokumen dan ringkas hasilnya
+fn summarize(
+    path: &Path,
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    deadline: SystemTime,
+    parse: ParseMode,
+) -> Result<DocumentSummary> {
+    let pdf_bytes = fs::read(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let mut doc = Document::load_mem(&pdf_bytes)?;
+    for issue in syntax::check_document(&pdf_bytes, &mut doc, parse)? {
+        crate::warning!("{}: {}; {}", path.display(), issue, issue.repair());
+    }
+    let reports = verify::verify_document(&doc, &pdf_bytes, public_key, policy, trust, None);
+    let vri = vri_keys(&doc);
+
+    let mut summary = DocumentSummary::new(path.to_path_buf());
+    summary.signatures = reports.len();
+    for report in &reports {
+        match report.status {
+            SignatureStatus::Valid => summary.valid += 1,
+            SignatureStatus::Invalid => summary.invalid += 1,
+            SignatureStatus::Unverifiable => summary.unverified += 1,
+        }
+    }
+    for field in form::signature_fields(&doc) {
+        let info = long_term_info(&field.value, &vri);
+        if let Some(expires) = info.timestamp_expires {
+            summary.timestamps += 1;
+            if expires < deadline {
+                summary.expiring_timestamps += 1;
+            }
+            summary.earliest_expiry = Some(summary.earliest_expiry.map_or(expires, |earliest| earliest.min(expires)));
+        }
+        // Document timestamp terakhir (B-LTA) memang belum punya data LTV
+        if !info.ltv && !info.document_timestamp {
+            summary.missing_ltv += 1;
+        }
+    }
+    summary.status = if reports.is_empty() {
+        DocumentStatus::Unsigned
+    } else if summary.invalid > 0 {
+        DocumentStatus::Invalid
+    } else if summary.unverified > 0 {
+        DocumentStatus::Unverified
+    } else {
+        DocumentStatus::Valid
+    };
+    Ok(summary)
+}
+
+/// Kunci /VRI di /DSS (SHA-1 hex huruf besar dari /Contents tiap signature)
+fn vri_keys(doc: &Document) -> Vec<Vec<u8>> {
+    let dictionary = |object: &Object| doc.dereference(object).ok().and_then(|(_, object)| object.as_dict().ok()).cloned();
+    let vri = form::catalog_id(doc)
+        .ok()
+        .and_then(|root| doc.get_dictionary(root).ok())
+        .and_then(|catalog| catalog.get(b"DSS").ok().and_then(dictionary))
+        .and_then(|dss| dss.get(b"VRI").ok().and_then(dictionary))
+        .unwrap_or_default();
+    vri.iter().map(|(key, _)| key.to_ascii_uppercase()).collect()
+}
+
+/// Waktu habis timestamp dan status LTV satu signature dictionary
+fn long_term_info(sig: &Dictionary, vri: &[Vec<u8>]) -> LongTermInfo {
+    let contents = sig.get(b"Contents").and_then(Object::as_str).unwrap_or_default();
+    let key: String = Sha1::digest(contents).iter().map(|b| format!("{:02X}", b)).collect();
+    let document_timestamp = sig.get(b"SubFilter").and_then(Object::as_name).ok() == Some(&b"ETSI.RFC3161"[..]);
+
+    // Document timestamp berisi TimeStampToken; signature biasa membawanya
+    // sebagai unsigned attribute di CMS SignedData
+    let der = verify::der_element_len(contents).map(|len| &contents[..len]);
+    let (token, chain_expires) = match der {
+        Some(der) if document_timestamp => (Some(der.to_vec()), None),
+        Some(der) => match cms::parse_signed_data(der) {
+            Ok(parsed) => (parsed.timestamp_token, earliest_not_after(&parsed.certificates)),
+            Err(_) => (None, None),
+        },
+        None => (None, None),
+    };
+    let timestamp_expires = token
+        .and_then(|token| tsa::token_certificates(&token).ok())
+        .and_then(|certificates| earliest_not_after(&certificates));
+    let byte_range = sig.get(b"ByteRange").and_then(Object::as_array).map(Vec::as_slice).unwrap_or_default();
+    let signed_until = match byte_range {
+        [_, _, start, length] => start.as_i64().unwrap_or(0) + length.as_i64().unwrap_or(0),
+        _ => 0,
+    };
+
+    LongTermInfo {
+        timestamp_expires,
+        chain_expires,
+        signed_until,
+        ltv: vri.iter().any(|entry| *entry == key.as_bytes()),
+        document_timestamp,
+    }
+}
+
+/// Akhir masa berlaku sertifikat yang paling cepat habis
+fn earliest_not_after(certificates: &[Vec<u8>]) -> Option<SystemTime> {
+    certificates
+        .iter()
+        .filter_map(|der| x509_cert::Certificate::from_der(der).ok())
+        .map(|cert| cert.tbs_certificate.validity.not_after.to_system_time())
+        .min()
+}
+
+impl DocumentSummary {
+    /// Ringkasan kosong (dokumen tanpa signature)
+    fn new(path: PathBuf) -> DocumentSummary {
+        DocumentSummary {
+            path,
+            status: DocumentStatus::Unsigned,
+            signatures: 0,
+            valid: 0,
+            invalid: 0,
+            unverified: 0,
+            timestamps: 0,
+            expiring_timestamps: 0,
+            earliest_expiry: None,
+            missing_ltv: 0,
+            error: None,
+        }
+    }
+
+    /// Ringkasan untuk dokumen yang tidak bisa dibaca
+    fn error(path: PathBuf, error: String) -> DocumentSummary {
+        DocumentSummary { status: DocumentStatus::Error, error: Some(error), ..DocumentSummary::new(path) }
+    }
+}
+
+impl ArchiveReport {
+    /// Hitung jumlah dokumen per status dan total temuan
+    pub fn totals(&self) -> ArchiveTotals {
+        let mut totals = ArchiveTotals { documents: self.documents.len(), ..Default::default() };
+        for document in &self.documents {
+            match document.status {
+                DocumentStatus::Valid => totals.valid += 1,
+                DocumentStatus::Invalid => totals.invalid += 1,
+                DocumentStatus::Unverified => totals.unverified += 1,
+                DocumentStatus::Unsigned => totals.unsigned += 1,
+                DocumentStatus::Error => totals.errors += 1,
+            }
+            totals.signatures += document.signatures;
+            totals.expiring_timestamps += document.expiring_timestamps;
+            totals.missing_ltv += document.missing_ltv;
+        }
+        totals
+    }
+
+    /// Tulis laporan dalam format tertentu
+    pub fn write(&self, format: ReportFormat, out: &mut impl Write) -> Result<()> {
+        match format {
+            ReportFormat::Csv => self.write_csv(out),
+            ReportFormat::Json => self.write_json(out),
+        }
+    }
+
+    /// Tulis satu baris CSV per dokumen (dengan header)
+    pub fn write_csv(&self, out: &mut impl Write) -> Result<()> {
+        writeln!(
+            out,
+            "path,status,signatures,valid,invalid,unverified,timestamps,expiring_timestamps,earliest_timestamp_expiry,missing_ltv,error"
+        )?;
+        for document in &self.documents {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&document.path.display().to_string()),
+                document.status.label(),
+                document.signatures,
+                document.valid,
+                document.invalid,
+                document.unverified,
+                document.timestamps,
+                document.expiring_timestamps,
+                document.earliest_expiry.map(text::machine_time).unwrap_or_default(),
+                document.missing_ltv,
+                csv_field(document.error.as_deref().unwrap_or_default()),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Tulis ringkasan dan daftar dokumen sebagai satu object JSON
+    pub fn write_json(&self, out: &mut impl Write) -> Result<()> {
+        let totals = self.totals();
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"root\": {},", json_string(&self.root.display().to_string()))?;
+        writeln!(out, "  \"expiring_within_days\": {},", self.expiring_within.as_secs() / 86_400)?;
+        writeln!(out, "  \"summary\": {{")?;
+        writeln!(out, "    \"documents\": {},", totals.documents)?;
+        writeln!(out, "    \"valid\": {},", totals.valid)?;
+        writeln!(out, "    \"invalid\": {},", totals.invalid)?;
+        writeln!(out, "    \"unverified\": {},", totals.unverified)?;
+        writeln!(out, "    \"unsigned\": {},", totals.unsigned)?;
+        writeln!(out, "    \"errors\": {},", totals.errors)?;
+        writeln!(out, "    \"signatures\": {},", totals.signatures)?;
+        writeln!(out, "    \"expiring_timestamps\": {},", totals.expiring_timestamps)?;
+        writeln!(out, "    \"missing_ltv\": {}", totals.missing_ltv)?;
+        writeln!(out, "  }},")?;
+        writeln!(out, "  \"documents\": [")?;
+        for (index, document) in self.documents.iter().enumerate() {
+            let optional = |value: Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+            write!(
+                out,
+                "    {{\"path\": {}, \"status\": \"{}\", \"signatures\": {}, \"valid\": {}, \"invalid\": {}, \"unverified\": {}, \
+                 \"timestamps\": {}, \"expiring_timestamps\": {}, \"earliest_timestamp_expiry\": {}, \"missing_ltv\": {}, \"error\": {}}}",
+                json_string(&document.path.display().to_string()),
+                document.status.label(),
+                document.signatures,
+                document.valid,
+                document.invalid,
+                document.unverified,
+                document.timestamps,
+                document.expiring_timestamps,
+                optional(document.earliest_expiry.map(text::machine_time)),
+                document.missing_ltv,
+                optional(document.error.clone()),
+            )?;
+            writeln!(out, "{}", if index + 1 < self.documents.len() { "," } else { "" })?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+}
+
+/// Tanggal (UTC) untuk laporan teks
+pub fn format_date(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).format("%Y-%m-%d").to_string()
+}
+
+/// Field CSV; diberi tanda kutip jika berisi koma, kutip, atau baris baru
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
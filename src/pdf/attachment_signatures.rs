@@ -0,0 +1,192 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, Context, Result}; // Error handling
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat}; // Struktur dasar PDF
+
+use crate::pdf::form; // Catalog dokumen
+use crate::pdf::text; // Nama file (text string PDF)
+
+/// Subtype (MIME) lampiran signature CMS terpisah, ditulis sebagai nama PDF
+const PKCS7_SIGNATURE_SUBTYPE: &[u8] = b"application/pkcs7-signature";
+
+/// Ekstensi nama lampiran signature ("kontrak.xml" -> "kontrak.xml.p7s")
+const SIGNATURE_EXTENSION: &str = ".p7s";
+
+/// Satu file lampiran dari name tree /EmbeddedFiles yang akan ditandatangani
+pub struct Attachment {
+    pub name: String,     // Nama file dari file specification (/UF, /F) atau name tree
+    pub content: Vec<u8>, // Isi file setelah filter stream didekode
+}
+
+/// Baca isi semua file lampiran di name tree /EmbeddedFiles
+///
+/// `load_document` tidak memuat isi stream EmbeddedFile, jadi dokumen dibaca
+/// ulang dengan filter yang hanya mempertahankan stream itu. Lampiran yang
+/// sendiri sudah berupa signature CMS (application/pkcs7-signature) dilewati.
+///
+/// Parameter:
+///   - bytes: isi file PDF
+///
+/// Return: lampiran sesuai urutan name tree
+pub fn read_attachments(bytes: &[u8]) -> Result<Vec<Attachment>> {
+    let doc = lopdf::Reader { buffer: bytes, document: Document::new() }.read(Some(keep_embedded_files))?;
+    let mut attachments = Vec::new();
+    for (key, spec) in name_tree_entries(&doc)? {
+        let Some(spec) = dereference_dict(&doc, &spec) else {
+            continue;
+        };
+        let text = |key: &[u8]| spec.get(key).and_then(Object::as_str).ok().map(text::decode_text_string);
+        let name = text(b"UF").or_else(|| text(b"F")).or_else(|| key.as_str().ok().map(text::decode_text_string)).unwrap_or_default();
+        let stream = spec
+            .get(b"EF")
+            .ok()
+            .and_then(|ef| dereference_dict(&doc, ef))
+            .and_then(|ef| ef.get(b"UF").or_else(|_| ef.get(b"F")).ok())
+            .and_then(|stream| doc.dereference(stream).ok())
+            .and_then(|(_, stream)| stream.as_stream().ok());
+        let Some(stream) = stream else {
+            crate::warning!("embedded file '{}' has no file stream; it is not signed separately", name);
+            continue;
+        };
+        if stream.dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(PKCS7_SIGNATURE_SUBTYPE) {
+            continue;
+        }
+        let content = match stream.dict.get(b"Filter") {
+            Err(_) => stream.content.clone(),
+            Ok(_) => stream
+                .decompressed_content()
+                .map_err(|e| anyhow!("{}", e))
+                .with_context(|| format!("cannot decode embedded file '{}'", name))?,
+        };
+        attachments.push(Attachment { name, content });
+    }
+    Ok(attachments)
+}
+
+/// Simpan signature CMS terpisah sebagai lampiran "<nama>.p7s" di samping
+/// file aslinya (/AFRelationship /Supplement)
+///
+/// Name tree /EmbeddedFiles ditulis ulang sebagai satu node daun yang terurut.
+///
+/// Parameter:
+///   - doc: dokumen yang diubah
+///   - signatures: pasangan lampiran dan ContentInfo CMS (DER) atas isinya
+pub fn add_signatures(doc: &mut Document, signatures: &[(Attachment, Vec<u8>)]) -> Result<()> {
+    let mut entries = name_tree_entries(doc)?;
+    for (attachment, signature) in signatures {
+        // Signature lampiran dari penandatangan sebelumnya tetap disimpan:
+        // "<nama>.p7s", lalu "<nama>.2.p7s", "<nama>.3.p7s", ...
+        let taken = |name: &str| entries.iter().any(|(key, _)| key.as_str().ok().map(text::decode_text_string).as_deref() == Some(name));
+        let mut name = format!("{}{}", attachment.name, SIGNATURE_EXTENSION);
+        let mut counter = 1;
+        while taken(&name) {
+            counter += 1;
+            name = format!("{}.{}{}", attachment.name, counter, SIGNATURE_EXTENSION);
+        }
+        let stream = Stream::new(
+            dictionary! {
+                "Type" => "EmbeddedFile",
+                "Subtype" => Object::Name(PKCS7_SIGNATURE_SUBTYPE.to_vec()),
+                "Params" => dictionary! {
+                    "Size" => signature.len() as i64,
+                    "CheckSum" => Object::String(md5::compute(signature).0.to_vec(), StringFormat::Hexadecimal),
+                },
+            },
+            signature.clone(),
+        );
+        let stream_id = doc.add_object(stream);
+        let spec_id = doc.add_object(dictionary! {
+            "Type" => "Filespec",
+            "F" => text::text_string(&name),
+            "UF" => text::text_string(&name),
+            "Desc" => text::text_string(&format!("Detached CMS signature of {}", attachment.name)),
+            "AFRelationship" => "Supplement",
+            "EF" => dictionary! { "F" => stream_id, "UF" => stream_id },
+        });
+        entries.push((text::text_string(&name), Object::Reference(spec_id)));
+    }
+
+    // Kunci name tree harus terurut (ISO 32000-1 7.9.6)
+    entries.sort_by(|(a, _), (b, _)| a.as_str().unwrap_or_default().cmp(b.as_str().unwrap_or_default()));
+    let names = entries.into_iter().flat_map(|(key, spec)| [key, spec]).collect::<Vec<_>>();
+    let tree_id = doc.add_object(dictionary! { "Names" => names });
+
+    let catalog_id = form::catalog_id(doc)?;
+    let names_dict = match doc.get_dictionary(catalog_id)?.get(b"Names") {
+        Ok(Object::Reference(id)) => *id,
+        _ => catalog_id,
+    };
+    let dict = doc.get_dictionary_mut(names_dict)?;
+    if names_dict == catalog_id {
+        // /Names langsung di catalog
+        let Object::Dictionary(names) = dict.get_mut(b"Names")? else {
+            return Err(anyhow!("the catalog /Names entry is not a dictionary"));
+        };
+        names.set("EmbeddedFiles", tree_id);
+    } else {
+        dict.set("EmbeddedFiles", tree_id);
+    }
+    Ok(())
+}
+
+/// Pasangan (kunci, file specification) dari seluruh name tree /EmbeddedFiles
+fn name_tree_entries(doc: &Document) -> Result<Vec<(Object, Object)>> {
+    let catalog = doc.get_dictionary(form::catalog_id(doc)?)?;
+    let tree = catalog
+        .get(b"Names")
+        .ok()
+        .and_then(|names| dereference_dict(doc, names))
+        .and_then(|names| names.get(b"EmbeddedFiles").ok())
+        .and_then(|tree| dereference_dict(doc, tree));
+    let mut entries = Vec::new();
+    if let Some(tree) = tree {
+        collect_name_tree(doc, tree, 0, &mut entries);
+    }
+    Ok(entries)
+}
+
+/// Kumpulkan pasangan (kunci, nilai) dari name tree secara rekursif
+fn collect_name_tree(doc: &Document, node: &Dictionary, depth: usize, entries: &mut Vec<(Object, Object)>) {
+    // Batasi kedalaman untuk menghindari loop pada /Kids yang saling merujuk
+    if depth > 32 {
+        return;
+    }
+    for pair in dereference_array(doc, node, b"Names").into_iter().flat_map(|names| names.chunks_exact(2)) {
+        // Node yang dirujuk lebih dari sekali hanya dihitung sekali
+        if !entries.iter().any(|(key, _)| key.as_str().ok() == pair[0].as_str().ok()) {
+            entries.push((pair[0].clone(), pair[1].clone()));
+        }
+    }
+    for kid in dereference_array(doc, node, b"Kids").into_iter().flatten().filter_map(|kid| dereference_dict(doc, kid)) {
+        collect_name_tree(doc, kid, depth + 1, entries);
+    }
+}
+
+/// Array `key` di dictionary, langsung atau lewat referensi
+fn dereference_array<'a>(doc: &'a Document, dict: &'a Dictionary, key: &[u8]) -> Option<&'a Vec<Object>> {
+    dict.get(key).ok().and_then(|array| doc.dereference(array).ok()).and_then(|(_, array)| array.as_array().ok())
+}
+
+/// Dictionary langsung atau lewat referensi
+fn dereference_dict<'a>(doc: &'a Document, object: &'a Object) -> Option<&'a Dictionary> {
+    doc.dereference(object).ok().and_then(|(_, object)| object.as_dict().ok())
+}
+
+/// Filter parser: kosongkan isi stream selain file lampiran (dan stream yang
+/// dibutuhkan parser) agar dokumen besar tidak dimuat seluruhnya
+fn keep_embedded_files(id: ObjectId, object: &mut Object) -> Option<(ObjectId, Object)> {
+    let Object::Stream(stream) = object else {
+        // Object di dalam object stream: nilai kembalian inilah yang disimpan
+        return Some((id, object.clone()));
+    };
+    // /Type di stream EmbeddedFile opsional: yang dikosongkan hanya stream yang
+    // jelas bukan file lampiran (gambar, form XObject, metadata, ...)
+    let name = |key: &[u8]| stream.dict.get(key).and_then(Object::as_name).ok();
+    let other_type = name(b"Type").is_some_and(|kind| !matches!(kind, b"EmbeddedFile" | b"ObjStm" | b"XRef"));
+    if other_type || matches!(name(b"Subtype"), Some(b"Image" | b"Form")) {
+        stream.content = Vec::new();
+        // Tanpa posisi, parser tidak mengisi ulang isi stream dari /Length
+        stream.start_position = None;
+    }
+    // Untuk object di level atas, lopdf menyimpan `object` yang sudah diubah
+    Some((id, Object::Null))
+}
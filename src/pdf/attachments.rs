@@ -0,0 +1,259 @@
+// Import library yang diperlukan
+use lopdf::{Dictionary, Document, Object, ObjectId}; // Struktur dasar PDF
+use lopdf::xref::XrefEntry; // Offset object di file (cakupan ByteRange)
+
+use crate::pdf::appearance::AppearanceLanguage; // Bahasa pesan laporan
+use crate::pdf::form; // Catalog dokumen
+use crate::pdf::text; // Nama file (text string PDF)
+use crate::pdf::verify::{Severity, SignatureReport, SignatureStatus}; // Signature yang mencakup lampiran
+
+/// Hasil pemeriksaan satu file lampiran (embedded file)
+pub struct AttachmentReport {
+    pub name: String,                 // Nama file dari file specification (/UF, /F) atau name tree
+    pub relationship: Option<String>, // /AFRelationship (Data, Source, Alternative, ...), jika ada
+    pub size: Option<usize>,          // Ukuran isi file setelah didekode
+    pub status: AttachmentStatus,     // Hasil pemeriksaan /CheckSum dan /Size
+    pub signed_by: Vec<String>,       // Signature valid yang ByteRange-nya mencakup isi file
+}
+
+/// Status integritas satu lampiran
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttachmentStatus {
+    /// /CheckSum (MD5) cocok dengan isi file
+    Valid,
+    /// /CheckSum tidak cocok dengan isi file
+    ChecksumMismatch,
+    /// /Size di /Params tidak sama dengan ukuran isi file
+    SizeMismatch { declared: i64, actual: usize },
+    /// Tidak ada /CheckSum; isi file tidak bisa dibandingkan
+    NoChecksum,
+    /// Stream file tidak ada atau tidak bisa didekode (detail)
+    Unreadable(String),
+}
+
+impl AttachmentReport {
+    /// Temuan untuk lampiran ini (kosong jika checksum cocok dan dicakup signature)
+    pub fn findings(&self, lang: AppearanceLanguage) -> Vec<(Severity, String)> {
+        let id = lang == AppearanceLanguage::Id;
+        let mut findings = Vec::new();
+        let status = match &self.status {
+            AttachmentStatus::Valid => None,
+            AttachmentStatus::ChecksumMismatch if id => Some((Severity::Error, "checksum MD5 di /Params tidak cocok dengan isi file".to_string())),
+            AttachmentStatus::ChecksumMismatch => Some((Severity::Error, "the MD5 checksum in /Params does not match the file contents".to_string())),
+            AttachmentStatus::SizeMismatch { declared, actual } if id => {
+                Some((Severity::Error, format!("/Size di /Params ({} bytes) tidak sama dengan ukuran isi file ({} bytes)", declared, actual)))
+            }
+            AttachmentStatus::SizeMismatch { declared, actual } => {
+                Some((Severity::Error, format!("/Size in /Params ({} bytes) differs from the file contents ({} bytes)", declared, actual)))
+            }
+            AttachmentStatus::NoChecksum if id => Some((Severity::Info, "tidak ada /CheckSum; isi file tidak bisa dibandingkan".to_string())),
+            AttachmentStatus::NoChecksum => Some((Severity::Info, "no /CheckSum; the file contents cannot be compared".to_string())),
+            AttachmentStatus::Unreadable(detail) if id => Some((Severity::Error, format!("isi file tidak bisa dibaca: {}", detail))),
+            AttachmentStatus::Unreadable(detail) => Some((Severity::Error, format!("cannot read the file contents: {}", detail))),
+        };
+        findings.extend(status);
+        if self.signed_by.is_empty() {
+            findings.push(match id {
+                true => (Severity::Warning, "tidak dicakup signature valid mana pun (ditambahkan atau diubah setelah ditandatangani)".to_string()),
+                false => (Severity::Warning, "not covered by any valid signature (added or changed after signing)".to_string()),
+            });
+        }
+        findings
+    }
+
+    /// Apakah lampiran gagal pemeriksaan integritas (ada temuan berseverity error)
+    pub fn failed(&self) -> bool {
+        self.findings(AppearanceLanguage::En).iter().any(|(severity, _)| *severity == Severity::Error)
+    }
+}
+
+/// Periksa integritas semua file lampiran di dokumen
+///
+/// Lampiran dikumpulkan dari name tree /EmbeddedFiles, /AF di catalog dan
+/// halaman (associated files, misalnya XML Factur-X / ZUGFeRD), dan anotasi
+/// FileAttachment; file specification yang sama hanya dilaporkan sekali. Isi
+/// file dibandingkan dengan /CheckSum (MD5) dan /Size di /Params stream-nya.
+/// Sebuah signature valid mencakup lampiran jika object stream file-nya
+/// (atau object stream yang memuatnya) berada di dalam ByteRange signature.
+///
+/// Parameter:
+///   - doc: dokumen hasil `Document::load_mem(pdf_bytes)`
+///   - signatures: laporan signature dokumen yang sama (`verify::verify_document`)
+///
+/// Return: laporan per lampiran, urut seperti ditemukan
+pub fn check_attachments(doc: &Document, signatures: &[SignatureReport]) -> Vec<AttachmentReport> {
+    let mut reports = Vec::new();
+    let mut seen: Vec<&Dictionary> = Vec::new();
+    for (fallback_name, spec) in file_specifications(doc) {
+        if seen.iter().any(|existing| std::ptr::eq(*existing, spec)) {
+            continue;
+        }
+        seen.push(spec);
+
+        let text = |key: &[u8]| spec.get(key).and_then(Object::as_str).ok().map(text::decode_text_string);
+        let name = text(b"UF").or_else(|| text(b"F")).or(fallback_name).unwrap_or_else(|| "(unnamed)".to_string());
+        let relationship = spec.get(b"AFRelationship").and_then(Object::as_name_str).ok().map(str::to_string);
+
+        // Stream file: /EF /UF lebih diutamakan, lalu /EF /F
+        let ef = spec.get(b"EF").ok().and_then(|ef| dereference_dict(doc, ef));
+        let stream_ref = ef.and_then(|ef| ef.get(b"UF").or_else(|_| ef.get(b"F")).ok());
+        let stream = stream_ref
+            .and_then(|stream| doc.dereference(stream).ok())
+            .and_then(|(_, stream)| stream.as_stream().ok());
+        let signed_by = match stream_ref.and_then(|stream| stream.as_reference().ok()) {
+            Some(id) => covering_signatures(doc, id, signatures),
+            // Stream langsung (bukan referensi) ada di dalam file specification-nya
+            None => Vec::new(),
+        };
+
+        let (size, status) = match stream {
+            None => (None, AttachmentStatus::Unreadable("no embedded file stream (/EF)".to_string())),
+            Some(stream) => match decoded_content(stream) {
+                Err(detail) => (None, AttachmentStatus::Unreadable(detail)),
+                Ok(content) => (Some(content.len()), integrity(doc, stream, &content)),
+            },
+        };
+        reports.push(AttachmentReport { name, relationship, size, status, signed_by });
+    }
+    reports
+}
+
+/// Laporan lampiran sebagai teks untuk user (ditampilkan oleh CLI)
+pub fn format_attachments(reports: &[AttachmentReport], lang: AppearanceLanguage) -> String {
+    let mut out = String::new();
+    for (index, report) in reports.iter().enumerate() {
+        let size = report.size.map_or("-".to_string(), |size| format!("{} bytes", size));
+        match &report.relationship {
+            Some(relationship) => out.push_str(&format!("Attachment #{}: {} ({}, {})\n", index + 1, report.name, relationship, size)),
+            None => out.push_str(&format!("Attachment #{}: {} ({})\n", index + 1, report.name, size)),
+        }
+        out.push_str(&format!("  Signed by:  {}\n", if report.signed_by.is_empty() { "-".to_string() } else { report.signed_by.join(", ") }));
+        let integrity = match report.status {
+            AttachmentStatus::Valid => "OK",
+            AttachmentStatus::ChecksumMismatch | AttachmentStatus::SizeMismatch { .. } => "MISMATCH",
+            AttachmentStatus::NoChecksum => "NOT CHECKED",
+            AttachmentStatus::Unreadable(_) => "UNREADABLE",
+        };
+        out.push_str(&format!("  Integrity:  {}\n", integrity));
+        for (severity, message) in report.findings(lang) {
+            out.push_str(&format!("  {:<11} {}\n", format!("{}:", severity.label(lang)), message));
+        }
+    }
+    out
+}
+
+/// Semua file specification di dokumen beserta nama dari name tree (jika ada)
+fn file_specifications(doc: &Document) -> Vec<(Option<String>, &Dictionary)> {
+    let mut specs = Vec::new();
+    let Some(catalog) = form::catalog_id(doc).ok().and_then(|root| doc.get_dictionary(root).ok()) else {
+        return specs;
+    };
+
+    // Name tree /Names /EmbeddedFiles
+    let tree = catalog
+        .get(b"Names")
+        .ok()
+        .and_then(|names| dereference_dict(doc, names))
+        .and_then(|names| names.get(b"EmbeddedFiles").ok())
+        .and_then(|tree| dereference_dict(doc, tree));
+    if let Some(tree) = tree {
+        collect_name_tree(doc, tree, 0, &mut specs);
+    }
+
+    // Associated files (/AF) di catalog dan halaman, serta anotasi FileAttachment
+    specs.extend(associated_files(doc, catalog).into_iter().map(|spec| (None, spec)));
+    for page_id in doc.get_pages().into_values() {
+        let Ok(page) = doc.get_dictionary(page_id) else {
+            continue;
+        };
+        specs.extend(associated_files(doc, page).into_iter().map(|spec| (None, spec)));
+        for annotation in dereference_array(doc, page, b"Annots").into_iter().flatten().filter_map(|annotation| dereference_dict(doc, annotation)) {
+            if annotation.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"FileAttachment") {
+                specs.extend(annotation.get(b"FS").ok().and_then(|spec| dereference_dict(doc, spec)).map(|spec| (None, spec)));
+            }
+        }
+    }
+    specs
+}
+
+/// File specification di array /AF sebuah dictionary (catalog atau halaman)
+fn associated_files<'a>(doc: &'a Document, dict: &'a Dictionary) -> Vec<&'a Dictionary> {
+    dereference_array(doc, dict, b"AF")
+        .into_iter()
+        .flatten()
+        .filter_map(|spec| dereference_dict(doc, spec))
+        .collect()
+}
+
+/// Array `key` di dictionary, langsung atau lewat referensi
+fn dereference_array<'a>(doc: &'a Document, dict: &'a Dictionary, key: &[u8]) -> Option<&'a Vec<Object>> {
+    dict.get(key).ok().and_then(|array| doc.dereference(array).ok()).and_then(|(_, array)| array.as_array().ok())
+}
+
+/// Dictionary langsung atau lewat referensi
+fn dereference_dict<'a>(doc: &'a Document, object: &'a Object) -> Option<&'a Dictionary> {
+    doc.dereference(object).ok().and_then(|(_, object)| object.as_dict().ok())
+}
+
+/// Kumpulkan pasangan (nama, file specification) dari name tree secara rekursif
+fn collect_name_tree<'a>(doc: &'a Document, node: &'a Dictionary, depth: usize, specs: &mut Vec<(Option<String>, &'a Dictionary)>) {
+    // Batasi kedalaman untuk menghindari loop pada /Kids yang saling merujuk
+    if depth > 32 {
+        return;
+    }
+    for pair in dereference_array(doc, node, b"Names").into_iter().flat_map(|names| names.chunks(2)) {
+        let name = pair[0].as_str().ok().map(text::decode_text_string);
+        specs.extend(pair.get(1).and_then(|spec| dereference_dict(doc, spec)).map(|spec| (name, spec)));
+    }
+    for kid in dereference_array(doc, node, b"Kids").into_iter().flatten().filter_map(|kid| dereference_dict(doc, kid)) {
+        collect_name_tree(doc, kid, depth + 1, specs);
+    }
+}
+
+/// Isi stream file setelah filter-nya didekode
+fn decoded_content(stream: &lopdf::Stream) -> Result<Vec<u8>, String> {
+    match stream.dict.get(b"Filter") {
+        Err(_) => Ok(stream.content.clone()),
+        Ok(_) => stream.decompressed_content().map_err(|e| format!("unsupported or corrupt stream filter ({})", e)),
+    }
+}
+
+/// Bandingkan isi file dengan /Size dan /CheckSum di /Params
+fn integrity(doc: &Document, stream: &lopdf::Stream, content: &[u8]) -> AttachmentStatus {
+    let params = stream.dict.get(b"Params").ok().and_then(|params| dereference_dict(doc, params));
+    let declared_size = params.and_then(|params| params.get(b"Size").ok()).and_then(|size| size.as_i64().ok());
+    if let Some(declared) = declared_size.filter(|declared| *declared != content.len() as i64) {
+        return AttachmentStatus::SizeMismatch { declared, actual: content.len() };
+    }
+    match params.and_then(|params| params.get(b"CheckSum").ok()).and_then(|checksum| checksum.as_str().ok()) {
+        Some(checksum) if checksum == md5::compute(content).0 => AttachmentStatus::Valid,
+        Some(_) => AttachmentStatus::ChecksumMismatch,
+        None => AttachmentStatus::NoChecksum,
+    }
+}
+
+/// Signature valid yang ByteRange-nya mencakup object `id`
+///
+/// Offset object diambil dari tabel xref terakhir: object yang diubah di
+/// incremental update setelah signature punya offset di luar ByteRange-nya.
+fn covering_signatures(doc: &Document, id: ObjectId, signatures: &[SignatureReport]) -> Vec<String> {
+    let offset = match doc.reference_table.get(id.0) {
+        Some(XrefEntry::Normal { offset, .. }) => *offset as i64,
+        Some(XrefEntry::Compressed { container, .. }) => match doc.reference_table.get(*container) {
+            Some(XrefEntry::Normal { offset, .. }) => *offset as i64,
+            _ => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    signatures
+        .iter()
+        .filter(|signature| signature.status == SignatureStatus::Valid)
+        .filter(|signature| match signature.byte_range[..] {
+            [start, length, second, second_length] => {
+                (start..start + length).contains(&offset) || (second..second + second_length).contains(&offset)
+            }
+            _ => false,
+        })
+        .map(|signature| signature.field.clone())
+        .collect()
+}
@@ -0,0 +1,156 @@
+// Import library yang diperlukan
+use anyhow::{Context, Result}; // Untuk error handling yang fleksibel
+use sha2::{Digest, Sha256}; // Hash dokumen yang diverifikasi
+use std::fs::{self, OpenOptions}; // Membaca dokumen dan menambah baris ke log
+use std::io::Write; // Menulis baris log
+use std::path::{Path, PathBuf}; // Path audit log
+
+use crate::lock::FileLock; // Beberapa proses verify boleh menulis ke log yang sama
+use crate::pdf::archive::{DocumentStatus, DocumentSummary}; // Status dokumen
+use crate::pdf::text::{self, json_string}; // Escape string JSON, waktu RFC 3339
+use crate::pdf::verify::{SignatureReport, SignatureStatus}; // Hasil verifikasi satu dokumen
+
+/// Hasil verifikasi satu dokumen untuk audit log
+pub struct AuditEntry {
+    pub document: String,                  // Path dokumen seperti yang diberikan
+    pub sha256: Option<String>,            // SHA-256 (hex) isi file saat diverifikasi, None jika tidak terbaca
+    pub status: DocumentStatus,            // Status keseluruhan
+    pub signatures: usize,                 // Jumlah signature (termasuk document timestamp)
+    pub valid: usize,                      // Signature valid
+    pub invalid: usize,                    // Signature tidak valid
+    pub unverified: usize,                 // Signature yang tidak bisa diperiksa
+    pub attachments_failed: Option<usize>, // Lampiran yang gagal diperiksa (None = tidak diperiksa)
+    pub error: Option<String>,             // Alasan dokumen tidak bisa diverifikasi
+}
+
+impl AuditEntry {
+    /// Entry dari laporan signature `verify --input`
+    ///
+    /// Parameter:
+    ///   - path: path dokumen
+    ///   - reports: laporan per signature
+    ///   - attachments_failed: jumlah lampiran yang gagal pemeriksaan integritas
+    pub fn from_reports(path: &str, reports: &[SignatureReport], attachments_failed: usize) -> AuditEntry {
+        let count = |status: SignatureStatus| reports.iter().filter(|report| report.status == status).count();
+        let (valid, invalid, unverified) = (count(SignatureStatus::Valid), count(SignatureStatus::Invalid), count(SignatureStatus::Unverifiable));
+        let status = if reports.is_empty() {
+            DocumentStatus::Unsigned
+        } else if invalid > 0 || attachments_failed > 0 {
+            DocumentStatus::Invalid
+        } else if unverified > 0 {
+            DocumentStatus::Unverified
+        } else {
+            DocumentStatus::Valid
+        };
+        AuditEntry {
+            document: path.to_string(),
+            sha256: file_sha256(Path::new(path)),
+            status,
+            signatures: reports.len(),
+            valid,
+            invalid,
+            unverified,
+            attachments_failed: Some(attachments_failed),
+            error: None,
+        }
+    }
+
+    /// Entry dari ringkasan dokumen `verify --recursive` (lampiran tidak diperiksa)
+    pub fn from_summary(summary: &DocumentSummary) -> AuditEntry {
+        AuditEntry {
+            document: summary.path.display().to_string(),
+            sha256: file_sha256(&summary.path),
+            status: summary.status,
+            signatures: summary.signatures,
+            valid: summary.valid,
+            invalid: summary.invalid,
+            unverified: summary.unverified,
+            attachments_failed: None,
+            error: summary.error.clone(),
+        }
+    }
+
+    /// Entry untuk dokumen yang tidak bisa diverifikasi
+    pub fn error(path: &str, error: String) -> AuditEntry {
+        AuditEntry {
+            document: path.to_string(),
+            sha256: file_sha256(Path::new(path)),
+            status: DocumentStatus::Error,
+            signatures: 0,
+            valid: 0,
+            invalid: 0,
+            unverified: 0,
+            attachments_failed: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Audit log verifikasi: file JSON Lines yang hanya pernah ditambah
+///
+/// Setiap dokumen yang diverifikasi menjadi satu baris berisi waktu
+/// verifikasi, siapa yang memverifikasi, versi pdfsign, hash dokumen, dan
+/// hasilnya, sebagai bukti bahwa arsip diperiksa secara berkala.
+pub struct AuditLog {
+    path: PathBuf,    // File audit log
+    verifier: String, // Siapa yang menjalankan verifikasi
+}
+
+impl AuditLog {
+    /// Parameter:
+    ///   - path: file audit log (dibuat jika belum ada)
+    ///   - verifier: nama pemeriksa; jika None, dipakai user sistem operasi
+    pub fn new(path: impl Into<PathBuf>, verifier: Option<String>) -> AuditLog {
+        let verifier = verifier
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "unknown".to_string());
+        AuditLog { path: path.into(), verifier }
+    }
+
+    /// Tambahkan satu baris per entry ke akhir log
+    ///
+    /// Semua entry satu kali verifikasi ditulis bersama dengan waktu yang sama,
+    /// di bawah lock agar baris dari proses lain tidak tercampur.
+    pub fn append(&self, entries: &[AuditEntry]) -> Result<()> {
+        let time = text::machine_time(chrono::Utc::now());
+        let tool = format!("pdfsign {}", env!("CARGO_PKG_VERSION"));
+        let mut lines = String::new();
+        for entry in entries {
+            let optional = |value: Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+            lines.push_str(&format!(
+                "{{\"time\": \"{}\", \"verifier\": {}, \"tool\": {}, \"document\": {}, \"sha256\": {}, \"status\": \"{}\", \
+                 \"signatures\": {}, \"valid\": {}, \"invalid\": {}, \"unverified\": {}, \"attachments_failed\": {}, \"error\": {}}}\n",
+                time,
+                json_string(&self.verifier),
+                json_string(&tool),
+                json_string(&entry.document),
+                optional(entry.sha256.clone()),
+                entry.status.label(),
+                entry.signatures,
+                entry.valid,
+                entry.invalid,
+                entry.unverified,
+                entry.attachments_failed.map_or("null".to_string(), |count| count.to_string()),
+                optional(entry.error.clone()),
+            ));
+        }
+
+        let _lock = FileLock::exclusive(&self.path)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("cannot open audit log {}", self.path.display()))?;
+        file.write_all(lines.as_bytes())
+            .and_then(|()| file.sync_data())
+            .with_context(|| format!("cannot write audit log {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+/// SHA-256 (hex) isi file, None jika tidak bisa dibaca
+fn file_sha256(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect())
+}
@@ -0,0 +1,186 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs; // Membuat direktori output
+use std::path::{Path, PathBuf}; // Path input dan output
+use std::sync::atomic::{AtomicUsize, Ordering}; // Antrian file untuk worker
+use std::sync::Mutex; // Mengumpulkan hasil dari worker
+use std::time::{Duration, Instant}; // Batas waktu dan lama per file
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::signer::Signer; // Kunci yang dipakai bersama semua worker
+use crate::pdf::health::{self, HealthPolicy}; // Self-test dan keep-alive token
+use crate::pdf::hooks::NoHooks; // Batch tidak memakai hook
+use crate::pdf::options::SignatureOptions; // Opsi yang sama untuk semua file
+use crate::pdf::sign::{load_credentials, sign_file, STDIO_PATH}; // Pipeline penandatanganan per file
+use crate::storage::collect_pdfs; // Semua file .pdf di bawah direktori
+
+/// Satu file dalam batch
+#[derive(Clone, Debug)]
+pub struct BatchItem {
+    pub input: PathBuf,  // PDF yang akan ditandatangani
+    pub output: PathBuf, // PDF hasil
+}
+
+/// Hasil penandatanganan satu file
+pub struct BatchOutcome {
+    pub item: BatchItem,    // File yang diproses
+    pub result: Result<()>, // Ok, atau penyebab gagal
+    pub elapsed: Duration,  // Lama proses file ini
+}
+
+/// Susun daftar file batch dari direktori input dan/atau daftar file
+///
+/// File dari `input_dir` (termasuk subdirektori) ditulis ke `output_dir` dengan
+/// path relatif yang sama; file dari `inputs` ditulis langsung di `output_dir`
+/// dengan nama filenya.
+///
+/// Parameter:
+///   - input_dir: direktori berisi PDF (opsional)
+///   - inputs: file PDF satu per satu (misalnya hasil glob shell)
+///   - output_dir: direktori tujuan
+///
+/// Return: daftar file, urut seperti di disk; error jika kosong atau jika dua
+/// input akan ditulis ke output yang sama
+pub fn batch_items(input_dir: Option<&Path>, inputs: &[PathBuf], output_dir: &Path) -> Result<Vec<BatchItem>> {
+    let mut items = Vec::new();
+    if let Some(dir) = input_dir {
+        let mut paths = Vec::new();
+        collect_pdfs(dir, &mut paths).with_context(|| format!("cannot read directory {}", dir.display()))?;
+        paths.sort();
+        for input in paths {
+            let relative = input.strip_prefix(dir).unwrap_or(&input).to_path_buf();
+            items.push(BatchItem { output: output_dir.join(relative), input });
+        }
+    }
+    for input in inputs {
+        if input.as_os_str() == STDIO_PATH {
+            bail!("sign-batch cannot read a document from stdin; use `pdfsign sign --input -`");
+        }
+        let Some(name) = input.file_name() else {
+            bail!("{} is not a file", input.display());
+        };
+        items.push(BatchItem { output: output_dir.join(name), input: input.clone() });
+    }
+    if items.is_empty() {
+        bail!("no PDF files to sign");
+    }
+
+    // Dua input dengan nama file sama akan saling menimpa di output_dir
+    let mut outputs: Vec<(&Path, &Path)> = items.iter().map(|item| (item.output.as_path(), item.input.as_path())).collect();
+    outputs.sort();
+    if let Some(pair) = outputs.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        bail!("{} and {} would both be written to {}", pair[0].1.display(), pair[1].1.display(), pair[0].0.display());
+    }
+    Ok(items)
+}
+
+/// Tandatangani banyak file secara paralel dengan satu kunci
+///
+/// Kunci dan sertifikat dimuat sekali (passphrase atau PIN hanya ditanyakan
+/// sekali), lalu `workers` thread mengambil file dari antrian. Semua file
+/// memakai opsi yang sama, termasuk cache tampilan, sehingga logo dan layout
+/// tampilan hanya dikompilasi sekali. File yang gagal tidak menghentikan
+/// batch; setelah token dibatalkan (Ctrl-C), file yang belum dimulai dicatat
+/// sebagai gagal.
+///
+/// Parameter:
+///   - items: hasil `batch_items`
+///   - key_path: path file kunci privat (diabaikan untuk PKCS#12/PKCS#11/KMS)
+///   - options: opsi signature untuk semua file
+///   - workers: jumlah thread (minimal 1)
+///   - timeout: batas waktu per file (None = tanpa batas)
+///   - cancel: token pembatalan seluruh batch
+///   - on_done: dipanggil dari thread worker setiap satu file selesai
+///
+/// Return: hasil per file, urut seperti `items`
+pub fn sign_batch(
+    items: Vec<BatchItem>,
+    key_path: &Path,
+    options: &SignatureOptions,
+    workers: usize,
+    timeout: Option<Duration>,
+    cancel: &CancellationToken,
+    on_done: &(dyn Fn(&BatchOutcome) + Sync),
+) -> Result<Vec<BatchOutcome>> {
+    sign_batch_with(items, key_path, options, workers, timeout, &HealthPolicy::default(), cancel, on_done)
+}
+
+/// Seperti `sign_batch`, dengan self-test sebelum file pertama dan ping
+/// keep-alive ke token selama batch berjalan (`health`)
+///
+/// Self-test yang gagal menghentikan batch sebelum satu file pun diproses;
+/// hasilnya dicatat di log.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_batch_with(
+    items: Vec<BatchItem>,
+    key_path: &Path,
+    options: &SignatureOptions,
+    workers: usize,
+    timeout: Option<Duration>,
+    health: &HealthPolicy,
+    cancel: &CancellationToken,
+    on_done: &(dyn Fn(&BatchOutcome) + Sync),
+) -> Result<Vec<BatchOutcome>> {
+    let (signer, certificates) = load_credentials(key_path, options)?;
+    if health.self_test {
+        let report = health::self_test(signer.as_ref(), &certificates, options, cancel)?;
+        for warning in &report.warnings {
+            crate::warning!("{}", warning);
+        }
+        crate::info!("Self-test passed: {}", report.summary());
+    }
+    let workers = workers.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::with_capacity(items.len()));
+    let finished = CancellationToken::new();
+
+    std::thread::scope(|scope| {
+        if let Some(interval) = health.keep_alive {
+            let (signer, finished) = (signer.as_ref(), &finished);
+            scope.spawn(move || health::keep_alive(signer, interval, &|| finished.check().is_err() || cancel.check().is_err()));
+        }
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let started = Instant::now();
+                    let result = cancel
+                        .check()
+                        .and_then(|_| sign_item(item, signer.as_ref(), &certificates, options, timeout, cancel));
+                    let outcome = BatchOutcome { item: item.clone(), result, elapsed: started.elapsed() };
+                    on_done(&outcome);
+                    outcomes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push((index, outcome));
+                })
+            })
+            .collect();
+        let panics: Vec<_> = handles.into_iter().filter_map(|handle| handle.join().err()).collect();
+        // Semua worker selesai: thread keep-alive ikut berhenti sebelum panic diteruskan
+        finished.cancel();
+        if let Some(panic) = panics.into_iter().next() {
+            std::panic::resume_unwind(panic);
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    outcomes.sort_by_key(|(index, _)| *index);
+    Ok(outcomes.into_iter().map(|(_, outcome)| outcome).collect())
+}
+
+/// Tandatangani satu file batch; direktori output dibuat jika belum ada
+fn sign_item(
+    item: &BatchItem,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    timeout: Option<Duration>,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if let Some(parent) = item.output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("cannot create directory {}", parent.display()))?;
+    }
+    let cancel = timeout.map_or_else(|| cancel.clone(), |timeout| cancel.child(timeout));
+    sign_file(&item.input, &item.output, signer, certificates, options, &mut NoHooks, &cancel)
+}
@@ -0,0 +1,221 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+use lopdf::xref::XrefEntry; // Posisi object di file
+use lopdf::{Document, Object, ObjectId}; // Struktur PDF
+
+use crate::pdf::text; // Ukuran untuk pesan
+use crate::pdf::update; // Ukuran serialisasi object
+
+/// Jumlah object terbesar yang disebut di peringatan
+const LARGEST_SHOWN: usize = 5;
+
+/// Batas pertumbuhan file: bytes, atau persen dari ukuran sebelum signing
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Growth {
+    /// Bytes yang ditambahkan
+    Bytes(u64),
+    /// Persen dari ukuran file asli
+    Percent(f64),
+}
+
+impl Growth {
+    /// Apakah pertumbuhan `added` bytes atas file `before` bytes melewati batas
+    pub fn exceeded(self, before: u64, added: u64) -> bool {
+        match self {
+            Growth::Bytes(limit) => added > limit,
+            Growth::Percent(limit) => percent(before, added) > limit,
+        }
+    }
+}
+
+/// Tulis kembali dalam format --warn-growth
+impl std::fmt::Display for Growth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Growth::Bytes(bytes) => write!(f, "{}", text::human_size(*bytes)),
+            Growth::Percent(limit) => write!(f, "{}%", limit),
+        }
+    }
+}
+
+/// Parse nilai --warn-growth: bytes ("500000"), ukuran dengan satuan KB/MB/GB
+/// (kelipatan 1024, misalnya "2MB"), atau persen ("50%")
+impl std::str::FromStr for Growth {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<Growth, String> {
+        let text = text.trim();
+        let invalid = || format!("expected a size (e.g. 500KB, 2MB) or a percentage (e.g. 50%), got `{}`", text);
+        if let Some(number) = text.strip_suffix('%') {
+            let limit: f64 = number.trim().parse().map_err(|_| invalid())?;
+            if !limit.is_finite() || limit < 0.0 {
+                return Err(invalid());
+            }
+            return Ok(Growth::Percent(limit));
+        }
+        let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        let factor: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1024,
+            "M" | "MB" | "MIB" => 1024 * 1024,
+            "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+            _ => return Err(invalid()),
+        };
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        if !number.is_finite() || number < 0.0 {
+            return Err(invalid());
+        }
+        Ok(Growth::Bytes((number * factor as f64) as u64))
+    }
+}
+
+/// Anggaran ukuran incremental update signature; batas yang terlewati hanya
+/// menghasilkan peringatan
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UpdateBudget {
+    pub max_growth: Option<Growth>,  // Pertumbuhan file maksimal; None = tidak diperiksa
+    pub max_objects: Option<usize>, // Jumlah object yang ditulis maksimal; None = tidak diperiksa
+}
+
+impl UpdateBudget {
+    /// Apakah tidak ada batas yang diperiksa
+    pub fn is_empty(&self) -> bool {
+        self.max_growth.is_none() && self.max_objects.is_none()
+    }
+}
+
+/// Object yang ditambahkan atau ditulis ulang oleh signing
+pub struct AddedObject {
+    pub id: ObjectId, // Nomor object
+    pub kind: String, // Jenis untuk pesan, misalnya "image XObject" atau "font file"
+    pub size: u64,    // Perkiraan ukuran serialisasi (bytes)
+}
+
+/// Pertumbuhan dokumen karena signing
+pub struct UpdateSize {
+    pub before: u64,               // Ukuran file sebelum signing
+    pub after: u64,                // Ukuran file setelah signing (termasuk DSS dan document timestamp)
+    pub objects: Vec<AddedObject>, // Object yang ditambahkan atau ditulis ulang, terbesar dulu
+}
+
+impl UpdateSize {
+    /// Bytes yang ditambahkan
+    pub fn added(&self) -> u64 {
+        self.after.saturating_sub(self.before)
+    }
+
+    /// Ringkasan object terbesar: "12 0 R image XObject (3.8 MB), ..."
+    pub fn largest(&self) -> String {
+        self.objects
+            .iter()
+            .take(LARGEST_SHOWN)
+            .map(|object| format!("{} {} R {} ({})", object.id.0, object.id.1, object.kind, text::human_size(object.size)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Ukur object yang ditambahkan ke dokumen sejak offset `before`
+///
+/// Object dihitung dari cross-reference dokumen hasil: entri yang menunjuk ke
+/// setelah `before`, atau ke object stream di sana. Isi stream yang tidak
+/// dimuat (`sign::read_document` melewati gambar dan file lampiran) dihitung
+/// dari /Length-nya.
+///
+/// Parameter:
+///   - doc: dokumen hasil signing (semua revisi)
+///   - before: ukuran file sebelum signing
+///   - after: ukuran file setelah signing
+pub fn measure(doc: &Document, before: u64, after: u64) -> Result<UpdateSize> {
+    let added_at = |offset: u32| u64::from(offset) >= before;
+    let mut objects = Vec::new();
+    for (&number, entry) in doc.reference_table.entries.iter() {
+        let generation = match *entry {
+            XrefEntry::Normal { offset, generation } if added_at(offset) => generation,
+            XrefEntry::Compressed { container, .. } => match doc.reference_table.get(container) {
+                Some(&XrefEntry::Normal { offset, .. }) if added_at(offset) => 0,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let id = (number, generation);
+        let Ok(object) = doc.get_object(id) else {
+            continue;
+        };
+        let Some(kind) = kind(object) else {
+            continue;
+        };
+        objects.push(AddedObject { id, kind, size: object_size(doc, object)? });
+    }
+    objects.sort_by(|a, b| b.size.cmp(&a.size).then(a.id.cmp(&b.id)));
+    Ok(UpdateSize { before, after, objects })
+}
+
+/// Bandingkan pertumbuhan dengan anggaran
+///
+/// Return: satu pesan per batas yang terlewati, beserta object terbesar
+pub fn check(size: &UpdateSize, budget: &UpdateBudget) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(limit) = budget.max_growth.filter(|limit| limit.exceeded(size.before, size.added())) {
+        warnings.push(format!(
+            "signing grew the document by {} (+{:.0}%, {} -> {}), above the growth budget of {}",
+            text::human_size(size.added()),
+            percent(size.before, size.added()),
+            text::human_size(size.before),
+            text::human_size(size.after),
+            limit
+        ));
+    }
+    if let Some(limit) = budget.max_objects.filter(|&limit| size.objects.len() > limit) {
+        warnings.push(format!("signing wrote {} objects, above the object budget of {}", size.objects.len(), limit));
+    }
+    if !warnings.is_empty() && !size.objects.is_empty() {
+        warnings.push(format!("largest added objects: {}", size.largest()));
+    }
+    warnings
+}
+
+/// Pertumbuhan dalam persen dari ukuran asli
+fn percent(before: u64, added: u64) -> f64 {
+    if before == 0 {
+        return 0.0;
+    }
+    added as f64 * 100.0 / before as f64
+}
+
+/// Jenis object untuk pesan; None untuk struktur file (xref dan object stream)
+fn kind(object: &Object) -> Option<String> {
+    let dict = match object {
+        Object::Stream(stream) => &stream.dict,
+        Object::Dictionary(dict) => dict,
+        _ => return Some("object".to_string()),
+    };
+    let name = |key: &[u8]| dict.get(key).and_then(Object::as_name).ok().map(|name| String::from_utf8_lossy(name).into_owned());
+    let kind = match (name(b"Type").as_deref(), name(b"Subtype").as_deref()) {
+        (Some("XRef" | "ObjStm"), _) => return None,
+        (_, Some("Image")) => "image XObject".to_string(),
+        (_, Some("Form")) if matches!(object, Object::Stream(_)) => "form XObject".to_string(),
+        (Some("Sig" | "DocTimeStamp"), _) => "signature".to_string(),
+        (Some("Annot"), Some(subtype)) => format!("{} annotation", subtype),
+        (Some("FontDescriptor"), _) => "font descriptor".to_string(),
+        (Some(kind), _) => kind.to_string(),
+        _ if dict.has(b"Length1") || dict.has(b"Length2") => "font file".to_string(),
+        _ if dict.has(b"FT") => "form field".to_string(),
+        _ if matches!(object, Object::Stream(_)) => "stream".to_string(),
+        _ => "dictionary".to_string(),
+    };
+    Some(kind)
+}
+
+/// Perkiraan ukuran serialisasi object; isi stream yang tidak dimuat dihitung
+/// dari /Length
+fn object_size(doc: &Document, object: &Object) -> Result<u64> {
+    let size = update::serialized_len(object)? as u64;
+    let Object::Stream(stream) = object else {
+        return Ok(size);
+    };
+    let declared = stream.dict.get(b"Length").ok().and_then(|length| doc.dereference(length).ok()).and_then(|(_, length)| length.as_i64().ok());
+    let missing = declared.and_then(|length| u64::try_from(length).ok()).unwrap_or(0).saturating_sub(stream.content.len() as u64);
+    Ok(size + missing)
+}
@@ -0,0 +1,208 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use sha1::Sha1; // Fingerprint SHA-1 sertifikat
+use sha2::{Digest, Sha256}; // Fingerprint SHA-256 sertifikat
+use std::str::FromStr; // Pola --expected-signer dari command line
+use x509_cert::der::Decode; // Parsing sertifikat DER
+use x509_cert::ext::pkix::name::GeneralName; // Alamat email di subjectAltName
+use x509_cert::ext::pkix::SubjectAltName; // Extension subjectAltName
+use x509_cert::Certificate; // Sertifikat penandatangan
+
+use crate::pdf::verify::{Severity, SignatureReport}; // Hasil pemeriksaan bawaan dan tingkat temuan
+
+/// Data satu signature yang diberikan ke pemeriksaan tambahan
+pub struct SignatureData<'a> {
+    pub report: &'a SignatureReport,           // Hasil pemeriksaan bawaan (status, waktu, temuan)
+    pub dictionary: &'a lopdf::Dictionary,     // Signature dictionary apa adanya
+    pub reason: Option<String>,                // /Reason
+    pub location: Option<String>,              // /Location
+    pub contact_info: Option<String>,          // /ContactInfo
+    pub signer_certificate: Option<&'a [u8]>,  // Sertifikat penandatangan (DER), jika tertanam
+    pub certificates: &'a [Vec<u8>],           // Sertifikat lain yang tertanam di signature (DER)
+}
+
+/// Temuan dari pemeriksaan tambahan
+pub struct CheckFinding {
+    pub severity: Severity, // Error membuat signature yang valid menjadi tidak valid
+    pub message: String,    // Pesan untuk pengguna
+}
+
+impl CheckFinding {
+    /// Temuan berseverity error
+    pub fn error(message: impl Into<String>) -> CheckFinding {
+        CheckFinding { severity: Severity::Error, message: message.into() }
+    }
+
+    /// Temuan berseverity peringatan
+    pub fn warning(message: impl Into<String>) -> CheckFinding {
+        CheckFinding { severity: Severity::Warning, message: message.into() }
+    }
+
+    /// Temuan berseverity info
+    pub fn info(message: impl Into<String>) -> CheckFinding {
+        CheckFinding { severity: Severity::Info, message: message.into() }
+    }
+}
+
+/// Pemeriksaan tambahan milik embedder yang dijalankan untuk setiap signature
+/// setelah pemeriksaan bawaan (kriptografi, rantai sertifikat, trust, revocation)
+///
+/// Temuannya masuk ke laporan sebagai `Finding::Custom` dengan nama
+/// pemeriksaannya. Temuan berseverity error membuat signature yang valid menjadi
+/// tidak valid; jika `check` mengembalikan error, signature dianggap tidak bisa
+/// diperiksa.
+///
+/// Contoh penggunaan: penandatangan harus terdaftar di direktori HR, atau
+/// /Reason harus memuat nomor tiket yang valid.
+pub trait VerificationCheck {
+    /// Nama pemeriksaan, ditampilkan di depan setiap temuannya
+    fn name(&self) -> &str;
+
+    /// Periksa satu signature dan kembalikan temuannya (kosong = tidak ada masalah)
+    fn check(&self, signature: &SignatureData) -> Result<Vec<CheckFinding>>;
+}
+
+/// Satu pola penandatangan yang diharapkan (verify --expected-signer)
+#[derive(Clone, Debug)]
+pub struct SignerPattern {
+    text: String,       // Pola seperti ditulis pengguna (untuk pesan)
+    kind: PatternKind,  // Cara mencocokkan sertifikat
+}
+
+/// Jenis pola penandatangan
+#[derive(Clone, Debug)]
+enum PatternKind {
+    /// Atribut subject DN (kunci huruf besar, nilai boleh memakai `*`) yang harus ada semua
+    SubjectDn(Vec<(String, String)>),
+    /// Alamat email rfc822Name di subjectAltName (huruf besar/kecil diabaikan)
+    Email(String),
+    /// SHA-1 (20 byte) atau SHA-256 (32 byte) sertifikat DER
+    Fingerprint(Vec<u8>),
+}
+
+impl FromStr for SignerPattern {
+    type Err = anyhow::Error;
+
+    /// Pola dari teks: `sha256:`/hex 64 digit atau `sha1:`/hex 40 digit
+    /// (titik dua boleh) untuk fingerprint, alamat dengan `@` untuk email
+    /// subjectAltName, selain itu atribut subject DN `CN=...,O=...`
+    fn from_str(text: &str) -> Result<SignerPattern> {
+        let value = text.trim();
+        let hex: String = value
+            .strip_prefix("sha256:")
+            .or_else(|| value.strip_prefix("sha1:"))
+            .unwrap_or(value)
+            .chars()
+            .filter(|&c| c != ':')
+            .collect();
+        let kind = if matches!(hex.len(), 40 | 64) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let bytes = (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16)).collect::<Result<Vec<u8>, _>>()?;
+            PatternKind::Fingerprint(bytes)
+        } else if value.starts_with("sha256:") || value.starts_with("sha1:") {
+            bail!("invalid certificate fingerprint `{}` (expected 64 hex digits for SHA-256 or 40 for SHA-1)", value);
+        } else if value.contains('@') && !value.contains('=') {
+            PatternKind::Email(value.to_ascii_lowercase())
+        } else if value.contains('=') {
+            let attributes = value
+                .split(',')
+                .map(|attribute| {
+                    let (key, value) = attribute.split_once('=').ok_or_else(|| anyhow!("invalid subject DN attribute `{}` (expected KEY=VALUE)", attribute.trim()))?;
+                    Ok((key.trim().to_ascii_uppercase(), value.trim().to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            PatternKind::SubjectDn(attributes)
+        } else {
+            bail!("invalid expected signer `{}` (expected a subject DN like CN=Name,O=Org, an email address, or a certificate fingerprint)", value);
+        };
+        Ok(SignerPattern { text: value.to_string(), kind })
+    }
+}
+
+impl SignerPattern {
+    /// Apakah sertifikat penandatangan (DER) cocok dengan pola ini
+    fn matches(&self, der: &[u8], certificate: &Certificate) -> bool {
+        match &self.kind {
+            PatternKind::Fingerprint(fingerprint) if fingerprint.len() == 20 => Sha1::digest(der)[..] == fingerprint[..],
+            PatternKind::Fingerprint(fingerprint) => Sha256::digest(der)[..] == fingerprint[..],
+            PatternKind::Email(email) => match certificate.tbs_certificate.get::<SubjectAltName>() {
+                Ok(Some((_, names))) => names.0.iter().any(|name| matches!(name, GeneralName::Rfc822Name(address) if address.as_str().eq_ignore_ascii_case(email))),
+                _ => false,
+            },
+            PatternKind::SubjectDn(wanted) => {
+                let subject: Vec<(String, String)> = certificate
+                    .tbs_certificate
+                    .subject
+                    .0
+                    .iter()
+                    .flat_map(|rdn| rdn.0.iter())
+                    .filter_map(|attribute| attribute.to_string().split_once('=').map(|(key, value)| (key.to_ascii_uppercase(), value.to_string())))
+                    .collect();
+                wanted.iter().all(|(key, pattern)| subject.iter().any(|(name, value)| name == key && wildcard_match(pattern, value)))
+            }
+        }
+    }
+}
+
+/// Pemeriksaan identitas penandatangan: sertifikat penandatangan harus cocok
+/// dengan salah satu pola (verify --expected-signer)
+///
+/// Signature dari orang lain, atau tanpa sertifikat yang bisa dibandingkan,
+/// mendapat temuan error sehingga tidak valid. Document timestamp tidak punya
+/// penandatangan dan dilewati.
+pub struct ExpectedSigner {
+    patterns: Vec<SignerPattern>,
+}
+
+impl ExpectedSigner {
+    /// Allow-list dari pola-pola penandatangan (cocok dengan salah satu = diterima)
+    pub fn new(patterns: Vec<SignerPattern>) -> ExpectedSigner {
+        ExpectedSigner { patterns }
+    }
+
+    /// Pola-pola untuk pesan, dipisah " or "
+    fn describe(&self) -> String {
+        self.patterns.iter().map(|pattern| pattern.text.as_str()).collect::<Vec<_>>().join(" or ")
+    }
+}
+
+impl VerificationCheck for ExpectedSigner {
+    fn name(&self) -> &str {
+        "expected signer"
+    }
+
+    fn check(&self, signature: &SignatureData) -> Result<Vec<CheckFinding>> {
+        if signature.report.sub_filter.as_deref() == Some("ETSI.RFC3161") {
+            return Ok(Vec::new());
+        }
+        let Some(der) = signature.signer_certificate else {
+            return Ok(vec![CheckFinding::error(format!("no signer certificate to compare with {}", self.describe()))]);
+        };
+        let certificate = Certificate::from_der(der).map_err(|e| anyhow!("cannot parse the signer certificate: {}", e))?;
+        Ok(match self.patterns.iter().find(|pattern| pattern.matches(der, &certificate)) {
+            Some(pattern) => vec![CheckFinding::info(format!("signer matches {}", pattern.text))],
+            None => vec![CheckFinding::error(format!("signed by {}, not by {}", certificate.tbs_certificate.subject, self.describe()))],
+        })
+    }
+}
+
+/// Cocokkan teks dengan pola yang boleh memakai `*` (nol karakter atau lebih);
+/// huruf besar/kecil diabaikan
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.to_lowercase(), text.to_lowercase());
+    let mut pieces = pattern.split('*');
+    let first = pieces.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let pieces: Vec<&str> = pieces.collect();
+    let Some((last, middle)) = pieces.split_last() else {
+        return rest.is_empty();
+    };
+    for piece in middle {
+        match rest.find(piece) {
+            Some(at) => rest = &rest[at + piece.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
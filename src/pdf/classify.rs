@@ -0,0 +1,164 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Document, Object}; // Dokumen yang diperiksa
+use std::io::Write; // Mengirim deskripsi dokumen ke stdin hook
+use std::process::{Command, Stdio}; // Menjalankan hook eksternal
+use std::sync::OnceLock; // Teks halaman diekstrak sekali, saat pertama diminta
+
+use crate::pdf::options::SignatureOptions; // Opsi yang boleh diubah hook
+use crate::pdf::text::{decode_text_string, json_string}; // Metadata /Info dan JSON untuk hook
+use crate::pdf::{form, placement}; // Signature yang sudah ada, pencarian teks
+
+/// Entri /Info yang dikirim ke hook eksternal
+const METADATA_KEYS: [&str; 6] = ["Title", "Author", "Subject", "Keywords", "Creator", "Producer"];
+
+/// Dokumen yang akan ditandatangani, seperti yang dilihat hook klasifikasi
+/// (`SigningHooks::on_document` dan --classify-hook)
+///
+/// Dokumen terenkripsi sudah didekripsi dengan --pdf-password. Teks halaman
+/// baru diekstrak saat pertama kali diminta.
+pub struct DocumentView<'a> {
+    doc: &'a Document,
+    text: OnceLock<Vec<String>>,
+}
+
+impl<'a> DocumentView<'a> {
+    /// Bungkus dokumen yang sudah dimuat
+    pub fn new(doc: &'a Document) -> Self {
+        DocumentView { doc, text: OnceLock::new() }
+    }
+
+    /// Dokumen lopdf lengkap, untuk pemeriksaan yang tidak disediakan di sini
+    pub fn document(&self) -> &Document {
+        self.doc
+    }
+
+    /// Jumlah halaman
+    pub fn page_count(&self) -> usize {
+        self.doc.get_pages().len()
+    }
+
+    /// Teks per halaman (mulai dari halaman 1); halaman yang isinya tidak bisa
+    /// dibaca (font tanpa encoding yang dikenal) berisi string kosong
+    pub fn page_text(&self) -> &[String] {
+        self.text.get_or_init(|| {
+            self.doc
+                .get_pages()
+                .keys()
+                .map(|&number| self.doc.extract_text(&[number]).unwrap_or_default())
+                .collect()
+        })
+    }
+
+    /// Apakah teks ada di salah satu halaman; spasi dan huruf besar/kecil
+    /// diabaikan seperti pada `anchor_text` aturan posisi
+    pub fn contains_text(&self, needle: &str) -> bool {
+        let needle = placement::normalize_text(needle);
+        needle.is_empty() || self.page_text().iter().any(|text| placement::normalize_text(text).contains(&needle))
+    }
+
+    /// Entri text string di dictionary /Info (misalnya "Title" atau "Producer")
+    pub fn metadata(&self, key: &str) -> Option<String> {
+        let info = self.doc.trailer.get(b"Info").and_then(|info| self.doc.dereference(info)).and_then(|(_, info)| info.as_dict()).ok()?;
+        match info.get(key.as_bytes()).and_then(|value| self.doc.dereference(value)).ok()?.1 {
+            Object::String(bytes, _) => Some(decode_text_string(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Nama field signature yang sudah ditandatangani
+    pub fn signatures(&self) -> Vec<String> {
+        form::signature_fields(self.doc).into_iter().map(|field| field.name).collect()
+    }
+
+    /// Deskripsi dokumen dan signature yang akan dibuat, dalam JSON (stdin --classify-hook)
+    fn to_json(&self, options: &SignatureOptions) -> String {
+        let list = |items: Vec<String>| items.iter().map(|item| json_string(item)).collect::<Vec<_>>().join(", ");
+        let metadata: Vec<String> = METADATA_KEYS
+            .iter()
+            .filter_map(|key| Some(format!("{}: {}", json_string(key), json_string(&self.metadata(key)?))))
+            .collect();
+        format!(
+            "{{\"version\": {}, \"pages\": {}, \"metadata\": {{{}}}, \"signatures\": [{}], \
+             \"signer\": {{\"name\": {}, \"reason\": {}, \"location\": {}, \"contact_info\": {}}}, \"text\": [{}]}}",
+            json_string(&self.doc.version),
+            self.page_count(),
+            metadata.join(", "),
+            list(self.signatures()),
+            json_string(&options.name),
+            json_string(&options.reason),
+            json_string(&options.location),
+            json_string(&options.contact_info),
+            list(self.page_text().to_vec()),
+        )
+    }
+}
+
+/// Jalankan hook klasifikasi eksternal (--classify-hook) sebelum dokumen
+/// ditandatangani
+///
+/// Command dijalankan lewat shell (`sh -c`, atau `cmd /C` di Windows) dengan
+/// deskripsi dokumen dalam JSON di stdin. Exit code selain 0 membatalkan
+/// signing dengan stderr command sebagai alasannya. Di stdout, baris
+/// `reason=...`, `location=...`, atau `contact-info=...` mengganti nilai
+/// tersebut; baris kosong dan baris yang diawali `#` diabaikan.
+///
+/// Parameter:
+///   - command: command line hook
+///   - view: dokumen yang akan ditandatangani
+///   - options: opsi signature; diubah sesuai output hook
+pub(crate) fn run_command(command: &str, view: &DocumentView, options: &mut SignatureOptions) -> Result<()> {
+    let input = view.to_json(options);
+    #[cfg(windows)]
+    let mut process = {
+        let mut process = Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    };
+    #[cfg(not(windows))]
+    let mut process = {
+        let mut process = Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+    let mut child = process
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("cannot run the classification hook `{}`", command))?;
+
+    // stdin ditulis dari thread lain agar hook yang menulis output besar
+    // sebelum selesai membaca tidak macet
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let output = std::thread::scope(|scope| {
+        // Hook boleh tidak membaca stdin sama sekali (broken pipe diabaikan)
+        scope.spawn(move || stdin.write_all(input.as_bytes()));
+        child.wait_with_output()
+    })
+    .with_context(|| format!("classification hook `{}` failed", command))?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let status = output.status.code().map_or_else(|| "terminated by a signal".to_string(), |code| format!("exit code {}", code));
+        if message.is_empty() {
+            bail!("signing refused by the classification hook `{}` ({})", command, status);
+        }
+        bail!("signing refused by the classification hook `{}`: {} ({})", command, message, status);
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|_| anyhow!("classification hook `{}` printed invalid UTF-8", command))?;
+    for line in stdout.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow!("classification hook `{}`: expected `key=value`, got `{}`", command, line))?;
+        let value = value.trim().to_string();
+        match key.trim() {
+            "reason" => options.reason = value,
+            "location" => options.location = value,
+            "contact-info" => options.contact_info = value,
+            other => bail!("classification hook `{}`: unknown option `{}` (expected reason, location or contact-info)", command, other),
+        }
+    }
+    Ok(())
+}
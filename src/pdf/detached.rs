@@ -0,0 +1,119 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs::File; // Dokumen input dibaca langsung dari file
+use std::io::Read; // Hash file per blok
+use std::path::Path; // Path input, .p7s, dan output
+
+use crate::cancel::CancellationToken; // Pembatalan dan timeout
+use crate::crypto::cert; // Kredensial uji (generate-cert)
+#[cfg(feature = "network")]
+use crate::crypto::cms::add_signature_timestamp; // Timestamp RFC 3161 di CMS SignedData
+use crate::crypto::cms::{build_signed_data, SignedAttributes}; // CMS SignedData
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::signer::Signer; // Backend penandatanganan
+use crate::pdf::naming; // Waktu signing default (mode deterministik)
+#[cfg(feature = "network")]
+use crate::crypto::tsa; // Request ke Time Stamping Authority
+use crate::lock::FileLock; // Lock antar proses pada file .p7s
+use crate::pdf::options::SignatureOptions; // Opsi penandatanganan
+use crate::pdf::hooks::NoHooks; // Signature di dalam PDF tanpa hook
+use crate::pdf::sign::{is_stdio, load_credentials, sign_file, SignSummary}; // Kunci, signature di dalam PDF, ringkasan
+
+/// Tandatangani isi file PDF apa adanya dan tulis CMS SignedData detached
+/// (DER) ke file terpisah, tanpa atau sekaligus dengan signature di dalam PDF
+///
+/// Digest dihitung atas seluruh bytes file input, jadi signature detached
+/// hanya valid selama file tersebut tidak berubah satu byte pun. Signed
+/// attributes selalu memuat signingTime, karena tidak ada /M di luar PDF. Tanpa
+/// `output`, opsi yang hanya berlaku untuk signature di dalam PDF (level PAdES,
+/// certification, --lock-fields, --sign-attachments) ditolak. Dengan `output`,
+/// signature detached dibuat lebih dulu, sehingga output boleh sama dengan
+/// input. Kunci dimuat sekali untuk keduanya.
+///
+/// Parameter:
+///   - input: path file PDF ("-" untuk stdin, hanya tanpa `output`)
+///   - detached: path file .p7s hasil
+///   - output: path PDF hasil dengan signature tertanam (None = PDF tidak ditulis)
+///   - key_path: path file kunci privat; diabaikan jika options.pkcs12 di-set
+///   - options: kunci, digest, TSA, dan kebijakan algoritma
+///   - cancel: token pembatalan; batas waktunya juga membatasi request TSA
+///
+/// Return: ringkasan signature yang dibuat
+pub fn sign_detached(
+    input: &Path,
+    detached: &Path,
+    output: Option<&Path>,
+    key_path: impl AsRef<Path>,
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+) -> Result<SignSummary> {
+    match output {
+        Some(_) if is_stdio(input) => bail!("--detached with --output needs --input to be a file, not stdin"),
+        Some(_) => {}
+        None if options.pades.is_some() => {
+            bail!("--pades-level applies to signatures embedded in the PDF; add --output to embed one next to the detached signature")
+        }
+        None if options.certify.is_some() || options.lock_fields.is_some() || options.sign_attachments => {
+            bail!("--certify, --lock-fields and --sign-attachments change the PDF; add --output to embed a signature next to the detached one")
+        }
+        None => {}
+    }
+    // Proses lain yang menulis file .p7s yang sama menunggu sampai selesai
+    let _lock = FileLock::exclusive(detached)?;
+    let (signer, certificates) = load_credentials(key_path, options)?;
+    let signature = detached_signature(input, signer.as_ref(), &certificates, options, cancel)?;
+    cancel.check()?;
+    crate::storage::write_file(detached, &signature)?;
+
+    if let Some(output) = output {
+        sign_file(input, output, signer.as_ref(), &certificates, options, &mut NoHooks, cancel)?;
+    }
+    Ok(SignSummary::new(signer.as_ref(), &certificates, options))
+}
+
+/// CMS SignedData detached atas seluruh isi file (tanpa menulis apa pun)
+fn detached_signature(
+    input: &Path,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    // Kunci Ed25519 menentukan digest-nya sendiri (SHA-512, RFC 8419)
+    let algorithm = signer.digest_for(options.digest);
+    options.algorithm_policy.check_signing(&signer.public_key_info()?, certificates)?;
+    cert::check_test_credentials(certificates, options.allow_test_credentials)?;
+    let embedded = options.embed_chain.select(certificates)?;
+
+    let digest = match is_stdio(input) {
+        true => digest_reader(std::io::stdin().lock(), algorithm, cancel)?,
+        false => digest_reader(File::open(input).with_context(|| format!("cannot read {}", input.display()))?, algorithm, cancel)?,
+    };
+    let signing_time = options.signing_time.unwrap_or_else(naming::now);
+    #[cfg_attr(not(feature = "network"), allow(unused_mut))]
+    let mut signature = build_signed_data(&digest, algorithm, signer, &embedded, signing_time.with_timezone(&chrono::Utc), SignedAttributes::Pkcs7)?;
+
+    // Timestamp RFC 3161 atas nilai signature, sama seperti signature di dalam PDF
+    #[cfg(feature = "network")]
+    if let Some(tsa) = &options.timestamp {
+        signature = add_signature_timestamp(&signature, |value| {
+            tsa::request_timestamp(tsa, &mut |hash| Ok(hash.digest(value)), &options.algorithm_policy, cancel)
+        })?;
+    }
+    Ok(signature)
+}
+
+/// Hitung digest isi reader per blok 1 MiB; token pembatalan diperiksa di setiap blok
+fn digest_reader(mut reader: impl Read, algorithm: DigestAlgorithm, cancel: &CancellationToken) -> Result<Vec<u8>> {
+    let mut hasher = algorithm.hasher();
+    let mut buffer = vec![0; 1024 * 1024];
+    loop {
+        cancel.check()?;
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize())
+}
@@ -0,0 +1,710 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Error handling
+use aes::cipher::block_padding::{NoPadding, Pkcs7}; // Padding CBC (string/stream: PKCS#7, kunci: tanpa padding)
+use aes::cipher::{BlockCipher, BlockDecrypt, BlockDecryptMut, BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit}; // Mode CBC
+use lopdf::xref::XrefEntry; // Object di dalam object stream
+use lopdf::{Dictionary, Document, Object, ObjectId, ObjectStream}; // Struktur dasar PDF
+use sha2::{Digest, Sha256, Sha384, Sha512}; // Hash password revisi 5 dan 6
+use std::collections::BTreeSet; // Object stream yang harus dimuat ulang
+
+use crate::crypto::provider; // Kunci file, salt, dan IV AES acak
+
+/// Padding password (ISO 32000-1 7.6.3.3, Algorithm 2 langkah a)
+const PASSWORD_PADDING: [u8; 32] = [
+    0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA, 0x01, 0x08,
+    0x2E, 0x2E, 0x00, 0xB6, 0xD0, 0x68, 0x3E, 0x80, 0x2F, 0x0C, 0xA9, 0xFE, 0x64, 0x53, 0x69, 0x7A,
+];
+
+/// Bit /P yang mengizinkan mengisi form field, termasuk field signature:
+/// bit 6 (anotasi dan form) atau bit 9 (hanya mengisi form, revisi 3 ke atas)
+const FILL_FORM_PERMISSIONS: i64 = (1 << 5) | (1 << 8);
+
+/// Algoritma crypt filter untuk string atau stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cipher {
+    Identity, // Tidak dienkripsi
+    Rc4,      // RC4 dengan kunci per object (V1, V2, /V2)
+    Aes128,   // AES-128-CBC dengan kunci per object (/AESV2)
+    Aes256,   // AES-256-CBC dengan kunci file (/AESV3)
+}
+
+/// Arah transformasi string dan stream
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Decrypt, // Dokumen yang dimuat
+    Encrypt, // Incremental update yang ditulis
+}
+
+/// Security handler standar (password) dari dokumen terenkripsi
+///
+/// Dibuat dari dictionary /Encrypt dan password; dipakai untuk mendekripsi
+/// dokumen yang dimuat dan mengenkripsi incremental update dengan kunci dan
+/// crypt filter yang sama seperti dokumen aslinya
+pub struct SecurityHandler {
+    key: Vec<u8>,                    // Kunci file (Algorithm 2 / 2.A)
+    strings: Cipher,                 // Crypt filter string (/StrF)
+    streams: Cipher,                 // Crypt filter stream (/StmF)
+    encrypt_metadata: bool,          // /EncryptMetadata: stream XMP ikut dienkripsi
+    encrypt_id: Option<ObjectId>,    // Dictionary /Encrypt (tidak pernah dienkripsi)
+    owner: bool,                     // Dibuka dengan password owner (semua izin)
+}
+
+impl SecurityHandler {
+    /// Dekripsi dokumen terenkripsi yang baru dimuat
+    ///
+    /// Object di dalam object stream tidak bisa dibaca lopdf dari stream yang
+    /// masih terenkripsi, jadi object stream dimuat ulang dari `bytes` setelah
+    /// didekripsi. Tanpa password, password user kosong dicoba (dokumen yang
+    /// hanya dibatasi izinnya).
+    ///
+    /// Parameter:
+    ///   - doc: dokumen hasil parsing `bytes`, didekripsi di tempat
+    ///   - bytes: isi file PDF
+    ///   - password: password user atau owner (--pdf-password)
+    ///
+    /// Return: None jika dokumen tidak terenkripsi
+    pub fn open(doc: &mut Document, bytes: &[u8], password: Option<&str>) -> Result<Option<SecurityHandler>> {
+        let Ok(encrypt) = doc.trailer.get(b"Encrypt") else {
+            return Ok(None);
+        };
+        let (encrypt_id, dict) = match encrypt {
+            Object::Reference(id) => (Some(*id), doc.get_dictionary(*id).context("cannot read the /Encrypt dictionary")?),
+            Object::Dictionary(dict) => (None, dict),
+            _ => bail!("the /Encrypt entry of the trailer is not a dictionary"),
+        };
+        let file_id = doc
+            .trailer
+            .get(b"ID")
+            .and_then(Object::as_array)
+            .ok()
+            .and_then(|id| id.first())
+            .and_then(|id| id.as_str().ok())
+            .unwrap_or_default();
+        let handler = SecurityHandler::new(dict, file_id, encrypt_id, password)?;
+        handler.decrypt_document(doc, bytes)?;
+        Ok(Some(handler))
+    }
+
+    /// Security handler baru (AES-256, revisi 6) untuk dokumen yang dibuat
+    /// sendiri, misalnya oleh `gen-testdoc`
+    ///
+    /// Parameter:
+    ///   - user_password: password untuk membuka dokumen (kosong = terbuka tanpa password)
+    ///   - owner_password: password pemilik (semua izin)
+    ///   - permissions: nilai /P
+    ///   - encrypt_id: ID object tempat dictionary /Encrypt disimpan (tidak dienkripsi)
+    ///
+    /// Return: handler untuk mengenkripsi object dokumen, dan dictionary /Encrypt-nya
+    pub fn create(user_password: &str, owner_password: &str, permissions: i64, encrypt_id: ObjectId) -> (SecurityHandler, Dictionary) {
+        let random = |length: usize| {
+            let mut bytes = vec![0u8; length];
+            provider::fill_random(&mut bytes);
+            bytes
+        };
+        let key = random(32);
+        let modern = Modern { revision: 6, owner: &[], user: &[], owner_key: &[], user_key: &[] };
+        // Password UTF-8 maksimal 127 byte (tanpa SASLprep), sama seperti saat dibuka
+        let truncate = |password: &str| password.as_bytes()[..password.len().min(127)].to_vec();
+        let (user_password, owner_password) = (truncate(user_password), truncate(owner_password));
+
+        // /U dan /UE (Algorithm 8), lalu /O dan /OE atas /U (Algorithm 9)
+        let (validation_salt, key_salt) = (random(8), random(8));
+        let user = [modern.hash(&user_password, &validation_salt, &[]), validation_salt, key_salt.clone()].concat();
+        let user_key = wrap_key(&modern.hash(&user_password, &key_salt, &[]), &key);
+        let (validation_salt, key_salt) = (random(8), random(8));
+        let owner = [modern.hash(&owner_password, &validation_salt, &user), validation_salt, key_salt.clone()].concat();
+        let owner_key = wrap_key(&modern.hash(&owner_password, &key_salt, &user), &key);
+        // /Perms (Algorithm 10): izin, "T" (metadata dienkripsi), "adb", 4 byte acak
+        let mut perms = (permissions as u32).to_le_bytes().to_vec();
+        perms.extend_from_slice(&[0xFF; 4]);
+        perms.extend_from_slice(b"Tadb");
+        perms.extend(random(4));
+
+        let mut filter = Dictionary::new();
+        filter.set("CFM", Object::Name(b"AESV3".to_vec()));
+        filter.set("AuthEvent", Object::Name(b"DocOpen".to_vec()));
+        filter.set("Length", Object::Integer(32));
+        let mut filters = Dictionary::new();
+        filters.set("StdCF", Object::Dictionary(filter));
+        let string = |bytes: Vec<u8>| Object::String(bytes, lopdf::StringFormat::Hexadecimal);
+        let mut dict = Dictionary::new();
+        dict.set("Filter", Object::Name(b"Standard".to_vec()));
+        dict.set("V", Object::Integer(5));
+        dict.set("R", Object::Integer(6));
+        dict.set("Length", Object::Integer(256));
+        dict.set("CF", Object::Dictionary(filters));
+        dict.set("StmF", Object::Name(b"StdCF".to_vec()));
+        dict.set("StrF", Object::Name(b"StdCF".to_vec()));
+        dict.set("O", string(owner));
+        dict.set("U", string(user));
+        dict.set("OE", string(owner_key));
+        dict.set("UE", string(user_key));
+        dict.set("P", Object::Integer(permissions));
+        dict.set("Perms", string(wrap_key(&key, &perms)));
+
+        let handler = SecurityHandler {
+            key,
+            strings: Cipher::Aes256,
+            streams: Cipher::Aes256,
+            encrypt_metadata: true,
+            encrypt_id: Some(encrypt_id),
+            owner: true,
+        };
+        (handler, dict)
+    }
+
+    /// Baca dictionary /Encrypt dan turunkan kunci file dari password
+    fn new(dict: &Dictionary, file_id: &[u8], encrypt_id: Option<ObjectId>, password: Option<&str>) -> Result<SecurityHandler> {
+        let name = |key: &[u8]| dict.get(key).and_then(Object::as_name).ok();
+        let integer = |key: &[u8]| dict.get(key).and_then(Object::as_i64).ok();
+        let bytes = |key: &[u8]| dict.get(key).and_then(Object::as_str).ok().unwrap_or_default();
+        if let Some(filter) = name(b"Filter").filter(|filter| *filter != b"Standard") {
+            bail!(
+                "the document is encrypted with the {} security handler; only password encryption (Standard) is supported",
+                String::from_utf8_lossy(filter)
+            );
+        }
+        let version = integer(b"V").unwrap_or(0);
+        let revision = integer(b"R").ok_or_else(|| anyhow!("the /Encrypt dictionary has no /R"))?;
+        let permissions = integer(b"P").ok_or_else(|| anyhow!("the /Encrypt dictionary has no /P"))?;
+        let encrypt_metadata = dict.get(b"EncryptMetadata").and_then(Object::as_bool).unwrap_or(true);
+
+        // Crypt filter: V1/V2 selalu RC4, V4/V5 memilih lewat /CF, /StmF, /StrF
+        let (strings, streams) = match version {
+            1 | 2 => (Cipher::Rc4, Cipher::Rc4),
+            4 | 5 => {
+                let filter = |key: &[u8]| -> Result<Cipher> {
+                    let name = name(key).unwrap_or(b"Identity");
+                    if name == b"Identity" {
+                        return Ok(Cipher::Identity);
+                    }
+                    let method = dict
+                        .get(b"CF")
+                        .and_then(Object::as_dict)
+                        .and_then(|filters| filters.get(name))
+                        .and_then(Object::as_dict)
+                        .and_then(|filter| filter.get(b"CFM"))
+                        .and_then(Object::as_name)
+                        .unwrap_or(b"None");
+                    match method {
+                        b"None" => Ok(Cipher::Identity),
+                        b"V2" => Ok(Cipher::Rc4),
+                        b"AESV2" => Ok(Cipher::Aes128),
+                        b"AESV3" => Ok(Cipher::Aes256),
+                        other => bail!("unsupported crypt filter method /{}", String::from_utf8_lossy(other)),
+                    }
+                };
+                (filter(b"StrF")?, filter(b"StmF")?)
+            }
+            other => bail!("unsupported encryption version /V {}", other),
+        };
+
+        // Password sebagai user, lalu sebagai owner
+        let password = password.unwrap_or_default().as_bytes();
+        let opened = match revision {
+            2..=4 => {
+                // /Length dalam bit; revisi 2 selalu 40 bit
+                let length = if revision == 2 { 5 } else { (integer(b"Length").unwrap_or(if version == 4 { 128 } else { 40 }) / 8).clamp(5, 16) as usize };
+                let legacy = Legacy { revision, length, owner: bytes(b"O"), user: bytes(b"U"), permissions, file_id, encrypt_metadata };
+                legacy.user_key(password).map(|key| (key, false)).or_else(|| legacy.owner_key(password).map(|key| (key, true)))
+            }
+            5 | 6 => {
+                let modern = Modern { revision, owner: bytes(b"O"), user: bytes(b"U"), owner_key: bytes(b"OE"), user_key: bytes(b"UE") };
+                // Password UTF-8 maksimal 127 byte (tanpa SASLprep)
+                let password = &password[..password.len().min(127)];
+                match modern.owner_key(password)? {
+                    Some(key) => Some((key, true)),
+                    None => modern.user_key(password)?.map(|key| (key, false)),
+                }
+            }
+            other => bail!("unsupported encryption revision /R {}", other),
+        };
+        let Some((key, owner)) = opened else {
+            if password.is_empty() {
+                bail!("the document is encrypted; pass its user or owner password with --pdf-password");
+            }
+            bail!("the --pdf-password is neither the user nor the owner password of the document");
+        };
+
+        // Dengan password user, izin dokumen harus membolehkan mengisi field signature
+        if !owner && permissions & FILL_FORM_PERMISSIONS == 0 {
+            bail!("the document's permissions do not allow filling in form fields, so it cannot be signed; use the owner password");
+        }
+        Ok(SecurityHandler { key, strings, streams, encrypt_metadata, encrypt_id, owner })
+    }
+
+    /// Apakah dokumen dibuka dengan password owner; hanya owner yang boleh
+    /// menghapus enkripsi (--decrypt-output)
+    pub fn is_owner(&self) -> bool {
+        self.owner
+    }
+
+    /// Enkripsi object incremental update sebelum ditulis (object stream tidak
+    /// dipakai untuk dokumen terenkripsi, jadi setiap object dienkripsi sendiri)
+    pub fn encrypt_update(&self, update: &mut Document) -> Result<()> {
+        for (&id, object) in update.objects.iter_mut() {
+            if Some(id) != self.encrypt_id {
+                self.transform(id, object, Direction::Encrypt)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Dekripsi semua object dan muat isi object stream
+    fn decrypt_document(&self, doc: &mut Document, bytes: &[u8]) -> Result<()> {
+        // String di dalam object stream tidak dienkripsi sendiri-sendiri
+        // (ISO 32000-1 7.5.7); yang dienkripsi adalah object stream-nya
+        let compressed = |doc: &Document, id: ObjectId| matches!(doc.reference_table.get(id.0), Some(XrefEntry::Compressed { .. }));
+        let ids: Vec<ObjectId> = doc.objects.keys().copied().filter(|&id| Some(id) != self.encrypt_id && !compressed(doc, id)).collect();
+        for id in ids {
+            let object = doc.objects.get_mut(&id).expect("id was just listed");
+            self.transform(id, object, Direction::Decrypt)?;
+        }
+
+        let containers: BTreeSet<u32> = doc
+            .reference_table
+            .entries
+            .values()
+            .filter_map(|entry| match entry {
+                XrefEntry::Compressed { container, .. } => Some(*container),
+                _ => None,
+            })
+            .collect();
+        for container in containers {
+            let Some(&XrefEntry::Normal { generation, .. }) = doc.reference_table.get(container) else {
+                continue;
+            };
+            let id = (container, generation);
+            // lopdf gagal mendekompresi object stream terenkripsi: object-nya
+            // dibuang atau disimpan dengan isi kosong, jadi dibaca ulang dari file
+            let mut document = Document::new();
+            document.reference_table = doc.reference_table.clone();
+            let reader = lopdf::Reader { buffer: bytes, document };
+            let mut object = reader.get_object(id).with_context(|| format!("cannot read object stream {} {}", id.0, id.1))?;
+            self.transform(id, &mut object, Direction::Decrypt)?;
+            let mut stream = object.as_stream().with_context(|| format!("object {} {} is not an object stream", id.0, id.1))?.clone();
+            doc.objects.insert(id, object);
+            let objects = ObjectStream::new(&mut stream).with_context(|| format!("cannot read object stream {} {}", id.0, id.1))?;
+            for (inner, object) in objects.objects {
+                // Hanya object yang cross-reference-nya menunjuk ke stream ini
+                let contained = matches!(
+                    doc.reference_table.get(inner.0),
+                    Some(&XrefEntry::Compressed { container: owner, .. }) if owner == container
+                );
+                if contained {
+                    doc.objects.insert(inner, object);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Dekripsi atau enkripsi string dan isi stream di dalam object `id`
+    fn transform(&self, id: ObjectId, object: &mut Object, direction: Direction) -> Result<()> {
+        let context = || format!("cannot {} object {} {}", if direction == Direction::Decrypt { "decrypt" } else { "encrypt" }, id.0, id.1);
+        match object {
+            Object::String(content, _) => *content = self.apply(self.strings, id, content, direction).with_context(context)?,
+            Object::Array(items) => {
+                for item in items {
+                    self.transform(id, item, direction)?;
+                }
+            }
+            Object::Dictionary(dict) => self.transform_dictionary(id, dict, direction)?,
+            Object::Stream(stream) => {
+                // Cross-reference stream tidak pernah dienkripsi
+                if stream.dict.type_is(b"XRef") {
+                    return Ok(());
+                }
+                self.transform_dictionary(id, &mut stream.dict, direction)?;
+                let crypt_filter = match stream.dict.get(b"Filter") {
+                    Ok(Object::Name(name)) => name == b"Crypt",
+                    Ok(Object::Array(filters)) => filters.iter().any(|filter| filter.as_name().ok() == Some(b"Crypt")),
+                    _ => false,
+                };
+                let plain_metadata = !self.encrypt_metadata && stream.dict.type_is(b"Metadata");
+                // Isi stream yang tidak dimuat (gambar, lampiran, stream besar) dibiarkan
+                if !stream.content.is_empty() && !crypt_filter && !plain_metadata {
+                    let content = self.apply(self.streams, id, &stream.content, direction).with_context(context)?;
+                    stream.set_content(content);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Transformasi isi dictionary; /Contents signature tidak dienkripsi
+    /// (ISO 32000-1 7.6.1) agar bisa ditambal setelah update ditulis
+    fn transform_dictionary(&self, id: ObjectId, dict: &mut Dictionary, direction: Direction) -> Result<()> {
+        let signature = dict.type_is(b"Sig") || dict.type_is(b"DocTimeStamp") || (dict.has(b"ByteRange") && dict.has(b"Contents"));
+        for (key, value) in dict.iter_mut() {
+            if signature && key == b"Contents" {
+                continue;
+            }
+            self.transform(id, value, direction)?;
+        }
+        Ok(())
+    }
+
+    /// Dekripsi atau enkripsi satu string / isi stream
+    fn apply(&self, cipher: Cipher, id: ObjectId, data: &[u8], direction: Direction) -> Result<Vec<u8>> {
+        match (cipher, direction) {
+            (Cipher::Identity, _) => Ok(data.to_vec()),
+            (Cipher::Rc4, _) => Ok(rc4(&self.object_key(id, false), data)),
+            // String kosong kadang ditulis tanpa enkripsi
+            (Cipher::Aes128 | Cipher::Aes256, Direction::Decrypt) if data.is_empty() => Ok(Vec::new()),
+            (Cipher::Aes128, Direction::Decrypt) => aes_decrypt::<aes::Aes128>(&self.object_key(id, true), data),
+            (Cipher::Aes256, Direction::Decrypt) => aes_decrypt::<aes::Aes256>(&self.key, data),
+            (Cipher::Aes128, Direction::Encrypt) => Ok(aes_encrypt::<aes::Aes128>(&self.object_key(id, true), data)),
+            (Cipher::Aes256, Direction::Encrypt) => Ok(aes_encrypt::<aes::Aes256>(&self.key, data)),
+        }
+    }
+
+    /// Kunci per object untuk RC4 dan AESV2 (Algorithm 1)
+    fn object_key(&self, id: ObjectId, aes: bool) -> Vec<u8> {
+        let mut context = md5::Context::new();
+        context.consume(&self.key);
+        context.consume(&id.0.to_le_bytes()[..3]);
+        context.consume(&id.1.to_le_bytes()[..2]);
+        if aes {
+            context.consume(b"sAlT");
+        }
+        context.compute().0[..(self.key.len() + 5).min(16)].to_vec()
+    }
+}
+
+/// Parameter security handler revisi 2-4 (kunci dari MD5 dan RC4)
+struct Legacy<'a> {
+    revision: i64,          // /R
+    length: usize,          // Panjang kunci file dalam bytes
+    owner: &'a [u8],        // /O
+    user: &'a [u8],         // /U
+    permissions: i64,       // /P
+    file_id: &'a [u8],      // Elemen pertama /ID di trailer
+    encrypt_metadata: bool, // /EncryptMetadata
+}
+
+impl Legacy<'_> {
+    /// Kunci file dari password user (Algorithm 2), None jika password salah (Algorithm 6)
+    fn user_key(&self, password: &[u8]) -> Option<Vec<u8>> {
+        let mut context = md5::Context::new();
+        context.consume(pad_password(password));
+        context.consume(self.owner.get(..32)?);
+        context.consume((self.permissions as u32).to_le_bytes());
+        context.consume(self.file_id);
+        if self.revision >= 4 && !self.encrypt_metadata {
+            context.consume([0xFF; 4]);
+        }
+        let mut key = context.compute().0[..self.length].to_vec();
+        if self.revision >= 3 {
+            for _ in 0..50 {
+                key = md5::compute(&key).0[..self.length].to_vec();
+            }
+        }
+
+        // Bandingkan /U hasil hitungan dengan /U di dokumen (Algorithm 4 / 5)
+        let valid = if self.revision == 2 {
+            rc4(&key, &PASSWORD_PADDING) == self.user
+        } else {
+            let mut context = md5::Context::new();
+            context.consume(PASSWORD_PADDING);
+            context.consume(self.file_id);
+            let mut hash = rc4(&key, &context.compute().0);
+            for round in 1..=19u8 {
+                let round_key: Vec<u8> = key.iter().map(|byte| byte ^ round).collect();
+                hash = rc4(&round_key, &hash);
+            }
+            self.user.get(..16) == Some(&hash[..])
+        };
+        valid.then_some(key)
+    }
+
+    /// Kunci file dari password owner: /O didekripsi menjadi password user (Algorithm 7)
+    fn owner_key(&self, password: &[u8]) -> Option<Vec<u8>> {
+        let mut key = md5::compute(pad_password(password)).0.to_vec();
+        if self.revision >= 3 {
+            for _ in 0..50 {
+                key = md5::compute(&key).0.to_vec();
+            }
+        }
+        key.truncate(self.length);
+        let mut user_password = self.owner.get(..32)?.to_vec();
+        if self.revision == 2 {
+            user_password = rc4(&key, &user_password);
+        } else {
+            for round in (0..=19u8).rev() {
+                let round_key: Vec<u8> = key.iter().map(|byte| byte ^ round).collect();
+                user_password = rc4(&round_key, &user_password);
+            }
+        }
+        self.user_key(&user_password)
+    }
+}
+
+/// Parameter security handler revisi 5-6 (AES-256, kunci file terbungkus di /OE dan /UE)
+struct Modern<'a> {
+    revision: i64,        // /R
+    owner: &'a [u8],      // /O: hash (32), validation salt (8), key salt (8)
+    user: &'a [u8],       // /U: hash (32), validation salt (8), key salt (8)
+    owner_key: &'a [u8],  // /OE: kunci file terenkripsi dengan password owner
+    user_key: &'a [u8],   // /UE: kunci file terenkripsi dengan password user
+}
+
+impl Modern<'_> {
+    /// Kunci file jika `password` adalah password owner (Algorithm 2.A)
+    fn owner_key(&self, password: &[u8]) -> Result<Option<Vec<u8>>> {
+        let (Some(owner), Some(user)) = (self.owner.get(..48), self.user.get(..48)) else {
+            bail!("the /O or /U entry of the /Encrypt dictionary is too short");
+        };
+        if self.hash(password, &owner[32..40], user) != owner[..32] {
+            return Ok(None);
+        }
+        unwrap_key(&self.hash(password, &owner[40..48], user), self.owner_key).map(Some)
+    }
+
+    /// Kunci file jika `password` adalah password user (Algorithm 2.A)
+    fn user_key(&self, password: &[u8]) -> Result<Option<Vec<u8>>> {
+        let Some(user) = self.user.get(..48) else {
+            bail!("the /U entry of the /Encrypt dictionary is too short");
+        };
+        if self.hash(password, &user[32..40], &[]) != user[..32] {
+            return Ok(None);
+        }
+        unwrap_key(&self.hash(password, &user[40..48], &[]), self.user_key).map(Some)
+    }
+
+    /// Hash password: SHA-256 (revisi 5) atau Algorithm 2.B (revisi 6)
+    fn hash(&self, password: &[u8], salt: &[u8], user: &[u8]) -> Vec<u8> {
+        let mut key = Sha256::new().chain_update(password).chain_update(salt).chain_update(user).finalize().to_vec();
+        if self.revision == 5 {
+            return key;
+        }
+        let mut round = 0usize;
+        loop {
+            let block: Vec<u8> = [password, &key, user].concat();
+            let repeated = block.repeat(64);
+            let encrypted = cbc::Encryptor::<aes::Aes128>::new_from_slices(&key[..16], &key[16..32])
+                .expect("AES-128 key and IV are 16 bytes")
+                .encrypt_padded_vec_mut::<NoPadding>(&repeated);
+            // 16 byte pertama sebagai bilangan big-endian modulo 3 = jumlah byte modulo 3
+            key = match encrypted[..16].iter().map(|&byte| usize::from(byte)).sum::<usize>() % 3 {
+                0 => Sha256::digest(&encrypted).to_vec(),
+                1 => Sha384::digest(&encrypted).to_vec(),
+                _ => Sha512::digest(&encrypted).to_vec(),
+            };
+            round += 1;
+            let last = usize::from(*encrypted.last().expect("the encrypted block is not empty"));
+            if round >= 64 && last + 32 <= round {
+                break;
+            }
+        }
+        key.truncate(32);
+        key
+    }
+}
+
+/// Buka kunci file dari /OE atau /UE (AES-256-CBC, IV nol, tanpa padding)
+fn unwrap_key(key: &[u8], wrapped: &[u8]) -> Result<Vec<u8>> {
+    let wrapped = wrapped.get(..32).ok_or_else(|| anyhow!("the /OE or /UE entry of the /Encrypt dictionary is too short"))?;
+    cbc::Decryptor::<aes::Aes256>::new_from_slices(key, &[0; 16])
+        .expect("AES-256 key is 32 bytes")
+        .decrypt_padded_vec_mut::<NoPadding>(wrapped)
+        .map_err(|_| anyhow!("cannot unwrap the file key"))
+}
+
+/// Bungkus kunci file untuk /OE dan /UE, atau enkripsi /Perms (AES-256-CBC,
+/// IV nol, tanpa padding; satu blok sama dengan ECB)
+fn wrap_key(key: &[u8], data: &[u8]) -> Vec<u8> {
+    cbc::Encryptor::<aes::Aes256>::new_from_slices(key, &[0; 16])
+        .expect("AES-256 key is 32 bytes")
+        .encrypt_padded_vec_mut::<NoPadding>(data)
+}
+
+/// Password dipotong / ditambah padding menjadi 32 byte
+fn pad_password(password: &[u8]) -> [u8; 32] {
+    let mut padded = PASSWORD_PADDING;
+    let length = password.len().min(32);
+    padded[..length].copy_from_slice(&password[..length]);
+    padded[length..].copy_from_slice(&PASSWORD_PADDING[..32 - length]);
+    padded
+}
+
+/// RC4 (simetris: enkripsi dan dekripsi sama)
+fn rc4(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut state: [u8; 256] = std::array::from_fn(|i| i as u8);
+    let mut j = 0u8;
+    for i in 0..256 {
+        j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+        state.swap(i, usize::from(j));
+    }
+    let (mut i, mut j) = (0u8, 0u8);
+    data.iter()
+        .map(|byte| {
+            i = i.wrapping_add(1);
+            j = j.wrapping_add(state[usize::from(i)]);
+            state.swap(usize::from(i), usize::from(j));
+            byte ^ state[usize::from(state[usize::from(i)].wrapping_add(state[usize::from(j)]))]
+        })
+        .collect()
+}
+
+/// Dekripsi AES-CBC: 16 byte pertama adalah IV, padding PKCS#7
+fn aes_decrypt<C>(key: &[u8], data: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockCipher + BlockDecrypt + KeyInit,
+{
+    if data.len() < 32 || !data.len().is_multiple_of(16) {
+        bail!("AES data of {} bytes is not an IV followed by whole blocks", data.len());
+    }
+    let (iv, encrypted) = data.split_at(16);
+    cbc::Decryptor::<C>::new_from_slices(key, iv)
+        .map_err(|_| anyhow!("invalid AES key length"))?
+        .decrypt_padded_vec_mut::<Pkcs7>(encrypted)
+        .map_err(|_| anyhow!("invalid AES padding (wrong key?)"))
+}
+
+/// Enkripsi AES-CBC dengan IV acak di depan hasil, padding PKCS#7
+fn aes_encrypt<C>(key: &[u8], data: &[u8]) -> Vec<u8>
+where
+    C: BlockCipher + BlockEncrypt + KeyInit,
+{
+    let mut iv = [0u8; 16];
+    provider::fill_random(&mut iv);
+    let encrypted = cbc::Encryptor::<C>::new_from_slices(key, &iv)
+        .expect("the file key has the AES key length")
+        .encrypt_padded_vec_mut::<Pkcs7>(data);
+    [iv.as_slice(), &encrypted].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{Stream, StringFormat};
+
+    // Vektor uji dihitung terpisah dari implementasi ini (ISO 32000-2 7.6.4):
+    // password user "user", owner "owner", /P -1084, /ID pertama FILE_ID
+    const FILE_ID: &[u8] = b"pdfsign-test-id!";
+    const PERMISSIONS: i64 = -1084;
+    const PLAIN: &[u8] = b"Signed by pdfsign";
+    const OBJECT: ObjectId = (7, 0);
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn string(bytes: Vec<u8>) -> Object {
+        Object::String(bytes, StringFormat::Hexadecimal)
+    }
+
+    /// /Encrypt revisi 3 (RC4 128 bit) atau 4 (crypt filter /AESV2) dengan /O dan /U yang sama
+    fn legacy(aes: bool) -> Dictionary {
+        let mut dict = Dictionary::new();
+        dict.set("Filter", Object::Name(b"Standard".to_vec()));
+        dict.set("Length", 128);
+        dict.set("P", PERMISSIONS);
+        dict.set("O", string(hex("0ba3835f88f90388e74e54584125ce142be0de24c6b0d37746e075b891756671")));
+        dict.set("U", string(hex("5852086eedc541adcc5a03e753c65d4c00000000000000000000000000000000")));
+        if aes {
+            let mut filter = Dictionary::new();
+            filter.set("CFM", Object::Name(b"AESV2".to_vec()));
+            dict.set("CF", Dictionary::from_iter([("StdCF", Object::Dictionary(filter))]));
+            dict.set("StmF", Object::Name(b"StdCF".to_vec()));
+            dict.set("StrF", Object::Name(b"StdCF".to_vec()));
+            dict.set("V", 4);
+            dict.set("R", 4);
+        } else {
+            dict.set("V", 2);
+            dict.set("R", 3);
+        }
+        dict
+    }
+
+    /// /Encrypt revisi 6 (AES-256) dengan kunci file 0x5A * 32
+    fn modern() -> Dictionary {
+        let mut filter = Dictionary::new();
+        filter.set("CFM", Object::Name(b"AESV3".to_vec()));
+        let mut dict = Dictionary::new();
+        dict.set("Filter", Object::Name(b"Standard".to_vec()));
+        dict.set("V", 5);
+        dict.set("R", 6);
+        dict.set("Length", 256);
+        dict.set("P", PERMISSIONS);
+        dict.set("CF", Dictionary::from_iter([("StdCF", Object::Dictionary(filter))]));
+        dict.set("StmF", Object::Name(b"StdCF".to_vec()));
+        dict.set("StrF", Object::Name(b"StdCF".to_vec()));
+        dict.set("U", string(hex("731758c09c8b0160a34721d18bdd24220abada0070aa3f05b8103fd5b8d05f17000102030405060708090a0b0c0d0e0f")));
+        dict.set("UE", string(hex("4003e74ae47590a4e88568eadbed7214ffcad29f0d081d009541fe5b76c3ce33")));
+        dict.set("O", string(hex("430fcaed602ced2ea5a8deaab9e323788ce324b8ae39b7d627f47fdc2c3f800d101112131415161718191a1b1c1d1e1f")));
+        dict.set("OE", string(hex("0745e15d1c68de6c1df15a4dd62ab8117074700098e94c155f575541eb14cd93")));
+        dict.set("Perms", string(hex("0ff8c9a58b9d71b4e714061eb345358d")));
+        dict
+    }
+
+    fn open(dict: &Dictionary, password: &str) -> Result<SecurityHandler> {
+        SecurityHandler::new(dict, FILE_ID, None, Some(password))
+    }
+
+    /// String `encrypted` (dibuat di luar pdfsign) didekripsi menjadi PLAIN
+    fn assert_decrypts(handler: &SecurityHandler, encrypted: &str) {
+        let mut object = string(hex(encrypted));
+        handler.transform(OBJECT, &mut object, Direction::Decrypt).unwrap();
+        assert_eq!(object.as_str().unwrap(), PLAIN);
+    }
+
+    /// Enkripsi update lalu dekripsi lagi: string dan stream kembali ke isi
+    /// semula, /Contents signature tidak pernah dienkripsi
+    fn assert_round_trip(handler: &SecurityHandler) {
+        let mut signature = Dictionary::new();
+        signature.set("Type", Object::Name(b"Sig".to_vec()));
+        signature.set("Contents", string(vec![0; 8]));
+        signature.set("Reason", string(PLAIN.to_vec()));
+        let mut update = Document::new();
+        update.objects.insert(OBJECT, Object::Dictionary(signature));
+        update.objects.insert((8, 0), Object::Stream(Stream::new(Dictionary::new(), PLAIN.repeat(3))));
+        let original = update.objects.clone();
+
+        handler.encrypt_update(&mut update).unwrap();
+        let signature = update.get_dictionary(OBJECT).unwrap();
+        assert_eq!(signature.get(b"Contents").unwrap(), original[&OBJECT].as_dict().unwrap().get(b"Contents").unwrap());
+        assert_ne!(signature.get(b"Reason").unwrap().as_str().unwrap(), PLAIN);
+        assert_ne!(update.get_object((8, 0)).unwrap().as_stream().unwrap().content, PLAIN.repeat(3));
+
+        for (&id, object) in update.objects.iter_mut() {
+            handler.transform(id, object, Direction::Decrypt).unwrap();
+        }
+        assert_eq!(update.objects, original);
+    }
+
+    #[test]
+    fn rc4_user_and_owner_password() {
+        let dict = legacy(false);
+        let user = open(&dict, "user").unwrap();
+        assert_eq!(user.key, hex("15dd44c8fb76c514905768312241eee5"));
+        assert!(!user.is_owner());
+        let owner = open(&dict, "owner").unwrap();
+        assert_eq!(owner.key, user.key);
+        assert!(owner.is_owner());
+        assert!(open(&dict, "wrong").is_err());
+        assert_decrypts(&user, "65e7451602e3403c6514cc27d5ed5c333f");
+        assert_round_trip(&user);
+    }
+
+    #[test]
+    fn aes128_crypt_filter() {
+        let handler = open(&legacy(true), "user").unwrap();
+        assert_eq!(handler.strings, Cipher::Aes128);
+        assert_decrypts(&handler, "000102030405060708090a0b0c0d0e0f561fb70bb04572f0db341176f766071fc73782342044ea4c63ac51db02aa1500");
+        assert_round_trip(&handler);
+    }
+
+    #[test]
+    fn aes256_user_and_owner_password() {
+        let dict = modern();
+        let user = open(&dict, "user").unwrap();
+        assert_eq!(user.key, vec![0x5A; 32]);
+        assert_eq!(open(&dict, "owner").unwrap().key, user.key);
+        assert!(open(&dict, "wrong").is_err());
+        assert_decrypts(&user, "000102030405060708090a0b0c0d0e0f84c81cfda41b5c40346aec5ee3ff483f685c27c705c54ba72bd906e52e640f7b");
+        assert_round_trip(&user);
+    }
+}
@@ -0,0 +1,225 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use std::fs; // Untuk membaca file PDF
+use std::io::{Read, Seek, SeekFrom, Write}; // Membaca dan menulis buffer sementara
+use sha2::{Digest, Sha256}; // SHA-256 hashing
+use x509_cert::der::Decode; // Parsing DER
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned}; // Identifier algoritma dan kunci publik
+use x509_cert::Certificate; // Sertifikat penandatangan
+
+use crate::cancel::CancellationToken; // Pembatalan dan timeout
+use crate::crypto::cert; // Kredensial uji (generate-cert)
+use crate::crypto::cms::{build_signed_data, parse_signed_data, signed_attributes_der, SignedAttributes}; // CMS SignedData
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::ecc; // Normalisasi signature ECDSA
+use crate::crypto::policy::AlgorithmPolicy; // Deny-list algoritma lemah
+use crate::crypto::rsa::RsaPadding; // Padding signature RSA
+use crate::crypto::signer::{signature_algorithm_for, PublicKey, Signer}; // Verifikasi dan trait signer
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::naming; // Waktu signing (mode deterministik)
+use crate::pdf::options::SignatureOptions; // Opsi penandatanganan
+use crate::pdf::pades::PadesLevel; // Level PAdES baseline
+use crate::pdf::sign::{find_placeholder, hash_byte_range, load_document, prepare_scratch, write_contents, Placeholder}; // Pipeline penandatanganan
+use crate::pdf::syntax::ParseMode; // Struktur file dibaca dalam mode lenient
+use crate::scratch::ScratchSpace; // Penyimpanan data sementara
+
+/// Ruang /Contents default untuk signature eksternal (dalam bytes, sebelum hex);
+/// CMS dari layanan signing sering berisi rantai lengkap dan timestamp
+pub const EXTERNAL_SIGNATURE_RESERVE: usize = 16384;
+
+/// Yang harus ditandatangani di luar pdfsign (HSM, API signing jarak jauh)
+pub struct SigningInput {
+    /// Bytes persis yang ditandatangani: signed attributes (DER) jika ada
+    /// sertifikat, selain itu segmen-segmen ByteRange dokumen
+    pub data: Vec<u8>,
+    /// SHA-256 dari `data`
+    pub digest: Vec<u8>,
+    /// true: layanan eksternal cukup mengembalikan signature raw;
+    /// false: layanan eksternal harus membuat CMS SignedData (detached) sendiri
+    pub raw: bool,
+}
+
+/// Bentuk signature yang disisipkan oleh `embed_signature`
+pub enum EmbeddedSignature {
+    /// CMS SignedData dari layanan eksternal, disisipkan apa adanya
+    Cms,
+    /// Signature raw yang dibungkus pdfsign menjadi CMS SignedData
+    Raw,
+}
+
+/// Tahap 1: siapkan signature field dengan /Contents kosong dan hitung apa
+/// yang harus ditandatangani secara eksternal
+///
+/// Dengan sertifikat, signed attributes CAdES (contentType, messageDigest,
+/// signing-certificate-v2) dibangun sekarang; tanpa signingTime sehingga
+/// `embed_signature` bisa membangunnya ulang persis sama. Waktu klaim
+/// penandatanganan ada di /M.
+///
+/// Parameter:
+///   - input: path file PDF asli
+///   - output: path file PDF yang sudah disiapkan
+///   - certificates: sertifikat DER (penandatangan dulu, lalu rantainya); boleh kosong
+///   - options: metadata dan posisi signature
+///   - reserve: ruang /Contents dalam bytes (sebelum hex)
+pub fn prepare_pdf(
+    input: &str,
+    output: &str,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    reserve: usize,
+) -> Result<SigningInput> {
+    // Timestamp dan data LTV ditambahkan setelah signature ada, jadi tidak
+    // bisa dilakukan di tahap prepare
+    if options.timestamp.is_some() || options.pades > Some(PadesLevel::BB) {
+        bail!("two-phase signing supports neither timestamps nor PAdES levels above B-B");
+    }
+    // `embed` tidak tahu digest yang dipilih saat prepare, jadi selalu SHA-256
+    if options.digest != DigestAlgorithm::Sha256 {
+        bail!("two-phase signing only supports SHA-256 digests");
+    }
+    if let Some(leaf) = certificates.first() {
+        let cert = Certificate::from_der(leaf).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+        options
+            .algorithm_policy
+            .check_signing(&cert.tbs_certificate.subject_public_key_info, certificates)?;
+    }
+    cert::check_test_credentials(certificates, options.allow_test_credentials)?;
+
+    let _lock = FileLock::exclusive(output)?;
+    let scratch = options.scratch.create_over(fs::File::open(input)?)?;
+    let mut prepared = prepare_scratch(scratch, certificates, options, reserve, &CancellationToken::new())?;
+
+    let data = if certificates.is_empty() {
+        // Tanpa sertifikat: layanan eksternal menandatangani dokumen itu sendiri
+        let mut data = Vec::new();
+        for pair in prepared.byte_range.chunks(2) {
+            prepared.scratch.seek(SeekFrom::Start(pair[0]))?;
+            (&mut prepared.scratch).take(pair[1]).read_to_end(&mut data)?;
+        }
+        data
+    } else {
+        signed_attributes_der(
+            &prepared.digest,
+            certificates,
+            prepared.signing_time.with_timezone(&chrono::Utc),
+            SignedAttributes::Cades,
+        )?
+    };
+    let digest = Sha256::digest(&data).to_vec();
+
+    prepared.scratch.flush()?;
+    prepared.scratch.persist_to(output)?;
+    Ok(SigningInput { data, digest, raw: !certificates.is_empty() })
+}
+
+/// Tahap 2: sisipkan signature eksternal ke /Contents yang sudah dicadangkan
+///
+/// Signature berupa CMS SignedData disisipkan apa adanya setelah atribut
+/// messageDigest-nya dicocokkan dengan digest dokumen. Signature raw
+/// (ECDSA DER atau r || s, RSA) diverifikasi terhadap sertifikat, lalu
+/// dibungkus menjadi CMS SignedData.
+///
+/// Parameter:
+///   - input: path PDF hasil `prepare_pdf`
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - signature: CMS SignedData (DER) atau signature raw
+///   - certificates: sertifikat DER yang sama dengan saat prepare (wajib untuk signature raw)
+///   - rsa_padding: padding yang dipakai layanan eksternal jika kuncinya RSA
+///   - policy: kebijakan algoritma lemah
+pub fn embed_signature(
+    input: &str,
+    output: &str,
+    signature: &[u8],
+    certificates: &[Vec<u8>],
+    rsa_padding: RsaPadding,
+    policy: &AlgorithmPolicy,
+) -> Result<EmbeddedSignature> {
+    // Output default-nya menimpa input; embed lain pada file yang sama menunggu
+    let _lock = FileLock::exclusive(output)?;
+    let mut scratch = ScratchSpace::default().create_over(fs::File::open(input)?)?;
+    let doc = load_document(&mut scratch, ParseMode::Lenient)?;
+    let Placeholder { contents_pos, contents_size, byte_range, current, .. } = find_placeholder(&mut scratch, &doc)?
+        .ok_or_else(|| anyhow!("no empty signature placeholder found (prepare the document with `pdfsign prepare`)"))?;
+    if !current {
+        bail!("the signature placeholder does not match the file (was the PDF changed after prepare?)");
+    }
+    scratch.detach_base()?;
+    let digest = hash_byte_range(&mut scratch, &byte_range, DigestAlgorithm::Sha256, &CancellationToken::new())?;
+
+    let (content, kind) = match parse_signed_data(signature) {
+        Ok(parsed) => {
+            // CMS dari layanan eksternal: pastikan dibuat untuk dokumen ini
+            if parsed.message_digest.as_deref() != Some(digest.as_slice()) {
+                bail!("the CMS signature was made for a different document digest (was the PDF changed after prepare?)");
+            }
+            if let Some(leaf) = &parsed.signer_certificate {
+                let cert = Certificate::from_der(leaf).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+                policy.check_signing(&cert.tbs_certificate.subject_public_key_info, &parsed.certificates)?;
+            }
+            (signature.to_vec(), EmbeddedSignature::Cms)
+        }
+        Err(_) => {
+            let leaf = certificates
+                .first()
+                .ok_or_else(|| anyhow!("the signature is not a CMS SignedData; a raw signature needs the signer certificate (--certificate)"))?;
+            let cert = Certificate::from_der(leaf).map_err(|e| anyhow!("invalid certificate: {}", e))?;
+            let spki = cert.tbs_certificate.subject_public_key_info;
+            policy.check_signing(&spki, certificates)?;
+
+            let external = ExternalSigner::new(signature, spki, rsa_padding)?;
+            // Signed attributes dibangun ulang persis seperti saat prepare
+            let attributes = signed_attributes_der(&digest, certificates, naming::now().with_timezone(&chrono::Utc), SignedAttributes::Cades)?;
+            let public_key = PublicKey::from_spki(&external.spki)?;
+            if !public_key.verify_message(&external.algorithm, DigestAlgorithm::Sha256, &attributes, &external.signature)? {
+                bail!("the raw signature does not match the prepared digest and the certificate's key");
+            }
+            let cms = build_signed_data(&digest, DigestAlgorithm::Sha256, &external, certificates, naming::now().with_timezone(&chrono::Utc), SignedAttributes::Cades)?;
+            (cms, EmbeddedSignature::Raw)
+        }
+    };
+
+    write_contents(&mut scratch, contents_pos, contents_size, &content)?;
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+    Ok(kind)
+}
+
+/// Signer yang "menandatangani" dengan signature yang sudah dibuat di luar
+/// pdfsign; dipakai untuk membungkus signature raw menjadi CMS SignedData
+struct ExternalSigner {
+    signature: Vec<u8>,                  // Signature raw (DER untuk ECDSA)
+    spki: SubjectPublicKeyInfoOwned,     // Kunci publik dari sertifikat penandatangan
+    algorithm: AlgorithmIdentifierOwned, // Algoritma signature sesuai jenis kunci
+}
+
+impl ExternalSigner {
+    fn new(signature: &[u8], spki: SubjectPublicKeyInfoOwned, rsa_padding: RsaPadding) -> Result<ExternalSigner> {
+        let algorithm = signature_algorithm_for(&spki, rsa_padding, DigestAlgorithm::Sha256)?;
+        let signature = match PublicKey::from_spki(&spki)? {
+            PublicKey::Ecdsa(curve, _) => ecc::signature_to_der(curve, signature),
+            PublicKey::Ed25519(_) => bail!("two-phase signing does not support Ed25519 keys (they sign the message, not a digest)"),
+            PublicKey::Rsa(_) => signature.to_vec(),
+        };
+        Ok(ExternalSigner { signature, spki, algorithm })
+    }
+}
+
+impl Signer for ExternalSigner {
+    fn sign_digest(&self, _digest: &[u8], _algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+        // Sudah diverifikasi terhadap digest signed attributes oleh embed_signature
+        Ok(self.signature.clone())
+    }
+
+    fn signature_algorithm(&self, _digest: DigestAlgorithm) -> Result<AlgorithmIdentifierOwned> {
+        // Signing dua tahap selalu memakai SHA-256
+        Ok(self.algorithm.clone())
+    }
+
+    fn public_key_info(&self) -> Result<SubjectPublicKeyInfoOwned> {
+        Ok(self.spki.clone())
+    }
+
+    fn description(&self) -> String {
+        "external signature".to_string()
+    }
+}
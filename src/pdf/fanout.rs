@@ -0,0 +1,153 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::Document; // Dokumen yang sudah diurai
+use std::fs::{self, File}; // Membaca input dan membuat direktori output
+use std::io::{Seek, SeekFrom}; // Kembali ke awal buffer sebelum disimpan
+use std::path::{Path, PathBuf}; // Path input dan output
+use std::sync::Mutex; // Mengumpulkan hasil dari thread
+use std::time::{Duration, Instant}; // Batas waktu dan lama per identitas
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::signer::Signer; // Kunci per identitas
+use crate::lock::FileLock; // Lock antar proses pada setiap output
+use crate::pdf::hooks::NoHooks; // Fan-out tidak memakai hook
+use crate::pdf::options::SignatureOptions; // Opsi per identitas
+use crate::pdf::sign::{load_credentials, read_document, sign_parsed, STDIO_PATH}; // Pipeline penandatanganan
+use crate::pdf::syntax::{self, ParseMode}; // Uraian struktur file (sekali untuk semua identitas)
+use crate::scratch::ScratchSpace; // Buffer sementara untuk uraian bersama
+use crate::storage::{DocumentSink, FileSink}; // Menyimpan hasil ke file output
+
+/// Satu identitas (misalnya satu profil departemen) dalam fan-out
+pub struct FanoutItem {
+    pub identity: String,          // Nama identitas, dipakai di nama file output
+    pub key_path: PathBuf,         // Path file kunci privat (diabaikan untuk PKCS#12/PKCS#11/KMS)
+    pub options: SignatureOptions, // Metadata, tampilan, dan kebijakan signature identitas ini
+    pub timeout: Option<Duration>, // Batas waktu signing identitas ini
+    pub output: PathBuf,           // PDF hasil
+}
+
+/// Hasil penandatanganan untuk satu identitas
+pub struct FanoutOutcome {
+    pub identity: String,   // Nama identitas
+    pub output: PathBuf,    // PDF hasil
+    pub result: Result<()>, // Ok, atau penyebab gagal
+    pub elapsed: Duration,  // Lama proses identitas ini
+}
+
+/// Path output identitas: "<nama input>-<identitas>.pdf" di `output_dir`
+///
+/// Parameter:
+///   - input: PDF yang ditandatangani
+///   - output_dir: direktori tujuan
+///   - identity: nama identitas (misalnya nama profil)
+pub fn fanout_output(input: &Path, output_dir: &Path, identity: &str) -> PathBuf {
+    let stem = input.file_stem().map_or_else(|| "document".into(), |stem| stem.to_string_lossy());
+    output_dir.join(format!("{}-{}.pdf", stem, identity))
+}
+
+/// Tandatangani satu dokumen dengan beberapa identitas, satu output per identitas
+///
+/// Dokumen diurai sekali; setiap identitas menambahkan signature-nya sendiri
+/// di atas dokumen asli (bukan di atas signature identitas lain), secara
+/// paralel. Kunci dan sertifikat semua identitas dimuat lebih dulu, satu per
+/// satu, sehingga passphrase ditanyakan berurutan dan tidak ada output yang
+/// ditulis jika salah satu kunci gagal dimuat. Identitas yang gagal saat
+/// signing tidak menghentikan identitas lain.
+///
+/// Parameter:
+///   - input: path PDF asli (bukan stdin)
+///   - items: identitas beserta output-nya
+///   - cancel: token pembatalan seluruh fan-out
+///   - on_done: dipanggil dari thread setiap satu identitas selesai
+///
+/// Return: hasil per identitas, urut seperti `items`
+pub fn sign_fanout(
+    input: &str,
+    items: Vec<FanoutItem>,
+    cancel: &CancellationToken,
+    on_done: &(dyn Fn(&FanoutOutcome) + Sync),
+) -> Result<Vec<FanoutOutcome>> {
+    if input == STDIO_PATH {
+        bail!("sign-fanout cannot read a document from stdin");
+    }
+    if items.is_empty() {
+        bail!("no identities to sign with");
+    }
+    // Dua identitas tidak boleh menulis file yang sama, dan input tidak boleh ditimpa
+    let mut outputs: Vec<&Path> = items.iter().map(|item| item.output.as_path()).collect();
+    outputs.sort();
+    if let Some(pair) = outputs.windows(2).find(|pair| pair[0] == pair[1]) {
+        bail!("two identities would both be written to {}", pair[0].display());
+    }
+    if let Some(output) = outputs.iter().find(|output| same_file(output, Path::new(input))) {
+        bail!("{} would overwrite the input document", output.display());
+    }
+
+    // Uraian bersama (mode lenient); identitas dengan --strict-parse menolak
+    // dokumen jika ada pelanggaran
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let mut scratch = ScratchSpace::default().create_over(file)?;
+    let (doc, issues) = read_document(&mut scratch, ParseMode::Lenient)?;
+    if items.iter().any(|item| item.options.parse_mode() == ParseMode::Lenient) {
+        syntax::warn(&issues);
+    }
+
+    let credentials = items
+        .iter()
+        .map(|item| load_credentials(&item.key_path, &item.options).with_context(|| format!("identity {}", item.identity)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let outcomes = Mutex::new(Vec::with_capacity(items.len()));
+    std::thread::scope(|scope| {
+        for (index, (item, (signer, certificates))) in items.iter().zip(&credentials).enumerate() {
+            let (doc, issues, outcomes) = (&doc, &issues, &outcomes);
+            scope.spawn(move || {
+                let started = Instant::now();
+                let cancel = item.timeout.map_or_else(|| cancel.clone(), |timeout| cancel.child(timeout));
+                let result = cancel.check().and_then(|_| {
+                    if item.options.parse_mode() == ParseMode::Strict {
+                        syntax::reject(issues)?;
+                    }
+                    sign_item(input, doc, item, signer.as_ref(), certificates, &cancel)
+                });
+                let outcome = FanoutOutcome { identity: item.identity.clone(), output: item.output.clone(), result, elapsed: started.elapsed() };
+                on_done(&outcome);
+                outcomes.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push((index, outcome));
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    outcomes.sort_by_key(|(index, _)| *index);
+    Ok(outcomes.into_iter().map(|(_, outcome)| outcome).collect())
+}
+
+/// Tandatangani dokumen yang sudah diurai untuk satu identitas
+fn sign_item(
+    input: &str,
+    doc: &Document,
+    item: &FanoutItem,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if let Some(parent) = item.output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("cannot create directory {}", parent.display()))?;
+    }
+    let _lock = FileLock::exclusive(&item.output)?;
+    // Setiap identitas membaca file asli langsung dari disk; hanya update-nya
+    // yang disimpan di buffer sementara masing-masing
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let scratch = item.options.scratch.create_over(file)?;
+    let mut scratch = sign_parsed(scratch, Some(doc), signer, certificates, &item.options, &mut NoHooks, cancel)?;
+    scratch.seek(SeekFrom::Start(0))?;
+    FileSink::new(&item.output).store(&mut scratch, cancel)
+}
+
+/// Apakah dua path menunjuk file yang sama (file yang belum ada tidak sama)
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,126 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Object, Stream}; // Struktur dasar PDF
+use std::fs::File; // Untuk membaca file input
+use std::io::Write; // Menulis hasil ke buffer sementara
+
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::options::{AnnotationFlags, Placement, Rect}; // Posisi dan flag widget
+use crate::pdf::seed::SeedValue; // Seed value dictionary (/SV)
+use crate::pdf::syntax::ParseMode; // Struktur file dibaca dalam mode lenient
+use crate::pdf::{form, mdp, pages, sign, text}; // Catalog, DocMDP, page tree, incremental update, text string
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+
+/// Signature field kosong yang ditambahkan oleh `prepare-field`
+#[derive(Clone, Debug, Default)]
+pub struct NewSignatureField {
+    pub name: Option<String>,          // Nama field (/T); None = SignatureN berikutnya yang belum dipakai
+    pub placement: Option<Placement>,  // None = field tak terlihat di halaman 1
+    pub seed_value: Option<SeedValue>, // Batasan untuk penandatangan field ini
+}
+
+/// Hasil `prepare_field`
+pub struct PreparedField {
+    pub name: String, // Nama lengkap field baru
+    pub page: u32,    // Halaman widget (mulai dari 1)
+    pub rect: Rect,   // Posisi widget (berukuran nol jika tak terlihat)
+}
+
+/// Tambahkan signature field kosong, opsional dengan seed value dictionary
+/// (/SV), agar penandatangan berikutnya mengisinya (`sign --field-name` atau
+/// viewer lain)
+///
+/// Field ditambahkan lewat incremental update, jadi signature yang sudah ada
+/// tetap valid; dokumen yang disertifikasi tanpa izin perubahan (DocMDP P=1)
+/// ditolak. Viewer yang patuh menegakkan batasan /SV yang ditandai wajib saat
+/// field ditandatangani.
+///
+/// Parameter:
+///   - input: path file PDF
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - field: nama, posisi, dan seed value field baru
+///   - object_streams: simpan objek update di object stream
+///
+/// Return: nama lengkap, halaman, dan posisi field baru
+pub fn prepare_field(input: &str, output: &str, field: &NewSignatureField, object_streams: bool) -> Result<PreparedField> {
+    if let Some(seed_value) = &field.seed_value {
+        seed_value.validate()?;
+    }
+    let _lock = FileLock::exclusive(output)?;
+    // Bytes asli dibaca langsung dari file; hanya update yang ditulis ke buffer sementara
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let mut scratch = ScratchSpace::default().create_over(file)?;
+    let original = sign::load_document(&mut scratch, ParseMode::Lenient)?;
+    // Field baru tanpa enkripsi di dokumen terenkripsi akan dibaca sebagai data rusak
+    if original.trailer.has(b"Encrypt") {
+        bail!("prepare-field does not support encrypted documents");
+    }
+    let root_id = form::catalog_id(&original)?;
+    if mdp::certification_permissions(&original, root_id) == Some(1) {
+        bail!("the document is certified with no changes allowed; a new signature field would invalidate the certification");
+    }
+
+    let mut doc = original.clone();
+    let acroform = form::load_acroform(&doc, root_id, false)?;
+    let name = match &field.name {
+        Some(name) if name.is_empty() || name.contains('.') => bail!("invalid field name `{}` (must be non-empty and without `.`)", name),
+        Some(name) if form::terminal_fields(&doc).iter().any(|(existing, _, _)| existing == name) => bail!("the document already has a field named {}", name),
+        Some(name) => name.clone(),
+        None => form::unused_field_name(&doc, &acroform, "Signature"),
+    };
+
+    // Halaman dan posisi widget, seperti pada `sign`
+    pages::check_page_tree(&doc)?;
+    let page_number = field.placement.map_or(1, |placement| placement.page());
+    let page_count = doc.get_pages().len();
+    let page_id = *doc
+        .get_pages()
+        .get(&page_number)
+        .ok_or_else(|| anyhow!("page {} does not exist (document has {} pages)", page_number, page_count))?;
+    let rect = match field.placement {
+        Some(placement) => sign::placement_rect(&doc, page_id, placement)?,
+        None => Rect::new(0.0, 0.0, 0.0, 0.0),
+    };
+
+    // Tampilan kosong seukuran widget; viewer menggambar kotak field-nya sendiri
+    let mut appearance = Dictionary::new();
+    appearance.set("Type", Object::Name(b"XObject".to_vec()));
+    appearance.set("Subtype", Object::Name(b"Form".to_vec()));
+    appearance.set("BBox", Object::Array(vec![0.into(), 0.into(), Object::Real(rect.width()), Object::Real(rect.height())]));
+    let appearance_id = doc.add_object(Stream::new(appearance, Vec::new()));
+    let mut appearance_dict = Dictionary::new();
+    appearance_dict.set("N", Object::Reference(appearance_id));
+
+    // Field sekaligus widget, tanpa /V; /SV harus berupa referensi tidak langsung
+    let mut field_dict = Dictionary::new();
+    field_dict.set("Type", Object::Name(b"Annot".to_vec()));
+    field_dict.set("Subtype", Object::Name(b"Widget".to_vec()));
+    field_dict.set("FT", Object::Name(b"Sig".to_vec()));
+    field_dict.set("T", text::text_string(&name));
+    field_dict.set("TU", text::text_string(&format!("Signature field {}", name)));
+    field_dict.set("F", Object::Integer(AnnotationFlags::WIDGET.bits()));
+    field_dict.set("Rect", Object::Array(vec![
+        Object::Real(rect.left),
+        Object::Real(rect.bottom),
+        Object::Real(rect.right),
+        Object::Real(rect.top),
+    ]));
+    field_dict.set("P", Object::Reference(page_id));
+    field_dict.set("AP", Object::Dictionary(appearance_dict));
+    if let Some(seed_value) = &field.seed_value {
+        let sv_id = doc.add_object(seed_value.dictionary());
+        field_dict.set("SV", Object::Reference(sv_id));
+    }
+    let field_id = doc.add_object(field_dict);
+
+    // Daftarkan field di AcroForm dan tambahkan widget ke /Annots halaman
+    form::register_field(&mut doc, root_id, acroform, field_id)?;
+    form::add_annotation(&mut doc, page_id, field_id)?;
+    sign::write_update(&mut scratch, &original, &doc, object_streams, None)?;
+
+    // Tulis ke buffer sementara dulu, lalu salin ke output secara atomik
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+
+    Ok(PreparedField { name, page: page_number, rect })
+}
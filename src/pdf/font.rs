@@ -0,0 +1,285 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream}; // Struktur PDF
+use std::fs; // Membaca file font
+
+use crate::pdf::text::{win_ansi_byte, win_ansi_char}; // Karakter yang bisa di-encode di font sederhana
+
+/// Font TrueType yang disematkan di tampilan signature, sudah dikompilasi
+/// menjadi font dictionary sederhana (WinAnsiEncoding) yang siap disalin ke
+/// dokumen
+///
+/// Seluruh file font disematkan sebagai /FontFile2 (tanpa subset); lebar
+/// karakter diambil dari tabel hmtx sehingga sama dengan program fontnya,
+/// seperti yang disyaratkan PDF/A. Kompresi dilakukan sekali saat font
+/// dibaca, sehingga satu font bisa dipakai untuk banyak dokumen.
+#[derive(Clone, Debug)]
+pub struct EmbeddedFont {
+    pub name: String,       // Nama PostScript font (/BaseFont)
+    font: Dictionary,       // Font dictionary (tanpa /FontDescriptor)
+    descriptor: Dictionary, // Font descriptor (tanpa /FontFile2)
+    file: Stream,           // Program font (terkompresi)
+    covered: Vec<bool>,     // Byte WinAnsi 0-255 yang glyph-nya ada di font
+}
+
+/// Isi tabel-tabel TrueType yang dipakai untuk membuat font dictionary
+struct TrueType<'a> {
+    bytes: &'a [u8],                      // Seluruh file font
+    tables: Vec<([u8; 4], usize, usize)>, // Tag, offset, dan panjang setiap tabel
+}
+
+impl EmbeddedFont {
+    /// Baca font TrueType (.ttf) dari file
+    pub fn load(path: &str) -> Result<EmbeddedFont> {
+        let bytes = fs::read(path).with_context(|| format!("cannot read appearance font {}", path))?;
+        EmbeddedFont::from_bytes(&bytes).with_context(|| format!("cannot use appearance font {}", path))
+    }
+
+    /// Urai font TrueType dan siapkan font dictionary, descriptor, dan stream-nya
+    pub fn from_bytes(bytes: &[u8]) -> Result<EmbeddedFont> {
+        let font = TrueType::parse(bytes)?;
+
+        // fsType bit 1: lisensi font melarang penyematan
+        let os2 = font.table(b"OS/2");
+        if let Some(os2) = os2 {
+            if read_u16(os2, 8)? & 0x000F == 0x0002 {
+                bail!("the font license does not allow embedding (OS/2 fsType is restricted)");
+            }
+        }
+
+        let head = font.table(b"head").ok_or_else(|| anyhow!("missing head table"))?;
+        let units_per_em = f32::from(read_u16(head, 18)?.max(1));
+        let scale = |value: f32| (value * 1000.0 / units_per_em).round() as i64;
+        let bbox: Vec<Object> = [36, 38, 40, 42]
+            .iter()
+            .map(|&offset| read_i16(head, offset).map(|value| Object::Integer(scale(f32::from(value)))))
+            .collect::<Result<_>>()?;
+
+        let hhea = font.table(b"hhea").ok_or_else(|| anyhow!("missing hhea table"))?;
+        let ascent = read_i16(hhea, 4)?;
+        let descent = read_i16(hhea, 6)?;
+        let metrics = usize::from(read_u16(hhea, 34)?);
+        let hmtx = font.table(b"hmtx").ok_or_else(|| anyhow!("missing hmtx table"))?;
+        // Glyph setelah numberOfHMetrics memakai lebar entri terakhir
+        let advance = |glyph: u16| {
+            let index = usize::from(glyph).min(metrics.saturating_sub(1));
+            read_u16(hmtx, index * 4)
+        };
+
+        // Font non-symbolic dengan WinAnsiEncoding memetakan karakter lewat cmap (3,1)
+        let cmap = font.unicode_cmap()?;
+        let mut widths = Vec::with_capacity(224);
+        let mut covered = vec![false; 256];
+        for byte in 32..=255u8 {
+            let glyph = win_ansi_char(byte).and_then(|ch| cmap_glyph(cmap, ch));
+            covered[usize::from(byte)] = glyph.is_some();
+            let width = match glyph {
+                Some(glyph) => scale(f32::from(advance(glyph)?)),
+                None => 0,
+            };
+            widths.push(Object::Integer(width));
+        }
+
+        // post: sudut miring dan lebar tetap; OS/2: tinggi huruf kapital
+        let (italic_angle, fixed_pitch) = match font.table(b"post") {
+            Some(post) => (read_i32(post, 4)? as f32 / 65536.0, read_u32(post, 12)? != 0),
+            None => (0.0, false),
+        };
+        let cap_height = match os2 {
+            Some(os2) if read_u16(os2, 0)? >= 2 => read_i16(os2, 88)?,
+            _ => ascent,
+        };
+        let name = font.postscript_name().unwrap_or_else(|| "EmbeddedFont".to_string());
+
+        // Flags: Nonsymbolic (6), ditambah FixedPitch (1) dan Italic (7)
+        let mut flags = 32;
+        if fixed_pitch {
+            flags |= 1;
+        }
+        if italic_angle != 0.0 {
+            flags |= 64;
+        }
+        let mut descriptor = Dictionary::new();
+        descriptor.set("Type", Object::Name(b"FontDescriptor".to_vec()));
+        descriptor.set("FontName", Object::Name(name.as_bytes().to_vec()));
+        descriptor.set("Flags", Object::Integer(flags));
+        descriptor.set("FontBBox", Object::Array(bbox));
+        descriptor.set("ItalicAngle", Object::Real(italic_angle));
+        descriptor.set("Ascent", Object::Integer(scale(f32::from(ascent))));
+        descriptor.set("Descent", Object::Integer(scale(f32::from(descent))));
+        descriptor.set("CapHeight", Object::Integer(scale(f32::from(cap_height))));
+        // StemV wajib ada tapi tidak tersimpan di TrueType; nilai umum untuk teks biasa
+        descriptor.set("StemV", Object::Integer(80));
+
+        let mut dict = Dictionary::new();
+        dict.set("Type", Object::Name(b"Font".to_vec()));
+        dict.set("Subtype", Object::Name(b"TrueType".to_vec()));
+        dict.set("BaseFont", Object::Name(name.as_bytes().to_vec()));
+        dict.set("FirstChar", Object::Integer(32));
+        dict.set("LastChar", Object::Integer(255));
+        dict.set("Widths", Object::Array(widths));
+        dict.set("Encoding", Object::Name(b"WinAnsiEncoding".to_vec()));
+
+        // Length1 = panjang program font sebelum dikompres
+        let mut file_dict = Dictionary::new();
+        file_dict.set("Length1", Object::Integer(bytes.len() as i64));
+        let mut file = Stream::new(file_dict, bytes.to_vec());
+        file.compress()?;
+
+        Ok(EmbeddedFont { name, font: dict, descriptor, file, covered })
+    }
+
+    /// Apakah `ch` bisa digambar dengan font ini (ada di WinAnsi dan glyph-nya ada)
+    pub fn has_glyph(&self, ch: char) -> bool {
+        win_ansi_byte(ch).is_some_and(|byte| self.covered[usize::from(byte)])
+    }
+
+    /// Tambahkan font ke dokumen (font dictionary, descriptor, dan program font)
+    ///
+    /// Return: object ID font dictionary
+    pub fn add_to(&self, doc: &mut Document) -> ObjectId {
+        let file_id = doc.add_object(self.file.clone());
+        let mut descriptor = self.descriptor.clone();
+        descriptor.set("FontFile2", Object::Reference(file_id));
+        let descriptor_id = doc.add_object(descriptor);
+        let mut font = self.font.clone();
+        font.set("FontDescriptor", Object::Reference(descriptor_id));
+        doc.add_object(font)
+    }
+}
+
+impl<'a> TrueType<'a> {
+    /// Baca direktori tabel font TrueType
+    fn parse(bytes: &'a [u8]) -> Result<TrueType<'a>> {
+        match bytes.get(..4) {
+            Some([0, 1, 0, 0]) | Some(b"true") => {}
+            Some(b"OTTO") => bail!("OpenType fonts with CFF outlines are not supported; use a TrueType (.ttf) font"),
+            Some(b"ttcf") => bail!("font collections (.ttc) are not supported; use a single TrueType (.ttf) font"),
+            _ => bail!("not a TrueType font"),
+        }
+        let count = usize::from(read_u16(bytes, 4)?);
+        let mut tables = Vec::with_capacity(count);
+        for index in 0..count {
+            let record = 12 + index * 16;
+            let tag: [u8; 4] = bytes
+                .get(record..record + 4)
+                .and_then(|tag| tag.try_into().ok())
+                .ok_or_else(|| anyhow!("truncated table directory"))?;
+            let offset = read_u32(bytes, record + 8)? as usize;
+            let length = read_u32(bytes, record + 12)? as usize;
+            if offset.checked_add(length).is_none_or(|end| end > bytes.len()) {
+                bail!("table {} lies outside the file", String::from_utf8_lossy(&tag));
+            }
+            tables.push((tag, offset, length));
+        }
+        Ok(TrueType { bytes, tables })
+    }
+
+    /// Isi tabel dengan tag `tag`, jika ada
+    fn table(&self, tag: &[u8; 4]) -> Option<&'a [u8]> {
+        self.tables
+            .iter()
+            .find(|(found, _, _)| found == tag)
+            .map(|&(_, offset, length)| &self.bytes[offset..offset + length])
+    }
+
+    /// Subtabel cmap Microsoft Unicode BMP (platform 3, encoding 1) format 4
+    fn unicode_cmap(&self) -> Result<&'a [u8]> {
+        let cmap = self.table(b"cmap").ok_or_else(|| anyhow!("missing cmap table"))?;
+        for index in 0..usize::from(read_u16(cmap, 2)?) {
+            let record = 4 + index * 8;
+            if read_u16(cmap, record)? == 3 && read_u16(cmap, record + 2)? == 1 {
+                let subtable = cmap.get(read_u32(cmap, record + 4)? as usize..).ok_or_else(|| anyhow!("truncated cmap table"))?;
+                if read_u16(subtable, 0)? == 4 {
+                    return Ok(subtable);
+                }
+            }
+        }
+        bail!("the font has no Unicode (3,1) cmap; symbol fonts cannot be used for the appearance text")
+    }
+
+    /// Nama PostScript (name ID 6), hanya karakter yang boleh ada di nama PDF
+    fn postscript_name(&self) -> Option<String> {
+        let table = self.table(b"name")?;
+        let count = usize::from(read_u16(table, 2).ok()?);
+        let strings = usize::from(read_u16(table, 4).ok()?);
+        for index in 0..count {
+            let record = 6 + index * 12;
+            let platform = read_u16(table, record).ok()?;
+            if read_u16(table, record + 6).ok()? != 6 {
+                continue;
+            }
+            let length = usize::from(read_u16(table, record + 8).ok()?);
+            let offset = strings + usize::from(read_u16(table, record + 10).ok()?);
+            let raw = table.get(offset..offset + length)?;
+            // Platform 3 (Windows) menyimpan UTF-16BE, platform 1 (Mac) ASCII
+            let name: String = if platform == 3 {
+                let units: Vec<u16> = raw.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+                String::from_utf16_lossy(&units)
+            } else {
+                raw.iter().map(|&byte| char::from(byte)).collect()
+            };
+            let name: String = name.chars().filter(|ch| ch.is_ascii_graphic() && !"[](){}<>/%#".contains(*ch)).collect();
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+        None
+    }
+}
+
+/// Glyph untuk karakter `ch` di subtabel cmap format 4 (None = tidak ada / .notdef)
+fn cmap_glyph(cmap: &[u8], ch: char) -> Option<u16> {
+    let code = u16::try_from(ch as u32).ok()?;
+    let segments = usize::from(read_u16(cmap, 6).ok()? / 2);
+    let ends = 14;
+    let starts = ends + segments * 2 + 2;
+    let deltas = starts + segments * 2;
+    let range_offsets = deltas + segments * 2;
+    for index in 0..segments {
+        if read_u16(cmap, ends + index * 2).ok()? < code {
+            continue;
+        }
+        let start = read_u16(cmap, starts + index * 2).ok()?;
+        if start > code {
+            return None;
+        }
+        let delta = read_u16(cmap, deltas + index * 2).ok()?;
+        let range_offset = read_u16(cmap, range_offsets + index * 2).ok()?;
+        let glyph = if range_offset == 0 {
+            code.wrapping_add(delta)
+        } else {
+            // idRangeOffset relatif terhadap posisi entri itu sendiri
+            let position = range_offsets + index * 2 + usize::from(range_offset) + usize::from(code - start) * 2;
+            match read_u16(cmap, position).ok()? {
+                0 => 0,
+                glyph => glyph.wrapping_add(delta),
+            }
+        };
+        return (glyph != 0).then_some(glyph);
+    }
+    None
+}
+
+/// Baca integer big-endian dari tabel font
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| anyhow!("truncated font table"))
+}
+
+fn read_i16(bytes: &[u8], offset: usize) -> Result<i16> {
+    read_u16(bytes, offset).map(|value| value as i16)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| anyhow!("truncated font table"))
+}
+
+fn read_i32(bytes: &[u8], offset: usize) -> Result<i32> {
+    read_u32(bytes, offset).map(|value| value as i32)
+}
@@ -0,0 +1,961 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId}; // Struktur dasar PDF
+
+use crate::pdf::naming; // Nama field baru (berurutan atau dari seed)
+use crate::pdf::text::decode_text_string; // Nama field (/T) dan /NM sebagai text string
+
+/// Kebijakan untuk menangani flag /NeedAppearances pada AcroForm
+///
+/// Jika /NeedAppearances bernilai true, viewer (misalnya Adobe Reader) akan
+/// membuat ulang tampilan field saat dokumen dibuka. Akibatnya, apa yang
+/// terlihat oleh user bisa berbeda dengan apa yang sebenarnya ditandatangani.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum NeedAppearancesPolicy {
+    /// Biarkan flag apa adanya, tapi tampilkan peringatan
+    Warn,
+    /// Hapus flag, hanya jika semua field sudah memiliki appearance
+    Clear,
+    /// Buat appearance untuk field yang belum punya, lalu hapus flag
+    Generate,
+}
+
+/// Informasi satu widget field yang ditemukan saat menelusuri AcroForm
+struct WidgetInfo {
+    id: ObjectId,                      // Object ID dari widget annotation
+    name: String,                      // Nama lengkap field
+    field_type: Vec<u8>,               // Tipe field (Tx, Btn, Ch, Sig), bisa diwarisi dari parent
+    value: Option<Vec<u8>>,            // Nilai field (/V) berupa string, bisa diwarisi dari parent
+    state: Option<Vec<u8>>,            // Nilai field (/V) berupa name (checkbox/radio)
+    da: Option<Vec<u8>>,               // Default appearance string (/DA)
+    flags: i64,                        // Field flags (/Ff)
+    has_appearance: bool,              // Apakah widget sudah punya /AP /N
+    appearance_state: Option<Vec<u8>>, // State appearance yang dipilih (/AS)
+}
+
+/// Atribut field yang diwariskan dari parent ke child saat menelusuri /Kids
+#[derive(Clone, Default)]
+struct Inherited {
+    name: String,                // Nama lengkap parent
+    field_type: Option<Vec<u8>>, // /FT
+    value: Option<Vec<u8>>,      // /V (string)
+    state: Option<Vec<u8>>,      // /V (name)
+    da: Option<Vec<u8>>,         // /DA
+    flags: i64,                  // /Ff
+}
+
+// Field flags (/Ff) yang relevan
+const FF_MULTILINE: i64 = 1 << 12; // Text field multi-baris
+const FF_PUSHBUTTON: i64 = 1 << 16; // Tombol biasa (bukan checkbox/radio)
+const FF_COMB: i64 = 1 << 24; // Text field dengan kotak per karakter
+
+/// Object ID catalog dari /Root pada trailer (catalog tidak selalu object 1)
+pub fn catalog_id(doc: &Document) -> Result<ObjectId> {
+    doc.trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|_| anyhow!("document has no /Root in its trailer"))
+}
+
+/// Daftarkan field baru di /Fields AcroForm, lalu tulis AcroForm ke catalog
+///
+/// /Fields yang berupa indirect array ditambah di tempatnya. AcroForm yang
+/// sudah berupa indirect object ditimpa di object yang sama; selain itu
+/// AcroForm ditulis sebagai object baru yang direferensikan catalog.
+///
+/// Parameter:
+///   - doc: dokumen yang sedang diubah
+///   - root_id: object ID catalog
+///   - acroform: AcroForm yang sudah disesuaikan (lihat `existing_acroform`)
+///   - field_id: object ID field baru
+pub fn register_field(doc: &mut Document, root_id: ObjectId, mut acroform: Dictionary, field_id: ObjectId) -> Result<()> {
+    match acroform.get(b"Fields").ok().cloned() {
+        Some(Object::Reference(fields_id)) if matches!(doc.get_object(fields_id), Ok(Object::Array(_))) => {
+            if let Ok(Object::Array(ref mut fields)) = doc.get_object_mut(fields_id) {
+                fields.push(Object::Reference(field_id));
+            }
+        }
+        fields => {
+            let mut fields = match fields {
+                Some(Object::Array(fields)) => fields,
+                _ => Vec::new(),
+            };
+            fields.push(Object::Reference(field_id));
+            acroform.set("Fields", Object::Array(fields));
+        }
+    }
+    store_acroform(doc, root_id, acroform)
+}
+
+/// Tulis AcroForm ke catalog: AcroForm yang sudah berupa indirect object ditimpa
+/// di object yang sama (stream yang salah tipe diganti dictionary), selain itu
+/// ditulis sebagai object baru
+pub fn store_acroform(doc: &mut Document, root_id: ObjectId, acroform: Dictionary) -> Result<()> {
+    let existing = doc.get_dictionary(root_id)?.get(b"AcroForm").ok().cloned();
+    match existing {
+        Some(Object::Reference(acroform_id)) if matches!(doc.get_object(acroform_id), Ok(Object::Dictionary(_) | Object::Stream(_))) => {
+            doc.objects.insert(acroform_id, Object::Dictionary(acroform));
+        }
+        _ => {
+            let acroform_id = doc.add_object(acroform);
+            if let Ok(Object::Dictionary(ref mut catalog)) = doc.get_object_mut(root_id) {
+                catalog.set("AcroForm", Object::Reference(acroform_id));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Tambahkan annotation ke /Annots halaman; /Annots boleh berupa array
+/// langsung atau indirect reference ke array (annotation lama dipertahankan)
+///
+/// Annotation disisipkan sesuai urutan baca (baris dari atas ke bawah, lalu
+/// kiri ke kanan) di antara annotation yang sudah ada, sehingga navigasi
+/// keyboard di viewer yang mengikuti urutan /Annots mencapainya di tempat
+/// yang wajar. Annotation tanpa ukuran (invisible signature) ditaruh di akhir.
+pub fn add_annotation(doc: &mut Document, page_id: ObjectId, annotation_id: ObjectId) -> Result<()> {
+    let annots = doc.get_dictionary(page_id)?.get(b"Annots").ok().cloned();
+    let position = |doc: &Document, annots: &[Object]| -> usize {
+        let Some(rect) = annotation_rect(doc, annotation_id).filter(|r| r[2] > r[0] && r[3] > r[1]) else {
+            return annots.len();
+        };
+        annots
+            .iter()
+            .position(|annot| {
+                let other = annot.as_reference().ok().and_then(|id| annotation_rect(doc, id));
+                other.is_some_and(|other| other[2] > other[0] && other[3] > other[1] && reads_before(&rect, &other))
+            })
+            .unwrap_or(annots.len())
+    };
+    match annots {
+        Some(Object::Reference(annots_id)) if matches!(doc.get_object(annots_id), Ok(Object::Array(_))) => {
+            let index = match doc.get_object(annots_id) {
+                Ok(Object::Array(annots)) => position(doc, annots),
+                _ => 0,
+            };
+            if let Ok(Object::Array(ref mut annots)) = doc.get_object_mut(annots_id) {
+                annots.insert(index, Object::Reference(annotation_id));
+            }
+        }
+        other => {
+            let mut annots = match other {
+                Some(Object::Array(annots)) => annots,
+                _ => Vec::new(),
+            };
+            let index = position(doc, &annots);
+            annots.insert(index, Object::Reference(annotation_id));
+            if let Ok(Object::Dictionary(ref mut page)) = doc.get_object_mut(page_id) {
+                page.set("Annots", Object::Array(annots));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Hapus annotation dari /Annots halaman; /Annots boleh berupa array langsung
+/// atau indirect reference ke array (annotation lain dipertahankan)
+///
+/// Return: true jika halaman memuat salah satu annotation itu
+pub fn remove_annotations(doc: &mut Document, page_id: ObjectId, annotation_ids: &[ObjectId]) -> Result<bool> {
+    let keep = |annot: &Object| !annot.as_reference().is_ok_and(|id| annotation_ids.contains(&id));
+    match doc.get_dictionary(page_id)?.get(b"Annots").ok().cloned() {
+        Some(Object::Reference(annots_id)) => {
+            if let Ok(Object::Array(ref mut annots)) = doc.get_object_mut(annots_id) {
+                let before = annots.len();
+                annots.retain(keep);
+                return Ok(annots.len() < before);
+            }
+            Ok(false)
+        }
+        Some(Object::Array(mut annots)) => {
+            let before = annots.len();
+            annots.retain(keep);
+            let removed = annots.len() < before;
+            if let Ok(Object::Dictionary(ref mut page)) = doc.get_object_mut(page_id) {
+                page.set("Annots", Object::Array(annots));
+            }
+            Ok(removed)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Apakah annotation di `rect` dibaca sebelum annotation di `other`: baris yang
+/// lebih tinggi lebih dulu; annotation yang tingginya bertumpuk dianggap satu
+/// baris dan diurutkan dari kiri ke kanan
+fn reads_before(rect: &[f32; 4], other: &[f32; 4]) -> bool {
+    let same_row = rect[1] < other[3] && other[1] < rect[3];
+    if same_row {
+        rect[0] < other[0]
+    } else {
+        rect[3] > other[3]
+    }
+}
+
+/// /Rect annotation yang dinormalisasi: kiri, bawah, kanan, atas
+pub fn annotation_rect(doc: &Document, id: ObjectId) -> Option<[f32; 4]> {
+    doc.get_dictionary(id)
+        .ok()?
+        .get(b"Rect")
+        .and_then(|rect| doc.dereference(rect).map(|(_, rect)| rect))
+        .and_then(Object::as_array)
+        .ok()
+        .and_then(|rect| rect.iter().map(|v| v.as_float().ok()).collect::<Option<Vec<f32>>>())
+        .filter(|rect| rect.len() == 4)
+        .map(|r| [r[0].min(r[2]), r[1].min(r[3]), r[0].max(r[2]), r[1].max(r[3])])
+}
+
+/// Urutan tab yang dipasang di halaman yang belum punya /Tabs: /S (urutan
+/// structure, diwajibkan PDF/UA) untuk dokumen ber-tag, /R (urutan baris)
+/// untuk dokumen tanpa structure tree, karena /S di sana tidak berarti apa-apa
+pub fn default_tab_order(doc: &Document, root_id: ObjectId) -> &'static [u8] {
+    let tagged = doc.get_dictionary(root_id).is_ok_and(|catalog| catalog.has(b"StructTreeRoot"));
+    if tagged {
+        b"S"
+    } else {
+        b"R"
+    }
+}
+
+/// Ambil salinan dictionary AcroForm dari catalog (jika ada)
+/// AcroForm bisa berupa dictionary langsung atau indirect reference; AcroForm
+/// yang salah ditulis sebagai stream dibaca dari dictionary stream-nya
+pub fn existing_acroform(doc: &Document, root_id: ObjectId) -> Option<Dictionary> {
+    let catalog = doc.get_dictionary(root_id).ok()?;
+    let acroform = catalog.get(b"AcroForm").ok()?;
+    match doc.dereference(acroform).ok()?.1 {
+        Object::Dictionary(acroform) => Some(acroform.clone()),
+        Object::Stream(stream) => Some(stream.dict.clone()),
+        _ => None,
+    }
+}
+
+/// Baca AcroForm untuk ditandatangani dan normalisasi struktur yang rusak
+///
+/// /AcroForm boleh berupa dictionary langsung atau indirect reference. Struktur
+/// yang rusak tidak ditimpa begitu saja (field yang sudah ada akan hilang):
+///   - tidak ada, null, atau reference ke object yang tidak ada: form baru
+///   - stream: dictionary stream-nya dipakai
+///   - tipe lain, atau /Fields yang bukan array: /Fields dibangun ulang dari
+///     widget di /Annots halaman (field teratas dari rantai /Parent)
+///
+/// Parameter:
+///   - doc: dokumen yang akan ditandatangani
+///   - root_id: object ID catalog
+///   - strict: tolak dokumen yang form-nya harus dibangun ulang
+///
+/// Return: AcroForm yang siap diubah lalu disimpan dengan `register_field` /
+/// `store_acroform`; error jika catalog sendiri bukan dictionary
+pub fn load_acroform(doc: &Document, root_id: ObjectId, strict: bool) -> Result<Dictionary> {
+    let catalog = doc
+        .get_dictionary(root_id)
+        .map_err(|_| anyhow!("the document catalog ({} {} R) is not a dictionary; the file is too damaged to sign", root_id.0, root_id.1))?;
+    let Ok(entry) = catalog.get(b"AcroForm") else {
+        return Ok(Dictionary::new());
+    };
+    let (mut acroform, problem) = match doc.dereference(entry) {
+        Err(_) => {
+            // Reference ke object yang tidak ada sama dengan null (ISO 32000-1 7.3.10)
+            let form = rebuilt_fields(doc);
+            if !form.is_empty() {
+                crate::warning!("/AcroForm points to a missing object; rebuilt the form from {} field(s) found on the pages", form.len());
+            }
+            return Ok(acroform_with_fields(Dictionary::new(), form));
+        }
+        Ok((_, Object::Null)) => return Ok(Dictionary::new()),
+        Ok((_, Object::Dictionary(acroform))) => (acroform.clone(), None),
+        Ok((_, Object::Stream(stream))) => {
+            crate::warning!("/AcroForm is a stream instead of a dictionary; using the stream's dictionary");
+            let mut acroform = stream.dict.clone();
+            for key in [&b"Length"[..], b"Filter", b"DecodeParms"] {
+                acroform.remove(key);
+            }
+            (acroform, None)
+        }
+        Ok((_, other)) => (Dictionary::new(), Some(format!("/AcroForm is {} instead of a dictionary", describe(other)))),
+    };
+
+    // /Fields harus array (langsung atau indirect); selain itu dibangun ulang
+    let problem = problem.or_else(|| match acroform.get(b"Fields").map(|fields| doc.dereference(fields)) {
+        Err(_) | Ok(Ok((_, Object::Array(_)))) => None,
+        Ok(Ok((_, other))) => Some(format!("/AcroForm /Fields is {} instead of an array", describe(other))),
+        Ok(Err(_)) => Some("/AcroForm /Fields points to a missing object".to_string()),
+    });
+    if let Some(problem) = problem {
+        if strict {
+            bail!("refusing to sign: {}; the form would have to be rebuilt from the page annotations", problem);
+        }
+        let fields = rebuilt_fields(doc);
+        crate::warning!("{}; rebuilt the form from {} field(s) found on the pages", problem, fields.len());
+        acroform.remove(b"Fields");
+        acroform = acroform_with_fields(acroform, fields);
+    }
+    Ok(acroform)
+}
+
+/// AcroForm dengan /Fields berisi field ini (tanpa /Fields jika kosong)
+fn acroform_with_fields(mut acroform: Dictionary, fields: Vec<ObjectId>) -> Dictionary {
+    if !fields.is_empty() {
+        acroform.set("Fields", Object::Array(fields.into_iter().map(Object::Reference).collect()));
+    }
+    acroform
+}
+
+/// Field teratas dari semua widget annotation di halaman dokumen (urut
+/// seperti halaman dan /Annots, tanpa duplikat)
+fn rebuilt_fields(doc: &Document) -> Vec<ObjectId> {
+    let mut fields = Vec::new();
+    for page_id in doc.get_pages().into_values() {
+        for annot_id in page_annotations(doc, page_id) {
+            let Ok(annot) = doc.get_dictionary(annot_id) else {
+                continue;
+            };
+            if annot.get(b"Subtype").and_then(Object::as_name).ok() != Some(b"Widget") {
+                continue;
+            }
+            // Naik lewat /Parent sampai field teratas (batasi untuk /Parent yang melingkar)
+            let mut top = annot_id;
+            for _ in 0..32 {
+                match doc.get_dictionary(top).and_then(|field| field.get(b"Parent")).and_then(Object::as_reference) {
+                    Ok(parent) if doc.get_dictionary(parent).is_ok() => top = parent,
+                    _ => break,
+                }
+            }
+            if !fields.contains(&top) {
+                fields.push(top);
+            }
+        }
+    }
+    fields
+}
+
+/// Nama tipe object untuk pesan error ("an array", "a name", ...)
+fn describe(object: &Object) -> &'static str {
+    match object {
+        Object::Null => "null",
+        Object::Boolean(_) => "a boolean",
+        Object::Integer(_) => "an integer",
+        Object::Real(_) => "a number",
+        Object::Name(_) => "a name",
+        Object::String(..) => "a string",
+        Object::Array(_) => "an array",
+        Object::Dictionary(_) => "a dictionary",
+        Object::Stream(_) => "a stream",
+        Object::Reference(_) => "a reference",
+    }
+}
+
+/// Periksa dan tangani flag /NeedAppearances sesuai kebijakan yang dipilih
+///
+/// Parameter:
+///   - doc: dokumen PDF yang akan ditandatangani
+///   - acroform: dictionary AcroForm yang akan ditulis ke catalog
+///   - policy: kebijakan penanganan flag
+pub fn handle_need_appearances(doc: &mut Document, acroform: &mut Dictionary, policy: NeedAppearancesPolicy) -> Result<()> {
+    // Jika flag tidak ada atau bernilai false, tidak ada yang perlu dilakukan
+    let need_appearances = acroform
+        .get(b"NeedAppearances")
+        .and_then(Object::as_bool)
+        .unwrap_or(false);
+    if !need_appearances {
+        return Ok(());
+    }
+
+    // Kumpulkan semua widget dari field yang ada di AcroForm
+    let widgets = form_widgets(doc, acroform);
+
+    match policy {
+        NeedAppearancesPolicy::Warn => {
+            // Flag tetap dipertahankan, user diberi tahu risikonya
+            crate::warning!("/NeedAppearances is set; viewers may regenerate field appearances, so what is displayed may differ from what was signed\nuse --need-appearances clear or --need-appearances generate to avoid this");
+        }
+        NeedAppearancesPolicy::Clear => {
+            // Menghapus flag hanya aman jika semua widget sudah punya appearance
+            let missing = widgets.iter().filter(|w| !w.has_appearance).count();
+            if missing > 0 {
+                bail!(
+                    "cannot clear /NeedAppearances: {} field widget(s) have no appearance stream (use --need-appearances generate)",
+                    missing
+                );
+            }
+            acroform.remove(b"NeedAppearances");
+        }
+        NeedAppearancesPolicy::Generate => {
+            // Buat appearance untuk setiap widget yang belum punya
+            let default_da = acroform.get(b"DA").and_then(Object::as_str).ok().map(|da| da.to_vec());
+            let resources = acroform.get(b"DR").ok().cloned();
+            for widget in widgets.iter().filter(|w| !w.has_appearance) {
+                if widget.field_type != b"Tx" {
+                    // Hanya text field yang bisa dibuat appearance-nya secara otomatis
+                    crate::warning!("field widget {} {} R has no appearance and is not a text field; it may render blank", widget.id.0, widget.id.1);
+                    continue;
+                }
+                let da = widget.da.clone().or_else(|| default_da.clone());
+                generate_text_appearance(doc, widget, da.as_deref(), resources.clone())?;
+            }
+            acroform.remove(b"NeedAppearances");
+        }
+    }
+
+    Ok(())
+}
+
+/// Kumpulkan semua widget dari field yang terdaftar di /Fields AcroForm
+fn form_widgets(doc: &Document, acroform: &Dictionary) -> Vec<WidgetInfo> {
+    let mut widgets = Vec::new();
+    if let Ok(fields) = acroform.get(b"Fields").and_then(|f| doc.dereference(f).map(|(_, f)| f)).and_then(Object::as_array) {
+        for field in fields {
+            if let Ok(id) = field.as_reference() {
+                collect_widgets(doc, id, &Inherited::default(), &mut widgets, 0);
+            }
+        }
+    }
+    widgets
+}
+
+/// Telusuri field secara rekursif (melalui /Kids) dan kumpulkan semua widget
+/// Atribut yang bisa diwarisi (/FT, /V, /DA, /Ff) diteruskan dari parent ke child
+fn collect_widgets(doc: &Document, id: ObjectId, parent: &Inherited, widgets: &mut Vec<WidgetInfo>, depth: usize) {
+    // Batasi kedalaman rekursi untuk menghindari loop pada dokumen yang rusak
+    if depth > 32 {
+        return;
+    }
+    let Ok(dict) = doc.get_dictionary(id) else {
+        return;
+    };
+
+    // Atribut milik node ini menggantikan atribut warisan dari parent
+    let value = dict.get(b"V").ok();
+    let inherited = Inherited {
+        name: qualified_name(&parent.name, dict),
+        field_type: dict.get(b"FT").and_then(Object::as_name).ok().map(|n| n.to_vec()).or(parent.field_type.clone()),
+        value: value.and_then(|v| v.as_str().ok()).map(|v| v.to_vec()).or(parent.value.clone()),
+        state: value.and_then(|v| v.as_name().ok()).map(|v| v.to_vec()).or(parent.state.clone()),
+        da: dict.get(b"DA").and_then(Object::as_str).ok().map(|v| v.to_vec()).or(parent.da.clone()),
+        flags: dict.get(b"Ff").and_then(Object::as_i64).unwrap_or(parent.flags),
+    };
+
+    if let Ok(kids) = dict.get(b"Kids").and_then(Object::as_array) {
+        // Node non-terminal: lanjutkan ke semua child
+        for kid in kids {
+            if let Ok(kid_id) = kid.as_reference() {
+                collect_widgets(doc, kid_id, &inherited, widgets, depth + 1);
+            }
+        }
+    } else if dict.has(b"Rect") {
+        // Node terminal dengan /Rect adalah widget annotation
+        let has_appearance = normal_appearance(doc, dict).is_some();
+        widgets.push(WidgetInfo {
+            id,
+            name: inherited.name,
+            field_type: inherited.field_type.unwrap_or_default(),
+            value: inherited.value,
+            state: inherited.state,
+            da: inherited.da,
+            flags: inherited.flags,
+            has_appearance,
+            appearance_state: dict.get(b"AS").and_then(Object::as_name).ok().map(|n| n.to_vec()),
+        });
+    }
+}
+
+/// Ambil appearance normal (/AP /N) dari widget: stream, atau dictionary state untuk checkbox/radio
+fn normal_appearance<'a>(doc: &'a Document, widget: &'a Dictionary) -> Option<&'a Object> {
+    let ap = widget.get(b"AP").and_then(|ap| doc.dereference(ap)).ok()?.1.as_dict().ok()?;
+    let normal = ap.get(b"N").ok()?;
+    doc.dereference(normal).ok().map(|(_, n)| n)
+}
+
+/// Periksa dan tangani data XFA pada form
+///
+/// Viewer yang mendukung XFA (Adobe) merender form dari XFA, bukan dari field
+/// AcroForm yang ditandatangani, sehingga signature diperlakukan tidak konsisten:
+/// - dynamic XFA (/NeedsRendering, atau tanpa field AcroForm) selalu ditolak,
+///   karena halamannya dibuat viewer dan tidak ada isi AcroForm yang bisa ditandatangani
+/// - form hybrid (XFA + AcroForm): XFA dihapus jika `drop_xfa`, ditolak jika `strict`,
+///   selain itu ditandatangani dengan peringatan
+///
+/// Parameter:
+///   - doc: dokumen PDF yang akan ditandatangani
+///   - acroform: dictionary AcroForm yang akan ditulis ke catalog
+///   - drop_xfa: hapus /XFA dari AcroForm sehingga form hanya memakai AcroForm
+///   - strict: tolak form hybrid alih-alih memberi peringatan
+pub fn handle_xfa(doc: &Document, acroform: &mut Dictionary, drop_xfa: bool, strict: bool) -> Result<()> {
+    let has_xfa = acroform.has(b"XFA");
+    let needs_rendering = doc
+        .catalog()
+        .ok()
+        .and_then(|catalog| catalog.get(b"NeedsRendering").and_then(Object::as_bool).ok())
+        .unwrap_or(false);
+    if !has_xfa && !needs_rendering {
+        return Ok(());
+    }
+
+    // Dynamic XFA: tanpa XFA tidak ada form yang tersisa, jadi --drop-xfa juga tidak membantu
+    if needs_rendering || form_widgets(doc, acroform).is_empty() {
+        bail!("refusing to sign: the document is a dynamic XFA form; its pages are generated by the viewer, so there is no AcroForm content the signature can cover");
+    }
+
+    if drop_xfa {
+        acroform.remove(b"XFA");
+        crate::log::status("Removed XFA data; the form is signed as a plain AcroForm", true);
+    } else if strict {
+        bail!("refusing to sign: the form contains XFA data, which Adobe renders instead of the signed field appearances (use --drop-xfa to sign the AcroForm fields only)");
+    } else {
+        crate::warning!("the form contains XFA data; Adobe renders it instead of the signed field appearances and may treat the signature inconsistently\nuse --drop-xfa to remove the XFA data, or --strict to refuse such documents");
+    }
+    Ok(())
+}
+
+/// Periksa perubahan form yang tertunda, yaitu kondisi di mana yang ditampilkan
+/// viewer bisa berbeda dengan yang ditandatangani:
+/// - nilai text/choice field yang tidak muncul di appearance stream-nya
+/// - checkbox/radio yang state tampilannya (/AS) tidak sesuai dengan nilainya (/V)
+///
+/// Pemeriksaan appearance bersifat heuristik: field multi-baris dan comb dilewati
+/// karena teksnya dipecah di appearance stream.
+///
+/// Return: daftar masalah (kosong jika tidak ada)
+pub fn pending_form_changes(doc: &Document, acroform: &Dictionary) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    for widget in form_widgets(doc, acroform) {
+        let Ok(dict) = doc.get_dictionary(widget.id) else {
+            continue;
+        };
+        match widget.field_type.as_slice() {
+            b"Tx" | b"Ch" => {
+                if widget.flags & (FF_MULTILINE | FF_COMB) != 0 {
+                    continue;
+                }
+                let text = decode_field_text(widget.value.as_deref().unwrap_or_default());
+                if text.is_empty() {
+                    continue;
+                }
+                let shown = match normal_appearance(doc, dict).and_then(|n| n.as_stream().ok()) {
+                    Some(stream) => appearance_shows_text(&stream.decompressed_content().unwrap_or_else(|_| stream.content.clone()), &text),
+                    None => false,
+                };
+                if !shown {
+                    issues.push(format!(
+                        "field '{}' has value \"{}\" that does not appear in its appearance stream",
+                        widget.name,
+                        decode_text_string(widget.value.as_deref().unwrap_or_default())
+                    ));
+                }
+            }
+            b"Btn" if widget.flags & FF_PUSHBUTTON == 0 => {
+                let Some(shown) = &widget.appearance_state else {
+                    continue;
+                };
+                // Widget menampilkan nilai field jika punya state dengan nama itu, selain itu /Off
+                let states = normal_appearance(doc, dict).and_then(|n| n.as_dict().ok());
+                let expected: &[u8] = match &widget.state {
+                    Some(state) if states.map(|s| s.has(state)).unwrap_or(false) => state,
+                    _ => b"Off",
+                };
+                if shown.as_slice() != expected {
+                    issues.push(format!(
+                        "field '{}' shows state /{} but its value is /{}",
+                        widget.name,
+                        String::from_utf8_lossy(shown),
+                        String::from_utf8_lossy(widget.state.as_deref().unwrap_or(b"Off"))
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+/// Cek apakah teks muncul di content stream sebagai literal string atau hex string
+fn appearance_shows_text(content: &[u8], text: &[u8]) -> bool {
+    let mut literal = vec![b'('];
+    for &byte in text {
+        if matches!(byte, b'(' | b')' | b'\\') {
+            literal.push(b'\\');
+        }
+        literal.push(byte);
+    }
+    literal.push(b')');
+    let hex: String = text.iter().map(|b| format!("{:02x}", b)).collect();
+    let lower = content.to_ascii_lowercase();
+    contains(content, &literal) || contains(&lower, format!("<{}>", hex).as_bytes())
+}
+
+/// Cari subslice di dalam slice
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Buat appearance stream sederhana untuk text field berdasarkan /V dan /DA
+fn generate_text_appearance(doc: &mut Document, widget: &WidgetInfo, da: Option<&[u8]>, resources: Option<Object>) -> Result<()> {
+    // Ukuran widget diambil dari /Rect [x1 y1 x2 y2]
+    let rect = doc
+        .get_dictionary(widget.id)?
+        .get(b"Rect")
+        .and_then(Object::as_array)?
+        .iter()
+        .map(|v| v.as_float().unwrap_or(0.0))
+        .collect::<Vec<f32>>();
+    if rect.len() != 4 {
+        bail!("field widget {} {} R has an invalid /Rect", widget.id.0, widget.id.1);
+    }
+    let width = (rect[2] - rect[0]).abs();
+    let height = (rect[3] - rect[1]).abs();
+
+    // Ganti ukuran font 0 (auto) dengan ukuran yang muat di tinggi widget
+    let font_size = (height * 0.7).clamp(4.0, 12.0);
+    let da = String::from_utf8_lossy(da.unwrap_or(b"/Helv 0 Tf 0 g")).to_string();
+    let da = da.replace(" 0 Tf", &format!(" {:.1} Tf", font_size));
+
+    // Escape karakter khusus pada literal string PDF
+    let text = decode_field_text(widget.value.as_deref().unwrap_or_default());
+    let mut escaped = Vec::new();
+    for byte in text {
+        if matches!(byte, b'(' | b')' | b'\\') {
+            escaped.push(b'\\');
+        }
+        escaped.push(byte);
+    }
+
+    // Susun content stream: teks diletakkan di kiri dengan padding 2pt
+    let mut content = format!(
+        "/Tx BMC\nq\nBT\n{}\n2 {:.2} Td\n(",
+        da,
+        ((height - font_size) / 2.0).max(0.0) + 1.0
+    )
+    .into_bytes();
+    content.extend_from_slice(&escaped);
+    content.extend_from_slice(b") Tj\nET\nQ\nEMC");
+
+    // Buat Form XObject dengan BBox seukuran widget
+    let mut stream_dict = Dictionary::new();
+    stream_dict.set("Type", Object::Name(b"XObject".to_vec()));
+    stream_dict.set("Subtype", Object::Name(b"Form".to_vec()));
+    stream_dict.set("BBox", Object::Array(vec![
+        Object::Integer(0),
+        Object::Integer(0),
+        Object::Real(width),
+        Object::Real(height),
+    ]));
+    // Resource font diambil dari /DR milik AcroForm
+    if let Some(resources) = resources {
+        stream_dict.set("Resources", resources);
+    }
+    let stream_id = doc.add_object(lopdf::Stream::new(stream_dict, content));
+
+    // Pasang appearance baru pada widget
+    let mut ap = Dictionary::new();
+    ap.set("N", Object::Reference(stream_id));
+    doc.get_dictionary_mut(widget.id)?.set("AP", Object::Dictionary(ap));
+    Ok(())
+}
+
+/// Ubah nilai field menjadi bytes single-byte yang bisa ditulis dengan font standar
+/// String UTF-16BE (diawali BOM FE FF) dikonversi; karakter di luar Latin-1 diganti '?'
+fn decode_field_text(bytes: &[u8]) -> Vec<u8> {
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        let units = bytes[2..]
+            .chunks(2)
+            .filter(|c| c.len() == 2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect::<Vec<u16>>();
+        String::from_utf16_lossy(&units)
+            .chars()
+            .map(|c| if (c as u32) < 256 { c as u8 } else { b'?' })
+            .collect()
+    } else {
+        bytes.to_vec()
+    }
+}
+
+/// Satu signature field yang sudah berisi nilai (/V) di dokumen
+pub struct SignatureField {
+    pub name: String,        // Nama lengkap field (/T, digabung dengan nama parent)
+    pub value: Dictionary,   // Signature dictionary (/V) yang sudah di-dereference
+}
+
+/// Cari semua signature field (/FT /Sig) yang sudah ditandatangani di AcroForm
+pub fn signature_fields(doc: &Document) -> Vec<SignatureField> {
+    let mut result = Vec::new();
+    let Some(acroform) = doc.catalog().ok().and_then(|catalog| {
+        let acroform = catalog.get(b"AcroForm").ok()?;
+        doc.dereference(acroform).ok()?.1.as_dict().ok()
+    }) else {
+        return result;
+    };
+    if let Ok(fields) = acroform.get(b"Fields").and_then(|f| doc.dereference(f).map(|(_, f)| f)).and_then(Object::as_array) {
+        for field in fields {
+            if let Ok(id) = field.as_reference() {
+                collect_signature_fields(doc, id, "", None, &mut result, 0);
+            }
+        }
+    }
+    result
+}
+
+/// Telusuri field secara rekursif dan kumpulkan signature field yang punya /V
+fn collect_signature_fields(
+    doc: &Document,
+    id: ObjectId,
+    parent_name: &str,
+    field_type: Option<Vec<u8>>,
+    result: &mut Vec<SignatureField>,
+    depth: usize,
+) {
+    // Batasi kedalaman rekursi untuk menghindari loop pada dokumen yang rusak
+    if depth > 32 {
+        return;
+    }
+    let Ok(dict) = doc.get_dictionary(id) else {
+        return;
+    };
+
+    // Nama lengkap field = nama parent + "." + nama field ini
+    let name = qualified_name(parent_name, dict);
+    let field_type = dict.get(b"FT").and_then(Object::as_name).ok().map(|n| n.to_vec()).or(field_type);
+
+    // Field dengan /V berupa dictionary adalah signature yang sudah terisi
+    if field_type.as_deref() == Some(b"Sig") {
+        if let Ok(value) = dict.get(b"V").and_then(|v| doc.dereference(v)).and_then(|(_, v)| v.as_dict()) {
+            result.push(SignatureField { name: name.clone(), value: value.clone() });
+            return;
+        }
+    }
+
+    if let Ok(kids) = dict.get(b"Kids").and_then(Object::as_array) {
+        for kid in kids {
+            if let Ok(kid_id) = kid.as_reference() {
+                collect_signature_fields(doc, kid_id, &name, field_type.clone(), result, depth + 1);
+            }
+        }
+    }
+}
+
+/// Signature field kosong yang sudah disiapkan di dokumen (misalnya oleh
+/// generator dokumen), untuk diisi dengan signature baru
+pub struct EmptySignatureField {
+    pub field_id: ObjectId,  // Field (/FT /Sig) yang /V-nya akan diisi
+    pub widget_id: ObjectId, // Widget annotation (bisa object yang sama dengan field)
+    pub page_id: ObjectId,   // Halaman tempat widget ditampilkan
+    pub rect: [f32; 4],      // /Rect widget: kiri, bawah, kanan, atas
+}
+
+/// Cari signature field kosong berdasarkan nama lengkapnya (`parent.child`)
+///
+/// Widget pertama field dipakai untuk posisi; halamannya diambil dari /P
+/// widget, atau dicari di /Annots setiap halaman jika /P tidak ada.
+pub fn find_empty_signature_field(doc: &Document, name: &str) -> Result<EmptySignatureField> {
+    let fields = terminal_fields(doc);
+    let Some((_, field_id, field_type)) = fields.iter().find(|(full_name, _, _)| full_name == name) else {
+        let empty = empty_signature_field_names(doc);
+        if empty.is_empty() {
+            bail!("no field named {} (the document has no empty signature fields)", name);
+        }
+        bail!("no field named {} (empty signature fields: {})", name, empty.join(", "));
+    };
+    if field_type.as_deref() != Some(b"Sig") {
+        bail!("field {} is not a signature field", name);
+    }
+    if has_value(doc, *field_id) {
+        bail!("signature field {} is already signed", name);
+    }
+
+    // Field dan widget bisa digabung dalam satu dictionary, atau widget ada di /Kids
+    let field = doc.get_dictionary(*field_id)?;
+    let widget_id = if field.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Widget") || field.has(b"Rect") {
+        *field_id
+    } else {
+        field
+            .get(b"Kids")
+            .and_then(Object::as_array)
+            .ok()
+            .and_then(|kids| kids.first())
+            .and_then(|kid| kid.as_reference().ok())
+            .ok_or_else(|| anyhow!("signature field {} has no widget", name))?
+    };
+    let widget = doc.get_dictionary(widget_id)?;
+    let rect = annotation_rect(doc, widget_id).unwrap_or_default();
+
+    let page_id = match widget.get(b"P").and_then(Object::as_reference) {
+        // /P harus halaman di page tree, bukan sembarang dictionary
+        Ok(page_id) if doc.page_iter().any(|id| id == page_id) => page_id,
+        _ => doc
+            .get_pages()
+            .into_values()
+            .find(|&page_id| page_annotations(doc, page_id).contains(&widget_id))
+            .ok_or_else(|| anyhow!("cannot find the page of signature field {}", name))?,
+    };
+
+    Ok(EmptySignatureField { field_id: *field_id, widget_id, page_id, rect })
+}
+
+/// Nama lengkap semua signature field yang belum ditandatangani (tanpa /V)
+pub fn empty_signature_field_names(doc: &Document) -> Vec<String> {
+    terminal_fields(doc)
+        .into_iter()
+        .filter(|(_, id, field_type)| field_type.as_deref() == Some(b"Sig") && !has_value(doc, *id))
+        .map(|(full_name, _, _)| full_name)
+        .collect()
+}
+
+/// Object ID annotation di /Annots satu halaman
+pub fn page_annotations(doc: &Document, page_id: ObjectId) -> Vec<ObjectId> {
+    doc.get_dictionary(page_id)
+        .and_then(|page| page.get(b"Annots"))
+        .and_then(|annots| doc.dereference(annots).map(|(_, annots)| annots))
+        .and_then(Object::as_array)
+        .map(|annots| annots.iter().filter_map(|annot| annot.as_reference().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Kotak signature yang sudah ada di satu halaman: widget signature field
+/// (/FT /Sig, boleh diwarisi dari parent) dan stamp annotation (--stamp-only)
+pub fn signature_rects(doc: &Document, page_id: ObjectId) -> Vec<[f32; 4]> {
+    let is_signature = |id: ObjectId| {
+        let Ok(annot) = doc.get_dictionary(id) else {
+            return false;
+        };
+        if annot.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Stamp") {
+            return true;
+        }
+        let parent = annot.get(b"Parent").and_then(Object::as_reference).ok().and_then(|parent| doc.get_dictionary(parent).ok());
+        [Some(annot), parent].into_iter().flatten().any(|dict| dict.get(b"FT").and_then(Object::as_name).ok() == Some(b"Sig"))
+    };
+    page_annotations(doc, page_id)
+        .into_iter()
+        .filter(|&id| is_signature(id))
+        .filter_map(|id| annotation_rect(doc, id))
+        .filter(|rect| rect[2] > rect[0] && rect[3] > rect[1])
+        .collect()
+}
+
+/// Widget annotation field: field itu sendiri (field dan widget digabung),
+/// atau kids tanpa /T
+pub fn field_widgets(doc: &Document, field_id: ObjectId, field: &Dictionary) -> Vec<ObjectId> {
+    if field.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Widget") || field.has(b"Rect") {
+        return vec![field_id];
+    }
+    field
+        .get(b"Kids")
+        .and_then(Object::as_array)
+        .map(|kids| {
+            kids.iter()
+                .filter_map(|kid| kid.as_reference().ok())
+                .filter(|&kid| doc.get_dictionary(kid).is_ok_and(|kid| !kid.has(b"T")))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Annotation /Stamp di semua halaman yang /NM-nya `name` (stamp dari
+/// --stamp-only diberi /NM sama dengan nama signature field-nya)
+pub fn stamps_named(doc: &Document, name: &str) -> Vec<ObjectId> {
+    doc.get_pages()
+        .into_values()
+        .flat_map(|page_id| page_annotations(doc, page_id))
+        .filter(|&id| {
+            doc.get_dictionary(id).is_ok_and(|annot| {
+                annot.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Stamp")
+                    && annot.get(b"NM").and_then(Object::as_str).ok().map(decode_text_string).as_deref() == Some(name)
+            })
+        })
+        .collect()
+}
+
+/// Nama lengkap field: nama parent + "." + /T field ini, atau nama parent
+/// jika field tidak punya /T
+///
+/// /T adalah text string (PDFDocEncoding atau UTF-16BE), sehingga didekode
+/// dulu; nama non-ASCII dari generator yang berbeda menjadi sama jika teksnya sama.
+fn qualified_name(parent_name: &str, dict: &Dictionary) -> String {
+    match dict.get(b"T").and_then(Object::as_str) {
+        Ok(t) if parent_name.is_empty() => decode_text_string(t),
+        Ok(t) => format!("{}.{}", parent_name, decode_text_string(t)),
+        Err(_) => parent_name.to_string(),
+    }
+}
+
+/// Apakah field sudah punya nilai /V
+fn has_value(doc: &Document, field_id: ObjectId) -> bool {
+    doc.get_dictionary(field_id).map(|field| field.has(b"V")).unwrap_or(false)
+}
+
+/// Semua field terminal (tanpa child field) beserta nama lengkap dan /FT-nya
+/// (/FT diwarisi dari parent)
+pub fn terminal_fields(doc: &Document) -> Vec<(String, ObjectId, Option<Vec<u8>>)> {
+    let mut result = Vec::new();
+    let Some(acroform) = catalog_id(doc).ok().and_then(|root_id| existing_acroform(doc, root_id)) else {
+        return result;
+    };
+    if let Ok(fields) = acroform.get(b"Fields").and_then(|f| doc.dereference(f).map(|(_, f)| f)).and_then(Object::as_array) {
+        for field in fields {
+            if let Ok(id) = field.as_reference() {
+                collect_terminal_fields(doc, id, "", None, &mut result, 0);
+            }
+        }
+    }
+    result
+}
+
+/// Telusuri field secara rekursif; kids tanpa /T adalah widget, bukan field
+fn collect_terminal_fields(
+    doc: &Document,
+    id: ObjectId,
+    parent_name: &str,
+    field_type: Option<Vec<u8>>,
+    result: &mut Vec<(String, ObjectId, Option<Vec<u8>>)>,
+    depth: usize,
+) {
+    // Batasi kedalaman rekursi untuk menghindari loop pada dokumen yang rusak
+    if depth > 32 {
+        return;
+    }
+    let Ok(dict) = doc.get_dictionary(id) else {
+        return;
+    };
+    let name = qualified_name(parent_name, dict);
+    let field_type = dict.get(b"FT").and_then(Object::as_name).ok().map(|n| n.to_vec()).or(field_type);
+
+    let child_fields: Vec<ObjectId> = dict
+        .get(b"Kids")
+        .and_then(Object::as_array)
+        .map(|kids| {
+            kids.iter()
+                .filter_map(|kid| kid.as_reference().ok())
+                .filter(|&kid| doc.get_dictionary(kid).map(|kid| kid.has(b"T")).unwrap_or(false))
+                .collect()
+        })
+        .unwrap_or_default();
+    if child_fields.is_empty() {
+        result.push((name, id, field_type));
+        return;
+    }
+    for kid in child_fields {
+        collect_terminal_fields(doc, kid, &name, field_type.clone(), result, depth + 1);
+    }
+}
+
+/// Cari nama field top-level yang belum dipakai: `<prefix>1`, `<prefix>2`, dst.
+/// Dipakai agar setiap penandatangan berikutnya mendapat field sendiri
+pub fn unused_field_name(doc: &Document, acroform: &Dictionary, prefix: &str) -> String {
+    let used: Vec<String> = acroform
+        .get(b"Fields")
+        .and_then(|f| doc.dereference(f).map(|(_, f)| f))
+        .and_then(Object::as_array)
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|field| doc.dereference(field).ok()?.1.as_dict().ok())
+                .filter_map(|dict| dict.get(b"T").and_then(Object::as_str).ok().map(decode_text_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    (1..)
+        .map(|n| naming::field_name(prefix, n))
+        .find(|name| !used.contains(name))
+        .unwrap()
+}
@@ -0,0 +1,173 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::time::{Duration, Instant, SystemTime}; // Interval keep-alive dan lama setiap pemeriksaan
+use x509_cert::der::{Decode, Encode}; // Sertifikat dan kunci publik dalam DER
+use x509_cert::Certificate; // Sertifikat penandatangan
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::cert; // Kredensial uji (generate-cert)
+use crate::crypto::provider; // Pesan uji acak
+#[cfg(feature = "network")]
+use crate::crypto::revocation::fetch_revocation_data_with; // Status sertifikat penandatangan
+use crate::crypto::signer::{PublicKey, Signer}; // Kunci yang diuji dan verifikasi signature uji
+#[cfg(feature = "network")]
+use crate::crypto::tsa; // Request timestamp uji
+use crate::pdf::options::SignatureOptions; // Kunci, TSA, dan kebijakan yang dipakai batch
+#[cfg(feature = "network")]
+use crate::pdf::pades::PadesLevel; // OCSP/CRL hanya untuk B-LT ke atas
+use crate::pdf::text; // Waktu untuk pesan
+
+/// Sertifikat yang berakhir dalam jangka ini diberi peringatan saat self-test
+const EXPIRY_WARNING: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Jeda pemeriksaan tanda berhenti di thread keep-alive
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Pemeriksaan kesehatan token untuk batch (`sign-batch`, `watch`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HealthPolicy {
+    pub self_test: bool,              // Jalankan `self_test` sebelum file pertama
+    pub keep_alive: Option<Duration>, // Interval ping ke token selama batch; None = tanpa ping
+}
+
+/// Hasil `self_test`
+pub struct SelfTest {
+    pub signer: String,               // Deskripsi kunci, misalnya "ECDSA P-256 (PKCS#11)"
+    pub subject: Option<String>,      // Subject sertifikat penandatangan
+    pub signing: Duration,            // Lama signing uji (round-trip ke HSM/KMS)
+    pub timestamp: Option<Duration>,  // Lama request timestamp uji; None = tanpa TSA
+    pub revocation: Option<Duration>, // Lama pengambilan OCSP/CRL; None = di bawah B-LT
+    pub warnings: Vec<String>,        // Masalah yang tidak menghentikan batch
+}
+
+impl SelfTest {
+    /// Ringkasan satu baris untuk log
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("test signature in {} ms", self.signing.as_millis())];
+        if let Some(elapsed) = self.timestamp {
+            parts.push(format!("TSA in {} ms", elapsed.as_millis()));
+        }
+        if let Some(elapsed) = self.revocation {
+            parts.push(format!("OCSP/CRL in {} ms", elapsed.as_millis()));
+        }
+        format!("{}: {}", self.signer, parts.join(", "))
+    }
+}
+
+/// Uji kunci dan kredensial sebelum batch, agar kegagalan muncul di menit
+/// pertama dan bukan setelah berjam-jam memproses dokumen
+///
+/// Yang diperiksa: sertifikat penandatangan cocok dengan kunci dan masih
+/// berlaku, algoritma lolos deny-list, bukan kredensial uji (kecuali
+/// diizinkan), kunci benar-benar bisa menandatangani
+/// (pesan acak ditandatangani lalu diverifikasi dengan kunci publiknya; untuk
+/// PKCS#11 dan KMS ini satu round-trip ke token atau layanan), lalu dengan
+/// fitur network: login TSA dengan satu request timestamp (`options.timestamp`)
+/// dan data OCSP/CRL rantai penandatangan untuk B-LT ke atas.
+///
+/// Parameter:
+///   - signer: kunci yang dimuat untuk batch
+///   - certificates: sertifikat DER (penandatangan dulu)
+///   - options: opsi signature batch (digest, TSA, PAdES, kebijakan LTV)
+///   - cancel: token pembatalan; batas waktunya membatasi request TSA/OCSP/CRL
+pub fn self_test(signer: &dyn Signer, certificates: &[Vec<u8>], options: &SignatureOptions, cancel: &CancellationToken) -> Result<SelfTest> {
+    let public_key = signer.public_key_info()?;
+    options.algorithm_policy.check_signing(&public_key, certificates)?;
+    cert::check_test_credentials(certificates, options.allow_test_credentials)?;
+
+    let mut warnings = Vec::new();
+    let subject = match certificates.first() {
+        Some(der) => {
+            let cert = Certificate::from_der(der).map_err(|e| anyhow!("invalid signer certificate: {}", e))?;
+            let tbs = &cert.tbs_certificate;
+            let subject = tbs.subject.to_string();
+            let certified = tbs.subject_public_key_info.to_der().map_err(|e| anyhow!("{}", e))?;
+            if certified != public_key.to_der().map_err(|e| anyhow!("{}", e))? {
+                bail!("the signer certificate {} does not belong to the key {}", subject, signer.description());
+            }
+            let (not_before, not_after) = (tbs.validity.not_before.to_system_time(), tbs.validity.not_after.to_system_time());
+            let now = SystemTime::now();
+            if now < not_before {
+                bail!("the signer certificate {} is not valid before {}", subject, text::human_time(not_before));
+            }
+            if now > not_after {
+                bail!("the signer certificate {} expired on {}", subject, text::human_time(not_after));
+            }
+            if not_after.duration_since(now).unwrap_or_default() < EXPIRY_WARNING {
+                warnings.push(format!("the signer certificate {} expires on {}", subject, text::human_time(not_after)));
+            }
+            Some(subject)
+        }
+        None => {
+            warnings.push("no signer certificate; verifiers need the public key to check the signatures".to_string());
+            None
+        }
+    };
+
+    // Signing uji dengan pesan acak, lalu verifikasi dengan kunci publik
+    let digest = signer.digest_for(options.digest);
+    let mut message = b"pdfsign self-test ".to_vec();
+    let mut nonce = [0u8; 16];
+    provider::fill_random(&mut nonce);
+    message.extend_from_slice(&nonce);
+    let started = Instant::now();
+    let signature = signer.sign_message(&message, digest).context("signing self-test failed")?;
+    let signing = started.elapsed();
+    let valid = PublicKey::from_spki(&public_key)?.verify_message(&signer.signature_algorithm(digest)?, digest, &message, &signature)?;
+    if !valid {
+        bail!("signing self-test failed: {} produced a signature that does not verify", signer.description());
+    }
+    cancel.check()?;
+
+    // Login TSA: satu timestamp atas pesan uji
+    #[cfg(feature = "network")]
+    let timestamp = match &options.timestamp {
+        Some(tsa) => {
+            let started = Instant::now();
+            tsa::request_timestamp(tsa, &mut |hash| Ok(hash.digest(&message)), &options.algorithm_policy, cancel)
+                .with_context(|| format!("TSA self-test failed ({})", tsa.url))?;
+            Some(started.elapsed())
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "network"))]
+    let timestamp = None;
+
+    // Status rantai penandatangan: sertifikat yang dicabut gagal sekarang, bukan di setiap file
+    #[cfg(feature = "network")]
+    let revocation = if options.pades >= Some(PadesLevel::BLt) {
+        let started = Instant::now();
+        fetch_revocation_data_with(certificates, &options.ltv, cancel).context("revocation self-test failed")?;
+        Some(started.elapsed())
+    } else {
+        None
+    };
+    #[cfg(not(feature = "network"))]
+    let revocation = None;
+
+    Ok(SelfTest { signer: signer.description(), subject, signing, timestamp, revocation, warnings })
+}
+
+/// Ping kunci setiap `interval` sampai `stop` mengembalikan true
+///
+/// Satu ping adalah signing pesan kecil, sehingga sesi PKCS#11 tidak habis
+/// dan koneksi ke KMS tetap terbuka selama batch memproses dokumen besar atau
+/// `watch` menunggu file. Ping yang gagal hanya diberi peringatan; file
+/// berikutnya akan gagal dengan penyebab yang sama. Dijalankan di thread
+/// tersendiri.
+pub fn keep_alive(signer: &dyn Signer, interval: Duration, stop: &dyn Fn() -> bool) {
+    let digest = signer.digest_for(Default::default());
+    let mut last = Instant::now();
+    while !stop() {
+        std::thread::sleep(STOP_POLL.min(interval));
+        if last.elapsed() < interval || stop() {
+            continue;
+        }
+        let started = Instant::now();
+        match signer.sign_message(b"pdfsign keep-alive", digest) {
+            Ok(_) => crate::verbose!("keep-alive", "{} answered in {} ms", signer.description(), started.elapsed().as_millis()),
+            Err(e) => crate::warning!("keep-alive ping to {} failed: {:#}", signer.description(), e),
+        }
+        last = Instant::now();
+    }
+}
@@ -0,0 +1,44 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+
+use crate::pdf::classify::DocumentView; // Dokumen yang diperiksa sebelum signing
+use crate::pdf::options::SignatureOptions; // Opsi yang boleh diubah sebelum signing
+
+/// Hook yang dipanggil pada setiap tahap pipeline penandatanganan
+///
+/// Semua method punya implementasi default yang tidak melakukan apa-apa,
+/// jadi embedder cukup meng-override tahap yang dibutuhkan. Jika sebuah hook
+/// mengembalikan error, proses signing dihentikan dan file output tidak ditulis
+/// (kecuali untuk `on_written`, yang dipanggil setelah file tersimpan).
+///
+/// Contoh penggunaan: mencatat digest ke log, mengarsipkan CMS, atau meminta
+/// persetujuan manusia sebelum digest benar-benar ditandatangani.
+pub trait SigningHooks {
+    /// Dipanggil setelah dokumen dimuat, sebelum apa pun disiapkan: hook boleh
+    /// menolak dokumen (misalnya yang masih bertanda "DRAFT") dengan error,
+    /// atau mengubah opsi signature untuk dokumen ini saja
+    fn on_document(&mut self, _document: &DocumentView, _options: &mut SignatureOptions) -> Result<()> {
+        Ok(())
+    }
+
+    /// Dipanggil setelah digest dokumen dihitung, sebelum ditandatangani
+    fn on_prepared(&mut self, _digest: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Dipanggil setelah struktur signature (PKCS#7/CMS) selesai dibuat,
+    /// sebelum disisipkan ke dalam dokumen
+    fn on_cms_built(&mut self, _cms: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Dipanggil setelah PDF hasil tanda tangan tersimpan (path file atau lokasi sink)
+    fn on_written(&mut self, _path: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Implementasi hook kosong, dipakai oleh `sign_pdf` biasa
+pub struct NoHooks;
+
+impl SigningHooks for NoHooks {}
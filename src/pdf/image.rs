@@ -0,0 +1,280 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream}; // Struktur PDF
+use std::fs; // Membaca file gambar
+
+/// Gambar (logo) untuk tampilan signature, sudah dikompilasi menjadi stream
+/// image XObject yang siap disalin ke dokumen
+///
+/// PNG didekode lalu dikompres ulang dengan Flate: kanal alpha dipisah menjadi
+/// /SMask agar latar transparan tidak tampil hitam. JPEG disisipkan apa adanya
+/// (DCTDecode), termasuk JPEG CMYK. Profil ICC dari iCCP (PNG) atau APP2 (JPEG)
+/// dipakai sebagai color space /ICCBased. Semua kompresi dilakukan sekali saat
+/// gambar dibaca, sehingga satu gambar bisa dipakai untuk banyak dokumen.
+#[derive(Clone, Debug)]
+pub struct AppearanceImage {
+    pub width: u32,          // Lebar dalam pixel
+    pub height: u32,         // Tinggi dalam pixel
+    image: Stream,           // Image XObject (tanpa /SMask dan /ColorSpace ICC)
+    smask: Option<Stream>,   // Kanal alpha sebagai image DeviceGray
+    icc: Option<Stream>,     // Profil ICC untuk /ICCBased
+    device: &'static [u8],   // Color space device sesuai jumlah kanal
+}
+
+/// Hasil dekode gambar sebelum dijadikan stream PDF
+struct DecodedImage {
+    width: u32,                   // Lebar dalam pixel
+    height: u32,                  // Tinggi dalam pixel
+    components: u8,               // Jumlah kanal warna: 1 (gray), 3 (RGB), 4 (CMYK)
+    data: Vec<u8>,                // Pixel (tanpa alpha) atau data JPEG
+    jpeg: bool,                   // true = data JPEG untuk DCTDecode
+    invert_cmyk: bool,            // JPEG CMYK dari Adobe menyimpan nilai terbalik
+    alpha: Option<Vec<u8>>,       // Kanal alpha 8 bit (untuk /SMask)
+    icc_profile: Option<Vec<u8>>, // Profil ICC yang disisipkan di gambar
+}
+
+impl AppearanceImage {
+    /// Baca gambar PNG atau JPEG dari file (format dikenali dari isinya)
+    pub fn load(path: &str) -> Result<AppearanceImage> {
+        let bytes = fs::read(path).with_context(|| format!("cannot read appearance image {}", path))?;
+        AppearanceImage::from_bytes(&bytes).with_context(|| format!("cannot use appearance image {}", path))
+    }
+
+    /// Dekode gambar PNG atau JPEG dari bytes dan siapkan stream-nya
+    pub fn from_bytes(bytes: &[u8]) -> Result<AppearanceImage> {
+        let decoded = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            decode_png(bytes)?
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            parse_jpeg(bytes)?
+        } else {
+            bail!("unsupported image format (expected PNG or JPEG)")
+        };
+        decoded.compile()
+    }
+
+    /// Tambahkan gambar ke dokumen sebagai image XObject (beserta /SMask dan
+    /// profil ICC-nya)
+    ///
+    /// Return: object ID image XObject
+    pub fn add_to(&self, doc: &mut Document) -> ObjectId {
+        let mut image = self.image.clone();
+        let color_space = match &self.icc {
+            Some(icc) => Object::Array(vec![Object::Name(b"ICCBased".to_vec()), Object::Reference(doc.add_object(icc.clone()))]),
+            None => Object::Name(self.device.to_vec()),
+        };
+        image.dict.set("ColorSpace", color_space);
+        if let Some(smask) = &self.smask {
+            image.dict.set("SMask", Object::Reference(doc.add_object(smask.clone())));
+        }
+        doc.add_object(image)
+    }
+
+    /// Jumlah kanal color space device gambar (1 gray, 3 RGB, 4 CMYK), atau
+    /// None jika warnanya ditentukan profil ICC (/ICCBased)
+    pub fn device_components(&self) -> Option<u8> {
+        match (&self.icc, self.device) {
+            (Some(_), _) => None,
+            (None, b"DeviceGray") => Some(1),
+            (None, b"DeviceRGB") => Some(3),
+            (None, _) => Some(4),
+        }
+    }
+
+    /// Apakah gambar punya kanal alpha (/SMask, transparansi)
+    pub fn has_alpha(&self) -> bool {
+        self.smask.is_some()
+    }
+}
+
+impl DecodedImage {
+    /// Buat stream image XObject, /SMask, dan profil ICC (terkompresi)
+    fn compile(self) -> Result<AppearanceImage> {
+        let device: &'static [u8] = match self.components {
+            1 => b"DeviceGray",
+            3 => b"DeviceRGB",
+            _ => b"DeviceCMYK",
+        };
+
+        let mut dict = image_dictionary(self.width, self.height);
+        if self.invert_cmyk {
+            dict.set("Decode", Object::Array([1, 0, 1, 0, 1, 0, 1, 0].iter().map(|&n| Object::Integer(n)).collect()));
+        }
+        let image = if self.jpeg {
+            dict.set("Filter", Object::Name(b"DCTDecode".to_vec()));
+            Stream::new(dict, self.data).with_compression(false)
+        } else {
+            let mut stream = Stream::new(dict, self.data);
+            stream.compress()?;
+            stream
+        };
+
+        let smask = match self.alpha {
+            Some(alpha) => {
+                let mut dict = image_dictionary(self.width, self.height);
+                dict.set("ColorSpace", Object::Name(b"DeviceGray".to_vec()));
+                let mut stream = Stream::new(dict, alpha);
+                stream.compress()?;
+                Some(stream)
+            }
+            None => None,
+        };
+
+        // Profil ICC hanya dipakai jika jumlah kanalnya cocok dengan gambar
+        let components = self.components;
+        let icc = match self.icc_profile.filter(|profile| icc_channels(profile) == Some(components)) {
+            Some(profile) => {
+                let mut dict = Dictionary::new();
+                dict.set("N", Object::Integer(components.into()));
+                dict.set("Alternate", Object::Name(device.to_vec()));
+                let mut stream = Stream::new(dict, profile);
+                stream.compress()?;
+                Some(stream)
+            }
+            None => None,
+        };
+
+        Ok(AppearanceImage { width: self.width, height: self.height, image, smask, icc, device })
+    }
+}
+
+/// Dictionary dasar image XObject 8 bit per komponen
+fn image_dictionary(width: u32, height: u32) -> Dictionary {
+    let mut dict = Dictionary::new();
+    dict.set("Type", Object::Name(b"XObject".to_vec()));
+    dict.set("Subtype", Object::Name(b"Image".to_vec()));
+    dict.set("Width", Object::Integer(width.into()));
+    dict.set("Height", Object::Integer(height.into()));
+    dict.set("BitsPerComponent", Object::Integer(8));
+    dict
+}
+
+/// Dekode PNG ke pixel 8 bit; palet dan tRNS dikembangkan menjadi RGB(A)
+fn decode_png(bytes: &[u8]) -> Result<DecodedImage> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| anyhow!("invalid PNG: {}", e))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(|e| anyhow!("invalid PNG: {}", e))?;
+    buffer.truncate(frame.buffer_size());
+    let icc_profile = reader.info().icc_profile.as_ref().map(|profile| profile.to_vec());
+
+    // Pisahkan kanal alpha dari kanal warna
+    let (components, has_alpha) = match frame.color_type {
+        png::ColorType::Grayscale => (1, false),
+        png::ColorType::GrayscaleAlpha => (1, true),
+        png::ColorType::Rgb => (3, false),
+        png::ColorType::Rgba => (3, true),
+        png::ColorType::Indexed => bail!("PNG palette was not expanded"),
+    };
+    let (data, alpha) = if has_alpha {
+        let stride = components + 1;
+        let mut colors = Vec::with_capacity(buffer.len() / stride * components);
+        let mut alpha = Vec::with_capacity(buffer.len() / stride);
+        for pixel in buffer.chunks_exact(stride) {
+            colors.extend_from_slice(&pixel[..components]);
+            alpha.push(pixel[components]);
+        }
+        // Alpha yang seluruhnya 255 tidak perlu /SMask
+        let alpha = if alpha.iter().all(|&a| a == u8::MAX) { None } else { Some(alpha) };
+        (colors, alpha)
+    } else {
+        (buffer, None)
+    };
+
+    Ok(DecodedImage {
+        width: frame.width,
+        height: frame.height,
+        components: components as u8,
+        data,
+        jpeg: false,
+        invert_cmyk: false,
+        alpha,
+        icc_profile,
+    })
+}
+
+/// Baca header JPEG: ukuran dan jumlah kanal (SOF), marker Adobe (APP14),
+/// dan profil ICC (APP2, bisa terbagi beberapa segmen); datanya tidak didekode
+fn parse_jpeg(bytes: &[u8]) -> Result<DecodedImage> {
+    let mut pos = 2;
+    let mut frame = None; // (lebar, tinggi, kanal)
+    let mut adobe = false;
+    let mut icc_chunks: Vec<(u8, &[u8])> = Vec::new();
+    while frame.is_none() {
+        // Marker diawali 0xFF (boleh lebih dari satu sebagai pengisi)
+        while bytes.get(pos) == Some(&0xFF) {
+            pos += 1;
+        }
+        let marker = *bytes.get(pos).ok_or_else(|| anyhow!("truncated JPEG"))?;
+        pos += 1;
+        // Marker tanpa segmen data
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            continue;
+        }
+        if marker == 0xD9 || marker == 0xDA {
+            bail!("JPEG has no frame header");
+        }
+        let length = match bytes.get(pos..pos + 2) {
+            Some(&[high, low]) => usize::from(u16::from_be_bytes([high, low])),
+            _ => bail!("truncated JPEG"),
+        };
+        let segment = bytes
+            .get(pos + 2..pos + length)
+            .filter(|_| length >= 2)
+            .ok_or_else(|| anyhow!("truncated JPEG"))?;
+        pos += length;
+
+        match marker {
+            // SOF0-SOF15 kecuali DHT (C4), JPG (C8), dan DAC (CC)
+            0xC0..=0xCF if ![0xC4, 0xC8, 0xCC].contains(&marker) => {
+                if segment.len() < 6 {
+                    bail!("invalid JPEG frame header");
+                }
+                if segment[0] != 8 {
+                    bail!("{}-bit JPEG is not supported (PDF needs 8 bits per component)", segment[0]);
+                }
+                let height = u16::from_be_bytes([segment[1], segment[2]]);
+                let width = u16::from_be_bytes([segment[3], segment[4]]);
+                if width == 0 || height == 0 {
+                    bail!("JPEG without image size in the frame header is not supported");
+                }
+                frame = Some((u32::from(width), u32::from(height), segment[5]));
+            }
+            0xE2 if segment.starts_with(b"ICC_PROFILE\0") && segment.len() > 14 => {
+                icc_chunks.push((segment[12], &segment[14..]));
+            }
+            0xEE if segment.starts_with(b"Adobe") => adobe = true,
+            _ => {}
+        }
+    }
+    let (width, height, components) = frame.unwrap_or_default();
+    if ![1, 3, 4].contains(&components) {
+        bail!("JPEG with {} color components is not supported", components);
+    }
+
+    // Gabungkan segmen ICC sesuai nomor urutnya
+    icc_chunks.sort_by_key(|(sequence, _)| *sequence);
+    let icc_profile = if icc_chunks.is_empty() { None } else { Some(icc_chunks.iter().flat_map(|(_, chunk)| chunk.iter().copied()).collect()) };
+
+    Ok(DecodedImage {
+        width,
+        height,
+        components,
+        data: bytes.to_vec(),
+        jpeg: true,
+        // Photoshop menulis JPEG CMYK dengan nilai terbalik dan marker Adobe
+        invert_cmyk: components == 4 && adobe,
+        alpha: None,
+        icc_profile,
+    })
+}
+
+/// Jumlah kanal warna menurut header profil ICC (color space di byte 16..20)
+fn icc_channels(profile: &[u8]) -> Option<u8> {
+    match profile.get(16..20)? {
+        b"GRAY" => Some(1),
+        b"RGB " => Some(3),
+        b"CMYK" => Some(4),
+        _ => None,
+    }
+}
@@ -0,0 +1,344 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object}; // Membaca struktur PDF
+use std::fs; // Membaca file PDF, menulis sertifikat (--dump-certs)
+use std::io::Write; // Menulis output JSON
+use std::path::{Path, PathBuf}; // Direktori dan file sertifikat
+use x509_cert::der::pem::{self, LineEnding}; // Sertifikat dalam PEM
+use x509_cert::der::Decode; // Parsing sertifikat DER
+use x509_cert::Certificate; // Urutan rantai (subject/issuer)
+
+pub use crate::crypto::cert::CertificateInfo; // Ringkasan sertifikat penandatangan
+use crate::pdf::form; // Signature field di AcroForm
+use crate::pdf::mdp; // Certification signature (DocMDP)
+use crate::pdf::text::{self, json_string}; // Text string PDF (PDFDocEncoding / UTF-16BE) dan escape JSON
+use crate::pdf::verify; // Sertifikat penandatangan dari /Contents
+
+/// Isi dokumen untuk `pdfsign inspect`: metadata dan daftar signature
+///
+/// Berbeda dengan `verify`, tidak ada yang diverifikasi: hanya apa yang
+/// tertulis di dokumen, sehingga signature yang rusak pun tetap terlihat.
+pub struct DocumentInfo {
+    pub path: String,                        // Path file PDF
+    pub version: String,                     // Versi PDF dari header, misalnya "1.7"
+    pub pages: usize,                        // Jumlah halaman
+    pub file_size: usize,                    // Ukuran file (bytes)
+    pub encrypted: bool,                     // Dokumen terenkripsi (/Encrypt di trailer)
+    pub metadata: Vec<(String, String)>,     // Isi /Info (Title, Author, Producer, ...); tanggal apa adanya (tanggal PDF)
+    pub certification: Option<&'static str>, // Level certification (DocMDP), jika ada
+    pub signatures: Vec<SignatureInfo>,      // Signature field yang sudah ditandatangani
+    pub empty_fields: Vec<EmptyFieldInfo>,   // Signature field yang belum ditandatangani
+}
+
+/// Signature field kosong yang menunggu ditandatangani (`sign --field`)
+pub struct EmptyFieldInfo {
+    pub field: String,            // Nama lengkap signature field
+    pub page: Option<u32>,        // Nomor halaman widget (1 = pertama); None jika widget tidak ada di halaman mana pun
+    pub rect: Option<[f32; 4]>,   // /Rect widget: kiri, bawah, kanan, atas
+}
+
+/// Satu signature field beserta isi signature dictionary-nya
+pub struct SignatureInfo {
+    pub field: String,                        // Nama lengkap signature field
+    pub signer: Option<String>,               // /Name
+    pub reason: Option<String>,               // /Reason
+    pub location: Option<String>,             // /Location
+    pub contact_info: Option<String>,         // /ContactInfo
+    pub signing_time: Option<String>,         // /M apa adanya (tanggal PDF "D:YYYYMMDDHHmmSS+hh'mm'")
+    pub sub_filter: Option<String>,           // /SubFilter (format signature)
+    pub byte_range: Vec<i64>,                 // /ByteRange
+    pub covers_whole_document: bool,          // ByteRange sampai akhir file
+    pub certificate: Option<CertificateInfo>, // Sertifikat penandatangan (jika tertanam)
+    pub certificates: Vec<Vec<u8>>,           // Sertifikat tertanam (DER): penandatangan, lalu rantainya ke atas
+}
+
+/// Format file sertifikat untuk `inspect --dump-certs`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum CertFormat {
+    /// Base64 dengan header BEGIN CERTIFICATE (.pem), plus satu file rantai per signature
+    #[default]
+    Pem,
+    /// DER biner (.der)
+    Der,
+}
+
+/// Entri /Info yang ditampilkan, dengan urutan tetap
+const METADATA_KEYS: [&str; 8] = ["Title", "Author", "Subject", "Keywords", "Creator", "Producer", "CreationDate", "ModDate"];
+
+/// Baca metadata dan semua signature dari file PDF
+///
+/// Parameter:
+///   - input: path file PDF
+///
+/// Return: isi dokumen (daftar signature boleh kosong)
+pub fn inspect_pdf(input: &str) -> Result<DocumentInfo> {
+    let pdf_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let doc = Document::load_mem(&pdf_bytes).with_context(|| format!("cannot parse {}", input))?;
+
+    // Metadata dari dictionary /Info di trailer
+    let info = doc
+        .trailer
+        .get(b"Info")
+        .and_then(|info| doc.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok();
+    let metadata = METADATA_KEYS
+        .iter()
+        .filter_map(|key| {
+            Some((key.to_string(), text(info?, key.as_bytes())?))
+        })
+        .collect();
+    let certification = certification_level(&doc);
+
+    let signatures = form::signature_fields(&doc)
+        .into_iter()
+        .map(|field| signature_info(field.name, &field.value, pdf_bytes.len()))
+        .collect();
+    // Posisi diambil dengan cara yang sama seperti `sign --field`
+    let page_numbers = doc.get_pages();
+    let empty_fields = form::empty_signature_field_names(&doc)
+        .into_iter()
+        .map(|name| match form::find_empty_signature_field(&doc, &name) {
+            Ok(field) => EmptyFieldInfo {
+                page: page_numbers.iter().find(|(_, &id)| id == field.page_id).map(|(&number, _)| number),
+                rect: Some(field.rect),
+                field: name,
+            },
+            Err(_) => EmptyFieldInfo { field: name, page: None, rect: None },
+        })
+        .collect();
+
+    Ok(DocumentInfo {
+        path: input.to_string(),
+        version: doc.version.clone(),
+        pages: doc.get_pages().len(),
+        file_size: pdf_bytes.len(),
+        encrypted: doc.trailer.get(b"Encrypt").is_ok(),
+        metadata,
+        certification,
+        signatures,
+        empty_fields,
+    })
+}
+
+/// Level certification (DocMDP) dokumen: "no-changes", "form-filling", atau
+/// "annotations"; None jika dokumen tidak di-certify
+pub fn certification_level(doc: &Document) -> Option<&'static str> {
+    form::catalog_id(doc)
+        .ok()
+        .and_then(|root_id| mdp::certification_permissions(doc, root_id))
+        .map(|permissions| match permissions {
+            1 => "no-changes",
+            3 => "annotations",
+            _ => "form-filling",
+        })
+}
+
+/// Ringkasan satu signature dictionary
+fn signature_info(field: String, sig: &Dictionary, file_size: usize) -> SignatureInfo {
+    let byte_range = sig
+        .get(b"ByteRange")
+        .and_then(Object::as_array)
+        .map(|range| range.iter().filter_map(|v| v.as_i64().ok()).collect::<Vec<i64>>())
+        .unwrap_or_default();
+    let covers_whole_document = byte_range.len() == 4 && byte_range[2] + byte_range[3] == file_size as i64;
+    let certificates = verify::signature_chain(sig).map(|(signer, others)| chain_order(signer, others)).unwrap_or_default();
+    let certificate = certificates.first().and_then(|signer| CertificateInfo::from_der(signer));
+
+    SignatureInfo {
+        field,
+        signer: text(sig, b"Name"),
+        reason: text(sig, b"Reason"),
+        location: text(sig, b"Location"),
+        contact_info: text(sig, b"ContactInfo"),
+        signing_time: text(sig, b"M"),
+        sub_filter: sig.get(b"SubFilter").and_then(Object::as_name_str).ok().map(|s| s.to_string()),
+        byte_range,
+        covers_whole_document,
+        certificate,
+        certificates,
+    }
+}
+
+/// Urutkan sertifikat tertanam: penandatangan, issuer-nya, issuer dari issuer
+/// itu, dan seterusnya; sertifikat lain (misalnya milik TSA) di akhir.
+/// Sertifikat yang sama hanya muncul sekali.
+fn chain_order(signer: Vec<u8>, others: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+    let names = |der: &[u8]| Certificate::from_der(der).ok().map(|cert| (cert.tbs_certificate.subject.to_string(), cert.tbs_certificate.issuer.to_string()));
+    let mut rest: Vec<Vec<u8>> = Vec::new();
+    for der in others {
+        if der != signer && !rest.contains(&der) {
+            rest.push(der);
+        }
+    }
+    let mut chain = vec![signer];
+    // Berhenti di root (subject = issuer) atau jika issuer tidak tertanam
+    while let Some((subject, issuer)) = chain.last().and_then(|last| names(last)) {
+        let next = rest.iter().position(|der| names(der).is_some_and(|(candidate, _)| candidate == issuer));
+        match next {
+            Some(index) if subject != issuer => chain.push(rest.remove(index)),
+            _ => break,
+        }
+    }
+    chain.extend(rest);
+    chain
+}
+
+/// Isi dokumen sebagai teks untuk user (ditampilkan oleh CLI)
+pub fn format_inspection(info: &DocumentInfo) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("Document:   {}\n", info.path));
+    out.push_str(&format!("  PDF version:  {}\n", info.version));
+    out.push_str(&format!("  Pages:        {}\n", info.pages));
+    out.push_str(&format!("  File size:    {} bytes\n", info.file_size));
+    out.push_str(&format!("  Encrypted:    {}\n", if info.encrypted { "yes" } else { "no" }));
+    for (key, value) in &info.metadata {
+        let value = if key.ends_with("Date") { text::human_pdf_date(value) } else { value.clone() };
+        out.push_str(&format!("  {:<13} {}\n", format!("{}:", key), value));
+    }
+    if let Some(level) = info.certification {
+        out.push_str(&format!("  Certified:    {}\n", level));
+    }
+    if info.signatures.is_empty() {
+        out.push_str("No signatures\n");
+    }
+    for (index, sig) in info.signatures.iter().enumerate() {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!("Signature #{} (field: {})\n", index + 1, sig.field));
+        out.push_str(&format!("  Signer:     {}\n", optional(&sig.signer)));
+        out.push_str(&format!("  Reason:     {}\n", optional(&sig.reason)));
+        out.push_str(&format!("  Location:   {}\n", optional(&sig.location)));
+        if let Some(contact_info) = &sig.contact_info {
+            out.push_str(&format!("  Contact:    {}\n", contact_info));
+        }
+        out.push_str(&format!("  Signed at:  {}\n", sig.signing_time.as_deref().map_or("-".to_string(), text::human_pdf_date)));
+        out.push_str(&format!("  SubFilter:  {}\n", optional(&sig.sub_filter)));
+        out.push_str(&format!("  ByteRange:  {:?} (covers whole document: {})\n", sig.byte_range, if sig.covers_whole_document { "yes" } else { "no" }));
+        match &sig.certificate {
+            Some(cert) => {
+                out.push_str(&format!("  Subject:    {}\n", cert.subject));
+                out.push_str(&format!("  Issuer:     {}\n", cert.issuer));
+                out.push_str(&format!("  Serial:     {}\n", cert.serial));
+                out.push_str(&format!("  Valid:      {}\n", cert.validity()));
+            }
+            None => out.push_str("  Certificate: none embedded\n"),
+        }
+    }
+    for field in &info.empty_fields {
+        out.push_str(&format!("Empty signature field: {}\n", field.field));
+        out.push_str(&format!("  Page:       {}\n", field.page.map_or("-".to_string(), |page| page.to_string())));
+        out.push_str(&format!("  Rect:       {}\n", field.rect.map_or("-".to_string(), |r| format!("[{} {} {} {}]", r[0], r[1], r[2], r[3]))));
+    }
+    out
+}
+
+impl DocumentInfo {
+    /// Tulis sertifikat tertanam setiap signature ke direktori (inspect --dump-certs)
+    ///
+    /// Nama file: `<field>-<n>.<pem|der>`, dengan n = 0 untuk penandatangan dan
+    /// 1, 2, ... untuk rantainya ke atas. Untuk PEM juga ditulis
+    /// `<field>-chain.pem` berisi semuanya, siap untuk
+    /// `openssl verify -untrusted`. Direktori dibuat jika belum ada; file yang
+    /// sudah ada ditimpa.
+    ///
+    /// Parameter:
+    ///   - dir: direktori tujuan
+    ///   - formats: format yang ditulis (PEM, DER, atau keduanya)
+    ///
+    /// Return: path file yang ditulis
+    pub fn dump_certificates(&self, dir: &Path, formats: &[CertFormat]) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        let mut written = Vec::new();
+        let mut write = |name: String, contents: &[u8]| -> Result<()> {
+            let path = dir.join(name);
+            fs::write(&path, contents).with_context(|| format!("cannot write {}", path.display()))?;
+            written.push(path);
+            Ok(())
+        };
+        for sig in self.signatures.iter().filter(|sig| !sig.certificates.is_empty()) {
+            // Nama field boleh berisi karakter apa saja; nama file hanya huruf, angka, dan ._-
+            let stem: String = sig.field.chars().map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '_' }).collect();
+            let pems = sig
+                .certificates
+                .iter()
+                .map(|der| pem::encode_string("CERTIFICATE", LineEnding::LF, der).map_err(|e| anyhow!("cannot encode certificate: {}", e)))
+                .collect::<Result<Vec<String>>>()?;
+            for format in formats {
+                for (index, der) in sig.certificates.iter().enumerate() {
+                    match format {
+                        CertFormat::Pem => write(format!("{}-{}.pem", stem, index), pems[index].as_bytes())?,
+                        CertFormat::Der => write(format!("{}-{}.der", stem, index), der)?,
+                    }
+                }
+                if *format == CertFormat::Pem {
+                    write(format!("{}-chain.pem", stem), pems.concat().as_bytes())?;
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    /// Tulis isi dokumen sebagai satu object JSON (untuk skrip)
+    pub fn write_json(&self, out: &mut impl Write) -> Result<()> {
+        let optional = |value: &Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"path\": {},", json_string(&self.path))?;
+        writeln!(out, "  \"pdf_version\": {},", json_string(&self.version))?;
+        writeln!(out, "  \"pages\": {},", self.pages)?;
+        writeln!(out, "  \"file_size\": {},", self.file_size)?;
+        writeln!(out, "  \"encrypted\": {},", self.encrypted)?;
+        // Tanggal /Info dalam RFC 3339 UTC; isi yang bukan tanggal PDF tetap teks aslinya
+        let metadata: Vec<String> = self
+            .metadata
+            .iter()
+            .map(|(key, value)| {
+                let value = if key.ends_with("Date") { text::machine_pdf_date(value).unwrap_or_else(|| value.clone()) } else { value.clone() };
+                format!("{}: {}", json_string(key), json_string(&value))
+            })
+            .collect();
+        writeln!(out, "  \"metadata\": {{{}}},", metadata.join(", "))?;
+        writeln!(out, "  \"certification\": {},", optional(&self.certification.map(str::to_string)))?;
+        writeln!(out, "  \"signatures\": [")?;
+        for (index, sig) in self.signatures.iter().enumerate() {
+            let certificate = sig.certificate.as_ref().map_or("null".to_string(), CertificateInfo::to_json);
+            let byte_range: Vec<String> = sig.byte_range.iter().map(i64::to_string).collect();
+            write!(
+                out,
+                "    {{\"field\": {}, \"signer\": {}, \"reason\": {}, \"location\": {}, \"contact_info\": {}, \"signing_time\": {}, \
+                 \"sub_filter\": {}, \"byte_range\": [{}], \"covers_whole_document\": {}, \"certificate\": {}}}",
+                json_string(&sig.field),
+                optional(&sig.signer),
+                optional(&sig.reason),
+                optional(&sig.location),
+                optional(&sig.contact_info),
+                optional(&sig.signing_time.as_deref().and_then(text::machine_pdf_date)),
+                optional(&sig.sub_filter),
+                byte_range.join(", "),
+                sig.covers_whole_document,
+                certificate,
+            )?;
+            writeln!(out, "{}", if index + 1 < self.signatures.len() { "," } else { "" })?;
+        }
+        writeln!(out, "  ],")?;
+        writeln!(out, "  \"empty_signature_fields\": [")?;
+        for (index, field) in self.empty_fields.iter().enumerate() {
+            write!(
+                out,
+                "    {{\"field\": {}, \"page\": {}, \"rect\": {}}}",
+                json_string(&field.field),
+                field.page.map_or("null".to_string(), |page| page.to_string()),
+                field.rect.map_or("null".to_string(), |r| format!("[{}, {}, {}, {}]", r[0], r[1], r[2], r[3])),
+            )?;
+            writeln!(out, "{}", if index + 1 < self.empty_fields.len() { "," } else { "" })?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+}
+
+/// Text string PDF dari dictionary (lihat `text::decode_text_string`)
+fn text(dict: &Dictionary, key: &[u8]) -> Option<String> {
+    dict.get(key).and_then(Object::as_str).ok().map(text::decode_text_string)
+}
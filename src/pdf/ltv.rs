@@ -0,0 +1,106 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::Object; // Struktur dasar PDF
+use std::fs::File; // Untuk membaca file input
+use std::io::Write; // Menulis hasil ke buffer sementara
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::cms; // Sertifikat dari CMS SignedData
+use crate::crypto::revocation::fetch_revocation_data_with; // OCSP/CRL untuk setiap sertifikat
+use crate::crypto::tsa; // Sertifikat dari TimeStampToken
+use crate::error::Error; // Dokumen tanpa signature
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::pades::LtvPolicy; // Sumber OCSP/CRL dan toleransi kegagalan
+use crate::pdf::{form, pades, sign}; // Signature field, DSS, incremental update
+use crate::pdf::syntax::ParseMode; // Struktur file dibaca dalam mode lenient
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+
+/// Tambahkan data validasi jangka panjang (LTV) untuk semua signature di dokumen
+///
+/// Untuk setiap signature, sertifikat yang tertanam (rantai penandatangan dan
+/// sertifikat TSA dari timestamp) dikumpulkan, lalu respons OCSP / CRL-nya
+/// diambil dan disimpan di /DSS beserta entry /VRI per signature. Semua
+/// perubahan ditulis sebagai satu incremental update, sehingga signature yang
+/// ada tetap valid.
+///
+/// Parameter:
+///   - input: path file PDF yang sudah ditandatangani
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - cancel: token pembatalan; batas waktunya membatasi request OCSP/CRL
+///
+/// Return: jumlah signature yang diberi data LTV
+pub fn add_ltv(input: &str, output: &str, cancel: &CancellationToken) -> Result<usize> {
+    add_ltv_with(input, output, &LtvPolicy::default(), cancel)
+}
+
+/// Seperti `add_ltv`, dengan sumber OCSP/CRL dan toleransi kegagalan dari
+/// `policy` (--ltv-revocation, --ltv-best-effort)
+pub fn add_ltv_with(input: &str, output: &str, policy: &LtvPolicy, cancel: &CancellationToken) -> Result<usize> {
+    // Output biasanya sama dengan input; proses lain yang mengubahnya menunggu
+    let _lock = FileLock::exclusive(output)?;
+    // Bytes asli dibaca langsung dari file; hanya update yang ditulis ke buffer sementara
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let mut scratch = ScratchSpace::default().create_over(file)?;
+    let original = sign::load_document(&mut scratch, ParseMode::Lenient)?;
+    // DSS tanpa enkripsi di dokumen terenkripsi akan dibaca sebagai data rusak
+    if original.trailer.has(b"Encrypt") {
+        bail!("add-ltv does not support encrypted documents; use `sign --pades-level b-lt --pdf-password` instead");
+    }
+    let mut doc = original.clone();
+
+    let fields = form::signature_fields(&doc);
+    if fields.is_empty() {
+        return Err(Error::NoSignatures(input.to_string()).into());
+    }
+
+    let mut count = 0;
+    for field in fields {
+        let sig = &field.value;
+        // /Contents lengkap dengan padding nol: SHA-1-nya menjadi kunci /VRI
+        let contents = sig
+            .get(b"Contents")
+            .and_then(Object::as_str)
+            .map_err(|_| anyhow!("signature {} has no /Contents", field.name))?
+            .to_vec();
+        let der = &contents[..cms::der_element_len(&contents).ok_or_else(|| anyhow!("signature {} is malformed", field.name))?];
+
+        // Document timestamp berisi TimeStampToken; signature biasa berisi CMS SignedData
+        // (blob lama buatan pdfsign tidak punya rantai sertifikat dan dilewati)
+        let certificates = if sig.get(b"SubFilter").and_then(Object::as_name).ok() == Some(&b"ETSI.RFC3161"[..]) {
+            tsa::token_certificates(der)?
+        } else {
+            let parsed = match cms::parse_signed_data(der) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    crate::warning!("skipping signature {}: {}", field.name, err);
+                    continue;
+                }
+            };
+            let mut certificates = parsed.certificates;
+            if let Some(token) = &parsed.timestamp_token {
+                certificates.extend(tsa::token_certificates(token)?);
+            }
+            certificates
+        };
+        if certificates.is_empty() {
+            crate::warning!("skipping signature {}: no embedded certificates", field.name);
+            continue;
+        }
+
+        let revocation = fetch_revocation_data_with(&certificates, policy, cancel)
+            .with_context(|| format!("cannot add LTV data for signature {}", field.name))?;
+        pades::add_dss(&mut doc, &contents, &certificates, &revocation)?;
+        count += 1;
+    }
+    if count == 0 {
+        bail!("no signature in {} has embedded certificates to validate", input);
+    }
+
+    // Tulis ke buffer sementara dulu, lalu salin ke output secara atomik
+    sign::write_update(&mut scratch, &original, &doc, false, None)?;
+    cancel.check()?;
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+
+    Ok(count)
+}
@@ -0,0 +1,270 @@
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId}; // Struktur PDF
+
+use crate::pdf::form; // Signature yang sudah ada di dokumen
+use crate::pdf::text; // Nama field di /Fields sebagai text string
+
+/// Tingkat izin certification signature (/P pada DocMDP transform)
+///
+/// Perubahan yang diizinkan setelah dokumen disertifikasi; setiap tingkat
+/// mencakup tingkat sebelumnya. Menambah DSS dan document timestamp selalu diizinkan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum CertifyLevel {
+    /// P=1: tidak ada perubahan sama sekali
+    NoChanges,
+    /// P=2: mengisi form, menandatangani signature field yang ada, mengganti template halaman
+    FormFilling,
+    /// P=3: seperti form-filling, ditambah membuat, mengubah, dan menghapus annotation
+    Annotations,
+}
+
+impl CertifyLevel {
+    /// Nilai /P di TransformParams
+    pub fn permissions(self) -> i64 {
+        match self {
+            CertifyLevel::NoChanges => 1,
+            CertifyLevel::FormFilling => 2,
+            CertifyLevel::Annotations => 3,
+        }
+    }
+
+    /// Nama tingkat seperti di command line
+    pub fn label(self) -> &'static str {
+        match self {
+            CertifyLevel::NoChanges => "no-changes",
+            CertifyLevel::FormFilling => "form-filling",
+            CertifyLevel::Annotations => "annotations",
+        }
+    }
+}
+
+/// Nilai /P certification signature dokumen (dari /Perms /DocMDP di catalog)
+///
+/// Return: None jika dokumen tidak disertifikasi; /P yang tidak ada dianggap 2
+pub fn certification_permissions(doc: &Document, root_id: ObjectId) -> Option<i64> {
+    let perms = doc.get_dictionary(root_id).ok()?.get(b"Perms").ok()?;
+    let (_, perms) = doc.dereference(perms).ok()?;
+    let (_, sig) = doc.dereference(perms.as_dict().ok()?.get(b"DocMDP").ok()?).ok()?;
+    let references = sig.as_dict().ok()?.get(b"Reference").and_then(Object::as_array).ok();
+    let permissions = references
+        .into_iter()
+        .flatten()
+        .filter_map(|reference| doc.dereference(reference).ok()?.1.as_dict().ok())
+        .filter(|reference| reference.get(b"TransformMethod").and_then(Object::as_name).ok() == Some(&b"DocMDP"[..]))
+        .find_map(|reference| {
+            let (_, params) = doc.dereference(reference.get(b"TransformParams").ok()?).ok()?;
+            params.as_dict().ok()?.get(b"P").and_then(Object::as_i64).ok()
+        });
+    Some(permissions.unwrap_or(2))
+}
+
+/// Pastikan dokumen masih bisa disertifikasi: certification signature harus
+/// menjadi signature pertama, dan dokumen hanya boleh punya satu
+pub fn check_certifiable(doc: &Document, root_id: ObjectId) -> Result<()> {
+    if certification_permissions(doc, root_id).is_some() {
+        bail!("the document is already certified; it can only be signed with an approval signature");
+    }
+    let signed = form::signature_fields(doc).len();
+    if signed > 0 {
+        bail!("a certification signature must be the first signature, but the document already has {} signature(s)", signed);
+    }
+    Ok(())
+}
+
+/// Field yang dikunci oleh signature (FieldMDP, --lock-fields)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldLock {
+    /// Semua field di dokumen
+    All,
+    /// Hanya field dengan nama lengkap ini
+    Include(Vec<String>),
+    /// Semua field kecuali field dengan nama lengkap ini
+    Exclude(Vec<String>),
+}
+
+impl FieldLock {
+    /// Nilai /Action di /Lock dan TransformParams
+    fn action(&self) -> &'static [u8] {
+        match self {
+            FieldLock::All => b"All",
+            FieldLock::Include(_) => b"Include",
+            FieldLock::Exclude(_) => b"Exclude",
+        }
+    }
+
+    /// Nama field yang disebutkan (kosong untuk `All`)
+    fn names(&self) -> &[String] {
+        match self {
+            FieldLock::All => &[],
+            FieldLock::Include(names) | FieldLock::Exclude(names) => names,
+        }
+    }
+
+    /// Apakah field dengan nama lengkap ini ikut dikunci
+    pub fn locks(&self, name: &str) -> bool {
+        match self {
+            FieldLock::All => true,
+            FieldLock::Include(names) => names.iter().any(|n| n == name),
+            FieldLock::Exclude(names) => !names.iter().any(|n| n == name),
+        }
+    }
+
+    /// Isi dictionary /Lock (SigFieldLock) atau TransformParams FieldMDP: /Action dan /Fields
+    fn fill(&self, dict: &mut Dictionary) {
+        dict.set("Action", Object::Name(self.action().to_vec()));
+        if !self.names().is_empty() {
+            let fields = self.names().iter().map(|name| text::text_string(name)).collect();
+            dict.set("Fields", Object::Array(fields));
+        }
+    }
+
+    /// Dictionary /Lock untuk signature field
+    pub fn lock_dictionary(&self) -> Dictionary {
+        let mut lock = Dictionary::new();
+        lock.set("Type", Object::Name(b"SigFieldLock".to_vec()));
+        self.fill(&mut lock);
+        lock
+    }
+}
+
+/// Tulis kembali dalam format --lock-fields
+impl std::fmt::Display for FieldLock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldLock::All => write!(f, "all"),
+            FieldLock::Include(names) => write!(f, "include:{}", names.join(",")),
+            FieldLock::Exclude(names) => write!(f, "exclude:{}", names.join(",")),
+        }
+    }
+}
+
+/// Parse nilai --lock-fields: "all", "include:f1,f2", atau "exclude:f3"
+impl std::str::FromStr for FieldLock {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<FieldLock, String> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("all") {
+            return Ok(FieldLock::All);
+        }
+        let (action, list) = text
+            .split_once(':')
+            .ok_or_else(|| format!("expected all, include:<fields> or exclude:<fields>, got `{}`", text))?;
+        let names: Vec<String> = list.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect();
+        if names.is_empty() {
+            return Err(format!("`{}` needs at least one field name", action));
+        }
+        match action.trim().to_ascii_lowercase().as_str() {
+            "include" => Ok(FieldLock::Include(names)),
+            "exclude" => Ok(FieldLock::Exclude(names)),
+            _ => Err(format!("expected all, include:<fields> or exclude:<fields>, got `{}`", text)),
+        }
+    }
+}
+
+/// Signature reference dictionary dengan DocMDP transform, untuk /Reference
+/// di signature dictionary
+pub fn docmdp_reference(level: CertifyLevel) -> Object {
+    let mut params = Dictionary::new();
+    params.set("Type", Object::Name(b"TransformParams".to_vec()));
+    params.set("P", Object::Integer(level.permissions()));
+    params.set("V", Object::Name(b"1.2".to_vec()));
+    signature_reference(b"DocMDP", params)
+}
+
+/// Signature reference dictionary dengan FieldMDP transform, untuk /Reference
+/// di signature dictionary
+pub fn fieldmdp_reference(lock: &FieldLock) -> Object {
+    let mut params = Dictionary::new();
+    params.set("Type", Object::Name(b"TransformParams".to_vec()));
+    lock.fill(&mut params);
+    params.set("V", Object::Name(b"1.2".to_vec()));
+    signature_reference(b"FieldMDP", params)
+}
+
+/// Signature reference dictionary (/Type /SigRef) dengan transform tertentu
+fn signature_reference(method: &[u8], params: Dictionary) -> Object {
+    let mut reference = Dictionary::new();
+    reference.set("Type", Object::Name(b"SigRef".to_vec()));
+    reference.set("TransformMethod", Object::Name(method.to_vec()));
+    reference.set("TransformParams", Object::Dictionary(params));
+    Object::Dictionary(reference)
+}
+
+/// Jadikan field yang dikunci read-only (bit 1 di /Ff)
+///
+/// Parameter:
+///   - lock: field yang dikunci
+///   - signature_field: field signature yang sedang diisi (tidak ikut dikunci)
+///
+/// Return: nama field yang dikunci
+pub fn lock_fields(doc: &mut Document, lock: &FieldLock, signature_field: ObjectId) -> Result<Vec<String>> {
+    let fields = form::terminal_fields(doc);
+    // Nama yang disebutkan harus ada di dokumen, agar salah ketik tidak diam-diam
+    // membiarkan field tetap bisa diubah
+    let unknown: Vec<&str> = lock
+        .names()
+        .iter()
+        .filter(|name| !fields.iter().any(|(full_name, _, _)| full_name == *name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        bail!("cannot lock unknown field(s): {}", unknown.join(", "));
+    }
+
+    let mut locked = Vec::new();
+    for (name, field_id, _) in fields {
+        if field_id == signature_field || !lock.locks(&name) {
+            continue;
+        }
+        // /Ff bisa diwarisi dari parent; flag lain dipertahankan
+        let flags = inherited_flags(doc, field_id);
+        if let Ok(Object::Dictionary(ref mut field)) = doc.get_object_mut(field_id) {
+            field.set("Ff", Object::Integer(flags | 1));
+        }
+        locked.push(name);
+    }
+    Ok(locked)
+}
+
+/// Nilai /Ff field, termasuk yang diwarisi dari parent
+fn inherited_flags(doc: &Document, field_id: ObjectId) -> i64 {
+    let mut current = field_id;
+    // Batasi kedalaman untuk menghindari loop /Parent pada dokumen yang rusak
+    for _ in 0..32 {
+        let Ok(field) = doc.get_dictionary(current) else {
+            break;
+        };
+        if let Ok(flags) = field.get(b"Ff").and_then(Object::as_i64) {
+            return flags;
+        }
+        match field.get(b"Parent").and_then(Object::as_reference) {
+            Ok(parent) => current = parent,
+            Err(_) => break,
+        }
+    }
+    0
+}
+
+/// Daftarkan signature sebagai certification signature: /Perms /DocMDP di
+/// catalog menunjuk ke signature dictionary
+///
+/// /Perms yang sudah ada (misalnya /UR3 dari Reader Extensions) dipertahankan.
+pub fn register(doc: &mut Document, root_id: ObjectId, sig_id: ObjectId) -> Result<()> {
+    let existing = doc.get_dictionary(root_id)?.get(b"Perms").ok().cloned();
+    let mut perms = existing
+        .as_ref()
+        .and_then(|perms| doc.dereference(perms).ok())
+        .and_then(|(_, perms)| perms.as_dict().ok())
+        .cloned()
+        .unwrap_or_default();
+    perms.set("DocMDP", Object::Reference(sig_id));
+    // /Perms indirect ditulis kembali ke object yang sama
+    match existing {
+        Some(Object::Reference(perms_id)) => doc.set_object(perms_id, Object::Dictionary(perms)),
+        _ => doc.get_dictionary_mut(root_id)?.set("Perms", Object::Dictionary(perms)),
+    }
+    Ok(())
+}
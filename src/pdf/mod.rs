@@ -1,2 +1,117 @@
 // Module untuk PDF signing operations
-pub mod sign;
\ No newline at end of file
+#[cfg(feature = "sign")]
+pub mod sign;
+// Module untuk penanganan AcroForm (form fields) yang sudah ada di dokumen
+pub mod form;
+// Module untuk opsi penandatanganan (builder SignatureOptions)
+#[cfg(feature = "sign")]
+pub mod options;
+// Module untuk builder PdfSigner (API library: reader -> writer)
+#[cfg(feature = "sign")]
+pub mod signer;
+// Module untuk signing dua tahap dengan kunci di luar pdfsign (prepare / embed)
+#[cfg(feature = "sign")]
+pub mod external;
+// Module untuk hook di sekitar pipeline signing
+#[cfg(feature = "sign")]
+pub mod hooks;
+// Module untuk hook klasifikasi dokumen sebelum signing (--classify-hook)
+#[cfg(feature = "sign")]
+pub mod classify;
+// Module untuk level PAdES baseline (DSS, document timestamp)
+#[cfg(feature = "sign")]
+pub mod pades;
+// Module untuk certification signature (DocMDP, --certify)
+#[cfg(feature = "sign")]
+pub mod mdp;
+// Module untuk signature CMS terpisah atas file lampiran (--sign-attachments)
+#[cfg(feature = "sign")]
+pub mod attachment_signatures;
+// Module untuk memperkecil dokumen yang belum ditandatangani (optimize)
+#[cfg(feature = "sign")]
+pub mod optimize;
+// Module untuk menghapus signature (remove-signature)
+#[cfg(feature = "sign")]
+pub mod remove;
+// Module untuk seed value dictionary (/SV) pada signature field kosong
+#[cfg(feature = "sign")]
+pub mod seed;
+// Module untuk menambahkan signature field kosong (prepare-field)
+#[cfg(feature = "sign")]
+pub mod field;
+// Module untuk signature CMS detached di file .p7s terpisah (sign --detached)
+#[cfg(feature = "sign")]
+pub mod detached;
+// Module untuk data Long-Term Validation (add-ltv)
+#[cfg(feature = "network")]
+pub mod ltv;
+// Module untuk document timestamp tanpa signature (pdfsign timestamp)
+#[cfg(feature = "network")]
+pub mod timestamp;
+// Module untuk dokumen terenkripsi (security handler standar, --pdf-password)
+#[cfg(feature = "sign")]
+pub mod encryption;
+// Module untuk serialisasi incremental update (biasa atau dengan object stream)
+#[cfg(feature = "sign")]
+pub mod update;
+
+#[cfg(feature = "sign")]
+pub mod budget;
+// Module untuk verifikasi signature di dalam PDF
+#[cfg(feature = "verify")]
+pub mod verify;
+// Module untuk pemeriksaan tambahan milik embedder saat verifikasi (VerificationCheck)
+#[cfg(feature = "verify")]
+pub mod checks;
+// Module untuk pemeriksaan integritas file lampiran (embedded file, /CheckSum)
+#[cfg(feature = "verify")]
+pub mod attachments;
+// Module untuk verifikasi seluruh arsip dokumen (verify --recursive)
+#[cfg(all(feature = "sign", feature = "verify"))]
+pub mod archive;
+// Module untuk audit log hasil verifikasi (verify --audit-log)
+#[cfg(all(feature = "sign", feature = "verify"))]
+pub mod audit;
+// Module untuk daftar signature dan metadata dokumen (inspect)
+#[cfg(all(feature = "sign", feature = "verify"))]
+pub mod inspect;
+// Module untuk membuat ulang tampilan signature yang sudah ada (refresh-appearance)
+#[cfg(all(feature = "appearance", feature = "verify"))]
+pub mod refresh;
+// Module untuk menandatangani banyak file secara paralel (sign-batch)
+#[cfg(feature = "sign")]
+pub mod batch;
+// Module untuk self-test dan keep-alive token sebelum dan selama batch (--keep-alive)
+#[cfg(feature = "sign")]
+pub mod health;
+// Module untuk menandatangani satu dokumen dengan beberapa identitas (sign-fanout)
+#[cfg(feature = "sign")]
+pub mod fanout;
+// Module untuk memantau folder dan menandatangani file yang masuk (watch)
+#[cfg(feature = "sign")]
+pub mod watch;
+// Module untuk deteksi dan pemeliharaan kesesuaian PDF/A (--require-pdfa)
+#[cfg(feature = "sign")]
+pub mod pdfa;
+// Module untuk aturan posisi signature berdasarkan ciri dokumen (placement_rules)
+#[cfg(feature = "sign")]
+pub mod placement;
+// Module untuk dokumen PDF sintetis (gen-testdoc)
+#[cfg(feature = "sign")]
+pub mod testdoc;
+// Module untuk koordinat halaman: kotak, rotasi, dan annotation (pages info)
+pub mod pages;
+// Module untuk pemeriksaan struktur file (nomor object ganda, /Length stream, --strict-parse)
+pub mod syntax;
+// Module untuk mode deterministik (nama field, waktu, dan bilangan acak dari seed)
+pub mod naming;
+// Module untuk encoding text string PDF (PDFDocEncoding, UTF-16BE, WinAnsi)
+pub mod text;
+// Module untuk teks tampilan (appearance) signature
+pub mod appearance;
+// Module untuk gambar (logo) di tampilan signature
+#[cfg(feature = "appearance")]
+pub mod image;
+// Module untuk font TrueType yang disematkan di tampilan signature (--appearance-font)
+#[cfg(feature = "appearance")]
+pub mod font;
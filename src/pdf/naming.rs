@@ -0,0 +1,95 @@
+// Mode deterministik untuk snapshot test: nama yang dibuat pdfsign (nama
+// signature field baru), waktu signing default, dan /ID dokumen uji diturunkan
+// dari satu seed, sehingga PDF yang dihasilkan sama persis di setiap run
+use sha2::{Digest, Sha256}; // Label dari seed
+use std::sync::RwLock; // Seed berlaku untuk seluruh proses
+
+#[cfg(feature = "sign")]
+use crate::crypto::provider; // /ID acak di luar mode deterministik
+
+/// Seed yang sedang terpasang; None = mode normal
+static SEED: RwLock<Option<Vec<u8>>> = RwLock::new(None);
+
+/// Waktu signing default dalam mode deterministik (RFC 3339)
+#[cfg(feature = "sign")]
+pub const DETERMINISTIC_TIME: &str = "2000-01-01T00:00:00+00:00";
+
+/// Jumlah hex label yang ditambahkan ke nama field
+const LABEL_LEN: usize = 8;
+
+/// Aktifkan mode deterministik untuk sisa proses ini (satu sesi CLI atau satu
+/// test)
+///
+/// Yang diturunkan dari `seed`:
+/// - nama signature field baru: `Signature_3fa2c91b` alih-alih `Signature1`
+///   (`sign`, `prepare-field`), tetap unik terhadap field yang sudah ada
+/// - waktu signing jika tidak ditentukan (`DETERMINISTIC_TIME`), sehingga /M,
+///   teks tampilan, dan atribut signingTime tetap; sertifikat `generate-cert`
+///   juga berlaku mulai waktu ini agar signature uji tetap valid
+/// - /ID dokumen `gen-testdoc` (`file_id`)
+///
+/// Sumber acak proses tidak diganti: kunci baru, salt dan IV kunci
+/// terenkripsi, serial sertifikat, dan kunci serta IV enkripsi PDF tetap dari
+/// generator acak OS, sehingga tidak bisa ditebak dari seed. Dokumen terenkripsi
+/// dan signature RSA-PSS karena itu tidak deterministik. Nomor object baru
+/// sudah deterministik (berurutan setelah nomor tertinggi dokumen, ditulis urut
+/// nomor) dan nama resource tampilan tetap (/F1, /Im1). Timestamp TSA dan data
+/// OCSP/CRL berasal dari server sehingga tidak ikut deterministik.
+pub fn set_deterministic(seed: &[u8]) {
+    *SEED.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(seed.to_vec());
+}
+
+/// Kembali ke mode normal (nama berurutan, waktu sekarang, /ID acak)
+pub fn reset() {
+    *SEED.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Apakah mode deterministik aktif
+pub fn is_deterministic() -> bool {
+    SEED.read().unwrap_or_else(|poisoned| poisoned.into_inner()).is_some()
+}
+
+/// Nama kandidat ke-`index` (mulai dari 1) untuk field baru berawalan
+/// `prefix`: `Signature1`, atau `Signature_<label>` dalam mode deterministik
+pub fn field_name(prefix: &str, index: u32) -> String {
+    match SEED.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_deref() {
+        Some(seed) => {
+            let mut input = prefix.as_bytes().to_vec();
+            input.extend_from_slice(&index.to_be_bytes());
+            let label: String = derive(seed, &input).iter().map(|byte| format!("{:02x}", byte)).collect();
+            format!("{}_{}", prefix, &label[..LABEL_LEN])
+        }
+        None => format!("{}{}", prefix, index),
+    }
+}
+
+/// Waktu signing jika tidak ditentukan: sekarang, atau `DETERMINISTIC_TIME`
+/// dalam mode deterministik
+#[cfg(feature = "sign")]
+pub fn now() -> chrono::DateTime<chrono::FixedOffset> {
+    match is_deterministic() {
+        true => chrono::DateTime::parse_from_rfc3339(DETERMINISTIC_TIME).expect("DETERMINISTIC_TIME is RFC 3339"),
+        false => chrono::Local::now().fixed_offset(),
+    }
+}
+
+/// /ID dokumen baru (16 byte): acak, atau diturunkan dari seed dalam mode
+/// deterministik
+///
+/// /ID hanya penanda file dan bukan rahasia, jadi boleh bisa ditebak.
+#[cfg(feature = "sign")]
+pub fn file_id() -> Vec<u8> {
+    match SEED.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_deref() {
+        Some(seed) => derive(seed, b"file-id")[..16].to_vec(),
+        None => {
+            let mut id = vec![0u8; 16];
+            provider::fill_random(&mut id);
+            id
+        }
+    }
+}
+
+/// SHA-256(seed || 0x00 || purpose): nilai terpisah per keperluan dari satu seed
+fn derive(seed: &[u8], purpose: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update(seed).chain_update([0]).chain_update(purpose).finalize().into()
+}
@@ -0,0 +1,233 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::xref::XrefType; // Cross-reference stream untuk object stream
+use lopdf::{Document, Object, ObjectId, Stream}; // Struktur PDF
+use std::collections::BTreeSet; // Object struktur file (object stream, xref stream)
+use std::io::Write; // Menulis hasil ke buffer sementara
+
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::form; // Signature field yang sudah ditandatangani
+use crate::pdf::pdfa; // PDF/A-1 tidak mengizinkan object stream
+use crate::pdf::syntax::{self, ParseMode}; // Struktur file dibaca dalam mode lenient
+use crate::pdf::update; // Penulisan object dan cross-reference
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+
+/// Versi PDF minimum untuk object stream
+const OBJECT_STREAM_VERSION: &str = "1.5";
+
+/// Yang dilakukan `optimize` pada dokumen yang sudah ditandatangani
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum IfSigned {
+    /// Tolak dengan error (default)
+    #[default]
+    Refuse,
+    /// Salin dokumen apa adanya ke output
+    Copy,
+}
+
+/// Opsi `optimize`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptimizeOptions {
+    pub recompress: bool,     // Kompres ulang stream Flate dan kompres stream tanpa filter
+    pub object_streams: bool, // Simpan object yang bukan stream di object stream
+    pub if_signed: IfSigned,  // Dokumen bertanda tangan: tolak atau salin apa adanya
+}
+
+impl Default for OptimizeOptions {
+    fn default() -> OptimizeOptions {
+        OptimizeOptions { recompress: true, object_streams: false, if_signed: IfSigned::Refuse }
+    }
+}
+
+/// Hasil akhir `optimize`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Dokumen ditulis ulang dan menjadi lebih kecil
+    Optimized,
+    /// Dokumen yang ditulis ulang tidak lebih kecil; bytes asli disalin
+    NotSmaller,
+    /// Dokumen bertanda tangan disalin apa adanya (`IfSigned::Copy`); berisi
+    /// signature yang ditemukan
+    Signed(Vec<String>),
+}
+
+/// Hasil `optimize`
+pub struct OptimizeSummary {
+    pub outcome: Outcome,    // Apa yang ditulis ke output
+    pub before: u64,         // Ukuran file input
+    pub after: u64,          // Ukuran file output
+    pub removed: usize,      // Object yang tidak terpakai dan dibuang
+    pub recompressed: usize, // Stream yang menjadi lebih kecil
+}
+
+/// Perkecil dokumen yang belum ditandatangani: buang object yang tidak lagi
+/// dipakai (revisi lama, resource yang sudah dilepas), kompres ulang stream,
+/// dan opsional simpan object di object stream
+///
+/// Dokumen ditulis ulang seluruhnya sebagai satu revisi, sehingga setiap
+/// signature di dalamnya tidak lagi cocok dengan bytes file. Karena itu
+/// dokumen dengan signature (signature field yang terisi, signature dari
+/// revisi lama, atau usage rights /Perms /UR3) tidak pernah ditulis ulang:
+/// `IfSigned::Refuse` menolaknya, `IfSigned::Copy` menyalinnya apa adanya.
+/// Tidak ada incremental update yang bisa memperkecil file, jadi menyalin
+/// adalah satu-satunya perubahan yang aman untuk signature.
+///
+/// Yang dikompres ulang hanya stream tanpa filter atau dengan /FlateDecode
+/// saja, dan hanya jika hasilnya lebih kecil; gambar JPEG/JBIG2/JPX dan XMP
+/// metadata tidak disentuh. Jika dokumen hasil tidak lebih kecil, bytes asli
+/// yang disalin.
+///
+/// Parameter:
+///   - input: path file PDF
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - options: kompresi, object stream, dan perlakuan dokumen bertanda tangan
+pub fn optimize(input: &str, output: &str, options: &OptimizeOptions) -> Result<OptimizeSummary> {
+    // Output biasanya sama dengan input; proses lain yang mengubahnya menunggu
+    let _lock = FileLock::exclusive(output)?;
+    // Seluruh isi stream dimuat karena semua object ditulis ulang
+    let bytes = std::fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let before = bytes.len() as u64;
+    let mut doc = Document::load_mem(&bytes).with_context(|| format!("cannot parse {}", input))?;
+    syntax::warn(&syntax::check_document(&bytes, &mut doc, ParseMode::Lenient)?);
+
+    let signatures = signatures(&doc);
+    if !signatures.is_empty() {
+        if options.if_signed == IfSigned::Refuse {
+            bail!(
+                "{} is signed ({}); optimizing rewrites the whole file and would break the signatures (use --if-signed copy to pass signed documents through unchanged)",
+                input,
+                signatures.join(", ")
+            );
+        }
+        store(bytes, output)?;
+        return Ok(OptimizeSummary { outcome: Outcome::Signed(signatures), before, after: before, removed: 0, recompressed: 0 });
+    }
+    if doc.trailer.has(b"Encrypt") {
+        bail!("{} is encrypted; optimize cannot rewrite encrypted documents", input);
+    }
+    if options.object_streams && pdfa::detect(&doc).is_some_and(|conformance| conformance.part == 1) {
+        bail!("{} is PDF/A-1, which does not allow object streams (--object-streams)", input);
+    }
+
+    // Object stream dan cross-reference stream lama tidak terhubung dari trailer;
+    // yang dihitung sebagai dibuang hanya object isi dokumen
+    let structure: BTreeSet<ObjectId> = doc.objects.iter().filter(|(_, object)| is_structure(object)).map(|(&id, _)| id).collect();
+    for key in [&b"Prev"[..], b"XRefStm", b"DecodeParms"] {
+        doc.trailer.remove(key);
+    }
+    let mut removed = doc.prune_objects().iter().filter(|id| !structure.contains(id)).count();
+
+    let mut recompressed = 0;
+    if options.recompress {
+        for object in doc.objects.values_mut() {
+            if let Object::Stream(ref mut stream) = object {
+                if recompress(stream)? {
+                    recompressed += 1;
+                }
+            }
+        }
+        // Stream yang dikompres ulang mendapat /Length langsung; object /Length lamanya dibuang
+        removed += doc.prune_objects().len();
+    }
+    doc.max_id = doc.objects.keys().map(|&(id, _)| id).max().unwrap_or(0);
+
+    // Tulis seluruh dokumen seperti gen-testdoc: header, lalu semua object dan
+    // xref; dengan object stream xref ditulis sebagai cross-reference stream
+    let mut version = doc.version.clone();
+    if options.object_streams && version.parse::<f32>().map_or(true, |number| number < 1.5) {
+        version = OBJECT_STREAM_VERSION.to_string();
+    }
+    let mut rewritten = format!("%PDF-{}\n%", version).into_bytes();
+    rewritten.extend_from_slice(&[0xE2, 0xE3, 0xCF, 0xD3, b'\n']);
+    let mut prev = Document::new();
+    if options.object_streams || matches!(doc.reference_table.cross_reference_type, XrefType::CrossReferenceStream) {
+        prev.reference_table.cross_reference_type = XrefType::CrossReferenceStream;
+    }
+    let base = rewritten.len() as u64;
+    update::save_to(&prev, &doc, base, options.object_streams, &mut rewritten)?;
+
+    if rewritten.len() as u64 >= before {
+        store(bytes, output)?;
+        return Ok(OptimizeSummary { outcome: Outcome::NotSmaller, before, after: before, removed, recompressed });
+    }
+    let after = rewritten.len() as u64;
+    store(rewritten, output)?;
+    Ok(OptimizeSummary { outcome: Outcome::Optimized, before, after, removed, recompressed })
+}
+
+/// Signature yang akan rusak jika dokumen ditulis ulang: nama signature
+/// field yang terisi, signature dictionary dari revisi lama yang tidak lagi
+/// terhubung ke field, dan usage rights di /Perms
+pub fn signatures(doc: &Document) -> Vec<String> {
+    let fields = form::signature_fields(doc);
+    let dictionaries = doc
+        .objects
+        .values()
+        .filter(|object| object.as_dict().is_ok_and(|dict| dict.has(b"ByteRange") && dict.has(b"Contents")))
+        .count();
+    let mut result: Vec<String> = fields.iter().map(|field| field.name.clone()).collect();
+    if dictionaries > fields.len() {
+        result.push(format!("{} signature(s) from earlier revisions", dictionaries - fields.len()));
+    }
+    let perms = doc.catalog().ok().and_then(|catalog| catalog.get(b"Perms").ok()).and_then(|perms| doc.dereference(perms).ok()).and_then(|(_, perms)| perms.as_dict().ok());
+    if let Some(perms) = perms {
+        for key in [&b"UR3"[..], b"UR"] {
+            if perms.has(key) {
+                result.push(format!("usage rights signature (/Perms /{})", String::from_utf8_lossy(key)));
+            }
+        }
+    }
+    result
+}
+
+/// Simpan bytes ke output lewat buffer sementara (atomic, sama seperti signing)
+fn store(bytes: Vec<u8>, output: &str) -> Result<()> {
+    let mut scratch = ScratchSpace::default().create_from(bytes)?;
+    scratch.flush()?;
+    scratch.persist_to(output)
+}
+
+/// Object stream dan cross-reference stream (struktur file, bukan isi dokumen)
+fn is_structure(object: &Object) -> bool {
+    let Object::Stream(stream) = object else {
+        return false;
+    };
+    matches!(stream.dict.get(b"Type").and_then(Object::as_name), Ok(b"ObjStm" | b"XRef"))
+}
+
+/// Kompres ulang satu stream dengan Flate tingkat terbaik
+///
+/// Return: true jika stream diganti dengan versi yang lebih kecil
+fn recompress(stream: &mut Stream) -> Result<bool> {
+    // XMP metadata dibiarkan apa adanya agar tetap terbaca oleh tool non-PDF
+    // (dan PDF/A); stream dengan data di file eksternal (/F) tidak punya isi
+    let kind = stream.dict.get(b"Type").and_then(Object::as_name).ok();
+    if matches!(kind, Some(b"Metadata" | b"XRef" | b"ObjStm")) || stream.dict.has(b"F") {
+        return Ok(false);
+    }
+    let decoded = match stream.filters().ok().as_deref() {
+        None | Some([]) => stream.content.clone(),
+        Some([filter]) if filter == "FlateDecode" => {
+            // lopdf menolak mendekode gambar; tanpa /Subtype isi Flate-nya tetap bisa dibaca
+            let mut plain = stream.clone();
+            plain.dict.remove(b"Subtype");
+            match plain.decompressed_content() {
+                Ok(decoded) => decoded,
+                Err(_) => return Ok(false),
+            }
+        }
+        _ => return Ok(false),
+    };
+
+    let mut dict = stream.dict.clone();
+    dict.remove(b"Filter");
+    dict.remove(b"DecodeParms");
+    let mut candidate = Stream::new(dict, decoded);
+    candidate.compress()?;
+    if candidate.content.len() >= stream.content.len() {
+        return Ok(false);
+    }
+    *stream = candidate;
+    Ok(true)
+}
@@ -0,0 +1,914 @@
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+use chrono::{DateTime, FixedOffset}; // Waktu penandatanganan (--signing-time)
+
+use crate::crypto::cms::EmbedChain; // Sertifikat yang disisipkan di CMS
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::kms::KmsKey; // Kunci di KMS cloud
+use crate::crypto::pkcs11::Pkcs11Token; // Kunci di token PKCS#11
+use crate::crypto::policy::AlgorithmPolicy; // Deny-list algoritma lemah
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::tsa::Tsa; // Time Stamping Authority
+use crate::pdf::budget::UpdateBudget; // Peringatan ukuran incremental update
+use crate::pdf::appearance::{validate_template, AppearanceCache, AppearanceLanguage, TextColor}; // Teks dan cache tampilan
+use crate::pdf::form::NeedAppearancesPolicy; // Kebijakan /NeedAppearances
+use crate::pdf::mdp::{CertifyLevel, FieldLock}; // Certification (DocMDP) dan penguncian field (FieldMDP)
+use crate::pdf::pades::{LtvPolicy, PadesLevel}; // Level PAdES baseline dan kebijakan data validasi
+use crate::pdf::placement::PlacementRule; // Posisi berdasarkan ciri dokumen
+use crate::pdf::syntax::ParseMode; // Penanganan pelanggaran struktur file
+use crate::pdf::text; // Normalisasi teks penandatangan
+use crate::scratch::ScratchSpace; // Penyimpanan data sementara
+
+/// Persegi panjang dalam koordinat halaman PDF (satuan point, 1/72 inch)
+/// Format sama dengan /Rect: [left, bottom, right, top]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub left: f32,   // Sisi kiri
+    pub bottom: f32, // Sisi bawah
+    pub right: f32,  // Sisi kanan
+    pub top: f32,    // Sisi atas
+}
+
+impl Rect {
+    /// Buat Rect dari posisi kiri-bawah beserta lebar dan tinggi
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect { left: x, bottom: y, right: x + width, top: y + height }
+    }
+
+    /// Lebar rect
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    /// Tinggi rect
+    pub fn height(&self) -> f32 {
+        self.top - self.bottom
+    }
+}
+
+/// Parse rect dari teks "x,y,w,h" (kiri, bawah, lebar, tinggi dalam point),
+/// misalnya nilai --rect di command line
+impl std::str::FromStr for Rect {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<Rect, String> {
+        let numbers = text
+            .split(',')
+            .map(|part| part.trim().parse::<f32>())
+            .collect::<std::result::Result<Vec<f32>, _>>()
+            .map_err(|_| format!("expected x,y,w,h in points, got `{}`", text))?;
+        match numbers[..] {
+            [x, y, width, height] if numbers.iter().all(|n| n.is_finite()) => Ok(Rect::new(x, y, width, height)),
+            _ => Err(format!("expected four numbers x,y,w,h in points, got `{}`", text)),
+        }
+    }
+}
+
+/// Posisi siap pakai untuk widget signature, relatif terhadap tepi halaman
+/// (CropBox, atau MediaBox jika halaman tidak punya CropBox)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum Position {
+    /// Pojok kiri atas
+    TopLeft,
+    /// Tengah atas
+    TopCenter,
+    /// Pojok kanan atas
+    TopRight,
+    /// Tengah halaman
+    Center,
+    /// Pojok kiri bawah
+    BottomLeft,
+    /// Tengah bawah
+    BottomCenter,
+    /// Pojok kanan bawah
+    BottomRight,
+}
+
+impl Position {
+    /// Ukuran widget untuk posisi siap pakai (point)
+    pub const SIZE: (f32, f32) = (200.0, 50.0);
+    /// Jarak widget dari tepi halaman (point, 1/2 inch)
+    pub const MARGIN: f32 = 36.0;
+
+    /// Hitung rect widget di dalam kotak halaman
+    ///
+    /// Parameter:
+    ///   - page_box: CropBox/MediaBox halaman
+    ///
+    /// Return: rect berukuran `SIZE` yang berjarak `MARGIN` dari tepi halaman
+    pub fn rect_in(self, page_box: &Rect) -> Rect {
+        let (width, height) = Self::SIZE;
+        let left = page_box.left + Self::MARGIN;
+        let right = page_box.right - Self::MARGIN - width;
+        let center_x = page_box.left + (page_box.width() - width) / 2.0;
+        let bottom = page_box.bottom + Self::MARGIN;
+        let top = page_box.top - Self::MARGIN - height;
+        let center_y = page_box.bottom + (page_box.height() - height) / 2.0;
+        let (x, y) = match self {
+            Position::TopLeft => (left, top),
+            Position::TopCenter => (center_x, top),
+            Position::TopRight => (right, top),
+            Position::Center => (center_x, center_y),
+            Position::BottomLeft => (left, bottom),
+            Position::BottomCenter => (center_x, bottom),
+            Position::BottomRight => (right, bottom),
+        };
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Posisi widget default (halaman 1 jika halamannya tidak ditentukan): [100 650 300 700]
+pub const DEFAULT_RECT: Rect = Rect { left: 100.0, bottom: 650.0, right: 300.0, top: 700.0 };
+
+/// Jarak vertikal antara kotak default dan signature yang sudah ada di atasnya
+pub const DEFAULT_RECT_GAP: f32 = 10.0;
+
+/// Lokasi tanda tangan yang terlihat (visible signature)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    /// Nomor halaman (mulai dari 1) dan posisi widget di halaman tersebut
+    Page(u32, Rect),
+    /// Nomor halaman dan posisi siap pakai; rect dihitung dari ukuran halaman
+    /// saat dokumen ditandatangani
+    Anchored(u32, Position),
+    /// Nomor halaman saja: `DEFAULT_RECT`, digeser ke bawah signature yang
+    /// sudah ada di posisi itu agar kotaknya tidak bertumpuk
+    Default(u32),
+}
+
+impl Placement {
+    /// Nomor halaman tempat widget ditampilkan (mulai dari 1)
+    pub fn page(&self) -> u32 {
+        match self {
+            Placement::Page(page, _) | Placement::Anchored(page, _) | Placement::Default(page) => *page,
+        }
+    }
+}
+
+/// Flag annotation (/F) yang bisa diatur untuk widget signature dan stamp:
+/// apakah tampilan ikut dicetak, tampil di layar, dan bisa diubah di viewer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnnotationFlags {
+    pub print: bool,           // Print: ikut dicetak
+    pub no_view: bool,         // NoView: tidak ditampilkan di layar (hanya saat dicetak)
+    pub locked: bool,          // Locked: tidak bisa dipindah, diubah ukurannya, atau dihapus
+    pub locked_contents: bool, // LockedContents: isinya tidak bisa diubah
+}
+
+impl AnnotationFlags {
+    /// Bit /F yang diatur oleh AnnotationFlags; bit lain (Hidden, ReadOnly, ...) tidak disentuh
+    pub const MASK: i64 = 4 | 32 | 128 | 512;
+    /// Default widget signature baru: ikut dicetak
+    pub const WIDGET: AnnotationFlags = AnnotationFlags { print: true, no_view: false, locked: false, locked_contents: false };
+    /// Default stamp --stamp-only: ikut dicetak, tidak bisa dipindah atau dihapus
+    pub const STAMP: AnnotationFlags = AnnotationFlags { print: true, no_view: false, locked: true, locked_contents: false };
+
+    /// Nilai bit /F
+    pub fn bits(self) -> i64 {
+        let mut bits = 0;
+        if self.print {
+            bits |= 4;
+        }
+        if self.no_view {
+            bits |= 32;
+        }
+        if self.locked {
+            bits |= 128;
+        }
+        if self.locked_contents {
+            bits |= 512;
+        }
+        bits
+    }
+
+    /// Ganti bit yang diatur di /F yang sudah ada, bit lain dipertahankan
+    pub fn apply(self, current: i64) -> i64 {
+        (current & !Self::MASK) | self.bits()
+    }
+}
+
+/// Tulis kembali dalam format --widget-flags / --stamp-flags
+impl std::fmt::Display for AnnotationFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = [(self.print, "print"), (self.no_view, "no-view"), (self.locked, "locked"), (self.locked_contents, "locked-contents")]
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| name)
+            .collect();
+        if names.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", names.join(","))
+        }
+    }
+}
+
+/// Parse daftar flag dipisah koma: print, no-view, locked, locked-contents,
+/// atau "none" untuk mengosongkan semuanya
+impl std::str::FromStr for AnnotationFlags {
+    type Err = String;
+
+    fn from_str(text: &str) -> std::result::Result<AnnotationFlags, String> {
+        let mut flags = AnnotationFlags::default();
+        if text.trim().eq_ignore_ascii_case("none") {
+            return Ok(flags);
+        }
+        for name in text.split(',').map(str::trim) {
+            match name.to_ascii_lowercase().as_str() {
+                "print" => flags.print = true,
+                "no-view" => flags.no_view = true,
+                "locked" => flags.locked = true,
+                "locked-contents" => flags.locked_contents = true,
+                _ => return Err(format!("expected none or a comma-separated list of print, no-view, locked, locked-contents, got `{}`", name)),
+            }
+        }
+        Ok(flags)
+    }
+}
+
+/// Bundle PKCS#12 (.p12/.pfx) yang berisi kunci privat dan rantai sertifikat
+#[derive(Clone, Debug)]
+pub struct Pkcs12Source {
+    pub path: String,             // Path file PKCS#12
+    pub password: Option<String>, // Password; None = coba kosong lalu tanyakan di terminal
+}
+
+/// Opsi lengkap untuk satu operasi penandatanganan
+///
+/// Dibuat melalui `SignatureOptions::builder()` sehingga kombinasi opsi
+/// selalu divalidasi sebelum dipakai oleh `sign_pdf`.
+#[derive(Clone, Debug)]
+pub struct SignatureOptions {
+    pub name: String,                             // Nama penandatangan
+    pub reason: String,                           // Alasan penandatanganan
+    pub location: String,                         // Lokasi penandatanganan
+    pub contact_info: String,                     // Informasi kontak penandatangan
+    pub placement: Option<Placement>,             // None = invisible signature
+    pub stamp_only: bool,                         // Tampilan di annotation /Stamp, widget tak terlihat
+    pub widget_flags: Option<AnnotationFlags>,    // /F widget; None = Print (field yang sudah ada tidak diubah)
+    pub stamp_flags: Option<AnnotationFlags>,     // /F stamp --stamp-only; None = Print + Locked
+    pub field_name: Option<String>,               // Signature field kosong yang diisi (menggantikan posisi placement)
+    pub placement_rules: Vec<PlacementRule>,      // Aturan posisi per jenis dokumen; yang pertama cocok menggantikan placement
+    pub appearance_languages: Vec<AppearanceLanguage>, // Blok teks tampilan (1 atau 2 bahasa)
+    pub appearance_template: Option<String>,      // Template teks tampilan; None = blok per bahasa
+    pub appearance_font_size: Option<f32>,        // Ukuran font tetap; None = menyesuaikan kotak
+    pub appearance_color: Option<TextColor>,      // Warna teks; None = hitam
+    pub appearance_image: Option<String>,         // Logo PNG/JPEG di sebelah kiri teks tampilan
+    pub appearance_font: Option<String>,          // Font TrueType yang disematkan; None = Helvetica standar
+    pub appearance_cache: AppearanceCache,        // Tampilan terkompilasi, dipakai bersama oleh clone
+    pub need_appearances: NeedAppearancesPolicy,  // Kebijakan /NeedAppearances
+    pub strict: bool,                             // Tolak form dengan perubahan tertunda
+    pub drop_xfa: bool,                           // Hapus data XFA dari form hybrid
+    pub strict_parse: bool,                       // Tolak dokumen yang melanggar spesifikasi PDF
+    pub repair: bool,                             // Bangun ulang dokumen yang cross-reference-nya rusak
+    pub classify_hook: Option<String>,            // Command yang memeriksa dokumen sebelum signing (boleh menolak)
+    pub require_pdfa: bool,                       // Tolak jika kesesuaian PDF/A tidak bisa dipertahankan
+    pub rsa_padding: RsaPadding,                  // Padding jika kunci privat RSA
+    pub digest: DigestAlgorithm,                  // Digest dokumen dan signed attributes
+    pub passphrase: Option<String>,               // Passphrase kunci privat terenkripsi
+    pub pdf_password: Option<String>,             // Password dokumen PDF terenkripsi (user atau owner)
+    pub decrypt_output: bool,                     // Tulis hasil tanpa enkripsi (dokumen terenkripsi tetap terenkripsi jika false)
+    pub pkcs12: Option<Pkcs12Source>,             // Bundle PKCS#12 sebagai pengganti file kunci
+    pub pkcs11: Option<Pkcs11Token>,              // Token PKCS#11 sebagai pengganti file kunci
+    pub kms: Option<KmsKey>,                      // Kunci di KMS cloud sebagai pengganti file kunci
+    pub keystore_cert: Option<String>,            // Sertifikat di keystore OS (thumbprint atau CN) sebagai pengganti file kunci
+    pub allow_test_credentials: bool,             // Izinkan sertifikat yang ditandai kredensial uji (generate-cert)
+    pub embed_chain: EmbedChain,                  // Sertifikat yang disisipkan di signature
+    pub algorithm_policy: AlgorithmPolicy,        // Deny-list algoritma lemah
+    pub timestamp: Option<Tsa>,                   // TSA untuk timestamp signature (RFC 3161)
+    pub pades: Option<PadesLevel>,                // None = signature adbe.pkcs7.detached biasa
+    pub ltv: LtvPolicy,                           // Sumber OCSP/CRL untuk B-LT ke atas
+    pub certify: Option<CertifyLevel>,            // None = approval signature biasa
+    pub lock_fields: Option<FieldLock>,           // Field yang dikunci signature ini (FieldMDP)
+    pub scratch: ScratchSpace,                    // Tempat serialisasi sementara
+    pub object_streams: bool,                     // Object baru di object stream terkompresi
+    pub sign_attachments: bool,                   // Signature CMS terpisah (.p7s) untuk tiap file lampiran
+    pub placeholder_size: Option<usize>,          // Ukuran /Contents signature; None = diperkirakan
+    pub budget: UpdateBudget,                     // Batas pertumbuhan file dan jumlah object (hanya peringatan)
+    pub signing_time: Option<DateTime<FixedOffset>>, // Waktu di /M dan signingTime; None = sekarang
+}
+
+impl SignatureOptions {
+    /// Mulai membuat SignatureOptions dengan nilai default
+    pub fn builder() -> SignatureOptionsBuilder {
+        SignatureOptionsBuilder::default()
+    }
+
+    /// Normalisasi NFC nama, alasan, lokasi, dan kontak penandatangan (lihat
+    /// `text::signer_text`); dipanggil lagi setelah hook klasifikasi mengubahnya
+    pub fn normalize_signer_text(&mut self) -> Result<()> {
+        self.name = text::signer_text("signer name", &self.name)?;
+        self.reason = text::signer_text("signing reason", &self.reason)?;
+        self.location = text::signer_text("signing location", &self.location)?;
+        self.contact_info = text::signer_text("contact info", &self.contact_info)?;
+        Ok(())
+    }
+
+    /// Cara menangani pelanggaran struktur file saat dokumen dimuat
+    pub fn parse_mode(&self) -> ParseMode {
+        if self.strict_parse {
+            ParseMode::Strict
+        } else {
+            ParseMode::Lenient
+        }
+    }
+}
+
+/// Builder untuk SignatureOptions
+///
+/// Contoh:
+/// ```ignore
+/// let options = SignatureOptions::builder()
+///     .name("Jane Doe")
+///     .reason("Contract approval")
+///     .visible(Placement::Page(3, Rect::new(100.0, 100.0, 200.0, 50.0)))
+///     .timestamp(Tsa::url("http://timestamp.example.com"))
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct SignatureOptionsBuilder {
+    name: Option<String>,
+    reason: Option<String>,
+    location: Option<String>,
+    contact_info: Option<String>,
+    placement: Option<Placement>,
+    invisible: bool,
+    stamp_only: bool,
+    widget_flags: Option<AnnotationFlags>,
+    stamp_flags: Option<AnnotationFlags>,
+    field_name: Option<String>,
+    placement_rules: Vec<PlacementRule>,
+    appearance_languages: Option<Vec<AppearanceLanguage>>,
+    appearance_template: Option<String>,
+    appearance_font_size: Option<f32>,
+    appearance_color: Option<TextColor>,
+    appearance_image: Option<String>,
+    appearance_font: Option<String>,
+    appearance_cache: AppearanceCache,
+    need_appearances: Option<NeedAppearancesPolicy>,
+    strict: bool,
+    drop_xfa: bool,
+    strict_parse: bool,
+    repair: bool,
+    classify_hook: Option<String>,
+    require_pdfa: bool,
+    rsa_padding: RsaPadding,
+    digest: DigestAlgorithm,
+    passphrase: Option<String>,
+    pdf_password: Option<String>,
+    decrypt_output: bool,
+    pkcs12: Option<Pkcs12Source>,
+    pkcs11: Option<Pkcs11Token>,
+    kms: Option<KmsKey>,
+    keystore_cert: Option<String>,
+    allow_test_credentials: bool,
+    embed_chain: EmbedChain,
+    algorithm_policy: AlgorithmPolicy,
+    timestamp: Option<Tsa>,
+    pades: Option<PadesLevel>,
+    ltv: LtvPolicy,
+    certify: Option<CertifyLevel>,
+    lock_fields: Option<FieldLock>,
+    scratch: ScratchSpace,
+    object_streams: bool,
+    sign_attachments: bool,
+    placeholder_size: Option<usize>,
+    budget: UpdateBudget,
+    signing_time: Option<DateTime<FixedOffset>>,
+}
+
+impl SignatureOptionsBuilder {
+    /// Nama penandatangan (default: "pdfsign-cli")
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Alasan penandatanganan (default: "Digitally signed")
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Lokasi penandatanganan (default: kosong)
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Informasi kontak penandatangan (default: kosong)
+    pub fn contact_info(mut self, contact_info: impl Into<String>) -> Self {
+        self.contact_info = Some(contact_info.into());
+        self
+    }
+
+    /// Tampilkan tanda tangan pada halaman dan posisi tertentu
+    pub fn visible(mut self, placement: Placement) -> Self {
+        self.placement = Some(placement);
+        self
+    }
+
+    /// Buat tanda tangan tanpa tampilan (rect berukuran nol)
+    pub fn invisible(mut self) -> Self {
+        self.invisible = true;
+        self
+    }
+
+    /// Tampilkan signature sebagai annotation /Stamp di posisi placement;
+    /// signature field-nya tetap ada tetapi tidak terlihat (rect nol), untuk
+    /// viewer dan workflow yang tidak menginginkan widget form yang interaktif
+    pub fn stamp_only(mut self, stamp_only: bool) -> Self {
+        self.stamp_only = stamp_only;
+        self
+    }
+
+    /// Flag annotation widget signature (default: print); untuk signature
+    /// field yang sudah ada, flag dari generator dokumen hanya diganti jika
+    /// opsi ini diberikan
+    pub fn widget_flags(mut self, flags: AnnotationFlags) -> Self {
+        self.widget_flags = Some(flags);
+        self
+    }
+
+    /// Flag annotation stamp --stamp-only (default: print, locked)
+    pub fn stamp_flags(mut self, flags: AnnotationFlags) -> Self {
+        self.stamp_flags = Some(flags);
+        self
+    }
+
+    /// Isi signature field kosong yang sudah ada di dokumen (nama lengkap,
+    /// misalnya "ApproverSignature"); halaman dan rect diambil dari widget-nya
+    pub fn field_name(mut self, field_name: impl Into<String>) -> Self {
+        self.field_name = Some(field_name.into());
+        self
+    }
+
+    /// Aturan posisi berdasarkan ciri dokumen (ukuran kertas, orientasi, teks
+    /// jangkar); aturan pertama yang cocok menggantikan placement, dokumen yang
+    /// tidak cocok dengan aturan mana pun memakai placement biasa
+    pub fn placement_rules(mut self, rules: Vec<PlacementRule>) -> Self {
+        self.placement_rules = rules;
+        self
+    }
+
+    /// Bahasa blok teks pada tampilan signature, maksimal dua (default: en)
+    /// Contoh: `vec![AppearanceLanguage::Id, AppearanceLanguage::En]` untuk dokumen dwibahasa
+    pub fn appearance_languages(mut self, languages: Vec<AppearanceLanguage>) -> Self {
+        self.appearance_languages = Some(languages);
+        self
+    }
+
+    /// Template teks tampilan, menggantikan blok per bahasa; baris dipisah
+    /// newline atau `\n`, variabel: {name}, {reason}, {location}, {contact}, {date}
+    pub fn appearance_template(mut self, template: impl Into<String>) -> Self {
+        self.appearance_template = Some(template.into());
+        self
+    }
+
+    /// Ukuran font teks tampilan dalam point (default: menyesuaikan kotak, maksimal 10pt)
+    pub fn appearance_font_size(mut self, size: f32) -> Self {
+        self.appearance_font_size = Some(size);
+        self
+    }
+
+    /// Warna teks tampilan (default: hitam)
+    pub fn appearance_color(mut self, color: TextColor) -> Self {
+        self.appearance_color = Some(color);
+        self
+    }
+
+    /// Logo PNG atau JPEG yang ditampilkan di sebelah kiri teks tampilan
+    pub fn appearance_image(mut self, path: impl Into<String>) -> Self {
+        self.appearance_image = Some(path.into());
+        self
+    }
+
+    /// Font TrueType (.ttf) yang disematkan untuk teks tampilan (default:
+    /// Helvetica standar yang tidak disematkan; dokumen PDF/A memakai font
+    /// sistem yang umum jika ini tidak diberikan)
+    pub fn appearance_font(mut self, path: impl Into<String>) -> Self {
+        self.appearance_font = Some(path.into());
+        self
+    }
+
+    /// Cache tampilan yang dipakai bersama dengan opsi lain (default: cache
+    /// baru). Clone dari SignatureOptions selalu berbagi cache yang sama
+    pub fn appearance_cache(mut self, cache: AppearanceCache) -> Self {
+        self.appearance_cache = cache;
+        self
+    }
+
+    /// Kebijakan jika AcroForm memiliki /NeedAppearances true (default: warn)
+    pub fn need_appearances(mut self, policy: NeedAppearancesPolicy) -> Self {
+        self.need_appearances = Some(policy);
+        self
+    }
+
+    /// Mode strict: tolak dokumen yang form-nya punya perubahan tertunda
+    /// (data XFA, nilai field yang tidak sesuai dengan appearance-nya)
+    /// alih-alih hanya memberi peringatan
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Hapus data XFA dari form hybrid (XFA + AcroForm) sebelum ditandatangani
+    pub fn drop_xfa(mut self, drop_xfa: bool) -> Self {
+        self.drop_xfa = drop_xfa;
+        self
+    }
+
+    /// Tolak dokumen yang melanggar spesifikasi PDF (nomor object ganda dalam
+    /// satu revisi, /Length stream yang salah) alih-alih memperbaikinya dengan
+    /// peringatan
+    pub fn strict_parse(mut self, strict_parse: bool) -> Self {
+        self.strict_parse = strict_parse;
+        self
+    }
+
+    /// Bangun ulang dokumen yang tidak bisa dibaca (cross-reference rusak atau
+    /// terpotong) dari header object-nya, lalu tulis ulang seluruhnya sebelum
+    /// ditandatangani; signature yang sudah ada tidak lagi valid
+    pub fn repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    /// Command eksternal yang memeriksa setiap dokumen sebelum ditandatangani
+    /// (lihat `classify::run_command`): exit code selain 0 menolak dokumen,
+    /// dan output-nya boleh mengganti alasan, lokasi, atau kontak
+    pub fn classify_hook(mut self, command: impl Into<String>) -> Self {
+        self.classify_hook = Some(command.into());
+        self
+    }
+
+    /// Wajibkan dokumen PDF/A dan tolak jika signature akan merusak
+    /// kesesuaiannya (font yang tidak disematkan, warna di luar OutputIntent)
+    /// alih-alih hanya memberi peringatan
+    pub fn require_pdfa(mut self, require_pdfa: bool) -> Self {
+        self.require_pdfa = require_pdfa;
+        self
+    }
+
+    /// Skema padding jika kunci privat RSA (default: PKCS#1 v1.5; diabaikan untuk ECDSA)
+    pub fn rsa_padding(mut self, padding: RsaPadding) -> Self {
+        self.rsa_padding = padding;
+        self
+    }
+
+    /// Algoritma digest signature (default: SHA-256)
+    pub fn digest(mut self, digest: DigestAlgorithm) -> Self {
+        self.digest = digest;
+        self
+    }
+
+    /// Passphrase untuk kunci privat PKCS#8 terenkripsi
+    /// (tanpa passphrase, user diminta mengetiknya di terminal)
+    pub fn passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    /// Password dokumen PDF terenkripsi (user atau owner); tanpa ini, password
+    /// user kosong dicoba. Update ditulis dengan enkripsi yang sama
+    pub fn pdf_password(mut self, password: impl Into<String>) -> Self {
+        self.pdf_password = Some(password.into());
+        self
+    }
+
+    /// Tulis dokumen terenkripsi sebagai salinan tanpa enkripsi: seluruh
+    /// dokumen didekripsi dan ditulis ulang sebelum ditandatangani, sehingga
+    /// signature yang sudah ada tidak lagi valid. Butuh password owner. Tanpa
+    /// ini hasil signing selalu terenkripsi seperti dokumen aslinya
+    pub fn decrypt_output(mut self, decrypt_output: bool) -> Self {
+        self.decrypt_output = decrypt_output;
+        self
+    }
+
+    /// Ambil kunci privat dan rantai sertifikat dari bundle PKCS#12 (.p12/.pfx)
+    /// alih-alih dari file kunci dan certificate.der
+    pub fn pkcs12(mut self, path: impl Into<String>, password: Option<String>) -> Self {
+        self.pkcs12 = Some(Pkcs12Source { path: path.into(), password });
+        self
+    }
+
+    /// Tandatangani dengan kunci di smart card / USB token / HSM lewat modul
+    /// PKCS#11; kunci privat tidak pernah keluar dari token
+    pub fn pkcs11(mut self, token: Pkcs11Token) -> Self {
+        self.pkcs11 = Some(token);
+        self
+    }
+
+    /// Tandatangani dengan kunci di AWS KMS, Google Cloud KMS, atau Azure Key
+    /// Vault; hanya digest yang dikirim ke layanan (butuh fitur Cargo-nya)
+    pub fn kms(mut self, key: KmsKey) -> Self {
+        self.kms = Some(key);
+        self
+    }
+
+    /// Tandatangani dengan kunci di Windows certificate store atau macOS
+    /// Keychain, dipilih dengan thumbprint SHA-1 atau common name sertifikatnya;
+    /// rantai sertifikat diambil dari store (butuh fitur Cargo `keystore`)
+    pub fn keystore_cert(mut self, selector: impl Into<String>) -> Self {
+        self.keystore_cert = Some(selector.into());
+        self
+    }
+
+    /// Izinkan signing dengan kredensial uji: sertifikat buatan `generate-cert`
+    /// (lihat `cert::TEST_CREDENTIAL_MARKER`) yang tanpa ini ditolak
+    pub fn allow_test_credentials(mut self, allow: bool) -> Self {
+        self.allow_test_credentials = allow;
+        self
+    }
+
+    /// Sertifikat yang disisipkan di signature (default: seluruh rantai)
+    pub fn embed_chain(mut self, embed_chain: EmbedChain) -> Self {
+        self.embed_chain = embed_chain;
+        self
+    }
+
+    /// Kebijakan algoritma lemah (default: tolak MD5, SHA-1, RSA < 2048, P-192)
+    pub fn algorithm_policy(mut self, policy: AlgorithmPolicy) -> Self {
+        self.algorithm_policy = policy;
+        self
+    }
+
+    /// Timestamp signature dari TSA (RFC 3161), disisipkan sebagai unsigned attribute
+    pub fn timestamp(mut self, tsa: Tsa) -> Self {
+        self.timestamp = Some(tsa);
+        self
+    }
+
+    /// Level PAdES baseline; B-T ke atas butuh TSA (lihat `timestamp`)
+    pub fn pades(mut self, level: PadesLevel) -> Self {
+        self.pades = Some(level);
+        self
+    }
+
+    /// Urutan sumber OCSP/CRL dan toleransi kegagalannya untuk B-LT ke atas
+    pub fn ltv_policy(mut self, policy: LtvPolicy) -> Self {
+        self.ltv = policy;
+        self
+    }
+
+    /// Buat certification signature (DocMDP) dengan tingkat izin perubahan
+    /// berikutnya; hanya bisa untuk signature pertama di dokumen
+    pub fn certify(mut self, level: CertifyLevel) -> Self {
+        self.certify = Some(level);
+        self
+    }
+
+    /// Kunci form field setelah ditandatangani (FieldMDP): field yang dikunci
+    /// menjadi read-only dan perubahannya membuat signature ini tidak valid
+    pub fn lock_fields(mut self, lock: FieldLock) -> Self {
+        self.lock_fields = Some(lock);
+        self
+    }
+
+    /// Tempat menyimpan hasil serialisasi sementara (default: memori)
+    pub fn scratch(mut self, scratch: ScratchSpace) -> Self {
+        self.scratch = scratch;
+        self
+    }
+
+    /// Masukkan object baru (widget, AcroForm, font, ...) ke object stream dan
+    /// kompres stream baru (appearance, gambar) dengan Flate; xref ditulis sebagai
+    /// cross-reference stream, dan dokumen di bawah PDF 1.5 dinaikkan ke 1.5
+    pub fn object_streams(mut self, object_streams: bool) -> Self {
+        self.object_streams = object_streams;
+        self
+    }
+
+    /// Tandatangani juga setiap file lampiran (name tree /EmbeddedFiles) dengan
+    /// CMS detached dan simpan hasilnya sebagai lampiran "<nama>.p7s", sehingga
+    /// file yang diekstrak tetap bisa diverifikasi di luar PDF
+    pub fn sign_attachments(mut self, sign_attachments: bool) -> Self {
+        self.sign_attachments = sign_attachments;
+        self
+    }
+
+    /// Ukuran ruang /Contents signature dalam bytes (sebelum hex); default:
+    /// diperkirakan dari rantai sertifikat, kunci, dan TSA
+    pub fn placeholder_size(mut self, size: usize) -> Self {
+        self.placeholder_size = Some(size);
+        self
+    }
+
+    /// Peringatkan jika signing menambah file melebihi batas ukuran (bytes atau
+    /// persen) atau jumlah object, beserta object terbesar yang ditambahkan;
+    /// misalnya logo 4 MB yang tidak sengaja disisipkan di setiap faktur
+    pub fn budget(mut self, budget: UpdateBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Waktu penandatanganan yang ditulis di /M, tampilan, dan atribut
+    /// signingTime (default: waktu lokal saat signing); untuk fixture yang
+    /// reproducible. Timestamp dari TSA tetap memakai waktu TSA.
+    pub fn signing_time(mut self, time: DateTime<FixedOffset>) -> Self {
+        self.signing_time = Some(time);
+        self
+    }
+
+    /// Validasi semua opsi dan buat SignatureOptions
+    pub fn build(self) -> Result<SignatureOptions> {
+        // visible() dan invisible() saling bertentangan
+        if self.invisible && self.placement.is_some() {
+            bail!("signature cannot be both visible (with a placement) and invisible");
+        }
+
+        // Validasi posisi widget
+        if let Some(placement) = &self.placement {
+            if placement.page() == 0 {
+                bail!("page numbers start at 1");
+            }
+            if let Placement::Page(_, rect) = placement {
+                if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                    bail!("signature rectangle must have a positive width and height");
+                }
+            }
+        }
+
+        // Field yang sudah ada menentukan posisinya sendiri
+        if let Some(field_name) = &self.field_name {
+            if self.placement.is_some() {
+                bail!("signature field {} already has a page and rectangle; drop the placement", field_name);
+            }
+            if field_name.is_empty() {
+                bail!("signature field name must not be empty");
+            }
+            if !self.placement_rules.is_empty() {
+                bail!("signature field {} already has a page and rectangle; drop the placement rules", field_name);
+            }
+        }
+
+        // Aturan posisi divalidasi seperti placement biasa
+        for rule in &self.placement_rules {
+            if rule.placement.page() == 0 {
+                bail!("placement rule {}: page numbers start at 1", rule.name);
+            }
+            if let Placement::Page(_, rect) = rule.placement {
+                if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                    bail!("placement rule {}: signature rectangle must have a positive width and height", rule.name);
+                }
+            }
+        }
+
+        if self.classify_hook.as_ref().is_some_and(|command| command.trim().is_empty()) {
+            bail!("the classification hook command must not be empty");
+        }
+
+        // --repair memperbaiki dokumen yang justru ditolak --strict-parse
+        if self.repair && self.strict_parse {
+            bail!("--repair rewrites damaged documents; it cannot be combined with --strict-parse");
+        }
+
+        // Stamp menggantikan widget yang terlihat, jadi perlu placement sendiri
+        if self.stamp_only && self.invisible {
+            bail!("a stamp-only signature shows its appearance as a stamp; it cannot be invisible");
+        }
+        if self.stamp_only && self.field_name.is_some() {
+            bail!("a stamp-only signature creates its own invisible field; it cannot fill an existing field");
+        }
+        if self.stamp_flags.is_some() && !self.stamp_only {
+            bail!("stamp flags need a stamp-only signature");
+        }
+
+        // Logo hanya bisa ditampilkan di signature yang terlihat
+        if self.invisible && self.appearance_image.is_some() {
+            bail!("an appearance image needs a visible signature");
+        }
+        if self.invisible && self.appearance_font.is_some() {
+            bail!("an appearance font needs a visible signature");
+        }
+
+        // Template menggantikan blok per bahasa; variabelnya harus dikenal
+        if let Some(template) = &self.appearance_template {
+            if self.appearance_languages.is_some() {
+                bail!("an appearance template replaces the appearance languages; choose one");
+            }
+            if let Err(e) = validate_template(template) {
+                bail!(e);
+            }
+        }
+        if let Some(size) = self.appearance_font_size {
+            if !(size > 0.0 && size <= 100.0) {
+                bail!("appearance font size must be between 0 and 100 points (got {})", size);
+            }
+        }
+
+        // /Contents ditulis dua kali lipat sebagai hex; 1 MiB sudah jauh di atas CMS nyata
+        if let Some(size) = self.placeholder_size {
+            if !(1024..=1024 * 1024).contains(&size) {
+                bail!("placeholder size must be between 1024 and 1048576 bytes (got {})", size);
+            }
+        }
+
+        // Tampilan berisi satu atau dua blok bahasa yang berbeda
+        let appearance_languages = self.appearance_languages.unwrap_or_else(|| vec![AppearanceLanguage::En]);
+        if appearance_languages.is_empty() || appearance_languages.len() > 2 {
+            bail!("appearance text supports one or two languages");
+        }
+        if appearance_languages.len() == 2 && appearance_languages[0] == appearance_languages[1] {
+            bail!("appearance languages must be different");
+        }
+
+        // Satu sumber kunci saja
+        let key_sources = [self.pkcs12.is_some(), self.pkcs11.is_some(), self.kms.is_some(), self.keystore_cert.is_some()];
+        if key_sources.iter().filter(|&&given| given).count() > 1 {
+            bail!("choose only one of a PKCS#12 bundle, a PKCS#11 token, a KMS key, or an OS keystore certificate");
+        }
+
+        // TSA hanya bisa dihubungi lewat HTTP(S)
+        if let Some(tsa) = &self.timestamp {
+            if !(tsa.url.starts_with("http://") || tsa.url.starts_with("https://")) {
+                bail!("TSA URL must start with http:// or https:// (got {})", tsa.url);
+            }
+        }
+
+        // Timestamp signature (B-T) dan document timestamp (B-LTA) butuh TSA
+        if let Some(level) = self.pades {
+            if level >= PadesLevel::BT && self.timestamp.is_none() {
+                bail!("PAdES {} needs a TSA for the signature timestamp (--tsa-url)", level.label());
+            }
+        }
+
+        // TSA (dan OCSP/CRL untuk PAdES B-LT ke atas) dihubungi lewat HTTP
+        #[cfg(not(feature = "network"))]
+        if self.timestamp.is_some() {
+            return Err(crate::error::missing_feature("TSA", "network"));
+        }
+
+        // Tanpa fitur appearance tidak ada yang bisa digambar di kotak signature
+        #[cfg(not(feature = "appearance"))]
+        if self.placement.is_some() || !self.placement_rules.is_empty() || self.appearance_image.is_some() || self.appearance_font.is_some() || self.stamp_only {
+            return Err(crate::error::missing_feature("visible signature", "appearance"));
+        }
+
+        let name = self.name.unwrap_or_else(|| "pdfsign-cli".to_string());
+        if name.trim().is_empty() {
+            bail!("signer name must not be empty");
+        }
+
+        // Posisi default: halaman 1, DEFAULT_RECT di bawah signature yang sudah
+        // ada (tanpa fitur appearance: signature tak terlihat)
+        let placement = if self.invisible || cfg!(not(feature = "appearance")) {
+            None
+        } else {
+            Some(self.placement.unwrap_or(Placement::Default(1)))
+        };
+
+        let mut options = SignatureOptions {
+            name,
+            reason: self.reason.unwrap_or_else(|| "Digitally signed".to_string()),
+            location: self.location.unwrap_or_default(),
+            contact_info: self.contact_info.unwrap_or_default(),
+            placement,
+            stamp_only: self.stamp_only,
+            widget_flags: self.widget_flags,
+            stamp_flags: self.stamp_flags,
+            field_name: self.field_name,
+            placement_rules: self.placement_rules,
+            appearance_languages,
+            appearance_template: self.appearance_template,
+            appearance_font_size: self.appearance_font_size,
+            appearance_color: self.appearance_color,
+            appearance_image: self.appearance_image,
+            appearance_font: self.appearance_font,
+            appearance_cache: self.appearance_cache,
+            need_appearances: self.need_appearances.unwrap_or(NeedAppearancesPolicy::Warn),
+            strict: self.strict,
+            drop_xfa: self.drop_xfa,
+            strict_parse: self.strict_parse,
+            repair: self.repair,
+            classify_hook: self.classify_hook,
+            require_pdfa: self.require_pdfa,
+            rsa_padding: self.rsa_padding,
+            digest: self.digest,
+            passphrase: self.passphrase,
+            pdf_password: self.pdf_password,
+            decrypt_output: self.decrypt_output,
+            pkcs12: self.pkcs12,
+            pkcs11: self.pkcs11,
+            kms: self.kms,
+            keystore_cert: self.keystore_cert,
+            allow_test_credentials: self.allow_test_credentials,
+            embed_chain: self.embed_chain,
+            algorithm_policy: self.algorithm_policy,
+            timestamp: self.timestamp,
+            pades: self.pades,
+            ltv: self.ltv,
+            certify: self.certify,
+            lock_fields: self.lock_fields,
+            scratch: self.scratch,
+            object_streams: self.object_streams,
+            sign_attachments: self.sign_attachments,
+            placeholder_size: self.placeholder_size,
+            budget: self.budget,
+            signing_time: self.signing_time,
+        };
+        options.normalize_signer_text()?;
+        Ok(options)
+    }
+}
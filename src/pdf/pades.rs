@@ -0,0 +1,256 @@
+// DSS dan document timestamp berisi data dari TSA/OCSP/CRL, sehingga hanya
+// dikompilasi dengan fitur network; tanpanya hanya PadesLevel yang tersisa
+
+// Import library yang diperlukan
+#[cfg(feature = "network")]
+use anyhow::{anyhow, Result}; // Untuk error handling yang fleksibel
+#[cfg(feature = "network")]
+use lopdf::{Dictionary, Document, Object, ObjectId}; // Struktur dasar PDF
+#[cfg(feature = "network")]
+use sha1::{Digest, Sha1}; // Kunci VRI = SHA-1 dari signature
+
+#[cfg(feature = "network")]
+use crate::crypto::revocation::RevocationData; // Respons OCSP dan CRL
+#[cfg(feature = "network")]
+use crate::pdf::form; // Nama field dan AcroForm yang sudah ada
+#[cfg(feature = "network")]
+use crate::pdf::pages; // Page tree sebelum widget timestamp ditempel
+#[cfg(feature = "network")]
+use crate::pdf::text; // Nama field sebagai text string
+
+/// Level PAdES baseline (ETSI EN 319 142-1)
+///
+/// Setiap level mencakup level sebelumnya:
+/// B-B < B-T < B-LT < B-LTA
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize))]
+pub enum PadesLevel {
+    /// Signature CAdES dasar: SubFilter ETSI.CAdES.detached, signing-certificate-v2
+    #[cfg_attr(feature = "cli", value(name = "b-b"), serde(rename = "b-b"))]
+    BB,
+    /// B-B + timestamp signature dari TSA (butuh --tsa-url)
+    #[cfg_attr(feature = "cli", value(name = "b-t"), serde(rename = "b-t"))]
+    BT,
+    /// B-T + sertifikat dan respons OCSP/CRL di Document Security Store (DSS)
+    #[cfg_attr(feature = "cli", value(name = "b-lt"), serde(rename = "b-lt"))]
+    BLt,
+    /// B-LT + document timestamp atas seluruh dokumen
+    #[cfg_attr(feature = "cli", value(name = "b-lta"), serde(rename = "b-lta"))]
+    BLta,
+}
+
+impl PadesLevel {
+    /// Nama level seperti di standar (B-B, B-T, B-LT, B-LTA)
+    pub fn label(self) -> &'static str {
+        match self {
+            PadesLevel::BB => "B-B",
+            PadesLevel::BT => "B-T",
+            PadesLevel::BLt => "B-LT",
+            PadesLevel::BLta => "B-LTA",
+        }
+    }
+}
+
+/// Sumber data revocation yang diambil untuk PAdES B-LT (--ltv-revocation)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum RevocationPreference {
+    /// OCSP lebih dulu, CRL jika tidak ada responder atau responder gagal
+    #[default]
+    Ocsp,
+    /// CRL lebih dulu, OCSP jika tidak ada distribution point atau unduhan gagal
+    Crl,
+    /// OCSP dan CRL sekaligus jika keduanya tersedia; minimal salah satu
+    Both,
+}
+
+/// Kebijakan pengambilan data validasi (DSS) untuk B-LT, B-LTA, dan `add-ltv`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LtvPolicy {
+    pub revocation: RevocationPreference, // Urutan dan jumlah sumber OCSP/CRL
+    pub best_effort: bool,                // Sertifikat tanpa data revocation hanya diberi peringatan
+}
+
+/// Tambahkan data validasi ke Document Security Store (/DSS di catalog)
+///
+/// DSS yang sudah ada (dari signature sebelumnya) dipertahankan; data baru
+/// ditambahkan ke /Certs, /OCSPs, /CRLs dan ke entry /VRI milik signature ini.
+///
+/// Parameter:
+///   - doc: dokumen yang sudah ditandatangani (dimuat ulang dari hasil serialisasi)
+///   - signature_contents: bytes nilai /Contents signature (termasuk padding nol);
+///     SHA-1-nya menjadi kunci /VRI
+///   - certificates: sertifikat DER (rantai penandatangan dan TSA)
+///   - revocation: respons OCSP dan CRL untuk sertifikat tersebut
+#[cfg(feature = "network")]
+pub fn add_dss(doc: &mut Document, signature_contents: &[u8], certificates: &[Vec<u8>], revocation: &RevocationData) -> Result<()> {
+    let root_id = form::catalog_id(doc)?;
+    let existing = doc.get_dictionary(root_id)?.get(b"DSS").ok().cloned();
+    let mut dss = existing
+        .as_ref()
+        .and_then(|dss| doc.dereference(dss).ok())
+        .and_then(|(_, dss)| dss.as_dict().ok())
+        .cloned()
+        .unwrap_or_default();
+
+    // Setiap sertifikat/respons disimpan sebagai stream terpisah; stream dengan
+    // isi yang sama di DSS dipakai ulang agar sertifikat tidak tersimpan berkali-kali
+    let certs = merge_streams(doc, &mut dss, b"Certs", certificates);
+    let ocsps = merge_streams(doc, &mut dss, b"OCSPs", &revocation.ocsp_responses);
+    let crls = merge_streams(doc, &mut dss, b"CRLs", &revocation.crls);
+
+    // VRI: data validasi per signature, dengan kunci SHA-1 (hex huruf besar) dari /Contents
+    let mut entry = Dictionary::new();
+    for (key, refs) in [(&b"Cert"[..], certs), (b"OCSP", ocsps), (b"CRL", crls)] {
+        if !refs.is_empty() {
+            entry.set(key, Object::Array(refs));
+        }
+    }
+    let key: String = Sha1::digest(signature_contents).iter().map(|b| format!("{:02X}", b)).collect();
+    let mut vri = dss
+        .get(b"VRI")
+        .ok()
+        .and_then(|vri| doc.dereference(vri).ok())
+        .and_then(|(_, vri)| vri.as_dict().ok())
+        .cloned()
+        .unwrap_or_default();
+    vri.set(key.into_bytes(), Object::Dictionary(entry));
+    dss.set("VRI", Object::Dictionary(vri));
+
+    // DSS indirect yang sudah ada ditulis ulang di tempat; selain itu buat object baru
+    match existing {
+        Some(Object::Reference(dss_id)) if doc.objects.contains_key(&dss_id) => {
+            doc.objects.insert(dss_id, Object::Dictionary(dss));
+        }
+        _ => {
+            let dss_id = doc.add_object(dss);
+            if let Ok(Object::Dictionary(ref mut catalog)) = doc.get_object_mut(root_id) {
+                catalog.set("DSS", Object::Reference(dss_id));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Widget field document timestamp
+#[cfg(feature = "network")]
+#[derive(Clone, Debug)]
+pub struct TimestampWidget {
+    pub page_id: Option<ObjectId>,    // Halaman widget; None = halaman 1
+    pub rect: [f32; 4],               // /Rect [left bottom right top]; nol = tak terlihat
+    pub flags: i64,                   // /F widget
+    pub appearance: Option<ObjectId>, // Appearance stream /AP /N; None = tanpa /AP
+}
+
+#[cfg(feature = "network")]
+impl TimestampWidget {
+    /// Widget berukuran nol di halaman 1 yang ikut dicetak (Print), tanpa tampilan
+    pub fn invisible() -> TimestampWidget {
+        TimestampWidget { page_id: None, rect: [0.0; 4], flags: 4, appearance: None }
+    }
+}
+
+/// Tambahkan field signature tak terlihat untuk document timestamp (PAdES B-LTA)
+///
+/// Parameter:
+///   - doc: dokumen yang sudah ditandatangani
+///   - contents: placeholder /Contents (diisi TimeStampToken setelah serialisasi)
+///   - byte_range: placeholder /ByteRange
+///
+/// Return: nama field timestamp yang ditambahkan
+#[cfg(feature = "network")]
+pub fn add_document_timestamp(doc: &mut Document, contents: Object, byte_range: Object) -> Result<String> {
+    add_document_timestamp_with(doc, contents, byte_range, &TimestampWidget::invisible())
+}
+
+/// Seperti `add_document_timestamp`, dengan posisi, flag, dan tampilan widget
+/// sendiri (stamp "Timestamped on ..." atau widget Hidden, lihat
+/// `timestamp::TimestampAppearance`)
+#[cfg(feature = "network")]
+pub fn add_document_timestamp_with(doc: &mut Document, contents: Object, byte_range: Object, widget: &TimestampWidget) -> Result<String> {
+    let root_id = form::catalog_id(doc)?;
+    pages::check_page_tree(doc)?;
+    let page_id = match widget.page_id {
+        Some(page_id) => page_id,
+        None => *doc
+            .get_pages()
+            .get(&1)
+            .ok_or_else(|| anyhow!("document has no pages"))?,
+    };
+
+    // Dictionary timestamp: /Type /DocTimeStamp dengan SubFilter ETSI.RFC3161
+    let mut timestamp_dict = Dictionary::new();
+    timestamp_dict.set("Type", Object::Name(b"DocTimeStamp".to_vec()));
+    timestamp_dict.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
+    timestamp_dict.set("SubFilter", Object::Name(b"ETSI.RFC3161".to_vec()));
+    timestamp_dict.set("Contents", contents);
+    timestamp_dict.set("ByteRange", byte_range);
+    let timestamp_id = doc.add_object(timestamp_dict);
+
+    // Field signature dengan widget di halaman tujuan
+    let mut acroform = form::existing_acroform(doc, root_id).unwrap_or_default();
+    let field_name = form::unused_field_name(doc, &acroform, "Timestamp");
+    let mut field_dict = Dictionary::new();
+    field_dict.set("Type", Object::Name(b"Annot".to_vec()));
+    field_dict.set("Subtype", Object::Name(b"Widget".to_vec()));
+    field_dict.set("FT", Object::Name(b"Sig".to_vec()));
+    field_dict.set("T", text::text_string(&field_name));
+    field_dict.set("F", Object::Integer(widget.flags));
+    field_dict.set("V", Object::Reference(timestamp_id));
+    field_dict.set("Rect", Object::Array(widget.rect.iter().map(|&value| Object::Real(value)).collect()));
+    field_dict.set("P", Object::Reference(page_id));
+    if let Some(appearance_id) = widget.appearance {
+        let mut appearance = Dictionary::new();
+        appearance.set("N", Object::Reference(appearance_id));
+        field_dict.set("AP", Object::Dictionary(appearance));
+    }
+    let field_id = doc.add_object(field_dict);
+
+    // Daftarkan field di AcroForm dan tambahkan widget ke /Annots halaman
+    acroform.set("SigFlags", Object::Integer(3));
+    form::register_field(doc, root_id, acroform, field_id)?;
+    form::add_annotation(doc, page_id, field_id)?;
+    Ok(field_name)
+}
+
+/// Simpan setiap blob DER sebagai stream di array DSS `key` dan kembalikan referensinya
+/// Blob yang isinya sudah ada di array tersebut memakai referensi stream yang lama
+#[cfg(feature = "network")]
+fn merge_streams(doc: &mut Document, dss: &mut Dictionary, key: &[u8], blobs: &[Vec<u8>]) -> Vec<Object> {
+    let mut array = existing_array(doc, dss, key);
+    let mut refs: Vec<Object> = Vec::new();
+    for blob in blobs {
+        let found = array.iter().find(|reference| {
+            reference
+                .as_reference()
+                .and_then(|id| doc.get_object(id))
+                .and_then(Object::as_stream)
+                .is_ok_and(|stream| stream.content == *blob)
+        });
+        let reference = match found {
+            Some(reference) => reference.clone(),
+            None => {
+                let reference = Object::Reference(doc.add_object(lopdf::Stream::new(Dictionary::new(), blob.clone())));
+                array.push(reference.clone());
+                reference
+            }
+        };
+        if !refs.contains(&reference) {
+            refs.push(reference);
+        }
+    }
+    if !array.is_empty() {
+        dss.set(key, Object::Array(array));
+    }
+    refs
+}
+
+/// Salin array dari dictionary (langsung atau indirect), kosong jika tidak ada
+#[cfg(feature = "network")]
+fn existing_array(doc: &Document, dict: &Dictionary, key: &[u8]) -> Vec<Object> {
+    dict.get(key)
+        .and_then(|array| doc.dereference(array).map(|(_, array)| array))
+        .and_then(Object::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
@@ -0,0 +1,288 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Document, Object, ObjectId}; // Membaca struktur PDF
+use std::collections::{HashMap, HashSet}; // Nama field per widget, node page tree yang sudah dikunjungi
+use std::fs; // Membaca file PDF
+use std::io::Write; // Menulis output JSON
+
+use crate::pdf::form; // Annotation dan signature field halaman
+use crate::pdf::text::{self, json_string}; // Text string PDF dan escape JSON
+
+/// Koordinat halaman untuk `pdfsign pages info`
+pub struct PageInfo {
+    pub number: usize,                      // Nomor halaman, mulai dari 1
+    pub id: ObjectId,                       // Object ID halaman
+    pub media_box: Option<[f32; 4]>,        // /MediaBox [kiri bawah kanan atas], boleh diwarisi
+    pub crop_box: Option<[f32; 4]>,         // /CropBox, jika ada (boleh diwarisi)
+    pub rotation: i64,                      // /Rotate dalam derajat (0, 90, 180, 270)
+    pub annotations: Vec<AnnotationInfo>,   // Annotation yang sudah ada di halaman
+}
+
+/// Satu annotation di halaman
+pub struct AnnotationInfo {
+    pub subtype: String,         // /Subtype, misalnya Widget, Link, Stamp
+    pub rect: Option<[f32; 4]>,  // /Rect [kiri bawah kanan atas]
+    pub name: Option<String>,    // Nama lengkap form field (widget) atau /NM
+}
+
+/// Kotak halaman (/MediaBox, /CropBox, ...), boleh diwarisi dari node /Pages induk
+///
+/// Sudut boleh ditulis dalam urutan apa saja; hasilnya selalu
+/// [kiri, bawah, kanan, atas]
+///
+/// Parameter:
+///   - doc: dokumen PDF
+///   - page_id: object ID halaman (dari page tree)
+///   - key: nama kotak, misalnya b"CropBox"
+///
+/// Return: None jika halaman dan induknya tidak punya kotak tersebut
+pub fn page_box(doc: &Document, page_id: ObjectId, key: &[u8]) -> Result<Option<[f32; 4]>> {
+    let Some(value) = inherited(doc, page_id, key) else {
+        return Ok(None);
+    };
+    let numbers: Vec<f32> = doc
+        .dereference(value)?
+        .1
+        .as_array()?
+        .iter()
+        .filter_map(|n| n.as_float().ok().or_else(|| n.as_i64().ok().map(|n| n as f32)))
+        .collect();
+    match numbers[..] {
+        [x1, y1, x2, y2] => Ok(Some([x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)])),
+        _ => bail!("page {} {} R has an invalid /{}", page_id.0, page_id.1, String::from_utf8_lossy(key)),
+    }
+}
+
+/// /Rotate halaman (termasuk yang diwarisi), dinormalisasi ke 0, 90, 180, atau 270
+pub fn rotation(doc: &Document, page_id: ObjectId) -> i64 {
+    inherited(doc, page_id, b"Rotate")
+        .and_then(|rotate| doc.dereference(rotate).ok())
+        .and_then(|(_, rotate)| rotate.as_i64().ok())
+        .unwrap_or(0)
+        .rem_euclid(360)
+}
+
+/// Saran perbaikan untuk dokumen dengan page tree yang rusak
+const PAGE_TREE_REPAIR: &str = "open and save the document again in a PDF editor, or rewrite it with `qpdf <input> <output>`, then sign the result";
+
+/// Periksa page tree sebelum widget ditempel ke halaman
+///
+/// lopdf melewati node page tree yang rusak tanpa pesan (kids yang tidak ada,
+/// node tanpa /Type, node yang muncul dua kali), sehingga nomor halaman bergeser
+/// dan widget bisa merujuk ke halaman yang tidak ditampilkan viewer. Dokumen
+/// seperti itu, dan dokumen tanpa halaman, ditolak dengan saran perbaikan.
+/// /Count yang tidak sama dengan jumlah halaman hanya diperingatkan.
+///
+/// Parameter:
+///   - doc: dokumen PDF
+///
+/// Return: jumlah halaman
+pub fn check_page_tree(doc: &Document) -> Result<usize> {
+    let damaged = |detail: String| anyhow!("damaged page tree: {}; {}", detail, PAGE_TREE_REPAIR);
+    let root = match doc.catalog()?.get(b"Pages") {
+        Ok(Object::Reference(root)) => *root,
+        Ok(_) => return Err(damaged("the catalog's /Pages is not a reference to a page tree node".to_string())),
+        Err(_) => bail!("the document has no pages (the catalog has no /Pages); a signature needs a page to attach its widget to"),
+    };
+
+    // Telusuri dari root; setiap node hanya boleh dikunjungi sekali
+    let mut visited = HashSet::new();
+    let mut stack = vec![(root, 0usize)];
+    let mut pages = 0;
+    while let Some((id, depth)) = stack.pop() {
+        if !visited.insert(id) {
+            return Err(damaged(format!("object {} {} R appears more than once (the page tree loops)", id.0, id.1)));
+        }
+        let node = match doc.get_object(id) {
+            Ok(Object::Dictionary(node)) => node,
+            Ok(_) => return Err(damaged(format!("object {} {} R is not a dictionary", id.0, id.1))),
+            // Object hilang sering berarti cross-reference table yang rusak
+            Err(_) => return Err(damaged(format!("object {} {} R does not exist (if the cross-reference table is damaged, --repair may recover it)", id.0, id.1))),
+        };
+        match node.get(b"Type").and_then(Object::as_name).ok() {
+            Some(b"Page") => pages += 1,
+            Some(b"Pages") => {
+                let kids = node.get(b"Kids").and_then(|kids| doc.dereference(kids)).and_then(|(_, kids)| kids.as_array());
+                let Ok(kids) = kids else {
+                    return Err(damaged(format!("node {} {} R has no /Kids array", id.0, id.1)));
+                };
+                if depth >= 256 {
+                    return Err(damaged(format!("node {} {} R is nested more than 256 levels deep", id.0, id.1)));
+                }
+                // Urutan terbalik agar halaman diperiksa sesuai urutannya
+                for kid in kids.iter().rev() {
+                    match kid {
+                        Object::Reference(kid) => stack.push((*kid, depth + 1)),
+                        _ => return Err(damaged(format!("node {} {} R lists a /Kids entry that is not a reference", id.0, id.1))),
+                    }
+                }
+            }
+            Some(other) => return Err(damaged(format!("object {} {} R has /Type /{}, not /Page or /Pages", id.0, id.1, String::from_utf8_lossy(other)))),
+            None => return Err(damaged(format!("object {} {} R has no /Type /Page or /Pages", id.0, id.1))),
+        }
+    }
+    if pages == 0 {
+        bail!("the document has no pages; a signature needs a page to attach its widget to");
+    }
+    let count = doc.get_dictionary(root).and_then(|root| root.get(b"Count")).and_then(|count| doc.dereference(count)).and_then(|(_, count)| count.as_i64());
+    if let Some(count) = count.ok().filter(|&count| count != pages as i64) {
+        crate::warning!("the page tree's /Count is {} but it has {} page(s); viewers may show a different page count", count, pages);
+    }
+    Ok(pages)
+}
+
+/// Entri halaman yang boleh diwarisi (/MediaBox, /CropBox, /Rotate, /Resources)
+fn inherited<'a>(doc: &'a Document, page_id: ObjectId, key: &[u8]) -> Option<&'a Object> {
+    let mut node = doc.get_dictionary(page_id).ok();
+    let mut depth = 0;
+    while let Some(dict) = node {
+        if let Ok(value) = dict.get(key) {
+            return Some(value);
+        }
+        // Batasi kedalaman agar /Parent yang melingkar tidak membuat loop tanpa akhir
+        depth += 1;
+        node = match dict.get(b"Parent").and_then(Object::as_reference) {
+            Ok(parent) if depth < 64 => doc.get_dictionary(parent).ok(),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Baca kotak, rotasi, dan annotation setiap halaman
+///
+/// Parameter:
+///   - input: path file PDF
+///
+/// Return: halaman sesuai urutan page tree
+pub fn pages_info(input: &str) -> Result<Vec<PageInfo>> {
+    let pdf_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let doc = Document::load_mem(&pdf_bytes).with_context(|| format!("cannot parse {}", input))?;
+
+    // Widget ditampilkan dengan nama lengkap field-nya
+    let mut field_names = HashMap::new();
+    for (name, field_id, _) in form::terminal_fields(&doc) {
+        if let Ok(field) = doc.get_dictionary(field_id) {
+            for widget_id in form::field_widgets(&doc, field_id, field) {
+                field_names.insert(widget_id, name.clone());
+            }
+        }
+    }
+
+    let mut pages = Vec::new();
+    for (number, page_id) in doc.get_pages() {
+        let rotation = rotation(&doc, page_id);
+        let annotations = form::page_annotations(&doc, page_id)
+            .into_iter()
+            .filter_map(|id| {
+                let annotation = doc.get_dictionary(id).ok()?;
+                let subtype = annotation.get(b"Subtype").and_then(Object::as_name_str).unwrap_or("?").to_string();
+                let name = field_names.get(&id).cloned().or_else(|| {
+                    annotation.get(b"NM").and_then(Object::as_str).ok().map(text::decode_text_string)
+                });
+                Some(AnnotationInfo { subtype, rect: form::annotation_rect(&doc, id), name })
+            })
+            .collect();
+        pages.push(PageInfo {
+            number: number as usize,
+            id: page_id,
+            media_box: page_box(&doc, page_id, b"MediaBox")?,
+            crop_box: page_box(&doc, page_id, b"CropBox")?,
+            rotation,
+            annotations,
+        });
+    }
+    Ok(pages)
+}
+
+/// Kotak sebagai "[kiri bawah kanan atas]"
+fn format_box(rect: &[f32; 4]) -> String {
+    format!("[{} {} {} {}]", rect[0], rect[1], rect[2], rect[3])
+}
+
+/// Kotak dalam format --rect (x,y,w,h)
+fn format_rect_option(rect: &[f32; 4]) -> String {
+    format!("{},{},{},{}", rect[0], rect[1], rect[2] - rect[0], rect[3] - rect[1])
+}
+
+/// Koordinat halaman sebagai teks untuk user (ditampilkan oleh CLI)
+pub fn format_pages(pages: &[PageInfo]) -> String {
+    let mut out = String::new();
+    for page in pages {
+        out.push_str(&format!("Page {} (object {} {} R)\n", page.number, page.id.0, page.id.1));
+        match &page.media_box {
+            Some(media_box) => out.push_str(&format!(
+                "  MediaBox:   {} ({} x {} pt)\n",
+                format_box(media_box),
+                media_box[2] - media_box[0],
+                media_box[3] - media_box[1]
+            )),
+            None => out.push_str("  MediaBox:   missing\n"),
+        }
+        match &page.crop_box {
+            Some(crop_box) => out.push_str(&format!("  CropBox:    {} (--rect and --position use this box)\n", format_box(crop_box))),
+            None => out.push_str("  CropBox:    none (same as MediaBox)\n"),
+        }
+        if page.rotation == 0 {
+            out.push_str("  Rotation:   0\n");
+        } else {
+            out.push_str(&format!("  Rotation:   {} (--rect is in unrotated page coordinates)\n", page.rotation));
+        }
+        if page.annotations.is_empty() {
+            out.push_str("  Annotations: none\n");
+            continue;
+        }
+        out.push_str("  Annotations:\n");
+        for annotation in &page.annotations {
+            let rect = annotation
+                .rect
+                .as_ref()
+                .map_or("no /Rect".to_string(), |rect| format!("{}  --rect {}", format_box(rect), format_rect_option(rect)));
+            match &annotation.name {
+                Some(name) => out.push_str(&format!("    {:<8} {}  {}\n", annotation.subtype, rect, name)),
+                None => out.push_str(&format!("    {:<8} {}\n", annotation.subtype, rect)),
+            }
+        }
+    }
+    out
+}
+
+/// Tulis koordinat halaman sebagai satu object JSON (untuk skrip)
+///
+/// Parameter:
+///   - input: path file PDF (field "file")
+///   - pages: hasil `pages_info`
+///   - out: tujuan output
+pub fn write_json(input: &str, pages: &[PageInfo], out: &mut impl Write) -> Result<()> {
+    let array = |rect: &Option<[f32; 4]>| rect.map_or("null".to_string(), |r| format!("[{}, {}, {}, {}]", r[0], r[1], r[2], r[3]));
+    writeln!(out, "{{")?;
+    writeln!(out, "  \"file\": {},", json_string(input))?;
+    writeln!(out, "  \"pages\": [")?;
+    for (index, page) in pages.iter().enumerate() {
+        let annotations: Vec<String> = page
+            .annotations
+            .iter()
+            .map(|annotation| {
+                format!(
+                    "{{\"subtype\": {}, \"rect\": {}, \"name\": {}}}",
+                    json_string(&annotation.subtype),
+                    array(&annotation.rect),
+                    annotation.name.as_deref().map_or("null".to_string(), json_string)
+                )
+            })
+            .collect();
+        write!(
+            out,
+            "    {{\"page\": {}, \"media_box\": {}, \"crop_box\": {}, \"rotation\": {}, \"annotations\": [{}]}}",
+            page.number,
+            array(&page.media_box),
+            array(&page.crop_box),
+            page.rotation,
+            annotations.join(", ")
+        )?;
+        writeln!(out, "{}", if index + 1 < pages.len() { "," } else { "" })?;
+    }
+    writeln!(out, "  ]")?;
+    writeln!(out, "}}")?;
+    Ok(())
+}
@@ -0,0 +1,213 @@
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Document, Object}; // Struktur PDF
+use std::fmt; // Nama tingkat kesesuaian untuk pesan ke user
+
+use crate::pdf::appearance::{AppearanceTarget, ColorSpace}; // Font dan ruang warna tampilan
+use crate::pdf::form; // Catalog dokumen
+#[cfg(feature = "appearance")]
+use crate::pdf::image::AppearanceImage; // Logo di tampilan signature
+use crate::pdf::options::{AnnotationFlags, SignatureOptions}; // Font, flag annotation, object stream, dan --require-pdfa
+
+/// Lokasi font TrueType sistem yang dicoba jika dokumen PDF/A butuh font yang
+/// disematkan dan `--appearance-font` tidak diberikan (Windows, macOS, Linux)
+const SYSTEM_FONTS: [&str; 7] = [
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/usr/share/fonts/liberation-sans/LiberationSans-Regular.ttf",
+];
+
+/// Tingkat kesesuaian PDF/A yang dinyatakan dokumen di metadata XMP catalog
+/// (pdfaid:part dan pdfaid:conformance), misalnya PDF/A-2b
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PdfaConformance {
+    pub part: u8,            // Bagian ISO 19005: 1, 2, 3, atau 4
+    pub conformance: String, // Level: "A", "B", "U" (kosong untuk PDF/A-4)
+}
+
+impl fmt::Display for PdfaConformance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PDF/A-{}{}", self.part, self.conformance.to_ascii_lowercase())
+    }
+}
+
+/// Tingkat kesesuaian PDF/A dokumen dari metadata XMP di catalog
+///
+/// Return: None jika dokumen tidak punya metadata XMP atau tidak menyatakan PDF/A
+pub fn detect(doc: &Document) -> Option<PdfaConformance> {
+    let catalog = doc.get_dictionary(form::catalog_id(doc).ok()?).ok()?;
+    let (_, metadata) = catalog.get(b"Metadata").and_then(|metadata| doc.dereference(metadata)).ok()?;
+    let stream = metadata.as_stream().ok()?;
+    let content = stream.decompressed_content().unwrap_or_else(|_| stream.content.clone());
+    let xmp = String::from_utf8_lossy(&content);
+    let part = xmp_property(&xmp, "pdfaid:part")?.parse().ok()?;
+    let conformance = xmp_property(&xmp, "pdfaid:conformance").unwrap_or_default();
+    Some(PdfaConformance { part, conformance })
+}
+
+/// Nilai properti XMP sederhana, sebagai atribut (`pdfaid:part="2"`) atau
+/// elemen (`<pdfaid:part>2</pdfaid:part>`)
+fn xmp_property(xmp: &str, name: &str) -> Option<String> {
+    let attribute = format!("{}=", name);
+    if let Some(position) = xmp.find(&attribute) {
+        let rest = &xmp[position + attribute.len()..];
+        if let Some(quote) = rest.chars().next().filter(|&quote| quote == '"' || quote == '\'') {
+            let value = &rest[1..];
+            return value.find(quote).map(|end| value[..end].trim().to_string());
+        }
+    }
+    let element = format!("<{}>", name);
+    let start = xmp.find(&element)? + element.len();
+    let end = start + xmp[start..].find('<')?;
+    Some(xmp[start..end].trim().to_string())
+}
+
+/// Jumlah komponen profil ICC OutputIntent PDF/A (/S /GTS_PDFA1):
+/// 1 gray, 3 RGB, 4 CMYK
+fn output_intent_components(doc: &Document) -> Option<u8> {
+    let catalog = doc.get_dictionary(form::catalog_id(doc).ok()?).ok()?;
+    let (_, intents) = catalog.get(b"OutputIntents").and_then(|intents| doc.dereference(intents)).ok()?;
+    intents.as_array().ok()?.iter().find_map(|intent| {
+        let (_, intent) = doc.dereference(intent).ok()?;
+        let intent = intent.as_dict().ok()?;
+        if intent.get(b"S").and_then(Object::as_name).ok()? != b"GTS_PDFA1" {
+            return None;
+        }
+        let (_, profile) = intent.get(b"DestOutputProfile").and_then(|profile| doc.dereference(profile)).ok()?;
+        let components = profile.as_stream().ok()?.dict.get(b"N").and_then(Object::as_i64).ok()?;
+        u8::try_from(components).ok()
+    })
+}
+
+/// Nama ruang warna untuk pesan ke user, dari jumlah komponennya
+#[cfg(feature = "appearance")]
+fn color_space_name(components: u8) -> &'static str {
+    match components {
+        1 => "gray",
+        3 => "RGB",
+        _ => "CMYK",
+    }
+}
+
+/// Rencana PDF/A untuk satu signature (atau satu refresh-appearance): font
+/// dan ruang warna tampilan, serta hal-hal yang akan membuat dokumen PDF/A
+/// tidak lagi sesuai
+///
+/// Dokumen PDF/A mendapat tampilan dengan font TrueType yang disematkan
+/// (`--appearance-font`, atau font sistem yang umum) dan warna teks dalam
+/// ruang warna OutputIntent-nya. Yang tidak bisa dipertahankan dicatat dan
+/// dilaporkan oleh `finish`.
+#[derive(Clone, Debug)]
+pub struct PdfaPlan {
+    pub conformance: Option<PdfaConformance>, // None = dokumen bukan PDF/A
+    pub target: AppearanceTarget,             // Font dan ruang warna tampilan
+    #[cfg_attr(not(feature = "appearance"), allow(dead_code))]
+    intent: Option<u8>,                       // Komponen OutputIntent (None = tidak ada)
+    issues: Vec<String>,                      // Pelanggaran PDF/A yang akan muncul
+}
+
+impl PdfaPlan {
+    /// Periksa dokumen sebelum ditandatangani
+    ///
+    /// Parameter:
+    ///   - doc: dokumen yang akan ditandatangani
+    ///   - options: font tampilan, object stream, dan `require_pdfa`
+    ///   - visible: tampilan signature berisi teks (bukan signature tak terlihat)
+    ///
+    /// Return: error jika `require_pdfa` dan dokumen bukan PDF/A
+    pub fn new(doc: &Document, options: &SignatureOptions, visible: bool) -> Result<PdfaPlan> {
+        let mut target = AppearanceTarget { font: options.appearance_font.clone(), color_space: ColorSpace::Rgb };
+        let Some(conformance) = detect(doc) else {
+            if options.require_pdfa {
+                bail!("--require-pdfa: the document is not PDF/A (its XMP metadata has no pdfaid:part)");
+            }
+            return Ok(PdfaPlan { conformance: None, target, intent: None, issues: Vec::new() });
+        };
+
+        let mut issues = Vec::new();
+        let intent = output_intent_components(doc);
+        if visible {
+            // Font standar tidak disematkan; PDF/A mewajibkan semua font disematkan
+            if target.font.is_none() {
+                target.font = SYSTEM_FONTS
+                    .into_iter()
+                    .find(|path| std::path::Path::new(path).is_file())
+                    .map(str::to_string);
+            }
+            if target.font.is_none() {
+                issues.push("the appearance text needs an embedded font; pass a TrueType font with --appearance-font".to_string());
+            }
+            // Warna device hanya boleh dipakai sesuai OutputIntent
+            target.color_space = match intent {
+                Some(1) => ColorSpace::Gray,
+                Some(4) => ColorSpace::Cmyk,
+                Some(_) => ColorSpace::Rgb,
+                None => {
+                    issues.push("the document has no PDF/A OutputIntent, so the appearance text cannot use a device color".to_string());
+                    ColorSpace::Rgb
+                }
+            };
+        }
+        // Annotation PDF/A harus ikut dicetak dan tidak boleh disembunyikan dari layar
+        let widget_flags = options.widget_flags.unwrap_or(AnnotationFlags::WIDGET);
+        let stamp_flags = options.stamp_only.then(|| options.stamp_flags.unwrap_or(AnnotationFlags::STAMP));
+        for (flags, option) in [(Some(widget_flags), "--widget-flags"), (stamp_flags, "--stamp-flags")] {
+            if flags.is_some_and(|flags| !flags.print || flags.no_view) {
+                issues.push(format!("PDF/A annotations must be printable and visible on screen; {} must include print and not no-view", option));
+            }
+        }
+        // PDF/A-1 berbasis PDF 1.4: tanpa object stream dan xref stream
+        if conformance.part == 1 && options.object_streams {
+            issues.push("PDF/A-1 does not allow object streams (--object-streams)".to_string());
+        }
+        Ok(PdfaPlan { conformance: Some(conformance), target, intent, issues })
+    }
+
+    /// Periksa logo tampilan: ruang warna device harus sesuai OutputIntent
+    /// (gray selalu boleh), dan PDF/A-1 tidak mengizinkan transparansi
+    #[cfg(feature = "appearance")]
+    pub fn check_image(&mut self, image: &AppearanceImage) {
+        let Some(conformance) = &self.conformance else {
+            return;
+        };
+        if conformance.part == 1 && image.has_alpha() {
+            self.issues.push("PDF/A-1 does not allow transparency, but the appearance image has an alpha channel".to_string());
+        }
+        match (image.device_components(), self.intent) {
+            (None, _) | (Some(1), Some(_)) => {}
+            (Some(components), Some(intent)) if components == intent => {}
+            (Some(components), intent) => self.issues.push(format!(
+                "the appearance image is {} without an ICC profile, but the PDF/A OutputIntent is {}; use an image with an embedded ICC profile",
+                color_space_name(components),
+                intent.map_or("missing", color_space_name)
+            )),
+        }
+    }
+
+    /// Tolak (`require`) atau beri peringatan jika kesesuaian PDF/A tidak
+    /// bisa dipertahankan; dokumen yang bukan PDF/A tidak diperiksa
+    pub fn finish(&self, require: bool) -> Result<()> {
+        let Some(conformance) = &self.conformance else {
+            return Ok(());
+        };
+        if self.issues.is_empty() {
+            return Ok(());
+        }
+        if require {
+            bail!(
+                "the document is {} and the new revision would break conformance:\n  - {}",
+                conformance,
+                self.issues.join("\n  - ")
+            );
+        }
+        for issue in &self.issues {
+            crate::warning!("{}", issue);
+        }
+        crate::log::status(&format!("         the result will no longer conform to {}; use --require-pdfa to refuse such documents", conformance), true);
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+use lopdf::Document; // Dokumen yang ciri-cirinya diperiksa
+
+use crate::pdf::options::{Placement, Rect}; // Posisi signature dan kotak halaman
+use crate::pdf::pages; // /Rotate yang diwarisi
+use crate::pdf::sign::page_box; // CropBox atau MediaBox halaman
+
+/// Selisih ukuran halaman (point) yang masih dianggap sama dengan ukuran kertas standar
+const PAGE_SIZE_TOLERANCE: f32 = 3.0;
+
+/// Ukuran kertas standar yang bisa dipakai sebagai ciri dokumen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(serde::Deserialize, clap::ValueEnum), serde(rename_all = "kebab-case"))]
+pub enum PageSize {
+    /// 297 x 420 mm
+    A3,
+    /// 210 x 297 mm
+    A4,
+    /// 148 x 210 mm
+    A5,
+    /// 8.5 x 11 inch
+    Letter,
+    /// 8.5 x 14 inch
+    Legal,
+}
+
+impl PageSize {
+    /// Lebar dan tinggi dalam point (tegak)
+    pub fn dimensions(self) -> (f32, f32) {
+        match self {
+            PageSize::A3 => (842.0, 1191.0),
+            PageSize::A4 => (595.0, 842.0),
+            PageSize::A5 => (420.0, 595.0),
+            PageSize::Letter => (612.0, 792.0),
+            PageSize::Legal => (612.0, 1008.0),
+        }
+    }
+
+    /// Apakah kotak halaman berukuran kertas ini (tegak atau mendatar)
+    pub fn matches(self, page_box: &Rect) -> bool {
+        let (width, height) = self.dimensions();
+        let close = |a: f32, b: f32| (a - b).abs() <= PAGE_SIZE_TOLERANCE;
+        (close(page_box.width(), width) && close(page_box.height(), height))
+            || (close(page_box.width(), height) && close(page_box.height(), width))
+    }
+}
+
+/// Orientasi halaman seperti yang ditampilkan viewer (setelah /Rotate)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum Orientation {
+    /// Tinggi lebih besar dari lebar
+    Portrait,
+    /// Lebar lebih besar dari tinggi
+    Landscape,
+}
+
+/// Aturan penempatan: `placement` dipakai untuk dokumen yang cocok dengan
+/// semua ciri yang diisi
+///
+/// Ukuran dan orientasi diambil dari halaman pertama; teks jangkar dicari di
+/// seluruh dokumen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlacementRule {
+    pub name: String,                     // Nama aturan (pesan dan --dry-run)
+    pub page_size: Option<PageSize>,      // Ukuran kertas halaman pertama
+    pub orientation: Option<Orientation>, // Orientasi halaman pertama
+    pub anchor_text: Option<String>,      // Teks yang harus ada di dokumen
+    pub placement: Placement,             // Halaman dan posisi signature jika aturan cocok
+}
+
+impl PlacementRule {
+    /// Apakah dokumen cocok dengan semua ciri aturan ini
+    ///
+    /// Parameter:
+    ///   - doc: dokumen yang akan ditandatangani
+    pub fn matches(&self, doc: &Document) -> Result<bool> {
+        if self.page_size.is_some() || self.orientation.is_some() {
+            let Some(&first_page) = doc.get_pages().get(&1) else {
+                return Ok(false);
+            };
+            let page_box = page_box(doc, first_page)?;
+            if self.page_size.is_some_and(|size| !size.matches(&page_box)) {
+                return Ok(false);
+            }
+            if let Some(orientation) = self.orientation {
+                if orientation_of(doc, first_page, &page_box) != orientation {
+                    return Ok(false);
+                }
+            }
+        }
+        if let Some(text) = &self.anchor_text {
+            if !contains_text(doc, text) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Pilih aturan pertama yang cocok dengan dokumen
+///
+/// Parameter:
+///   - doc: dokumen yang akan ditandatangani
+///   - rules: aturan sesuai urutan di profil
+///
+/// Return: aturan yang cocok, atau None (placement default dipakai)
+pub fn select<'a>(doc: &Document, rules: &'a [PlacementRule]) -> Result<Option<&'a PlacementRule>> {
+    for rule in rules {
+        if rule.matches(doc)? {
+            return Ok(Some(rule));
+        }
+    }
+    Ok(None)
+}
+
+/// Orientasi halaman setelah /Rotate (90 dan 270 menukar lebar dan tinggi)
+fn orientation_of(doc: &Document, page_id: lopdf::ObjectId, page_box: &Rect) -> Orientation {
+    let rotated = pages::rotation(doc, page_id).rem_euclid(180) == 90;
+    let landscape = page_box.width() > page_box.height();
+    if landscape != rotated {
+        Orientation::Landscape
+    } else {
+        Orientation::Portrait
+    }
+}
+
+/// Cari teks di isi halaman; spasi dan huruf besar/kecil diabaikan, karena
+/// generator PDF sering memecah kata menjadi beberapa operator teks
+///
+/// Halaman yang isinya tidak bisa dibaca (font tanpa encoding yang dikenal,
+/// stream yang tidak dimuat) dilewati.
+fn contains_text(doc: &Document, needle: &str) -> bool {
+    let needle = normalize_text(needle);
+    if needle.is_empty() {
+        return true;
+    }
+    doc.get_pages()
+        .keys()
+        .any(|&number| doc.extract_text(&[number]).is_ok_and(|text| normalize_text(&text).contains(&needle)))
+}
+
+/// Teks tanpa spasi dan dalam huruf kecil, untuk pencarian teks di halaman
+pub(crate) fn normalize_text(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+}
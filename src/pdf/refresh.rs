@@ -0,0 +1,223 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream}; // Struktur dasar PDF
+use std::fs::File; // Untuk membaca file input
+use std::io::{Read, Seek, SeekFrom, Write}; // Membaca hasil dari buffer sementara
+
+use crate::crypto::policy::AlgorithmPolicy; // Kebijakan algoritma untuk pemeriksaan ulang
+use crate::error::Error; // Dokumen tanpa signature
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::appearance; // Layout dan resource tampilan signature
+use crate::pdf::options::{Rect, SignatureOptions}; // Opsi tampilan
+use crate::pdf::syntax::ParseMode; // Struktur file dibaca dalam mode lenient
+use crate::pdf::verify::{self, SignatureReport}; // Pemeriksaan signature sebelum dan sesudah
+use crate::pdf::{form, mdp, pdfa, sign, text}; // Signature field, DocMDP, PDF/A, incremental update, text string
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+
+/// Buat ulang tampilan signature pdfsign yang sudah ada, misalnya setelah
+/// pergantian logo atau template
+///
+/// Teks diambil dari signature dictionary masing-masing (/Name, /Reason,
+/// /Location, /ContactInfo, dan /M untuk {date}); template, bahasa, font,
+/// warna, dan logo dari `options`. Hanya widget yang terlihat dan stamp
+/// --stamp-only dengan tampilan buatan pdfsign (font /F1 Helvetica atau
+/// TrueType yang disematkan) yang diganti; signature tak terlihat dan
+/// tampilan buatan aplikasi lain dilewati. Dokumen PDF/A mendapat tampilan
+/// yang sesuai PDF/A seperti saat ditandatangani (lihat `pdfa::PdfaPlan`).
+///
+/// Tampilan baru ditulis sebagai incremental update: bytes revisi sebelumnya
+/// tidak berubah. Sebelum output disimpan, hasilnya diperiksa: revisi lama
+/// harus tetap menjadi awal file, dan setiap signature harus memberi hasil
+/// verifikasi yang sama seperti sebelumnya. Jika tidak, output tidak ditulis.
+///
+/// Parameter:
+///   - input: path file PDF yang sudah ditandatangani
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - field: hanya signature field ini (nama lengkap); None = semua
+///   - options: template, bahasa, font, warna, dan logo tampilan baru;
+///     `object_streams` dan `require_pdfa` berlaku untuk update yang ditulis
+///
+/// Return: nama signature field yang tampilannya diganti
+pub fn refresh_appearances(input: &str, output: &str, field: Option<&str>, options: &SignatureOptions) -> Result<Vec<String>> {
+    // Output biasanya sama dengan input; proses lain yang mengubahnya menunggu
+    let _lock = FileLock::exclusive(output)?;
+    // Bytes asli dibaca langsung dari file; hanya update yang ditulis ke buffer sementara
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let mut scratch = ScratchSpace::default().create_over(file)?;
+    let original = sign::load_document(&mut scratch, ParseMode::Lenient)?;
+    if original.trailer.has(b"Encrypt") {
+        bail!("refresh-appearance does not support encrypted documents");
+    }
+    let root_id = form::catalog_id(&original)?;
+    // Level no-changes dan form-filling tidak mengizinkan perubahan annotation
+    if let Some(permissions) = mdp::certification_permissions(&original, root_id).filter(|&permissions| permissions < 3) {
+        bail!(
+            "{} is certified with DocMDP P={}, which does not allow changing signature appearances (only --certify annotations does)",
+            input,
+            permissions
+        );
+    }
+    let mut doc = original.clone();
+
+    let signed: Vec<(String, ObjectId)> = form::terminal_fields(&doc)
+        .into_iter()
+        .filter(|(_, id, field_type)| field_type.as_deref() == Some(b"Sig") && doc.get_dictionary(*id).is_ok_and(|field| field.has(b"V")))
+        .map(|(name, id, _)| (name, id))
+        .filter(|(name, _)| field.is_none_or(|field| field == name))
+        .collect();
+    if signed.is_empty() {
+        match field {
+            Some(field) => bail!("no signed field named {} in {}", field, input),
+            None => return Err(Error::NoSignatures(input.to_string()).into()),
+        }
+    }
+
+    // Font dan ruang warna tampilan untuk dokumen PDF/A
+    let mut pdfa = pdfa::PdfaPlan::new(&doc, options, true)?;
+
+    let mut refreshed = Vec::new();
+    let mut logo = None;
+    let mut font = None;
+    for (name, field_id) in signed {
+        let field_dict = doc.get_dictionary(field_id)?.clone();
+        let Ok((_, Object::Dictionary(sig))) = field_dict.get(b"V").and_then(|value| doc.dereference(value)) else {
+            continue;
+        };
+        let sig = sig.clone();
+
+        // Widget yang terlihat, dan stamp --stamp-only (/NM = nama field widget)
+        let mut targets = form::field_widgets(&doc, field_id, &field_dict);
+        for &widget_id in &form::field_widgets(&doc, field_id, &field_dict) {
+            if let Ok(partial_name) = doc.get_dictionary(widget_id).and_then(|widget| widget.get(b"T")).and_then(Object::as_str) {
+                targets.extend(form::stamps_named(&doc, &text::decode_text_string(partial_name)));
+            }
+        }
+        targets.retain(|&id| is_pdfsign_appearance(&doc, id));
+        if targets.is_empty() {
+            if field.is_some() {
+                bail!("signature {} has no visible appearance created by pdfsign", name);
+            }
+            continue;
+        }
+
+        // Isi teks dari signature dictionary, tampilan dari opsi
+        let value = |key: &[u8]| sig.get(key).and_then(Object::as_str).map(text::decode_text_string).unwrap_or_default();
+        let mut signature_options = options.clone();
+        signature_options.name = value(b"Name");
+        signature_options.reason = value(b"Reason");
+        signature_options.location = value(b"Location");
+        signature_options.contact_info = value(b"ContactInfo");
+        let signed_at = value(b"M");
+        let signed_at = text::parse_pdf_date(&signed_at).map_or(signed_at, |time| time.format(appearance::DATE_FORMAT).to_string());
+
+        for target in targets {
+            let Some([left, bottom, right, top]) = form::annotation_rect(&doc, target) else {
+                continue;
+            };
+            let rect = Rect { left, bottom, right, top };
+            let compiled = options.appearance_cache.get_or_compile(&signature_options, &rect, &pdfa.target)?;
+            // Font dan logo yang sama dipakai bersama oleh semua tampilan di update ini
+            if let (None, Some(image)) = (logo, compiled.image()) {
+                pdfa.check_image(image);
+            }
+            let image_id = compiled.image().map(|image| *logo.get_or_insert_with(|| image.add_to(&mut doc)));
+            let font = font.get_or_insert_with(|| compiled.add_font(&mut doc)).clone();
+
+            let mut stream_dict = Dictionary::new();
+            stream_dict.set("Type", Object::Name(b"XObject".to_vec()));
+            stream_dict.set("Subtype", Object::Name(b"Form".to_vec()));
+            stream_dict.set("FormType", Object::Integer(1));
+            stream_dict.set("BBox", Object::Array(vec![0.into(), 0.into(), Object::Real(rect.width()), Object::Real(rect.height())]));
+            stream_dict.set("Resources", Object::Dictionary(appearance::resources(Some(font), image_id)));
+            let stream_id = doc.add_object(Stream::new(stream_dict, compiled.content(&signed_at)));
+            let mut appearance_dict = Dictionary::new();
+            appearance_dict.set("N", Object::Reference(stream_id));
+            if let Ok(Object::Dictionary(ref mut annotation)) = doc.get_object_mut(target) {
+                annotation.set("AP", Object::Dictionary(appearance_dict));
+            }
+        }
+        refreshed.push(name);
+    }
+    if refreshed.is_empty() {
+        bail!("no signature in {} has a visible appearance created by pdfsign", input);
+    }
+    pdfa.finish(options.require_pdfa)?;
+
+    // Hasil verifikasi sebelum update, untuk dibandingkan dengan hasil sesudahnya
+    let before = verify_scratch(&mut scratch)?;
+    sign::write_update(&mut scratch, &original, &doc, options.object_streams, None)?;
+    check_unchanged(&mut scratch, &before)?;
+
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+    Ok(refreshed)
+}
+
+/// Apakah /AP /N annotation adalah tampilan buatan pdfsign (resource /F1 =
+/// Helvetica atau font TrueType yang disematkan, lihat `appearance::resources`)
+/// dengan kotak yang terlihat
+fn is_pdfsign_appearance(doc: &Document, id: ObjectId) -> bool {
+    let visible = form::annotation_rect(doc, id).is_some_and(|[left, bottom, right, top]| right > left && top > bottom);
+    let font = doc
+        .get_dictionary(id)
+        .and_then(|annotation| annotation.get(b"AP"))
+        .and_then(|ap| doc.dereference(ap))
+        .and_then(|(_, ap)| ap.as_dict())
+        .and_then(|ap| ap.get(b"N"))
+        .and_then(|normal| doc.dereference(normal))
+        .and_then(|(_, normal)| normal.as_stream())
+        .and_then(|normal| normal.dict.get(b"Resources"))
+        .and_then(|resources| doc.dereference(resources))
+        .and_then(|(_, resources)| resources.as_dict())
+        .and_then(|resources| resources.get(b"Font"))
+        .and_then(|fonts| doc.dereference(fonts))
+        .and_then(|(_, fonts)| fonts.as_dict())
+        .and_then(|fonts| fonts.get(b"F1"))
+        .and_then(|font| doc.dereference(font))
+        .and_then(|(_, font)| font.as_dict());
+    let Ok(font) = font else {
+        return false;
+    };
+    let name = |key: &[u8]| font.get(key).and_then(Object::as_name).ok();
+    let embedded = name(b"Subtype") == Some(b"TrueType")
+        && font
+            .get(b"FontDescriptor")
+            .and_then(|descriptor| doc.dereference(descriptor))
+            .and_then(|(_, descriptor)| descriptor.as_dict())
+            .is_ok_and(|descriptor| descriptor.has(b"FontFile2"));
+    visible && (name(b"BaseFont") == Some(b"Helvetica") || embedded)
+}
+
+/// Verifikasi seluruh isi buffer sementara (tanpa kunci publik dan sumber trust)
+fn verify_scratch(scratch: &mut (impl Read + Seek)) -> Result<(Vec<u8>, Vec<SignatureReport>)> {
+    let mut bytes = Vec::new();
+    scratch.seek(SeekFrom::Start(0))?;
+    scratch.read_to_end(&mut bytes)?;
+    let doc = Document::load_mem(&bytes)?;
+    let reports = verify::verify_document(&doc, &bytes, None, &AlgorithmPolicy::default(), &[], None);
+    Ok((bytes, reports))
+}
+
+/// Pastikan update tidak mengubah apa pun yang dicakup signature: revisi
+/// lama tetap menjadi awal file tanpa perubahan, dan setiap signature
+/// memberi status yang sama dengan ByteRange yang sama
+fn check_unchanged(scratch: &mut (impl Read + Seek), before: &(Vec<u8>, Vec<SignatureReport>)) -> Result<()> {
+    let (original, before) = before;
+    let (bytes, after) = verify_scratch(scratch)?;
+    if !bytes.starts_with(original) {
+        bail!("the update would change bytes of earlier revisions; the output was not written");
+    }
+    for report in before {
+        match after.iter().find(|after| after.field == report.field) {
+            Some(after) if after.status == report.status && after.byte_range == report.byte_range => {}
+            Some(after) => bail!(
+                "signature {} would change from {:?} to {:?} after refreshing its appearance; the output was not written",
+                report.field,
+                report.status,
+                after.status
+            ),
+            None => bail!("signature {} is missing after refreshing the appearance; the output was not written", report.field),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,251 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream}; // Struktur dasar PDF
+use std::io::Write; // Menulis hasil ke buffer sementara
+
+use crate::error::Error; // Dokumen tanpa signature
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::form; // Signature field, widget, dan AcroForm
+use crate::pdf::syntax::{self, ParseMode}; // Struktur file dibaca dalam mode lenient
+use crate::pdf::text::decode_text_string; // Nama field (/T) sebagai text string
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+
+/// Signature yang dihapus oleh `remove_signatures`
+#[derive(Clone, Debug)]
+pub enum Selection {
+    /// Satu signature field berdasarkan nama lengkapnya (`parent.child`)
+    Field(String),
+    /// Semua signature di dokumen
+    All,
+}
+
+/// Hapus signature dari dokumen, misalnya agar dokumen bisa diperbaiki lalu
+/// ditandatangani ulang
+///
+/// Nilai /V field dihapus; widget-nya diberi tampilan kosong sehingga field
+/// bisa ditandatangani lagi dengan --field-name, atau dihapus seluruhnya
+/// bersama field-nya jika `remove_fields`. Stamp dari --stamp-only (/NM sama
+/// dengan nama field) dan entry /Perms /DocMDP milik signature itu ikut
+/// dihapus. Jika tidak ada signature yang tersisa, /SigFlags dan /DSS dihapus,
+/// dan AcroForm tanpa field dihapus dari catalog.
+///
+/// Dokumen ditulis ulang seluruhnya, bukan sebagai incremental update: revisi
+/// lama masih memuat signature yang dihapus. Akibatnya signature lain yang
+/// tersisa tidak lagi cocok dengan bytes file (ditampilkan sebagai peringatan).
+///
+/// Parameter:
+///   - input: path file PDF yang sudah ditandatangani
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - selection: satu signature field atau semua signature
+///   - remove_fields: hapus juga signature field dan widget-nya
+///
+/// Return: nama lengkap signature field yang signature-nya dihapus
+pub fn remove_signatures(input: &str, output: &str, selection: &Selection, remove_fields: bool) -> Result<Vec<String>> {
+    // Output biasanya sama dengan input; proses lain yang mengubahnya menunggu
+    let _lock = FileLock::exclusive(output)?;
+    // Seluruh isi stream dimuat karena semua object ditulis ulang
+    let bytes = std::fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let mut doc = Document::load_mem(&bytes).with_context(|| format!("cannot parse {}", input))?;
+    syntax::warn(&syntax::check_document(&bytes, &mut doc, ParseMode::Lenient)?);
+    drop(bytes);
+    if doc.trailer.has(b"Encrypt") {
+        bail!("{} is encrypted; remove-signature cannot rewrite encrypted documents", input);
+    }
+    doc.max_id = doc.max_id.max(doc.objects.keys().map(|&(id, _)| id).max().unwrap_or(0));
+
+    let signed: Vec<(String, ObjectId)> = form::terminal_fields(&doc)
+        .into_iter()
+        .filter(|(_, id, field_type)| field_type.as_deref() == Some(b"Sig") && doc.get_dictionary(*id).is_ok_and(|field| field.has(b"V")))
+        .map(|(name, id, _)| (name, id))
+        .collect();
+    if signed.is_empty() {
+        return Err(Error::NoSignatures(input.to_string()).into());
+    }
+    let selected = match selection {
+        Selection::All => signed.clone(),
+        Selection::Field(name) => match signed.iter().find(|(full_name, _)| full_name == name) {
+            Some(field) => vec![field.clone()],
+            None => {
+                let names: Vec<&str> = signed.iter().map(|(full_name, _)| full_name.as_str()).collect();
+                bail!("no signed field named {} (signed fields: {})", name, names.join(", "));
+            }
+        },
+    };
+
+    let root_id = form::catalog_id(&doc)?;
+    for (_, field_id) in &selected {
+        let field = doc.get_dictionary(*field_id)?.clone();
+        let widgets = form::field_widgets(&doc, *field_id, &field);
+
+        // Stamp dari --stamp-only tidak terhubung ke field selain lewat /NM
+        let mut annotations = match field.get(b"T").and_then(Object::as_str) {
+            Ok(name) => form::stamps_named(&doc, &decode_text_string(name)),
+            Err(_) => Vec::new(),
+        };
+        if remove_fields {
+            annotations.extend(&widgets);
+            detach_field(&mut doc, root_id, *field_id)?;
+        } else {
+            if let Ok(Object::Dictionary(ref mut field)) = doc.get_object_mut(*field_id) {
+                field.remove(b"V");
+            }
+            for &widget_id in &widgets {
+                blank_appearance(&mut doc, widget_id);
+            }
+        }
+        for page_id in doc.get_pages().into_values() {
+            form::remove_annotations(&mut doc, page_id, &annotations)?;
+        }
+        if let Ok(signature_id) = field.get(b"V").and_then(Object::as_reference) {
+            remove_certification(&mut doc, root_id, signature_id)?;
+        }
+    }
+
+    // Tanpa signature yang tersisa, /SigFlags dan data validasi di /DSS tidak lagi berarti
+    let remaining = form::signature_fields(&doc);
+    if remaining.is_empty() {
+        if let Some(mut acroform) = form::existing_acroform(&doc, root_id) {
+            let fields = acroform
+                .get(b"Fields")
+                .and_then(|fields| doc.dereference(fields))
+                .and_then(|(_, fields)| fields.as_array())
+                .map_or(0, Vec::len);
+            if fields == 0 {
+                if let Ok(Object::Dictionary(ref mut catalog)) = doc.get_object_mut(root_id) {
+                    catalog.remove(b"AcroForm");
+                }
+            } else {
+                acroform.remove(b"SigFlags");
+                form::store_acroform(&mut doc, root_id, acroform)?;
+            }
+        }
+        if let Ok(Object::Dictionary(ref mut catalog)) = doc.get_object_mut(root_id) {
+            catalog.remove(b"DSS");
+        }
+    } else {
+        let names: Vec<&str> = remaining.iter().map(|field| field.name.as_str()).collect();
+        crate::warning!(
+            "the document is rewritten, so the remaining signature(s) no longer verify: {} (remove them as well with --all, or sign the corrected document again)",
+            names.join(", ")
+        );
+    }
+
+    // Tulis ulang sebagai satu revisi: trailer tidak lagi menunjuk ke xref lama,
+    // dan object yang tidak terpakai (signature, revisi lama) dibuang
+    for key in [&b"Prev"[..], b"XRefStm", b"DecodeParms"] {
+        doc.trailer.remove(key);
+    }
+    doc.prune_objects();
+    let mut scratch = ScratchSpace::default().create()?;
+    doc.save_to(&mut scratch)?;
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+
+    Ok(selected.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Ganti /AP widget dengan tampilan kosong seukuran /Rect-nya
+fn blank_appearance(doc: &mut Document, widget_id: ObjectId) {
+    let rect = form::annotation_rect(doc, widget_id).unwrap_or_default();
+    let mut stream_dict = Dictionary::new();
+    stream_dict.set("Type", Object::Name(b"XObject".to_vec()));
+    stream_dict.set("Subtype", Object::Name(b"Form".to_vec()));
+    stream_dict.set("BBox", Object::Array(vec![0.into(), 0.into(), (rect[2] - rect[0]).into(), (rect[3] - rect[1]).into()]));
+    let stream_id = doc.add_object(Stream::new(stream_dict, Vec::new()));
+    let mut appearance_dict = Dictionary::new();
+    appearance_dict.set("N", Object::Reference(stream_id));
+    if let Ok(Object::Dictionary(ref mut widget)) = doc.get_object_mut(widget_id) {
+        widget.set("AP", Object::Dictionary(appearance_dict));
+    }
+}
+
+/// Lepaskan field dari /Kids parent-nya atau dari /Fields AcroForm; parent
+/// yang tidak lagi punya kids ikut dilepaskan
+fn detach_field(doc: &mut Document, root_id: ObjectId, field_id: ObjectId) -> Result<()> {
+    let mut id = field_id;
+    // Batasi kedalaman untuk menghindari loop pada dokumen yang rusak
+    for _ in 0..32 {
+        match doc.get_dictionary(id)?.get(b"Parent").and_then(Object::as_reference) {
+            Ok(parent_id) => {
+                let kids = doc.get_dictionary(parent_id)?.get(b"Kids").ok().cloned();
+                let (inline, remaining) = without_reference(doc, kids, id);
+                if let (Some(kids), Ok(Object::Dictionary(ref mut parent))) = (inline, doc.get_object_mut(parent_id)) {
+                    parent.set("Kids", kids);
+                }
+                if remaining > 0 {
+                    return Ok(());
+                }
+                id = parent_id;
+            }
+            Err(_) => {
+                let Some(mut acroform) = form::existing_acroform(doc, root_id) else {
+                    return Ok(());
+                };
+                let (inline, _) = without_reference(doc, acroform.get(b"Fields").ok().cloned(), id);
+                if let Some(fields) = inline {
+                    acroform.set("Fields", fields);
+                    form::store_acroform(doc, root_id, acroform)?;
+                }
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Hapus reference ke `id` dari array; array indirect diubah di tempatnya,
+/// array langsung dikembalikan untuk ditulis ulang oleh pemanggil
+///
+/// Return: array langsung yang baru (jika ada) dan jumlah elemen yang tersisa
+fn without_reference(doc: &mut Document, array: Option<Object>, id: ObjectId) -> (Option<Object>, usize) {
+    let keep = |item: &Object| item.as_reference().ok() != Some(id);
+    match array {
+        Some(Object::Reference(array_id)) => match doc.get_object_mut(array_id) {
+            Ok(Object::Array(ref mut items)) => {
+                items.retain(keep);
+                (None, items.len())
+            }
+            _ => (None, 0),
+        },
+        Some(Object::Array(mut items)) => {
+            items.retain(keep);
+            let remaining = items.len();
+            (Some(Object::Array(items)), remaining)
+        }
+        _ => (None, 0),
+    }
+}
+
+/// Hapus /Perms /DocMDP jika menunjuk ke signature yang dihapus; /Perms yang
+/// menjadi kosong ikut dihapus
+fn remove_certification(doc: &mut Document, root_id: ObjectId, signature_id: ObjectId) -> Result<()> {
+    let perms = doc.get_dictionary(root_id)?.get(b"Perms").ok().cloned();
+    let (perms_id, mut perms) = match perms {
+        Some(Object::Reference(perms_id)) => match doc.get_dictionary(perms_id) {
+            Ok(perms) => (Some(perms_id), perms.clone()),
+            Err(_) => return Ok(()),
+        },
+        Some(Object::Dictionary(perms)) => (None, perms),
+        _ => return Ok(()),
+    };
+    if perms.get(b"DocMDP").and_then(Object::as_reference).ok() != Some(signature_id) {
+        return Ok(());
+    }
+    perms.remove(b"DocMDP");
+
+    match (perms.is_empty(), perms_id) {
+        (false, Some(perms_id)) => {
+            doc.objects.insert(perms_id, Object::Dictionary(perms));
+        }
+        (empty, _) => {
+            if let Ok(Object::Dictionary(ref mut catalog)) = doc.get_object_mut(root_id) {
+                if empty {
+                    catalog.remove(b"Perms");
+                } else {
+                    catalog.set("Perms", Object::Dictionary(perms));
+                }
+            }
+        }
+    }
+    Ok(())
+}
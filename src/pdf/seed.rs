@@ -0,0 +1,414 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Dictionary, Document, Object, ObjectId, StringFormat}; // Struktur dasar PDF
+use x509_cert::der::Decode; // Parsing sertifikat DER
+use x509_cert::ext::pkix::{CertificatePolicies, KeyUsage}; // Extension yang dibatasi /Cert
+use x509_cert::Certificate; // Sertifikat X.509
+
+use crate::crypto::digest::DigestAlgorithm; // Nama /DigestMethod
+use crate::pdf::options::SignatureOptions; // Opsi signature yang diperiksa terhadap /SV
+use crate::pdf::text::{decode_text_string, text_string}; // Text string PDF (/Reasons, /SubjectDN)
+
+// Bit /Ff seed value dictionary (ISO 32000-1, tabel 234)
+const SV_FILTER: i64 = 1;
+const SV_SUB_FILTER: i64 = 1 << 1;
+const SV_REASONS: i64 = 1 << 3;
+const SV_DIGEST_METHOD: i64 = 1 << 6;
+
+// Bit /Ff certificate seed value dictionary (tabel 235)
+const SV_CERT_SUBJECT: i64 = 1;
+const SV_CERT_ISSUER: i64 = 1 << 1;
+const SV_CERT_OID: i64 = 1 << 2;
+const SV_CERT_SUBJECT_DN: i64 = 1 << 3;
+const SV_CERT_KEY_USAGE: i64 = 1 << 5;
+
+/// Batasan yang wajib dipenuhi penandatangan berikutnya (bit /Ff); batasan
+/// lain hanya menjadi saran bagi viewer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum SeedConstraint {
+    /// Alasan harus salah satu dari /Reasons
+    Reasons,
+    /// Digest signature harus salah satu dari /DigestMethod
+    DigestMethods,
+    /// SubFilter signature harus salah satu dari /SubFilter
+    SubFilters,
+    /// Signature harus diberi timestamp dari TSA di /TimeStamp
+    Timestamp,
+    /// Sertifikat penandatangan harus salah satu dari /Cert /Subject
+    Subjects,
+    /// Sertifikat penandatangan harus diterbitkan salah satu dari /Cert /Issuer
+    Issuers,
+    /// Subject penandatangan harus cocok dengan salah satu /Cert /SubjectDN
+    SubjectDn,
+    /// Key usage sertifikat harus cocok dengan salah satu /Cert /KeyUsage
+    KeyUsage,
+    /// Sertifikat harus memuat salah satu certificate policy /Cert /OID
+    Oids,
+}
+
+impl SeedConstraint {
+    /// Nama seperti di file seed value (`required`)
+    pub fn name(self) -> &'static str {
+        match self {
+            SeedConstraint::Reasons => "reasons",
+            SeedConstraint::DigestMethods => "digest_methods",
+            SeedConstraint::SubFilters => "sub_filters",
+            SeedConstraint::Timestamp => "timestamp",
+            SeedConstraint::Subjects => "subjects",
+            SeedConstraint::Issuers => "issuers",
+            SeedConstraint::SubjectDn => "subject_dn",
+            SeedConstraint::KeyUsage => "key_usage",
+            SeedConstraint::Oids => "oids",
+        }
+    }
+}
+
+/// Seed value dictionary (/SV) untuk signature field kosong: batasan yang
+/// diterapkan viewer yang patuh (dan `pdfsign sign --field-name`) pada
+/// penandatangan berikutnya
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeedValue {
+    pub reasons: Vec<String>,                 // /Reasons: alasan yang boleh dipilih
+    pub digest_methods: Vec<DigestAlgorithm>, // /DigestMethod
+    pub sub_filters: Vec<String>,             // /SubFilter, misalnya ETSI.CAdES.detached
+    pub timestamp_url: Option<String>,        // /TimeStamp /URL: TSA yang dipakai
+    pub certificate: CertificateSeed,         // /Cert: batasan sertifikat penandatangan
+    pub required: Vec<SeedConstraint>,        // Batasan yang wajib (bukan sekadar saran)
+}
+
+/// Batasan sertifikat penandatangan (/Cert, certificate seed value dictionary)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CertificateSeed {
+    pub subjects: Vec<Vec<u8>>,                 // /Subject: sertifikat DER yang boleh menandatangani
+    pub issuers: Vec<Vec<u8>>,                  // /Issuer: sertifikat DER penerbit yang diterima
+    pub subject_dn: Vec<Vec<(String, String)>>, // /SubjectDN: atribut (CN, O, ...) yang harus cocok semua
+    pub key_usage: Vec<String>,                 // /KeyUsage: 9 karakter 0/1/X per bit key usage
+    pub oids: Vec<String>,                      // /OID: certificate policy
+}
+
+impl CertificateSeed {
+    /// Tidak ada batasan sertifikat sama sekali
+    pub fn is_empty(&self) -> bool {
+        self.subjects.is_empty() && self.issuers.is_empty() && self.subject_dn.is_empty() && self.key_usage.is_empty() && self.oids.is_empty()
+    }
+}
+
+impl SeedValue {
+    /// Periksa nilai yang tidak bisa ditulis sebagai seed value yang valid
+    pub fn validate(&self) -> Result<()> {
+        for pattern in &self.certificate.key_usage {
+            if pattern.is_empty() || pattern.len() > 9 || !pattern.chars().all(|c| matches!(c, '0' | '1' | 'X')) {
+                bail!("invalid key usage pattern `{}` (up to 9 characters of 0, 1 or X, one per key usage bit)", pattern);
+            }
+        }
+        for oid in &self.certificate.oids {
+            oid.parse::<x509_cert::der::asn1::ObjectIdentifier>().map_err(|_| anyhow!("invalid certificate policy OID `{}`", oid))?;
+        }
+        let listed = |constraint: SeedConstraint| match constraint {
+            SeedConstraint::Reasons => !self.reasons.is_empty(),
+            SeedConstraint::DigestMethods => !self.digest_methods.is_empty(),
+            SeedConstraint::SubFilters => !self.sub_filters.is_empty(),
+            SeedConstraint::Timestamp => self.timestamp_url.is_some(),
+            SeedConstraint::Subjects => !self.certificate.subjects.is_empty(),
+            SeedConstraint::Issuers => !self.certificate.issuers.is_empty(),
+            SeedConstraint::SubjectDn => !self.certificate.subject_dn.is_empty(),
+            SeedConstraint::KeyUsage => !self.certificate.key_usage.is_empty(),
+            SeedConstraint::Oids => !self.certificate.oids.is_empty(),
+        };
+        if let Some(constraint) = self.required.iter().find(|&&constraint| !listed(constraint)) {
+            bail!("`{}` is required but lists no values", constraint.name());
+        }
+        Ok(())
+    }
+
+    /// Seed value dictionary (/Type /SV) untuk /SV signature field
+    pub fn dictionary(&self) -> Dictionary {
+        let required = |constraint| self.required.contains(&constraint);
+        let mut sv = Dictionary::new();
+        sv.set("Type", Object::Name(b"SV".to_vec()));
+        // pdfsign (dan hampir semua viewer) hanya mengenal handler Adobe.PPKLite
+        sv.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
+        let mut flags = 0;
+        if !self.reasons.is_empty() {
+            sv.set("Reasons", Object::Array(self.reasons.iter().map(|reason| text_string(reason)).collect()));
+            if required(SeedConstraint::Reasons) {
+                flags |= SV_REASONS;
+            }
+        }
+        if !self.digest_methods.is_empty() {
+            let names = self.digest_methods.iter().map(|digest| Object::Name(digest_method(*digest).into_bytes())).collect();
+            sv.set("DigestMethod", Object::Array(names));
+            if required(SeedConstraint::DigestMethods) {
+                flags |= SV_DIGEST_METHOD;
+            }
+        }
+        if !self.sub_filters.is_empty() {
+            sv.set("SubFilter", Object::Array(self.sub_filters.iter().map(|name| Object::Name(name.as_bytes().to_vec())).collect()));
+            if required(SeedConstraint::SubFilters) {
+                flags |= SV_SUB_FILTER;
+            }
+        }
+        if let Some(url) = &self.timestamp_url {
+            let mut timestamp = Dictionary::new();
+            timestamp.set("URL", Object::String(url.as_bytes().to_vec(), StringFormat::Literal));
+            timestamp.set("Ff", Object::Integer(i64::from(required(SeedConstraint::Timestamp))));
+            sv.set("TimeStamp", Object::Dictionary(timestamp));
+        }
+        if !self.certificate.is_empty() {
+            sv.set("Cert", Object::Dictionary(self.certificate_dictionary()));
+        }
+        sv.set("Ff", Object::Integer(flags));
+        sv
+    }
+
+    /// Certificate seed value dictionary (/Type /SVCert)
+    fn certificate_dictionary(&self) -> Dictionary {
+        let seed = &self.certificate;
+        let required = |constraint| self.required.contains(&constraint);
+        let der_strings = |certificates: &[Vec<u8>]| Object::Array(certificates.iter().map(|der| Object::String(der.clone(), StringFormat::Hexadecimal)).collect());
+        let mut cert = Dictionary::new();
+        cert.set("Type", Object::Name(b"SVCert".to_vec()));
+        let mut flags = 0;
+        if !seed.subjects.is_empty() {
+            cert.set("Subject", der_strings(&seed.subjects));
+            if required(SeedConstraint::Subjects) {
+                flags |= SV_CERT_SUBJECT;
+            }
+        }
+        if !seed.issuers.is_empty() {
+            cert.set("Issuer", der_strings(&seed.issuers));
+            if required(SeedConstraint::Issuers) {
+                flags |= SV_CERT_ISSUER;
+            }
+        }
+        if !seed.subject_dn.is_empty() {
+            let names = seed
+                .subject_dn
+                .iter()
+                .map(|attributes| {
+                    let mut name = Dictionary::new();
+                    for (attribute, value) in attributes {
+                        name.set(attribute.as_bytes().to_vec(), text_string(value));
+                    }
+                    Object::Dictionary(name)
+                })
+                .collect();
+            cert.set("SubjectDN", Object::Array(names));
+            if required(SeedConstraint::SubjectDn) {
+                flags |= SV_CERT_SUBJECT_DN;
+            }
+        }
+        if !seed.key_usage.is_empty() {
+            cert.set("KeyUsage", Object::Array(seed.key_usage.iter().map(|pattern| Object::String(pattern.as_bytes().to_vec(), StringFormat::Literal)).collect()));
+            if required(SeedConstraint::KeyUsage) {
+                flags |= SV_CERT_KEY_USAGE;
+            }
+        }
+        if !seed.oids.is_empty() {
+            cert.set("OID", Object::Array(seed.oids.iter().map(|oid| Object::String(oid.as_bytes().to_vec(), StringFormat::Literal)).collect()));
+            if required(SeedConstraint::Oids) {
+                flags |= SV_CERT_OID;
+            }
+        }
+        cert.set("Ff", Object::Integer(flags));
+        cert
+    }
+}
+
+/// Nama /DigestMethod (SHA256, SHA384, SHA512)
+fn digest_method(digest: DigestAlgorithm) -> String {
+    digest.name().to_ascii_uppercase()
+}
+
+/// Periksa signature yang akan dibuat terhadap batasan wajib /SV signature
+/// field yang diisi (--field-name), seperti yang dilakukan viewer yang patuh
+///
+/// Hanya batasan dengan bit /Ff yang ditegakkan; sisanya saran untuk viewer.
+/// Batasan sertifikat dilewati jika sertifikat penandatangan belum diketahui
+/// (`prepare` tanpa --certificate).
+///
+/// Parameter:
+///   - doc: dokumen yang akan ditandatangani
+///   - field_id: signature field kosong yang diisi
+///   - name: nama lengkap field (untuk pesan error)
+///   - options: opsi signature (alasan, digest, SubFilter dari PAdES, TSA)
+///   - certificates: sertifikat penandatangan diikuti rantainya (boleh kosong)
+pub fn check(doc: &Document, field_id: ObjectId, name: &str, options: &SignatureOptions, certificates: &[Vec<u8>]) -> Result<()> {
+    let Some(sv) = doc
+        .get_dictionary(field_id)
+        .ok()
+        .and_then(|field| field.get(b"SV").ok())
+        .and_then(|sv| doc.dereference(sv).ok())
+        .and_then(|(_, sv)| sv.as_dict().ok())
+    else {
+        return Ok(());
+    };
+    let flags = sv.get(b"Ff").and_then(Object::as_i64).unwrap_or(0);
+    let refuse = |message: String| anyhow!("signature field {} does not accept this signature: {}", name, message);
+
+    if flags & SV_FILTER != 0 {
+        if let Ok(filter) = sv.get(b"Filter").and_then(Object::as_name) {
+            if filter != b"Adobe.PPKLite" {
+                return Err(refuse(format!("it requires the {} signature handler", String::from_utf8_lossy(filter))));
+            }
+        }
+    }
+    if flags & SV_SUB_FILTER != 0 {
+        let allowed = names(doc, sv, b"SubFilter");
+        let sub_filter = if options.pades.is_some() { "ETSI.CAdES.detached" } else { "adbe.pkcs7.detached" };
+        if !allowed.is_empty() && !allowed.iter().any(|name| name == sub_filter) {
+            return Err(refuse(format!("SubFilter {} is not one of {} (choose it with or without --pades-level)", sub_filter, allowed.join(", "))));
+        }
+    }
+    if flags & SV_REASONS != 0 {
+        let allowed = text_strings(doc, sv, b"Reasons");
+        if !allowed.is_empty() && !allowed.contains(&options.reason) {
+            return Err(refuse(format!("reason \"{}\" is not one of: {} (--reason)", options.reason, allowed.join("; "))));
+        }
+    }
+    if flags & SV_DIGEST_METHOD != 0 {
+        let allowed = names(doc, sv, b"DigestMethod");
+        if !allowed.is_empty() && !allowed.iter().any(|name| name.eq_ignore_ascii_case(&digest_method(options.digest))) {
+            return Err(refuse(format!("digest {} is not one of {} (--digest)", options.digest.label(), allowed.join(", "))));
+        }
+    }
+    if let Some(timestamp) = dictionary(doc, sv, b"TimeStamp") {
+        let url = timestamp.get(b"URL").and_then(Object::as_str).ok().map(|url| String::from_utf8_lossy(url).into_owned());
+        if let (Some(url), 1) = (url, timestamp.get(b"Ff").and_then(Object::as_i64).unwrap_or(0)) {
+            if options.timestamp.as_ref().map(|tsa| tsa.url.as_str()) != Some(url.as_str()) {
+                return Err(refuse(format!("it requires a timestamp from {} (--tsa-url {})", url, url)));
+            }
+        }
+    }
+    if let Some(cert) = dictionary(doc, sv, b"Cert") {
+        match certificates.first() {
+            Some(signer) => check_certificate(doc, cert, signer, certificates).map_err(|e| refuse(e.to_string()))?,
+            None => crate::warning!("signature field {} restricts the signing certificate, which is not known yet; it is not checked", name),
+        }
+    }
+    Ok(())
+}
+
+/// Periksa sertifikat penandatangan terhadap batasan wajib /Cert
+fn check_certificate(doc: &Document, seed: &Dictionary, signer: &[u8], chain: &[Vec<u8>]) -> Result<()> {
+    let flags = seed.get(b"Ff").and_then(Object::as_i64).unwrap_or(0);
+    let certificate = Certificate::from_der(signer).map_err(|e| anyhow!("invalid signing certificate: {}", e))?;
+    let tbs = &certificate.tbs_certificate;
+
+    if flags & SV_CERT_SUBJECT != 0 {
+        let allowed = byte_strings(doc, seed, b"Subject");
+        if !allowed.is_empty() && !allowed.iter().any(|der| der == signer) {
+            bail!("the signing certificate ({}) is not one of the {} allowed certificate(s)", tbs.subject, allowed.len());
+        }
+    }
+    if flags & SV_CERT_ISSUER != 0 {
+        let issuers: Vec<String> = byte_strings(doc, seed, b"Issuer")
+            .iter()
+            .filter_map(|der| Certificate::from_der(der).ok())
+            .map(|issuer| issuer.tbs_certificate.subject.to_string())
+            .collect();
+        let issued = chain
+            .iter()
+            .filter_map(|der| Certificate::from_der(der).ok())
+            .any(|cert| issuers.contains(&cert.tbs_certificate.issuer.to_string()));
+        if !issuers.is_empty() && !issued {
+            bail!("the signing certificate was not issued by {}", issuers.join(" or "));
+        }
+    }
+    if flags & SV_CERT_SUBJECT_DN != 0 {
+        let subject: Vec<(String, String)> = tbs
+            .subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .filter_map(|attribute| attribute.to_string().split_once('=').map(|(key, value)| (key.to_ascii_uppercase(), value.to_string())))
+            .collect();
+        let names: Vec<Vec<(String, String)>> = seed
+            .get(b"SubjectDN")
+            .and_then(|names| doc.dereference(names))
+            .and_then(|(_, names)| names.as_array())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| doc.dereference(name).ok()?.1.as_dict().ok())
+                    .map(|name| {
+                        name.iter()
+                            .filter_map(|(key, value)| Some((String::from_utf8_lossy(key).to_ascii_uppercase(), decode_text_string(value.as_str().ok()?))))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !names.is_empty() && !names.iter().any(|name| name.iter().all(|attribute| subject.contains(attribute))) {
+            bail!("the signing certificate's subject ({}) does not match the required subject DN", tbs.subject);
+        }
+    }
+    if flags & SV_CERT_KEY_USAGE != 0 {
+        let patterns: Vec<String> = byte_strings(doc, seed, b"KeyUsage").iter().map(|pattern| String::from_utf8_lossy(pattern).into_owned()).collect();
+        let bits = match tbs.get::<KeyUsage>() {
+            Ok(Some((_, usage))) => [
+                usage.digital_signature(),
+                usage.non_repudiation(),
+                usage.key_encipherment(),
+                usage.data_encipherment(),
+                usage.key_agreement(),
+                usage.key_cert_sign(),
+                usage.crl_sign(),
+                usage.encipher_only(),
+                usage.decipher_only(),
+            ],
+            _ => [false; 9],
+        };
+        let matches = |pattern: &String| {
+            pattern.chars().zip(bits).all(|(wanted, set)| match wanted {
+                '1' => set,
+                '0' => !set,
+                _ => true,
+            })
+        };
+        if !patterns.is_empty() && !patterns.iter().any(matches) {
+            bail!("the signing certificate's key usage does not match {}", patterns.join(" or "));
+        }
+    }
+    if flags & SV_CERT_OID != 0 {
+        let allowed: Vec<String> = byte_strings(doc, seed, b"OID").iter().map(|oid| String::from_utf8_lossy(oid).into_owned()).collect();
+        let policies: Vec<String> = match tbs.get::<CertificatePolicies>() {
+            Ok(Some((_, policies))) => policies.0.iter().map(|policy| policy.policy_identifier.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|oid| policies.contains(oid)) {
+            bail!("the signing certificate has none of the certificate policies {}", allowed.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Dictionary di `key` (langsung atau lewat referensi)
+fn dictionary<'a>(doc: &'a Document, dict: &'a Dictionary, key: &[u8]) -> Option<&'a Dictionary> {
+    dict.get(key).and_then(|value| doc.dereference(value)).and_then(|(_, value)| value.as_dict()).ok()
+}
+
+/// Isi array di `key` (langsung atau lewat referensi)
+fn array<'a>(doc: &'a Document, dict: &'a Dictionary, key: &[u8]) -> Vec<&'a Object> {
+    dict.get(key)
+        .and_then(|value| doc.dereference(value))
+        .and_then(|(_, value)| value.as_array())
+        .map(|items| items.iter().filter_map(|item| doc.dereference(item).ok().map(|(_, item)| item)).collect())
+        .unwrap_or_default()
+}
+
+/// Array name di `key`, misalnya /SubFilter dan /DigestMethod
+fn names(doc: &Document, dict: &Dictionary, key: &[u8]) -> Vec<String> {
+    array(doc, dict, key).into_iter().filter_map(|item| item.as_name().ok()).map(|name| String::from_utf8_lossy(name).into_owned()).collect()
+}
+
+/// Array text string di `key`, misalnya /Reasons
+fn text_strings(doc: &Document, dict: &Dictionary, key: &[u8]) -> Vec<String> {
+    array(doc, dict, key).into_iter().filter_map(|item| item.as_str().ok()).map(decode_text_string).collect()
+}
+
+/// Array byte string di `key`, misalnya sertifikat DER di /Subject
+fn byte_strings(doc: &Document, dict: &Dictionary, key: &[u8]) -> Vec<Vec<u8>> {
+    array(doc, dict, key).into_iter().filter_map(|item| item.as_str().ok()).map(<[u8]>::to_vec).collect()
+}
@@ -1,58 +1,855 @@
 // Import library yang diperlukan
-use anyhow::Result;  // Untuk error handling yang flexible
-use std::fs;        // Untuk membaca dan menulis file
-use lopdf::Document; // Untuk manipulasi dokumen PDF
+use anyhow::{anyhow, bail, Context, Result};  // Untuk error handling yang flexible
+use std::fs::File; // Dokumen input dibaca langsung dari file
+use std::path::Path; // Path input, output, dan kunci
+use std::io::{Read, Seek, SeekFrom, Write}; // Menambal buffer hasil serialisasi
+use lopdf::{Document, Object}; // Untuk manipulasi dokumen PDF
+use der::Decode; // Sertifikat penandatangan (--dry-run)
+use x509_cert::Certificate; // Mencocokkan sertifikat dengan kunci (--dry-run)
 
-use crate::crypto::ecc::sign; // Fungsi untuk sign data dengan ECDSA
+use crate::cancel::CancellationToken; // Pembatalan dan timeout
+use crate::crypto::cert::{self, certificates_for_key, CertificateInfo}; // certificate.der di sebelah kunci, ringkasan sertifikat, kredensial uji
+#[cfg(feature = "network")]
+use crate::crypto::cms::add_signature_timestamp; // Timestamp RFC 3161 di CMS SignedData
+use crate::crypto::cms::{build_signed_data, SignedAttributes}; // CMS SignedData
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::p12::load_pkcs12; // Bundle PKCS#12
+use crate::crypto::keystore::load_keystore; // Kunci di keystore OS
+use crate::crypto::kms::load_kms; // Kunci di KMS cloud
+use crate::crypto::pkcs11::load_pkcs11; // Kunci di smart card / token PKCS#11
+#[cfg(feature = "network")]
+use crate::crypto::revocation::fetch_revocation_data_with; // OCSP/CRL untuk PAdES B-LT
+use crate::crypto::signer::{load_signer, Credentials, Signer}; // Backend penandatanganan (ECDSA / RSA)
+#[cfg(feature = "network")]
+use crate::crypto::tsa; // Request ke Time Stamping Authority
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::log::Progress; // Progress bar hashing dokumen besar
+use crate::pdf::hooks::{NoHooks, SigningHooks}; // Hook di sekitar pipeline signing
+use crate::pdf::classify::{self, DocumentView}; // Hook klasifikasi sebelum signing
+use crate::pdf::appearance; // Teks tampilan signature
+use crate::pdf::budget; // Peringatan ukuran incremental update
+use crate::pdf::attachment_signatures; // Signature .p7s terpisah untuk file lampiran
+use crate::pdf::encryption::SecurityHandler; // Dokumen terenkripsi (--pdf-password)
+use crate::pdf::form; // Penanganan AcroForm yang sudah ada
+use crate::pdf::mdp; // Certification signature (DocMDP)
+use crate::pdf::naming; // Waktu signing default (mode deterministik)
+use crate::pdf::pages; // Kotak halaman (/CropBox, /MediaBox) yang diwarisi, pemeriksaan page tree
+use crate::pdf::pdfa; // Kesesuaian PDF/A (--require-pdfa)
+use crate::pdf::placement; // Aturan posisi berdasarkan ciri dokumen
+use crate::pdf::seed; // Batasan /SV signature field yang diisi (--field-name)
+use crate::pdf::options::{AnnotationFlags, Placement, Rect, SignatureOptions, DEFAULT_RECT, DEFAULT_RECT_GAP}; // Opsi penandatanganan
+use crate::pdf::syntax::{self, ParseMode, SyntaxIssue}; // Pemeriksaan struktur file (--strict-parse)
+use crate::pdf::text; // Text string PDF (PDFDocEncoding / UTF-16BE)
+use crate::pdf::update; // Serialisasi incremental update (--object-streams)
+#[cfg(feature = "network")]
+use crate::pdf::pades::{self, PadesLevel}; // Level PAdES baseline (DSS, document timestamp)
+use crate::scratch::Scratch; // Buffer sementara hasil serialisasi
+use crate::storage::{DocumentSink, DocumentSource, FileSink, ReaderSource, WriterSink}; // Input dan output CLI berupa file atau stdin/stdout
 
-/// Struktur untuk menyimpan metadata signature
-/// Informasi ini akan ditampilkan di signature panel di Adobe Reader
-pub struct SignatureMetadata {
-    pub name: String,        // Nama penandatangan
-    pub reason: String,      // Alasan penandatanganan
-    pub location: String,    // Lokasi penandatanganan
-    pub contact_info: String, // Informasi kontak penandatangan
-}
+/// Ruang minimum /Contents signature (dalam bytes, sebelum hex)
+const MIN_PLACEHOLDER_SIZE: usize = 4096;
+
+/// Perkiraan ruang CMS di luar sertifikat dan nilai signature: AlgorithmIdentifier,
+/// signed attributes, dan struktur SignedData/SignerInfo
+const CMS_OVERHEAD: usize = 2048;
+
+/// Ruang tambahan di /Contents untuk TimeStampToken dari TSA (termasuk sertifikat TSA);
+/// juga ukuran /Contents document timestamp
+pub(crate) const TIMESTAMP_TOKEN_RESERVE: usize = 12288;
+
+/// Path input/output yang berarti stdin/stdout
+pub const STDIO_PATH: &str = "-";
+
+/// Stream lebih besar dari ini (selain object stream, xref stream, dan form
+/// XObject) tidak dimuat isinya saat signing
+const LARGE_STREAM: usize = 1024 * 1024;
+
+/// Dokumen dengan ByteRange sebesar ini atau lebih mendapat progress bar saat di-hash
+const LARGE_HASH: u64 = 64 * 1024 * 1024;
 
-/// Fungsi utama untuk menandatangani file PDF dengan ECDSA P-256
+/// Angka placeholder /ByteRange (10 digit, cukup untuk file sampai ~9 GB)
+const BYTE_RANGE_PLACEHOLDER: i64 = 9_999_999_999;
+
+/// Fungsi utama untuk menandatangani file PDF dengan ECDSA, Ed25519, atau RSA
 /// 
 /// Parameter:
-///   - input: path file PDF yang akan ditandatangani
-///   - output: path file PDF hasil penandatanganan
-///   - key_path: path file kunci privat
-///   - metadata: informasi metadata untuk signature
-pub fn sign_pdf(input: &str, output: &str, key_path: &str, metadata: SignatureMetadata) -> Result<()> {
-    // Baca file PDF asli dari disk
-    let pdf_bytes = fs::read(input)?;
-    
-    // Baca kunci privat dari file
-    let private_key = fs::read(key_path)?;
+///   - input: path file PDF yang akan ditandatangani ("-" untuk stdin)
+///   - output: path file PDF hasil penandatanganan ("-" untuk stdout; pesan status ke stderr)
+///   - key_path: path file kunci privat (ECDSA, Ed25519, atau RSA); diabaikan jika options.pkcs12 di-set
+///   - options: metadata, posisi, dan kebijakan signature (lihat SignatureOptions)
+pub fn sign_pdf(input: impl AsRef<Path>, output: impl AsRef<Path>, key_path: impl AsRef<Path>, options: SignatureOptions) -> Result<()> {
+    sign_pdf_with_hooks(input, output, key_path, options, &mut NoHooks, &CancellationToken::new())
+}
+
+/// Sama seperti `sign_pdf`, tetapi memanggil hook pada setiap tahap pipeline
+///
+/// Parameter tambahan:
+///   - hooks: implementasi SigningHooks (lihat pdf::hooks)
+///   - cancel: token pembatalan; diperiksa saat hashing dan sebelum output disimpan
+pub fn sign_pdf_with_hooks(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    key_path: impl AsRef<Path>,
+    options: SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let output = output.as_ref();
+    let summary = sign_pdf_summary(input, output, key_path, &options, hooks, cancel)?;
+
+    // Tampilkan pesan sukses ke user; jika PDF hasil ditulis ke stdout,
+    // pesan ke stderr agar tidak tercampur dengan isi PDF
+    let stdout = is_stdio(output);
+    let report = |line: String| crate::log::status(&line, stdout);
+    report(format!("PDF signed: {}", if stdout { "<stdout>".into() } else { output.display().to_string() }));
+    print_summary(&summary, stdout);
+    Ok(())
+}
+
+/// Tampilkan ringkasan signature (format, penandatangan, TSA, PAdES, certification)
+///
+/// Parameter:
+///   - summary: ringkasan signature yang dibuat
+///   - stderr: tulis ke stderr (PDF hasil ditulis ke stdout)
+pub fn print_summary(summary: &SignSummary, stderr: bool) {
+    let report = |line: String| crate::log::status(&line, stderr);
+    report(format!("Signature: {}", summary.signature));
+    report(format!("Signer: {}", summary.signer));
+    if let Some(url) = &summary.timestamp {
+        report(format!("Timestamp: {}", url));
+    }
+    if let Some(level) = summary.pades {
+        report(format!("PAdES: {}", level));
+    }
+    if let Some(certification) = &summary.certification {
+        report(format!("Certification: {}", certification));
+    }
+    if let Some(lock) = &summary.locked_fields {
+        report(format!("Locked fields: {}", lock));
+    }
+}
+
+/// Ringkasan signature yang baru dibuat (pesan sukses `sign`, output JSON)
+pub struct SignSummary {
+    pub signature: String,                    // Format dan algoritma, mis. "CMS SignedData (ECDSA P-256, SHA-256)"
+    pub signer: String,                       // Nama penandatangan (/Name)
+    pub certificate: Option<CertificateInfo>, // Sertifikat penandatangan (jika ada)
+    pub timestamp: Option<String>,            // URL TSA (jika timestamp diminta)
+    pub pades: Option<&'static str>,          // Level PAdES baseline
+    pub certification: Option<String>,        // Level certification dan DocMDP P
+    pub locked_fields: Option<String>,        // Field yang dikunci (FieldMDP)
+}
+
+/// Sama seperti `sign_pdf_with_hooks`, tanpa pesan ke user
+///
+/// Return: ringkasan signature yang dibuat
+pub fn sign_pdf_summary(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    key_path: impl AsRef<Path>,
+    options: &SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<SignSummary> {
+    let (signer, certificates) = load_credentials(key_path, options)?;
+    sign_file(input.as_ref(), output.as_ref(), signer.as_ref(), &certificates, options, hooks, cancel)?;
+    Ok(SignSummary::new(signer.as_ref(), &certificates, options))
+}
+
+/// Apakah path berarti stdin/stdout ("-")
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
+impl SignSummary {
+    /// Ringkasan signature yang dibuat dengan kunci, sertifikat, dan opsi ini
+    pub(crate) fn new(signer: &dyn Signer, certificates: &[Vec<u8>], options: &SignatureOptions) -> SignSummary {
+        SignSummary {
+            signature: format!("CMS SignedData ({}, {})", signer.description(), signer.digest_for(options.digest).label()),
+            signer: options.name.clone(),
+            certificate: certificates.first().and_then(|der| CertificateInfo::from_der(der)),
+            timestamp: options.timestamp.as_ref().map(|tsa| tsa.url.clone()),
+            pades: options.pades.map(|level| level.label()),
+            certification: options.certify.map(|level| format!("{} (DocMDP P={})", level.label(), level.permissions())),
+            locked_fields: options.lock_fields.as_ref().map(|lock| lock.to_string()),
+        }
+    }
+}
+
+/// Hasil `sign --dry-run`: apa yang akan ditulis, tanpa menulis output
+pub struct DryRun {
+    pub summary: SignSummary,                  // Format, penandatangan, level PAdES, certification
+    pub placement: Option<SignaturePlacement>, // Field, halaman, dan kotak (None: placeholder dari `prepare` dipakai lagi)
+    pub estimated_cms_size: usize,             // Perkiraan ukuran CMS SignedData (bytes)
+    pub contents_size: usize,                  // Ruang /Contents yang dipesan (bytes, sebelum hex)
+}
+
+/// Hasil `sign --dry-run` sebagai teks untuk user (ditampilkan oleh CLI)
+///
+/// Parameter:
+///   - plan: hasil `sign_dry_run`
+///   - output: --output (jika diberikan; tidak ditulis)
+pub fn format_dry_run(plan: &DryRun, output: Option<&Path>) -> String {
+    let mut out = String::new();
+    match output {
+        Some(output) if is_stdio(output) => out.push_str("Dry run: nothing written (output would be <stdout>)\n"),
+        Some(output) => out.push_str(&format!("Dry run: nothing written (output would be {})\n", output.display())),
+        None => out.push_str("Dry run: nothing written\n"),
+    }
+    match &plan.placement {
+        Some(placement) => {
+            out.push_str(&format!("Field: {}\n", placement.field_name));
+            if let Some(rule) = &placement.rule {
+                out.push_str(&format!("Placement rule: {}\n", rule));
+            }
+            out.push_str(&format!("Page: {}\n", placement.page));
+            let rect = placement.rect;
+            if rect.width() > 0.0 && rect.height() > 0.0 {
+                out.push_str(&format!("Rect: {},{},{},{} (x,y,w,h)\n", rect.left, rect.bottom, rect.width(), rect.height()));
+            } else {
+                out.push_str("Rect: none (invisible signature)\n");
+            }
+        }
+        None => out.push_str("Field: the empty placeholder from `prepare` is reused\n"),
+    }
+    out.push_str(&format!("Signature: {}\n", plan.summary.signature));
+    out.push_str(&format!("Signer: {}\n", plan.summary.signer));
+    if let Some(certificate) = &plan.summary.certificate {
+        out.push_str(&format!("Certificate: {} (matches the private key)\n", certificate.subject));
+    }
+    out.push_str(&format!("Estimated CMS size: {} bytes (/Contents reserves {} bytes)\n", plan.estimated_cms_size, plan.contents_size));
+    out.push_str(&format!("PAdES: {}\n", plan.summary.pades.unwrap_or("none (adbe.pkcs7.detached)")));
+    if let Some(url) = &plan.summary.timestamp {
+        out.push_str(&format!("Timestamp: {}\n", url));
+    }
+    if let Some(certification) = &plan.summary.certification {
+        out.push_str(&format!("Certification: {}\n", certification));
+    }
+    if let Some(lock) = &plan.summary.locked_fields {
+        out.push_str(&format!("Locked fields: {}\n", lock));
+    }
+    out
+}
+
+/// Jalankan semua tahap `sign` kecuali penandatanganan dan penulisan output
+///
+/// Dokumen diurai, kunci dan sertifikat dimuat dan dicocokkan, halaman dan
+/// posisi ditentukan, dan incremental update dengan placeholder dibuat di
+/// buffer sementara, sehingga error yang akan muncul saat signing sungguhan
+/// (halaman tidak ada, field tidak ditemukan, dokumen bersertifikasi, kunci
+/// yang ditolak kebijakan algoritma) muncul di sini. Kunci privat tidak
+/// dipakai untuk menandatangani, TSA tidak dihubungi, dan lampiran
+/// (--sign-attachments) tidak ditandatangani.
+///
+/// Parameter:
+///   - input: path file PDF ("-" untuk stdin)
+///   - key_path: path file kunci privat; diabaikan jika options.pkcs12 di-set
+///   - options: metadata, posisi, dan kebijakan signature
+///   - cancel: token pembatalan
+pub fn sign_dry_run(input: impl AsRef<Path>, key_path: impl AsRef<Path>, options: &SignatureOptions, cancel: &CancellationToken) -> Result<DryRun> {
+    let (signer, certificates) = load_credentials(key_path, options)?;
+    let signer = signer.as_ref();
+    // Sertifikat yang tidak cocok dengan kunci menghasilkan signature yang tidak bisa diverifikasi
+    if let Some(leaf) = certificates.first() {
+        let leaf = Certificate::from_der(leaf).map_err(|e| anyhow!("invalid signer certificate: {}", e))?;
+        if leaf.tbs_certificate.subject_public_key_info.subject_public_key != signer.public_key_info()?.subject_public_key {
+            bail!("the signer certificate ({}) does not belong to the private key", leaf.tbs_certificate.subject);
+        }
+    }
+    // Dry run tidak menandatangani apa pun, jadi kredensial uji hanya diberi peringatan
+    if let Some(subject) = cert::test_credential(&certificates).filter(|_| !options.allow_test_credentials) {
+        crate::warning!("the certificate {} is a test credential; signing will need --allow-test-credentials", subject);
+    }
+    let mut scratch = open_input(input.as_ref(), options, cancel)?;
+    // Hook klasifikasi ikut dijalankan, jadi penolakannya terlihat di sini
+    let doc = load_or_repair(&mut scratch, options)?;
+    let options = &classify_document(&mut scratch, &doc, options, &mut NoHooks)?;
+
+    let summary = SignSummary::new(signer, &certificates, options);
+    let options = &SignatureOptions { digest: signer.digest_for(options.digest), ..options.clone() };
+    let (embedded, contents_size) = plan_signature(signer, &certificates, options)?;
+    let prepared = prepare_parsed(scratch, Some(&doc), &certificates, options, contents_size, cancel)?;
+    Ok(DryRun {
+        summary,
+        placement: prepared.placement,
+        estimated_cms_size: estimate_cms_size(signer, &certificates, &embedded, options)?,
+        contents_size: prepared.contents_size,
+    })
+}
+
+/// Tandatangani satu file dengan kunci yang sudah dimuat (dipakai juga oleh
+/// `sign-batch` untuk banyak file dengan satu kunci), tanpa pesan ke user
+pub(crate) fn sign_file(
+    input: &Path,
+    output: &Path,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    // Proses pdfsign lain yang menulis output yang sama menunggu sampai selesai;
+    // dengan --output sama dengan --input, perubahannya tidak saling menimpa
+    let _lock = match is_stdio(output) {
+        true => None,
+        false => Some(FileLock::exclusive(output)?),
+    };
+
+    let scratch = open_input(input, options, cancel)?;
+
+    let mut scratch = sign_scratch(scratch, signer, certificates, options, hooks, cancel)?;
+    scratch.seek(SeekFrom::Start(0))?;
+    // Output "-" ditulis ke stdout; tujuan tanpa nama, jadi on_written tidak dipanggil
+    if is_stdio(output) {
+        return WriterSink::new(std::io::stdout().lock()).store(&mut scratch, cancel);
+    }
+    FileSink::new(output).store(&mut scratch, cancel)?;
+    hooks.on_written(&output.to_string_lossy())
+}
+
+/// Buka dokumen input: file PDF asli dibaca langsung dari disk tanpa disalin
+/// (hanya update yang disimpan di buffer sementara); stdin ("-") dibaca ke memori
+fn open_input(input: &Path, options: &SignatureOptions, cancel: &CancellationToken) -> Result<Scratch> {
+    if is_stdio(input) {
+        return options.scratch.create_from(ReaderSource::new(std::io::stdin().lock()).load(cancel)?);
+    }
+    options.scratch.create_over(File::open(input).with_context(|| format!("cannot read {}", input.display()))?)
+}
+
+/// Muat kunci privat dan rantai sertifikat dari token PKCS#11 (options.pkcs11),
+/// KMS cloud (options.kms), keystore OS (options.keystore_cert), bundle PKCS#12 (options.pkcs12), atau dari file kunci dan certificate.der di sebelahnya
+///
+/// Return: signer dan sertifikat DER (penandatangan dulu, lalu rantainya)
+pub fn load_credentials(key_path: impl AsRef<Path>, options: &SignatureOptions) -> Result<Credentials> {
+    let key_path = key_path.as_ref();
+    // Token PKCS#11: signing dilakukan di token, PIN ditanyakan jika perlu
+    if let Some(token) = &options.pkcs11 {
+        return load_pkcs11(token, options.rsa_padding, true);
+    }
+    // KMS cloud: signing dilakukan oleh Sign API layanan
+    if let Some(key) = &options.kms {
+        return load_kms(key, options.rsa_padding);
+    }
+    // Keystore OS: signing dilakukan oleh CNG / Security framework
+    if let Some(selector) = &options.keystore_cert {
+        return load_keystore(selector, options.rsa_padding);
+    }
+
+    // Sesi signing yang aktif untuk kunci/bundle ini: tidak perlu passphrase lagi
+    #[cfg(all(unix, feature = "server"))]
+    {
+        let identity = options.pkcs12.as_ref().map_or(key_path, |source| Path::new(&source.path));
+        if let Some(credentials) = crate::crypto::session::credentials_for(identity, options.rsa_padding)? {
+            return Ok(credentials);
+        }
+    }
+
+    Ok(match &options.pkcs12 {
+        // Bundle PKCS#12: kunci privat dan seluruh rantai sertifikat dari satu file
+        Some(source) => {
+            let bundle = load_pkcs12(&source.path, source.password.as_deref(), options.rsa_padding)?;
+            (bundle.signer, bundle.certificates)
+        }
+        None => {
+            // Baca kunci privat dari file; jenis kunci (ECDSA / Ed25519 / RSA) dideteksi otomatis
+            let signer = load_signer(key_path, options.rsa_padding, options.passphrase.as_deref())?;
+
+            // Sertifikat (opsional) dari certificate.der di sebelah kunci
+            (signer, certificates_for_key(key_path)?)
+        }
+    })
+}
+
+/// Tandatangani dokumen di memori dan kembalikan buffer berisi PDF hasil
+///
+/// Inti pipeline yang dipakai `sign_pdf_with_hooks` (file) dan `PdfSigner`
+/// (reader/writer). Tidak menulis ke output dan tidak memanggil `on_written`.
+///
+/// Parameter:
+///   - pdf_bytes: isi PDF asli
+///   - signer: kunci privat (ECDSA, Ed25519, atau RSA)
+///   - certificates: sertifikat DER; penandatangan dulu, lalu rantainya
+///   - options: metadata, posisi, dan kebijakan signature
+///   - hooks: implementasi SigningHooks
+///   - cancel: token pembatalan
+///
+/// Return: buffer sementara berisi PDF yang sudah ditandatangani
+pub fn sign_document(
+    pdf_bytes: Vec<u8>,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<Scratch> {
+    sign_scratch(options.scratch.create_from(pdf_bytes)?, signer, certificates, options, hooks, cancel)
+}
+
+/// Sama seperti `sign_document`, tetapi dokumen asli sudah ada di buffer
+/// sementara (misalnya `ScratchSpace::create_over` untuk file besar); update
+/// ditambahkan di akhir buffer yang sama
+pub fn sign_scratch(
+    scratch: Scratch,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<Scratch> {
+    sign_parsed(scratch, None, signer, certificates, options, hooks, cancel)
+}
+
+/// Sama seperti `sign_scratch`, tetapi dokumen boleh sudah diurai (lihat
+/// `sign-fanout`: satu dokumen diurai sekali untuk beberapa penandatangan)
+///
+/// Parameter tambahan:
+///   - parsed: hasil `read_document` atas isi `scratch`; None = diurai di sini
+pub(crate) fn sign_parsed(
+    scratch: Scratch,
+    parsed: Option<&Document>,
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    hooks: &mut dyn SigningHooks,
+    cancel: &CancellationToken,
+) -> Result<Scratch> {
+    // Kredensial uji ditolak sebelum dokumen dimuat
+    cert::check_test_credentials(certificates, options.allow_test_credentials)?;
+
+    // Dokumen dimuat lebih dulu agar hook klasifikasi bisa menolaknya atau
+    // mengubah opsi sebelum signature direncanakan
+    let mut scratch = scratch;
+    let loaded;
+    let parsed = match parsed {
+        Some(doc) => doc,
+        None => {
+            loaded = load_or_repair(&mut scratch, options)?;
+            &loaded
+        }
+    };
+    let options = &classify_document(&mut scratch, parsed, options, hooks)?;
+    let before = scratch.seek(SeekFrom::End(0))?;
+
+    // Kunci Ed25519 menentukan digest-nya sendiri (SHA-512, RFC 8419)
+    let options = &SignatureOptions { digest: signer.digest_for(options.digest), ..options.clone() };
+    let (embedded, placeholder_size) = plan_signature(signer, certificates, options)?;
+    crate::verbose!(
+        "sign",
+        "{} with {}, {} certificate(s) embedded, /Contents reserves {} bytes",
+        signer.description(),
+        options.digest.label(),
+        embedded.len(),
+        placeholder_size
+    );
+
+    // Signature lampiran ditulis di revisi tersendiri sebelum signature dokumen,
+    // sehingga file .p7s ikut dicakup ByteRange signature dokumen
+    // (revisi tersebut mengubah dokumen, jadi hasil uraian sebelumnya tidak dipakai)
+    let mut parsed = Some(parsed);
+    if options.sign_attachments {
+        sign_attachments(&mut scratch, signer, &embedded, options, cancel)?;
+        parsed = None;
+    }
+    let PreparedDocument { mut scratch, contents_pos, contents_size, digest, signing_time, cades, reused, .. } =
+        prepare_parsed(scratch, parsed, certificates, options, placeholder_size, cancel)?;
+
+    // Beri kesempatan embedder memeriksa/menyetujui digest sebelum ditandatangani
+    hooks.on_prepared(&digest)?;
+
+    // Bangun CMS SignedData: signed attributes (contentType, signingTime,
+    // messageDigest; untuk PAdES signing-certificate-v2 menggantikan signingTime)
+    // ditandatangani dengan kunci privat, sertifikat ikut disisipkan
+    let attributes = if cades { SignedAttributes::Cades } else { SignedAttributes::Pkcs7 };
+    #[cfg_attr(not(feature = "network"), allow(unused_mut))]
+    let mut pkcs7_content = build_signed_data(
+        &digest,
+        options.digest,
+        signer,
+        &embedded,
+        signing_time.with_timezone(&chrono::Utc),
+        attributes,
+    )?;
+
+    // Timestamp RFC 3161 atas nilai signature, disimpan sebagai unsigned attribute
+    // (signature-time-stamp) sehingga waktu penandatanganan bisa dibuktikan pihak ketiga
+    // (tanpa fitur network, SignatureOptions menolak TSA dan level PAdES B-T ke atas)
+    #[cfg(feature = "network")]
+    let signature_token = match &options.timestamp {
+        Some(tsa) => {
+            let mut signature_token = None;
+            pkcs7_content = add_signature_timestamp(&pkcs7_content, |signature| {
+                let token = tsa::request_timestamp(tsa, &mut |hash| Ok(hash.digest(signature)), &options.algorithm_policy, cancel)?;
+                signature_token = Some(token.clone());
+                Ok(token)
+            })?;
+            signature_token
+        }
+        None => None,
+    };
+
+    // Struktur signature sudah lengkap, beri tahu embedder
+    hooks.on_cms_built(&pkcs7_content)?;
+    crate::verbose!("cms", "CMS SignedData {} bytes ({} bytes reserved, {} spare)", pkcs7_content.len(), contents_size, contents_size.saturating_sub(pkcs7_content.len()));
+
+    // Placeholder sudah ikut di-hash, jadi CMS yang terlalu besar tidak bisa
+    // diperbaiki lagi; beri tahu ukuran yang cukup untuk percobaan berikutnya
+    if pkcs7_content.len() > contents_size {
+        let retry = if reused {
+            format!("prepare the original document again with --reserve {}", round_up_kib(pkcs7_content.len() + 1024))
+        } else {
+            format!("sign again with --placeholder-size {}", round_up_kib(pkcs7_content.len() + 1024))
+        };
+        bail!(
+            "signature ({} bytes) does not fit the reserved /Contents space ({} bytes); {}",
+            pkcs7_content.len(),
+            contents_size,
+            retry
+        );
+    }
+
+    // Sisipkan signature ke placeholder /Contents (hex, sisa placeholder tetap nol)
+    write_contents(&mut scratch, contents_pos, contents_size, &pkcs7_content)?;
+
+    // ===== PADES B-LT / B-LTA =====
+    #[cfg(feature = "network")]
+    add_long_term_validation(&mut scratch, &pkcs7_content, signature_token.as_deref(), certificates, options, contents_size, cancel)?;
+    scratch.flush()?;
+    cancel.check()?;
+    check_budget(&mut scratch, before, options)?;
+    Ok(scratch)
+}
+
+/// Peringatkan jika signing menambah file melebihi `options.budget`
+/// (--warn-growth, --warn-objects); dokumen hasil hanya diurai jika ada batas
+fn check_budget(scratch: &mut Scratch, before: u64, options: &SignatureOptions) -> Result<()> {
+    if options.budget.is_empty() {
+        return Ok(());
+    }
+    let after = scratch.seek(SeekFrom::End(0))?;
+    let (doc, _) = read_document(scratch, ParseMode::Lenient)?;
+    let size = budget::measure(&doc, before, after)?;
+    crate::verbose!("budget", "{} added in {} object(s); largest: {}", text::human_size(size.added()), size.objects.len(), size.largest());
+    for warning in budget::check(&size, &options.budget) {
+        crate::warning!("{}", warning);
+    }
+    Ok(())
+}
+
+/// Pemeriksaan kunci dan sertifikat sebelum dokumen diubah (juga untuk `--dry-run`)
+///
+/// Return: sertifikat yang disisipkan di CMS dan ruang /Contents dalam bytes
+fn plan_signature(signer: &dyn Signer, certificates: &[Vec<u8>], options: &SignatureOptions) -> Result<(Vec<Vec<u8>>, usize)> {
+    // PAdES (signing-certificate-v2) mengikat signature ke sertifikat penandatangan
+    if options.pades.is_some() && certificates.is_empty() {
+        bail!("PAdES signatures need the signer certificate (certificate.der next to the key, or --p12)");
+    }
+
+    // Tolak kunci dan rantai sertifikat dengan algoritma dari deny-list
+    options.algorithm_policy.check_signing(&signer.public_key_info()?, certificates)?;
+
+    // Rantai yang disisipkan bisa dipangkas (--embed-chain); DSS tetap memakai rantai lengkap
+    let embedded = options.embed_chain.select(certificates)?;
+
+    // Ruang /Contents dari --placeholder-size, atau diperkirakan dari isi CMS nanti
+    let placeholder_size = match options.placeholder_size {
+        Some(size) => size,
+        None => estimate_placeholder_size(signer, certificates, &embedded, options)?,
+    };
+    Ok((embedded, placeholder_size))
+}
+
+/// Perkirakan ruang /Contents yang dibutuhkan CMS signature: sertifikat yang
+/// disisipkan, nilai signature, atribut, dan TimeStampToken jika memakai TSA.
+/// Data revocation PAdES B-LT disimpan di DSS, bukan di /Contents.
+///
+/// Parameter:
+///   - signer: kunci penandatangan (ukuran signature mengikuti kunci publiknya)
+///   - certificates: rantai sertifikat lengkap (penandatangan di indeks 0)
+///   - embedded: sertifikat yang benar-benar disisipkan (--embed-chain)
+///   - options: opsi signature (TSA)
+///
+/// Return: ukuran dalam bytes, dibulatkan ke atas ke kelipatan 1 KiB
+pub fn estimate_placeholder_size(
+    signer: &dyn Signer,
+    certificates: &[Vec<u8>],
+    embedded: &[Vec<u8>],
+    options: &SignatureOptions,
+) -> Result<usize> {
+    Ok(round_up_kib(estimate_cms_size(signer, certificates, embedded, options)?).max(MIN_PLACEHOLDER_SIZE))
+}
+
+/// Perkiraan ukuran CMS SignedData (batas atas, sebelum dibulatkan)
+fn estimate_cms_size(signer: &dyn Signer, certificates: &[Vec<u8>], embedded: &[Vec<u8>], options: &SignatureOptions) -> Result<usize> {
+    let mut size = CMS_OVERHEAD + embedded.iter().map(Vec::len).sum::<usize>();
+    // Issuer dan serial penandatangan muncul lagi di SignerInfo dan signing-certificate-v2
+    size += certificates.first().map_or(0, Vec::len);
+    // Signature tidak pernah lebih dari dua kali kunci publiknya (DER ECDSA, modulus RSA, Ed25519)
+    size += 2 * signer.public_key_info()?.subject_public_key.raw_bytes().len() + 16;
+    if options.timestamp.is_some() {
+        size += TIMESTAMP_TOKEN_RESERVE;
+    }
+    Ok(size)
+}
+
+/// Bulatkan ke atas ke kelipatan 1 KiB
+fn round_up_kib(size: usize) -> usize {
+    size.div_ceil(1024) * 1024
+}
+
+/// Tambahkan data validasi (PAdES B-LT) dan document timestamp (PAdES B-LTA)
+/// sesuai `options.pades`; setiap tahap menjadi incremental update baru
+/// setelah signature
+///
+/// Parameter:
+///   - scratch: PDF yang sudah ditandatangani
+///   - pkcs7_content: CMS SignedData di /Contents signature
+///   - signature_token: TimeStampToken signature (jika ada), sertifikat TSA-nya ikut divalidasi
+///   - certificates: rantai sertifikat penandatangan
+///   - options: opsi signature (level PAdES, TSA, object stream)
+///   - placeholder_size: ukuran placeholder /Contents signature
+///   - cancel: token pembatalan
+#[cfg(feature = "network")]
+fn add_long_term_validation(
+    scratch: &mut Scratch,
+    pkcs7_content: &[u8],
+    signature_token: Option<&[u8]>,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    placeholder_size: usize,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if options.pades >= Some(PadesLevel::BLt) {
+        // Data validasi untuk rantai penandatangan dan sertifikat TSA
+        let mut chain = certificates.to_vec();
+        if let Some(token) = signature_token {
+            chain.extend(tsa::token_certificates(token)?);
+        }
+        let revocation = fetch_revocation_data_with(&chain, &options.ltv, cancel)?;
+        let mut signature_contents = pkcs7_content.to_vec();
+        signature_contents.resize(placeholder_size, 0);
+        append_update(scratch, options, |doc| pades::add_dss(doc, &signature_contents, &chain, &revocation))?;
+    }
+    if options.pades == Some(PadesLevel::BLta) {
+        // Document timestamp: TimeStampToken atas seluruh dokumen termasuk DSS
+        let tsa = options
+            .timestamp
+            .as_ref()
+            .ok_or_else(|| anyhow!("PAdES B-LTA needs a TSA (--tsa-url)"))?;
+        let update_start = append_update(scratch, options, |doc| {
+            let (contents, byte_range) = signature_placeholders(TIMESTAMP_TOKEN_RESERVE);
+            pades::add_document_timestamp(doc, contents, byte_range).map(drop)
+        })?;
+        let (contents_pos, byte_range) = patch_byte_range(scratch, update_start, TIMESTAMP_TOKEN_RESERVE)?;
+        // Imprint dengan hash TSA (--tsa-hash), bukan digest signature
+        let token = tsa::request_timestamp(
+            tsa,
+            &mut |hash| hash_byte_range(scratch, &byte_range, hash, cancel),
+            &options.algorithm_policy,
+            cancel,
+        )?;
+        write_contents(scratch, contents_pos, TIMESTAMP_TOKEN_RESERVE, &token)?;
+    }
+    Ok(())
+}
+
+/// Dokumen dengan signature field dan placeholder /Contents yang belum diisi
+pub struct PreparedDocument {
+    pub scratch: Scratch,      // PDF hasil serialisasi; /ByteRange sudah ditambal
+    pub contents_pos: u64,     // Posisi '<' nilai /Contents
+    pub contents_size: usize,  // Ruang /Contents dalam bytes (sebelum hex)
+    pub byte_range: [u64; 4],  // Nilai /ByteRange yang ditulis
+    pub digest: Vec<u8>,       // Digest (--digest) atas kedua segmen ByteRange
+    pub signing_time: chrono::DateTime<chrono::FixedOffset>, // Waktu di /M
+    pub cades: bool,           // SubFilter ETSI.CAdES.detached (signed attributes CAdES)
+    pub reused: bool,          // Placeholder dari `prepare` sebelumnya dipakai lagi
+    pub placement: Option<SignaturePlacement>, // Field, halaman, dan kotak signature baru (None jika placeholder dipakai lagi)
+}
+
+/// Letak signature baru di dokumen
+#[derive(Clone, Debug)]
+pub struct SignaturePlacement {
+    pub field_name: String, // Nama lengkap signature field
+    pub page: u32,          // Nomor halaman (mulai dari 1)
+    pub rect: Rect,         // Kotak tampilan (stamp untuk --stamp-only); berukuran nol jika invisible
+    pub rule: Option<String>, // Aturan posisi yang cocok dengan dokumen (placement_rules)
+}
+
+/// Signature dictionary dengan /Contents yang masih kosong (nol semua),
+/// biasanya hasil `pdfsign prepare` yang belum diisi
+pub struct Placeholder {
+    pub contents_pos: u64,     // Posisi '<' nilai /Contents
+    pub contents_size: usize,  // Ruang /Contents dalam bytes (sebelum hex)
+    pub byte_range: [u64; 4],  // Nilai /ByteRange
+    pub cades: bool,           // SubFilter ETSI.CAdES.detached
+    pub current: bool,         // /ByteRange mencakup seluruh file (tidak ada revisi sesudahnya)
+}
+
+/// Tambahkan signature field dengan placeholder /Contents ke dokumen, serialisasi
+/// sebagai incremental update, tambal /ByteRange, lalu hitung digest dokumen
+///
+/// Dipakai `sign_document` dan alur signing eksternal (`pdf::external`).
+///
+/// Parameter:
+///   - pdf_bytes: isi PDF asli
+///   - certificates: sertifikat DER (untuk /Cert; boleh kosong)
+///   - options: metadata, posisi, dan kebijakan signature
+///   - placeholder_size: ruang /Contents dalam bytes (sebelum hex)
+///   - cancel: token pembatalan
+pub fn prepare_document(
+    pdf_bytes: Vec<u8>,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    placeholder_size: usize,
+    cancel: &CancellationToken,
+) -> Result<PreparedDocument> {
+    prepare_scratch(options.scratch.create_from(pdf_bytes)?, certificates, options, placeholder_size, cancel)
+}
+
+/// Sama seperti `prepare_document`, tetapi dokumen asli sudah ada di buffer
+/// sementara; update ditambahkan di akhir buffer yang sama
+pub fn prepare_scratch(
+    scratch: Scratch,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    placeholder_size: usize,
+    cancel: &CancellationToken,
+) -> Result<PreparedDocument> {
+    prepare_parsed(scratch, None, certificates, options, placeholder_size, cancel)
+}
+
+/// Sama seperti `prepare_scratch`; `parsed` adalah hasil `read_document` atas
+/// isi `scratch` (None = diurai di sini)
+fn prepare_parsed(
+    mut scratch: Scratch,
+    parsed: Option<&Document>,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    placeholder_size: usize,
+    cancel: &CancellationToken,
+) -> Result<PreparedDocument> {
+// Load PDF document menggunakan lopdf library (tanpa isi gambar dan stream besar)
+    let mut doc = match parsed {
+        Some(doc) => doc.clone(),
+        None => load_or_repair(&mut scratch, options)?,
+    };
+    crate::verbose!("load", "PDF {}, {} objects, {} page(s)", doc.version, doc.objects.len(), doc.get_pages().len());
+    let mut security = decrypt_document(&mut scratch, &mut doc, options)?;
+    if security.is_none() && options.pdf_password.is_some() {
+        crate::warning!("the document is not encrypted; --pdf-password is ignored");
+    }
+    // Dokumen terenkripsi hanya ditulis tanpa enkripsi jika diminta eksplisit
+    if options.decrypt_output {
+        match security.take() {
+            Some(handler) => doc = decrypt_copy(&mut scratch, &handler, options)?,
+            None => crate::warning!("the document is not encrypted; --decrypt-output is ignored"),
+        }
+    }
+
+    // Placeholder kosong di revisi terakhir (prepare yang belum diisi, atau
+    // signing yang gagal) dipakai lagi daripada menambah revisi placeholder kedua
+    if let Some(placeholder) = find_placeholder(&mut scratch, &doc)?.filter(|p| p.current) {
+        return reuse_placeholder(scratch, placeholder, options, cancel);
+    }
+
+    // Simpan salinan kondisi awal: hanya object yang berubah/baru yang akan
+    // ditulis sebagai incremental update di akhir file
+    let original = doc.clone();
     
-    // Coba baca file certificate jika ada (opsional)
-    let cert_path = key_path.replace("private.key", "certificate.der");
-    let cert_der = fs::read(&cert_path).ok();
+    // Object ID catalog diambil dari /Root pada trailer (tidak selalu (1, 0))
+    let root_id = form::catalog_id(&doc)?;
+    // Page tree yang rusak membuat nomor halaman bergeser; widget tidak boleh
+    // ditempel ke halaman yang tidak ditampilkan viewer
+    pages::check_page_tree(&doc)?;
 
-    // Tandatangani seluruh PDF bytes dengan kunci privat
-    // Hasil adalah signature dalam format DER
-    let signature_bytes = sign(&pdf_bytes, &private_key);
+    // Certification signature hanya boleh menjadi signature pertama; dokumen
+    // yang disertifikasi tanpa izin perubahan tidak bisa ditandatangani lagi
+    if options.certify.is_some() {
+        mdp::check_certifiable(&doc, root_id)?;
+    } else if mdp::certification_permissions(&doc, root_id) == Some(1) {
+        bail!("the document is certified with no changes allowed; another signature would invalidate the certification");
+    }
 
-    // Load PDF document menggunakan lopdf library
-    let mut doc = Document::load_mem(&pdf_bytes)?;
+    // AcroForm yang rusak (stream, tipe lain, /Fields bukan array) dinormalisasi
+    // dulu, sehingga pencarian field di bawah melihat form yang sama
+    let acroform = form::load_acroform(&doc, root_id, options.strict)?;
+    if form::existing_acroform(&doc, root_id).as_ref() != Some(&acroform) && !acroform.is_empty() {
+        form::store_acroform(&mut doc, root_id, acroform)?;
+    }
+    
+    // Generate timestamp dalam format PDF (D:YYYYMMDDHHmmSS+HH'mm')
+    // Contoh: D:20260120105337+07'00' = 20 Januari 2026 10:53:37 WIB
+    let signing_time = options.signing_time.unwrap_or_else(naming::now);
+    let timestamp = pdf_date(&signing_time);
     
-    // Generate timestamp dalam format PDF (D:YYYYMMDDHHmmss)
-    // Contoh: D:20260120105337 = 20 Januari 2026 10:53:37
-    let timestamp = chrono::Local::now().format("D:%Y%m%d%H%M%S").to_string();
+    // Aturan posisi (placement_rules di profil): aturan pertama yang cocok
+    // dengan dokumen menggantikan placement biasa
+    let rule = placement::select(&doc, &options.placement_rules)?;
+    let placement = rule.map_or(options.placement, |rule| Some(rule.placement));
+
+    // Signature field yang sudah disiapkan di dokumen (--field-name): halaman
+    // dan posisinya diambil dari widget field tersebut
+    // Batasan wajib di /SV field tersebut ditegakkan sebelum apa pun ditulis
+    let existing_field = match &options.field_name {
+        Some(name) => {
+            let field = form::find_empty_signature_field(&doc, name)?;
+            seed::check(&doc, field.field_id, name, options, certificates)?;
+            Some(field)
+        }
+        None => None,
+    };
+    let (page_id, rect) = match &existing_field {
+        Some(field) => {
+            let [left, bottom, right, top] = field.rect;
+            (field.page_id, Rect { left, bottom, right, top })
+        }
+        None => {
+            // Tentukan halaman tujuan dari opsi
+            // Invisible signature tetap ditempel di halaman 1, tapi dengan rect berukuran nol
+            let page_number = placement.map_or(1, |placement| placement.page());
+            // Cari object ID halaman melalui page tree
+            let page_count = doc.get_pages().len();
+            let page_id = *doc
+                .get_pages()
+                .get(&page_number)
+                .ok_or_else(|| anyhow!("page {} does not exist (document has {} pages)", page_number, page_count))?;
+            // Posisi widget: rect eksplisit, atau dihitung dari ukuran halaman untuk --position
+            let rect = match placement {
+                Some(placement) => placement_rect(&doc, page_id, placement)?,
+                None => Rect::new(0.0, 0.0, 0.0, 0.0),
+            };
+            (page_id, rect)
+        }
+    };
     
+    // Dokumen PDF/A: tampilan memakai font yang disematkan dan warna sesuai
+    // OutputIntent; yang tidak bisa dipertahankan ditolak (--require-pdfa)
+    // atau dilaporkan sebagai peringatan sebelum dokumen ditulis
+    let visible = placement.is_some() && rect.width() > 0.0 && rect.height() > 0.0;
+    #[cfg_attr(not(feature = "appearance"), allow(unused_mut))]
+    let mut pdfa = pdfa::PdfaPlan::new(&doc, options, visible)?;
+
     // Buat appearance stream (visual representation) dari signature
     // Ini adalah teks yang akan ditampilkan di dalam signature box
-    let appearance_content = b"q
-BT
-/F1 0 Tf
-0 0 0 rg
-50 50 Td
-(Digitally signed) Tj
-ET
-Q".to_vec();
+    // Invisible signature (dan field yang sudah ada dengan rect berukuran nol)
+    // memakai appearance kosong tanpa font
+    // Layout, font, dan logo diambil dari cache (dikompilasi sekali untuk opsi
+    // yang sama); logo (jika ada) ditambahkan sebagai image XObject /Im1
+    // Tanpa fitur appearance, SignatureOptions sudah menolak signature yang terlihat
+    #[cfg(feature = "appearance")]
+    let (appearance_content, font, image_id) = if visible {
+        let compiled = options.appearance_cache.get_or_compile(options, &rect, &pdfa.target)?;
+        if let Some(image) = compiled.image() {
+            pdfa.check_image(image);
+        }
+        let font = compiled.add_font(&mut doc);
+        let image_id = compiled.image().map(|image| image.add_to(&mut doc));
+        (compiled.content(&signing_time.format(appearance::DATE_FORMAT).to_string()), Some(font), image_id)
+    } else {
+        (Vec::new(), None, None)
+    };
+    #[cfg(not(feature = "appearance"))]
+    let (appearance_content, font, image_id) = (Vec::new(), None, None);
+    pdfa.finish(options.require_pdfa)?;
     
     // Buat dictionary untuk appearance stream (form XObject)
     let mut appearance_stream_dict = lopdf::Dictionary::new();
@@ -63,9 +860,11 @@ Q".to_vec();
     appearance_stream_dict.set("BBox", lopdf::Object::Array(vec![
         lopdf::Object::Integer(0),
         lopdf::Object::Integer(0),
-        lopdf::Object::Integer(200),
-        lopdf::Object::Integer(60),
+        lopdf::Object::Real(rect.width()),
+        lopdf::Object::Real(rect.height()),
     ]));
+    // Resources = font /F1 untuk teks tampilan dan logo /Im1
+    appearance_stream_dict.set("Resources", lopdf::Object::Dictionary(appearance::resources(font, image_id)));
     
     // Buat stream object yang berisi appearance content
     let appearance_stream = lopdf::Stream::new(
@@ -79,54 +878,15 @@ Q".to_vec();
     // "N" = normal appearance (penampilan normal dari signature)
     let mut appearance_dict = lopdf::Dictionary::new();
     appearance_dict.set("N", lopdf::Object::Reference(appearance_id));
-    
-    // ===== BUAT STRUKTUR PKCS#7 SIGNATURE =====
-    // PKCS#7 adalah format standar untuk digital signature dengan sertifikat
-    
-    let mut pkcs7_content = Vec::new();
-    
-    // SEQUENCE tag (0x30) - adalah container untuk semua data signature
-    pkcs7_content.push(0x30);
-    // Placeholder untuk panjang SEQUENCE (akan diisi nanti)
-    let content_pos = pkcs7_content.len();
-    pkcs7_content.extend_from_slice(&[0x00, 0x00]);
-    
-    // Version = 1 (format DER: tag=0x02, length=1, value=1)
-    pkcs7_content.extend_from_slice(&[0x02, 0x01, 0x01]);
-    
-    // DigestAlgorithms SET (algoritma hashing yang digunakan)
-    pkcs7_content.extend_from_slice(&[0x31, 0x0b]); // SET dengan length 11
-    pkcs7_content.extend_from_slice(&[0x30, 0x09]); // SEQUENCE dengan length 9
-    pkcs7_content.extend_from_slice(&[0x06, 0x05]); // OID dengan length 5
-    // OID untuk SHA-1 (2.16.840.1.101.3.4.2.1)
-    pkcs7_content.extend_from_slice(&[0x2b, 0x0e, 0x03, 0x02, 0x1a]);
-    
-    // Tambahkan signature bytes
-    pkcs7_content.extend_from_slice(&[0x04]); // OCTET STRING tag
-    let sig_len = signature_bytes.len();
-    // Encode panjang signature
-    if sig_len < 128 {
-        pkcs7_content.push(sig_len as u8);
+
+    // --stamp-only: tampilan dipasang di annotation /Stamp (dibuat setelah
+    // field-nya), widget signature mendapat rect nol dan appearance kosong
+    let (stamp_appearance, appearance_dict) = if options.stamp_only {
+        (Some(appearance_dict), empty_appearance(&mut doc))
     } else {
-        pkcs7_content.push(0x81); // Indica long form length
-        pkcs7_content.push(sig_len as u8);
-    }
-    // Tambahkan signature data
-    pkcs7_content.extend_from_slice(&signature_bytes);
-    
-    // Jika certificate tersedia, tambahkan ke PKCS#7 structure
-    if let Some(cert) = &cert_der {
-        pkcs7_content.extend_from_slice(&cert);
-    }
-    
-    // Hitung panjang total SEQUENCE content (tanpa tag dan length byte pertama)
-    let total_len = pkcs7_content.len() - content_pos - 2;
-    // Encode panjang menggunakan DER format
-    let len_bytes = encode_der_length(total_len);
-    if len_bytes.len() == 1 {
-        // Jika panjang bisa dalam 1 byte, isi placeholder
-        pkcs7_content[content_pos] = len_bytes[0];
-    }
+        (None, appearance_dict)
+    };
+    let widget_rect = if stamp_appearance.is_some() { Rect::new(0.0, 0.0, 0.0, 0.0) } else { rect };
     
     // ===== BUAT SIGNATURE DICTIONARY =====
     // Ini adalah object PDF yang menyimpan informasi signature
@@ -136,69 +896,740 @@ Q".to_vec();
     sig_dict.set("Type", lopdf::Object::Name(b"Sig".to_vec()));
     // Filter = Adobe.PPKLite (format signature yang kompatibel dengan Adobe Reader)
     sig_dict.set("Filter", lopdf::Object::Name(b"Adobe.PPKLite".to_vec()));
-    // SubFilter = adbe.pkcs7.detached (menggunakan PKCS#7 detached signature)
-    sig_dict.set("SubFilter", lopdf::Object::Name(b"adbe.pkcs7.detached".to_vec()));
+    // SubFilter = adbe.pkcs7.detached (menggunakan PKCS#7 detached signature),
+    // atau ETSI.CAdES.detached untuk PAdES
+    let sub_filter: &[u8] = if options.pades.is_some() { b"ETSI.CAdES.detached" } else { b"adbe.pkcs7.detached" };
+    sig_dict.set("SubFilter", lopdf::Object::Name(sub_filter.to_vec()));
     // Nama penandatangan
-    sig_dict.set("Name", lopdf::Object::String(metadata.name.as_bytes().to_vec(), lopdf::StringFormat::Literal));
+    sig_dict.set("Name", text::text_string(&options.name));
     
     // Timestamp penandatanganan
     sig_dict.set("M", lopdf::Object::String(timestamp.as_bytes().to_vec(), lopdf::StringFormat::Literal));
     // Alasan penandatanganan
-    sig_dict.set("Reason", lopdf::Object::String(metadata.reason.as_bytes().to_vec(), lopdf::StringFormat::Literal));
+    sig_dict.set("Reason", text::text_string(&options.reason));
     
     // Lokasi penandatanganan (opsional)
-    if !metadata.location.is_empty() {
-        sig_dict.set("Location", lopdf::Object::String(metadata.location.as_bytes().to_vec(), lopdf::StringFormat::Literal));
+    if !options.location.is_empty() {
+        sig_dict.set("Location", text::text_string(&options.location));
     }
     // Informasi kontak penandatangan (opsional)
-    if !metadata.contact_info.is_empty() {
-        sig_dict.set("ContactInfo", lopdf::Object::String(metadata.contact_info.as_bytes().to_vec(), lopdf::StringFormat::Literal));
+    if !options.contact_info.is_empty() {
+        sig_dict.set("ContactInfo", text::text_string(&options.contact_info));
     }
     
-    // Reference certificate jika tersedia
-    if let Some(cert) = &cert_der {
+    // Reference certificate jika tersedia (PAdES melarang /Cert; sertifikat ada di CMS)
+    if let (Some(cert), None) = (certificates.first(), options.pades) {
         sig_dict.set("Cert", lopdf::Object::String(cert.clone(), lopdf::StringFormat::Literal));
     }
     
-    // Appearance stream untuk menampilkan signature secara visual
-    sig_dict.set("AP", lopdf::Object::Dictionary(appearance_dict.clone()));
-    
-    // ===== TAMBAHKAN SIGNATURE CONTENT =====
-    // Ini adalah data signature PKCS#7 dalam format hexadecimal
-    
-    let mut padded_content = pkcs7_content.clone();
-    // Padding signature content ke ukuran minimum 4096 bytes
-    // Ini diperlukan karena Adobe memerlukan placeholder untuk signature yang mungkin berkembang
-    while padded_content.len() < 4096 {
-        padded_content.push(0x00);
+    // Certification signature: DocMDP transform menentukan perubahan yang
+    // masih diizinkan setelah dokumen disertifikasi; FieldMDP transform
+    // mencatat field yang dikunci signature ini
+    let references: Vec<lopdf::Object> = options
+        .certify
+        .map(mdp::docmdp_reference)
+        .into_iter()
+        .chain(options.lock_fields.as_ref().map(mdp::fieldmdp_reference))
+        .collect();
+    if !references.is_empty() {
+        sig_dict.set("Reference", lopdf::Object::Array(references));
     }
     
-    // Tambahkan signature content dalam format hexadecimal
-    sig_dict.set("Contents", lopdf::Object::String(padded_content, lopdf::StringFormat::Hexadecimal));
+    // Appearance stream untuk menampilkan signature secara visual
+    sig_dict.set("AP", lopdf::Object::Dictionary(appearance_dict.clone()));
     
+    // ===== PLACEHOLDER SIGNATURE CONTENT =====
+    // Signature belum bisa dihitung di sini: yang ditandatangani adalah bytes
+    // file final. Jadi /Contents dan /ByteRange diisi placeholder berukuran tetap,
+    // lalu ditambal setelah dokumen diserialisasi (lihat bagian bawah fungsi ini).
     // ByteRange menunjukkan byte mana dari PDF yang ditandatangani
     // Format: [start1, length1, start2, length2]
-    // start1/length1 = bagian PDF sebelum signature
-    // start2/length2 = bagian PDF setelah signature (biasanya kosong)
-    sig_dict.set("ByteRange", lopdf::Object::Array(vec![
-        lopdf::Object::Integer(0),
-        lopdf::Object::Integer(pdf_bytes.len() as i64),
-        lopdf::Object::Integer(pdf_bytes.len() as i64 + 8192),
-        lopdf::Object::Integer(0),
-    ]));
+    // start1/length1 = bagian PDF sebelum nilai /Contents
+    // start2/length2 = bagian PDF setelah nilai /Contents sampai akhir file
+    let (contents, byte_range) = signature_placeholders(placeholder_size);
+    sig_dict.set("Contents", contents);
+    sig_dict.set("ByteRange", byte_range);
     
     // Tambahkan signature dictionary ke PDF document
     let sig_id = doc.add_object(sig_dict);
+    // /Perms /DocMDP di catalog menandai signature ini sebagai certification signature
+    if options.certify.is_some() {
+        mdp::register(&mut doc, root_id, sig_id)?;
+    }
     
     // ===== BUAT SIGNATURE FIELD (Widget Annotation) =====
     // Ini adalah field form yang menampilkan signature di halaman PDF
+    // Alternate text untuk screen reader (PDF/UA):
+    // TU = nama field yang dibacakan ke user, Contents = deskripsi annotation
+    let alt_text = format!("Digital signature of {}", options.name);
     
+    // Widget yang ditempel di halaman; field baru sekaligus menjadi widget-nya
+    let (widget_id, new_field) = match &existing_field {
+        Some(field) => {
+            // Field yang sudah ada: isi /V dan pasang tampilan di widget-nya;
+            // nama, flag, dan posisi dari generator dokumen dipertahankan
+            if let Ok(lopdf::Object::Dictionary(ref mut field_dict)) = doc.get_object_mut(field.field_id) {
+                field_dict.set("V", lopdf::Object::Reference(sig_id));
+                if !field_dict.has(b"TU") {
+                    field_dict.set("TU", text::text_string(&alt_text));
+                }
+            }
+            if let Ok(lopdf::Object::Dictionary(ref mut widget)) = doc.get_object_mut(field.widget_id) {
+                widget.set("AP", lopdf::Object::Dictionary(appearance_dict));
+                if !widget.has(b"P") {
+                    widget.set("P", lopdf::Object::Reference(page_id));
+                }
+                // --widget-flags mengganti flag tampilan saja; bit lain tetap
+                if let Some(flags) = options.widget_flags {
+                    let current = widget.get(b"F").and_then(lopdf::Object::as_i64).unwrap_or(0);
+                    widget.set("F", lopdf::Object::Integer(flags.apply(current)));
+                }
+            }
+            (field.widget_id, false)
+        }
+        None => {
+            let flags = options.widget_flags.unwrap_or(AnnotationFlags::WIDGET);
+            (create_field(&mut doc, root_id, sig_id, appearance_dict, &widget_rect, page_id, &alt_text, flags), true)
+        }
+    };
+    let placement = SignaturePlacement {
+        field_name: match &options.field_name {
+            Some(name) => name.clone(),
+            None => text::decode_text_string(doc.get_dictionary(widget_id)?.get(b"T")?.as_str()?),
+        },
+        page: doc.get_pages().into_iter().find(|(_, id)| *id == page_id).map_or(1, |(number, _)| number),
+        rect,
+        rule: rule.map(|rule| rule.name.clone()),
+    };
+
+    // Stamp (--stamp-only) diberi /NM sama dengan nama signature field-nya
+    let stamp_id = match stamp_appearance {
+        Some(stamp_appearance) => {
+            let field_name = doc.get_dictionary(widget_id)?.get(b"T")?.clone();
+            let flags = options.stamp_flags.unwrap_or(AnnotationFlags::STAMP);
+            Some(create_stamp(&mut doc, stamp_appearance, &rect, page_id, field_name, &alt_text, &timestamp, flags))
+        }
+        None => None,
+    };
+
+    // Kunci field (--lock-fields): /Lock di signature field dan field yang
+    // dikunci dijadikan read-only sebelum ditandatangani
+    if let Some(lock) = &options.lock_fields {
+        let field_id = existing_field.as_ref().map_or(widget_id, |field| field.field_id);
+        let locked = mdp::lock_fields(&mut doc, lock, field_id)?;
+        if locked.is_empty() {
+            crate::warning!("--lock-fields matched no form fields");
+        }
+        if let Ok(lopdf::Object::Dictionary(ref mut field_dict)) = doc.get_object_mut(field_id) {
+            field_dict.set("Lock", lopdf::Object::Dictionary(lock.lock_dictionary()));
+        }
+    }
+    
+    // ===== BUAT ACROFORM (Form Structure) =====
+    // AcroForm adalah struktur PDF yang mendefinisikan form fields
+    // Jika dokumen sudah punya AcroForm, field yang ada dipertahankan
+    
+    let mut acroform = form::existing_acroform(&doc, root_id).unwrap_or_default();
+    
+    // Cek flag /NeedAppearances agar tampilan field tidak berubah setelah ditandatangani
+    form::handle_need_appearances(&mut doc, &mut acroform, options.need_appearances)?;
+
+    // Form XFA: tolak, hapus XFA-nya (--drop-xfa), atau beri peringatan
+    form::handle_xfa(&doc, &mut acroform, options.drop_xfa, options.strict)?;
+
+    // Cek perubahan form yang tertunda (nilai field yang tidak tampil);
+    // dalam mode strict dokumen seperti ini ditolak
+    let pending = form::pending_form_changes(&doc, &acroform);
+    if !pending.is_empty() {
+        if options.strict {
+            bail!("refusing to sign: the form has pending changes, so what renders may differ from what is signed:\n  - {}", pending.join("\n  - "));
+        }
+        for issue in &pending {
+            crate::warning!("{}", issue);
+        }
+        crate::log::status("         what renders may differ from what is signed; use --strict to refuse such documents", true);
+    }
+    
+    // SigFlags = 3 (tanda bahwa ini adalah signed form)
+    acroform.set("SigFlags", lopdf::Object::Integer(3));
+    // DA = default appearance string untuk text di form
+    if !acroform.has(b"DA") {
+        acroform.set("DA", lopdf::Object::String(b"/F1 0 Tf 0 0 0 rg".to_vec(), lopdf::StringFormat::Literal));
+    }
+    // Fields = array yang berisi referensi ke semua fields (lama + signature baru);
+    // field yang sudah ada sudah terdaftar di /Fields
+    // AcroForm ditulis kembali ke catalog (object yang sama jika sudah indirect)
+    if new_field {
+        form::register_field(&mut doc, root_id, acroform, widget_id)?;
+    } else {
+        form::store_acroform(&mut doc, root_id, acroform)?;
+    }
+    
+    // ===== TAMBAHKAN ANNOTATION KE HALAMAN TUJUAN =====
+    
+    let tab_order = form::default_tab_order(&doc, root_id);
+    if let Ok(lopdf::Object::Dictionary(ref mut page_dict)) = doc.get_object_mut(page_id) {
+        // Tabs = urutan tab annotation; /S (urutan structure) diwajibkan PDF/UA
+        // untuk halaman yang punya annotation, /R untuk dokumen tanpa tag.
+        // Nilai yang sudah ada dipertahankan
+        if !page_dict.has(b"Tabs") {
+            page_dict.set("Tabs", lopdf::Object::Name(tab_order.to_vec()));
+        }
+    }
+    // Tambahkan signature field ke /Annots (array langsung atau indirect) sesuai
+    // urutan baca, kecuali widget field yang sudah ada memang sudah ada di sana
+    if !form::page_annotations(&doc, page_id).contains(&widget_id) {
+        form::add_annotation(&mut doc, page_id, widget_id)?;
+    }
+    if let Some(stamp_id) = stamp_id {
+        form::add_annotation(&mut doc, page_id, stamp_id)?;
+    }
+
+    // ===== INCREMENTAL UPDATE =====
+    // Bytes asli tidak diubah sama sekali: object baru/berubah, xref baru, dan
+    // trailer dengan /Prev ditambahkan di akhir file. Dengan begitu signature
+    // yang sudah ada tetap valid dan dokumen bisa ditandatangani berurutan.
+    // Update ditulis ke buffer sementara (memori/temp dir/file anonim) setelah
+    // dokumen asli, lalu disalin ke output secara atomik agar pembatalan atau
+    // error tidak pernah meninggalkan file output yang setengah jadi
+    let update_start = write_update(&mut scratch, &original, &doc, options.object_streams, security.as_ref())?;
+    cancel.check()?;
+
+    // ===== HITUNG BYTERANGE DAN DIGEST =====
+    // Cari posisi placeholder di update dan tambal /ByteRange
+    let (contents_pos, byte_range) = patch_byte_range(&mut scratch, update_start, placeholder_size)?;
+
+    // Hitung digest atas kedua segmen ByteRange (per blok, agar bisa dibatalkan
+    // dan dokumen besar tidak perlu dimuat sekaligus)
+    let digest = hash_byte_range(&mut scratch, &byte_range, options.digest, cancel)?;
+    crate::verbose!("digest", "{} {}", options.digest.label(), digest.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+
+    Ok(PreparedDocument {
+        scratch,
+        contents_pos,
+        contents_size: placeholder_size,
+        byte_range,
+        digest,
+        signing_time,
+        cades: options.pades.is_some(),
+        reused: false,
+        placement: Some(placement),
+    })
+}
+
+/// Tanggal PDF lengkap dengan selisih UTC, "D:YYYYMMDDHHmmSS+HH'mm'"
+///
+/// Tanpa selisih UTC, validator menganggap zona waktunya tidak diketahui.
+fn pdf_date(time: &chrono::DateTime<chrono::FixedOffset>) -> String {
+    let offset = time.offset().local_minus_utc();
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.unsigned_abs() / 60;
+    format!("D:{}{}{:02}'{:02}'", time.format("%Y%m%d%H%M%S"), sign, minutes / 60, minutes % 60)
+}
+
+/// Cari signature dictionary yang /Contents-nya masih kosong; jika ada lebih
+/// dari satu, yang paling akhir di file
+///
+/// Return: None jika tidak ada; `current` false jika dokumen sudah berubah
+/// setelah placeholder ditulis (placeholder tidak bisa diisi lagi)
+pub fn find_placeholder(scratch: &mut Scratch, doc: &Document) -> Result<Option<Placeholder>> {
+    let mut found: Option<([u64; 4], bool)> = None;
+    for object in doc.objects.values() {
+        let Object::Dictionary(dict) = object else { continue };
+        if dict.get(b"Type").and_then(Object::as_name).ok() != Some(b"Sig".as_slice()) {
+            continue;
+        }
+        let Ok(Object::String(contents, _)) = dict.get(b"Contents") else { continue };
+        if contents.iter().any(|&b| b != 0) {
+            continue;
+        }
+        let Ok(values) = dict.get(b"ByteRange").and_then(Object::as_array) else { continue };
+        let values: Vec<u64> = values.iter().filter_map(|v| v.as_i64().ok()).map(|v| v as u64).collect();
+        let Ok(byte_range) = <[u64; 4]>::try_from(values) else { continue };
+        let cades = dict.get(b"SubFilter").and_then(Object::as_name).ok() == Some(b"ETSI.CAdES.detached".as_slice());
+        if found.is_none_or(|(current, _)| byte_range[1] > current[1]) {
+            found = Some((byte_range, cades));
+        }
+    }
+    let Some((byte_range, cades)) = found else { return Ok(None) };
+
+    // ByteRange harus mencakup seluruh file kecuali nilai /Contents (termasuk '<' dan '>')
+    let [start, contents_pos, contents_end, tail] = byte_range;
+    let len = scratch.seek(SeekFrom::End(0))?;
+    let current = start == 0
+        && contents_end > contents_pos + 2
+        && contents_end.checked_add(tail) == Some(len)
+        && byte_at(scratch, contents_pos)? == b'<'
+        && byte_at(scratch, contents_end - 1)? == b'>';
+    let contents_size = (contents_end.saturating_sub(contents_pos + 2) / 2) as usize;
+    Ok(Some(Placeholder { contents_pos, contents_size, byte_range, cades, current }))
+}
+
+/// Baca satu byte di posisi tertentu
+fn byte_at(scratch: &mut Scratch, pos: u64) -> Result<u8> {
+    let mut byte = [0u8];
+    scratch.seek(SeekFrom::Start(pos))?;
+    scratch.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Isi placeholder yang sudah ada: field, tampilan, dan metadata signature dari
+/// saat placeholder dibuat tetap dipakai, hanya digest yang dihitung
+fn reuse_placeholder(
+    mut scratch: Scratch,
+    placeholder: Placeholder,
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+) -> Result<PreparedDocument> {
+    // Opsi yang mengubah signature dictionary tidak bisa diterapkan lagi
+    if options.certify.is_some() || options.lock_fields.is_some() {
+        bail!("the document ends with an empty signature placeholder from `pdfsign prepare`; --certify and --lock-fields cannot be added to it (sign the original document instead)");
+    }
+    if options.pades.is_some() && !placeholder.cades {
+        bail!("the document ends with an empty adbe.pkcs7.detached signature placeholder; PAdES needs one prepared as ETSI.CAdES.detached");
+    }
+    crate::log::status("Reusing the empty signature placeholder at the end of the document; its field, name and reason are kept", true);
+    // Placeholder ada di dokumen asli, jadi dokumen asli harus bisa ditulis
+    scratch.detach_base()?;
+
+    let digest = hash_byte_range(&mut scratch, &placeholder.byte_range, options.digest, cancel)?;
+    Ok(PreparedDocument {
+        scratch,
+        contents_pos: placeholder.contents_pos,
+        contents_size: placeholder.contents_size,
+        byte_range: placeholder.byte_range,
+        digest,
+        signing_time: options.signing_time.unwrap_or_else(naming::now),
+        cades: placeholder.cades || options.pades.is_some(),
+        reused: true,
+        placement: None,
+    })
+}
+
+/// Muat struktur dokumen dari buffer sementara untuk ditandatangani
+///
+/// Isi gambar, file lampiran, dan stream besar lain tidak dimuat (lihat
+/// `skip_stream_content`): signing hanya mengubah dictionary, jadi untuk arsip
+/// hasil scan yang berukuran gigabyte, memori yang tersisa setelah parsing
+/// hanya sebesar struktur dokumennya.
+///
+/// Object baru di update diberi nomor berurutan setelah nomor tertinggi yang
+/// pernah dipakai dokumen (lihat `highest_object_number`); object lama tidak
+/// pernah diberi nomor baru.
+///
+/// Pelanggaran struktur file (lihat `syntax::check_document`) diperbaiki dan
+/// ditampilkan sebagai peringatan, atau ditolak jika `mode` Strict.
+pub fn load_document(scratch: &mut Scratch, mode: ParseMode) -> Result<Document> {
+    let (doc, issues) = read_document(scratch, mode)?;
+    syntax::warn(&issues);
+    Ok(doc)
+}
+
+/// Muat dokumen seperti `load_document`; dengan --repair, dokumen yang tidak
+/// bisa dibaca lewat cross-reference-nya dibangun ulang (`syntax::rebuild`) dan
+/// ditulis ulang seluruhnya ke buffer sementara baru yang menggantikan `scratch`
+fn load_or_repair(scratch: &mut Scratch, options: &SignatureOptions) -> Result<Document> {
+    let err = match load_document(scratch, options.parse_mode()) {
+        Err(err) if options.repair => err,
+        loaded => return loaded,
+    };
+    crate::warning!("the document cannot be parsed ({:#}); rebuilding it from its objects (--repair)", err);
+    let mut doc = scratch.with_contents(syntax::rebuild)??;
+    let signatures = form::signature_fields(&doc);
+    if !signatures.is_empty() {
+        let names: Vec<&str> = signatures.iter().map(|field| field.name.as_str()).collect();
+        crate::warning!("the repaired document is rewritten, so its existing signature(s) no longer verify: {}", names.join(", "));
+    }
+
+    // Tulis ulang sebagai satu revisi tanpa object yang tidak terpakai
+    doc.prune_objects();
+    let mut repaired = options.scratch.create()?;
+    doc.save_to(&mut repaired)?;
+    *scratch = repaired;
+    crate::verbose!("load", "repaired document rewritten with {} objects", doc.objects.len());
+    load_document(scratch, ParseMode::Lenient)
+}
+
+/// Tulis ulang dokumen terenkripsi tanpa enkripsi (--decrypt-output)
+///
+/// Seluruh dokumen (termasuk isi stream yang dilewati `read_document`) dimuat
+/// dan didekripsi, lalu ditulis sebagai satu revisi tanpa /Encrypt ke buffer
+/// sementara baru yang menggantikan `scratch`. Hanya pemilik dokumen yang
+/// boleh menghapus enkripsinya.
+///
+/// Return: dokumen hasil tulis ulang, dimuat seperti `load_document`
+fn decrypt_copy(scratch: &mut Scratch, security: &SecurityHandler, options: &SignatureOptions) -> Result<Document> {
+    if !security.is_owner() {
+        bail!("removing the encryption of the document needs its owner password; --pdf-password is only the user password");
+    }
+    let mut doc = scratch.with_contents(|bytes| -> Result<Document> {
+        let mut doc = Document::load_mem(bytes)?;
+        syntax::check_document(bytes, &mut doc, ParseMode::Lenient)?;
+        SecurityHandler::open(&mut doc, bytes, options.pdf_password.as_deref())?;
+        Ok(doc)
+    })??;
+    let signatures = form::signature_fields(&doc);
+    if !signatures.is_empty() {
+        let names: Vec<&str> = signatures.iter().map(|field| field.name.as_str()).collect();
+        crate::warning!("the decrypted copy is rewritten, so its existing signature(s) no longer verify: {}", names.join(", "));
+    }
+
+    // Tanpa /Encrypt, object dictionary-nya tidak terpakai lagi dan ikut dibuang
+    doc.trailer.remove(b"Encrypt");
+    doc.prune_objects();
+    let mut decrypted = options.scratch.create()?;
+    doc.save_to(&mut decrypted)?;
+    *scratch = decrypted;
+    crate::warning!("the signed copy is written without encryption (--decrypt-output)");
+    load_document(scratch, ParseMode::Lenient)
+}
+
+/// Jalankan hook klasifikasi (--classify-hook, lalu `SigningHooks::on_document`)
+/// atas dokumen yang sudah dimuat
+///
+/// Return: opsi untuk dokumen ini, setelah diubah oleh hook
+fn classify_document(scratch: &mut Scratch, doc: &Document, options: &SignatureOptions, hooks: &mut dyn SigningHooks) -> Result<SignatureOptions> {
+    // Hook melihat isi dokumen terenkripsi setelah didekripsi
+    let decrypted;
+    let doc = if doc.trailer.has(b"Encrypt") {
+        let mut copy = doc.clone();
+        decrypt_document(scratch, &mut copy, options)?;
+        decrypted = copy;
+        &decrypted
+    } else {
+        doc
+    };
+    let view = DocumentView::new(doc);
+    let mut classified = options.clone();
+    if let Some(command) = &options.classify_hook {
+        classify::run_command(command, &view, &mut classified)?;
+    }
+    hooks.on_document(&view, &mut classified)?;
+    // Nilai dari hook diperiksa seperti nilai dari command line
+    classified.normalize_signer_text()?;
+    Ok(classified)
+}
+
+/// Dekripsi dokumen terenkripsi dengan --pdf-password (lihat `SecurityHandler::open`)
+///
+/// Return: security handler untuk mengenkripsi update, None jika dokumen tidak terenkripsi
+fn decrypt_document(scratch: &mut Scratch, doc: &mut Document, options: &SignatureOptions) -> Result<Option<SecurityHandler>> {
+    scratch.with_contents(|bytes| SecurityHandler::open(doc, bytes, options.pdf_password.as_deref()))?
+}
+
+/// Sama seperti `load_document`, tetapi pelanggaran struktur file dikembalikan
+/// tanpa ditampilkan
+pub(crate) fn read_document(scratch: &mut Scratch, mode: ParseMode) -> Result<(Document, Vec<SyntaxIssue>)> {
+    let (mut doc, issues) = scratch.with_contents(|bytes| -> Result<_> {
+        let mut doc = lopdf::Reader { buffer: bytes, document: Document::new() }.read(Some(skip_stream_content))?;
+        let issues = syntax::check_document(bytes, &mut doc, mode)?;
+        Ok((doc, issues))
+    })??;
+    doc.max_id = highest_object_number(&doc);
+    Ok((doc, issues))
+}
+
+/// Nomor object tertinggi yang dipakai dokumen
+///
+/// lopdf hanya melihat entri xref. Producer yang kurang rapi bisa menulis
+/// object dengan nomor di luar xref (header "N G obj" tidak sama dengan entri
+/// xref-nya, object stream berisi nomor lain) atau /Size yang lebih besar dari
+/// entri xref; nomor-nomor itu juga tidak boleh dipakai untuk object baru.
+/// Nomor free di tengah (gap di xref) juga tidak dipakai ulang, karena generation
+/// berikutnya harus diambil dari free list.
+fn highest_object_number(doc: &Document) -> u32 {
+    let in_xref = doc.reference_table.max_id();
+    let loaded = doc.objects.keys().map(|&(id, _)| id).max().unwrap_or(0);
+    let declared = doc
+        .trailer
+        .get(b"Size")
+        .and_then(Object::as_i64)
+        .ok()
+        .and_then(|size| u32::try_from(size - 1).ok())
+        // /Size yang tidak masuk akal diabaikan (batas nomor object PDF 8.388.607)
+        .filter(|&id| id <= 8_388_607)
+        .unwrap_or(0);
+    in_xref.max(loaded).max(declared)
+}
+
+/// Filter parser: kosongkan isi stream yang tidak pernah dibaca saat signing
+///
+/// Object stream, xref stream, form XObject (tampilan field), dan metadata
+/// XMP (deteksi PDF/A) selalu dimuat.
+/// Stream yang dikosongkan tidak boleh ikut ditulis ulang (lihat `incremental_update`).
+fn skip_stream_content(id: lopdf::ObjectId, object: &mut lopdf::Object) -> Option<(lopdf::ObjectId, lopdf::Object)> {
+    let lopdf::Object::Stream(stream) = object else {
+        // Object di dalam object stream: nilai kembalian inilah yang disimpan
+        return Some((id, object.clone()));
+    };
+    let name = |key: &[u8]| stream.dict.get(key).and_then(lopdf::Object::as_name).ok();
+    let needed = matches!(name(b"Type"), Some(b"ObjStm" | b"XRef" | b"Metadata")) || name(b"Subtype") == Some(b"Form");
+    let unused = name(b"Subtype") == Some(b"Image") || name(b"Type") == Some(b"EmbeddedFile") || stream.content.len() > LARGE_STREAM;
+    if unused && !needed {
+        stream.content = Vec::new();
+        // Tanpa posisi, parser tidak mengisi ulang isi stream dari /Length
+        stream.start_position = None;
+    }
+    // Untuk object di level atas, lopdf menyimpan `object` yang sudah diubah
+    Some((id, lopdf::Object::Null))
+}
+
+/// Apakah isi stream tidak dimuat oleh `load_document`
+fn content_skipped(stream: &lopdf::Stream) -> bool {
+    stream.content.is_empty() && stream.dict.get(b"Length").is_ok_and(|length| length.as_i64().ok() != Some(0))
+}
+
+/// Buat incremental update: hanya object yang baru atau berubah dibanding
+/// `original`, dengan trailer yang menunjuk ke xref sebelumnya (/Prev)
+pub fn incremental_update(original: &Document, doc: &Document) -> Result<Document> {
+    let mut update = Document::new_from_prev(original);
+    update.version = doc.version.clone();
+    update.max_id = doc.max_id;
+    for (id, object) in &doc.objects {
+        if original.objects.get(id) != Some(object) {
+            if let lopdf::Object::Stream(stream) = object {
+                if content_skipped(stream) {
+                    bail!("object {} {} R would be rewritten without its stream data", id.0, id.1);
+                }
+            }
+            update.objects.insert(*id, object.clone());
+        }
+    }
+    Ok(update)
+}
+
+/// Serialisasi incremental update (tanpa bytes asli, lihat `update::save_to`)
+///
+/// Parameter:
+///   - original: dokumen sebelum diubah
+///   - update: hasil `incremental_update`
+///   - base: panjang file sebelum update
+///   - object_streams: masukkan object baru ke object stream terkompresi
+///     (diabaikan untuk dokumen terenkripsi)
+///   - target: tujuan penulisan
+pub fn save_update(original: &Document, update: &Document, base: u64, object_streams: bool, target: &mut impl Write) -> Result<()> {
+    let object_streams = object_streams && !original.trailer.has(b"Encrypt");
+    update::save_to(original, update, base, object_streams, target)
+}
+
+/// Tambahkan perubahan `doc` dibanding `original` sebagai incremental update
+/// di akhir buffer sementara (bytes sebelumnya tidak diubah); untuk dokumen
+/// terenkripsi, object update dienkripsi dengan `security`
+///
+/// Return: offset awal update (placeholder baru dicari mulai dari sini)
+pub fn write_update(scratch: &mut Scratch, original: &Document, doc: &Document, object_streams: bool, security: Option<&SecurityHandler>) -> Result<u64> {
+    let mut update = incremental_update(original, doc)?;
+    if let Some(security) = security {
+        security.encrypt_update(&mut update)?;
+    }
+    if crate::log::enabled(crate::log::Verbosity::Debug) {
+        for (id, object) in &update.objects {
+            crate::debug!("update", "object {} {} R: {}", id.0, id.1, object.type_name().unwrap_or(match object { Object::Stream(_) => "stream", Object::Dictionary(_) => "dictionary", _ => "object" }));
+        }
+    }
+    // Update dimulai di baris baru
+    let mut start = scratch.seek(SeekFrom::End(0))?;
+    let mut last = [0u8];
+    if start > 0 {
+        scratch.seek(SeekFrom::Start(start - 1))?;
+        scratch.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            scratch.write_all(b"\n")?;
+            start += 1;
+        }
+    }
+    save_update(original, &update, start, object_streams, scratch)?;
+    Ok(start)
+}
+
+/// Muat ulang isi buffer sementara, ubah dokumen, lalu tambahkan perubahannya
+/// sebagai incremental update di akhir buffer (bytes sebelumnya tidak diubah)
+///
+/// Return: offset awal update baru (placeholder baru dicari mulai dari sini)
+#[cfg(feature = "network")]
+fn append_update(scratch: &mut Scratch, options: &SignatureOptions, update: impl FnOnce(&mut Document) -> Result<()>) -> Result<u64> {
+    // Peringatan struktur file sudah ditampilkan saat dokumen pertama kali dimuat
+    let (mut original, _) = read_document(scratch, ParseMode::Lenient)?;
+    let security = decrypt_document(scratch, &mut original, options)?;
+    let mut doc = original.clone();
+    update(&mut doc)?;
+    write_update(scratch, &original, &doc, options.object_streams, security.as_ref())
+}
+
+/// Tandatangani setiap file lampiran dengan CMS detached (--sign-attachments)
+/// dan simpan hasilnya sebagai lampiran "<nama>.p7s" di incremental update baru
+///
+/// Parameter:
+///   - scratch: PDF yang akan ditandatangani
+///   - signer: kunci penandatangan
+///   - embedded: sertifikat yang disisipkan di CMS
+///   - options: opsi signature (digest, waktu, object stream)
+///   - cancel: token pembatalan
+fn sign_attachments(
+    scratch: &mut Scratch,
+    signer: &dyn Signer,
+    embedded: &[Vec<u8>],
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let (original, _) = read_document(scratch, ParseMode::Lenient)?;
+    if original.trailer.has(b"Encrypt") {
+        bail!("--sign-attachments cannot add files to an encrypted document");
+    }
+    let attachments = scratch.with_contents(attachment_signatures::read_attachments)??;
+    if attachments.is_empty() {
+        crate::warning!("--sign-attachments: the document has no embedded files to sign");
+        return Ok(());
+    }
+
+    let signing_time = options.signing_time.unwrap_or_else(naming::now);
+    let mut signatures = Vec::new();
+    for attachment in attachments {
+        cancel.check()?;
+        let digest = options.digest.digest(&attachment.content);
+        // File .p7s berdiri sendiri di luar PDF (tanpa /M), jadi signingTime selalu
+        // ikut di signed attributes
+        let signature = build_signed_data(&digest, options.digest, signer, embedded, signing_time.with_timezone(&chrono::Utc), SignedAttributes::Pkcs7)
+            .with_context(|| format!("cannot sign embedded file '{}'", attachment.name))?;
+        signatures.push((attachment, signature));
+    }
+
+    let mut doc = original.clone();
+    attachment_signatures::add_signatures(&mut doc, &signatures)?;
+    write_update(scratch, &original, &doc, options.object_streams, None)?;
+    Ok(())
+}
+
+/// Cari placeholder /ByteRange dan /Contents (mulai dari offset `search_from`),
+/// lalu tambal /ByteRange dengan nilai asli
+///
+/// Return: posisi '<' nilai /Contents dan ByteRange yang ditulis
+pub(crate) fn patch_byte_range(scratch: &mut Scratch, search_from: u64, contents_size: usize) -> Result<(u64, [u64; 4])> {
+    let total_len = scratch.seek(SeekFrom::End(0))?;
+    let byte_range_pos = find_in_scratch(scratch, &byte_range_placeholder(), search_from)?
+        .ok_or_else(|| anyhow!("cannot locate /ByteRange placeholder in serialized document"))?;
+    let contents_pos = find_in_scratch(scratch, &contents_placeholder(contents_size), search_from)?
+        .ok_or_else(|| anyhow!("cannot locate /Contents placeholder in serialized document"))?;
+    // Bagian yang dikecualikan adalah nilai /Contents termasuk '<' dan '>'
+    let contents_end = contents_pos + contents_placeholder(contents_size).len() as u64;
+    let byte_range = [0, contents_pos, contents_end, total_len - contents_end];
+    crate::verbose!(
+        "placeholder",
+        "update at offset {} ({} bytes), /ByteRange at {}, /Contents at {}..{} ({} bytes reserved)",
+        search_from,
+        total_len - search_from,
+        byte_range_pos,
+        contents_pos,
+        contents_end,
+        contents_size
+    );
+    crate::verbose!("placeholder", "ByteRange [{} {} {} {}]", byte_range[0], byte_range[1], byte_range[2], byte_range[3]);
+
+    // Tambal /ByteRange dengan nilai asli, dipadding spasi agar panjangnya tetap
+    let mut byte_range_value = format!(
+        "/ByteRange[{} {} {} {}]",
+        byte_range[0], byte_range[1], byte_range[2], byte_range[3]
+    )
+    .into_bytes();
+    byte_range_value.resize(byte_range_placeholder().len(), b' ');
+    scratch.seek(SeekFrom::Start(byte_range_pos))?;
+    scratch.write_all(&byte_range_value)?;
+    Ok((contents_pos, byte_range))
+}
+
+/// Tulis isi /Contents (hex) ke placeholder; sisa placeholder tetap nol
+pub fn write_contents(scratch: &mut Scratch, contents_pos: u64, contents_size: usize, content: &[u8]) -> Result<()> {
+    if content.len() > contents_size {
+        bail!(
+            "signature ({} bytes) does not fit the reserved /Contents space ({} bytes)",
+            content.len(),
+            contents_size
+        );
+    }
+    let hex: String = content.iter().map(|b| format!("{:02X}", b)).collect();
+    scratch.seek(SeekFrom::Start(contents_pos + 1))?;
+    scratch.write_all(hex.as_bytes())?;
+    Ok(())
+}
+
+/// Hitung digest atas segmen-segmen ByteRange yang dibaca dari buffer sementara
+/// Data dibaca per blok 1 MiB; token pembatalan diperiksa di setiap blok
+pub fn hash_byte_range(
+    scratch: &mut Scratch,
+    byte_range: &[u64; 4],
+    algorithm: DigestAlgorithm,
+    cancel: &CancellationToken,
+) -> Result<Vec<u8>> {
+    let mut hasher = algorithm.hasher();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    // Progress bar hanya untuk dokumen besar; dokumen biasa selesai seketika
+    let total = byte_range[1] + byte_range[3];
+    let progress = (total >= LARGE_HASH).then(|| Progress::bytes("Hashing", total));
+    for pair in byte_range.chunks(2) {
+        scratch.seek(SeekFrom::Start(pair[0]))?;
+        let mut remaining = pair[1];
+        while remaining > 0 {
+            cancel.check()?;
+            let len = remaining.min(buffer.len() as u64) as usize;
+            scratch.read_exact(&mut buffer[..len])?;
+            hasher.update(&buffer[..len]);
+            remaining -= len as u64;
+            if let Some(progress) = &progress {
+                progress.advance(len as u64);
+            }
+        }
+    }
+    Ok(hasher.finalize())
+}
+
+/// Cari kemunculan pertama `needle` di buffer sementara, mulai dari offset `start`
+/// Return: offset byte dari awal buffer, atau None jika tidak ditemukan
+fn find_in_scratch(scratch: &mut Scratch, needle: &[u8], start: u64) -> Result<Option<u64>> {
+    scratch.seek(SeekFrom::Start(start))?;
+    let mut window: Vec<u8> = Vec::new(); // Sisa blok sebelumnya + blok baru
+    let mut window_start = start;         // Offset byte pertama di window
+    let mut chunk = vec![0u8; 1024 * 1024];
+    loop {
+        let read = scratch.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        window.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = window.windows(needle.len()).position(|w| w == needle) {
+            return Ok(Some(window_start + pos as u64));
+        }
+        // Simpan ekor window agar needle yang terpotong antar blok tetap ditemukan
+        let keep = needle.len().saturating_sub(1).min(window.len());
+        let drop = window.len() - keep;
+        window.drain(..drop);
+        window_start += drop as u64;
+    }
+}
+
+/// Buat signature field baru (field dan widget dalam satu dictionary) dengan
+/// nama unik berikutnya (Signature1, Signature2, ...)
+///
+/// Return: object ID field
+#[allow(clippy::too_many_arguments)]
+fn create_field(
+    doc: &mut Document,
+    root_id: lopdf::ObjectId,
+    sig_id: lopdf::ObjectId,
+    appearance_dict: lopdf::Dictionary,
+    rect: &Rect,
+    page_id: lopdf::ObjectId,
+    alt_text: &str,
+    flags: AnnotationFlags,
+) -> lopdf::ObjectId {
     let mut field_dict = lopdf::Dictionary::new();
     field_dict.set("Type", lopdf::Object::Name(b"Annot".to_vec()));
     field_dict.set("Subtype", lopdf::Object::Name(b"Widget".to_vec()));
     field_dict.set("FT", lopdf::Object::Name(b"Sig".to_vec())); // Field Type = Signature
-    field_dict.set("T", lopdf::Object::String(b"Signature1".to_vec(), lopdf::StringFormat::Literal));
-    field_dict.set("F", lopdf::Object::Integer(4)); // Flags untuk form field
+    // Nama field unik (Signature1, Signature2, ...) agar penandatangan berikutnya
+    // tidak menimpa field signature yang sudah ada
+    let field_name = form::unused_field_name(doc, &form::existing_acroform(doc, root_id).unwrap_or_default(), "Signature");
+    field_dict.set("T", text::text_string(&field_name));
+    field_dict.set("F", lopdf::Object::Integer(flags.bits())); // Flag annotation (--widget-flags)
     // V = reference ke signature object yang dibuat di atas
     field_dict.set("V", lopdf::Object::Reference(sig_id));
     // Appearance stream untuk field
@@ -206,104 +1637,230 @@ Q".to_vec();
     // Rect = posisi dan ukuran signature field di halaman PDF
     // Format: [left, bottom, right, top]
     field_dict.set("Rect", lopdf::Object::Array(vec![
-        lopdf::Object::Integer(100),   // Left edge
-        lopdf::Object::Integer(650),   // Bottom edge
-        lopdf::Object::Integer(300),   // Right edge
-        lopdf::Object::Integer(700),   // Top edge
+        lopdf::Object::Real(rect.left),     // Left edge
+        lopdf::Object::Real(rect.bottom),   // Bottom edge
+        lopdf::Object::Real(rect.right),    // Right edge
+        lopdf::Object::Real(rect.top),      // Top edge
     ]));
-    // P = reference ke halaman pertama PDF
-    field_dict.set("P", lopdf::Object::Reference((2, 0)));
+    // P = reference ke halaman tempat signature ditampilkan
+    field_dict.set("P", lopdf::Object::Reference(page_id));
+    // Alternate text untuk screen reader (PDF/UA):
+    // TU = nama field yang dibacakan ke user, Contents = deskripsi annotation
+    field_dict.set("TU", text::text_string(alt_text));
+    field_dict.set("Contents", text::text_string(alt_text));
     
-    // Tambahkan field ke PDF document
-    let field_id = doc.add_object(field_dict);
     
-    // ===== BUAT ACROFORM (Form Structure) =====
-    // AcroForm adalah struktur PDF yang mendefinisikan form fields
-    
-    let mut acroform = lopdf::Dictionary::new();
-    // SigFlags = 3 (tanda bahwa ini adalah signed form)
-    acroform.set("SigFlags", lopdf::Object::Integer(3));
-    // DA = default appearance string untuk text di form
-    acroform.set("DA", lopdf::Object::String(b"/F1 0 Tf 0 0 0 rg".to_vec(), lopdf::StringFormat::Literal));
-    // Fields = array yang berisi referensi ke semua signature fields
-    acroform.set("Fields", lopdf::Object::Array(vec![
-        lopdf::Object::Reference(field_id),
+    // Tambahkan field ke PDF document
+    doc.add_object(field_dict)
+}
+
+/// Appearance kosong untuk widget yang tidak terlihat (--stamp-only)
+fn empty_appearance(doc: &mut Document) -> lopdf::Dictionary {
+    let mut stream_dict = lopdf::Dictionary::new();
+    stream_dict.set("Type", lopdf::Object::Name(b"XObject".to_vec()));
+    stream_dict.set("Subtype", lopdf::Object::Name(b"Form".to_vec()));
+    stream_dict.set("BBox", lopdf::Object::Array(vec![0.into(), 0.into(), 0.into(), 0.into()]));
+    let stream_id = doc.add_object(lopdf::Stream::new(stream_dict, Vec::new()));
+    let mut appearance_dict = lopdf::Dictionary::new();
+    appearance_dict.set("N", lopdf::Object::Reference(stream_id));
+    appearance_dict
+}
+
+/// Buat annotation /Stamp yang menampilkan tampilan signature (--stamp-only)
+///
+/// Stamp adalah markup annotation biasa, jadi viewer menampilkannya tanpa
+/// mesin form; ia ikut ditandatangani karena ditambahkan di revisi yang sama.
+///
+/// Parameter:
+///   - appearance_dict: /AP dengan tampilan signature (selebar `rect`)
+///   - rect: posisi stamp di halaman
+///   - page_id: halaman tempat stamp ditampilkan
+///   - field_name: /T signature field; dipakai sebagai /NM agar stamp bisa
+///     dihubungkan ke signature-nya
+///   - alt_text: deskripsi untuk screen reader (/Contents)
+///   - timestamp: tanggal PDF waktu penandatanganan (/M)
+///   - flags: flag annotation (--stamp-flags)
+#[allow(clippy::too_many_arguments)]
+fn create_stamp(
+    doc: &mut Document,
+    appearance_dict: lopdf::Dictionary,
+    rect: &Rect,
+    page_id: lopdf::ObjectId,
+    field_name: lopdf::Object,
+    alt_text: &str,
+    timestamp: &str,
+    flags: AnnotationFlags,
+) -> lopdf::ObjectId {
+    let mut stamp = lopdf::Dictionary::new();
+    stamp.set("Type", lopdf::Object::Name(b"Annot".to_vec()));
+    stamp.set("Subtype", lopdf::Object::Name(b"Stamp".to_vec()));
+    stamp.set("Rect", lopdf::Object::Array(vec![
+        lopdf::Object::Real(rect.left),
+        lopdf::Object::Real(rect.bottom),
+        lopdf::Object::Real(rect.right),
+        lopdf::Object::Real(rect.top),
     ]));
-    
-    // Tambahkan AcroForm ke PDF document
-    let acroform_id = doc.add_object(acroform);
-    
-    // ===== UPDATE PDF CATALOG ROOT =====
-    // Catalog adalah root object yang mereferensikan semua struktur PDF
-    
-    let root_id = (1, 0); // Object ID untuk catalog biasanya (1, 0)
-    
-    // Dapatkan mutable reference ke catalog
-    if let Ok(ref mut root) = doc.get_object_mut(root_id) {
-        if let lopdf::Object::Dictionary(ref mut dict) = root {
-            // Tambahkan referensi AcroForm ke catalog
-            dict.set("AcroForm", lopdf::Object::Reference(acroform_id));
+    stamp.set("AP", lopdf::Object::Dictionary(appearance_dict));
+    stamp.set("P", lopdf::Object::Reference(page_id));
+    // F default = Print + Locked: ikut dicetak, tidak bisa dipindah atau dihapus
+    stamp.set("F", lopdf::Object::Integer(flags.bits()));
+    stamp.set("NM", field_name);
+    stamp.set("Contents", text::text_string(alt_text));
+    stamp.set("M", lopdf::Object::String(timestamp.as_bytes().to_vec(), lopdf::StringFormat::Literal));
+    doc.add_object(stamp)
+}
+
+/// Kotak halaman yang terlihat: /CropBox, atau /MediaBox jika tidak ada
+/// Keduanya boleh diwarisi dari node /Pages induk
+///
+/// Parameter:
+///   - doc: dokumen PDF
+///   - page_id: object ID halaman (dari page tree)
+pub(crate) fn page_box(doc: &Document, page_id: lopdf::ObjectId) -> Result<Rect> {
+    for key in [&b"CropBox"[..], b"MediaBox"] {
+        if let Some([left, bottom, right, top]) = pages::page_box(doc, page_id, key)? {
+            return Ok(Rect { left, bottom, right, top });
         }
     }
-    
-    // ===== TAMBAHKAN ANNOTATION KE HALAMAN PERTAMA =====
-    // Halaman pertama biasanya adalah object (2, 0)
-    
-    if let Ok(ref mut page) = doc.get_object_mut((2, 0)) {
-        if let lopdf::Object::Dictionary(ref mut page_dict) = page {
-            // Cek apakah sudah ada Annots array
-            if let Ok(annots_ref) = page_dict.get_mut(b"Annots") {
-                // Jika ada, tambahkan signature field ke array
-                if let lopdf::Object::Array(ref mut annots) = annots_ref {
-                    annots.push(lopdf::Object::Reference(field_id));
-                } else {
-                    // Jika ada tapi bukan array, buat array baru
-                    page_dict.set("Annots", lopdf::Object::Array(vec![
-                        lopdf::Object::Reference(field_id),
-                    ]));
+    bail!("page {} {} R has no /MediaBox", page_id.0, page_id.1)
+}
+
+/// Kotak widget untuk `placement` di halaman `page_id`
+///
+/// `Placement::Default` memakai `DEFAULT_RECT`; jika kotak itu bertumpuk
+/// dengan signature yang sudah ada di halaman (widget signature field atau
+/// stamp --stamp-only), kotaknya digeser ke bawah signature tersebut. Jika
+/// tidak ada lagi tempat di atas tepi bawah halaman, posisinya harus
+/// ditentukan sendiri.
+pub(crate) fn placement_rect(doc: &Document, page_id: lopdf::ObjectId, placement: Placement) -> Result<Rect> {
+    match placement {
+        Placement::Page(_, rect) => Ok(rect),
+        Placement::Anchored(_, position) => Ok(position.rect_in(&page_box(doc, page_id)?)),
+        Placement::Default(page) => {
+            let taken = form::signature_rects(doc, page_id);
+            let bottom_edge = page_box(doc, page_id)?.bottom;
+            let mut rect = DEFAULT_RECT;
+            while let Some(other) = taken.iter().find(|other| rect.left < other[2] && other[0] < rect.right && rect.bottom < other[3] && other[1] < rect.top) {
+                let bottom = other[1] - DEFAULT_RECT_GAP - DEFAULT_RECT.height();
+                if bottom < bottom_edge {
+                    bail!("page {} has no free space for another signature below the existing ones; choose a position with --rect or --position", page);
                 }
-            } else {
-                // Jika tidak ada Annots, buat baru dengan signature field
-                page_dict.set("Annots", lopdf::Object::Array(vec![
-                    lopdf::Object::Reference(field_id),
-                ]));
+                rect = Rect::new(rect.left, bottom, DEFAULT_RECT.width(), DEFAULT_RECT.height());
             }
+            Ok(rect)
         }
     }
+}
 
-    // Simpan PDF yang sudah ditandatangani ke file output
-    doc.save(output)?;
-    
-    // Tampilkan pesan sukses ke user
-    println!("PDF signed: {}", output);
-    println!("Signature: PKCS#7 format (ECDSA)");
-    println!("Signer: {}", metadata.name);
+/// Placeholder /Contents (nol) dan /ByteRange untuk signature dictionary
+/// Angka ByteRange dibuat selebar mungkin agar nilai asli selalu muat
+pub(crate) fn signature_placeholders(contents_size: usize) -> (lopdf::Object, lopdf::Object) {
+    (
+        lopdf::Object::String(vec![0x00; contents_size], lopdf::StringFormat::Hexadecimal),
+        lopdf::Object::Array(vec![
+            lopdf::Object::Integer(0),
+            lopdf::Object::Integer(BYTE_RANGE_PLACEHOLDER),
+            lopdf::Object::Integer(BYTE_RANGE_PLACEHOLDER),
+            lopdf::Object::Integer(BYTE_RANGE_PLACEHOLDER),
+        ]),
+    )
+}
 
-    Ok(())
+/// Placeholder /ByteRange persis seperti yang ditulis lopdf
+fn byte_range_placeholder() -> Vec<u8> {
+    format!(
+        "/ByteRange[0 {} {} {}]",
+        BYTE_RANGE_PLACEHOLDER, BYTE_RANGE_PLACEHOLDER, BYTE_RANGE_PLACEHOLDER
+    )
+    .into_bytes()
 }
 
-/// Helper function untuk encode panjang dalam format DER
-/// Digunakan untuk encoding panjang SEQUENCE dan object lain dalam PKCS#7
-/// 
-/// DER length encoding:
-/// - Jika < 128: encode sebagai 1 byte
-/// - Jika >= 128: encode sebagai 0x80|numOfBytes diikuti bytes panjang
-fn encode_der_length(len: usize) -> Vec<u8> {
-    if len < 128 {
-        // Panjang pendek: langsung sebagai 1 byte
-        vec![len as u8]
-    } else {
-        // Panjang panjang: encode sebagai multi-byte
-        let mut bytes = Vec::new();
-        let mut l = len;
-        // Ambil bytes dari kanan ke kiri
-        while l > 0 {
-            bytes.insert(0, (l & 0xff) as u8);
-            l >>= 8;
-        }
-        // Tambahkan byte indicator: 0x80 | jumlah bytes
-        let mut result = vec![0x80 | bytes.len() as u8];
-        result.extend_from_slice(&bytes);
-        result
+/// Placeholder nilai /Contents persis seperti yang ditulis lopdf: <0000...>
+/// Parameter: size = ukuran ruang signature dalam bytes (sebelum hex)
+fn contents_placeholder(size: usize) -> Vec<u8> {
+    let mut placeholder = vec![b'<'];
+    placeholder.resize(1 + size * 2, b'0');
+    placeholder.push(b'>');
+    placeholder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::ScratchSpace;
+    use std::collections::BTreeSet;
+
+    /// PDF kecil dengan xref klasik: `objects` berisi nomor entri xref dan
+    /// teks object lengkap (header "N G obj" boleh berbeda dari nomor entri),
+    /// `entries` jumlah entri xref, `size` nilai /Size di trailer
+    fn pdf(objects: &[(u32, &str)], entries: u32, size: u32) -> Vec<u8> {
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+        let mut offsets = vec![None; entries as usize];
+        for &(number, text) in objects {
+            offsets[number as usize] = Some(bytes.len());
+            bytes.extend_from_slice(text.as_bytes());
+            bytes.push(b'\n');
+        }
+        let start = bytes.len();
+        bytes.extend_from_slice(format!("xref\n0 {}\n", entries).as_bytes());
+        for (number, offset) in offsets.iter().enumerate() {
+            let entry = match offset {
+                Some(offset) => format!("{:010} 00000 n \n", offset),
+                None if number == 0 => "0000000000 65535 f \n".to_string(),
+                None => "0000000000 00001 f \n".to_string(),
+            };
+            bytes.extend_from_slice(entry.as_bytes());
+        }
+        bytes.extend_from_slice(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", size, start).as_bytes());
+        bytes
+    }
+
+    const CATALOG: &str = "1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj";
+    const PAGES: &str = "2 0 obj << /Type /Pages /Kids [] /Count 0 >> endobj";
+
+    fn load(bytes: Vec<u8>) -> Document {
+        let mut scratch = ScratchSpace::default().create_from(bytes).unwrap();
+        read_document(&mut scratch, ParseMode::Lenient).unwrap().0
+    }
+
+    /// Object baru mulai dari `first_new`, dan incremental update hanya berisi
+    /// object baru itu: object lama tetap dengan nomor dan isinya
+    fn assert_appends_after(original: Document, first_new: u32) {
+        let existing: BTreeSet<lopdf::ObjectId> = original.objects.keys().copied().collect();
+        let mut doc = original.clone();
+        let first = doc.add_object(Object::Null);
+        let second = doc.add_object(Object::Null);
+        assert_eq!(first, (first_new, 0));
+        assert_eq!(second, (first_new + 1, 0));
+
+        let update = incremental_update(&original, &doc).unwrap();
+        let written: BTreeSet<lopdf::ObjectId> = update.objects.keys().copied().collect();
+        assert_eq!(written, BTreeSet::from([first, second]));
+        for id in &existing {
+            assert_eq!(doc.objects.get(id), original.objects.get(id));
+        }
+    }
+
+    #[test]
+    fn size_larger_than_xref() {
+        let doc = load(pdf(&[(1, CATALOG), (2, PAGES)], 3, 20));
+        assert_eq!(highest_object_number(&doc), 19);
+        assert_appends_after(doc, 20);
+    }
+
+    #[test]
+    fn free_entries_in_the_middle_are_not_reused() {
+        let doc = load(pdf(&[(1, CATALOG), (2, PAGES), (5, "5 0 obj (last) endobj")], 6, 6));
+        assert_eq!(highest_object_number(&doc), 5);
+        assert_appends_after(doc, 6);
+    }
+
+    #[test]
+    fn object_header_above_xref() {
+        // Entri xref 3 menunjuk ke object yang header-nya bernomor 40
+        let doc = load(pdf(&[(1, CATALOG), (2, PAGES), (3, "40 0 obj (misnumbered) endobj")], 4, 4));
+        assert!(doc.objects.contains_key(&(40, 0)));
+        assert_eq!(highest_object_number(&doc), 40);
+        assert_appends_after(doc, 41);
     }
 }
@@ -0,0 +1,452 @@
+// Import library yang diperlukan
+use std::io::{Read, Seek, SeekFrom, Write}; // Input/output berupa reader dan writer
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::cms::{split_certificates, EmbedChain}; // Rantai sertifikat
+use crate::crypto::digest::DigestAlgorithm; // Algoritma digest signature
+use crate::crypto::keyfile; // Kunci PKCS#8 terenkripsi
+use crate::crypto::p12::load_pkcs12; // Bundle PKCS#12
+use crate::crypto::keystore::load_keystore; // Kunci di keystore OS
+use crate::crypto::kms::{load_kms, KmsKey}; // Kunci di KMS cloud
+use crate::crypto::pkcs11::{load_pkcs11, Pkcs11Token}; // Kunci di token PKCS#11
+use crate::crypto::policy::AlgorithmPolicy; // Deny-list algoritma lemah
+use crate::crypto::rsa::RsaPadding; // Padding untuk kunci RSA
+use crate::crypto::signer::{signer_from_bytes, Credentials}; // Backend penandatanganan (ECDSA / RSA)
+use crate::crypto::tsa::Tsa; // Time Stamping Authority
+use crate::error::Error; // Error API library
+use crate::pdf::appearance::{AppearanceCache, AppearanceLanguage, TextColor}; // Teks dan cache tampilan
+use crate::pdf::form::NeedAppearancesPolicy; // Kebijakan /NeedAppearances
+use crate::pdf::hooks::{NoHooks, SigningHooks}; // Hook di sekitar pipeline signing
+use crate::pdf::options::{AnnotationFlags, Placement, SignatureOptions, SignatureOptionsBuilder}; // Opsi penandatanganan
+use crate::pdf::mdp::{CertifyLevel, FieldLock}; // Certification (DocMDP) dan penguncian field (FieldMDP)
+use crate::pdf::pades::PadesLevel; // Level PAdES baseline
+use crate::pdf::sign::sign_document; // Pipeline penandatanganan
+use crate::scratch::ScratchSpace; // Penyimpanan data sementara
+use crate::storage::{DocumentSink, DocumentSource, ReaderSource, WriterSink}; // Sumber dan tujuan dokumen
+
+/// Sumber kunci privat
+enum KeySource {
+    Bytes(Vec<u8>),                            // Isi file kunci (raw, PKCS#8, SEC1, PKCS#1; PEM atau DER)
+    File(String),                              // Path file kunci
+    Pkcs12 { path: String, password: String }, // Bundle PKCS#12 berisi kunci dan rantai sertifikat
+    Pkcs11(Pkcs11Token),                       // Kunci dan sertifikat di token PKCS#11
+    Kms(KmsKey),                               // Kunci di KMS cloud, sertifikat dari KmsKey::certificate
+    Keystore(String),                          // Sertifikat di keystore OS (thumbprint atau CN), rantai dari store
+}
+
+/// Builder untuk menandatangani PDF dari kode Rust
+///
+/// Berbeda dengan CLI, `PdfSigner` tidak pernah bertanya di terminal: kunci
+/// terenkripsi butuh `passphrase`, bundle PKCS#12 butuh password eksplisit.
+///
+/// Contoh:
+/// ```ignore
+/// let signer = PdfSigner::new()
+///     .key(std::fs::read("private.key")?)
+///     .certificate(std::fs::read("certificate.der")?)
+///     .reason("Invoice approval");
+/// signer.sign(File::open("invoice.pdf")?, File::create("invoice_signed.pdf")?)?;
+/// ```
+#[derive(Default)]
+pub struct PdfSigner {
+    key: Option<KeySource>,
+    passphrase: Option<String>,
+    certificates: Vec<u8>,
+    options: SignatureOptionsBuilder,
+    cancel: CancellationToken,
+}
+
+impl PdfSigner {
+    /// Mulai membuat PdfSigner dengan opsi default
+    pub fn new() -> PdfSigner {
+        PdfSigner::default()
+    }
+
+    /// Kunci privat dari bytes (ECDSA raw/PKCS#8/SEC1, Ed25519 PKCS#8, atau RSA PKCS#1/PKCS#8, PEM atau DER)
+    pub fn key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = Some(KeySource::Bytes(key.into()));
+        self
+    }
+
+    /// Kunci privat dari file
+    pub fn key_file(mut self, path: impl Into<String>) -> Self {
+        self.key = Some(KeySource::File(path.into()));
+        self
+    }
+
+    /// Passphrase untuk kunci privat PKCS#8 terenkripsi
+    pub fn passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    /// Sertifikat DER yang disisipkan; boleh dipanggil berulang atau berisi beberapa
+    /// sertifikat berurutan (penandatangan dulu, lalu rantainya)
+    pub fn certificate(mut self, der: impl AsRef<[u8]>) -> Self {
+        self.certificates.extend_from_slice(der.as_ref());
+        self
+    }
+
+    /// Kunci privat dan rantai sertifikat dari bundle PKCS#12 (.p12/.pfx);
+    /// menggantikan `key` dan `certificate`
+    pub fn pkcs12_file(mut self, path: impl Into<String>, password: impl Into<String>) -> Self {
+        self.key = Some(KeySource::Pkcs12 { path: path.into(), password: password.into() });
+        self
+    }
+
+    /// Kunci dan rantai sertifikat di smart card / USB token / HSM lewat modul
+    /// PKCS#11; menggantikan `key` dan `certificate`. Tanpa PIN hanya bisa
+    /// dipakai untuk token dengan PIN pad (PIN tidak pernah ditanyakan di terminal)
+    pub fn pkcs11(mut self, token: Pkcs11Token) -> Self {
+        self.key = Some(KeySource::Pkcs11(token));
+        self
+    }
+
+    /// Kunci di AWS KMS, Google Cloud KMS, atau Azure Key Vault; menggantikan
+    /// `key`, dan sertifikatnya dibaca dari `KmsKey::certificate`. Butuh fitur
+    /// Cargo `aws-kms`, `gcp-kms`, atau `azure-kms`
+    pub fn kms(mut self, key: KmsKey) -> Self {
+        self.key = Some(KeySource::Kms(key));
+        self
+    }
+
+    /// Kunci di Windows certificate store atau macOS Keychain, dipilih dengan
+    /// thumbprint SHA-1 atau common name sertifikatnya; menggantikan `key` dan
+    /// `certificate` (rantai diambil dari store). Butuh fitur Cargo `keystore`
+    pub fn keystore_cert(mut self, selector: impl Into<String>) -> Self {
+        self.key = Some(KeySource::Keystore(selector.into()));
+        self
+    }
+
+    /// Nama penandatangan (default: "pdfsign-cli")
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.options = self.options.name(name);
+        self
+    }
+
+    /// Alasan penandatanganan (default: "Digitally signed")
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.options = self.options.reason(reason);
+        self
+    }
+
+    /// Lokasi penandatanganan
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.options = self.options.location(location);
+        self
+    }
+
+    /// Informasi kontak penandatangan
+    pub fn contact_info(mut self, contact_info: impl Into<String>) -> Self {
+        self.options = self.options.contact_info(contact_info);
+        self
+    }
+
+    /// Tampilkan tanda tangan pada halaman dan posisi tertentu
+    pub fn visible(mut self, placement: Placement) -> Self {
+        self.options = self.options.visible(placement);
+        self
+    }
+
+    /// Buat tanda tangan tanpa tampilan
+    pub fn invisible(mut self) -> Self {
+        self.options = self.options.invisible();
+        self
+    }
+
+    /// Isi signature field kosong yang sudah ada di dokumen
+    pub fn field_name(mut self, field_name: impl Into<String>) -> Self {
+        self.options = self.options.field_name(field_name);
+        self
+    }
+
+    /// Bahasa blok teks pada tampilan signature (satu atau dua)
+    pub fn appearance_languages(mut self, languages: Vec<AppearanceLanguage>) -> Self {
+        self.options = self.options.appearance_languages(languages);
+        self
+    }
+
+    /// Template teks tampilan, misalnya "Signed by {name}\n{date}"
+    pub fn appearance_template(mut self, template: impl Into<String>) -> Self {
+        self.options = self.options.appearance_template(template);
+        self
+    }
+
+    /// Ukuran font teks tampilan dalam point
+    pub fn appearance_font_size(mut self, size: f32) -> Self {
+        self.options = self.options.appearance_font_size(size);
+        self
+    }
+
+    /// Warna teks tampilan
+    pub fn appearance_color(mut self, color: TextColor) -> Self {
+        self.options = self.options.appearance_color(color);
+        self
+    }
+
+    /// Logo PNG atau JPEG di sebelah kiri teks tampilan
+    pub fn appearance_image(mut self, path: impl Into<String>) -> Self {
+        self.options = self.options.appearance_image(path);
+        self
+    }
+
+    /// Font TrueType (.ttf) yang disematkan untuk teks tampilan
+    pub fn appearance_font(mut self, path: impl Into<String>) -> Self {
+        self.options = self.options.appearance_font(path);
+        self
+    }
+
+    /// Cache tampilan yang dipakai bersama oleh beberapa PdfSigner, agar logo
+    /// dan layout disusun sekali untuk satu batch dokumen
+    pub fn appearance_cache(mut self, cache: AppearanceCache) -> Self {
+        self.options = self.options.appearance_cache(cache);
+        self
+    }
+
+    /// Kebijakan jika AcroForm memiliki /NeedAppearances true
+    pub fn need_appearances(mut self, policy: NeedAppearancesPolicy) -> Self {
+        self.options = self.options.need_appearances(policy);
+        self
+    }
+
+    /// Tampilkan signature sebagai annotation /Stamp dengan signature field
+    /// yang tidak terlihat
+    pub fn stamp_only(mut self, stamp_only: bool) -> Self {
+        self.options = self.options.stamp_only(stamp_only);
+        self
+    }
+
+    /// Flag annotation widget signature (default: print)
+    pub fn widget_flags(mut self, flags: AnnotationFlags) -> Self {
+        self.options = self.options.widget_flags(flags);
+        self
+    }
+
+    /// Flag annotation stamp --stamp-only (default: print, locked)
+    pub fn stamp_flags(mut self, flags: AnnotationFlags) -> Self {
+        self.options = self.options.stamp_flags(flags);
+        self
+    }
+
+    /// Tolak form dengan perubahan tertunda alih-alih memberi peringatan
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options = self.options.strict(strict);
+        self
+    }
+
+    /// Password dokumen PDF terenkripsi (user atau owner)
+    pub fn pdf_password(mut self, password: impl Into<String>) -> Self {
+        self.options = self.options.pdf_password(password);
+        self
+    }
+
+    /// Hapus data XFA dari form hybrid sebelum ditandatangani
+    pub fn drop_xfa(mut self, drop_xfa: bool) -> Self {
+        self.options = self.options.drop_xfa(drop_xfa);
+        self
+    }
+
+    /// Tolak dokumen yang melanggar spesifikasi PDF alih-alih memperbaikinya
+    pub fn strict_parse(mut self, strict_parse: bool) -> Self {
+        self.options = self.options.strict_parse(strict_parse);
+        self
+    }
+
+    /// Bangun ulang dokumen yang cross-reference-nya rusak sebelum ditandatangani
+    pub fn repair(mut self, repair: bool) -> Self {
+        self.options = self.options.repair(repair);
+        self
+    }
+
+    /// Command eksternal yang boleh menolak dokumen atau mengubah alasan,
+    /// lokasi, dan kontak sebelum ditandatangani
+    pub fn classify_hook(mut self, command: impl Into<String>) -> Self {
+        self.options = self.options.classify_hook(command);
+        self
+    }
+
+    /// Wajibkan dokumen PDF/A dan tolak jika signature akan merusak kesesuaiannya
+    pub fn require_pdfa(mut self, require_pdfa: bool) -> Self {
+        self.options = self.options.require_pdfa(require_pdfa);
+        self
+    }
+
+    /// Skema padding jika kunci privat RSA
+    pub fn rsa_padding(mut self, padding: RsaPadding) -> Self {
+        self.options = self.options.rsa_padding(padding);
+        self
+    }
+
+    /// Algoritma digest signature (default: SHA-256)
+    pub fn digest(mut self, digest: DigestAlgorithm) -> Self {
+        self.options = self.options.digest(digest);
+        self
+    }
+
+    /// Sertifikat yang disisipkan di signature (default: seluruh rantai)
+    pub fn embed_chain(mut self, embed_chain: EmbedChain) -> Self {
+        self.options = self.options.embed_chain(embed_chain);
+        self
+    }
+
+    /// Kebijakan algoritma lemah (default: tolak MD5, SHA-1, RSA < 2048, P-192)
+    pub fn algorithm_policy(mut self, policy: AlgorithmPolicy) -> Self {
+        self.options = self.options.algorithm_policy(policy);
+        self
+    }
+
+    /// Timestamp signature dari TSA (RFC 3161)
+    pub fn timestamp(mut self, tsa: Tsa) -> Self {
+        self.options = self.options.timestamp(tsa);
+        self
+    }
+
+    /// Buat signature PAdES baseline pada level tertentu
+    pub fn pades(mut self, level: PadesLevel) -> Self {
+        self.options = self.options.pades(level);
+        self
+    }
+
+    /// Buat certification signature (DocMDP) dengan tingkat izin tertentu
+    pub fn certify(mut self, level: CertifyLevel) -> Self {
+        self.options = self.options.certify(level);
+        self
+    }
+
+    /// Kunci form field setelah ditandatangani (FieldMDP)
+    pub fn lock_fields(mut self, lock: FieldLock) -> Self {
+        self.options = self.options.lock_fields(lock);
+        self
+    }
+
+    /// Tempat menyimpan hasil serialisasi sementara (default: memori)
+    pub fn scratch(mut self, scratch: ScratchSpace) -> Self {
+        self.options = self.options.scratch(scratch);
+        self
+    }
+
+    /// Masukkan object baru ke object stream terkompresi (output lebih kecil)
+    pub fn object_streams(mut self, object_streams: bool) -> Self {
+        self.options = self.options.object_streams(object_streams);
+        self
+    }
+
+    /// Tandatangani juga setiap file lampiran (signature .p7s terpisah)
+    pub fn sign_attachments(mut self, sign_attachments: bool) -> Self {
+        self.options = self.options.sign_attachments(sign_attachments);
+        self
+    }
+
+    /// Ukuran ruang /Contents signature (default: diperkirakan otomatis)
+    pub fn placeholder_size(mut self, size: usize) -> Self {
+        self.options = self.options.placeholder_size(size);
+        self
+    }
+
+    /// Waktu penandatanganan tetap (default: waktu lokal saat signing)
+    pub fn signing_time(mut self, time: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        self.options = self.options.signing_time(time);
+        self
+    }
+
+    /// Token pembatalan (batas waktu atau pembatalan dari thread lain)
+    pub fn cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Tandatangani PDF dari `input` dan tulis hasilnya ke `output`
+    ///
+    /// Output baru ditulis setelah seluruh pipeline berhasil, sehingga error
+    /// tidak meninggalkan data setengah jadi di `output`.
+    pub fn sign(self, input: impl Read, output: impl Write) -> Result<(), Error> {
+        self.sign_with_hooks(input, output, &mut NoHooks)
+    }
+
+    /// Sama seperti `sign`, tetapi memanggil hook pada setiap tahap pipeline
+    /// (`on_written` tidak dipanggil karena output bukan file)
+    pub fn sign_with_hooks(self, input: impl Read, output: impl Write, hooks: &mut dyn SigningHooks) -> Result<(), Error> {
+        self.sign_to_with_hooks(&mut ReaderSource::new(input), &mut WriterSink::new(output), hooks)
+    }
+
+    /// Tandatangani PDF dari `source` dan simpan hasilnya ke `sink`
+    ///
+    /// Dipakai untuk storage sendiri (database, content store, HTTP/S3) tanpa
+    /// file sementara; lihat modul `storage`. `sink` baru dipanggil setelah
+    /// seluruh pipeline berhasil.
+    pub fn sign_to(self, source: &mut dyn DocumentSource, sink: &mut dyn DocumentSink) -> Result<(), Error> {
+        self.sign_to_with_hooks(source, sink, &mut NoHooks)
+    }
+
+    /// Sama seperti `sign_to`, tetapi memanggil hook pada setiap tahap pipeline
+    /// (`on_written` hanya dipanggil jika sink punya lokasi)
+    pub fn sign_to_with_hooks(
+        self,
+        source: &mut dyn DocumentSource,
+        sink: &mut dyn DocumentSink,
+        hooks: &mut dyn SigningHooks,
+    ) -> Result<(), Error> {
+        let options = self
+            .options
+            .build()
+            .map_err(|err| Error::from_anyhow(err, Error::InvalidOptions))?;
+        let (signer, certificates) = load_credentials(self.key, self.passphrase.as_deref(), &self.certificates, &options)?;
+
+        let storage_error = |err| Error::from_anyhow(err, Error::Signing);
+        let pdf_bytes = source
+            .load(&self.cancel)
+            .map_err(|err| storage_error(err.context(format!("cannot read {}", source.describe()))))?;
+        let mut scratch = sign_document(pdf_bytes, signer.as_ref(), &certificates, &options, hooks, &self.cancel)
+            .map_err(|err| Error::from_anyhow(err, Error::Signing))?;
+
+        scratch.seek(SeekFrom::Start(0))?;
+        sink.store(&mut scratch, &self.cancel).map_err(storage_error)?;
+        if let Some(location) = sink.location() {
+            hooks.on_written(&location).map_err(|err| Error::from_anyhow(err, Error::Signing))?;
+        }
+        Ok(())
+    }
+}
+
+/// Muat kunci privat dan sertifikat tanpa pernah bertanya di terminal
+fn load_credentials(
+    key: Option<KeySource>,
+    passphrase: Option<&str>,
+    certificates: &[u8],
+    options: &SignatureOptions,
+) -> Result<Credentials, Error> {
+    let key_error = |err| Error::from_anyhow(err, Error::Key);
+    let bytes = match key {
+        None => return Err(Error::Key("no private key given (use key, key_file, pkcs12_file, pkcs11, kms, or keystore_cert)".to_string())),
+        Some(KeySource::Pkcs11(token)) => {
+            return load_pkcs11(&token, options.rsa_padding, false).map_err(key_error);
+        }
+        Some(KeySource::Kms(key)) => {
+            return load_kms(&key, options.rsa_padding).map_err(key_error);
+        }
+        Some(KeySource::Keystore(selector)) => {
+            return load_keystore(&selector, options.rsa_padding).map_err(key_error);
+        }
+        Some(KeySource::Pkcs12 { path, password }) => {
+            let bundle = load_pkcs12(&path, Some(&password), options.rsa_padding)
+                .map_err(|err| Error::from_anyhow(err, Error::Certificate))?;
+            return Ok((bundle.signer, bundle.certificates));
+        }
+        Some(KeySource::File(path)) => std::fs::read(&path)?,
+        Some(KeySource::Bytes(bytes)) => bytes,
+    };
+
+    let bytes = if keyfile::is_encrypted(&bytes) {
+        let passphrase = passphrase.ok_or_else(|| Error::Key("private key is encrypted; set a passphrase".to_string()))?;
+        keyfile::decrypt_private_key(&bytes, Some(passphrase), "private key").map_err(key_error)?
+    } else {
+        bytes
+    };
+    let signer = signer_from_bytes(&bytes, options.rsa_padding).map_err(key_error)?;
+    let certificates = if certificates.is_empty() {
+        Vec::new()
+    } else {
+        split_certificates(certificates).map_err(|err| Error::from_anyhow(err, Error::Certificate))?
+    };
+    Ok((signer, certificates))
+}
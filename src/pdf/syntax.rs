@@ -0,0 +1,655 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Error handling
+use lopdf::xref::XrefEntry; // Entri cross-reference
+use lopdf::{Document, Object, ObjectId, ObjectStream}; // Struktur PDF
+use std::collections::BTreeMap; // Hitungan definisi per revisi
+use std::fmt; // Format pesan pelanggaran
+use std::io::Write; // Menyusun PDF kecil untuk parsing ulang
+
+/// Cara menangani dokumen yang melanggar spesifikasi PDF saat dibaca
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Perbaiki yang bisa diperbaiki dan tampilkan peringatan (default):
+    /// dokumen dari producer yang kurang rapi tetap bisa diproses
+    #[default]
+    Lenient,
+    /// Tolak dokumen (--strict-parse): yang diproses hanya dokumen yang
+    /// strukturnya dibaca sama oleh semua viewer
+    Strict,
+}
+
+/// Pelanggaran spesifikasi yang ditemukan di struktur file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyntaxIssue {
+    /// Nomor object yang sama didefinisikan lebih dari sekali dalam satu revisi;
+    /// viewer bisa memilih definisi yang berbeda
+    DuplicateObject { number: u32, revision: usize, count: usize },
+    /// /Length stream (None jika tidak ada atau tidak valid) tidak sama dengan
+    /// panjang data sampai "endstream"
+    StreamLength { id: ObjectId, declared: Option<i64>, actual: usize },
+}
+
+impl fmt::Display for SyntaxIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyntaxIssue::DuplicateObject { number, revision, count } => {
+                write!(f, "object {} is defined {} times in revision {}", number, count, revision)
+            }
+            SyntaxIssue::StreamLength { id, declared: Some(declared), actual } => {
+                write!(f, "object {} {}: stream /Length is {} but the data is {} bytes", id.0, id.1, declared, actual)
+            }
+            SyntaxIssue::StreamLength { id, declared: None, actual } => {
+                write!(f, "object {} {}: stream /Length is missing or invalid (the data is {} bytes)", id.0, id.1, actual)
+            }
+        }
+    }
+}
+
+impl SyntaxIssue {
+    /// Cara mode lenient menangani pelanggaran ini (untuk pesan peringatan)
+    pub fn repair(&self) -> &'static str {
+        match self {
+            SyntaxIssue::DuplicateObject { .. } => "using the definition the cross-reference table points to",
+            SyntaxIssue::StreamLength { .. } => "using the data up to endstream",
+        }
+    }
+}
+
+/// Definisi "N G obj" yang ditemukan di file
+struct Definition {
+    id: ObjectId,                 // Nomor dan generation di header
+    offset: usize,                // Posisi header
+    revision: usize,              // Revisi (jumlah %%EOF sebelum header)
+    stream: Option<StreamData>,   // Posisi data jika object berupa stream
+}
+
+/// Posisi bagian-bagian stream di file
+struct StreamData {
+    dict: (usize, usize),           // Dictionary stream (setelah "obj" sampai kata kunci "stream")
+    length: Option<(usize, usize)>, // Nilai /Length di dalam dictionary
+    declared: Option<i64>,          // Nilai /Length (referensi sudah di-resolve)
+    data: (usize, usize),           // Data stream yang sebenarnya (sampai sebelum EOL "endstream")
+}
+
+impl StreamData {
+    /// Apakah /Length menunjuk tepat ke "endstream" (aturan yang sama dengan parser lopdf)
+    fn length_valid(&self, bytes: &[u8]) -> bool {
+        let Some(end) = self.declared.and_then(|len| usize::try_from(len).ok()).and_then(|len| self.data.0.checked_add(len)) else {
+            return false;
+        };
+        let Some(rest) = bytes.get(end..) else {
+            return false;
+        };
+        let rest = rest.strip_prefix(b"\r\n").or_else(|| rest.strip_prefix(b"\n")).or_else(|| rest.strip_prefix(b"\r")).unwrap_or(rest);
+        rest.starts_with(b"endstream")
+    }
+}
+
+/// Periksa struktur file terhadap pelanggaran yang membuat viewer membaca
+/// dokumen secara berbeda: nomor object ganda dalam satu revisi dan /Length
+/// stream yang salah
+///
+/// lopdf membaca stream yang /Length-nya salah sebagai dictionary biasa tanpa
+/// isi (atau membuangnya) tanpa pesan, jadi dalam mode lenient object itu
+/// dimuat ulang dari data sampai "endstream"; untuk
+/// nomor ganda, yang dipakai tetap definisi yang ditunjuk cross-reference.
+/// Pelanggaran tidak ditampilkan di sini (lihat `warn`).
+///
+/// Parameter:
+///   - bytes: isi file PDF apa adanya
+///   - doc: dokumen hasil parsing `bytes`; object yang diperbaiki ditambahkan ke sini
+///   - mode: Strict menolak dokumen yang punya pelanggaran
+///
+/// Return: daftar pelanggaran yang ditemukan (dan diperbaiki jika bisa)
+pub fn check_document(bytes: &[u8], doc: &mut Document, mode: ParseMode) -> Result<Vec<SyntaxIssue>> {
+    let definitions = scan_definitions(bytes, doc);
+
+    // Nomor object ganda dalam satu revisi
+    let mut counts: BTreeMap<(usize, u32), usize> = BTreeMap::new();
+    for definition in &definitions {
+        *counts.entry((definition.revision, definition.id.0)).or_default() += 1;
+    }
+    let mut issues: Vec<SyntaxIssue> = counts
+        .into_iter()
+        .filter(|&(_, count)| count > 1)
+        .map(|((revision, number), count)| SyntaxIssue::DuplicateObject { number, revision: revision + 1, count })
+        .collect();
+
+    // /Length stream yang salah
+    let broken: Vec<&Definition> = definitions
+        .iter()
+        .filter(|definition| definition.stream.as_ref().is_some_and(|stream| !stream.length_valid(bytes)))
+        .collect();
+    issues.extend(broken.iter().filter_map(|definition| {
+        let stream = definition.stream.as_ref()?;
+        Some(SyntaxIssue::StreamLength { id: definition.id, declared: stream.declared, actual: stream.data.1 - stream.data.0 })
+    }));
+
+    if issues.is_empty() {
+        return Ok(issues);
+    }
+    if mode == ParseMode::Strict {
+        reject(&issues)?;
+    }
+
+    for definition in broken {
+        // Hanya definisi yang dipakai revisi terakhir yang dimuat ulang
+        if !is_current(bytes, doc, definition) {
+            continue;
+        }
+        let stream = definition.stream.as_ref().expect("broken definitions are streams");
+        let object = reparse_stream(bytes, definition.id, stream)
+            .with_context(|| format!("cannot repair object {} {}", definition.id.0, definition.id.1))?;
+        insert_repaired(doc, definition.id, object);
+    }
+    Ok(issues)
+}
+
+/// Tolak dokumen yang punya pelanggaran (--strict-parse)
+///
+/// Return: Ok jika `issues` kosong, selain itu error berisi daftar pelanggaran
+pub fn reject(issues: &[SyntaxIssue]) -> Result<()> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let list: Vec<String> = issues.iter().map(|issue| format!("  - {}", issue)).collect();
+    bail!("document violates the PDF specification (--strict-parse):\n{}", list.join("\n"))
+}
+
+/// Tampilkan pelanggaran yang sudah diperbaiki `check_document` sebagai peringatan
+pub fn warn(issues: &[SyntaxIssue]) {
+    for issue in issues {
+        crate::warning!("{}; {}", issue, issue.repair());
+    }
+}
+
+/// Bangun ulang dokumen yang tidak bisa dibaca lewat cross-reference-nya
+/// (--repair), misalnya xref yang offset-nya salah atau terpotong dari scanner
+///
+/// Cross-reference baru disusun dari header "N G obj" yang ditemukan di file;
+/// untuk nomor yang didefinisikan lebih dari sekali, definisi terakhir (revisi
+/// terbaru) yang dipakai. Object di object stream dimuat dari stream-nya, dan
+/// stream dengan /Length yang salah diperbaiki seperti pada mode lenient.
+/// /Root diambil dari trailer lama jika masih menunjuk ke catalog, selain itu
+/// dari object /Type /Catalog dengan nomor tertinggi.
+///
+/// Parameter:
+///   - bytes: isi file PDF apa adanya
+///
+/// Return: dokumen lengkap (termasuk isi stream) tanpa riwayat revisi, siap
+/// ditulis ulang seluruhnya
+pub fn rebuild(bytes: &[u8]) -> Result<Document> {
+    // Tanpa kunci dekripsi yang ditunjuk trailer, isi string dan stream tidak terbaca
+    if trailer_reference(bytes, b"/Encrypt").is_some() {
+        bail!("encrypted documents cannot be repaired");
+    }
+
+    // Tanpa cross-reference, /Length tidak langsung belum bisa di-resolve;
+    // stream seperti itu diakhiri di "endstream"
+    let definitions = scan_definitions(bytes, &Document::new());
+    let mut offsets: BTreeMap<u32, (u16, usize)> = BTreeMap::new();
+    for definition in &definitions {
+        offsets.insert(definition.id.0, (definition.id.1, definition.offset));
+    }
+    let Some(&highest) = offsets.keys().next_back() else {
+        bail!("no PDF objects found; the file cannot be repaired");
+    };
+
+    // Cross-reference baru di akhir salinan file, tanpa /Prev ke xref lama
+    let mut repaired = bytes.to_vec();
+    repaired.push(b'\n');
+    let xref = repaired.len();
+    write!(repaired, "xref\n0 {}\n", highest + 1)?;
+    for number in 0..=highest {
+        match offsets.get(&number) {
+            Some((generation, offset)) => write!(repaired, "{:010} {:05} n\r\n", offset, generation)?,
+            None => write!(repaired, "0000000000 65535 f\r\n")?,
+        }
+    }
+    write!(repaired, "trailer\n<< /Size {} >>\nstartxref\n{}\n%%EOF\n", highest + 1, xref)?;
+    let mut doc = Document::load_mem(&repaired).context("cannot parse the rebuilt cross-reference table")?;
+
+    // Stream dengan /Length salah dibaca ulang sebelum object stream dibuka
+    warn(&check_document(&repaired, &mut doc, ParseMode::Lenient)?);
+
+    // Object di object stream tidak tercantum di cross-reference baru
+    let containers: Vec<ObjectId> = doc
+        .objects
+        .iter()
+        .filter(|(_, object)| object.type_name().ok() == Some("ObjStm"))
+        .map(|(&id, _)| id)
+        .collect();
+    for id in containers {
+        let Ok(Object::Stream(mut stream)) = doc.get_object(id).cloned() else {
+            continue;
+        };
+        match ObjectStream::new(&mut stream) {
+            Ok(objects) => {
+                for (inner, value) in objects.objects {
+                    doc.objects.entry(inner).or_insert(value);
+                }
+            }
+            Err(err) => crate::warning!("object stream {} {} cannot be read: {}", id.0, id.1, err),
+        }
+    }
+
+    // Cross-reference stream lama tidak lagi berarti setelah ditulis ulang
+    doc.objects.retain(|_, object| object.type_name().ok() != Some("XRef"));
+
+    // /Root dan /Info dari trailer lama (yang terakhir), jika masih valid
+    let is_catalog = |doc: &Document, id: ObjectId| doc.get_dictionary(id).is_ok_and(|dict| dict.type_name().ok() == Some("Catalog"));
+    let root = trailer_reference(bytes, b"/Root")
+        .filter(|&id| is_catalog(&doc, id))
+        .or_else(|| doc.objects.keys().copied().rev().find(|&id| is_catalog(&doc, id)))
+        .ok_or_else(|| anyhow!("no document catalog found; the file cannot be repaired"))?;
+    doc.trailer.set("Root", Object::Reference(root));
+    if let Some(info) = trailer_reference(bytes, b"/Info").filter(|&id| doc.get_dictionary(id).is_ok()) {
+        doc.trailer.set("Info", Object::Reference(info));
+    }
+    doc.max_id = doc.objects.keys().map(|&(id, _)| id).max().unwrap_or(0);
+    Ok(doc)
+}
+
+/// Referensi "N G R" terakhir setelah `key` (misalnya /Root) di trailer atau
+/// dictionary cross-reference stream
+fn trailer_reference(bytes: &[u8], key: &[u8]) -> Option<ObjectId> {
+    let mut found = None;
+    let mut from = 0;
+    while let Some(at) = find(bytes, from, key) {
+        from = at + key.len();
+        if bytes.get(from).is_some_and(|&byte| !is_delimiter(byte)) {
+            continue;
+        }
+        let mut tokens = bytes[from..].split(|&byte| is_delimiter(byte)).filter(|token| !token.is_empty());
+        let mut number = || std::str::from_utf8(tokens.next()?).ok()?.parse::<u64>().ok();
+        let (Some(number), Some(generation)) = (number(), number()) else {
+            continue;
+        };
+        if tokens.next() == Some(b"R".as_slice()) {
+            if let (Ok(number), Ok(generation)) = (u32::try_from(number), u16::try_from(generation)) {
+                found = Some((number, generation));
+            }
+        }
+    }
+    found
+}
+
+/// Akhir setiap revisi file (file asli, lalu setiap incremental update), urut
+/// dari yang paling lama
+///
+/// Revisi berakhir di penanda `%%EOF` yang didahului `startxref <offset>`
+/// (penanda di dalam isi stream tidak dihitung). Setiap entri berisi posisi
+/// tepat setelah `%%EOF` dan posisi setelah baris baru yang mengikutinya (sama
+/// jika tidak ada baris baru); penulis PDF berbeda dalam hal mana yang dicakup
+/// ByteRange signature. `%%EOF` bagian halaman pertama di file linearized
+/// bukan akhir revisi.
+pub fn revision_ends(bytes: &[u8]) -> Vec<(usize, usize)> {
+    let mut ends = Vec::new();
+    let mut from = 0;
+    while let Some(at) = find(bytes, from, b"%%EOF") {
+        from = at + 5;
+        // Mundur: whitespace, offset, whitespace, lalu "startxref"
+        let before = bytes[..at].trim_ascii_end();
+        let digits = before.iter().rev().take_while(|byte| byte.is_ascii_digit()).count();
+        let keyword = before[..before.len() - digits].trim_ascii_end();
+        if digits == 0 || keyword.len() == before.len() - digits || !keyword.ends_with(b"startxref") {
+            continue;
+        }
+        let eol = match &bytes[from..] {
+            [b'\r', b'\n', ..] => 2,
+            [b'\r' | b'\n', ..] => 1,
+            _ => 0,
+        };
+        ends.push((from, from + eol));
+    }
+    // File linearized: dictionary /Linearized di object pertama, dan xref bagian
+    // halaman pertama punya penanda %%EOF sendiri sebelum isi dokumen lainnya
+    let head = &bytes[..bytes.len().min(1024)];
+    if ends.len() > 1 && find(head, 0, b"/Linearized").is_some() {
+        ends.remove(0);
+    }
+    ends
+}
+
+/// Cari semua header "N G obj" di file, melompati isi stream
+fn scan_definitions(bytes: &[u8], doc: &Document) -> Vec<Definition> {
+    let mut definitions = Vec::new();
+    let mut revision = 0;
+    let mut pos = 0;
+    while let Some(found) = find(bytes, pos, b"obj") {
+        revision += count(&bytes[pos..found], b"%%EOF");
+        pos = found + 3;
+        let Some((id, offset)) = object_header(bytes, found) else {
+            continue;
+        };
+        let stream = stream_data(bytes, found + 3, doc);
+        if let Some(stream) = &stream {
+            // Lanjutkan setelah "endstream": isi stream bisa berisi apa saja
+            pos = stream.data.1;
+        }
+        definitions.push(Definition { id, offset, revision, stream });
+    }
+    definitions
+}
+
+/// Nomor object dan posisi header jika "obj" di `at` adalah akhir header "N G obj"
+fn object_header(bytes: &[u8], at: usize) -> Option<(ObjectId, usize)> {
+    if bytes.get(at + 3).is_some_and(|&byte| !is_delimiter(byte)) {
+        return None;
+    }
+    let mut start = at;
+    let skip_spaces = |start: &mut usize, required: bool| {
+        let end = *start;
+        while *start > 0 && bytes[*start - 1].is_ascii_whitespace() {
+            *start -= 1;
+        }
+        !required || *start < end
+    };
+    let digits = |start: &mut usize| {
+        let end = *start;
+        while *start > 0 && bytes[*start - 1].is_ascii_digit() {
+            *start -= 1;
+        }
+        std::str::from_utf8(&bytes[*start..end]).ok()?.parse::<u64>().ok()
+    };
+    if !skip_spaces(&mut start, true) {
+        return None;
+    }
+    let generation = u16::try_from(digits(&mut start)?).ok()?;
+    if !skip_spaces(&mut start, true) {
+        return None;
+    }
+    let number = u32::try_from(digits(&mut start)?).ok()?;
+    // Header harus berdiri sendiri (awal baris atau setelah spasi)
+    if start > 0 && !is_delimiter(bytes[start - 1]) {
+        return None;
+    }
+    Some(((number, generation), start))
+}
+
+/// Posisi data stream jika object yang dimulai di `from` adalah stream
+fn stream_data(bytes: &[u8], from: usize, doc: &Document) -> Option<StreamData> {
+    // Kata kunci pertama setelah header: "stream" atau "endobj"
+    let keyword = next_keyword(bytes, from)?;
+    if !bytes[keyword..].starts_with(b"stream") {
+        return None;
+    }
+    let dict = (from, keyword);
+    let mut start = keyword + 6;
+    if bytes[start..].starts_with(b"\r\n") {
+        start += 2;
+    } else if bytes[start..].starts_with(b"\n") || bytes[start..].starts_with(b"\r") {
+        start += 1;
+    }
+    let (length, declared) = match stream_length(bytes, dict, doc) {
+        Some((span, value)) => (Some(span), value),
+        None => (None, None),
+    };
+    let mut stream = StreamData { dict, length, declared, data: (start, start) };
+    if stream.length_valid(bytes) {
+        // length_valid memastikan /Length tidak negatif dan masih di dalam file
+        stream.data.1 = start + declared.unwrap_or_default() as usize;
+        return Some(stream);
+    }
+    // /Length salah: data berakhir sebelum EOL di depan "endstream" (atau
+    // "endobj" jika "endstream" hilang, agar object berikutnya tidak ikut)
+    let mut end = match (find(bytes, start, b"endstream"), find(bytes, start, b"endobj")) {
+        (Some(endstream), Some(endobj)) => endstream.min(endobj),
+        (endstream, endobj) => endstream.or(endobj)?,
+    };
+    if bytes[start..end].ends_with(b"\r\n") {
+        end -= 2;
+    } else if bytes[start..end].ends_with(b"\n") || bytes[start..end].ends_with(b"\r") {
+        end -= 1;
+    }
+    stream.data.1 = end;
+    Some(stream)
+}
+
+/// Posisi kata kunci "stream" atau "endobj" pertama mulai dari `from`
+///
+/// Isi string (literal dan hex) dan komentar dilewati, sehingga teks seperti
+/// /Reason (xref-stream) tidak dianggap awal data stream.
+fn next_keyword(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => {
+                // String literal: kurung boleh bersarang, backslash meng-escape satu byte
+                let mut depth = 0;
+                while i < bytes.len() {
+                    match bytes[i] {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            // "<<" membuka dictionary; "<" saja membuka string hex
+            b'<' if bytes.get(i + 1) == Some(&b'<') => i += 2,
+            b'<' => i = find(bytes, i, b">")? + 1,
+            b'%' => i = (i..bytes.len()).find(|&j| bytes[j] == b'\n' || bytes[j] == b'\r').unwrap_or(bytes.len()),
+            _ if (bytes[i..].starts_with(b"stream") || bytes[i..].starts_with(b"endobj")) && (i == from || is_delimiter(bytes[i - 1])) => {
+                return Some(i);
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Posisi dan nilai /Length di dictionary stream (referensi di-resolve lewat `doc`)
+fn stream_length(bytes: &[u8], dict: (usize, usize), doc: &Document) -> Option<((usize, usize), Option<i64>)> {
+    let mut at = dict.0;
+    let key = loop {
+        let found = find(&bytes[..dict.1], at, b"/Length")?;
+        at = found + 7;
+        // /Length1, /Length2, dan /Length3 milik font file adalah key lain
+        if bytes.get(at).is_some_and(|&byte| is_delimiter(byte)) {
+            break at;
+        }
+    };
+    let token = |from: usize| {
+        let start = (from..dict.1).find(|&i| !bytes[i].is_ascii_whitespace())?;
+        let end = (start..dict.1).find(|&i| is_delimiter(bytes[i])).unwrap_or(dict.1);
+        Some((start, end, &bytes[start..end]))
+    };
+    let parse = |value: &[u8]| std::str::from_utf8(value).ok()?.parse::<i64>().ok();
+    let (start, end, value) = token(key)?;
+    // Referensi tidak langsung: "N G R"
+    if let Some((_, gen_end, generation)) = token(end) {
+        if let Some((_, r_end, b"R")) = token(gen_end) {
+            let reference = (u32::try_from(parse(value)?).ok()?, u16::try_from(parse(generation)?).ok()?);
+            let declared = doc.get_object(reference).and_then(Object::as_i64).ok();
+            return Some(((start, r_end), declared));
+        }
+    }
+    Some(((start, end), parse(value)))
+}
+
+/// Apakah `definition` adalah definisi yang ditunjuk cross-reference revisi terakhir
+fn is_current(bytes: &[u8], doc: &Document, definition: &Definition) -> bool {
+    match doc.reference_table.get(definition.id.0) {
+        Some(&XrefEntry::Normal { offset, generation }) => {
+            let offset = offset as usize;
+            // Offset di xref boleh menunjuk spasi sebelum header
+            generation == definition.id.1
+                && offset <= definition.offset
+                && bytes[offset..definition.offset].iter().all(u8::is_ascii_whitespace)
+        }
+        _ => false,
+    }
+}
+
+/// Parse ulang stream dengan /Length yang sudah dibetulkan
+///
+/// Parser lopdf tidak publik, jadi object disalin ke PDF kecil yang hanya
+/// berisi object itu; dictionary-nya dibaca oleh parser yang sama dengan dokumen.
+fn reparse_stream(bytes: &[u8], id: ObjectId, stream: &StreamData) -> Result<Object> {
+    let data = &bytes[stream.data.0..stream.data.1];
+    let mut dict = bytes[stream.dict.0..stream.dict.1].to_vec();
+    let length = data.len().to_string().into_bytes();
+    match stream.length {
+        Some((start, end)) => {
+            dict.splice(start - stream.dict.0..end - stream.dict.0, length);
+        }
+        None => {
+            let open = find(&dict, 0, b"<<").ok_or_else(|| anyhow!("stream dictionary not found"))? + 2;
+            dict.splice(open..open, [b"/Length ".as_slice(), &length, b" "].concat());
+        }
+    }
+
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    let offset = pdf.len();
+    write!(pdf, "{} {} obj", id.0, id.1)?;
+    pdf.extend_from_slice(&dict);
+    pdf.extend_from_slice(b"stream\n");
+    pdf.extend_from_slice(data);
+    pdf.extend_from_slice(b"\nendstream\nendobj\n");
+    let xref = pdf.len();
+    write!(
+        pdf,
+        "xref\n0 1\n0000000000 65535 f \n{} 1\n{:010} {:05} n \ntrailer\n<< /Size {} >>\nstartxref\n{}\n%%EOF\n",
+        id.0,
+        offset,
+        id.1,
+        id.0 + 1,
+        xref
+    )?;
+    let object = Document::load_mem(&pdf)?.get_object(id)?.clone();
+    Ok(object)
+}
+
+/// Ganti object di dokumen dengan hasil perbaikan; isi object stream ikut
+/// dimuat karena lopdf tidak bisa membacanya dari stream yang rusak
+fn insert_repaired(doc: &mut Document, id: ObjectId, mut object: Object) {
+    if let Object::Stream(stream) = &mut object {
+        if stream.dict.get(b"Type").and_then(Object::as_name).ok() == Some(b"ObjStm") {
+            match ObjectStream::new(stream) {
+                Ok(objects) => {
+                    for (inner, value) in objects.objects {
+                        // Hanya object yang cross-reference-nya menunjuk ke stream ini
+                        let contained = matches!(
+                            doc.reference_table.get(inner.0),
+                            Some(&XrefEntry::Compressed { container, .. }) if container == id.0
+                        );
+                        if contained {
+                            doc.objects.entry(inner).or_insert(value);
+                        }
+                    }
+                }
+                Err(err) => crate::warning!("object stream {} {} cannot be read: {}", id.0, id.1, err),
+            }
+        }
+    }
+    doc.objects.insert(id, object);
+}
+
+/// Apakah byte adalah whitespace atau delimiter PDF (akhir sebuah token)
+fn is_delimiter(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || b"()<>[]{}/%".contains(&byte)
+}
+
+/// Posisi pertama `needle` di `bytes` mulai dari `from`
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes.get(from..)?.windows(needle.len()).position(|window| window == needle).map(|at| from + at)
+}
+
+/// Jumlah kemunculan `needle` di `bytes`
+fn count(bytes: &[u8], needle: &[u8]) -> usize {
+    bytes.windows(needle.len()).filter(|window| *window == needle).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// PDF kecil dengan xref klasik; object ke-i (mulai 1) adalah `objects[i - 1]`
+    /// lengkap dengan header, `extra` ditulis setelahnya tanpa entri xref
+    fn pdf(objects: &[&str], extra: &str) -> Vec<u8> {
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+        let mut offsets = Vec::new();
+        for object in objects {
+            offsets.push(bytes.len());
+            bytes.extend_from_slice(object.as_bytes());
+            bytes.push(b'\n');
+        }
+        bytes.extend_from_slice(extra.as_bytes());
+        let start = bytes.len();
+        bytes.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            bytes.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        bytes.extend_from_slice(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, start).as_bytes());
+        bytes
+    }
+
+    const CATALOG: &str = "1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj";
+    const PAGES: &str = "2 0 obj << /Type /Pages /Kids [] /Count 0 >> endobj";
+
+    fn check(bytes: &[u8], mode: ParseMode) -> (Result<Vec<SyntaxIssue>>, Document) {
+        let mut doc = Document::load_mem(bytes).unwrap();
+        let issues = check_document(bytes, &mut doc, mode);
+        (issues, doc)
+    }
+
+    fn content(doc: &Document, id: ObjectId) -> Vec<u8> {
+        doc.get_object(id).unwrap().as_stream().unwrap().content.clone()
+    }
+
+    #[test]
+    fn wrong_length_is_repaired() {
+        let bytes = pdf(&[CATALOG, PAGES, "3 0 obj << /Length 3 >>\nstream\nhello world\nendstream\nendobj"], "");
+        let (issues, doc) = check(&bytes, ParseMode::Lenient);
+        assert_eq!(issues.unwrap(), vec![SyntaxIssue::StreamLength { id: (3, 0), declared: Some(3), actual: 11 }]);
+        assert_eq!(content(&doc, (3, 0)), b"hello world");
+    }
+
+    #[test]
+    fn missing_length_is_added() {
+        let bytes = pdf(&[CATALOG, PAGES, "3 0 obj << /Filter /ASCIIHexDecode >>\r\nstream\r\n68656c6c6f>\r\nendstream\r\nendobj"], "");
+        let (issues, doc) = check(&bytes, ParseMode::Lenient);
+        assert_eq!(issues.unwrap(), vec![SyntaxIssue::StreamLength { id: (3, 0), declared: None, actual: 11 }]);
+        assert_eq!(content(&doc, (3, 0)), b"68656c6c6f>");
+    }
+
+    #[test]
+    fn strict_mode_rejects_instead_of_repairing() {
+        let bytes = pdf(&[CATALOG, PAGES, "3 0 obj << /Length 99 >>\nstream\nhello\nendstream\nendobj"], "");
+        let (issues, _) = check(&bytes, ParseMode::Strict);
+        let message = issues.unwrap_err().to_string();
+        assert!(message.contains("--strict-parse"), "{}", message);
+        assert!(message.contains("object 3 0: stream /Length is 99 but the data is 5 bytes"), "{}", message);
+    }
+
+    #[test]
+    fn duplicate_object_in_one_revision() {
+        // Definisi kedua object 3 tidak ditunjuk xref
+        let bytes = pdf(&[CATALOG, PAGES, "3 0 obj (first) endobj"], "3 0 obj (second) endobj\n");
+        let (issues, doc) = check(&bytes, ParseMode::Lenient);
+        assert_eq!(issues.unwrap(), vec![SyntaxIssue::DuplicateObject { number: 3, revision: 1, count: 2 }]);
+        assert_eq!(doc.get_object((3, 0)).unwrap().as_str().unwrap(), b"first");
+    }
+
+    #[test]
+    fn correct_streams_have_no_issues() {
+        let stream = "3 0 obj << /Reason (an xref-stream \\) (nested stream)) /Id <73747265616d> /Length 5 >>\nstream\nhello\nendstream\nendobj";
+        let bytes = pdf(&[CATALOG, PAGES, stream], "");
+        let (issues, doc) = check(&bytes, ParseMode::Strict);
+        assert_eq!(issues.unwrap(), Vec::new());
+        assert_eq!(content(&doc, (3, 0)), b"hello");
+    }
+
+    #[test]
+    fn keyword_search_skips_strings_and_comments() {
+        let bytes = b"<< /A (stream) /B <73747265616d> % stream\n/C [(endobj)] >> endobj";
+        assert_eq!(next_keyword(bytes, 0), Some(bytes.len() - 6));
+        assert_eq!(next_keyword(b"<</Length 1>>stream", 0), Some(13));
+        assert_eq!(next_keyword(b"/Upstream 1 endobj", 0), Some(12));
+    }
+}
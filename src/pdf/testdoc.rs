@@ -0,0 +1,334 @@
+// Import library yang diperlukan
+use anyhow::{bail, Result}; // Untuk error handling yang fleksibel
+use lopdf::xref::XrefType; // Tabel xref atau cross-reference stream
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, StringFormat}; // Struktur PDF
+use std::io::{Seek, SeekFrom}; // Ukuran file hasil
+
+use crate::cancel::CancellationToken; // Penulisan ke stdout
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::encryption::SecurityHandler; // Enkripsi AES-256 (--encrypt)
+use crate::pdf::naming; // Tanggal dokumen (mode deterministik)
+use crate::pdf::placement::PageSize; // Ukuran kertas halaman
+use crate::pdf::sign::{self, STDIO_PATH}; // Muat dokumen dan tulis incremental update, output ke stdout
+use crate::pdf::syntax::ParseMode; // Revisi sebelumnya dibaca dalam mode lenient
+use crate::pdf::text::{text_string, win_ansi_byte}; // String metadata dan teks halaman WinAnsi
+use crate::pdf::update; // Serialisasi object dan xref
+use crate::scratch::ScratchSpace; // Buffer sebelum output disimpan
+use crate::storage::{DocumentSink, WriterSink}; // Output ke stdout
+
+/// Versi PDF dokumen uji (cukup untuk xref stream dan object stream)
+const VERSION: &str = "1.7";
+
+/// Jumlah field teks per halaman sebelum pindah ke halaman berikutnya
+const FIELDS_PER_PAGE: u32 = 40;
+
+/// Izin dokumen terenkripsi: semua diizinkan (bit 1-2 nol, sisanya satu)
+const ALL_PERMISSIONS: i64 = -4;
+
+/// Paragraf pengisi halaman (teks sintetis, bukan isi dokumen asli)
+const FILLER: [&str; 6] = [
+    "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor.",
+    "Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip.",
+    "Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat.",
+    "Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt.",
+    "Sed ut perspiciatis unde omnis iste natus error sit voluptatem accusantium doloremque.",
+    "Nemo enim ipsam voluptatem quia voluptas sit aspernatur aut odit aut fugit.",
+];
+
+/// Password dokumen uji terenkripsi
+#[derive(Clone, Debug)]
+pub struct TestEncryption {
+    pub user_password: String,  // Password untuk membuka (kosong = terbuka tanpa password)
+    pub owner_password: String, // Password pemilik
+}
+
+/// Ciri dokumen uji yang dibuat `generate_test_document`
+#[derive(Clone, Debug)]
+pub struct TestDocOptions {
+    pub pages: u32,                          // Jumlah halaman
+    pub page_size: PageSize,                 // Ukuran kertas
+    pub landscape: bool,                     // Halaman mendatar
+    pub text: Option<String>,                // Teks tambahan di halaman pertama (misalnya teks jangkar placement)
+    pub form_fields: u32,                    // Jumlah field teks yang sudah terisi
+    pub signature_field: bool,               // Field signature kosong di halaman terakhir
+    pub xref_stream: bool,                   // Cross-reference stream alih-alih tabel xref
+    pub object_streams: bool,                // Object di object stream (juga untuk setiap update)
+    pub updates: u32,                        // Jumlah incremental update setelah revisi pertama
+    pub encryption: Option<TestEncryption>,  // Enkripsi AES-256 (None = tidak dienkripsi)
+}
+
+impl Default for TestDocOptions {
+    fn default() -> Self {
+        TestDocOptions {
+            pages: 1,
+            page_size: PageSize::A4,
+            landscape: false,
+            text: None,
+            form_fields: 0,
+            signature_field: false,
+            xref_stream: false,
+            object_streams: false,
+            updates: 0,
+            encryption: None,
+        }
+    }
+}
+
+/// Ringkasan dokumen uji yang ditulis
+pub struct TestDocSummary {
+    pub pages: u32,     // Jumlah halaman
+    pub revisions: u32, // Revisi pertama ditambah incremental update
+    pub size: u64,      // Ukuran file (bytes)
+}
+
+/// Buat PDF sintetis dengan ciri tertentu untuk menguji pdfsign dan
+/// mereproduksi bug tanpa membagikan dokumen asli
+///
+/// Isi halaman hanya teks pengisi. Revisi pertama ditulis utuh; setiap
+/// incremental update menambahkan satu anotasi teks dan memperbarui /ModDate,
+/// lewat jalur yang sama dengan `sign` (dimuat ulang, lalu ditambahkan di akhir
+/// file).
+///
+/// Parameter:
+///   - output: path file PDF hasil ("-" untuk stdout)
+///   - options: ciri dokumen
+///
+/// Return: jumlah halaman, revisi, dan ukuran file
+pub fn generate_test_document(output: &str, options: &TestDocOptions) -> Result<TestDocSummary> {
+    if options.pages == 0 {
+        bail!("a test document needs at least one page");
+    }
+    if options.object_streams && options.encryption.is_some() {
+        bail!("object streams cannot be combined with encryption (signed updates of encrypted documents never use them)");
+    }
+
+    let (mut doc, security) = base_document(options)?;
+    let mut bytes = format!("%PDF-{}\n%", VERSION).into_bytes();
+    bytes.extend_from_slice(&[0xE2, 0xE3, 0xCF, 0xD3, b'\n']);
+    let mut prev = Document::new();
+    if options.xref_stream || options.object_streams {
+        prev.reference_table.cross_reference_type = XrefType::CrossReferenceStream;
+    }
+    if let Some(security) = &security {
+        security.encrypt_update(&mut doc)?;
+    }
+    let base = bytes.len() as u64;
+    update::save_to(&prev, &doc, base, options.object_streams, &mut bytes)?;
+
+    let mut scratch = ScratchSpace::default().create_from(bytes)?;
+    let password = options.encryption.as_ref().map(|encryption| encryption.owner_password.as_str());
+    for revision in 1..=options.updates {
+        let mut original = sign::load_document(&mut scratch, ParseMode::Lenient)?;
+        let security = scratch.with_contents(|bytes| SecurityHandler::open(&mut original, bytes, password))??;
+        let mut doc = original.clone();
+        add_revision(&mut doc, revision)?;
+        sign::write_update(&mut scratch, &original, &doc, options.object_streams, security.as_ref())?;
+    }
+
+    let size = scratch.seek(SeekFrom::End(0))?;
+    if output == STDIO_PATH {
+        scratch.seek(SeekFrom::Start(0))?;
+        WriterSink::new(std::io::stdout().lock()).store(&mut scratch, &CancellationToken::new())?;
+    } else {
+        let _lock = FileLock::exclusive(output)?;
+        scratch.persist_to(output)?;
+    }
+    Ok(TestDocSummary { pages: options.pages, revisions: options.updates + 1, size })
+}
+
+/// Revisi pertama: halaman, form, metadata, dan (jika diminta) /Encrypt
+fn base_document(options: &TestDocOptions) -> Result<(Document, Option<SecurityHandler>)> {
+    let mut doc = Document::with_version(VERSION);
+    let (width, height) = match (options.page_size.dimensions(), options.landscape) {
+        ((width, height), false) => (width, height),
+        ((width, height), true) => (height, width),
+    };
+
+    let mut font = Dictionary::new();
+    font.set("Type", Object::Name(b"Font".to_vec()));
+    font.set("Subtype", Object::Name(b"Type1".to_vec()));
+    font.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
+    font.set("Encoding", Object::Name(b"WinAnsiEncoding".to_vec()));
+    let font_id = doc.add_object(font);
+    let mut fonts = Dictionary::new();
+    fonts.set("F1", Object::Reference(font_id));
+    fonts.set("Helv", Object::Reference(font_id));
+
+    // Halaman: teks pengisi, nomor halaman, dan teks tambahan di halaman pertama
+    let pages_id = doc.new_object_id();
+    let mut page_ids = Vec::new();
+    for number in 1..=options.pages {
+        let mut lines = vec![format!("pdfsign test document - page {} of {}", number, options.pages)];
+        if number == 1 {
+            lines.extend(options.text.clone());
+        }
+        let rows = ((height - 144.0) / 16.0) as usize;
+        lines.extend((0..rows.saturating_sub(lines.len() + 1)).map(|row| FILLER[(row + number as usize) % FILLER.len()].to_string()));
+        let content = doc.add_object(Stream::new(Dictionary::new(), page_content(&lines, height)));
+
+        let mut resources = Dictionary::new();
+        resources.set("Font", Object::Dictionary(fonts.clone()));
+        let mut page = Dictionary::new();
+        page.set("Type", Object::Name(b"Page".to_vec()));
+        page.set("Parent", Object::Reference(pages_id));
+        page.set("MediaBox", Object::Array(vec![0.into(), 0.into(), width.into(), height.into()]));
+        page.set("Resources", Object::Dictionary(resources));
+        page.set("Contents", Object::Reference(content));
+        page_ids.push(doc.add_object(page));
+    }
+
+    // Field teks yang sudah terisi, bertumpuk dari bawah halaman, dan field signature kosong
+    let mut fields = Vec::new();
+    let mut annotations: Vec<Vec<Object>> = vec![Vec::new(); page_ids.len()];
+    for index in 0..options.form_fields {
+        let page = (index / FIELDS_PER_PAGE) as usize % page_ids.len();
+        let slot = index % FIELDS_PER_PAGE;
+        let (x, y) = (72.0 + 240.0 * (slot / 20) as f32, 72.0 + 24.0 * (slot % 20) as f32);
+        let value = format!("Value {}", index + 1);
+        let mut appearance = Dictionary::new();
+        appearance.set("N", Object::Reference(doc.add_object(field_appearance(&value, &fonts))));
+        let mut field = widget(page_ids[page], [x, y, x + 200.0, y + 18.0]);
+        field.set("FT", Object::Name(b"Tx".to_vec()));
+        field.set("T", text_string(&format!("Field{}", index + 1)));
+        field.set("V", text_string(&value));
+        field.set("DA", Object::string_literal("/Helv 10 Tf 0 g"));
+        field.set("AP", Object::Dictionary(appearance));
+        let id = doc.add_object(field);
+        fields.push(Object::Reference(id));
+        annotations[page].push(Object::Reference(id));
+    }
+    if options.signature_field {
+        let page = page_ids.len() - 1;
+        let mut field = widget(page_ids[page], [width - 250.0, 50.0, width - 50.0, 110.0]);
+        field.set("FT", Object::Name(b"Sig".to_vec()));
+        field.set("T", text_string("Signature1"));
+        let id = doc.add_object(field);
+        fields.push(Object::Reference(id));
+        annotations[page].push(Object::Reference(id));
+    }
+    for (&page_id, annots) in page_ids.iter().zip(annotations) {
+        if !annots.is_empty() {
+            doc.get_dictionary_mut(page_id)?.set("Annots", Object::Array(annots));
+        }
+    }
+
+    let mut pages = Dictionary::new();
+    pages.set("Type", Object::Name(b"Pages".to_vec()));
+    pages.set("Count", Object::Integer(options.pages.into()));
+    pages.set("Kids", Object::Array(page_ids.iter().map(|&id| Object::Reference(id)).collect()));
+    doc.objects.insert(pages_id, Object::Dictionary(pages));
+
+    let mut catalog = Dictionary::new();
+    catalog.set("Type", Object::Name(b"Catalog".to_vec()));
+    catalog.set("Pages", Object::Reference(pages_id));
+    if !fields.is_empty() {
+        let mut acroform = Dictionary::new();
+        acroform.set("Fields", Object::Array(fields));
+        acroform.set("DA", Object::string_literal("/Helv 10 Tf 0 g"));
+        let mut resources = Dictionary::new();
+        resources.set("Font", Object::Dictionary(fonts));
+        acroform.set("DR", Object::Dictionary(resources));
+        catalog.set("AcroForm", Object::Dictionary(acroform));
+    }
+    let catalog_id = doc.add_object(catalog);
+
+    let now = naming::now().format("D:%Y%m%d%H%M%S").to_string();
+    let mut info = Dictionary::new();
+    info.set("Title", text_string("pdfsign test document"));
+    info.set("Producer", text_string("pdfsign gen-testdoc"));
+    info.set("CreationDate", Object::string_literal(now.clone()));
+    info.set("ModDate", Object::string_literal(now));
+    let info_id = doc.add_object(info);
+
+    let file_id = naming::file_id();
+    doc.trailer.set("Root", Object::Reference(catalog_id));
+    doc.trailer.set("Info", Object::Reference(info_id));
+    doc.trailer.set("ID", Object::Array(vec![Object::String(file_id.clone(), StringFormat::Hexadecimal), Object::String(file_id, StringFormat::Hexadecimal)]));
+
+    let security = match &options.encryption {
+        Some(encryption) => {
+            let encrypt_id = doc.new_object_id();
+            let (handler, dict) = SecurityHandler::create(&encryption.user_password, &encryption.owner_password, ALL_PERMISSIONS, encrypt_id);
+            doc.objects.insert(encrypt_id, Object::Dictionary(dict));
+            doc.trailer.set("Encrypt", Object::Reference(encrypt_id));
+            Some(handler)
+        }
+        None => None,
+    };
+    Ok((doc, security))
+}
+
+/// Satu incremental update: anotasi teks di halaman berikutnya dan /ModDate baru
+fn add_revision(doc: &mut Document, revision: u32) -> Result<()> {
+    let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+    let page_id = pages[(revision as usize - 1) % pages.len()];
+    let y = 700.0 - 30.0 * ((revision - 1) % 20) as f32;
+    let mut note = Dictionary::new();
+    note.set("Type", Object::Name(b"Annot".to_vec()));
+    note.set("Subtype", Object::Name(b"Text".to_vec()));
+    note.set("Rect", Object::Array(vec![20.into(), y.into(), 40.into(), (y + 20.0).into()]));
+    note.set("Contents", text_string(&format!("Revision {}", revision + 1)));
+    let note_id = doc.add_object(note);
+
+    let page = doc.get_dictionary_mut(page_id)?;
+    let mut annots = page.get(b"Annots").and_then(Object::as_array).cloned().unwrap_or_default();
+    annots.push(Object::Reference(note_id));
+    page.set("Annots", Object::Array(annots));
+
+    if let Ok(info_id) = doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        let now = naming::now().format("D:%Y%m%d%H%M%S").to_string();
+        doc.get_dictionary_mut(info_id)?.set("ModDate", Object::string_literal(now));
+    }
+    Ok(())
+}
+
+/// Widget annotation yang sekaligus menjadi field (tanpa /FT dan /T)
+fn widget(page_id: ObjectId, rect: [f32; 4]) -> Dictionary {
+    let mut widget = Dictionary::new();
+    widget.set("Type", Object::Name(b"Annot".to_vec()));
+    widget.set("Subtype", Object::Name(b"Widget".to_vec()));
+    widget.set("Rect", Object::Array(rect.iter().map(|&value| value.into()).collect()));
+    widget.set("P", Object::Reference(page_id));
+    widget.set("F", Object::Integer(4));
+    widget
+}
+
+/// Appearance stream field teks 200 x 18 berisi `value` (sesuai /DA)
+fn field_appearance(value: &str, fonts: &Dictionary) -> Stream {
+    let mut resources = Dictionary::new();
+    resources.set("Font", Object::Dictionary(fonts.clone()));
+    let mut dict = Dictionary::new();
+    dict.set("Type", Object::Name(b"XObject".to_vec()));
+    dict.set("Subtype", Object::Name(b"Form".to_vec()));
+    dict.set("BBox", Object::Array(vec![0.into(), 0.into(), 200.into(), 18.into()]));
+    dict.set("Resources", Object::Dictionary(resources));
+    let mut content = b"/Tx BMC\nq BT /Helv 10 Tf 0 g 2 5 Td ".to_vec();
+    content.extend(pdf_string(value));
+    content.extend_from_slice(b" Tj ET Q\nEMC\n");
+    Stream::new(dict, content)
+}
+
+/// Content stream berisi baris-baris teks Helvetica 11 pt dari kiri atas halaman
+fn page_content(lines: &[String], height: f32) -> Vec<u8> {
+    let mut content = format!("BT\n/F1 11 Tf\n16 TL\n72 {} Td\n", height - 72.0).into_bytes();
+    for line in lines {
+        content.extend(pdf_string(line));
+        content.extend_from_slice(b" Tj T*\n");
+    }
+    content.extend_from_slice(b"ET\n");
+    content
+}
+
+/// String literal WinAnsi untuk content stream (karakter lain menjadi "?")
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut string = vec![b'('];
+    for ch in text.chars() {
+        let byte = win_ansi_byte(ch).unwrap_or(b'?');
+        if matches!(byte, b'(' | b')' | b'\\') {
+            string.push(b'\\');
+        }
+        string.push(byte);
+    }
+    string.push(b')');
+    string
+}
@@ -0,0 +1,288 @@
+// Import library yang diperlukan
+use chrono::{DateTime, FixedOffset, Utc}; // Tanggal untuk laporan
+use lopdf::{Object, StringFormat}; // String PDF
+#[cfg(feature = "sign")]
+use unicode_normalization::UnicodeNormalization; // NFC untuk teks dari penandatangan
+
+/// Karakter kontrol arah teks (bidi) dan karakter lebar nol yang ditolak di
+/// nama, alasan, dan lokasi penandatangan: tampilan signature bisa menampilkan
+/// teks yang berbeda dari metadata yang tercatat
+#[cfg(feature = "sign")]
+const HIDDEN_CHARACTERS: [(char, &str); 21] = [
+    ('\u{061C}', "ARABIC LETTER MARK"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+];
+
+/// Karakter PDFDocEncoding di 0x18-0x1F dan 0x80-0xA0 yang berbeda dari Latin-1
+/// (ISO 32000-1 Annex D); byte lain di rentang itu tidak terdefinisi
+const PDF_DOC_SPECIAL: [(u8, char); 40] = [
+    (0x18, '\u{02D8}'), (0x19, '\u{02C7}'), (0x1A, '\u{02C6}'), (0x1B, '\u{02D9}'),
+    (0x1C, '\u{02DD}'), (0x1D, '\u{02DB}'), (0x1E, '\u{02DA}'), (0x1F, '\u{02DC}'),
+    (0x80, '\u{2022}'), (0x81, '\u{2020}'), (0x82, '\u{2021}'), (0x83, '\u{2026}'),
+    (0x84, '\u{2014}'), (0x85, '\u{2013}'), (0x86, '\u{0192}'), (0x87, '\u{2044}'),
+    (0x88, '\u{2039}'), (0x89, '\u{203A}'), (0x8A, '\u{2212}'), (0x8B, '\u{2030}'),
+    (0x8C, '\u{201E}'), (0x8D, '\u{201C}'), (0x8E, '\u{201D}'), (0x8F, '\u{2018}'),
+    (0x90, '\u{2019}'), (0x91, '\u{201A}'), (0x92, '\u{2122}'), (0x93, '\u{FB01}'),
+    (0x94, '\u{FB02}'), (0x95, '\u{0141}'), (0x96, '\u{0152}'), (0x97, '\u{0160}'),
+    (0x98, '\u{0178}'), (0x99, '\u{017D}'), (0x9A, '\u{0131}'), (0x9B, '\u{0142}'),
+    (0x9C, '\u{0153}'), (0x9D, '\u{0161}'), (0x9E, '\u{017E}'), (0xA0, '\u{20AC}'),
+];
+
+/// Karakter WinAnsiEncoding (Windows-1252) di 0x80-0x9F; sisanya sama dengan Latin-1
+const WIN_ANSI_SPECIAL: [(u8, char); 27] = [
+    (0x80, '\u{20AC}'), (0x82, '\u{201A}'), (0x83, '\u{0192}'), (0x84, '\u{201E}'),
+    (0x85, '\u{2026}'), (0x86, '\u{2020}'), (0x87, '\u{2021}'), (0x88, '\u{02C6}'),
+    (0x89, '\u{2030}'), (0x8A, '\u{0160}'), (0x8B, '\u{2039}'), (0x8C, '\u{0152}'),
+    (0x8E, '\u{017D}'), (0x91, '\u{2018}'), (0x92, '\u{2019}'), (0x93, '\u{201C}'),
+    (0x94, '\u{201D}'), (0x95, '\u{2022}'), (0x96, '\u{2013}'), (0x97, '\u{2014}'),
+    (0x98, '\u{02DC}'), (0x99, '\u{2122}'), (0x9A, '\u{0161}'), (0x9B, '\u{203A}'),
+    (0x9C, '\u{0153}'), (0x9E, '\u{017E}'), (0x9F, '\u{0178}'),
+];
+
+/// Text string PDF untuk nilai seperti /Name, /Reason, /Location, /ContactInfo,
+/// dan /TU: PDFDocEncoding jika semua karakternya ada di sana, selain itu
+/// UTF-16BE diawali BOM FE FF (ditulis hex karena isinya biner)
+pub fn text_string(text: &str) -> Object {
+    match text.chars().map(pdf_doc_byte).collect::<Option<Vec<u8>>>() {
+        Some(bytes) => Object::String(bytes, StringFormat::Literal),
+        None => {
+            let mut bytes = vec![0xFE, 0xFF];
+            bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+            Object::String(bytes, StringFormat::Hexadecimal)
+        }
+    }
+}
+
+/// Baca text string PDF: UTF-16BE (BOM FE FF), UTF-8 (BOM EF BB BF, PDF 2.0),
+/// atau PDFDocEncoding
+///
+/// Tanpa BOM, bytes yang valid UTF-8 dan berisi karakter non-ASCII dibaca
+/// sebagai UTF-8: versi pdfsign lama menulis metadata sebagai UTF-8 mentah.
+pub fn decode_text_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(utf8).to_string();
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_string();
+    }
+    bytes.iter().map(|&byte| pdf_doc_char(byte)).collect()
+}
+
+/// Byte WinAnsiEncoding untuk `ch` (font standar di appearance stream);
+/// None jika font standar tidak punya karakter itu
+pub fn win_ansi_byte(ch: char) -> Option<u8> {
+    match ch as u32 {
+        0x20..=0x7E | 0xA0..=0xFF => Some(ch as u32 as u8),
+        _ => WIN_ANSI_SPECIAL.iter().find(|&&(_, special)| special == ch).map(|&(byte, _)| byte),
+    }
+}
+
+/// Karakter untuk byte WinAnsiEncoding (kebalikan `win_ansi_byte`);
+/// None untuk byte yang tidak terdefinisi
+pub fn win_ansi_char(byte: u8) -> Option<char> {
+    match byte {
+        0x20..=0x7E | 0xA0..=0xFF => Some(char::from(byte)),
+        _ => WIN_ANSI_SPECIAL.iter().find(|&&(special, _)| special == byte).map(|&(_, ch)| ch),
+    }
+}
+
+/// Byte PDFDocEncoding untuk `ch`, atau None jika tidak ada
+fn pdf_doc_byte(ch: char) -> Option<u8> {
+    match ch as u32 {
+        0x09 | 0x0A | 0x0D | 0x20..=0x7E => Some(ch as u32 as u8),
+        0xA1..=0xFF if ch != '\u{AD}' => Some(ch as u32 as u8),
+        _ => PDF_DOC_SPECIAL.iter().find(|&&(_, special)| special == ch).map(|&(byte, _)| byte),
+    }
+}
+
+/// Karakter untuk byte PDFDocEncoding (byte tak terdefinisi menjadi U+FFFD)
+fn pdf_doc_char(byte: u8) -> char {
+    match byte {
+        0x09 | 0x0A | 0x0D | 0x20..=0x7E => char::from(byte),
+        0xA1..=0xFF if byte != 0xAD => char::from(byte),
+        _ => PDF_DOC_SPECIAL
+            .iter()
+            .find(|&&(special, _)| special == byte)
+            .map_or('\u{FFFD}', |&(_, ch)| ch),
+    }
+}
+
+/// String JSON dengan escape untuk kutip, backslash, dan karakter kontrol
+pub fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Normalisasi NFC teks dari penandatangan (nama, alasan, lokasi, kontak)
+/// dan tolak karakter bidi dan lebar nol
+///
+/// NFC membuat "é" yang diketik sebagai e + aksen gabungan sama dengan "é"
+/// siap pakai, sehingga tampilan, /Name, dan pencarian memakai bytes yang sama.
+///
+/// Parameter:
+///   - label: nama nilai untuk pesan error, misalnya "signer name"
+///   - value: teks dari command line, profil, metadata, atau hook
+///
+/// Return: teks dalam NFC; error jika ada karakter dari `HIDDEN_CHARACTERS`
+#[cfg(feature = "sign")]
+pub fn signer_text(label: &str, value: &str) -> anyhow::Result<String> {
+    if let Some((position, (ch, name))) =
+        value.chars().enumerate().find_map(|(i, ch)| HIDDEN_CHARACTERS.iter().find(|(hidden, _)| *hidden == ch).map(|hidden| (i + 1, hidden)))
+    {
+        anyhow::bail!(
+            "{} contains U+{:04X} {} at character {}; bidi-control and zero-width characters can make the visible signature differ from its recorded metadata",
+            label,
+            u32::from(*ch),
+            name,
+            position
+        );
+    }
+    Ok(value.nfc().collect())
+}
+
+/// Waktu untuk output mesin (JSON, CSV, log audit): RFC 3339 di UTC dengan
+/// presisi detik, misalnya "2024-05-01T08:30:00Z", tidak tergantung locale
+pub fn machine_time(time: impl Into<DateTime<Utc>>) -> String {
+    time.into().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Waktu untuk output teks: "2024-05-01 08:30:00 UTC"
+pub fn human_time(time: impl Into<DateTime<Utc>>) -> String {
+    time.into().format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+/// Ukuran untuk pesan: "812 bytes", "4.0 KB", "3.2 MB"
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} bytes", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Tanggal PDF "D:YYYYMMDDHHmmSS+hh'mm'" beserta offset zona waktunya (offset
+/// boleh Z atau tidak ada = UTC); None jika tidak dikenali
+pub fn parse_pdf_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let digits = value.strip_prefix("D:").unwrap_or(value);
+    let local = chrono::NaiveDateTime::parse_from_str(digits.get(..14)?, "%Y%m%d%H%M%S").ok()?;
+    let zone = digits[14..].replace('\'', "");
+    let offset = match zone.as_bytes() {
+        [] | [b'Z', ..] => 0,
+        [sign @ (b'+' | b'-'), rest @ ..] if rest.len() >= 2 && rest.iter().all(u8::is_ascii_digit) => {
+            let hours: i32 = zone[1..3].parse().ok()?;
+            let minutes: i32 = zone.get(3..5).and_then(|m| m.parse().ok()).unwrap_or(0);
+            let seconds = hours * 3600 + minutes * 60;
+            if *sign == b'-' { -seconds } else { seconds }
+        }
+        _ => return None,
+    };
+    local.and_local_timezone(FixedOffset::east_opt(offset)?).single()
+}
+
+/// Tanggal PDF untuk output mesin (lihat `machine_time`); None jika tidak dikenali
+pub fn machine_pdf_date(value: &str) -> Option<String> {
+    parse_pdf_date(value).map(machine_time)
+}
+
+/// Tanggal PDF untuk output teks: "YYYY-MM-DD HH:MM:SS +hh:mm" dengan zona
+/// waktu penandatangan ("UTC" jika offset 0); nilai yang tidak dikenali
+/// dikembalikan apa adanya
+pub fn human_pdf_date(value: &str) -> String {
+    match parse_pdf_date(value) {
+        Some(time) if time.offset().local_minus_utc() == 0 => time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S %:z").to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(text: &str) -> (Vec<u8>, StringFormat) {
+        match text_string(text) {
+            Object::String(bytes, format) => (bytes, format),
+            other => panic!("not a string: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn pdf_doc_encoding_when_possible() {
+        assert_eq!(encoded("Jakarta"), (b"Jakarta".to_vec(), StringFormat::Literal));
+        // ü dari Latin-1, – dan € dari tabel khusus PDFDocEncoding
+        assert_eq!(encoded("Zürich – €5"), (vec![b'Z', 0xFC, b'r', b'i', b'c', b'h', b' ', 0x85, b' ', 0xA0, b'5'], StringFormat::Literal));
+    }
+
+    #[test]
+    fn utf16_for_other_characters() {
+        let (bytes, format) = encoded("Łódź 北京");
+        assert_eq!(format, StringFormat::Hexadecimal);
+        assert_eq!(&bytes[..4], &[0xFE, 0xFF, 0x01, 0x41]);
+        assert_eq!(&bytes[bytes.len() - 4..], &[0x53, 0x17, 0x4E, 0xAC]);
+        // Soft hyphen tidak terdefinisi di PDFDocEncoding
+        assert_eq!(encoded("co\u{AD}op").1, StringFormat::Hexadecimal);
+    }
+
+    #[test]
+    fn decode_round_trip() {
+        for text in ["Digitally signed", "Zürich – €5", "Łódź 北京", "Ŝ\u{02D8}\u{FB01}"] {
+            assert_eq!(decode_text_string(&encoded(text).0), text);
+        }
+        assert_eq!(decode_text_string(&[0xEF, 0xBB, 0xBF, 0xC3, 0xA9]), "é");
+        // Metadata UTF-8 mentah dari versi pdfsign lama
+        assert_eq!(decode_text_string("Müller".as_bytes()), "Müller");
+        // Bukan UTF-8: PDFDocEncoding, byte tak terdefinisi menjadi U+FFFD
+        assert_eq!(decode_text_string(&[0x7F, 0xFF]), "\u{FFFD}ÿ");
+    }
+
+    #[test]
+    fn win_ansi_bytes() {
+        assert_eq!(win_ansi_byte('A'), Some(b'A'));
+        assert_eq!(win_ansi_byte('é'), Some(0xE9));
+        assert_eq!(win_ansi_byte('€'), Some(0x80));
+        assert_eq!(win_ansi_byte('\u{2013}'), Some(0x96));
+        assert_eq!(win_ansi_byte('北'), None);
+    }
+}
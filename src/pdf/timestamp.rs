@@ -0,0 +1,206 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::fs::File; // Untuk membaca file input
+use std::io::Write; // Menulis hasil ke buffer sementara
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+use crate::crypto::policy::AlgorithmPolicy; // Tolak token TSA dengan digest lemah
+use crate::crypto::tsa::{self, Tsa}; // Request TimeStampToken ke TSA
+use crate::lock::FileLock; // Lock antar proses pada file output
+use crate::pdf::sign::{self, TIMESTAMP_TOKEN_RESERVE}; // Placeholder, ByteRange, incremental update
+use crate::pdf::syntax::ParseMode; // Struktur file dibaca dalam mode lenient
+use crate::pdf::{form, mdp, pades}; // Catalog, DocMDP, field document timestamp
+use crate::pdf::pades::TimestampWidget; // Posisi, flag, dan tampilan widget timestamp
+use crate::scratch::ScratchSpace; // Buffer sementara sebelum output disimpan
+#[cfg(feature = "appearance")]
+use crate::pdf::appearance::{self, CompiledAppearance}; // Template dan layout tampilan
+#[cfg(feature = "appearance")]
+use crate::pdf::naming; // Waktu request untuk teks stamp (mode deterministik)
+#[cfg(feature = "appearance")]
+use crate::pdf::options::{Placement, SignatureOptions}; // Posisi dan opsi tampilan stamp
+#[cfg(feature = "appearance")]
+use crate::pdf::pdfa::PdfaPlan; // Font dan warna untuk dokumen PDF/A
+
+/// Hasil `pdfsign timestamp`
+pub struct DocumentTimestamp {
+    pub field_name: String,                     // Nama field document timestamp baru
+    pub time: chrono::DateTime<chrono::Utc>,    // genTime dari TSA
+    pub token_size: usize,                      // Ukuran TimeStampToken (bytes)
+}
+
+/// Tampilan field document timestamp
+#[derive(Clone, Debug, Default)]
+pub enum TimestampAppearance {
+    /// Widget berukuran nol di halaman 1 tanpa tampilan (default)
+    #[default]
+    Invisible,
+    /// Benar-benar tak terlihat: widget berukuran nol dengan flag Hidden
+    /// (tanpa Print), sehingga tidak pernah digambar, dicetak, atau disorot
+    /// viewer; tidak boleh dipakai di dokumen PDF/A
+    Hidden,
+    /// Stamp kecil di `placement` opsi (default: halaman 1, `DEFAULT_RECT`),
+    /// disusun dengan engine template yang sama seperti signature biasa
+    ///
+    /// Yang dipakai hanya posisi, `widget_flags`, `require_pdfa`, dan opsi
+    /// `appearance_*`. Tanpa template, teksnya satu baris per bahasa
+    /// (`AppearanceLanguage::timestamp_template`, "Timestamped on {date}").
+    /// `{name}` berisi host TSA; `{date}` adalah waktu lokal saat timestamp
+    /// diminta (atau `signing_time`), karena waktu TSA baru diketahui setelah
+    /// tampilannya ikut di-hash.
+    #[cfg(feature = "appearance")]
+    Stamp(Box<SignatureOptions>),
+}
+
+/// Tambahkan document timestamp (/DocTimeStamp, SubFilter ETSI.RFC3161) tanpa
+/// signature penandatangan
+///
+/// Field signature ditambahkan lewat incremental update (tak terlihat, atau
+/// dengan stamp sesuai `appearance`), lalu /Contents-nya diisi TimeStampToken
+/// dari TSA atas digest ByteRange (`tsa.hash`). Signature yang sudah ada tetap
+/// valid; dokumen yang disertifikasi tanpa izin perubahan (DocMDP P=1) ditolak.
+///
+/// Parameter:
+///   - input: path file PDF
+///   - output: path file PDF hasil (boleh sama dengan input)
+///   - tsa: TSA yang memberi timestamp
+///   - appearance: tampilan field timestamp
+///   - object_streams: simpan objek update di object stream
+///   - cancel: token pembatalan; batas waktunya membatasi request TSA
+pub fn timestamp_document(
+    input: &str,
+    output: &str,
+    tsa: &Tsa,
+    appearance: &TimestampAppearance,
+    object_streams: bool,
+    cancel: &CancellationToken,
+) -> Result<DocumentTimestamp> {
+    let _lock = FileLock::exclusive(output)?;
+    // Bytes asli dibaca langsung dari file; hanya update yang ditulis ke buffer sementara
+    let file = File::open(input).with_context(|| format!("cannot read {}", input))?;
+    let mut scratch = ScratchSpace::default().create_over(file)?;
+    let original = sign::load_document(&mut scratch, ParseMode::Lenient)?;
+    // Field timestamp tanpa enkripsi di dokumen terenkripsi akan dibaca sebagai data rusak
+    if original.trailer.has(b"Encrypt") {
+        bail!("timestamp does not support encrypted documents; use `sign --pades-level b-lta --pdf-password` instead");
+    }
+    let root_id = form::catalog_id(&original)?;
+    if mdp::certification_permissions(&original, root_id) == Some(1) {
+        bail!("the document is certified with no changes allowed; a document timestamp would invalidate the certification");
+    }
+
+    let mut doc = original.clone();
+    let (contents, byte_range) = sign::signature_placeholders(TIMESTAMP_TOKEN_RESERVE);
+    let widget = timestamp_widget(&mut doc, appearance, tsa)?;
+    let field_name = pades::add_document_timestamp_with(&mut doc, contents, byte_range, &widget)?;
+    let update_start = sign::write_update(&mut scratch, &original, &doc, object_streams, None)?;
+    let (contents_pos, byte_range) = sign::patch_byte_range(&mut scratch, update_start, TIMESTAMP_TOKEN_RESERVE)?;
+
+    // Imprint dengan hash TSA (--tsa-hash), sama seperti document timestamp B-LTA;
+    // token yang ditandatangani TSA dengan MD5/SHA-1 selalu ditolak
+    let token = tsa::request_timestamp(
+        tsa,
+        &mut |hash| sign::hash_byte_range(&mut scratch, &byte_range, hash, cancel),
+        &AlgorithmPolicy::default(),
+        cancel,
+    )?;
+    let time = tsa::tst_info(&token)?.gen_time.to_system_time();
+    sign::write_contents(&mut scratch, contents_pos, TIMESTAMP_TOKEN_RESERVE, &token)?;
+
+    // Tulis ke buffer sementara dulu, lalu salin ke output secara atomik
+    cancel.check()?;
+    scratch.flush()?;
+    scratch.persist_to(output)?;
+
+    Ok(DocumentTimestamp {
+        field_name,
+        time: time.into(),
+        token_size: token.len(),
+    })
+}
+
+/// Widget untuk `appearance`; tampilan stamp ditambahkan ke `doc`
+#[cfg_attr(not(feature = "appearance"), allow(unused_variables))]
+fn timestamp_widget(doc: &mut lopdf::Document, appearance: &TimestampAppearance, tsa: &Tsa) -> Result<TimestampWidget> {
+    match appearance {
+        TimestampAppearance::Invisible => Ok(TimestampWidget::invisible()),
+        TimestampAppearance::Hidden => {
+            // PDF/A mewajibkan flag Print dan melarang Hidden pada annotation
+            if crate::pdf::pdfa::detect(doc).is_some() {
+                bail!("PDF/A does not allow hidden annotations; leave out --invisible to add a zero-size timestamp widget");
+            }
+            Ok(TimestampWidget { flags: 2, ..TimestampWidget::invisible() })
+        }
+        #[cfg(feature = "appearance")]
+        TimestampAppearance::Stamp(options) => stamp_widget(doc, options, tsa),
+    }
+}
+
+/// Widget dengan stamp "Timestamped on ..." di posisi `options.placement`
+#[cfg(feature = "appearance")]
+fn stamp_widget(doc: &mut lopdf::Document, options: &SignatureOptions, tsa: &Tsa) -> Result<TimestampWidget> {
+    use crate::pdf::options::{AnnotationFlags, Rect};
+    use lopdf::{Dictionary, Object, Stream};
+
+    let placement = options.placement.unwrap_or(Placement::Default(1));
+    let page_number = placement.page();
+    let page_count = doc.get_pages().len();
+    let page_id = *doc
+        .get_pages()
+        .get(&page_number)
+        .ok_or_else(|| anyhow::anyhow!("page {} does not exist (document has {} pages)", page_number, page_count))?;
+    let rect = sign::placement_rect(doc, page_id, placement)?;
+
+    // Teks stamp: template dari opsi, atau satu baris bawaan per bahasa;
+    // {name} berisi host TSA
+    let mut options = options.clone();
+    if options.appearance_template.is_none() {
+        let lines: Vec<&str> = options.appearance_languages.iter().map(|lang| lang.timestamp_template()).collect();
+        options.appearance_template = Some(lines.join("\n"));
+    }
+    options.name = tsa_host(&tsa.url).to_string();
+    options.reason.clear();
+    options.location.clear();
+    options.contact_info.clear();
+
+    let mut pdfa = PdfaPlan::new(doc, &options, true)?;
+    let compiled = CompiledAppearance::compile(&options, &rect, &pdfa.target)?;
+    if let Some(image) = compiled.image() {
+        pdfa.check_image(image);
+    }
+    pdfa.finish(options.require_pdfa)?;
+
+    let requested_at = match options.signing_time {
+        Some(time) => time.format(appearance::DATE_FORMAT).to_string(),
+        None => naming::now().format(appearance::DATE_FORMAT).to_string(),
+    };
+    let font = compiled.add_font(doc);
+    let image_id = compiled.image().map(|image| image.add_to(doc));
+    let mut stream_dict = Dictionary::new();
+    stream_dict.set("Type", Object::Name(b"XObject".to_vec()));
+    stream_dict.set("Subtype", Object::Name(b"Form".to_vec()));
+    stream_dict.set("FormType", Object::Integer(1));
+    stream_dict.set("BBox", Object::Array(vec![0.into(), 0.into(), Object::Real(rect.width()), Object::Real(rect.height())]));
+    stream_dict.set("Resources", Object::Dictionary(appearance::resources(Some(font), image_id)));
+    let appearance_id = doc.add_object(Stream::new(stream_dict, compiled.content(&requested_at)));
+
+    let Rect { left, bottom, right, top } = rect;
+    Ok(TimestampWidget {
+        page_id: Some(page_id),
+        rect: [left, bottom, right, top],
+        flags: options.widget_flags.unwrap_or(AnnotationFlags::WIDGET).bits(),
+        appearance: Some(appearance_id),
+    })
+}
+
+/// Host TSA untuk `{name}`, misalnya "freetsa.org" dari "https://freetsa.org/tsr"
+#[cfg(feature = "appearance")]
+fn tsa_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    // Port dibuang; alamat IPv6 tetap dengan kurung sikunya
+    match host.find(']') {
+        Some(end) if host.starts_with('[') => &host[..=end],
+        _ => host.split(':').next().unwrap_or(host),
+    }
+}
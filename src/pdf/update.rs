@@ -0,0 +1,302 @@
+// Import library yang diperlukan
+use anyhow::Result; // Untuk error handling yang fleksibel
+use lopdf::xref::XrefType; // Jenis xref dokumen asli (tabel atau stream)
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, StringFormat}; // Struktur PDF
+use std::collections::BTreeMap; // Object baru, urut berdasarkan ID
+use std::io::Write; // Menulis hasil serialisasi
+
+/// Versi PDF minimum untuk object stream dan cross-reference stream
+const MIN_VERSION: f32 = 1.5;
+
+/// Posisi satu object di cross-reference stream
+enum XrefEntry {
+    /// Object biasa: offset byte dari awal file dan generation number
+    Offset(u64, u16),
+    /// Object di dalam object stream: ID object stream dan indeks di dalamnya
+    Compressed(u32, u16),
+}
+
+/// Tulis incremental update (hanya object yang baru/berubah, xref, dan trailer)
+/// yang dimulai di offset `base` dari awal file; bytes sebelumnya tidak ditulis
+///
+/// Tanpa `object_streams`, object ditulis apa adanya dan xref mengikuti dokumen
+/// asli: tabel xref, atau cross-reference stream jika dokumen asli memakainya.
+/// Dengan `object_streams`, object baru yang bukan stream dimasukkan ke satu
+/// object stream (/Type /ObjStm) berkompresi Flate, stream baru dikompres
+/// Flate, dan xref selalu ditulis sebagai cross-reference stream.
+///
+/// Signature dictionary (yang punya /ByteRange) tetap ditulis sebagai object
+/// biasa agar placeholder /Contents dan /ByteRange bisa ditambal langsung di file.
+///
+/// Parameter:
+///   - prev: dokumen asli (semua revisi sebelum update ini)
+///   - update: object baru/berubah dan trailer (`sign::incremental_update`)
+///   - base: panjang file sebelum update (update dimulai di baris baru)
+///   - object_streams: pakai object stream dan kompresi
+///   - target: tujuan penulisan (hanya update)
+pub fn save_to(prev: &Document, update: &Document, base: u64, object_streams: bool, target: &mut impl Write) -> Result<()> {
+    let mut objects = update.objects.clone();
+    if object_streams {
+        require_version(prev, &mut objects);
+    }
+
+    let mut body = Vec::new(); // Isi update setelah bytes asli
+    let mut entries = BTreeMap::new(); // Entri cross-reference per object ID
+    let mut next_id = update.max_id + 1;
+
+    // Object stream: header "id offset id offset ..." lalu object-object-nya
+    let object_stream_id = next_id;
+    let (mut header, mut packed) = (Vec::new(), Vec::new());
+    let mut count: u16 = 0;
+    for (&(id, generation), object) in &objects {
+        if object_streams && can_pack(prev, (id, generation), object) && count < u16::MAX {
+            write!(header, "{} {} ", id, packed.len())?;
+            write_object(&mut packed, object)?;
+            packed.push(b'\n');
+            entries.insert(id, XrefEntry::Compressed(object_stream_id, count));
+            count += 1;
+        } else {
+            let mut object = object.clone();
+            if let (Object::Stream(ref mut stream), true) = (&mut object, object_streams) {
+                stream.compress()?;
+            }
+            entries.insert(id, XrefEntry::Offset(base + body.len() as u64, generation));
+            write_indirect(&mut body, (id, generation), &object)?;
+        }
+    }
+    if count > 0 {
+        let mut dict = Dictionary::new();
+        dict.set("Type", Object::Name(b"ObjStm".to_vec()));
+        dict.set("N", Object::Integer(count.into()));
+        dict.set("First", Object::Integer(header.len() as i64));
+        header.append(&mut packed);
+        let mut stream = Stream::new(dict, header);
+        stream.compress()?;
+        entries.insert(object_stream_id, XrefEntry::Offset(base + body.len() as u64, 0));
+        write_indirect(&mut body, (object_stream_id, 0), &Object::Stream(stream))?;
+        next_id += 1;
+    }
+
+    let xref_offset = base + body.len() as u64;
+    let mut trailer = update.trailer.clone();
+    for key in [&b"XRefStm"[..], b"Type", b"W", b"Index", b"Filter", b"DecodeParms", b"Length"] {
+        trailer.remove(key);
+    }
+    if object_streams || matches!(prev.reference_table.cross_reference_type, XrefType::CrossReferenceStream) {
+        // Cross-reference stream menggantikan tabel xref dan trailer
+        let xref_id = next_id;
+        entries.insert(xref_id, XrefEntry::Offset(xref_offset, 0));
+        let (data, width, index) = encode_xref(&entries);
+        trailer.set("Type", Object::Name(b"XRef".to_vec()));
+        trailer.set("Size", Object::Integer(i64::from(xref_id) + 1));
+        trailer.set("W", Object::Array(vec![Object::Integer(1), Object::Integer(width as i64), Object::Integer(2)]));
+        trailer.set("Index", Object::Array(index));
+        let mut stream = Stream::new(trailer, data);
+        stream.compress()?;
+        write_indirect(&mut body, (xref_id, 0), &Object::Stream(stream))?;
+    } else {
+        // Tabel xref: entri 0 lalu subsection untuk setiap rentang ID berurutan
+        trailer.set("Size", Object::Integer(i64::from(next_id)));
+        body.extend_from_slice(b"xref\n0 1\n0000000000 65535 f \n");
+        let ids: Vec<u32> = entries.keys().copied().collect();
+        for section in ids.chunk_by(|a, b| a + 1 == *b) {
+            writeln!(body, "{} {}", section[0], section.len())?;
+            for id in section {
+                if let Some(XrefEntry::Offset(offset, generation)) = entries.get(id) {
+                    writeln!(body, "{:010} {:05} n ", offset, generation)?;
+                }
+            }
+        }
+        body.extend_from_slice(b"trailer\n");
+        write_dictionary(&mut body, &trailer)?;
+        body.push(b'\n');
+    }
+    write!(body, "startxref\n{}\n%%EOF", xref_offset)?;
+
+    target.write_all(&body)?;
+    Ok(())
+}
+
+/// Object yang boleh masuk object stream: object baru (bukan stream) dengan
+/// generation 0, kecuali signature dictionary (placeholder-nya harus bisa
+/// ditambal di file)
+///
+/// Object lama yang berubah (catalog, halaman) ditulis biasa: jika object yang
+/// sama ada di dua object stream, lopdf dan sebagian reader lain bisa memakai
+/// versi lama karena tidak mengikuti xref untuk object terkompresi.
+fn can_pack(prev: &Document, (id, generation): ObjectId, object: &Object) -> bool {
+    let packable = match object {
+        Object::Stream(_) => false,
+        Object::Dictionary(dict) => !dict.has(b"ByteRange"),
+        _ => true,
+    };
+    packable && generation == 0 && !prev.objects.contains_key(&(id, generation))
+}
+
+/// Object stream butuh PDF 1.5; dokumen yang lebih lama dinaikkan lewat
+/// /Version di catalog (header file tidak boleh diubah di incremental update)
+fn require_version(prev: &Document, objects: &mut BTreeMap<ObjectId, Object>) {
+    let Ok(root_id) = prev.trailer.get(b"Root").and_then(Object::as_reference) else {
+        return;
+    };
+    let catalog = match objects.get(&root_id) {
+        Some(Object::Dictionary(catalog)) => catalog.clone(),
+        _ => match prev.get_dictionary(root_id) {
+            Ok(catalog) => catalog.clone(),
+            Err(_) => return,
+        },
+    };
+    let version = |value: &str| value.trim().parse::<f32>().unwrap_or(0.0);
+    let current = catalog
+        .get(b"Version")
+        .and_then(Object::as_name_str)
+        .map(version)
+        .unwrap_or(0.0)
+        .max(version(&prev.version));
+    if current < MIN_VERSION {
+        let mut catalog = catalog;
+        catalog.set("Version", Object::Name(b"1.5".to_vec()));
+        objects.insert(root_id, Object::Dictionary(catalog));
+    }
+}
+
+/// Encode entri xref sebagai data cross-reference stream
+/// Return: data, lebar field kedua (bytes), dan array /Index
+fn encode_xref(entries: &BTreeMap<u32, XrefEntry>) -> (Vec<u8>, usize, Vec<Object>) {
+    // Field kedua selebar offset / ID object stream terbesar
+    let largest = entries
+        .values()
+        .map(|entry| match entry {
+            XrefEntry::Offset(offset, _) => *offset,
+            XrefEntry::Compressed(container, _) => u64::from(*container),
+        })
+        .max()
+        .unwrap_or(0);
+    let width = (8 - largest.leading_zeros() as usize / 8).max(1);
+
+    let mut data = Vec::new();
+    let mut index: Vec<Object> = Vec::new();
+    let mut section: Option<(u32, i64)> = None; // Awal dan jumlah entri subsection aktif
+    for (&id, entry) in entries {
+        section = match section {
+            Some((start, len)) if i64::from(start) + len == i64::from(id) => Some((start, len + 1)),
+            Some((start, len)) => {
+                index.extend([Object::Integer(start.into()), Object::Integer(len)]);
+                Some((id, 1))
+            }
+            None => Some((id, 1)),
+        };
+        let (kind, field, extra) = match entry {
+            XrefEntry::Offset(offset, generation) => (1u8, *offset, *generation),
+            XrefEntry::Compressed(container, position) => (2u8, u64::from(*container), *position),
+        };
+        data.push(kind);
+        data.extend_from_slice(&field.to_be_bytes()[8 - width..]);
+        data.extend_from_slice(&extra.to_be_bytes());
+    }
+    if let Some((start, len)) = section {
+        index.extend([Object::Integer(start.into()), Object::Integer(len)]);
+    }
+    (data, width, index)
+}
+
+/// Tulis "id gen obj ... endobj"
+fn write_indirect(out: &mut Vec<u8>, (id, generation): ObjectId, object: &Object) -> Result<()> {
+    writeln!(out, "{} {} obj", id, generation)?;
+    if needs_separator(object) {
+        out.push(b' ');
+    }
+    write_object(out, object)?;
+    out.extend_from_slice(b"\nendobj\n");
+    Ok(())
+}
+
+/// Panjang serialisasi object (tanpa header "id gen obj")
+pub(crate) fn serialized_len(object: &Object) -> Result<usize> {
+    let mut out = Vec::new();
+    write_object(&mut out, object)?;
+    Ok(out.len())
+}
+
+/// Serialisasi object dengan format yang sama seperti lopdf, sehingga
+/// placeholder signature (/ByteRange[0 ...], <000...>) tetap bisa dicari
+fn write_object(out: &mut Vec<u8>, object: &Object) -> Result<()> {
+    match object {
+        Object::Null => out.extend_from_slice(b"null"),
+        Object::Boolean(value) => out.extend_from_slice(if *value { b"true" } else { b"false" }),
+        Object::Integer(value) => write!(out, "{}", value)?,
+        Object::Real(value) => write!(out, "{}", value)?,
+        Object::Name(name) => write_name(out, name),
+        Object::String(text, StringFormat::Literal) => {
+            out.push(b'(');
+            for &byte in text {
+                match byte {
+                    b'(' | b')' | b'\\' => out.extend_from_slice(&[b'\\', byte]),
+                    b'\r' => out.extend_from_slice(b"\\r"),
+                    _ => out.push(byte),
+                }
+            }
+            out.push(b')');
+        }
+        Object::String(text, StringFormat::Hexadecimal) => {
+            out.push(b'<');
+            for byte in text {
+                write!(out, "{:02X}", byte)?;
+            }
+            out.push(b'>');
+        }
+        Object::Array(array) => {
+            out.push(b'[');
+            for (i, item) in array.iter().enumerate() {
+                if i > 0 && needs_separator(item) {
+                    out.push(b' ');
+                }
+                write_object(out, item)?;
+            }
+            out.push(b']');
+        }
+        Object::Dictionary(dict) => write_dictionary(out, dict)?,
+        Object::Stream(stream) => {
+            write_dictionary(out, &stream.dict)?;
+            out.extend_from_slice(b"stream\n");
+            out.extend_from_slice(&stream.content);
+            out.extend_from_slice(b"\nendstream");
+        }
+        Object::Reference((id, generation)) => write!(out, "{} {} R", id, generation)?,
+    }
+    Ok(())
+}
+
+/// Tulis dictionary: <</Key value ...>>
+fn write_dictionary(out: &mut Vec<u8>, dict: &Dictionary) -> Result<()> {
+    out.extend_from_slice(b"<<");
+    for (key, value) in dict.iter() {
+        write_name(out, key);
+        if needs_separator(value) {
+            out.push(b' ');
+        }
+        write_object(out, value)?;
+    }
+    out.extend_from_slice(b">>");
+    Ok(())
+}
+
+/// Tulis name; whitespace, delimiter, dan byte di luar ASCII cetak ditulis sebagai #xx
+fn write_name(out: &mut Vec<u8>, name: &[u8]) {
+    out.push(b'/');
+    for &byte in name {
+        if b" \t\n\r\x0C()<>[]{}/%#".contains(&byte) || !(33..=126).contains(&byte) {
+            out.extend_from_slice(format!("#{:02X}", byte).as_bytes());
+        } else {
+            out.push(byte);
+        }
+    }
+}
+
+/// Object yang harus dipisah spasi dari token sebelumnya
+fn needs_separator(object: &Object) -> bool {
+    matches!(
+        object,
+        Object::Null | Object::Boolean(_) | Object::Integer(_) | Object::Real(_) | Object::Reference(_)
+    )
+}
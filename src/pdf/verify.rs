@@ -0,0 +1,1226 @@
+// Import library yang diperlukan
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use lopdf::{Document, Object}; // Untuk membaca struktur PDF
+use std::fs; // Untuk membaca file
+use std::sync::atomic::{AtomicUsize, Ordering}; // Jumlah worker dan signature berikutnya
+use std::sync::Mutex; // Laporan dari worker
+use x509_cert::der::{Decode, Encode}; // Parsing DER untuk sertifikat X.509
+
+use crate::crypto::cms; // Parsing CMS SignedData
+pub use crate::crypto::cms::der_element_len; // Panjang elemen DER di /Contents (dipakai juga oleh add-ltv)
+use crate::crypto::digest::DigestAlgorithm; // Digest ByteRange dan timestamp
+use crate::crypto::policy::{AlgorithmPolicy, Violation, WeakAction}; // Deny-list algoritma lemah
+use crate::crypto::revocation::{self, RevocationData, RevocationMode, RevocationSource, RevocationStatus}; // Status OCSP/CRL (--check-revocation)
+use crate::crypto::signer::{self, PublicKey}; // Verifikasi ECDSA / RSA
+use crate::crypto::trust::{self, ChainIssue, Purpose, TrustAnchor, TrustSource}; // Sumber trust anchor (--trust) dan validasi rantai
+use crate::crypto::tsa; // Timestamp RFC 3161
+use crate::error::Error; // Dokumen tanpa signature
+use crate::pdf::attachments::{self, AttachmentReport}; // Integritas file lampiran
+use crate::pdf::checks::{SignatureData, VerificationCheck}; // Pemeriksaan tambahan milik embedder
+use crate::pdf::appearance::AppearanceLanguage; // Bahasa pesan laporan
+use crate::pdf::form; // Pencarian signature field
+use crate::pdf::syntax::{self, ParseMode}; // Pemeriksaan struktur file (--strict-parse)
+use crate::pdf::text; // Text string PDF (/Name, /Reason, ...)
+
+/// Hasil verifikasi satu file PDF (`verify_pdf`)
+pub struct Verification {
+    pub signatures: Vec<SignatureReport>,   // Laporan per signature
+    pub attachments: Vec<AttachmentReport>, // Laporan per file lampiran (embedded file)
+    pub revision: Option<Revision>,         // Revisi yang diverifikasi (`verify_revision_with_checks`); None = seluruh file
+}
+
+/// Revisi file yang diverifikasi: file asli (1), lalu setiap incremental update
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Revision {
+    pub number: usize, // Nomor revisi, mulai dari 1
+    pub count: usize,  // Jumlah revisi di seluruh file
+    pub end: usize,    // Panjang file sampai akhir revisi ini (bytes)
+}
+
+/// Hasil verifikasi untuk satu signature di dalam dokumen
+pub struct SignatureReport {
+    pub field: String,                 // Nama signature field
+    pub signer: Option<String>,        // /Name dari signature dictionary
+    pub signing_time: Option<String>,  // /M dari signature dictionary
+    pub sub_filter: Option<String>,    // /SubFilter (format signature)
+    pub byte_range: Vec<i64>,          // /ByteRange yang ditandatangani
+    pub covers_whole_document: bool,   // Apakah ByteRange mencakup sampai akhir file
+    pub timestamp: Option<Timestamp>,  // Waktu dari timestamp RFC 3161, UTC (jika ada)
+    pub status: SignatureStatus,       // Hasil verifikasi
+    pub trust: Vec<TrustAnchor>,       // Sumber trust yang menjadi ujung rantai sertifikat
+    pub findings: Vec<Finding>,        // Temuan (error, peringatan, info) beserta alasannya
+}
+
+/// Status verifikasi satu signature
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Digest cocok dan signature valid terhadap kunci publik
+    Valid,
+    /// Signature tidak valid (alasannya ada di temuan berseverity error)
+    Invalid,
+    /// Signature tidak bisa diperiksa (misalnya tidak ada kunci publik)
+    Unverifiable,
+}
+
+/// Tingkat keparahan temuan verifikasi
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Signature gagal atau tidak bisa diverifikasi
+    Error,
+    /// Signature valid, tetapi ada hal yang perlu diperhatikan
+    Warning,
+    /// Keterangan tambahan
+    Info,
+}
+
+impl Severity {
+    /// Label severity dalam bahasa tertentu
+    pub fn label(self, lang: AppearanceLanguage) -> &'static str {
+        match (self, lang) {
+            (Severity::Error, AppearanceLanguage::En) => "Error",
+            (Severity::Error, AppearanceLanguage::Id) => "Galat",
+            (Severity::Warning, AppearanceLanguage::En) => "Warning",
+            (Severity::Warning, AppearanceLanguage::Id) => "Peringatan",
+            (Severity::Info, _) => "Info",
+        }
+    }
+}
+
+/// Satu temuan saat memverifikasi signature
+///
+/// Setiap temuan punya severity tetap (kecuali algoritma lemah, yang mengikuti
+/// kebijakan) dan pesan yang bisa ditampilkan dalam bahasa Inggris atau
+/// Indonesia (lihat `message`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Finding {
+    /// messageDigest tidak sama dengan digest ByteRange
+    DigestMismatch,
+    /// Signature tidak cocok dengan bytes yang ditandatangani / kuncinya
+    SignatureMismatch,
+    /// Timestamp dibuat untuk nilai signature lain
+    TimestampMismatch,
+    /// Timestamp tidak bisa dibaca (detail dari parser)
+    BadTimestamp(String),
+    /// Document timestamp tidak sama dengan digest ByteRange
+    DocumentTimestampMismatch,
+    /// Signature TSA tidak mencakup TSTInfo
+    TsaSignatureUncovered,
+    /// Signature TSA tidak valid
+    TsaSignatureInvalid,
+    /// Signature TSA tidak bisa diperiksa (detail dari parser)
+    TsaSignatureUnchecked(String),
+    /// Document timestamp tanpa sertifikat TSA
+    NoTsaCertificate,
+    /// Tidak ada sertifikat maupun --public-key
+    NoCertificate,
+    /// Signature tidak bisa dibaca (detail dari parser)
+    Malformed(String),
+    /// Ada incremental update setelah signature ini
+    NotWholeDocument,
+    /// Format blob lama buatan pdfsign (bukan CMS)
+    LegacyFormat,
+    /// Tidak ada timestamp terpercaya; waktu hanya dari jam penandatangan
+    NoTimestamp,
+    /// Algoritma dari deny-list; `fatal` jika kebijakan verify-nya fail
+    WeakAlgorithm { description: String, fatal: bool },
+    /// Rantai sertifikat tidak berujung di sumber trust mana pun (label sumber)
+    Untrusted(Vec<String>),
+    /// Tidak ada sumber trust anchor (--trust kosong), sehingga rantai
+    /// sertifikat tidak diperiksa sampai ke root yang dipercaya
+    NoTrustAnchor,
+    /// Masalah pada rantai sertifikat penandatangan (lihat `trust::validate_chain`)
+    Chain(ChainIssue),
+    /// Sertifikat di rantai tidak dicabut (--check-revocation)
+    NotRevoked { subject: String, source: RevocationSource },
+    /// Sertifikat di rantai dicabut; `after_signing` jika pencabutannya setelah waktu timestamp
+    Revoked { subject: String, source: RevocationSource, revoked_at: std::time::SystemTime, after_signing: bool },
+    /// Status revocation sertifikat tidak diketahui (detail kegagalan, jika ada)
+    RevocationUnknown { subject: String, detail: Option<String> },
+    /// Temuan dari pemeriksaan tambahan (`checks::VerificationCheck`) bernama `check`
+    Custom { check: String, severity: Severity, message: String },
+}
+
+impl Finding {
+    /// Severity temuan
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::NotWholeDocument | Finding::LegacyFormat | Finding::Untrusted(_) | Finding::NoTrustAnchor => Severity::Warning,
+            Finding::Chain(ChainIssue::MissingIssuer { .. }) => Severity::Warning,
+            Finding::NoTimestamp | Finding::NotRevoked { .. } => Severity::Info,
+            Finding::Revoked { after_signing: true, .. } | Finding::RevocationUnknown { .. } => Severity::Warning,
+            Finding::WeakAlgorithm { fatal: false, .. } => Severity::Warning,
+            Finding::Custom { severity, .. } => *severity,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Pesan temuan untuk pengguna
+    pub fn message(&self, lang: AppearanceLanguage) -> String {
+        let id = lang == AppearanceLanguage::Id;
+        match self {
+            Finding::DigestMismatch if id => "message digest tidak cocok dengan bytes yang ditandatangani (dokumen diubah)".to_string(),
+            Finding::DigestMismatch => "message digest does not match the signed bytes (document modified)".to_string(),
+            Finding::SignatureMismatch if id => "signature tidak cocok dengan bytes yang ditandatangani (dokumen diubah atau kunci salah)".to_string(),
+            Finding::SignatureMismatch => "signature does not match the signed bytes (document modified or wrong key)".to_string(),
+            Finding::TimestampMismatch if id => "timestamp dibuat untuk signature lain".to_string(),
+            Finding::TimestampMismatch => "timestamp was issued for a different signature".to_string(),
+            Finding::BadTimestamp(detail) if id => format!("timestamp tidak valid: {}", detail),
+            Finding::BadTimestamp(detail) => format!("bad timestamp: {}", detail),
+            Finding::DocumentTimestampMismatch if id => "document timestamp tidak cocok dengan bytes yang ditandatangani (dokumen diubah)".to_string(),
+            Finding::DocumentTimestampMismatch => "document timestamp does not match the signed bytes (document modified)".to_string(),
+            Finding::TsaSignatureUncovered if id => "signature TSA tidak mencakup data timestamp".to_string(),
+            Finding::TsaSignatureUncovered => "TSA signature does not cover the timestamp data".to_string(),
+            Finding::TsaSignatureInvalid if id => "signature TSA tidak valid".to_string(),
+            Finding::TsaSignatureInvalid => "TSA signature is not valid".to_string(),
+            Finding::TsaSignatureUnchecked(detail) if id => format!("signature TSA tidak bisa diperiksa: {}", detail),
+            Finding::TsaSignatureUnchecked(detail) => format!("cannot check the TSA signature: {}", detail),
+            Finding::NoTsaCertificate if id => "document timestamp tidak berisi sertifikat TSA".to_string(),
+            Finding::NoTsaCertificate => "document timestamp has no TSA certificate".to_string(),
+            Finding::NoCertificate if id => "tidak ada sertifikat yang tertanam; gunakan --public-key".to_string(),
+            Finding::NoCertificate => "no embedded certificate; pass --public-key".to_string(),
+            Finding::Malformed(detail) if id => format!("signature tidak bisa dibaca: {}", detail),
+            Finding::Malformed(detail) => format!("cannot read signature: {}", detail),
+            Finding::NotWholeDocument if id => "dokumen punya perubahan (incremental update) setelah signature ini".to_string(),
+            Finding::NotWholeDocument => "the document has incremental updates after this signature".to_string(),
+            Finding::LegacyFormat if id => "format signature lama pdfsign (bukan CMS); validator lain tidak bisa membacanya".to_string(),
+            Finding::LegacyFormat => "legacy pdfsign signature format (not CMS); other validators cannot read it".to_string(),
+            Finding::NoTimestamp if id => "tidak ada timestamp terpercaya; waktu penandatanganan hanya dari jam penandatangan".to_string(),
+            Finding::NoTimestamp => "no trusted timestamp; the signing time comes from the signer's clock".to_string(),
+            Finding::WeakAlgorithm { description, .. } if id => format!("algoritma lemah: {}", description),
+            Finding::WeakAlgorithm { description, .. } => format!("weak algorithm: {}", description),
+            Finding::Untrusted(sources) if id => format!("rantai sertifikat tidak berujung di sumber trust mana pun ({})", sources.join(", ")),
+            Finding::Untrusted(sources) => format!("certificate chain is not anchored in any trust source ({})", sources.join(", ")),
+            Finding::NoTrustAnchor if id => "tidak ada trust anchor yang dikonfigurasi; rantai sertifikat tidak diperiksa (gunakan --trust, misalnya --trust system)".to_string(),
+            Finding::NoTrustAnchor => "no trust anchor configured; the certificate chain was not checked (pass --trust, e.g. --trust system)".to_string(),
+            Finding::Chain(issue) => chain_message(issue, id),
+            Finding::NotRevoked { subject, source } if id => format!("sertifikat {} tidak dicabut ({})", subject, source.label()),
+            Finding::NotRevoked { subject, source } => format!("certificate {} is not revoked ({})", subject, source.label()),
+            Finding::Revoked { subject, source, revoked_at, after_signing: true } if id => {
+                format!("sertifikat {} dicabut pada {}, setelah waktu penandatanganan ({})", subject, format_time(revoked_at), source.label())
+            }
+            Finding::Revoked { subject, source, revoked_at, after_signing: true } => {
+                format!("certificate {} was revoked on {}, after the signing time ({})", subject, format_time(revoked_at), source.label())
+            }
+            Finding::Revoked { subject, source, revoked_at, .. } if id => {
+                format!("sertifikat {} dicabut pada {} ({})", subject, format_time(revoked_at), source.label())
+            }
+            Finding::Revoked { subject, source, revoked_at, .. } => {
+                format!("certificate {} was revoked on {} ({})", subject, format_time(revoked_at), source.label())
+            }
+            Finding::RevocationUnknown { subject, detail: Some(detail) } if id => format!("status revocation sertifikat {} tidak diketahui: {}", subject, detail),
+            Finding::RevocationUnknown { subject, detail: Some(detail) } => format!("revocation status of certificate {} is unknown: {}", subject, detail),
+            Finding::RevocationUnknown { subject, detail: None } if id => {
+                format!("status revocation sertifikat {} tidak diketahui (tidak ada respons OCSP atau CRL untuknya)", subject)
+            }
+            Finding::RevocationUnknown { subject, detail: None } => {
+                format!("revocation status of certificate {} is unknown (no OCSP response or CRL covers it)", subject)
+            }
+            // Pesan pemeriksaan tambahan tidak diterjemahkan
+            Finding::Custom { check, message, .. } => format!("{}: {}", check, message),
+        }
+    }
+}
+
+/// Waktu dari timestamp RFC 3161 (genTime, UTC)
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// Waktu untuk pesan temuan (UTC)
+fn format_time(time: &std::time::SystemTime) -> String {
+    text::human_time(*time)
+}
+
+/// Pesan untuk masalah rantai sertifikat
+fn chain_message(issue: &ChainIssue, id: bool) -> String {
+    let date = format_time;
+    match issue {
+        ChainIssue::MissingIssuer { subject, issuer } if id => {
+            format!("rantai sertifikat tidak lengkap: penerbit sertifikat {} ({}) tidak tertanam dan tidak ada di sumber trust", subject, issuer)
+        }
+        ChainIssue::MissingIssuer { subject, issuer } => {
+            format!("incomplete certificate chain: the issuer of {} ({}) is neither embedded nor in a trust source", subject, issuer)
+        }
+        ChainIssue::BadSignature { subject } if id => format!("sertifikat {} tidak ditandatangani oleh issuer-nya", subject),
+        ChainIssue::BadSignature { subject } => format!("certificate {} is not signed by its issuer", subject),
+        ChainIssue::NotYetValid { subject, not_before } if id => {
+            format!("sertifikat {} belum berlaku pada waktu penandatanganan (berlaku mulai {})", subject, date(not_before))
+        }
+        ChainIssue::NotYetValid { subject, not_before } => {
+            format!("certificate {} was not yet valid at signing time (valid from {})", subject, date(not_before))
+        }
+        ChainIssue::Expired { subject, not_after } if id => {
+            format!("sertifikat {} sudah habis masa berlakunya pada waktu penandatanganan (berlaku sampai {})", subject, date(not_after))
+        }
+        ChainIssue::Expired { subject, not_after } => {
+            format!("certificate {} had expired at signing time (valid until {})", subject, date(not_after))
+        }
+        ChainIssue::KeyUsage { subject } if id => {
+            format!("key usage sertifikat {} tidak mengizinkan tanda tangan (digitalSignature / nonRepudiation)", subject)
+        }
+        ChainIssue::KeyUsage { subject } => {
+            format!("key usage of certificate {} does not allow signing (digitalSignature / nonRepudiation)", subject)
+        }
+        ChainIssue::ExtendedKeyUsage { subject, purpose: Purpose::DocumentSigning } if id => {
+            format!("extended key usage sertifikat {} tidak mencakup tanda tangan dokumen", subject)
+        }
+        ChainIssue::ExtendedKeyUsage { subject, purpose: Purpose::DocumentSigning } => {
+            format!("extended key usage of certificate {} does not cover document signing", subject)
+        }
+        ChainIssue::ExtendedKeyUsage { subject, purpose: Purpose::Timestamping } if id => {
+            format!("sertifikat TSA {} tidak punya extended key usage timeStamping", subject)
+        }
+        ChainIssue::ExtendedKeyUsage { subject, purpose: Purpose::Timestamping } => {
+            format!("TSA certificate {} lacks the timeStamping extended key usage", subject)
+        }
+        ChainIssue::NotCa { subject } if id => format!("sertifikat {} menerbitkan sertifikat lain, tetapi bukan CA", subject),
+        ChainIssue::NotCa { subject } => format!("certificate {} issues other certificates but is not a CA", subject),
+    }
+}
+
+/// Data yang dicakup nilai signature
+enum SignedContent {
+    Digest(Vec<u8>),                   // Digest dokumen (signature tanpa signed attributes)
+    Message(DigestAlgorithm, Vec<u8>), // DER signed attributes beserta digestAlgorithm-nya
+}
+
+/// Hasil pemeriksaan kriptografis satu signature
+struct Check {
+    status: SignatureStatus,    // Hasil verifikasi
+    findings: Vec<Finding>,     // Alasan kegagalan dan temuan lain
+    timestamp: Option<Timestamp>, // Waktu timestamp RFC 3161 (jika ada)
+}
+
+impl Check {
+    /// Signature valid
+    fn valid(timestamp: Option<Timestamp>) -> Check {
+        Check { status: SignatureStatus::Valid, findings: Vec::new(), timestamp }
+    }
+
+    /// Signature tidak valid karena `finding`
+    fn invalid(finding: Finding, timestamp: Option<Timestamp>) -> Check {
+        Check { status: SignatureStatus::Invalid, findings: vec![finding], timestamp }
+    }
+
+    /// Signature tidak bisa diperiksa karena `finding`
+    fn unverifiable(finding: Finding, timestamp: Option<Timestamp>) -> Check {
+        Check { status: SignatureStatus::Unverifiable, findings: vec![finding], timestamp }
+    }
+
+    /// Tambahkan pelanggaran kebijakan algoritma sebagai temuan; dengan aksi
+    /// fail, signature yang valid menjadi tidak valid
+    fn apply_policy(&mut self, violations: Vec<Violation>, policy: &AlgorithmPolicy) {
+        let fatal = policy.verify_action == WeakAction::Fail;
+        if fatal && !violations.is_empty() && self.status == SignatureStatus::Valid {
+            self.status = SignatureStatus::Invalid;
+        }
+        self.findings
+            .extend(violations.into_iter().map(|v| Finding::WeakAlgorithm { description: v.description, fatal }));
+    }
+}
+
+/// Jumlah signature yang diverifikasi bersamaan dalam satu dokumen; 0 = jumlah CPU
+static PARALLELISM: AtomicUsize = AtomicUsize::new(0);
+
+/// Atur berapa signature satu dokumen yang diverifikasi bersamaan (rantai
+/// sertifikat, status revocation, dan timestamp per signature)
+///
+/// 0 = jumlah CPU (default), 1 = satu per satu tanpa thread tambahan.
+/// Berlaku untuk semua verifikasi berikutnya dalam proses ini.
+pub fn set_parallelism(workers: usize) {
+    PARALLELISM.store(workers, Ordering::Relaxed);
+}
+
+/// Jumlah worker untuk `signatures` signature
+fn workers_for(signatures: usize) -> usize {
+    let workers = match PARALLELISM.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, usize::from),
+        workers => workers,
+    };
+    workers.clamp(1, signatures.max(1))
+}
+
+/// Fungsi utama untuk memverifikasi semua signature dalam file PDF
+///
+/// Parameter:
+///   - input: path file PDF yang akan diverifikasi
+///   - public_key_path: path kunci publik (public.key); jika None, dipakai
+///     sertifikat yang tertanam di signature
+///   - policy: deny-list algoritma lemah dan apakah pelanggarannya gagal atau peringatan
+///   - trust: sumber trust anchor; jika kosong rantai tidak diperiksa dan setiap
+///     signature mendapat temuan `Finding::NoTrustAnchor`
+///   - parse: Strict menolak dokumen yang melanggar spesifikasi PDF; Lenient
+///     memperbaikinya dengan peringatan
+///   - revocation: periksa status OCSP/CRL setiap sertifikat di rantai (None = tidak diperiksa)
+///
+/// Return: laporan per signature dan per file lampiran
+pub fn verify_pdf(
+    input: &str,
+    public_key_path: Option<&str>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    parse: ParseMode,
+    revocation: Option<RevocationMode>,
+) -> Result<Verification> {
+    verify_pdf_with_checks(input, public_key_path, policy, trust, parse, revocation, &[])
+}
+
+/// Sama seperti `verify_pdf`, tetapi menjalankan pemeriksaan tambahan untuk
+/// setiap signature
+///
+/// Parameter tambahan:
+///   - checks: pemeriksaan milik embedder (lihat pdf::checks); temuannya masuk
+///     ke laporan signature
+pub fn verify_pdf_with_checks(
+    input: &str,
+    public_key_path: Option<&str>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    parse: ParseMode,
+    revocation: Option<RevocationMode>,
+    checks: &[&dyn VerificationCheck],
+) -> Result<Verification> {
+    // Baca file PDF apa adanya: ByteRange merujuk ke bytes mentah di file
+    let pdf_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+
+    // Kunci publik dari file (opsional)
+    let public_key = match public_key_path {
+        Some(path) => Some(fs::read(path).with_context(|| format!("cannot read public key {}", path))?),
+        None => None,
+    };
+    verify_memory_with_checks(input, &pdf_bytes, public_key.as_deref(), policy, trust, parse, revocation, checks)
+}
+
+/// Sama seperti `verify_pdf_with_checks`, untuk PDF yang sudah ada di memori
+/// (misalnya dokumen yang diunggah ke `pdfsign serve`)
+///
+/// Parameter:
+///   - name: nama dokumen untuk pesan error
+///   - pdf_bytes: isi file PDF apa adanya
+///   - public_key: isi file kunci publik; jika None, dipakai sertifikat yang
+///     tertanam di signature
+///   - policy, trust, parse, revocation, checks: sama seperti `verify_pdf_with_checks`
+#[allow(clippy::too_many_arguments)]
+pub fn verify_memory_with_checks(
+    name: &str,
+    pdf_bytes: &[u8],
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    parse: ParseMode,
+    revocation: Option<RevocationMode>,
+    checks: &[&dyn VerificationCheck],
+) -> Result<Verification> {
+    let mut doc = Document::load_mem(pdf_bytes)?;
+    syntax::warn(&syntax::check_document(pdf_bytes, &mut doc, parse)?);
+    let signatures = verify_document_with_checks(&doc, pdf_bytes, public_key, policy, trust, revocation, checks);
+    if signatures.is_empty() {
+        return Err(Error::NoSignatures(name.to_string()).into());
+    }
+    let attachments = attachments::check_attachments(&doc, &signatures);
+    Ok(Verification { signatures, attachments, revision: None })
+}
+
+/// Verifikasi dokumen seperti pada revisi tertentu (verify --revision): file
+/// dipotong di akhir revisi itu, sehingga hanya signature yang sudah ada saat
+/// itu yang diperiksa, dan signature terakhir di revisi tersebut harus mencakup
+/// seluruh isinya
+///
+/// Dipakai untuk menjawab "apa yang sebenarnya dilihat penandatangan kedua":
+/// laporan dan lampiran berasal dari dokumen seperti saat revisi itu selesai.
+/// Akhir revisi mengikuti ByteRange signature jika ada signature yang berakhir
+/// tepat di penanda %%EOF-nya (lihat `syntax::revision_ends`).
+///
+/// Parameter:
+///   - revision: nomor revisi, mulai dari 1 (file asli)
+///   - input, public_key_path, policy, trust, parse, revocation, checks: sama
+///     seperti `verify_pdf_with_checks`
+#[allow(clippy::too_many_arguments)]
+pub fn verify_revision_with_checks(
+    input: &str,
+    revision: usize,
+    public_key_path: Option<&str>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    parse: ParseMode,
+    revocation: Option<RevocationMode>,
+    checks: &[&dyn VerificationCheck],
+) -> Result<Verification> {
+    let pdf_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let public_key = match public_key_path {
+        Some(path) => Some(fs::read(path).with_context(|| format!("cannot read public key {}", path))?),
+        None => None,
+    };
+    let ends = syntax::revision_ends(&pdf_bytes);
+    if revision == 0 || revision > ends.len() {
+        bail!("{} has {} revision(s); choose a revision from 1 to {}", input, ends.len(), ends.len());
+    }
+
+    // ByteRange signature menentukan apakah baris baru setelah %%EOF termasuk revisinya
+    let (exact, with_eol) = ends[revision - 1];
+    let signed_ends: Vec<usize> = Document::load_mem(&pdf_bytes)
+        .map(|doc| {
+            form::signature_fields(&doc)
+                .iter()
+                .filter_map(|field| {
+                    let range = field.value.get(b"ByteRange").and_then(Object::as_array).ok()?;
+                    let (start, length) = (range.get(range.len().checked_sub(2)?)?.as_i64().ok()?, range.last()?.as_i64().ok()?);
+                    usize::try_from(start + length).ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let end = if signed_ends.contains(&exact) { exact } else { with_eol };
+
+    let name = format!("{} (revision {})", input, revision);
+    let mut verification = verify_memory_with_checks(&name, &pdf_bytes[..end], public_key.as_deref(), policy, trust, parse, revocation, checks)?;
+    verification.revision = Some(Revision { number: revision, count: ends.len(), end });
+    Ok(verification)
+}
+
+/// Verifikasi semua signature dalam PDF yang sudah ada di memori (tanpa akses
+/// file, untuk build verify-only seperti WASM dan aplikasi mobile)
+///
+/// Parameter:
+///   - pdf_bytes: isi file PDF apa adanya
+///   - public_key: isi file kunci publik; jika None, dipakai sertifikat yang
+///     tertanam di signature
+///   - policy: deny-list algoritma lemah dan apakah pelanggarannya gagal atau peringatan
+///
+/// Return: laporan per signature (kosong jika PDF tidak punya signature)
+pub fn verify_bytes(pdf_bytes: &[u8], public_key: Option<&[u8]>, policy: &AlgorithmPolicy) -> Result<Vec<SignatureReport>> {
+    let mut doc = Document::load_mem(pdf_bytes)?;
+    syntax::warn(&syntax::check_document(pdf_bytes, &mut doc, ParseMode::Lenient)?);
+    Ok(verify_document(&doc, pdf_bytes, public_key, policy, &[], None))
+}
+
+/// Verifikasi signature CMS detached (file .p7s dari `sign --detached`) atas
+/// seluruh isi file
+///
+/// Signature diperiksa seperti signature di dalam PDF yang ByteRange-nya
+/// mencakup seluruh file: messageDigest, nilai signature, timestamp, rantai
+/// sertifikat, trust, dan (jika diminta) status revocation secara online. File
+/// tidak perlu diurai sebagai PDF; satu byte yang berubah membuat signature
+/// tidak valid.
+///
+/// Parameter:
+///   - input: path file yang ditandatangani
+///   - detached: path file .p7s (CMS SignedData DER)
+///   - public_key_path, policy, trust: sama seperti `verify_pdf`
+///   - revocation: mode pemeriksaan status OCSP/CRL (None = tidak diperiksa;
+///     tanpa /DSS, mode offline selalu "tidak diketahui")
+///
+/// Return: laporan dengan satu signature (bernama sesuai file .p7s), tanpa lampiran
+pub fn verify_detached(
+    input: &str,
+    detached: &str,
+    public_key_path: Option<&str>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    revocation: Option<RevocationMode>,
+) -> Result<Verification> {
+    let file_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let signature = fs::read(detached).with_context(|| format!("cannot read {}", detached))?;
+    let public_key = match public_key_path {
+        Some(path) => Some(fs::read(path).with_context(|| format!("cannot read public key {}", path))?),
+        None => None,
+    };
+    if der_element_len(&signature).is_none_or(|len| cms::parse_signed_data(&signature[..len]).is_err()) {
+        bail!("{} is not a CMS SignedData signature (DER)", detached);
+    }
+
+    // Signature dictionary buatan: ByteRange mencakup seluruh file
+    let mut sig = lopdf::Dictionary::new();
+    sig.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
+    sig.set("SubFilter", Object::Name(b"adbe.pkcs7.detached".to_vec()));
+    sig.set("Contents", Object::String(signature, lopdf::StringFormat::Hexadecimal));
+    sig.set("ByteRange", Object::Array(vec![0.into(), (file_bytes.len() as i64).into()]));
+    let name = std::path::Path::new(detached).file_name().map_or_else(|| detached.to_string(), |name| name.to_string_lossy().into_owned());
+    let mut report = verify_signature_dictionary(name, &sig, &file_bytes, public_key.as_deref(), policy, trust, revocation, &[], &RevocationData::default());
+    // Tidak ada /Name di luar PDF: penandatangan diambil dari subject sertifikatnya
+    report.signer = signature_chain(&sig)
+        .and_then(|(leaf, _)| x509_cert::Certificate::from_der(&leaf).ok())
+        .map(|cert| cert.tbs_certificate.subject.to_string());
+    Ok(Verification { signatures: vec![report], attachments: Vec::new(), revision: None })
+}
+
+/// Verifikasi semua signature dalam dokumen yang sudah dimuat (dipakai ulang
+/// oleh pemanggil yang juga memeriksa bagian lain dokumen, misalnya /DSS)
+///
+/// Parameter:
+///   - doc: dokumen hasil `Document::load_mem(pdf_bytes)`
+///   - pdf_bytes: isi file PDF apa adanya (ByteRange merujuk ke bytes ini)
+///   - public_key, policy: sama seperti `verify_bytes`
+///   - trust: sumber trust anchor; jika tidak kosong, sumber yang menjadi ujung
+///     rantai setiap signature dicatat di laporan, jika kosong setiap signature
+///     mendapat temuan `Finding::NoTrustAnchor`
+///   - revocation: mode pemeriksaan status OCSP/CRL (None = tidak diperiksa)
+///
+/// Return: laporan per signature, urut seperti `form::signature_fields`
+pub fn verify_document(
+    doc: &Document,
+    pdf_bytes: &[u8],
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    revocation: Option<RevocationMode>,
+) -> Vec<SignatureReport> {
+    verify_document_with_checks(doc, pdf_bytes, public_key, policy, trust, revocation, &[])
+}
+
+/// Sama seperti `verify_document`, tetapi menjalankan pemeriksaan tambahan
+/// untuk setiap signature (juga untuk PDF di memori, tanpa akses file)
+///
+/// Parameter tambahan:
+///   - checks: pemeriksaan milik embedder (lihat pdf::checks)
+pub fn verify_document_with_checks(
+    doc: &Document,
+    pdf_bytes: &[u8],
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    revocation: Option<RevocationMode>,
+    checks: &[&dyn VerificationCheck],
+) -> Vec<SignatureReport> {
+    // Sertifikat dan respons OCSP/CRL di /DSS, dipakai bersama oleh semua signature
+    let (dss_certificates, dss_revocation) = match revocation {
+        Some(_) => dss_contents(doc),
+        None => Default::default(),
+    };
+
+    // Verifikasi setiap signature field; dokumen dengan banyak signature
+    // diperiksa bersamaan oleh beberapa worker (lihat `set_parallelism`)
+    let fields = form::signature_fields(doc);
+    let verify = |field: &form::SignatureField| {
+        verify_signature_dictionary(field.name.clone(), &field.value, pdf_bytes, public_key, policy, trust, revocation, &dss_certificates, &dss_revocation)
+    };
+    let workers = workers_for(fields.len());
+    let mut reports: Vec<SignatureReport> = if workers == 1 {
+        fields.iter().map(verify).collect()
+    } else {
+        let next = AtomicUsize::new(0);
+        let reports = Mutex::new(Vec::with_capacity(fields.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(field) = fields.get(index) else {
+                        break;
+                    };
+                    let report = verify(field);
+                    reports.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push((index, report));
+                });
+            }
+        });
+        let mut reports = reports.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        reports.sort_by_key(|(index, _)| *index);
+        reports.into_iter().map(|(_, report)| report).collect()
+    };
+
+    // Pemeriksaan milik embedder berjalan berurutan di thread pemanggil,
+    // sehingga tidak perlu aman antar thread
+    if !checks.is_empty() {
+        for (report, field) in reports.iter_mut().zip(&fields) {
+            run_checks(report, &field.value, signature_chain(&field.value).as_ref(), checks);
+        }
+    }
+    reports
+}
+
+/// Verifikasi satu signature dictionary beserta rantai sertifikat, status
+/// revocation, dan sumber trust-nya (dipakai juga untuk signature detached)
+///
+/// Parameter:
+///   - name: nama field (atau file .p7s) untuk laporan
+///   - sig: signature dictionary (/Contents, /ByteRange, /SubFilter, ...)
+///   - dss_certificates, dss_revocation: isi /DSS dokumen (kosong jika tidak dipakai)
+#[allow(clippy::too_many_arguments)]
+fn verify_signature_dictionary(
+    name: String,
+    sig: &lopdf::Dictionary,
+    pdf_bytes: &[u8],
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    trust: &[TrustSource],
+    revocation: Option<RevocationMode>,
+    dss_certificates: &[Vec<u8>],
+    dss_revocation: &RevocationData,
+) -> SignatureReport {
+    let text = |key: &[u8]| sig.get(key).and_then(Object::as_str).ok().map(text::decode_text_string);
+    let byte_range = sig
+        .get(b"ByteRange")
+        .and_then(Object::as_array)
+        .map(|range| range.iter().filter_map(|v| v.as_i64().ok()).collect::<Vec<i64>>())
+        .unwrap_or_default();
+    // Segmen terakhir harus berakhir di akhir file (signature detached: satu segmen)
+    let covers_whole_document = match byte_range[..] {
+        [.., start, length] if byte_range.len().is_multiple_of(2) => start + length == pdf_bytes.len() as i64,
+        _ => false,
+    };
+
+    let mut violations = Vec::new();
+    let mut check = match verify_signature(pdf_bytes, sig, &byte_range, public_key, policy, &mut violations) {
+        Ok(check) => check,
+        Err(err) => Check::invalid(Finding::Malformed(err.to_string()), None),
+    };
+    check.apply_policy(violations, policy);
+    if !covers_whole_document {
+        check.findings.push(Finding::NotWholeDocument);
+    }
+    // Rantai sertifikat: masa berlaku pada waktu penandatanganan (timestamp,
+    // atau /M jika tidak ada; tanpa keduanya tidak diperiksa), key usage, dan peran CA issuer
+    let chain = signature_chain(sig);
+    let purpose = match sig.get(b"SubFilter").and_then(Object::as_name).ok() {
+        Some(b"ETSI.RFC3161") => Purpose::Timestamping,
+        _ => Purpose::DocumentSigning,
+    };
+    let signed_at = check
+        .timestamp
+        .or_else(|| text(b"M").as_deref().and_then(parse_pdf_date))
+        .map(std::time::SystemTime::from);
+    let mut incomplete = false;
+    if let Some((signer, certificates)) = &chain {
+        for issue in trust::validate_chain(trust, signer, certificates, signed_at, purpose) {
+            match issue {
+                // Issuer yang hilang hanya berarti jika rantai harus berujung di sumber trust
+                ChainIssue::MissingIssuer { .. } if trust.is_empty() => {}
+                ChainIssue::MissingIssuer { .. } => {
+                    incomplete = true;
+                    check.findings.push(Finding::Chain(issue));
+                }
+                issue => {
+                    if check.status == SignatureStatus::Valid {
+                        check.status = SignatureStatus::Invalid;
+                    }
+                    check.findings.push(Finding::Chain(issue));
+                }
+            }
+        }
+    }
+    // Status revocation setiap sertifikat di rantai, kecuali root dan trust anchor;
+    // issuer boleh berasal dari sertifikat di /DSS
+    if let (Some(mode), Some((signer, certificates))) = (revocation, &chain) {
+        let known: Vec<Vec<u8>> = certificates.iter().chain(dss_certificates).cloned().collect();
+        for (cert, issuer) in trust::issuer_chain(trust, signer, &known) {
+            let subject = cert.tbs_certificate.subject.to_string();
+            let finding = match revocation::check_status(&cert, issuer.as_ref(), dss_revocation, mode) {
+                RevocationStatus::Good(source) => Finding::NotRevoked { subject, source },
+                RevocationStatus::Revoked { source, revoked_at } => {
+                    // Hanya timestamp yang membuktikan signature dibuat sebelum pencabutan;
+                    // /M berasal dari jam penandatangan dan bisa dimundurkan
+                    let after_signing = check.timestamp.is_some_and(|time| revoked_at > std::time::SystemTime::from(time));
+                    Finding::Revoked { subject, source, revoked_at, after_signing }
+                }
+                RevocationStatus::Unknown(detail) => Finding::RevocationUnknown { subject, detail },
+            };
+            if finding.severity() == Severity::Error && check.status == SignatureStatus::Valid {
+                check.status = SignatureStatus::Invalid;
+            }
+            check.findings.push(finding);
+        }
+    }
+    // Sumber trust yang memuat root (atau intermediate) rantai penandatangan
+    let anchors = match &chain {
+        Some((signer, certificates)) if !trust.is_empty() => trust::anchors_for(trust, signer, certificates),
+        _ => Vec::new(),
+    };
+    if !trust.is_empty() && anchors.is_empty() && !incomplete {
+        check.findings.push(Finding::Untrusted(trust.iter().map(|source| source.label.clone()).collect()));
+    }
+    // Tanpa sumber trust, sertifikat self-signed pun lolos; laporkan agar
+    // signature seperti itu tidak terlihat terpercaya. --public-key sudah
+    // menentukan kunci yang dipercaya.
+    if trust.is_empty() && public_key.is_none() && chain.is_some() {
+        check.findings.push(Finding::NoTrustAnchor);
+    }
+    // Urutkan: error dulu, lalu peringatan, lalu info
+    check.findings.sort_by_key(Finding::severity);
+
+    SignatureReport {
+        field: name,
+        signer: text(b"Name"),
+        signing_time: text(b"M"),
+        sub_filter: sig.get(b"SubFilter").and_then(Object::as_name_str).ok().map(|s| s.to_string()),
+        byte_range,
+        covers_whole_document,
+        timestamp: check.timestamp,
+        status: check.status,
+        trust: anchors,
+        findings: check.findings,
+    }
+}
+
+/// Jalankan pemeriksaan tambahan atas laporan yang sudah lengkap dan tambahkan
+/// temuannya; temuan error membuat signature valid menjadi tidak valid,
+/// pemeriksaan yang gagal berjalan membuatnya tidak bisa diperiksa
+fn run_checks(report: &mut SignatureReport, sig: &lopdf::Dictionary, chain: Option<&(Vec<u8>, Vec<Vec<u8>>)>, checks: &[&dyn VerificationCheck]) {
+    let text = |key: &[u8]| sig.get(key).and_then(Object::as_str).ok().map(text::decode_text_string);
+    let mut findings = Vec::new();
+    let (mut invalid, mut failed) = (false, false);
+    {
+        let data = SignatureData {
+            report,
+            dictionary: sig,
+            reason: text(b"Reason"),
+            location: text(b"Location"),
+            contact_info: text(b"ContactInfo"),
+            signer_certificate: chain.map(|(signer, _)| signer.as_slice()),
+            certificates: chain.map_or(&[], |(_, certificates)| certificates.as_slice()),
+        };
+        for check in checks {
+            let name = check.name().to_string();
+            match check.check(&data) {
+                Ok(results) => {
+                    invalid |= results.iter().any(|result| result.severity == Severity::Error);
+                    findings.extend(results.into_iter().map(|result| Finding::Custom { check: name.clone(), severity: result.severity, message: result.message }));
+                }
+                Err(err) => {
+                    failed = true;
+                    findings.push(Finding::Custom { check: name, severity: Severity::Error, message: format!("check failed: {}", err) });
+                }
+            }
+        }
+    }
+    if report.status == SignatureStatus::Valid {
+        if invalid {
+            report.status = SignatureStatus::Invalid;
+        } else if failed {
+            report.status = SignatureStatus::Unverifiable;
+        }
+    }
+    report.findings.extend(findings);
+    report.findings.sort_by_key(Finding::severity);
+}
+
+/// Laporan verifikasi sebagai teks untuk user (ditampilkan oleh CLI)
+///
+/// Parameter:
+///   - reports: hasil `verify_pdf`
+///   - lang: bahasa pesan temuan
+pub fn format_reports(reports: &[SignatureReport], lang: AppearanceLanguage) -> String {
+    let mut out = String::new();
+    for (index, report) in reports.iter().enumerate() {
+        out.push_str(&format!("Signature #{} (field: {})\n", index + 1, report.field));
+        out.push_str(&format!("  Signer:     {}\n", report.signer.as_deref().unwrap_or("-")));
+        out.push_str(&format!("  Signed at:  {}\n", report.signing_time.as_deref().map_or("-".to_string(), text::human_pdf_date)));
+        out.push_str(&format!("  SubFilter:  {}\n", report.sub_filter.as_deref().unwrap_or("-")));
+        out.push_str(&format!("  ByteRange:  {:?} (covers whole document: {})\n", report.byte_range, if report.covers_whole_document { "yes" } else { "no" }));
+        if let Some(timestamp) = &report.timestamp {
+            out.push_str(&format!("  Timestamp:  {}\n", text::human_time(*timestamp)));
+        }
+        for anchor in &report.trust {
+            out.push_str(&format!("  Trusted by: {} ({})\n", anchor.source, anchor.subject));
+        }
+        match report.status {
+            SignatureStatus::Valid => out.push_str("  Status:     VALID\n"),
+            SignatureStatus::Invalid => out.push_str("  Status:     INVALID\n"),
+            SignatureStatus::Unverifiable => out.push_str("  Status:     UNVERIFIED\n"),
+        }
+        for finding in &report.findings {
+            out.push_str(&format!("  {:<11} {}\n", format!("{}:", finding.severity().label(lang)), finding.message(lang)));
+        }
+    }
+    out
+}
+
+/// Hasil pemeriksaan satu signature format lama (sebelum CMS)
+pub struct LegacyReport {
+    pub field: String,                // Nama signature field
+    pub signer: Option<String>,       // /Name dari signature dictionary
+    pub signing_time: Option<String>, // /M dari signature dictionary
+    pub signed_length: Option<i64>,   // Panjang file asli menurut /ByteRange
+    pub status: SignatureStatus,      // Hasil verifikasi
+    pub problem: Option<String>,      // Alasan jika signature tidak valid
+}
+
+/// Verifikasi signature format lama pdfsign (sebelum CMS)
+///
+/// Versi lama menandatangani SHA-256 dari seluruh file input lalu menyimpan
+/// ulang dokumen, sehingga ByteRange di file hasil tidak menunjuk ke bytes
+/// yang ditandatangani. Karena itu file asli (sebelum signing) diperlukan.
+///
+/// Parameter:
+///   - input: path file PDF yang ditandatangani versi lama
+///   - original: path file PDF asli yang ditandatangani
+///   - public_key_path: path file kunci publik (titik P-256 mentah, DER, atau PEM)
+///
+/// Return: laporan per signature format lama (signature CMS dilewati)
+pub fn verify_legacy(input: &str, original: &str, public_key_path: &str) -> Result<Vec<LegacyReport>> {
+    let pdf_bytes = fs::read(input).with_context(|| format!("cannot read {}", input))?;
+    let original_bytes = fs::read(original).with_context(|| format!("cannot read {}", original))?;
+    let public_key = fs::read(public_key_path).with_context(|| format!("cannot read public key {}", public_key_path))?;
+    let key = PublicKey::from_bytes(&public_key)?;
+    // Versi lama memberikan SHA-256 file asli ke `Signer::sign`, yang meng-hash
+    // lagi pesannya, jadi digest yang ditandatangani adalah SHA-256 dua kali
+    let digest = DigestAlgorithm::Sha256.digest(&DigestAlgorithm::Sha256.digest(&original_bytes));
+
+    let doc = Document::load_mem(&pdf_bytes)?;
+    let mut reports = Vec::new();
+    for field in form::signature_fields(&doc) {
+        let sig = &field.value;
+        // Hanya blob format lama; signature CMS diperiksa oleh `verify`
+        let Some((signature_der, _)) = sig.get(b"Contents").and_then(Object::as_str).ok().and_then(|contents| parse_signature_blob(contents).ok())
+        else {
+            continue;
+        };
+        let text = |key: &[u8]| sig.get(key).and_then(Object::as_str).ok().map(text::decode_text_string);
+        // Versi lama menulis /ByteRange [0 <panjang file asli> ...]
+        let signed_length = sig
+            .get(b"ByteRange")
+            .and_then(Object::as_array)
+            .ok()
+            .and_then(|range| range.get(1))
+            .and_then(|length| length.as_i64().ok());
+
+        let problem = match signed_length {
+            Some(length) if length != original_bytes.len() as i64 => Some(format!(
+                "{} is {} bytes, but the signature covers a {}-byte file; is this the file that was signed?",
+                original,
+                original_bytes.len(),
+                length
+            )),
+            _ => match key.verify(&signer::ecdsa_with_sha256(), &digest, &signature_der) {
+                Ok(true) => None,
+                Ok(false) => Some(Finding::SignatureMismatch.message(AppearanceLanguage::En)),
+                Err(err) => Some(err.to_string()),
+            },
+        };
+        reports.push(LegacyReport {
+            field: field.name,
+            signer: text(b"Name"),
+            signing_time: text(b"M"),
+            signed_length,
+            status: if problem.is_none() { SignatureStatus::Valid } else { SignatureStatus::Invalid },
+            problem,
+        });
+    }
+
+    if reports.is_empty() {
+        bail!("no legacy pdfsign signatures found in {} (use `pdfsign verify` for CMS signatures)", input);
+    }
+    Ok(reports)
+}
+
+/// Laporan `verify_legacy` sebagai teks untuk user (ditampilkan oleh CLI)
+pub fn format_legacy_reports(reports: &[LegacyReport]) -> String {
+    let mut out = String::new();
+    for (index, report) in reports.iter().enumerate() {
+        out.push_str(&format!("Signature #{} (field: {})\n", index + 1, report.field));
+        out.push_str(&format!("  Signer:     {}\n", report.signer.as_deref().unwrap_or("-")));
+        out.push_str(&format!("  Signed at:  {}\n", report.signing_time.as_deref().map_or("-".to_string(), text::human_pdf_date)));
+        out.push_str(&format!("  Signed:     {} bytes (whole original file, legacy format)\n", report.signed_length.map_or("-".to_string(), |length| length.to_string())));
+        match report.status {
+            SignatureStatus::Valid => out.push_str("  Status:     VALID\n"),
+            _ => out.push_str("  Status:     INVALID\n"),
+        }
+        if let Some(problem) = &report.problem {
+            out.push_str(&format!("  Error:      {}\n", problem));
+        }
+    }
+    out
+}
+
+/// Verifikasi satu signature dictionary terhadap bytes file
+/// Algoritma dari deny-list `policy` dicatat di `violations`, juga jika verifikasi gagal
+/// Return: status verifikasi, temuan, dan waktu timestamp RFC 3161 (jika ada)
+fn verify_signature(
+    pdf_bytes: &[u8],
+    sig: &lopdf::Dictionary,
+    byte_range: &[i64],
+    public_key: Option<&[u8]>,
+    policy: &AlgorithmPolicy,
+    violations: &mut Vec<Violation>,
+) -> Result<Check> {
+    // Digest ulang atas bagian file yang ditunjuk ByteRange, dengan algoritma
+    // yang dinyatakan signature
+    let digest_with = |algorithm| digest_byte_range(pdf_bytes, byte_range, algorithm);
+
+    // Ambil signature dan sertifikat (jika ada) dari /Contents
+    let contents = sig
+        .get(b"Contents")
+        .and_then(Object::as_str)
+        .map_err(|_| anyhow!("signature dictionary has no /Contents"))?;
+
+    // Document timestamp (PAdES B-LTA): /Contents berisi TimeStampToken atas ByteRange
+    if sig.get(b"SubFilter").and_then(Object::as_name).ok() == Some(&b"ETSI.RFC3161"[..]) {
+        return verify_document_timestamp(contents, &digest_with, policy, violations);
+    }
+
+    // /Contents berisi CMS SignedData (dipadding nol); blob lama buatan pdfsign
+    // versi sebelumnya masih didukung sebagai fallback
+    let cms = der_element_len(contents).and_then(|len| cms::parse_signed_data(&contents[..len]).ok());
+    let mut timestamp = None;
+    let mut findings = Vec::new();
+    let (signed, signature_der, algorithm, embedded_cert) = match cms {
+        Some(parsed) => {
+            // Algoritma digest, signature, dan rantai sertifikat terhadap deny-list
+            violations.extend(policy.check_digest(&parsed.digest_algorithm.oid, "message digest"));
+            violations.extend(policy.check_signature_algorithm(&parsed.signature_algorithm, "signature"));
+            violations.extend(policy.check_certificates(&parsed.certificates)?);
+            if !parsed.digest_supported() {
+                let detail = format!("unsupported digest algorithm {}", parsed.digest_algorithm.oid);
+                return Ok(Check::unverifiable(Finding::Malformed(detail), None));
+            }
+            let digest_algorithm = parsed.digest().unwrap_or_default();
+            let digest = digest_with(digest_algorithm)?;
+            // messageDigest harus sama dengan digest ByteRange
+            if let Some(message_digest) = &parsed.message_digest {
+                if *message_digest != digest {
+                    return Ok(Check::invalid(Finding::DigestMismatch, None));
+                }
+            }
+            // Timestamp (jika ada) harus dibuat atas nilai signature ini
+            match &parsed.timestamp_token {
+                Some(token) => match timestamp_time(token, &parsed.signature, policy, violations) {
+                    Ok(time) => timestamp = Some(time),
+                    Err(finding) => return Ok(Check::invalid(finding, None)),
+                },
+                None => findings.push(Finding::NoTimestamp),
+            }
+            // Dengan signed attributes yang ditandatangani adalah SET atributnya,
+            // tanpa atribut langsung digest dokumen
+            let signed = match parsed.signed_attributes {
+                Some(attributes) => SignedContent::Message(digest_algorithm, attributes),
+                None => SignedContent::Digest(digest),
+            };
+            (signed, parsed.signature, parsed.signature_algorithm, parsed.signer_certificate)
+        }
+        None => {
+            let (signature_der, embedded_cert) = parse_signature_blob(contents)?;
+            if let Some(cert) = &embedded_cert {
+                violations.extend(policy.check_certificates(std::slice::from_ref(cert))?);
+            }
+            findings.push(Finding::LegacyFormat);
+            (SignedContent::Digest(digest_with(DigestAlgorithm::Sha256)?), signature_der, signer::ecdsa_with_sha256(), embedded_cert)
+        }
+    };
+
+    // Pilih kunci publik: dari file yang diberikan, atau dari sertifikat
+    let cert = embedded_cert.or_else(|| sig.get(b"Cert").and_then(Object::as_str).ok().map(|c| c.to_vec()));
+    let key = match (public_key, cert) {
+        (Some(key), _) => {
+            let key = PublicKey::from_bytes(key)?;
+            if let PublicKey::Rsa(rsa) = &key {
+                violations.extend(policy.check_rsa_bits(rsa.bits(), "public key"));
+            }
+            key
+        }
+        (None, Some(cert)) => public_key_from_certificate(&cert)?,
+        (None, None) => return Ok(Check::unverifiable(Finding::NoCertificate, timestamp)),
+    };
+
+    let valid = match signed {
+        SignedContent::Message(digest, message) => key.verify_message(&algorithm, digest, &message, &signature_der)?,
+        // Ed25519 tanpa signed attributes menandatangani isi ByteRange, yang tidak disimpan di sini
+        SignedContent::Digest(_) if matches!(key, PublicKey::Ed25519(_)) => {
+            return Ok(Check::unverifiable(Finding::Malformed("Ed25519 signature without signed attributes".to_string()), timestamp));
+        }
+        SignedContent::Digest(digest) => key.verify(&algorithm, &digest, &signature_der)?,
+    };
+    let mut check = if valid {
+        Check::valid(timestamp)
+    } else {
+        Check::invalid(Finding::SignatureMismatch, timestamp)
+    };
+    check.findings.extend(findings);
+    Ok(check)
+}
+
+/// Baca waktu dari TimeStampToken dan pastikan token dibuat atas nilai signature
+/// Catatan: signature TSA dan rantai sertifikatnya tidak divalidasi di sini
+/// Return: waktu timestamp, atau temuan jika token tidak cocok / tidak terbaca
+fn timestamp_time(
+    token_der: &[u8],
+    signature: &[u8],
+    policy: &AlgorithmPolicy,
+    violations: &mut Vec<Violation>,
+) -> std::result::Result<Timestamp, Finding> {
+    let info = tsa::tst_info(token_der).map_err(|err| Finding::BadTimestamp(err.to_string()))?;
+    violations.extend(policy.check_digest(&info.message_imprint.hash_algorithm.oid, "timestamp imprint"));
+    let Some(imprint) = DigestAlgorithm::from_oid(&info.message_imprint.hash_algorithm.oid) else {
+        return Err(Finding::BadTimestamp(format!("unsupported imprint algorithm {}", info.message_imprint.hash_algorithm.oid)));
+    };
+    if info.message_imprint.hashed_message.as_bytes() != imprint.digest(signature) {
+        return Err(Finding::TimestampMismatch);
+    }
+    Ok(gen_time(&info))
+}
+
+/// Verifikasi document timestamp: token harus dibuat atas digest ByteRange dan
+/// ditandatangani TSA dengan sertifikat yang tertanam di token
+///
+/// Parameter:
+///   - digest_with: menghitung digest ByteRange dengan algoritma imprint token
+fn verify_document_timestamp(
+    contents: &[u8],
+    digest_with: &dyn Fn(DigestAlgorithm) -> Result<Vec<u8>>,
+    policy: &AlgorithmPolicy,
+    violations: &mut Vec<Violation>,
+) -> Result<Check> {
+    let token = &contents[..der_element_len(contents).ok_or_else(|| anyhow!("malformed document timestamp"))?];
+    let info = tsa::tst_info(token)?;
+    let time = Some(gen_time(&info));
+    violations.extend(policy.check_digest(&info.message_imprint.hash_algorithm.oid, "timestamp imprint"));
+    let Some(imprint) = DigestAlgorithm::from_oid(&info.message_imprint.hash_algorithm.oid) else {
+        let detail = format!("unsupported imprint algorithm {}", info.message_imprint.hash_algorithm.oid);
+        return Ok(Check::unverifiable(Finding::Malformed(detail), time));
+    };
+    if info.message_imprint.hashed_message.as_bytes() != digest_with(imprint)? {
+        return Ok(Check::invalid(Finding::DocumentTimestampMismatch, time));
+    }
+
+    // Signature TSA atas TSTInfo (messageDigest = digest TSTInfo)
+    let parsed = match cms::parse_signed_data(token) {
+        Ok(parsed) => parsed,
+        Err(err) => return Ok(Check::unverifiable(Finding::TsaSignatureUnchecked(err.to_string()), time)),
+    };
+    violations.extend(policy.check_digest(&parsed.digest_algorithm.oid, "TSA message digest"));
+    violations.extend(policy.check_signature_algorithm(&parsed.signature_algorithm, "TSA signature"));
+    violations.extend(policy.check_certificates(&tsa::token_certificates(token)?)?);
+    if !parsed.digest_supported() {
+        let detail = format!("unsupported digest algorithm {}", parsed.digest_algorithm.oid);
+        return Ok(Check::unverifiable(Finding::TsaSignatureUnchecked(detail), time));
+    }
+    let tst_info_der = info.to_der().map_err(|e| anyhow!("invalid TSTInfo: {}", e))?;
+    if parsed.message_digest != parsed.digest().map(|algorithm| algorithm.digest(&tst_info_der)) {
+        return Ok(Check::invalid(Finding::TsaSignatureUncovered, time));
+    }
+    let digest_algorithm = parsed.digest().unwrap_or_default();
+    let Some(cert) = parsed.signer_certificate else {
+        return Ok(Check::unverifiable(Finding::NoTsaCertificate, time));
+    };
+    let key = public_key_from_certificate(&cert)?;
+    let valid = match &parsed.signed_attributes {
+        Some(attributes) => key.verify_message(&parsed.signature_algorithm, digest_algorithm, attributes, &parsed.signature)?,
+        None => key.verify(&parsed.signature_algorithm, &parsed.signed_digest, &parsed.signature)?,
+    };
+    if valid {
+        Ok(Check::valid(time))
+    } else {
+        Ok(Check::invalid(Finding::TsaSignatureInvalid, time))
+    }
+}
+
+/// Sertifikat penandatangan dan sertifikat lain yang tertanam di signature:
+/// dari CMS SignedData, dari TimeStampToken (document timestamp), atau /Cert
+/// pada format lama
+pub(crate) fn signature_chain(sig: &lopdf::Dictionary) -> Option<(Vec<u8>, Vec<Vec<u8>>)> {
+    let contents = sig.get(b"Contents").and_then(Object::as_str).ok()?;
+    let parsed = der_element_len(contents).and_then(|len| cms::parse_signed_data(&contents[..len]).ok());
+    match parsed {
+        Some(parsed) => {
+            let certificates = match &parsed.timestamp_token {
+                // Document timestamp: sertifikat ada di SET certificates token itu sendiri
+                _ if sig.get(b"SubFilter").and_then(Object::as_name).ok() == Some(&b"ETSI.RFC3161"[..]) => {
+                    tsa::token_certificates(&contents[..der_element_len(contents)?]).unwrap_or(parsed.certificates)
+                }
+                _ => parsed.certificates,
+            };
+            Some((parsed.signer_certificate?, certificates))
+        }
+        None => {
+            let (_, cert) = parse_signature_blob(contents).ok()?;
+            let cert = cert.or_else(|| sig.get(b"Cert").and_then(Object::as_str).ok().map(|c| c.to_vec()))?;
+            Some((cert, Vec::new()))
+        }
+    }
+}
+
+/// Sertifikat dan data revocation (OCSP/CRL) di /DSS dokumen (kosong jika tidak ada)
+fn dss_contents(doc: &Document) -> (Vec<Vec<u8>>, RevocationData) {
+    let dss = form::catalog_id(doc)
+        .ok()
+        .and_then(|root| doc.get_dictionary(root).ok())
+        .and_then(|catalog| catalog.get(b"DSS").ok())
+        .and_then(|dss| doc.dereference(dss).ok())
+        .and_then(|(_, dss)| dss.as_dict().ok());
+    let streams = |key: &[u8]| -> Vec<Vec<u8>> {
+        dss.and_then(|dss| dss.get(key).ok())
+            .and_then(|array| doc.dereference(array).ok())
+            .and_then(|(_, array)| array.as_array().ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|item| doc.dereference(item).ok())
+            .filter_map(|(_, stream)| stream.as_stream().ok())
+            .map(|stream| stream.decompressed_content().unwrap_or_else(|_| stream.content.clone()))
+            .collect()
+    };
+    let data = RevocationData { ocsp_responses: streams(b"OCSPs"), crls: streams(b"CRLs") };
+    (streams(b"Certs"), data)
+}
+
+/// Waktu dari TSTInfo
+fn gen_time(info: &x509_tsp::TstInfo) -> Timestamp {
+    Timestamp::from(info.gen_time.to_system_time())
+}
+
+/// Tanggal PDF sebagai waktu UTC (lihat `text::parse_pdf_date`)
+fn parse_pdf_date(value: &str) -> Option<Timestamp> {
+    text::parse_pdf_date(value).map(|time| time.with_timezone(&chrono::Utc))
+}
+
+/// Gabungkan semua segmen ByteRange dan hitung digest-nya
+fn digest_byte_range(pdf_bytes: &[u8], byte_range: &[i64], algorithm: DigestAlgorithm) -> Result<Vec<u8>> {
+    if byte_range.is_empty() || !byte_range.len().is_multiple_of(2) {
+        bail!("malformed /ByteRange");
+    }
+    let mut hasher = algorithm.hasher();
+    for pair in byte_range.chunks(2) {
+        let (start, length) = (pair[0], pair[1]);
+        if start < 0 || length < 0 || (start + length) as usize > pdf_bytes.len() {
+            bail!("/ByteRange points outside the file");
+        }
+        hasher.update(&pdf_bytes[start as usize..(start + length) as usize]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Parse blob signature lama buatan pdfsign (sebelum memakai CMS):
+/// SEQUENCE(placeholder 2 byte) { version, digestAlgorithms SET, OCTET STRING signature, [sertifikat DER] }
+///
+/// Return: (signature DER, sertifikat DER opsional)
+fn parse_signature_blob(contents: &[u8]) -> Result<(Vec<u8>, Option<Vec<u8>>)> {
+    let malformed = || anyhow!("unsupported or malformed signature container");
+    // Tag SEQUENCE diikuti 2 byte placeholder panjang
+    if contents.first() != Some(&0x30) {
+        return Err(malformed());
+    }
+    let mut pos = 3;
+
+    // Version INTEGER 1
+    if contents.get(pos..pos + 3) != Some(&[0x02, 0x01, 0x01]) {
+        return Err(malformed());
+    }
+    pos += 3;
+
+    // DigestAlgorithms SET selalu ditulis sebagai 11 bytes yang sama
+    // (header SET mengklaim panjang 11, padahal isinya hanya 9 bytes)
+    if contents.get(pos..pos + 11) != Some(&[0x31, 0x0b, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a]) {
+        return Err(malformed());
+    }
+    pos += 11;
+
+    // OCTET STRING berisi signature ECDSA dalam DER
+    if contents.get(pos) != Some(&0x04) {
+        return Err(malformed());
+    }
+    let (sig_len, header) = match *contents.get(pos + 1).ok_or_else(malformed)? {
+        0x81 => (*contents.get(pos + 2).ok_or_else(malformed)? as usize, 3),
+        len if len < 0x80 => (len as usize, 2),
+        _ => return Err(malformed()),
+    };
+    pos += header;
+    let signature = contents.get(pos..pos + sig_len).ok_or_else(malformed)?.to_vec();
+    pos += sig_len;
+
+    // Sisa bytes (sebelum padding nol) adalah sertifikat DER jika diawali SEQUENCE
+    let cert = match contents.get(pos) {
+        Some(0x30) => der_element_len(&contents[pos..]).map(|len| contents[pos..pos + len].to_vec()),
+        _ => None,
+    };
+    Ok((signature, cert))
+}
+
+/// Ambil kunci publik dari sertifikat X.509 DER
+fn public_key_from_certificate(cert_der: &[u8]) -> Result<PublicKey> {
+    let cert = x509_cert::Certificate::from_der(cert_der).map_err(|e| anyhow!("invalid embedded certificate: {}", e))?;
+    PublicKey::from_spki(&cert.tbs_certificate.subject_public_key_info)
+}
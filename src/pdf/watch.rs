@@ -0,0 +1,238 @@
+// Import library yang diperlukan
+use anyhow::{bail, Context, Result}; // Untuk error handling yang fleksibel
+use std::collections::{HashMap, HashSet}; // Ukuran file terakhir dan file yang sedang diproses
+use std::fs::{self, File}; // Membaca, memindahkan, dan menghapus file
+use std::io::{Read, Seek, SeekFrom}; // Pemeriksaan header dan trailer PDF
+use std::path::{Path, PathBuf}; // Path file di folder yang dipantau
+use std::sync::atomic::{AtomicUsize, Ordering}; // Jumlah file yang sedang diproses
+use std::sync::Mutex; // File yang sedang diproses, dibagi dengan worker
+use std::time::{Duration, Instant, SystemTime}; // Interval polling dan lama per file
+
+use crate::cancel::CancellationToken; // Ctrl-C menghentikan pemantauan
+use crate::pdf::health::{self, HealthPolicy}; // Self-test dan keep-alive token
+use crate::pdf::hooks::NoHooks; // Watch tidak memakai hook
+use crate::pdf::options::SignatureOptions; // Opsi yang sama untuk semua file
+use crate::pdf::sign::{load_credentials, sign_file}; // Pipeline penandatanganan per file
+
+/// Pengaturan `pdfsign watch`
+#[derive(Clone, Debug)]
+pub struct WatchConfig {
+    pub input_dir: PathBuf,       // Folder yang dipantau (tanpa subdirektori)
+    pub output_dir: PathBuf,      // Folder PDF hasil
+    pub quarantine_dir: PathBuf,  // Folder file yang gagal pemeriksaan atau signing
+    pub max_file_size: u64,       // Ukuran file maksimal (bytes)
+    pub max_in_flight: usize,     // Jumlah file yang diproses bersamaan (minimal 1)
+    pub poll_interval: Duration,  // Jeda antar pemindaian folder
+    pub timeout: Option<Duration>, // Batas waktu per file
+    pub health: HealthPolicy,      // Self-test saat mulai dan ping keep-alive ke token
+}
+
+/// Kejadian selama pemantauan, untuk log pemanggil
+pub enum WatchEvent<'a> {
+    /// File selesai ditandatangani; file input dihapus
+    Signed { input: &'a Path, output: &'a Path, elapsed: Duration },
+    /// File dipindahkan ke karantina beserta alasannya
+    Quarantined { input: &'a Path, quarantined: &'a Path, reason: &'a str },
+}
+
+/// Pantau folder dan tandatangani setiap PDF yang masuk, sampai `cancel` dibatalkan
+///
+/// File baru diproses setelah ukuran dan waktu ubahnya tidak berubah selama
+/// satu interval polling (file yang masih disalin tidak diambil). Sebelum
+/// signing, setiap file diperiksa: ekstensi .pdf, ukuran tidak nol dan tidak
+/// melebihi `max_file_size`, header %PDF- dan %%EOF di akhir file. File yang
+/// gagal pemeriksaan atau signing dipindahkan ke `quarantine_dir` bersama file
+/// "<nama>.reason.txt". Paling banyak `max_in_flight` file diproses
+/// bersamaan; file lain menunggu di folder input, sehingga banjir file tidak
+/// menghabiskan memori atau CPU. File tersembunyi (".nama") dan file dengan
+/// akhiran .part/.tmp/.lock diabaikan.
+///
+/// Parameter:
+///   - config: folder dan batas pemantauan
+///   - key_path: path file kunci privat (diabaikan untuk PKCS#12/PKCS#11/KMS)
+///   - options: opsi signature untuk semua file
+///   - cancel: token pembatalan; file yang sedang diproses diselesaikan dulu
+///   - on_event: dipanggil dari thread worker setiap satu file selesai
+pub fn watch(
+    config: &WatchConfig,
+    key_path: &Path,
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+    on_event: &(dyn Fn(WatchEvent) + Sync),
+) -> Result<()> {
+    if !config.input_dir.is_dir() {
+        bail!("{} is not a directory", config.input_dir.display());
+    }
+    for dir in [&config.output_dir, &config.quarantine_dir] {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create directory {}", dir.display()))?;
+    }
+    if same_dir(&config.input_dir, &config.output_dir) || same_dir(&config.input_dir, &config.quarantine_dir) {
+        bail!("the output and quarantine directories must differ from the watched directory");
+    }
+    // Kunci dimuat sekali; passphrase hanya ditanyakan saat mulai
+    let (signer, certificates) = load_credentials(key_path, options)?;
+    if config.health.self_test {
+        let report = health::self_test(signer.as_ref(), &certificates, options, cancel)?;
+        for warning in &report.warnings {
+            crate::warning!("{}", warning);
+        }
+        crate::info!("Self-test passed: {}", report.summary());
+    }
+    let max_in_flight = config.max_in_flight.max(1);
+    let in_flight = AtomicUsize::new(0);
+    let claimed: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+    let mut seen: HashMap<PathBuf, (u64, SystemTime)> = HashMap::new();
+
+    let stopped = CancellationToken::new();
+
+    std::thread::scope(|scope| -> Result<()> {
+        if let Some(interval) = config.health.keep_alive {
+            let (signer, stopped) = (signer.as_ref(), &stopped);
+            scope.spawn(move || health::keep_alive(signer, interval, &|| stopped.check().is_err() || cancel.check().is_err()));
+        }
+        // Thread keep-alive juga dihentikan jika membaca folder gagal
+        let result = (|| -> Result<()> {
+            while cancel.check().is_ok() {
+                let mut stable = Vec::new();
+                let mut current = HashMap::new();
+                for entry in fs::read_dir(&config.input_dir).with_context(|| format!("cannot read directory {}", config.input_dir.display()))? {
+                    let entry = entry?;
+                    let path = entry.path();
+                    let Ok(metadata) = entry.metadata() else {
+                        continue;
+                    };
+                    if !metadata.is_file() || ignored(&path) || claimed.lock().unwrap_or_else(|p| p.into_inner()).contains(&path) {
+                        continue;
+                    }
+                    let state = (metadata.len(), metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+                    if seen.get(&path) == Some(&state) {
+                        stable.push(path.clone());
+                    }
+                    current.insert(path, state);
+                }
+                seen = current;
+                stable.sort();
+
+                for input in stable {
+                    // Backpressure: file berikutnya menunggu di folder sampai ada slot
+                    if in_flight.load(Ordering::SeqCst) >= max_in_flight || cancel.check().is_err() {
+                        break;
+                    }
+                    seen.remove(&input);
+                    claimed.lock().unwrap_or_else(|p| p.into_inner()).insert(input.clone());
+                    in_flight.fetch_add(1, Ordering::SeqCst);
+                    let (signer, certificates, in_flight, claimed) = (&signer, &certificates, &in_flight, &claimed);
+                    scope.spawn(move || {
+                        process(config, &input, signer.as_ref(), certificates, options, cancel, on_event);
+                        claimed.lock().unwrap_or_else(|p| p.into_inner()).remove(&input);
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+                std::thread::sleep(config.poll_interval);
+            }
+            Ok(())
+        })();
+        stopped.cancel();
+        result
+    })
+}
+
+/// Periksa, tandatangani, lalu hapus satu file; jika gagal, pindahkan ke karantina
+fn process(
+    config: &WatchConfig,
+    input: &Path,
+    signer: &dyn crate::crypto::signer::Signer,
+    certificates: &[Vec<u8>],
+    options: &SignatureOptions,
+    cancel: &CancellationToken,
+    on_event: &(dyn Fn(WatchEvent) + Sync),
+) {
+    let started = Instant::now();
+    let output = config.output_dir.join(input.file_name().unwrap_or_default());
+    let result = preflight(input, config.max_file_size).and_then(|_| {
+        let cancel = config.timeout.map_or_else(|| cancel.clone(), |timeout| cancel.child(timeout));
+        sign_file(input, &output, signer, certificates, options, &mut NoHooks, &cancel)?;
+        fs::remove_file(input).with_context(|| format!("cannot remove {}", input.display()))
+    });
+    match result {
+        Ok(()) => on_event(WatchEvent::Signed { input, output: &output, elapsed: started.elapsed() }),
+        Err(err) => {
+            let reason = format!("{:#}", err);
+            match quarantine(input, &config.quarantine_dir, &reason) {
+                Ok(quarantined) => on_event(WatchEvent::Quarantined { input, quarantined: &quarantined, reason: &reason }),
+                // File tetap di folder input; dicoba lagi pada pemindaian berikutnya
+                Err(err) => crate::warning!("cannot quarantine {}: {:#}", input.display(), err),
+            }
+        }
+    }
+}
+
+/// Pemeriksaan murah sebelum signing: ekstensi, ukuran, header, dan trailer
+///
+/// Parameter:
+///   - path: file di folder input
+///   - max_file_size: ukuran maksimal (bytes)
+pub fn preflight(path: &Path, max_file_size: u64) -> Result<()> {
+    if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) {
+        bail!("not a .pdf file");
+    }
+    let mut file = File::open(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let size = file.metadata()?.len();
+    if size == 0 {
+        bail!("empty file");
+    }
+    if size > max_file_size {
+        bail!("file is {} bytes, larger than --max-file-size ({} bytes)", size, max_file_size);
+    }
+    // Header %PDF- boleh didahului sampah di 1024 bytes pertama (seperti di viewer)
+    let mut head = vec![0; size.min(1024) as usize];
+    file.read_exact(&mut head)?;
+    if !head.windows(5).any(|window| window == b"%PDF-") {
+        bail!("no %PDF- header; not a PDF document");
+    }
+    // %%EOF di akhir file: file yang terpotong (upload belum selesai) ditolak
+    let tail_len = size.min(1024);
+    file.seek(SeekFrom::End(-(tail_len as i64)))?;
+    let mut tail = vec![0; tail_len as usize];
+    file.read_exact(&mut tail)?;
+    if !tail.windows(5).any(|window| window == b"%%EOF") {
+        bail!("no %%EOF marker at the end; the file is truncated");
+    }
+    Ok(())
+}
+
+/// Pindahkan file ke folder karantina dan tulis alasannya di sebelahnya
+///
+/// Return: path file di folder karantina
+fn quarantine(input: &Path, quarantine_dir: &Path, reason: &str) -> Result<PathBuf> {
+    let name = input.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    // Nama yang sudah ada di karantina tidak ditimpa
+    let mut target = quarantine_dir.join(&name);
+    let mut counter = 1;
+    while target.exists() {
+        target = quarantine_dir.join(format!("{}.{}", name, counter));
+        counter += 1;
+    }
+    if fs::rename(input, &target).is_err() {
+        // Folder di file system lain: salin lalu hapus
+        fs::copy(input, &target).with_context(|| format!("cannot copy {} to {}", input.display(), target.display()))?;
+        fs::remove_file(input).with_context(|| format!("cannot remove {}", input.display()))?;
+    }
+    let note = target.with_file_name(format!("{}.reason.txt", target.file_name().unwrap_or_default().to_string_lossy()));
+    fs::write(&note, format!("{}\n", reason)).with_context(|| format!("cannot write {}", note.display()))?;
+    Ok(target)
+}
+
+/// File yang diabaikan: tersembunyi, atau masih ditulis oleh proses lain
+fn ignored(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with('.') || [".part", ".tmp", ".lock"].iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Apakah dua path menunjuk direktori yang sama
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
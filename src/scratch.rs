@@ -0,0 +1,342 @@
+// Import library yang diperlukan
+use anyhow::{Context, Result}; // Untuk error handling yang fleksibel
+use std::fs::File; // File sementara anonim
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write}; // Trait IO untuk scratch buffer
+use std::path::PathBuf; // Path direktori sementara
+
+use crate::cancel::CancellationToken; // Token kosong untuk penulisan output
+use crate::storage::{DocumentSink, FileSink}; // Penulisan output secara atomik
+
+/// Strategi penyimpanan data sementara (hasil serialisasi dokumen sebelum final)
+///
+/// Host dengan /tmp kecil atau aturan data-at-rest yang ketat bisa memilih
+/// di mana dokumen plaintext boleh menyentuh disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum TempStrategy {
+    /// Semua data sementara disimpan di memori (tidak pernah menyentuh disk)
+    #[default]
+    Memory,
+    /// File sementara bernama di direktori temp (otomatis dihapus)
+    Dir,
+    /// File anonim tanpa nama (O_TMPFILE di Linux), tidak terlihat di filesystem
+    Anonymous,
+}
+
+/// Konfigurasi tempat penyimpanan sementara
+#[derive(Clone, Debug, Default)]
+pub struct ScratchSpace {
+    pub strategy: TempStrategy, // Strategi yang dipilih
+    pub dir: Option<PathBuf>,   // Direktori untuk Dir/Anonymous (default: temp dir OS)
+}
+
+/// Buffer sementara yang bisa ditulis, dibaca ulang, dan di-seek
+///
+/// Strategi berbasis disk selalu membuat file di dalam direktori run yang unik
+/// (pdfsign-run-XXXX). Direktori ini ikut di-drop bersama buffer, baik saat
+/// signing selesai, gagal, maupun panic, sehingga dokumen sensitif tidak
+/// menumpuk di server yang sibuk.
+///
+/// Scratch bisa diawali file dokumen asli (`ScratchSpace::create_over`): bytes
+/// asli dibaca langsung dari file itu dan tidak pernah disalin, sehingga hanya
+/// incremental update yang disimpan sesuai strategi.
+pub struct Scratch {
+    buffer: Buffer,                     // Tempat data sebenarnya disimpan
+    base: Option<Base>,                 // Dokumen asli di depan buffer (hanya dibaca)
+    position: u64,                      // Posisi baca/tulis, dihitung dari awal base
+    _run_dir: Option<tempfile::TempDir>, // Direktori run; di-drop setelah buffer
+}
+
+/// Dokumen asli yang menjadi awal isi Scratch
+struct Base {
+    file: File,             // File dokumen asli
+    len: u64,               // Panjang file saat Scratch dibuat
+    mapped: Option<Mapped>, // Isi file yang di-map ke memori, dibuat saat pertama dibutuhkan
+}
+
+/// Isi file dokumen asli yang di-map ke memori (hanya dibaca)
+///
+/// Halaman file dimuat kernel saat dibaca parser dan bisa dilepas lagi, jadi
+/// dokumen sebesar apa pun tidak disalin ke heap.
+#[cfg(unix)]
+struct Mapped {
+    pointer: *mut std::ffi::c_void, // Awal mapping
+    len: usize,                     // Panjang mapping
+}
+
+/// Tanpa mmap, isi file selalu dibaca ke memori (lihat `Scratch::with_contents`)
+#[cfg(not(unix))]
+struct Mapped;
+
+/// Tempat penyimpanan data di dalam Scratch
+enum Buffer {
+    Memory(Cursor<Vec<u8>>),        // Buffer di memori
+    Named(tempfile::NamedTempFile), // File bernama, dihapus saat di-drop
+    Anonymous(File),                // File anonim, hilang saat ditutup
+}
+
+impl ScratchSpace {
+    /// Buat buffer sementara baru sesuai strategi
+    pub fn create(&self) -> Result<Scratch> {
+        if self.strategy == TempStrategy::Memory {
+            return Ok(Scratch {
+                buffer: Buffer::Memory(Cursor::new(Vec::new())),
+                base: None,
+                position: 0,
+                _run_dir: None,
+            });
+        }
+
+        // Direktori run unik, dihapus beserta isinya saat Scratch di-drop
+        let dir = self.dir.clone().unwrap_or_else(std::env::temp_dir);
+        let run_dir = tempfile::Builder::new()
+            .prefix("pdfsign-run-")
+            .tempdir_in(&dir)
+            .with_context(|| format!("cannot create run directory in {}", dir.display()))?;
+        let buffer = match self.strategy {
+            TempStrategy::Memory => unreachable!(),
+            TempStrategy::Dir => {
+                let file = tempfile::Builder::new()
+                    .prefix("pdfsign-")
+                    .tempfile_in(run_dir.path())
+                    .with_context(|| format!("cannot create temporary file in {}", run_dir.path().display()))?;
+                Buffer::Named(file)
+            }
+            TempStrategy::Anonymous => {
+                let file = tempfile::tempfile_in(run_dir.path()).with_context(|| {
+                    format!("cannot create anonymous temporary file in {}", run_dir.path().display())
+                })?;
+                Buffer::Anonymous(file)
+            }
+        };
+        Ok(Scratch {
+            buffer,
+            base: None,
+            position: 0,
+            _run_dir: Some(run_dir),
+        })
+    }
+
+    /// Buat buffer sementara berisi `bytes`; dengan strategi memori vektornya
+    /// dipakai langsung tanpa disalin
+    pub fn create_from(&self, bytes: Vec<u8>) -> Result<Scratch> {
+        if self.strategy == TempStrategy::Memory {
+            return Ok(Scratch {
+                buffer: Buffer::Memory(Cursor::new(bytes)),
+                base: None,
+                position: 0,
+                _run_dir: None,
+            });
+        }
+        let mut scratch = self.create()?;
+        scratch.write_all(&bytes)?;
+        Ok(scratch)
+    }
+
+    /// Buat buffer sementara yang diawali isi `file`
+    ///
+    /// Isi file hanya dibaca (menulis di bagian itu gagal); data yang ditulis
+    /// setelahnya disimpan sesuai strategi. Untuk dokumen besar, hanya
+    /// incremental update yang menempati memori atau direktori temp.
+    pub fn create_over(&self, file: File) -> Result<Scratch> {
+        let len = file.metadata()?.len();
+        let mut scratch = self.create()?;
+        scratch.base = Some(Base { file, len, mapped: None });
+        Ok(scratch)
+    }
+}
+
+impl Scratch {
+    /// Salin seluruh isi buffer ke file output secara atomik (lihat `FileSink`)
+    pub fn persist_to(&mut self, output: &str) -> Result<()> {
+        self.seek(SeekFrom::Start(0))?;
+        FileSink::new(output).store(self, &CancellationToken::new())
+    }
+
+    /// Panggil `f` dengan seluruh isi buffer sebagai satu slice (misalnya untuk
+    /// parser PDF)
+    ///
+    /// Buffer memori tanpa base dipakai langsung. Selama belum ada yang ditulis
+    /// setelah base, file dokumen asli di-map ke memori dan tidak disalin; selain
+    /// itu (atau jika file tidak bisa di-map) isinya dibaca ke memori selama `f`
+    /// berjalan.
+    pub fn with_contents<T>(&mut self, f: impl FnOnce(&[u8]) -> T) -> Result<T> {
+        let written = self.buffer.seek(SeekFrom::End(0))?;
+        match (&self.buffer, &mut self.base) {
+            (Buffer::Memory(cursor), None) => return Ok(f(cursor.get_ref())),
+            (_, Some(base)) if written == 0 => {
+                if let Some(bytes) = base.contents() {
+                    return Ok(f(bytes));
+                }
+            }
+            _ => {}
+        }
+        let mut contents = Vec::new();
+        self.seek(SeekFrom::Start(0))?;
+        self.read_to_end(&mut contents)?;
+        Ok(f(&contents))
+    }
+
+    /// Salin dokumen asli (base) ke buffer sehingga seluruh isinya bisa ditulis,
+    /// misalnya untuk mengisi placeholder signature yang sudah ada di dokumen asli
+    pub fn detach_base(&mut self) -> Result<()> {
+        let Some(mut base) = self.base.take() else { return Ok(()) };
+        // Data yang sudah ditulis setelah base dipindah ke belakang salinannya
+        let mut tail = Vec::new();
+        self.buffer.seek(SeekFrom::Start(0))?;
+        self.buffer.read_to_end(&mut tail)?;
+        self.buffer.seek(SeekFrom::Start(0))?;
+        base.file.seek(SeekFrom::Start(0))?;
+        std::io::copy(&mut (&mut base.file).take(base.len), &mut self.buffer)?;
+        self.buffer.write_all(&tail)?;
+        Ok(())
+    }
+
+    /// Panjang bagian base (0 jika Scratch tidak diawali file)
+    fn base_len(&self) -> u64 {
+        self.base.as_ref().map_or(0, |base| base.len)
+    }
+}
+
+impl Base {
+    /// Isi file dokumen asli tanpa disalin; None jika file tidak bisa di-map
+    /// (file kosong, atau platform tanpa mmap)
+    fn contents(&mut self) -> Option<&[u8]> {
+        if self.mapped.is_none() {
+            self.mapped = Mapped::new(&self.file, self.len);
+        }
+        self.mapped.as_ref().map(Mapped::bytes)
+    }
+}
+
+#[cfg(unix)]
+impl Mapped {
+    /// Map `len` byte pertama `file`; None jika gagal (isi file lalu dibaca biasa)
+    fn new(file: &File, len: u64) -> Option<Mapped> {
+        use rustix::mm::{mmap, MapFlags, ProtFlags};
+        let len = usize::try_from(len).ok().filter(|&len| len > 0)?;
+        // SAFETY: mapping baru, hanya dibaca, dan privat (perubahan tidak
+        // ditulis ke file); file tetap terbuka selama mapping ada. Output ditulis
+        // ke file baru lalu di-rename (`FileSink`), jadi --output yang sama
+        // dengan --input tidak mengubah isi yang sedang di-map.
+        let pointer = unsafe { mmap(std::ptr::null_mut(), len, ProtFlags::READ, MapFlags::PRIVATE, file, 0) }.ok()?;
+        Some(Mapped { pointer, len })
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: `pointer` menunjuk ke mapping `len` byte yang hidup selama `self`
+        unsafe { std::slice::from_raw_parts(self.pointer.cast::<u8>(), self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapped {
+    fn drop(&mut self) {
+        // SAFETY: mapping dibuat oleh `Mapped::new` dan tidak dipakai lagi
+        let _ = unsafe { rustix::mm::munmap(self.pointer, self.len) };
+    }
+}
+
+// Mapping hanya dibaca, jadi boleh dipakai dari thread lain (sign-batch, serve)
+#[cfg(unix)]
+unsafe impl Send for Mapped {}
+#[cfg(unix)]
+unsafe impl Sync for Mapped {}
+
+#[cfg(not(unix))]
+impl Mapped {
+    fn new(_file: &File, _len: u64) -> Option<Mapped> {
+        None
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &[]
+    }
+}
+
+impl Write for Scratch {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let base_len = self.base_len();
+        if self.position < base_len {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "cannot overwrite the original document"));
+        }
+        self.buffer.seek(SeekFrom::Start(self.position - base_len))?;
+        let written = self.buffer.write(buf)?;
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.buffer.flush()
+    }
+}
+
+impl Read for Scratch {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let base_len = self.base_len();
+        let read = match &mut self.base {
+            // Bagian base: baca dari file asli, berhenti di akhir base
+            Some(base) if self.position < base_len => {
+                let len = buf.len().min((base_len - self.position).try_into().unwrap_or(usize::MAX));
+                base.file.seek(SeekFrom::Start(self.position))?;
+                base.file.read(&mut buf[..len])?
+            }
+            _ => {
+                self.buffer.seek(SeekFrom::Start(self.position - base_len))?;
+                self.buffer.read(buf)?
+            }
+        };
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for Scratch {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+            SeekFrom::End(delta) => (self.base_len() + self.buffer.seek(SeekFrom::End(0))?).checked_add_signed(delta),
+        };
+        self.position = target.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position"))?;
+        Ok(self.position)
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Buffer::Memory(cursor) => cursor.write(buf),
+            Buffer::Named(file) => file.write(buf),
+            Buffer::Anonymous(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Buffer::Memory(cursor) => cursor.flush(),
+            Buffer::Named(file) => file.flush(),
+            Buffer::Anonymous(file) => file.flush(),
+        }
+    }
+}
+
+impl Read for Buffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Buffer::Memory(cursor) => cursor.read(buf),
+            Buffer::Named(file) => file.read(buf),
+            Buffer::Anonymous(file) => file.read(buf),
+        }
+    }
+}
+
+impl Seek for Buffer {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Buffer::Memory(cursor) => cursor.seek(pos),
+            Buffer::Named(file) => file.seek(pos),
+            Buffer::Anonymous(file) => file.seek(pos),
+        }
+    }
+}
@@ -0,0 +1,360 @@
+// Layanan HTTP signing (pdfsign serve): satu kunci di server, dokumen lewat HTTP
+use anyhow::{anyhow, bail, Context, Result}; // Untuk error handling yang fleksibel
+use pdfsign::crypto::policy::AlgorithmPolicy; // Kebijakan algoritma /verify
+use pdfsign::crypto::signer::Signer; // Kunci yang dimuat saat start
+use pdfsign::crypto::trust::TrustSource; // Trust anchor /verify
+use pdfsign::pdf::appearance::AppearanceLanguage; // Bahasa pesan temuan /verify
+use pdfsign::pdf::options::{Position, Rect}; // Posisi signature per request
+use pdfsign::pdf::syntax::ParseMode; // Dokumen yang diunggah dibaca lenient
+use pdfsign::{cancel, pdf}; // Pipeline signing dan verifikasi
+use std::io::Read; // Membaca body request
+use std::sync::Arc; // Kunci dan konfigurasi dipakai bersama oleh worker
+use std::time::{Duration, Instant}; // Batas waktu per request dan lama proses
+use tiny_http::{Header, Method, Request, Response, Server}; // Server HTTP/1.1
+
+use crate::cli::SignArgs; // Opsi signature default (sama seperti `sign`)
+use crate::exit::{self, ExitStatus}; // Jenis kegagalan dan status verifikasi
+use crate::output; // JSON hasil verify dan error
+
+/// Jeda maksimal menunggu request sebelum tanda berhenti (Ctrl-C) diperiksa lagi
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Konfigurasi `pdfsign serve`
+pub struct ServeConfig {
+    pub listen: String,            // Alamat dan port yang didengarkan
+    pub workers: usize,            // Request yang diproses bersamaan
+    pub max_body: u64,             // Ukuran body maksimal (bytes)
+    pub token: Option<String>,     // Bearer token wajib; None = tanpa autentikasi
+    pub trust: Vec<TrustSource>,   // Trust anchor untuk /verify
+    pub args: SignArgs,            // Opsi signature default; request hanya mengganti metadata dan posisi
+}
+
+/// Keadaan yang dipakai bersama oleh semua worker
+struct State {
+    config: ServeConfig,
+    signer: Box<dyn Signer>,       // Kunci dari --key / --p12 / token / KMS, dimuat sekali
+    certificates: Vec<Vec<u8>>,    // Rantai sertifikat penandatangan
+}
+
+/// Respons HTTP: status, content type, header tambahan, dan isi
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    /// Respons JSON
+    fn json(status: u16, body: String) -> Reply {
+        Reply { status, content_type: "application/json", headers: Vec::new(), body: body.into_bytes() }
+    }
+
+    /// Respons error JSON seperti --output-format json (`file` boleh kosong)
+    fn error(status: u16, file: Option<&str>, exit: ExitStatus, message: &str) -> Reply {
+        Reply::json(status, output::error(file, exit, message))
+    }
+}
+
+/// Bagian body multipart/form-data
+struct Part {
+    name: String,             // Nama field form
+    filename: Option<String>, // Nama file (untuk field file)
+    data: Vec<u8>,            // Isi field
+}
+
+/// Jalankan layanan sampai `shutdown` dibatalkan (Ctrl-C)
+///
+/// Kunci dimuat sekali di sini (passphrase/PIN ditanyakan saat start jika
+/// perlu). Request yang sedang diproses saat berhenti diselesaikan dulu.
+///
+/// Parameter:
+///   - config: alamat, batas, token, trust, dan opsi signature default
+///   - shutdown: token yang dibatalkan untuk menghentikan layanan
+pub fn run(config: ServeConfig, shutdown: &cancel::CancellationToken) -> Result<()> {
+    // Opsi default divalidasi sekarang, bukan saat request pertama
+    let (key, options, _) = crate::sign_options(config.args.clone())?;
+    let (signer, certificates) = pdf::sign::load_credentials(&key, &options)?;
+    let server = Server::http(&config.listen).map_err(|e| anyhow!("cannot listen on {}: {}", config.listen, e))?;
+    let address = server.server_addr().to_ip().ok_or_else(|| anyhow!("cannot listen on {}", config.listen))?;
+    if config.token.is_none() && !address.ip().is_loopback() {
+        pdfsign::warning!("listening on {} without --token: anyone who can reach this address can sign with this key", address);
+    }
+    pdfsign::info!("Serving on http://{} with {} worker(s) (Ctrl-C to stop)", address, config.workers);
+
+    let state = Arc::new(State { config, signer, certificates });
+    std::thread::scope(|scope| {
+        for _ in 0..state.config.workers {
+            let (server, state) = (&server, &state);
+            scope.spawn(move || {
+                while shutdown.check().is_ok() {
+                    match server.recv_timeout(POLL_INTERVAL) {
+                        Ok(Some(request)) => respond(request, state),
+                        Ok(None) => {}
+                        Err(err) => pdfsign::log::failure(&format!("cannot accept request: {}", err)),
+                    }
+                }
+            });
+        }
+    });
+    pdfsign::info!("Server stopped");
+    Ok(())
+}
+
+/// Proses satu request dan kirim responsnya
+fn respond(mut request: Request, state: &State) {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.url().split('?').next().unwrap_or_default().to_string();
+    let reply = handle(&mut request, &method, &path, state);
+
+    let status = reply.status;
+    let mut response = Response::from_data(reply.body).with_status_code(status);
+    for (name, value) in [("Content-Type", reply.content_type.to_string())].into_iter().chain(reply.headers) {
+        if let Ok(header) = Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+            response.add_header(header);
+        }
+    }
+    let line = format!("{} {} {} ({:.1} s)", method, path, status, started.elapsed().as_secs_f64());
+    if status < 400 {
+        pdfsign::info!("{}", line);
+    } else {
+        pdfsign::log::failure(&line);
+    }
+    if let Err(err) = request.respond(response) {
+        pdfsign::log::failure(&format!("cannot send the response to {} {}: {}", method, path, err));
+    }
+}
+
+/// Pilih endpoint, periksa token, dan baca body
+fn handle(request: &mut Request, method: &Method, path: &str, state: &State) -> Reply {
+    match (method, path) {
+        // Untuk load balancer: tanpa token
+        (Method::Get, "/health") => return Reply::json(200, "{\"status\": \"ok\"}".to_string()),
+        (Method::Post, "/sign" | "/verify") => {}
+        (_, "/health" | "/sign" | "/verify") => return Reply::error(405, None, ExitStatus::Failure, &format!("{} is not allowed on {}", method, path)),
+        _ => return Reply::error(404, None, ExitStatus::Failure, &format!("no endpoint {}", path)),
+    }
+
+    if let Some(token) = &state.config.token {
+        let expected = format!("Bearer {}", token);
+        let authorized = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Authorization"))
+            .is_some_and(|header| constant_time_eq(header.value.as_bytes(), expected.as_bytes()));
+        if !authorized {
+            let mut reply = Reply::error(401, None, ExitStatus::Failure, "missing or wrong bearer token");
+            reply.headers.push(("WWW-Authenticate", "Bearer".to_string()));
+            return reply;
+        }
+    }
+
+    // Body dibatasi --max-file-size, juga jika Content-Length tidak dikirim
+    let too_large = || Reply::error(413, None, ExitStatus::Failure, &format!("request body exceeds {} bytes", state.config.max_body));
+    if request.body_length().is_some_and(|length| length as u64 > state.config.max_body) {
+        return too_large();
+    }
+    let mut body = Vec::new();
+    if let Err(err) = request.as_reader().take(state.config.max_body + 1).read_to_end(&mut body) {
+        return Reply::error(400, None, ExitStatus::Io, &format!("cannot read the request body: {}", err));
+    }
+    if body.len() as u64 > state.config.max_body {
+        return too_large();
+    }
+    let content_type = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Content-Type"))
+        .map(|header| header.value.as_str().to_string())
+        .unwrap_or_default();
+    let parts = match multipart(&content_type, &body) {
+        Ok(parts) => parts,
+        Err(err) => return Reply::error(400, None, ExitStatus::Failure, &format!("{:#}", err)),
+    };
+    let Some(file) = parts.iter().find(|part| part.name == "file") else {
+        return Reply::error(400, None, ExitStatus::Failure, "missing form field `file` (the PDF document)");
+    };
+    let filename = file.filename.clone().unwrap_or_else(|| "document.pdf".to_string());
+
+    match path {
+        "/sign" => sign(&parts, &file.data, &filename, state),
+        _ => verify(&parts, &file.data, &filename, state),
+    }
+}
+
+/// POST /sign: tandatangani PDF dengan kunci server dan kembalikan PDF hasil
+fn sign(parts: &[Part], pdf_bytes: &[u8], filename: &str, state: &State) -> Reply {
+    let (options, timeout) = match request_args(&state.config.args, parts).and_then(crate::sign_options) {
+        Ok((_, options, timeout)) => (options, timeout),
+        Err(err) => return Reply::error(400, Some(filename), ExitStatus::Failure, &format!("{:#}", err)),
+    };
+    let cancel = match timeout {
+        Some(seconds) => cancel::CancellationToken::with_timeout(Duration::from_secs(seconds)),
+        None => cancel::CancellationToken::new(),
+    };
+    let signed = options.scratch.create_from(pdf_bytes.to_vec()).and_then(|scratch| {
+        let mut scratch = pdf::sign::sign_scratch(scratch, state.signer.as_ref(), &state.certificates, &options, &mut pdf::hooks::NoHooks, &cancel)?;
+        scratch.with_contents(<[u8]>::to_vec)
+    });
+    match signed {
+        Ok(signed) => {
+            let stem = filename.strip_suffix(".pdf").unwrap_or(filename);
+            let safe: String = stem.chars().filter(|c| c.is_ascii_alphanumeric() || "._- ".contains(*c)).collect();
+            let disposition = format!("attachment; filename=\"{}_signed.pdf\"", if safe.is_empty() { "document" } else { &safe });
+            Reply { status: 200, content_type: "application/pdf", headers: vec![("Content-Disposition", disposition)], body: signed }
+        }
+        Err(err) => failure(filename, &err),
+    }
+}
+
+/// POST /verify: verifikasi semua signature dan kembalikan laporan JSON
+/// (sama seperti `verify --output-format json`)
+fn verify(parts: &[Part], pdf_bytes: &[u8], filename: &str, state: &State) -> Reply {
+    let lang = match text_field(parts, "lang").map(|value| <AppearanceLanguage as clap::ValueEnum>::from_str(value, true)) {
+        None => AppearanceLanguage::En,
+        Some(Ok(lang)) => lang,
+        Some(Err(_)) => return Reply::error(400, Some(filename), ExitStatus::Failure, "invalid `lang` (expected en or id)"),
+    };
+    let trust = &state.config.trust;
+    match pdf::verify::verify_memory_with_checks(filename, pdf_bytes, None, &AlgorithmPolicy::default(), trust, ParseMode::Lenient, None, &[]) {
+        Ok(verification) => {
+            let status = exit::verification_status(&verification, !trust.is_empty());
+            Reply::json(200, output::verify(filename, &verification, status, lang))
+        }
+        Err(err) => failure(filename, &err),
+    }
+}
+
+/// Respons untuk signing atau verifikasi yang gagal
+fn failure(filename: &str, err: &anyhow::Error) -> Reply {
+    let status = exit::classify(err);
+    let code = match status {
+        ExitStatus::Cancelled => 504,
+        ExitStatus::Io => 500,
+        ExitStatus::DiskFull => 507,
+        _ => 422,
+    };
+    Reply::error(code, Some(filename), status, &format!("{:#}", err))
+}
+
+/// Opsi `sign` untuk satu request: opsi default server, dengan metadata,
+/// posisi, dan tampilan dari field form
+///
+/// Field posisi (page, rect, position, invisible, field_name) menggantikan
+/// seluruh posisi default.
+fn request_args(base: &SignArgs, parts: &[Part]) -> Result<SignArgs> {
+    let mut args = base.clone();
+    let placement_fields = ["page", "rect", "position", "invisible", "field_name"];
+    if parts.iter().any(|part| placement_fields.contains(&part.name.as_str())) {
+        (args.page, args.rect, args.position, args.invisible, args.field_name) = (None, None, None, false, None);
+    }
+    for part in parts.iter().filter(|part| part.name != "file") {
+        let value = std::str::from_utf8(&part.data).with_context(|| format!("form field `{}` is not UTF-8 text", part.name))?.trim();
+        let invalid = |expected: &str| anyhow!("invalid `{}`: {} (expected {})", part.name, value, expected);
+        match part.name.as_str() {
+            "name" => args.name = Some(value.to_string()),
+            "reason" => args.reason = Some(value.to_string()),
+            "location" => args.location = Some(value.to_string()),
+            "contact_info" => args.contact_info = Some(value.to_string()),
+            "page" => args.page = Some(value.parse().map_err(|_| invalid("a page number"))?),
+            "rect" => args.rect = Some(value.parse::<Rect>().map_err(|e| anyhow!("invalid `rect`: {}", e))?),
+            "position" => args.position = Some(<Position as clap::ValueEnum>::from_str(value, true).map_err(|_| invalid("top-left ... bottom-right"))?),
+            "invisible" => {
+                args.invisible = match value {
+                    "true" | "1" | "yes" => true,
+                    "false" | "0" | "no" => false,
+                    _ => return Err(invalid("true or false")),
+                }
+            }
+            "field_name" => args.field_name = Some(value.to_string()),
+            "appearance_lang" => {
+                let languages = value
+                    .split(',')
+                    .map(|lang| <AppearanceLanguage as clap::ValueEnum>::from_str(lang.trim(), true))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|_| invalid("id, en, or both separated by a comma"))?;
+                args.appearance_lang = Some(languages);
+            }
+            "appearance_template" => (args.appearance_template, args.appearance_template_file) = (Some(value.to_string()), None),
+            other => bail!(
+                "unknown form field `{}` (expected file, name, reason, location, contact_info, page, rect, position, invisible, field_name, appearance_lang or appearance_template)",
+                other
+            ),
+        }
+    }
+    if args.rect.is_some() && args.position.is_some() {
+        bail!("`rect` and `position` cannot be combined");
+    }
+    if args.invisible && (args.page.is_some() || args.rect.is_some() || args.position.is_some() || args.field_name.is_some()) {
+        bail!("an invisible signature has no page or position");
+    }
+    Ok(args)
+}
+
+/// Nilai field teks (None jika tidak ada atau bukan UTF-8)
+fn text_field<'a>(parts: &'a [Part], name: &str) -> Option<&'a str> {
+    parts.iter().find(|part| part.name == name).and_then(|part| std::str::from_utf8(&part.data).ok()).map(str::trim)
+}
+
+/// Uraikan body multipart/form-data (RFC 7578)
+///
+/// Parameter:
+///   - content_type: header Content-Type (berisi boundary)
+///   - body: isi request
+fn multipart(content_type: &str, body: &[u8]) -> Result<Vec<Part>> {
+    let mut params = content_type.split(';').map(str::trim);
+    if !params.next().is_some_and(|mime| mime.eq_ignore_ascii_case("multipart/form-data")) {
+        bail!("expected a multipart/form-data request (got `{}`)", content_type);
+    }
+    let boundary = params
+        .find_map(|param| param.strip_prefix("boundary="))
+        .map(|boundary| boundary.trim_matches('"'))
+        .filter(|boundary| !boundary.is_empty())
+        .ok_or_else(|| anyhow!("multipart request without a boundary"))?;
+    let delimiter = format!("\r\n--{}", boundary).into_bytes();
+
+    // Delimiter pertama boleh langsung di awal body (tanpa CRLF di depannya)
+    let mut pos = match body.starts_with(&delimiter[2..]) {
+        true => delimiter.len() - 2,
+        false => find(body, 0, &delimiter).ok_or_else(|| anyhow!("malformed multipart body: boundary not found"))? + delimiter.len(),
+    };
+    let mut parts = Vec::new();
+    // Setelah delimiter: "--" menutup body, selain itu CRLF lalu header bagian berikutnya
+    while !body[pos..].starts_with(b"--") {
+        pos += if body[pos..].starts_with(b"\r\n") { 2 } else { 0 };
+        let header_end = find(body, pos, b"\r\n\r\n").ok_or_else(|| anyhow!("malformed multipart body: part without headers"))?;
+        let headers = String::from_utf8_lossy(&body[pos..header_end]);
+        let data_start = header_end + 4;
+        let data_end = find(body, data_start, &delimiter).ok_or_else(|| anyhow!("malformed multipart body: unterminated part"))?;
+
+        let disposition = headers
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.trim().eq_ignore_ascii_case("Content-Disposition").then(|| value.to_string())
+            })
+            .ok_or_else(|| anyhow!("malformed multipart body: part without Content-Disposition"))?;
+        let param = |key: &str| {
+            disposition
+                .split(';')
+                .map(str::trim)
+                .find_map(|param| param.strip_prefix(key)?.strip_prefix('='))
+                .map(|value| value.trim_matches('"').to_string())
+        };
+        let name = param("name").ok_or_else(|| anyhow!("malformed multipart body: part without a name"))?;
+        parts.push(Part { name, filename: param("filename"), data: body[data_start..data_end].to_vec() });
+        pos = data_end + delimiter.len();
+    }
+    Ok(parts)
+}
+
+/// Posisi pertama `needle` di `bytes` mulai dari `from`
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes.get(from..)?.windows(needle.len()).position(|window| window == needle).map(|at| from + at)
+}
+
+/// Bandingkan token tanpa bocoran waktu tentang posisi byte yang berbeda
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,253 @@
+// Import library yang diperlukan
+use anyhow::{Context, Result}; // Untuk error handling yang fleksibel
+use std::fs::{self, File}; // Operasi file
+use std::io::{self, Read, Write}; // Sumber dan tujuan berupa reader/writer
+use std::path::{Path, PathBuf}; // Lokasi file dokumen
+use std::sync::atomic::{AtomicU64, Ordering}; // Nomor unik file sementara
+
+use crate::cancel::CancellationToken; // Pembatalan dan batas waktu
+#[cfg(feature = "network")]
+use crate::crypto::http; // GET/PUT dokumen lewat HTTP
+
+/// Sumber PDF yang akan ditandatangani
+///
+/// Implementasikan trait ini untuk membaca dokumen langsung dari database,
+/// content store, atau object storage tanpa file sementara.
+pub trait DocumentSource {
+    /// Lokasi sumber untuk pesan error (path, URL, ...)
+    fn describe(&self) -> String;
+
+    /// Baca seluruh isi dokumen
+    fn load(&mut self, cancel: &CancellationToken) -> Result<Vec<u8>>;
+}
+
+/// Tujuan PDF hasil tanda tangan
+///
+/// `store` hanya dipanggil sekali, setelah seluruh pipeline berhasil, sehingga
+/// error tidak pernah meninggalkan dokumen setengah jadi di tujuan.
+pub trait DocumentSink {
+    /// Lokasi tujuan untuk pesan dan hook `on_written`; None jika tujuan tidak
+    /// punya nama (writer, memori), dan `on_written` tidak dipanggil
+    fn location(&self) -> Option<String>;
+
+    /// Simpan seluruh isi `document` (dibaca dari awal sampai habis)
+    fn store(&mut self, document: &mut dyn Read, cancel: &CancellationToken) -> Result<()>;
+}
+
+/// Dokumen di file lokal
+pub struct FileSource {
+    path: PathBuf, // Path file PDF
+}
+
+/// File output, ditulis secara atomik (file sementara + rename)
+pub struct FileSink {
+    path: PathBuf, // Path file PDF hasil
+}
+
+/// Dokumen dari sembarang `Read` (misalnya `&[u8]` untuk dokumen di memori)
+pub struct ReaderSource<R: Read> {
+    reader: R, // Reader dokumen
+}
+
+/// Output ke sembarang `Write` (misalnya `Vec<u8>` untuk dokumen di memori)
+pub struct WriterSink<W: Write> {
+    writer: W, // Writer tujuan
+}
+
+/// Dokumen yang diunduh dengan HTTP GET (termasuk presigned URL S3/GCS/Azure Blob)
+#[cfg(feature = "network")]
+pub struct HttpSource {
+    url: String,                    // URL dokumen
+    headers: Vec<(String, String)>, // Header tambahan (otorisasi, ...)
+}
+
+/// Output yang diunggah dengan HTTP PUT (termasuk presigned URL S3/GCS/Azure Blob)
+#[cfg(feature = "network")]
+pub struct HttpSink {
+    url: String,                    // URL tujuan
+    headers: Vec<(String, String)>, // Header tambahan (otorisasi, ...)
+}
+
+impl FileSource {
+    /// Sumber dari file di `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSource { path: path.into() }
+    }
+}
+
+impl DocumentSource for FileSource {
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&mut self, _cancel: &CancellationToken) -> Result<Vec<u8>> {
+        Ok(fs::read(&self.path)?)
+    }
+}
+
+impl FileSink {
+    /// Tujuan berupa file di `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSink { path: path.into() }
+    }
+}
+
+impl DocumentSink for FileSink {
+    fn location(&self) -> Option<String> {
+        Some(self.path.display().to_string())
+    }
+
+    /// Data ditulis ke file sementara di direktori yang sama dengan output,
+    /// di-sync ke disk, lalu di-rename. Nama file sementara unik per proses dan
+    /// per panggilan, sehingga penulis lain untuk output yang sama tidak saling
+    /// menimpa. Jika gagal (termasuk panic), file sementara dihapus oleh guard.
+    ///
+    /// Setelah sync, ukuran file sementara dibandingkan dengan jumlah bytes yang
+    /// ditulis: filesystem yang diam-diam memotong data (disk penuh di share
+    /// jaringan, kuota) menjadi error `ErrorKind::WriteZero`, bukan output
+    /// "signed" yang terpotong. Lihat `error::is_disk_full`.
+    fn store(&mut self, document: &mut dyn Read, cancel: &CancellationToken) -> Result<()> {
+        static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+        let mut tmp_output = self.path.clone().into_os_string();
+        tmp_output.push(format!(".{}-{}.pdfsign-tmp", std::process::id(), NEXT_TMP.fetch_add(1, Ordering::Relaxed)));
+        let guard = scopeguard::guard(PathBuf::from(tmp_output), |path| {
+            if Path::new(&path).exists() {
+                let _ = fs::remove_file(&path);
+            }
+        });
+        let mut file = File::create_new(&*guard).with_context(|| format!("cannot create {}", guard.display()))?;
+        let written = io::copy(document, &mut file).with_context(|| format!("cannot write {}", self.path.display()))?;
+        file.sync_all().with_context(|| format!("cannot write {}", self.path.display()))?;
+        let on_disk = file.metadata()?.len();
+        if on_disk != written {
+            let err = io::Error::new(io::ErrorKind::WriteZero, format!("short write: {} of {} bytes reached the disk", on_disk, written));
+            return Err(anyhow::Error::new(err).context(format!("cannot write {}", self.path.display())));
+        }
+        cancel.check()?;
+        fs::rename(&*guard, &self.path)?;
+        // Rename berhasil: file sementara sudah menjadi output, jangan dihapus
+        scopeguard::ScopeGuard::into_inner(guard);
+        Ok(())
+    }
+}
+
+impl<R: Read> ReaderSource<R> {
+    /// Sumber dari `reader`
+    pub fn new(reader: R) -> Self {
+        ReaderSource { reader }
+    }
+}
+
+impl<R: Read> DocumentSource for ReaderSource<R> {
+    fn describe(&self) -> String {
+        "input stream".to_string()
+    }
+
+    fn load(&mut self, _cancel: &CancellationToken) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.reader.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl<W: Write> WriterSink<W> {
+    /// Tujuan berupa `writer`
+    pub fn new(writer: W) -> Self {
+        WriterSink { writer }
+    }
+
+    /// Ambil kembali writer (misalnya `Vec<u8>` berisi PDF hasil)
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> DocumentSink for WriterSink<W> {
+    fn location(&self) -> Option<String> {
+        None
+    }
+
+    fn store(&mut self, document: &mut dyn Read, _cancel: &CancellationToken) -> Result<()> {
+        io::copy(document, &mut self.writer)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "network")]
+impl HttpSource {
+    /// Sumber dari `url` (http:// atau https://)
+    pub fn new(url: impl Into<String>) -> Self {
+        HttpSource { url: url.into(), headers: Vec::new() }
+    }
+
+    /// Tambahkan header request, misalnya "Authorization"
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[cfg(feature = "network")]
+impl DocumentSource for HttpSource {
+    fn describe(&self) -> String {
+        self.url.clone()
+    }
+
+    fn load(&mut self, cancel: &CancellationToken) -> Result<Vec<u8>> {
+        let headers: Vec<(&str, &str)> = self.headers.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        http::download(&self.url, &headers, cancel)
+    }
+}
+
+#[cfg(feature = "network")]
+impl HttpSink {
+    /// Tujuan berupa `url` (http:// atau https://)
+    pub fn new(url: impl Into<String>) -> Self {
+        HttpSink { url: url.into(), headers: Vec::new() }
+    }
+
+    /// Tambahkan header request, misalnya "Authorization"
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[cfg(feature = "network")]
+impl DocumentSink for HttpSink {
+    fn location(&self) -> Option<String> {
+        Some(self.url.clone())
+    }
+
+    fn store(&mut self, document: &mut dyn Read, cancel: &CancellationToken) -> Result<()> {
+        let mut body = Vec::new();
+        document.read_to_end(&mut body)?;
+        let mut headers: Vec<(&str, &str)> = vec![("Content-Type", "application/pdf")];
+        headers.extend(self.headers.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+        http::call_api("PUT", &self.url, &headers, Some(&body), cancel)?;
+        Ok(())
+    }
+}
+
+/// Tulis `contents` ke file `path` secara atomik lewat `FileSink`: jika
+/// penulisan gagal di tengah jalan (disk penuh), file lama tetap utuh dan
+/// tidak ada file setengah jadi yang tertinggal
+pub fn write_file(path: impl AsRef<Path>, contents: &[u8]) -> Result<()> {
+    FileSink::new(path.as_ref()).store(&mut &contents[..], &CancellationToken::new())
+}
+
+/// Kumpulkan path semua file .pdf di bawah `dir`
+pub(crate) fn collect_pdfs(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_pdfs(&path, paths).with_context(|| format!("cannot read directory {}", path.display()))?;
+        } else if file_type.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
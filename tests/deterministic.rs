@@ -0,0 +1,143 @@
+// Snapshot test mode deterministik (--deterministic-seed)
+#![cfg(feature = "sign")]
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use lopdf::{Document, Object};
+use pdfsign::crypto::cert::{self, CertificateParams, KeyUsageFlag};
+use pdfsign::crypto::provider;
+use pdfsign::pdf::naming;
+use pdfsign::pdf::options::SignatureOptions;
+use pdfsign::pdf::sign::sign_pdf;
+use pdfsign::pdf::testdoc::{generate_test_document, TestDocOptions};
+
+/// Seed berlaku untuk seluruh proses; test di file ini tidak boleh berjalan bersamaan
+static SEED_LOCK: Mutex<()> = Mutex::new(());
+
+/// Nama field dan /M yang diharapkan untuk seed "snapshot"
+const SNAPSHOT_FIELD: &str = "Signature_b4887c59";
+const SNAPSHOT_TIME: &str = "D:20000101000000+00'00'";
+
+/// Aktifkan seed sampai guard dilepas
+struct Seeded {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Seeded {
+    fn new(seed: &str) -> Seeded {
+        let guard = SEED_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        naming::set_deterministic(seed.as_bytes());
+        Seeded { _guard: guard }
+    }
+}
+
+impl Drop for Seeded {
+    fn drop(&mut self) {
+        naming::reset();
+    }
+}
+
+/// Direktori kerja dengan kunci P-256 raw dan sertifikat uji yang dibuat
+/// dalam mode deterministik (berlaku mulai `DETERMINISTIC_TIME`)
+fn workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pdfsign-deterministic-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("private.key"), [0x24; 32]).unwrap();
+    let params = CertificateParams {
+        common_name: "pdfsign snapshot".to_string(),
+        organization: None,
+        country: None,
+        validity_days: 30,
+        key_usage: vec![KeyUsageFlag::DigitalSignature],
+    };
+    cert::generate_certificate(&path(&dir, "private.key"), None, &path(&dir, "certificate.der"), &params).unwrap();
+    dir
+}
+
+fn path(dir: &Path, name: &str) -> String {
+    dir.join(name).to_string_lossy().into_owned()
+}
+
+/// Buat dokumen uji `input` lalu tandatangani ke `output` dengan seed yang aktif
+fn generate_and_sign(dir: &Path, input: &str, output: &str) -> Vec<u8> {
+    generate_test_document(&path(dir, input), &TestDocOptions { form_fields: 2, ..Default::default() }).unwrap();
+    let options = SignatureOptions::builder().name("Snapshot").allow_test_credentials(true).build().unwrap();
+    sign_pdf(path(dir, input), path(dir, output), dir.join("private.key"), options).unwrap();
+    std::fs::read(dir.join(output)).unwrap()
+}
+
+/// Signature field yang ditambahkan `sign`: nama dan /M signature dictionary-nya
+fn new_signature(bytes: &[u8]) -> (String, String) {
+    let doc = Document::load_mem(bytes).unwrap();
+    let fields = pdfsign::pdf::form::terminal_fields(&doc);
+    let (name, id, _) = fields.iter().find(|(_, _, field_type)| field_type.as_deref() == Some(&b"Sig"[..])).unwrap();
+    let value = doc.get_dictionary(*id).unwrap().get(b"V").and_then(Object::as_reference).unwrap();
+    let time = doc.get_dictionary(value).unwrap().get(b"M").and_then(Object::as_str).unwrap();
+    (name.clone(), String::from_utf8_lossy(time).into_owned())
+}
+
+#[test]
+fn same_seed_gives_identical_bytes() {
+    let _seed = Seeded::new("snapshot");
+    let dir = workspace("identical");
+    let first = generate_and_sign(&dir, "a.pdf", "a-signed.pdf");
+    let second = generate_and_sign(&dir, "b.pdf", "b-signed.pdf");
+    assert_eq!(std::fs::read(dir.join("a.pdf")).unwrap(), std::fs::read(dir.join("b.pdf")).unwrap());
+    assert!(first == second, "signed output differs between runs with the same seed");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn field_name_and_signing_time_snapshot() {
+    let _seed = Seeded::new("snapshot");
+    let dir = workspace("snapshot");
+    let signed = generate_and_sign(&dir, "input.pdf", "signed.pdf");
+    assert_eq!(new_signature(&signed), (SNAPSHOT_FIELD.to_string(), SNAPSHOT_TIME.to_string()));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn other_seed_gives_other_field_name() {
+    let _seed = Seeded::new("another seed");
+    let dir = workspace("other");
+    let signed = generate_and_sign(&dir, "input.pdf", "signed.pdf");
+    let (name, time) = new_signature(&signed);
+    assert_ne!(name, SNAPSHOT_FIELD);
+    assert!(name.starts_with("Signature_"), "{}", name);
+    assert_eq!(time, SNAPSHOT_TIME);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn new_objects_follow_the_highest_number() {
+    let _seed = Seeded::new("snapshot");
+    let dir = workspace("numbering");
+    let signed = Document::load_mem(&generate_and_sign(&dir, "input.pdf", "signed.pdf")).unwrap();
+    let original = Document::load(dir.join("input.pdf")).unwrap();
+    let highest = original.objects.keys().map(|&(id, _)| id).max().unwrap();
+    for (id, _) in original.objects.iter() {
+        assert!(signed.objects.contains_key(id), "object {:?} was renumbered", id);
+    }
+    let added: Vec<u32> = signed.objects.keys().map(|&(id, _)| id).filter(|&id| id > highest).collect();
+    assert!(!added.is_empty());
+    assert_eq!(added, (highest + 1..=highest + added.len() as u32).collect::<Vec<u32>>());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn random_source_is_not_seeded() {
+    let _seed = Seeded::new("snapshot");
+    // Kunci, salt kunci terenkripsi, serial sertifikat, dan IV enkripsi PDF tetap acak
+    let (mut first, mut second) = ([0u8; 32], [0u8; 32]);
+    provider::fill_random(&mut first);
+    provider::fill_random(&mut second);
+    assert_ne!(first, second);
+    let dir = workspace("serial");
+    let serial = |dir: &Path| cert::CertificateInfo::from_der(&std::fs::read(dir.join("certificate.der")).unwrap()).unwrap().serial;
+    let again = workspace("serial-again");
+    assert_ne!(serial(&dir), serial(&again));
+    std::fs::remove_dir_all(&dir).unwrap();
+    std::fs::remove_dir_all(&again).unwrap();
+}
@@ -0,0 +1,109 @@
+// Pemakaian memori saat menandatangani dokumen besar dari file
+#![cfg(feature = "sign")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use lopdf::{dictionary, Document, Object, Stream};
+use pdfsign::crypto::cert::{self, CertificateParams, KeyUsageFlag};
+use pdfsign::pdf::options::SignatureOptions;
+use pdfsign::pdf::sign::sign_pdf;
+
+/// Allocator yang mencatat jumlah memori heap yang sedang dipakai dan puncaknya
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Jumlah dan ukuran gambar di dokumen uji (total 64 MB)
+const IMAGES: usize = 2048;
+const IMAGE_SIZE: usize = 32 * 1024;
+
+/// Direktori kerja dengan kunci P-256 raw dan sertifikat self-signed
+fn workspace() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pdfsign-memory-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("private.key"), [0x42; 32]).unwrap();
+    let params = CertificateParams {
+        common_name: "pdfsign test suite".to_string(),
+        organization: None,
+        country: None,
+        validity_days: 30,
+        key_usage: vec![KeyUsageFlag::DigitalSignature],
+    };
+    cert::generate_certificate(path(&dir, "private.key").as_str(), None, path(&dir, "certificate.der").as_str(), &params).unwrap();
+    dir
+}
+
+fn path(dir: &Path, name: &str) -> String {
+    dir.join(name).to_string_lossy().into_owned()
+}
+
+/// Satu halaman dengan banyak gambar (isi gambar tidak dimuat saat signing)
+fn large_document(output: &str) {
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+    let mut images = lopdf::Dictionary::new();
+    for index in 0..IMAGES {
+        let content = (0..IMAGE_SIZE).map(|i| (i * 31 + index) as u8).collect();
+        let image = Stream::new(
+            dictionary! {
+                "Type" => "XObject", "Subtype" => "Image", "Width" => 128, "Height" => 256,
+                "ColorSpace" => "DeviceGray", "BitsPerComponent" => 8,
+            },
+            content,
+        );
+        images.set(format!("Im{}", index), doc.add_object(image));
+    }
+    let page_id = doc.add_object(dictionary! {
+        "Type" => "Page",
+        "Parent" => pages_id,
+        "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        "Resources" => dictionary! { "XObject" => images },
+    });
+    doc.objects.insert(pages_id, Object::Dictionary(dictionary! { "Type" => "Pages", "Kids" => vec![page_id.into()], "Count" => 1 }));
+    let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+    doc.trailer.set("Root", catalog_id);
+    doc.save(output).unwrap();
+}
+
+/// Dokumen asli dibaca langsung dari file-nya: memori yang dipakai signing
+/// jauh lebih kecil dari ukuran dokumen
+#[test]
+fn signing_does_not_buffer_the_input() {
+    let dir = workspace();
+    let (input, output) = (path(&dir, "input.pdf"), path(&dir, "signed.pdf"));
+    large_document(&input);
+    let size = std::fs::metadata(&input).unwrap().len() as usize;
+    assert!(size > IMAGES * IMAGE_SIZE);
+
+    let options = SignatureOptions::builder().name("Test Signer").allow_test_credentials(true).build().unwrap();
+    let before = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(before, Ordering::SeqCst);
+    sign_pdf(&input, &output, dir.join("private.key"), options).unwrap();
+    let used = PEAK.load(Ordering::SeqCst) - before;
+    assert!(used < size / 2, "signing a {} byte document used {} bytes of heap", size, used);
+    assert!(std::fs::metadata(&output).unwrap().len() > size as u64);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,97 @@
+// Dokumen uji dari gen-testdoc: setiap varian ditandatangani lalu diverifikasi
+#![cfg(all(feature = "sign", feature = "verify"))]
+
+use std::path::{Path, PathBuf};
+
+use pdfsign::crypto::cert::{self, CertificateParams, KeyUsageFlag};
+use pdfsign::crypto::policy::AlgorithmPolicy;
+use pdfsign::crypto::trust::TrustSource;
+use pdfsign::pdf::options::SignatureOptions;
+use pdfsign::pdf::sign::sign_pdf;
+use pdfsign::pdf::syntax::ParseMode;
+use pdfsign::pdf::testdoc::{generate_test_document, TestDocOptions, TestEncryption};
+use pdfsign::pdf::verify::{self, Severity, SignatureStatus};
+
+/// Password pemilik dokumen uji terenkripsi
+const OWNER_PASSWORD: &str = "owner-secret";
+
+/// Direktori kerja per test, dengan kunci P-256 raw dan sertifikat self-signed
+/// (`certificate.der` di sebelah kunci, seperti hasil generate-cert)
+fn workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pdfsign-testdoc-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("private.key"), [0x42; 32]).unwrap();
+    let params = CertificateParams {
+        common_name: "pdfsign test suite".to_string(),
+        organization: None,
+        country: None,
+        validity_days: 30,
+        key_usage: vec![KeyUsageFlag::DigitalSignature, KeyUsageFlag::NonRepudiation],
+    };
+    cert::generate_certificate(path(&dir, "private.key").as_str(), None, path(&dir, "certificate.der").as_str(), &params).unwrap();
+    dir
+}
+
+fn path(dir: &Path, name: &str) -> String {
+    dir.join(name).to_string_lossy().into_owned()
+}
+
+/// Buat dokumen uji, tandatangani, dan pastikan signature-nya valid dan
+/// berujung di sertifikat penandatangan sebagai trust anchor
+fn sign_and_verify(name: &str, testdoc: TestDocOptions) {
+    let dir = workspace(name);
+    let (input, output) = (path(&dir, "input.pdf"), path(&dir, "signed.pdf"));
+    generate_test_document(&input, &testdoc).unwrap();
+
+    let mut builder = SignatureOptions::builder().name("Test Signer").reason(name).allow_test_credentials(true);
+    if testdoc.encryption.is_some() {
+        builder = builder.pdf_password(OWNER_PASSWORD);
+    }
+    if testdoc.signature_field {
+        builder = builder.field_name("Signature1");
+    }
+    sign_pdf(&input, &output, dir.join("private.key"), builder.build().unwrap()).unwrap();
+
+    let trust = vec![TrustSource::load(&format!("file:{}", path(&dir, "certificate.der")), &dir).unwrap()];
+    let verification = verify::verify_pdf(&output, None, &AlgorithmPolicy::default(), &trust, ParseMode::Strict, None).unwrap();
+    assert_eq!(verification.signatures.len(), 1, "{}: one signature", name);
+    for report in &verification.signatures {
+        assert_eq!(report.status, SignatureStatus::Valid, "{}: {:?}", name, report.findings);
+        assert!(report.covers_whole_document, "{}: signature covers the whole file", name);
+        assert!(!report.trust.is_empty(), "{}: chain anchored in the test certificate", name);
+        assert!(report.findings.iter().all(|finding| finding.severity() == Severity::Info), "{}: {:?}", name, report.findings);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn plain() {
+    sign_and_verify("plain", TestDocOptions::default());
+}
+
+#[test]
+fn xref_stream() {
+    sign_and_verify("xref-stream", TestDocOptions { xref_stream: true, ..Default::default() });
+}
+
+#[test]
+fn object_streams_with_updates() {
+    sign_and_verify("object-streams", TestDocOptions { object_streams: true, updates: 2, ..Default::default() });
+}
+
+#[test]
+fn existing_form() {
+    sign_and_verify("form", TestDocOptions { form_fields: 3, signature_field: true, ..Default::default() });
+}
+
+#[test]
+fn encrypted() {
+    let encryption = TestEncryption { user_password: String::new(), owner_password: OWNER_PASSWORD.to_string() };
+    sign_and_verify("encrypted", TestDocOptions { encryption: Some(encryption), updates: 1, ..Default::default() });
+}
+
+#[test]
+fn many_pages() {
+    sign_and_verify("many-pages", TestDocOptions { pages: 200, ..Default::default() });
+}